@@ -304,9 +304,11 @@ pub struct RunCmd {
 	)]
 	pub relay_chain_rpc_urls: Vec<Url>,
 
-	/// EXPERIMENTAL: Embed a light client for the relay chain. Only supported for full-nodes.
+	/// EXPERIMENTAL: Embed a light client for the relay chain.
 	/// Will use the specified relay chain chainspec.
-	#[arg(long, conflicts_with_all = ["relay_chain_rpc_urls", "collator"])]
+	///
+	/// When collating, relay chain storage proofs are requested from the relay chain validators.
+	#[arg(long, conflicts_with_all = ["relay_chain_rpc_urls"])]
 	pub relay_chain_light_client: bool,
 }
 
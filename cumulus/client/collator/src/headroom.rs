@@ -0,0 +1,346 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus. If not, see <https://www.gnu.org/licenses/>.
+
+//! Forecasting whether a collation fits into the limits imposed by the relay chain.
+//!
+//! While a block is built, the collator forecasts the PoV, the storage proof, the head data and
+//! the execution time of the block, and checks them against the limits of the relay chain, so that
+//! blocks which would be rejected can be rebuilt smaller. Before a collation is submitted, the
+//! compressed PoV is measured and checked once more. Collations that would still be rejected by
+//! the relay chain are skipped instead of wasting the resources of the backing validators.
+
+use cumulus_primitives_core::CollationResources;
+use polkadot_node_primitives::MAX_POV_SIZE;
+use polkadot_primitives::{
+	executor_params::DEFAULT_BACKING_EXECUTION_TIMEOUT, ExecutorParams, PvfExecKind,
+};
+use prometheus_endpoint::{
+	register, CounterVec, HistogramOpts, HistogramVec, Opts, PrometheusError, Registry, U64,
+};
+use std::time::Duration;
+
+/// A resource of a collation that is limited by the relay chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resource {
+	/// The size of the compressed PoV.
+	PovSize,
+	/// The size of the storage proof, measured while building the block.
+	ProofSize,
+	/// The `proof_size` weight the runtime accounted for the block.
+	ProofSizeWeight,
+	/// The size of the head data.
+	HeadDataSize,
+	/// The time it takes to execute the block.
+	ExecutionTime,
+}
+
+impl Resource {
+	/// The name of the resource, as used in logs and metrics.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Resource::PovSize => "pov_size",
+			Resource::ProofSize => "proof_size",
+			Resource::ProofSizeWeight => "proof_size_weight",
+			Resource::HeadDataSize => "head_data_size",
+			Resource::ExecutionTime => "execution_time",
+		}
+	}
+}
+
+/// The limits a collation is checked against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CollationLimits {
+	/// The maximum size of the compressed PoV in bytes.
+	pub max_pov_size: u32,
+	/// The maximum size of the head data in bytes, if known.
+	pub max_head_data_size: Option<u32>,
+	/// The maximum time the backing validators spend on executing the block.
+	///
+	/// This is the backing execution timeout of the executor parameters of the session.
+	pub max_execution_time: Duration,
+}
+
+impl Default for CollationLimits {
+	fn default() -> Self {
+		Self {
+			max_pov_size: MAX_POV_SIZE,
+			max_head_data_size: None,
+			max_execution_time: DEFAULT_BACKING_EXECUTION_TIMEOUT,
+		}
+	}
+}
+
+impl CollationLimits {
+	/// Tighten the limits with the ones the runtime reported for the block.
+	pub fn with_resources(mut self, resources: &CollationResources) -> Self {
+		self.max_pov_size = self.max_pov_size.min(resources.max_pov_size);
+		self.max_head_data_size = Some(resources.max_head_data_size);
+		self
+	}
+
+	/// Use the maximum PoV size of the persisted validation data of the candidate.
+	pub fn with_max_pov_size(mut self, max_pov_size: u32) -> Self {
+		self.max_pov_size = self.max_pov_size.min(max_pov_size);
+		self
+	}
+
+	/// Use the backing execution timeout of the executor parameters of the session.
+	pub fn with_executor_params(mut self, executor_params: &ExecutorParams) -> Self {
+		self.max_execution_time = executor_params
+			.pvf_exec_timeout(PvfExecKind::Backing)
+			.unwrap_or(DEFAULT_BACKING_EXECUTION_TIMEOUT);
+		self
+	}
+}
+
+/// The resources used by a collation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CollationUsage {
+	/// The size of the compressed PoV in bytes.
+	pub pov_size: usize,
+	/// The size of the storage proof in bytes.
+	pub proof_size: usize,
+	/// The `proof_size` weight the runtime accounted for the block, if known.
+	pub consumed_proof_size: Option<u64>,
+	/// The size of the head data in bytes.
+	pub head_data_size: usize,
+	/// The estimated time it takes to execute the block, if known.
+	pub execution_time: Option<Duration>,
+}
+
+impl CollationUsage {
+	/// Estimate the execution time of a block from the `ref_time` weight it consumed.
+	///
+	/// `ref_time` is measured in picoseconds on the reference hardware of the validators.
+	pub fn estimate_execution_time(ref_time: u64) -> Duration {
+		Duration::from_nanos(ref_time / 1_000)
+	}
+
+	/// Calculate the headroom of this collation with respect to the given limits.
+	pub fn headroom(&self, limits: &CollationLimits) -> Headroom {
+		let max_pov_size = limits.max_pov_size as f64;
+		let mut entries = vec![
+			(Resource::PovSize, share_left(self.pov_size as f64, max_pov_size)),
+			(Resource::ProofSize, share_left(self.proof_size as f64, max_pov_size)),
+		];
+
+		if let Some(consumed_proof_size) = self.consumed_proof_size {
+			entries.push((
+				Resource::ProofSizeWeight,
+				share_left(consumed_proof_size as f64, max_pov_size),
+			));
+		}
+
+		if let Some(max_head_data_size) = limits.max_head_data_size {
+			entries.push((
+				Resource::HeadDataSize,
+				share_left(self.head_data_size as f64, max_head_data_size as f64),
+			));
+		}
+
+		if let Some(execution_time) = self.execution_time {
+			entries.push((
+				Resource::ExecutionTime,
+				share_left(execution_time.as_secs_f64(), limits.max_execution_time.as_secs_f64()),
+			));
+		}
+
+		Headroom { entries }
+	}
+}
+
+/// The share of each limit that is left unused by a collation.
+///
+/// A share of `0.25` means that a quarter of the limit is unused, a negative share means that the
+/// limit is exceeded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Headroom {
+	entries: Vec<(Resource, f64)>,
+}
+
+impl Headroom {
+	/// The share left for each checked resource.
+	pub fn entries(&self) -> &[(Resource, f64)] {
+		&self.entries
+	}
+
+	/// The share left for the given resource, if it was checked.
+	pub fn get(&self, resource: Resource) -> Option<f64> {
+		self.entries.iter().find(|(r, _)| *r == resource).map(|(_, share)| *share)
+	}
+
+	/// The resources whose limits are exceeded.
+	pub fn exceeded(&self) -> Vec<Resource> {
+		self.entries.iter().filter(|(_, share)| *share < 0.0).map(|(r, _)| *r).collect()
+	}
+
+	/// Returns `true` if the collation fits into all limits.
+	pub fn fits(&self) -> bool {
+		self.entries.iter().all(|(_, share)| *share >= 0.0)
+	}
+}
+
+fn share_left(used: f64, limit: f64) -> f64 {
+	if limit == 0.0 {
+		return if used == 0.0 { 0.0 } else { -1.0 }
+	}
+
+	1.0 - used / limit
+}
+
+/// Metrics about the headroom of the built collations.
+#[derive(Clone)]
+pub struct Metrics {
+	headroom: HistogramVec,
+	skipped: CounterVec<U64>,
+}
+
+impl Metrics {
+	/// Register the metrics at the given registry.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			headroom: register(
+				HistogramVec::new(
+					HistogramOpts::new(
+						"cumulus_collation_headroom",
+						"Share of the relay chain limits left unused by built collations",
+					)
+					.buckets(vec![-0.5, -0.1, 0.0, 0.05, 0.1, 0.25, 0.5, 0.75, 1.0]),
+					&["resource"],
+				)?,
+				registry,
+			)?,
+			skipped: register(
+				CounterVec::new(
+					Opts::new(
+						"cumulus_collations_skipped",
+						"Number of collations skipped for exceeding a relay chain limit",
+					),
+					&["resource"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	/// Record the headroom of a built collation.
+	pub fn observe(&self, headroom: &Headroom) {
+		for (resource, share) in headroom.entries() {
+			self.headroom.with_label_values(&[resource.as_str()]).observe(*share);
+		}
+
+		for resource in headroom.exceeded() {
+			self.skipped.with_label_values(&[resource.as_str()]).inc();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn usage(pov_size: usize, execution_time: Option<Duration>) -> CollationUsage {
+		CollationUsage {
+			pov_size,
+			proof_size: pov_size / 2,
+			consumed_proof_size: None,
+			head_data_size: 100,
+			execution_time,
+		}
+	}
+
+	#[test]
+	fn limits_are_tightened_by_runtime_resources() {
+		let limits = CollationLimits::default().with_resources(&CollationResources {
+			consumed_ref_time: 0,
+			consumed_proof_size: 0,
+			max_pov_size: 1024,
+			max_head_data_size: 64,
+			max_upward_message_num: 16,
+			max_horizontal_message_num: 16,
+		});
+
+		assert_eq!(limits.max_pov_size, 1024);
+		assert_eq!(limits.max_head_data_size, Some(64));
+		assert_eq!(limits.max_execution_time, DEFAULT_BACKING_EXECUTION_TIMEOUT);
+	}
+
+	#[test]
+	fn headroom_reports_exceeded_resources() {
+		let limits = CollationLimits {
+			max_pov_size: 1000,
+			max_head_data_size: Some(50),
+			max_execution_time: Duration::from_secs(2),
+		};
+
+		let headroom = usage(750, Some(Duration::from_secs(1))).headroom(&limits);
+		assert_eq!(headroom.get(Resource::PovSize), Some(0.25));
+		assert_eq!(headroom.get(Resource::ExecutionTime), Some(0.5));
+		assert_eq!(headroom.exceeded(), vec![Resource::HeadDataSize]);
+		assert!(!headroom.fits());
+
+		let limits = CollationLimits { max_head_data_size: None, ..limits };
+		let headroom = usage(1000, None).headroom(&limits);
+		assert_eq!(headroom.get(Resource::PovSize), Some(0.0));
+		assert_eq!(headroom.get(Resource::ExecutionTime), None);
+		assert!(headroom.fits());
+	}
+
+	#[test]
+	fn limits_use_backing_timeout_of_executor_params() {
+		use polkadot_primitives::ExecutorParam;
+
+		let params =
+			ExecutorParams::from(&[ExecutorParam::PvfExecTimeout(PvfExecKind::Backing, 1_500)][..]);
+		let limits =
+			CollationLimits::default().with_executor_params(&params).with_max_pov_size(2048);
+
+		assert_eq!(limits.max_execution_time, Duration::from_millis(1_500));
+		assert_eq!(limits.max_pov_size, 2048);
+		assert_eq!(
+			CollationLimits::default()
+				.with_executor_params(&ExecutorParams::default())
+				.max_execution_time,
+			DEFAULT_BACKING_EXECUTION_TIMEOUT,
+		);
+	}
+
+	#[test]
+	fn proof_size_is_checked() {
+		let limits = CollationLimits {
+			max_pov_size: 1000,
+			max_head_data_size: None,
+			max_execution_time: Duration::from_secs(2),
+		};
+
+		let mut usage = usage(500, None);
+		usage.proof_size = 1200;
+		usage.consumed_proof_size = Some(800);
+
+		let headroom = usage.headroom(&limits);
+		assert_eq!(headroom.get(Resource::ProofSizeWeight), Some(0.2));
+		assert_eq!(headroom.exceeded(), vec![Resource::ProofSize]);
+	}
+
+	#[test]
+	fn execution_time_is_estimated_from_ref_time() {
+		assert_eq!(
+			CollationUsage::estimate_execution_time(1_500_000_000_000),
+			Duration::from_millis(1_500),
+		);
+	}
+}
@@ -38,6 +38,7 @@ use std::sync::Arc;
 
 use crate::service::CollatorService;
 
+pub mod headroom;
 pub mod service;
 
 /// The logging target.
@@ -443,8 +444,11 @@ mod tests {
 			_ => panic!("unexpected message or no collator fn"),
 		};
 
-		let validation_data =
-			PersistedValidationData { parent_head: header.encode().into(), ..Default::default() };
+		let validation_data = PersistedValidationData {
+			parent_head: header.encode().into(),
+			max_pov_size: 5 * 1024 * 1024,
+			..Default::default()
+		};
 		let relay_parent = Default::default();
 
 		let collation = block_on(collator_fn(relay_parent, &validation_data))
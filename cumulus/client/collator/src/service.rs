@@ -19,13 +19,16 @@
 //! operations used in parachain consensus/authoring.
 
 use cumulus_client_network::WaitToAnnounce;
-use cumulus_primitives_core::{CollationInfo, CollectCollationInfo, ParachainBlockData};
+use cumulus_primitives_core::{
+	CollationInfo, CollationResources, CollectCollationInfo, MessageLimits, ParachainBlockData,
+};
 
 use sc_client_api::BlockBackend;
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_consensus::BlockStatus;
 use sp_core::traits::SpawnNamed;
 use sp_runtime::traits::{Block as BlockT, HashingFor, Header as HeaderT, Zero};
+use sp_trie::StorageProof;
 
 use cumulus_client_consensus_common::ParachainCandidate;
 use polkadot_node_primitives::{
@@ -37,9 +40,39 @@ use futures::channel::oneshot;
 use parking_lot::Mutex;
 use std::sync::Arc;
 
+use crate::headroom::{CollationLimits, CollationUsage, Headroom, Metrics};
+
 /// The logging target.
 const LOG_TARGET: &str = "cumulus-collator";
 
+// The merged outputs and resources of consecutive blocks validated as one candidate.
+struct BundleInfo {
+	collation_info: CollationInfo,
+	// The resources reported by the runtime for the last block, if known.
+	resources: Option<CollationResources>,
+	// The `ref_time` and `proof_size` weight consumed by all blocks, if known for each of them.
+	consumed: Option<(u64, u64)>,
+}
+
+impl BundleInfo {
+	// The given limits, tightened by the ones reported by the runtime.
+	fn limits(&self, limits: &CollationLimits) -> CollationLimits {
+		self.resources.as_ref().map_or(*limits, |r| limits.with_resources(r))
+	}
+
+	fn usage(&self, pov_size: usize, proof_size: usize) -> CollationUsage {
+		CollationUsage {
+			pov_size,
+			proof_size,
+			consumed_proof_size: self.consumed.map(|(_, proof_size)| proof_size),
+			head_data_size: self.collation_info.head_data.0.len(),
+			execution_time: self
+				.consumed
+				.map(|(ref_time, _)| CollationUsage::estimate_execution_time(ref_time)),
+		}
+	}
+}
+
 /// Utility functions generally applicable to writing collators for Cumulus.
 pub trait ServiceInterface<Block: BlockT> {
 	/// Checks the status of the given block hash in the Parachain.
@@ -52,6 +85,8 @@ pub trait ServiceInterface<Block: BlockT> {
 	/// as implementations will fetch underlying runtime API data.
 	///
 	/// This also returns the unencoded parachain block data, in case that is desired.
+	///
+	/// Returns `None` if the collation would exceed the limits of the relay chain.
 	fn build_collation(
 		&self,
 		parent_header: &Block::Header,
@@ -59,6 +94,38 @@ pub trait ServiceInterface<Block: BlockT> {
 		candidate: ParachainCandidate<Block>,
 	) -> Option<(Collation, ParachainBlockData<Block>)>;
 
+	/// Build a single [`Collation`] bundling the given consecutive [`ParachainCandidate`]s,
+	/// which share one storage proof.
+	///
+	/// This requires that all blocks have been fully imported into the underlying client.
+	///
+	/// Returns `None` if the collation would exceed the given `limits`, tightened by the limits
+	/// of the relay chain reported by the runtime.
+	fn build_bundled_collation(
+		&self,
+		parent_header: &Block::Header,
+		candidates: Vec<ParachainCandidate<Block>>,
+		limits: &CollationLimits,
+	) -> Option<(Collation, ParachainBlockData<Block>)>;
+
+	/// Forecast the headroom of a collation bundling the given consecutive
+	/// [`ParachainCandidate`]s, without building it.
+	///
+	/// This requires that all blocks have been fully imported into the underlying client.
+	///
+	/// Returns `None` if the outputs of the candidates can not be merged without exceeding the
+	/// message limits of the relay chain.
+	fn forecast_headroom(
+		&self,
+		candidates: &[ParachainCandidate<Block>],
+		limits: &CollationLimits,
+	) -> Option<Headroom>;
+
+	/// The maximum number of blocks the runtime at `block_hash` accepts bundled into one PoV.
+	///
+	/// Returns `1` if the runtime doesn't support bundled PoVs.
+	fn max_blocks_per_pov(&self, block_hash: Block::Hash) -> u32;
+
 	/// Inform networking systems that the block should be announced after a signal has
 	/// been received to indicate the block has been seconded by a relay-chain validator.
 	///
@@ -83,6 +150,7 @@ pub struct CollatorService<Block: BlockT, BS, RA> {
 	wait_to_announce: Arc<Mutex<WaitToAnnounce<Block>>>,
 	announce_block: Arc<dyn Fn(Block::Hash, Option<Vec<u8>>) + Send + Sync>,
 	runtime_api: Arc<RA>,
+	metrics: Option<Metrics>,
 }
 
 impl<Block: BlockT, BS, RA> Clone for CollatorService<Block, BS, RA> {
@@ -92,6 +160,7 @@ impl<Block: BlockT, BS, RA> Clone for CollatorService<Block, BS, RA> {
 			wait_to_announce: self.wait_to_announce.clone(),
 			announce_block: self.announce_block.clone(),
 			runtime_api: self.runtime_api.clone(),
+			metrics: self.metrics.clone(),
 		}
 	}
 }
@@ -113,7 +182,13 @@ where
 		let wait_to_announce =
 			Arc::new(Mutex::new(WaitToAnnounce::new(spawner, announce_block.clone())));
 
-		Self { block_status, wait_to_announce, announce_block, runtime_api }
+		Self { block_status, wait_to_announce, announce_block, runtime_api, metrics: None }
+	}
+
+	/// Record the headroom of built collations in the given metrics.
+	pub fn with_metrics(mut self, metrics: Option<Metrics>) -> Self {
+		self.metrics = metrics;
+		self
 	}
 
 	/// Checks the status of the given block hash in the Parachain.
@@ -209,21 +284,244 @@ where
 		Ok(Some(collation_info))
 	}
 
+	/// Fetch the resources consumed by the block and the relay chain limits from the runtime.
+	///
+	/// Returns `Ok(None)` if the runtime doesn't implement version 3 of the
+	/// `CollectCollationInfo` runtime api or doesn't know the limits at this block.
+	pub fn fetch_collation_resources(
+		&self,
+		block_hash: Block::Hash,
+	) -> Result<Option<CollationResources>, sp_api::ApiError> {
+		let runtime_api = self.runtime_api.runtime_api();
+
+		match runtime_api.api_version::<dyn CollectCollationInfo<Block>>(block_hash)? {
+			Some(version) if version >= 3 => runtime_api.collation_resources(block_hash),
+			_ => Ok(None),
+		}
+	}
+
+	/// The maximum number of blocks the runtime at `block_hash` accepts bundled into one PoV.
+	///
+	/// Returns `1` if the runtime doesn't implement version 4 of the `CollectCollationInfo`
+	/// runtime api, as its `validate_block` can't decode bundled PoVs.
+	pub fn max_blocks_per_pov(&self, block_hash: Block::Hash) -> u32 {
+		let runtime_api = self.runtime_api.runtime_api();
+
+		let max = match runtime_api.api_version::<dyn CollectCollationInfo<Block>>(block_hash) {
+			Ok(Some(version)) if version >= 4 => runtime_api.max_blocks_per_pov(block_hash),
+			Ok(_) => return 1,
+			Err(e) => Err(e),
+		};
+
+		max.map_err(|e| {
+			tracing::debug!(
+				target: LOG_TARGET,
+				?block_hash,
+				error = ?e,
+				"Failed to fetch the maximum number of blocks per PoV.",
+			)
+		})
+		.unwrap_or(1)
+		.max(1)
+	}
+
+	/// Forecast the headroom of a collation bundling the given consecutive
+	/// [`ParachainCandidate`]s, without building it.
+	///
+	/// This is meant to be called while building blocks, so that blocks which would exceed the
+	/// limits of the relay chain can be rebuilt smaller. The size of the PoV is estimated from the
+	/// uncompressed blocks and the non-compact storage proof, which can only overestimate it.
+	///
+	/// Returns `None` if the outputs of the candidates can not be merged without exceeding the
+	/// message limits of the relay chain.
+	pub fn forecast_headroom(
+		&self,
+		candidates: &[ParachainCandidate<Block>],
+		limits: &CollationLimits,
+	) -> Option<Headroom> {
+		let info = self.collect_bundle_info(candidates.iter().map(|c| c.block.header()))?;
+
+		let proof_size =
+			StorageProof::merge(candidates.iter().map(|c| c.proof.clone())).encoded_size();
+		let blocks_size = candidates.iter().map(|c| c.block.encoded_size()).sum::<usize>();
+
+		let usage = info.usage(blocks_size + proof_size, proof_size);
+		let headroom = usage.headroom(&info.limits(limits));
+
+		tracing::trace!(
+			target: LOG_TARGET,
+			num_blocks = candidates.len(),
+			?usage,
+			headroom = ?headroom.entries(),
+			"Forecast headroom of collation.",
+		);
+
+		Some(headroom)
+	}
+
+	// Collect the collation info and resources of consecutive blocks and merge them as they
+	// are validated as one candidate.
+	fn collect_bundle_info<'a>(
+		&self,
+		headers: impl IntoIterator<Item = &'a Block::Header>,
+	) -> Option<BundleInfo> {
+		let mut collation_info: Option<CollationInfo> = None;
+		let mut resources: Option<CollationResources> = None;
+		let mut consumed = Some((0u64, 0u64));
+
+		for header in headers {
+			let block_hash = header.hash();
+
+			let block_resources = self
+				.fetch_collation_resources(block_hash)
+				.map_err(|e| {
+					tracing::debug!(
+						target: LOG_TARGET,
+						error = ?e,
+						"Failed to collect collation resources.",
+					)
+				})
+				.ok()
+				.flatten();
+
+			let block_info = self
+				.fetch_collation_info(block_hash, header)
+				.map_err(|e| {
+					tracing::error!(
+						target: LOG_TARGET,
+						error = ?e,
+						"Failed to collect collation info.",
+					)
+				})
+				.ok()
+				.flatten()?;
+
+			if let Some(info) = collation_info.as_mut() {
+				let limits = block_resources
+					.as_ref()
+					.map_or_else(MessageLimits::default, |r| r.message_limits());
+				if let Err(err) = info.merge(block_info, &limits) {
+					tracing::debug!(
+						target: LOG_TARGET,
+						?block_hash,
+						?err,
+						"Unable to bundle block with its predecessors.",
+					);
+					return None
+				}
+			} else {
+				collation_info = Some(block_info);
+			}
+
+			consumed = consumed.zip(block_resources.as_ref()).map(|((ref_time, proof_size), r)| {
+				(
+					ref_time.saturating_add(r.consumed_ref_time),
+					proof_size.saturating_add(r.consumed_proof_size),
+				)
+			});
+			resources = block_resources.or(resources);
+		}
+
+		Some(BundleInfo { collation_info: collation_info?, resources, consumed })
+	}
+
+	/// Check the headroom of a collation against the limits of the relay chain.
+	///
+	/// Returns `false` if any limit is exceeded and the collation should be skipped.
+	fn check_headroom(
+		&self,
+		block_hash: Block::Hash,
+		usage: &CollationUsage,
+		limits: &CollationLimits,
+	) -> bool {
+		let headroom = usage.headroom(limits);
+
+		if let Some(ref metrics) = self.metrics {
+			metrics.observe(&headroom);
+		}
+
+		if headroom.fits() {
+			tracing::debug!(
+				target: LOG_TARGET,
+				?block_hash,
+				?usage,
+				?limits,
+				headroom = ?headroom.entries(),
+				"Collation fits into the relay chain limits.",
+			);
+			true
+		} else {
+			tracing::warn!(
+				target: LOG_TARGET,
+				?block_hash,
+				?usage,
+				?limits,
+				exceeded = ?headroom.exceeded(),
+				"Skipping collation, because it exceeds the relay chain limits.",
+			);
+			false
+		}
+	}
+
 	/// Build a full [`Collation`] from a given [`ParachainCandidate`]. This requires
 	/// that the underlying block has been fully imported into the underlying client,
 	/// as it fetches underlying runtime API data.
 	///
 	/// This also returns the unencoded parachain block data, in case that is desired.
+	///
+	/// Before the collation is returned, the compressed PoV, the storage proof, the head data and
+	/// the execution time estimated by the runtime are checked against the limits of the relay
+	/// chain reported by the runtime. Returns `None` if any of them is exceeded, as the relay
+	/// chain would reject the collation anyway.
 	pub fn build_collation(
 		&self,
 		parent_header: &Block::Header,
 		block_hash: Block::Hash,
 		candidate: ParachainCandidate<Block>,
 	) -> Option<(Collation, ParachainBlockData<Block>)> {
-		let (header, extrinsics) = candidate.block.deconstruct();
+		self.build_collation_from_blocks(
+			parent_header,
+			vec![(block_hash, candidate)],
+			&CollationLimits::default(),
+		)
+	}
+
+	/// Build a single [`Collation`] bundling the given consecutive [`ParachainCandidate`]s.
+	///
+	/// The first candidate needs to be built on top of `parent_header` and every following
+	/// candidate on top of the previous one. The storage proofs of all candidates are merged
+	/// into one, so that trie nodes accessed by multiple blocks are only proven once. The
+	/// collation outputs, like upward and horizontal messages, are merged as described in
+	/// [`CollationInfo::merge`].
+	///
+	/// The collation is checked against the given `limits`, tightened by the ones reported by the
+	/// runtime. Returns `None` under the same conditions as [`Self::build_collation`], or if the
+	/// outputs of the candidates can not be merged.
+	pub fn build_bundled_collation(
+		&self,
+		parent_header: &Block::Header,
+		candidates: Vec<ParachainCandidate<Block>>,
+		limits: &CollationLimits,
+	) -> Option<(Collation, ParachainBlockData<Block>)> {
+		let candidates =
+			candidates.into_iter().map(|c| (c.block.header().hash(), c)).collect::<Vec<_>>();
+
+		self.build_collation_from_blocks(parent_header, candidates, limits)
+	}
+
+	fn build_collation_from_blocks(
+		&self,
+		parent_header: &Block::Header,
+		candidates: Vec<(Block::Hash, ParachainCandidate<Block>)>,
+		limits: &CollationLimits,
+	) -> Option<(Collation, ParachainBlockData<Block>)> {
+		let info = self.collect_bundle_info(candidates.iter().map(|(_, c)| c.block.header()))?;
+
+		let (blocks, proofs): (Vec<_>, Vec<_>) =
+			candidates.into_iter().map(|(_, c)| (c.block, c.proof)).unzip();
+		let block_hash = blocks.last()?.header().hash();
 
-		let compact_proof = match candidate
-			.proof
+		let compact_proof = match StorageProof::merge(proofs)
 			.into_compact_proof::<HashingFor<Block>>(*parent_header.state_root())
 		{
 			Ok(proof) => proof,
@@ -233,25 +531,19 @@ where
 			},
 		};
 
-		// Create the parachain block data for the validators.
-		let collation_info = self
-			.fetch_collation_info(block_hash, &header)
-			.map_err(|e| {
-				tracing::error!(
-					target: LOG_TARGET,
-					error = ?e,
-					"Failed to collect collation info.",
-				)
-			})
-			.ok()
-			.flatten()?;
-
-		let block_data = ParachainBlockData::<Block>::new(header, extrinsics, compact_proof);
+		let block_data = ParachainBlockData::<Block>::new_bundle(blocks, compact_proof);
 
 		let pov = polkadot_node_primitives::maybe_compress_pov(PoV {
 			block_data: BlockData(block_data.encode()),
 		});
 
+		let usage = info.usage(pov.block_data.0.len(), block_data.storage_proof().encoded_size());
+		if !self.check_headroom(block_hash, &usage, &info.limits(limits)) {
+			return None
+		}
+
+		let collation_info = info.collation_info;
+
 		let upward_messages = collation_info
 			.upward_messages
 			.try_into()
@@ -320,6 +612,27 @@ where
 		CollatorService::build_collation(self, parent_header, block_hash, candidate)
 	}
 
+	fn build_bundled_collation(
+		&self,
+		parent_header: &Block::Header,
+		candidates: Vec<ParachainCandidate<Block>>,
+		limits: &CollationLimits,
+	) -> Option<(Collation, ParachainBlockData<Block>)> {
+		CollatorService::build_bundled_collation(self, parent_header, candidates, limits)
+	}
+
+	fn forecast_headroom(
+		&self,
+		candidates: &[ParachainCandidate<Block>],
+		limits: &CollationLimits,
+	) -> Option<Headroom> {
+		CollatorService::forecast_headroom(self, candidates, limits)
+	}
+
+	fn max_blocks_per_pov(&self, block_hash: Block::Hash) -> u32 {
+		CollatorService::max_blocks_per_pov(self, block_hash)
+	}
+
 	fn announce_with_barrier(
 		&self,
 		block_hash: Block::Hash,
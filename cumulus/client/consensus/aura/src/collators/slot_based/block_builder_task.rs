@@ -18,7 +18,9 @@
 use codec::{Codec, Encode};
 
 use cumulus_client_collator::service::ServiceInterface as CollatorServiceInterface;
-use cumulus_client_consensus_common::{self as consensus_common, ParachainBlockImportMarker};
+use cumulus_client_consensus_common::{
+	self as consensus_common, ParachainBlockImportMarker, PotentialParent,
+};
 use cumulus_client_consensus_proposer::ProposerInterface;
 use cumulus_primitives_aura::AuraUnincludedSegmentApi;
 use cumulus_primitives_core::{GetCoreSelectorApi, PersistedValidationData};
@@ -49,8 +51,15 @@ use sp_consensus_aura::AuraApi;
 use sp_core::crypto::Pair;
 use sp_inherents::CreateInherentDataProviders;
 use sp_keystore::KeystorePtr;
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT, Member};
-use std::{sync::Arc, time::Duration};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, Member, NumberFor},
+	DigestItem,
+};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+/// The number of times a block that exceeds the limits of the relay chain is rebuilt with halved
+/// limits before the slot is skipped.
+const MAX_SHRINK_ATTEMPTS: u32 = 2;
 
 /// Parameters for [`run_block_builder`].
 pub struct BuilderTaskParams<
@@ -88,6 +97,10 @@ pub struct BuilderTaskParams<
 	pub collator_service: CS,
 	/// The amount of time to spend authoring each block.
 	pub authoring_duration: Duration,
+	/// The number of consecutive blocks to bundle into each PoV.
+	///
+	/// The authoring duration is split evenly between the bundled blocks.
+	pub blocks_per_pov: u32,
 	/// Channel to send built blocks to the collation task.
 	pub collator_sender: sc_utils::mpsc::TracingUnboundedSender<CollatorMessage<Block>>,
 	/// Slot duration of the relay chain.
@@ -145,11 +158,14 @@ where
 			collator_sender,
 			code_hash_provider,
 			authoring_duration,
+			blocks_per_pov,
 			relay_chain_slot_duration,
 			para_backend,
 			slot_offset,
 		} = params;
 
+		let blocks_per_pov = blocks_per_pov.max(1);
+
 		let mut slot_timer = SlotTimer::<_, _, P>::new_with_offset(
 			para_client.clone(),
 			slot_offset,
@@ -172,6 +188,10 @@ where
 
 		let mut relay_chain_data_cache = RelayChainDataCache::new(relay_client.clone(), para_id);
 
+		// Blocks that were built and imported, but couldn't be bundled with their predecessors.
+		// They are neither announced nor submitted, so they are replaced instead of built upon.
+		let mut discarded_blocks = HashMap::<Block::Hash, NumberFor<Block>>::new();
+
 		loop {
 			// We wait here until the next slot arrives.
 			let Some(para_slot) = slot_timer.wait_until_next_slot().await else {
@@ -183,13 +203,32 @@ where
 				continue
 			};
 
-			let Some((included_block, parent)) =
+			let Some((included_block, mut parent)) =
 				crate::collators::find_parent(relay_parent, para_id, &*para_backend, &relay_client)
 					.await
 			else {
 				continue
 			};
 
+			if let Ok(Some(included_number)) = para_client.number(included_block) {
+				discarded_blocks.retain(|_, number| *number > included_number);
+			}
+
+			while discarded_blocks.contains_key(&parent.hash) {
+				let parent_hash = *parent.header.parent_hash();
+				let Ok(Some(header)) = para_client.header(parent_hash) else { break };
+				parent = PotentialParent {
+					hash: parent_hash,
+					header,
+					depth: parent.depth.saturating_sub(1),
+					aligned_with_pending: parent.aligned_with_pending,
+				};
+			}
+
+			if discarded_blocks.contains_key(&parent.hash) {
+				continue
+			}
+
 			let parent_hash = parent.hash;
 
 			// Retrieve the core selector.
@@ -209,6 +248,7 @@ where
 			let Ok(RelayChainData {
 				relay_parent_header,
 				max_pov_size,
+				collation_limits,
 				scheduled_cores,
 				claimed_cores,
 			}) = relay_chain_data_cache
@@ -349,33 +389,156 @@ where
 				validation_data.max_pov_size / 2
 			} as usize;
 
-			let Ok(Some(candidate)) = collator
-				.build_block_and_import(
-					&parent_header,
-					&slot_claim,
-					None,
-					(parachain_inherent_data, other_inherent_data),
-					authoring_duration,
-					allowed_pov_size,
+			// Only bundle blocks if the runtime is able to validate bundled PoVs.
+			let blocks_per_pov =
+				blocks_per_pov.min(collator.collator_service().max_blocks_per_pov(parent_hash));
+
+			// The backing validators stop executing the candidate after the execution timeout
+			// of the session, so there is no point in spending more time on authoring.
+			let collation_limits = *collation_limits;
+			let authoring_duration = authoring_duration.min(collation_limits.max_execution_time);
+
+			// Build the blocks of the bundle on top of each other, all using the same relay
+			// parent and sharing the PoV size limit.
+			let mut parachain_candidates = Vec::with_capacity(blocks_per_pov as usize);
+			let mut block_parent_header = parent_header.clone();
+			let mut block_slot_claim = slot_claim;
+			let mut inherent_data = (parachain_inherent_data, other_inherent_data);
+			let mut remaining_pov_size = allowed_pov_size;
+			let mut block_pov_size = allowed_pov_size;
+			let mut block_authoring_duration = authoring_duration / blocks_per_pov;
+			let mut shrink_attempts = 0;
+
+			loop {
+				let Ok(Some(candidate)) = collator
+					.build_block_and_import(
+						&block_parent_header,
+						&block_slot_claim,
+						None,
+						inherent_data.clone(),
+						block_authoring_duration,
+						block_pov_size.min(remaining_pov_size),
+					)
+					.await
+				else {
+					tracing::error!(target: crate::LOG_TARGET, "Unable to build block at slot.");
+					break;
+				};
+
+				let new_block_hash = candidate.block.header().hash();
+				let new_block_number = *candidate.block.header().number();
+				let new_block_proof_size = candidate.proof.encoded_size();
+
+				parachain_candidates.push(candidate);
+
+				// The block is already imported, but it is neither announced nor used as a
+				// parent if the collation would exceed the limits of the relay chain together
+				// with its predecessors.
+				let headroom = collator
+					.collator_service()
+					.forecast_headroom(&parachain_candidates, &collation_limits);
+				if !headroom.as_ref().map_or(false, |headroom| headroom.fits()) {
+					parachain_candidates.pop();
+					discarded_blocks.insert(new_block_hash, new_block_number);
+
+					// Submit the blocks that fit, the next slot continues on top of them.
+					if !parachain_candidates.is_empty() || shrink_attempts >= MAX_SHRINK_ATTEMPTS {
+						tracing::debug!(
+							target: crate::LOG_TARGET,
+							hash = %new_block_hash,
+							exceeded = ?headroom.map(|headroom| headroom.exceeded()),
+							"Discarding block which exceeds the limits of the relay chain."
+						);
+						break;
+					}
+
+					// Rebuild the block on the same parent with smaller limits.
+					shrink_attempts += 1;
+					block_pov_size /= 2;
+					block_authoring_duration /= 2;
+					tracing::debug!(
+						target: crate::LOG_TARGET,
+						hash = %new_block_hash,
+						exceeded = ?headroom.map(|headroom| headroom.exceeded()),
+						block_pov_size,
+						?block_authoring_duration,
+						"Rebuilding block which exceeds the limits of the relay chain."
+					);
+					continue;
+				}
+
+				remaining_pov_size = remaining_pov_size.saturating_sub(new_block_proof_size);
+				block_parent_header = parachain_candidates
+					.last()
+					.expect("The block was pushed above; qed")
+					.block
+					.header()
+					.clone();
+
+				// Announce the newly built block to our peers.
+				collator.collator_service().announce_block(new_block_hash, None);
+
+				// Blocks following a runtime upgrade can not be validated with the code of the
+				// bundle's parent, so they go into the next PoV.
+				let upgraded = block_parent_header
+					.digest()
+					.logs()
+					.iter()
+					.any(|d| matches!(d, DigestItem::RuntimeEnvironmentUpdated));
+
+				if parachain_candidates.len() >= blocks_per_pov as usize || upgraded {
+					break;
+				}
+
+				let Some(next_slot_claim) = crate::collators::can_build_upon::<_, _, P>(
+					para_slot.slot,
+					relay_slot,
+					para_slot.timestamp,
+					new_block_hash,
+					included_block,
+					&*para_client,
+					&keystore,
 				)
 				.await
-			else {
-				tracing::error!(target: crate::LOG_TARGET, "Unable to build block at slot.");
-				continue;
-			};
+				else {
+					break;
+				};
 
-			let new_block_hash = candidate.block.header().hash();
+				let block_validation_data = PersistedValidationData {
+					parent_head: block_parent_header.encode().into(),
+					..validation_data.clone()
+				};
 
-			// Announce the newly built block to our peers.
-			collator.collator_service().announce_block(new_block_hash, None);
+				inherent_data = match collator
+					.create_inherent_data(
+						relay_parent,
+						&block_validation_data,
+						new_block_hash,
+						next_slot_claim.timestamp(),
+					)
+					.await
+				{
+					Err(err) => {
+						tracing::error!(target: crate::LOG_TARGET, ?err);
+						break;
+					},
+					Ok(x) => x,
+				};
+				block_slot_claim = next_slot_claim;
+			}
+
+			if parachain_candidates.is_empty() {
+				continue;
+			}
 
 			if let Err(err) = collator_sender.unbounded_send(CollatorMessage {
 				relay_parent,
 				parent_header,
-				parachain_candidate: candidate,
+				parachain_candidates,
 				validation_code_hash,
 				core_index: *core_index,
 				max_pov_size: validation_data.max_pov_size,
+				collation_limits,
 			}) {
 				tracing::error!(target: crate::LOG_TARGET, ?err, "Unable to send block to collation task.");
 				return
@@ -122,29 +122,40 @@ async fn handle_collation_message<Block: BlockT, RClient: RelayChainInterface +
 ) {
 	let CollatorMessage {
 		parent_header,
-		parachain_candidate,
+		parachain_candidates,
 		validation_code_hash,
 		relay_parent,
 		core_index,
 		max_pov_size,
+		collation_limits,
 	} = message;
 
-	let hash = parachain_candidate.block.header().hash();
-	let number = *parachain_candidate.block.header().number();
-	let (collation, block_data) =
-		match collator_service.build_collation(&parent_header, hash, parachain_candidate) {
-			Some(collation) => collation,
-			None => {
-				tracing::warn!(target: LOG_TARGET, %hash, ?number, ?core_index, "Unable to build collation.");
-				return;
-			},
-		};
+	// The block builder only bundles blocks whose outputs can be merged and which are forecast to
+	// fit into the limits, so the collation is built from all of them or not at all.
+	let Some(last) = parachain_candidates.last() else { return };
+	let hash = last.block.header().hash();
+	let number = *last.block.header().number();
+
+	let (collation, block_data) = match collator_service.build_bundled_collation(
+		&parent_header,
+		parachain_candidates,
+		&collation_limits,
+	) {
+		Some(collation) => collation,
+		None => {
+			tracing::warn!(target: LOG_TARGET, %hash, ?number, ?core_index, "Unable to build collation.");
+			return;
+		},
+	};
+
+	let num_blocks = block_data.blocks().len();
 
 	tracing::info!(
 		target: LOG_TARGET,
 		"PoV size {{ header: {:.2}kB, extrinsics: {:.2}kB, storage_proof: {:.2}kB }}",
 		block_data.header().encoded_size() as f64 / 1024f64,
-		block_data.extrinsics().encoded_size() as f64 / 1024f64,
+		block_data.blocks().iter().map(|b| b.extrinsics().encoded_size()).sum::<usize>() as f64 /
+			1024f64,
 		block_data.storage_proof().encoded_size() as f64 / 1024f64,
 	);
 
@@ -175,7 +186,7 @@ async fn handle_collation_message<Block: BlockT, RClient: RelayChainInterface +
 		);
 	}
 
-	tracing::debug!(target: LOG_TARGET, ?core_index, %hash, %number, "Submitting collation for core.");
+	tracing::debug!(target: LOG_TARGET, ?core_index, %hash, %number, num_blocks, "Submitting collation for core.");
 	overseer_handle
 		.send_msg(
 			CollationGenerationMessage::SubmitCollation(SubmitCollationParams {
@@ -69,7 +69,9 @@
 use self::{block_builder_task::run_block_builder, collation_task::run_collation_task};
 use codec::Codec;
 use consensus_common::ParachainCandidate;
-use cumulus_client_collator::service::ServiceInterface as CollatorServiceInterface;
+use cumulus_client_collator::{
+	headroom::CollationLimits, service::ServiceInterface as CollatorServiceInterface,
+};
 use cumulus_client_consensus_common::{self as consensus_common, ParachainBlockImportMarker};
 use cumulus_client_consensus_proposer::ProposerInterface;
 use cumulus_primitives_aura::AuraUnincludedSegmentApi;
@@ -130,6 +132,14 @@ pub struct Params<Block, BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS,
 	pub collator_service: CS,
 	/// The amount of time to spend authoring each block.
 	pub authoring_duration: Duration,
+	/// The number of consecutive blocks to bundle into each PoV.
+	///
+	/// Bundled blocks share a single storage proof and are validated together, which saves
+	/// proving the same trie nodes for each of them. All blocks of a bundle are built on the same
+	/// relay parent, so the runtime needs to allow building that many blocks per relay chain
+	/// slot. Use `1` to submit every block in its own PoV. This is capped by the number of blocks
+	/// the runtime accepts per PoV, which is `1` for runtimes that don't support bundled PoVs.
+	pub blocks_per_pov: u32,
 	/// Whether we should reinitialize the collator config (i.e. we are transitioning to aura).
 	pub reinitialize: bool,
 	/// Offset slots by a fixed duration. This can be used to create more preferrable authoring
@@ -187,6 +197,7 @@ pub fn run<Block, P, BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS, Spaw
 		proposer,
 		collator_service,
 		authoring_duration,
+		blocks_per_pov,
 		reinitialize,
 		slot_offset,
 		block_import_handle,
@@ -221,6 +232,7 @@ pub fn run<Block, P, BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS, Spaw
 		proposer,
 		collator_service,
 		authoring_duration,
+		blocks_per_pov,
 		collator_sender: tx,
 		relay_chain_slot_duration,
 		slot_offset,
@@ -249,14 +261,16 @@ struct CollatorMessage<Block: BlockT> {
 	pub relay_parent: RelayHash,
 	/// The header of the parent block.
 	pub parent_header: Block::Header,
-	/// The parachain block candidate.
-	pub parachain_candidate: ParachainCandidate<Block>,
+	/// The consecutive parachain block candidates to bundle into one collation.
+	pub parachain_candidates: Vec<ParachainCandidate<Block>>,
 	/// The validation code hash at the parent block.
 	pub validation_code_hash: ValidationCodeHash,
 	/// Core index that this block should be submitted on
 	pub core_index: CoreIndex,
 	/// Maximum pov size. Currently needed only for exporting PoV.
 	pub max_pov_size: u32,
+	/// The limits the collation is checked against.
+	pub collation_limits: CollationLimits,
 }
 
 /// Fetch the `CoreSelector` and `ClaimQueueOffset` for `parent_hash`.
@@ -18,10 +18,12 @@
 //! Utility for caching [`RelayChainData`] for different relay blocks.
 
 use crate::collators::cores_scheduled_for_para;
+use cumulus_client_collator::headroom::CollationLimits;
 use cumulus_primitives_core::ClaimQueueOffset;
-use cumulus_relay_chain_interface::RelayChainInterface;
+use cumulus_relay_chain_interface::{call_runtime_api, RelayChainInterface};
 use polkadot_primitives::{
-	CoreIndex, Hash as RelayHash, Header as RelayHeader, Id as ParaId, OccupiedCoreAssumption,
+	CoreIndex, ExecutorParams, Hash as RelayHash, Header as RelayHeader, Id as ParaId,
+	OccupiedCoreAssumption,
 };
 use sp_runtime::generic::BlockId;
 use std::collections::BTreeSet;
@@ -35,6 +37,8 @@ pub struct RelayChainData {
 	pub scheduled_cores: Vec<CoreIndex>,
 	/// Maximum configured PoV size on the relay chain.
 	pub max_pov_size: u32,
+	/// The limits the collations built at the relay parent are checked against.
+	pub collation_limits: CollationLimits,
 	/// The claimed cores at a relay parent.
 	pub claimed_cores: BTreeSet<CoreIndex>,
 }
@@ -118,11 +122,44 @@ where
 			},
 		};
 
+		let collation_limits = CollationLimits::default().with_max_pov_size(max_pov_size);
+		let collation_limits = match self.executor_params(relay_parent).await {
+			Some(executor_params) => collation_limits.with_executor_params(&executor_params),
+			None => collation_limits,
+		};
+
 		Ok(RelayChainData {
 			relay_parent_header,
 			scheduled_cores,
 			max_pov_size,
+			collation_limits,
 			claimed_cores: BTreeSet::new(),
 		})
 	}
+
+	/// Fetch the executor parameters of the session the candidates built at the given relay
+	/// parent are validated in.
+	async fn executor_params(&self, relay_parent: RelayHash) -> Option<ExecutorParams> {
+		let session_index = self
+			.relay_client
+			.session_index_for_child(relay_parent)
+			.await
+			.map_err(
+				|err| tracing::debug!(target: crate::LOG_TARGET, ?err, "Failed to fetch session index."),
+			)
+			.ok()?;
+
+		call_runtime_api::<Option<ExecutorParams>>(
+			&self.relay_client,
+			"ParachainHost_session_executor_params",
+			relay_parent,
+			session_index,
+		)
+		.await
+		.map_err(
+			|err| tracing::debug!(target: crate::LOG_TARGET, ?err, "Failed to fetch executor parameters."),
+		)
+		.ok()
+		.flatten()
+	}
 }
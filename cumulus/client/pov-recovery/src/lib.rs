@@ -403,9 +403,10 @@ where
 			},
 		};
 
-		let block = block_data.into_block();
+		// A PoV may bundle several consecutive blocks, which are all imported together.
+		let blocks = block_data.into_blocks();
 
-		let parent = *block.header().parent_hash();
+		let parent = *blocks[0].header().parent_hash();
 
 		match self.parachain_client.block_status(parent) {
 			Ok(BlockStatus::Unknown) => {
@@ -423,7 +424,7 @@ where
 						"Waiting for recovery of parent.",
 					);
 
-					self.waiting_for_parent.entry(parent).or_default().push(block);
+					self.waiting_for_parent.entry(parent).or_default().extend(blocks);
 					return
 				} else {
 					tracing::debug!(
@@ -452,22 +453,20 @@ where
 			_ => (),
 		}
 
-		self.import_block(block);
+		self.import_blocks(blocks);
 	}
 
-	/// Import the given `block`.
+	/// Import the given `blocks`.
 	///
 	/// This will also recursively drain `waiting_for_parent` and import them as well.
-	fn import_block(&mut self, block: Block) {
-		let mut blocks = VecDeque::new();
-
-		tracing::debug!(target: LOG_TARGET, block_hash = ?block.hash(), "Importing block retrieved using pov_recovery");
-		blocks.push_back(block);
+	fn import_blocks(&mut self, blocks: Vec<Block>) {
+		let mut blocks = VecDeque::from(blocks);
 
 		let mut incoming_blocks = Vec::new();
 
 		while let Some(block) = blocks.pop_front() {
 			let block_hash = block.hash();
+			tracing::debug!(target: LOG_TARGET, ?block_hash, "Importing block retrieved using pov_recovery");
 			let (header, body) = block.deconstruct();
 
 			incoming_blocks.push(IncomingBlock {
@@ -589,10 +588,10 @@ where
 						// Can happen when a waiting child block is queued to wait for parent while the parent block is still
 						// in the import queue.
 						if let Some(waiting_blocks) = self.waiting_for_parent.remove(&imported.hash) {
-							for block in waiting_blocks {
+							for block in &waiting_blocks {
 								tracing::debug!(target: LOG_TARGET, block_hash = ?block.hash(), resolved_parent = ?imported.hash, "Found new waiting child block during import, queuing.");
-								self.import_block(block);
 							}
+							self.import_blocks(waiting_blocks);
 						};
 
 					} else {
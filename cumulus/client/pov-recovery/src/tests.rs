@@ -565,6 +565,7 @@ fn dummy_usage_info(finalized_number: u32) -> ClientInfo<Block> {
 			finalized_state: None,
 			number_leaves: 0,
 			block_gap: None,
+			pruned_blocks: None,
 		},
 		usage: None,
 	}
@@ -99,6 +99,7 @@ impl ChainApiBackend for BlockChainRpcClient {
 			finalized_state: Some((finalized_header.hash(), finalized_header.number)),
 			number_leaves: 1,
 			block_gap: None,
+			pruned_blocks: None,
 		})
 	}
 
@@ -473,6 +474,16 @@ impl RuntimeApiSubsystemClient for BlockChainRpcClient {
 	async fn validation_code_bomb_limit(&self, at: Hash) -> Result<u32, sp_api::ApiError> {
 		Ok(self.rpc_client.parachain_host_validation_code_bomb_limit(at).await?)
 	}
+
+	async fn on_chain_bitfields(
+		&self,
+		at: Hash,
+	) -> Result<
+		Option<polkadot_primitives::vstaging::ScrapedOnChainBitfields<Hash>>,
+		sp_api::ApiError,
+	> {
+		Ok(self.rpc_client.parachain_host_on_chain_bitfields(at).await?)
+	}
 }
 
 #[async_trait::async_trait]
@@ -23,6 +23,8 @@ use polkadot_overseer::{
 };
 use polkadot_service::overseer::{collator_overseer_builder, OverseerGenArgs};
 
+use sc_authority_discovery::Service as AuthorityDiscoveryService;
+use sc_network::{service::traits::NetworkService, ProtocolName};
 use sc_service::TaskManager;
 use sc_utils::mpsc::tracing_unbounded;
 
@@ -88,6 +90,12 @@ pub struct NewMinimalNode {
 	pub task_manager: TaskManager,
 	/// Overseer handle to interact with subsystems
 	pub overseer_handle: Handle,
+	/// Network service of the minimal node
+	pub network: Arc<dyn NetworkService>,
+	/// Authority discovery service of the minimal node
+	pub authority_discovery_service: AuthorityDiscoveryService,
+	/// Name of the light client request-response protocol
+	pub light_client_protocol_name: ProtocolName,
 }
 
 /// Glues together the [`Overseer`] and `BlockchainEvents` by forwarding
@@ -47,8 +47,10 @@ use std::sync::Arc;
 mod blockchain_rpc_client;
 mod collator_overseer;
 mod network;
+mod remote_read;
 
 pub use blockchain_rpc_client::BlockChainRpcClient;
+pub use remote_read::NetworkReadProofProvider;
 
 const LOG_TARGET: &str = "minimal-relaychain-node";
 
@@ -91,10 +93,20 @@ fn build_authority_discovery_service<Block: BlockT>(
 	service
 }
 
+/// Where the minimal node gets relay chain storage read proofs from.
+enum ReadProofSource {
+	/// The RPC target generates the proofs.
+	Rpc,
+	/// The proofs are requested from the relay chain authorities, see
+	/// [`NetworkReadProofProvider`].
+	Network,
+}
+
 async fn build_interface(
 	polkadot_config: Configuration,
 	task_manager: &mut TaskManager,
 	client: RelayChainRpcClient,
+	read_proof_source: ReadProofSource,
 ) -> RelayChainResult<(Arc<(dyn RelayChainInterface + 'static)>, Option<CollatorPair>)> {
 	let collator_pair = CollatorPair::generate().0;
 	let blockchain_rpc_client = Arc::new(BlockChainRpcClient::new(client.clone()));
@@ -120,10 +132,18 @@ async fn build_interface(
 			.await?,
 	};
 	task_manager.add_child(collator_node.task_manager);
-	Ok((
-		Arc::new(RelayChainRpcInterface::new(client, collator_node.overseer_handle)),
-		Some(collator_pair),
-	))
+
+	let mut interface = RelayChainRpcInterface::new(client.clone(), collator_node.overseer_handle);
+	if let ReadProofSource::Network = read_proof_source {
+		interface = interface.with_read_proof_provider(Arc::new(NetworkReadProofProvider::new(
+			client,
+			collator_node.network,
+			collator_node.authority_discovery_service,
+			collator_node.light_client_protocol_name,
+		)));
+	}
+
+	Ok((Arc::new(interface), Some(collator_pair)))
 }
 
 pub async fn build_minimal_relay_chain_node_with_rpc(
@@ -139,7 +159,7 @@ pub async fn build_minimal_relay_chain_node_with_rpc(
 	)
 	.await?;
 
-	build_interface(relay_chain_config, task_manager, client).await
+	build_interface(relay_chain_config, task_manager, client, ReadProofSource::Rpc).await
 }
 
 pub async fn build_minimal_relay_chain_node_light_client(
@@ -164,7 +184,7 @@ pub async fn build_minimal_relay_chain_node_light_client(
 	)
 	.await?;
 
-	build_interface(polkadot_config, task_manager, client).await
+	build_interface(polkadot_config, task_manager, client, ReadProofSource::Network).await
 }
 
 /// Builds a minimal relay chain node. Chain data is fetched
@@ -226,6 +246,13 @@ async fn new_minimal_relay_chain<Block: BlockT, Network: NetworkBackend<RelayBlo
 	let (collation_req_v1_receiver, collation_req_v2_receiver, available_data_req_receiver) =
 		build_request_response_protocol_receivers(&request_protocol_names, &mut net_config);
 
+	let light_client_protocol_name =
+		remote_read::protocol_name(genesis_hash, config.chain_spec.fork_id());
+	net_config.add_request_response_protocol(remote_read::get_outbound_only_config::<Network>(
+		light_client_protocol_name.clone(),
+		&config.protocol_id(),
+	));
+
 	let best_header = relay_chain_rpc_client
 		.chain_get_header(None)
 		.await?
@@ -250,9 +277,9 @@ async fn new_minimal_relay_chain<Block: BlockT, Network: NetworkBackend<RelayBlo
 
 	let overseer_args = OverseerGenArgs {
 		runtime_client: relay_chain_rpc_client.clone(),
-		network_service: network,
+		network_service: network.clone(),
 		sync_service,
-		authority_discovery_service,
+		authority_discovery_service: authority_discovery_service.clone(),
 		collation_req_v1_receiver,
 		collation_req_v2_receiver,
 		available_data_req_receiver,
@@ -268,7 +295,13 @@ async fn new_minimal_relay_chain<Block: BlockT, Network: NetworkBackend<RelayBlo
 	let overseer_handle =
 		collator_overseer::spawn_overseer(overseer_args, &task_manager, relay_chain_rpc_client)?;
 
-	Ok(NewMinimalNode { task_manager, overseer_handle })
+	Ok(NewMinimalNode {
+		task_manager,
+		overseer_handle,
+		network,
+		authority_discovery_service,
+		light_client_protocol_name,
+	})
 }
 
 fn build_request_response_protocol_receivers<
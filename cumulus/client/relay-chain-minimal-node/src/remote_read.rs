@@ -0,0 +1,386 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus. If not, see <https://www.gnu.org/licenses/>.
+
+//! Relay chain storage read proofs fetched from relay chain validators.
+//!
+//! The embedded light client only serves verified storage values, but block production needs
+//! read proofs of the relay chain state. In light client mode these are requested from the
+//! current relay chain authorities via the light client request-response protocol. Every proof
+//! is checked against the state root of the header verified by the light client.
+
+use async_trait::async_trait;
+use codec::{Decode, Encode};
+use polkadot_core_primitives::{Block as RelayBlock, Hash as RelayHash};
+use prost::Message;
+use rand::seq::SliceRandom;
+use sc_authority_discovery::Service as AuthorityDiscoveryService;
+use sc_client_api::StorageProof;
+use sc_network::{
+	config::ProtocolId, service::traits::NetworkService, IfDisconnected, NetworkBackend, PeerId,
+	ProtocolName, MAX_RESPONSE_SIZE,
+};
+use sp_runtime::traits::{HashingFor, Header as HeaderT};
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use cumulus_relay_chain_interface::{RelayChainError, RelayChainResult};
+use cumulus_relay_chain_rpc_interface::{ReadProofProvider, RelayChainRpcClient};
+
+/// Maximum number of authorities asked for a single read proof.
+const MAX_ATTEMPTS: usize = 5;
+
+/// Timeout of a single read proof request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Messages of the light client request-response protocol, as defined in `sc-network-light`.
+mod schema {
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Request {
+		#[prost(oneof = "request::Request", tags = "2")]
+		pub request: Option<request::Request>,
+	}
+
+	pub mod request {
+		#[derive(Clone, PartialEq, prost::Oneof)]
+		pub enum Request {
+			#[prost(message, tag = "2")]
+			RemoteReadRequest(super::RemoteReadRequest),
+		}
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct Response {
+		#[prost(oneof = "response::Response", tags = "2")]
+		pub response: Option<response::Response>,
+	}
+
+	pub mod response {
+		#[derive(Clone, PartialEq, prost::Oneof)]
+		pub enum Response {
+			#[prost(message, tag = "2")]
+			RemoteReadResponse(super::RemoteReadResponse),
+		}
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct RemoteReadRequest {
+		#[prost(bytes = "vec", required, tag = "2")]
+		pub block: Vec<u8>,
+		#[prost(bytes = "vec", repeated, tag = "3")]
+		pub keys: Vec<Vec<u8>>,
+	}
+
+	#[derive(Clone, PartialEq, prost::Message)]
+	pub struct RemoteReadResponse {
+		#[prost(bytes = "vec", optional, tag = "2")]
+		pub proof: Option<Vec<u8>>,
+	}
+}
+
+/// Name of the light client request-response protocol.
+pub(crate) fn protocol_name(genesis_hash: RelayHash, fork_id: Option<&str>) -> ProtocolName {
+	let genesis_hash = genesis_hash.as_ref();
+	if let Some(fork_id) = fork_id {
+		format!("/{}/{}/light/2", array_bytes::bytes2hex("", genesis_hash), fork_id).into()
+	} else {
+		format!("/{}/light/2", array_bytes::bytes2hex("", genesis_hash)).into()
+	}
+}
+
+/// Outbound only configuration of the light client request-response protocol.
+pub(crate) fn get_outbound_only_config<Network: NetworkBackend<RelayBlock, RelayHash>>(
+	protocol_name: ProtocolName,
+	protocol_id: &ProtocolId,
+) -> Network::RequestResponseProtocolConfig {
+	Network::request_response_config(
+		protocol_name,
+		std::iter::once(format!("/{}/light/2", protocol_id.as_ref()).into()).collect(),
+		1024 * 1024,
+		MAX_RESPONSE_SIZE,
+		REQUEST_TIMEOUT,
+		None,
+	)
+}
+
+/// [`ReadProofProvider`] requesting read proofs from the relay chain authorities.
+pub struct NetworkReadProofProvider {
+	rpc_client: RelayChainRpcClient,
+	network: Arc<dyn NetworkService>,
+	authority_discovery_service: AuthorityDiscoveryService,
+	protocol_name: ProtocolName,
+}
+
+impl NetworkReadProofProvider {
+	pub(crate) fn new(
+		rpc_client: RelayChainRpcClient,
+		network: Arc<dyn NetworkService>,
+		authority_discovery_service: AuthorityDiscoveryService,
+		protocol_name: ProtocolName,
+	) -> Self {
+		Self { rpc_client, network, authority_discovery_service, protocol_name }
+	}
+
+	/// Peer ids of the authorities at `relay_parent`, in random order.
+	async fn authority_peers(&self, relay_parent: RelayHash) -> RelayChainResult<Vec<PeerId>> {
+		let mut authorities = self.rpc_client.authority_discovery_authorities(relay_parent).await?;
+		authorities.shuffle(&mut rand::thread_rng());
+
+		let mut authority_discovery_service = self.authority_discovery_service.clone();
+		let mut peers = Vec::new();
+		let mut seen = HashSet::new();
+		for authority in authorities {
+			if peers.len() >= MAX_ATTEMPTS {
+				break
+			}
+
+			let Some(addresses) =
+				authority_discovery_service.get_addresses_by_authority_id(authority).await
+			else {
+				continue
+			};
+
+			peers.extend(
+				addresses
+					.iter()
+					.filter_map(PeerId::try_from_multiaddr)
+					.filter(|peer| seen.insert(*peer))
+					.take(1),
+			);
+		}
+
+		Ok(peers)
+	}
+
+	/// Request a read proof from `peer` and check it against `state_root`.
+	async fn request_proof(
+		&self,
+		peer: PeerId,
+		request: Vec<u8>,
+		state_root: RelayHash,
+		keys: &[Vec<u8>],
+	) -> Result<StorageProof, String> {
+		let (response, _) = self
+			.network
+			.request(peer, self.protocol_name.clone(), request, None, IfDisconnected::TryConnect)
+			.await
+			.map_err(|e| e.to_string())?;
+
+		check_read_response(&response, state_root, keys)
+	}
+}
+
+/// Encode a request for a read proof of `keys` at `block`.
+fn read_request(block: RelayHash, keys: Vec<Vec<u8>>) -> Vec<u8> {
+	schema::Request {
+		request: Some(schema::request::Request::RemoteReadRequest(schema::RemoteReadRequest {
+			block: block.encode(),
+			keys,
+		})),
+	}
+	.encode_to_vec()
+}
+
+/// Decode a read proof response and check the proof of `keys` against `state_root`.
+fn check_read_response(
+	response: &[u8],
+	state_root: RelayHash,
+	keys: &[Vec<u8>],
+) -> Result<StorageProof, String> {
+	let response = schema::Response::decode(response).map_err(|e| e.to_string())?;
+	let proof = match response.response {
+		Some(schema::response::Response::RemoteReadResponse(schema::RemoteReadResponse {
+			proof: Some(proof),
+		})) => StorageProof::decode(&mut &proof[..]).map_err(|e| e.to_string())?,
+		_ => return Err("Peer did not provide a read proof".into()),
+	};
+
+	sp_state_machine::read_proof_check::<HashingFor<RelayBlock>, _>(
+		state_root,
+		proof.clone(),
+		keys.iter(),
+	)
+	.map_err(|e| e.to_string())?;
+
+	Ok(proof)
+}
+
+#[async_trait]
+impl ReadProofProvider for NetworkReadProofProvider {
+	async fn prove_read(
+		&self,
+		relay_parent: RelayHash,
+		relevant_keys: &Vec<Vec<u8>>,
+	) -> RelayChainResult<StorageProof> {
+		if relevant_keys.is_empty() {
+			return Ok(StorageProof::empty())
+		}
+
+		let state_root = *self
+			.rpc_client
+			.chain_get_header(Some(relay_parent))
+			.await?
+			.ok_or_else(|| {
+				RelayChainError::GenericError(format!(
+					"Unable to fetch header of relay parent {relay_parent:?}"
+				))
+			})?
+			.state_root();
+
+		let request = read_request(relay_parent, relevant_keys.clone());
+
+		for peer in self.authority_peers(relay_parent).await? {
+			match self.request_proof(peer, request.clone(), state_root, relevant_keys).await {
+				Ok(proof) => return Ok(proof),
+				Err(error) => tracing::debug!(
+					target: crate::LOG_TARGET,
+					?peer,
+					?relay_parent,
+					%error,
+					"Failed to fetch read proof from peer.",
+				),
+			}
+		}
+
+		Err(RelayChainError::GenericError(format!(
+			"Unable to fetch read proof at {relay_parent:?} from any relay chain authority"
+		)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::channel::oneshot;
+	use sc_client_api::ProofProvider;
+	use sc_network::{
+		config::{IncomingRequest, OutgoingResponse},
+		NetworkWorker,
+	};
+	use sc_network_light::light_client_requests::handler::LightClientRequestHandler;
+	use sp_blockchain::HeaderBackend;
+	use substrate_test_runtime_client::runtime::Block;
+
+	fn keys() -> Vec<Vec<u8>> {
+		vec![b":code".to_vec(), b"missing".to_vec()]
+	}
+
+	fn read_response(proof: Option<Vec<u8>>) -> Vec<u8> {
+		schema::Response {
+			response: Some(schema::response::Response::RemoteReadResponse(
+				schema::RemoteReadResponse { proof },
+			)),
+		}
+		.encode_to_vec()
+	}
+
+	#[test]
+	fn read_proofs_are_checked_against_the_state_root() {
+		let client = substrate_test_runtime_client::new();
+		let genesis_hash = client.info().genesis_hash;
+		let state_root = *client.header(genesis_hash).unwrap().unwrap().state_root();
+		let keys = keys();
+		let proof = client
+			.read_proof(genesis_hash, &mut keys.iter().map(|key| key.as_slice()))
+			.unwrap();
+
+		assert_eq!(
+			check_read_response(&read_response(Some(proof.encode())), state_root, &keys),
+			Ok(proof.clone()),
+		);
+
+		// The proof does not match another state root.
+		assert!(check_read_response(
+			&read_response(Some(proof.encode())),
+			RelayHash::repeat_byte(1),
+			&keys,
+		)
+		.is_err());
+
+		// The proof does not contain the requested keys.
+		assert!(check_read_response(
+			&read_response(Some(StorageProof::empty().encode())),
+			state_root,
+			&keys,
+		)
+		.is_err());
+
+		// The peer could not answer.
+		assert!(check_read_response(&read_response(None), state_root, &keys).is_err());
+
+		// The proof can not be decoded.
+		assert!(
+			check_read_response(&read_response(Some(vec![0xff; 4])), state_root, &keys).is_err()
+		);
+		assert!(check_read_response(&[0xff; 4], state_root, &keys).is_err());
+	}
+
+	#[test]
+	fn read_responses_decode_like_sc_network_light() {
+		// Encodings of the responses without and with an empty proof, as produced by
+		// `sc-network-light`.
+		assert_eq!(read_response(None), vec![(2 << 3) | 2, 0]);
+		assert_eq!(read_response(Some(Vec::new())), vec![(2 << 3) | 2, 2, (2 << 3) | 2, 0]);
+		assert_eq!(
+			schema::Response::decode(&[(2 << 3) | 2, 6, (2 << 3) | 2, 4, 1, 2, 3, 4][..]).unwrap(),
+			schema::Response {
+				response: Some(schema::response::Response::RemoteReadResponse(
+					schema::RemoteReadResponse { proof: Some(vec![1, 2, 3, 4]) },
+				)),
+			},
+		);
+	}
+
+	#[tokio::test]
+	async fn read_requests_are_answered_by_sc_network_light() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let genesis_hash = client.info().genesis_hash;
+		let state_root = *client.header(genesis_hash).unwrap().unwrap().state_root();
+		let protocol_id = ProtocolId::from("test");
+
+		let (handler, config) = LightClientRequestHandler::<Block, _>::new::<
+			NetworkWorker<Block, RelayHash>,
+		>(&protocol_id, None, client.clone());
+		let outbound_config = get_outbound_only_config::<NetworkWorker<RelayBlock, RelayHash>>(
+			protocol_name(genesis_hash, None),
+			&protocol_id,
+		);
+		assert_eq!(outbound_config.name, config.name);
+		assert_eq!(outbound_config.fallback_names, config.fallback_names);
+		tokio::spawn(handler.run());
+
+		let keys = keys();
+		let (pending_response, response) = oneshot::channel();
+		config
+			.inbound_queue
+			.expect("The light client request handler answers requests; qed")
+			.send(IncomingRequest {
+				peer: PeerId::random(),
+				payload: read_request(genesis_hash, keys.clone()),
+				pending_response,
+			})
+			.await
+			.unwrap();
+		let OutgoingResponse { result, .. } = response.await.unwrap();
+
+		let proof = check_read_response(&result.unwrap(), state_root, &keys).unwrap();
+		let values = sp_state_machine::read_proof_check::<HashingFor<RelayBlock>, _>(
+			state_root, proof, &keys,
+		)
+		.unwrap();
+		assert!(values[&keys[0]].is_some());
+		assert_eq!(values[&keys[1]], None);
+	}
+}
@@ -35,7 +35,7 @@ use sc_client_api::StorageProof;
 use sp_state_machine::StorageValue;
 use sp_storage::StorageKey;
 use sp_version::RuntimeVersion;
-use std::{collections::btree_map::BTreeMap, pin::Pin};
+use std::{collections::btree_map::BTreeMap, pin::Pin, sync::Arc};
 
 use cumulus_primitives_core::relay_chain::BlockId;
 pub use url::Url;
@@ -53,17 +53,39 @@ pub use rpc_client::{
 
 const TIMEOUT_IN_SECONDS: u64 = 6;
 
+/// Source of relay chain storage read proofs.
+///
+/// Not every RPC target is able to generate read proofs, the embedded light client for example
+/// only serves verified storage values. In that case the proofs can be fetched from somewhere
+/// else by setting a [`ReadProofProvider`] on the [`RelayChainRpcInterface`].
+#[async_trait]
+pub trait ReadProofProvider: Send + Sync {
+	/// Generate a storage read proof for the given keys at `relay_parent`.
+	async fn prove_read(
+		&self,
+		relay_parent: RelayHash,
+		relevant_keys: &Vec<Vec<u8>>,
+	) -> RelayChainResult<StorageProof>;
+}
+
 /// RelayChainRpcInterface is used to interact with a full node that is running locally
 /// in the same process.
 #[derive(Clone)]
 pub struct RelayChainRpcInterface {
 	rpc_client: RelayChainRpcClient,
 	overseer_handle: Handle,
+	read_proof_provider: Option<Arc<dyn ReadProofProvider>>,
 }
 
 impl RelayChainRpcInterface {
 	pub fn new(rpc_client: RelayChainRpcClient, overseer_handle: Handle) -> Self {
-		Self { rpc_client, overseer_handle }
+		Self { rpc_client, overseer_handle, read_proof_provider: None }
+	}
+
+	/// Fetch storage read proofs from the given provider instead of the RPC target.
+	pub fn with_read_proof_provider(mut self, provider: Arc<dyn ReadProofProvider>) -> Self {
+		self.read_proof_provider = Some(provider);
+		self
 	}
 }
 
@@ -204,6 +226,10 @@ impl RelayChainInterface for RelayChainRpcInterface {
 		relay_parent: RelayHash,
 		relevant_keys: &Vec<Vec<u8>>,
 	) -> RelayChainResult<StorageProof> {
+		if let Some(provider) = &self.read_proof_provider {
+			return provider.prove_read(relay_parent, relevant_keys).await
+		}
+
 		let cloned = relevant_keys.clone();
 		let storage_keys: Vec<StorageKey> = cloned.into_iter().map(StorageKey).collect();
 
@@ -38,7 +38,7 @@ use cumulus_primitives_core::{
 		vstaging::{
 			async_backing::{BackingState, Constraints},
 			CandidateEvent, CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreState,
-			ScrapedOnChainVotes,
+			ScrapedOnChainBitfields, ScrapedOnChainVotes,
 		},
 		ApprovalVotingParams, BlockNumber, CandidateCommitments, CandidateHash, CoreIndex,
 		DisputeState, ExecutorParams, GroupRotationInfo, Hash as RelayHash, Header as RelayHeader,
@@ -727,6 +727,15 @@ impl RelayChainRpcClient {
 		.await
 	}
 
+	/// Scrape the availability bitfields included on chain.
+	pub async fn parachain_host_on_chain_bitfields(
+		&self,
+		at: RelayHash,
+	) -> Result<Option<ScrapedOnChainBitfields<RelayHash>>, RelayChainError> {
+		self.call_remote_runtime_function("ParachainHost_on_chain_bitfields", at, None::<()>)
+			.await
+	}
+
 	pub async fn validation_code_hash(
 		&self,
 		at: RelayHash,
@@ -41,7 +41,7 @@ use sc_consensus::{
 use sc_network::{config::SyncMode, service::traits::NetworkService, NetworkBackend};
 use sc_network_sync::SyncingService;
 use sc_network_transactions::TransactionsHandlerController;
-use sc_service::{Configuration, SpawnTaskHandle, TaskManager, WarpSyncConfig};
+use sc_service::{Configuration, SpawnTaskHandle, StateSyncStore, TaskManager, WarpSyncConfig};
 use sc_telemetry::{log, TelemetryWorkerHandle};
 use sc_utils::mpsc::TracingUnboundedSender;
 use sp_api::ProvideRuntimeApi;
@@ -420,6 +420,7 @@ pub struct BuildNetworkParams<
 	pub relay_chain_interface: RCInterface,
 	pub spawn_handle: SpawnTaskHandle,
 	pub import_queue: IQ,
+	pub state_sync_store: Option<Arc<dyn StateSyncStore>>,
 	pub sybil_resistance_level: CollatorSybilResistance,
 }
 
@@ -434,6 +435,7 @@ pub async fn build_network<'a, Block, Client, RCInterface, IQ, Network>(
 		spawn_handle,
 		relay_chain_interface,
 		import_queue,
+		state_sync_store,
 		sybil_resistance_level,
 	}: BuildNetworkParams<'a, Block, Client, Network, RCInterface, IQ>,
 ) -> sc_service::error::Result<(
@@ -506,6 +508,7 @@ where
 		import_queue,
 		block_announce_validator_builder: Some(Box::new(move |_| block_announce_validator)),
 		warp_sync_config,
+		state_sync_store,
 		block_relay: None,
 		metrics,
 	})
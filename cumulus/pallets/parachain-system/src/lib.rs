@@ -37,10 +37,10 @@ use cumulus_primitives_core::{
 		self,
 		vstaging::{ClaimQueueOffset, CoreSelector, DEFAULT_CLAIM_QUEUE_OFFSET},
 	},
-	AbridgedHostConfiguration, ChannelInfo, ChannelStatus, CollationInfo, GetChannelInfo,
-	InboundDownwardMessage, InboundHrmpMessage, ListChannelInfos, MessageSendError,
+	AbridgedHostConfiguration, ChannelInfo, ChannelStatus, CollationInfo, CollationResources,
+	GetChannelInfo, InboundDownwardMessage, InboundHrmpMessage, ListChannelInfos, MessageSendError,
 	OutboundHrmpMessage, ParaId, PersistedValidationData, UpwardMessage, UpwardMessageSender,
-	XcmpMessageHandler, XcmpMessageSource,
+	XcmpMessageHandler, XcmpMessageSource, MAX_BUNDLED_BLOCKS,
 };
 use cumulus_primitives_parachain_inherent::{MessageQueueChain, ParachainInherentData};
 use frame_support::{
@@ -1434,6 +1434,37 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Returns the [`CollationResources`] of the current active block.
+	///
+	/// The consumed weight is taken from `frame_system` and the limits from the validation data
+	/// and host configuration provided by the relay chain. Returns `None` if these are not
+	/// available, e.g. before the validation data inherent was applied.
+	///
+	/// This is expected to be used by the
+	/// [`CollectCollationInfo`](cumulus_primitives_core::CollectCollationInfo) runtime api.
+	pub fn collation_resources() -> Option<CollationResources> {
+		let validation_data = ValidationData::<T>::get()?;
+		let host_config = HostConfiguration::<T>::get()?;
+		let consumed = frame_system::Pallet::<T>::block_weight().total();
+
+		Some(CollationResources {
+			consumed_ref_time: consumed.ref_time(),
+			consumed_proof_size: consumed.proof_size(),
+			max_pov_size: validation_data.max_pov_size,
+			max_head_data_size: host_config.max_head_data_size,
+			max_upward_message_num: host_config.max_upward_message_num_per_candidate,
+			max_horizontal_message_num: host_config.hrmp_max_message_num_per_candidate,
+		})
+	}
+
+	/// Returns the maximum number of blocks `validate_block` accepts bundled into a single PoV.
+	///
+	/// This is expected to be used by the
+	/// [`CollectCollationInfo`](cumulus_primitives_core::CollectCollationInfo) runtime api.
+	pub fn max_blocks_per_pov() -> u32 {
+		MAX_BUNDLED_BLOCKS
+	}
+
 	/// Returns the core selector for the next block.
 	pub fn core_selector() -> (CoreSelector, ClaimQueueOffset) {
 		T::SelectCore::select_next_core()
@@ -20,7 +20,9 @@ use super::*;
 use crate::mock::*;
 
 use core::num::NonZeroU32;
-use cumulus_primitives_core::{AbridgedHrmpChannel, InboundDownwardMessage, InboundHrmpMessage};
+use cumulus_primitives_core::{
+	AbridgedHrmpChannel, InboundDownwardMessage, InboundHrmpMessage, MessageLimits,
+};
 use frame_support::{assert_ok, parameter_types, weights::Weight};
 use frame_system::RawOrigin;
 use hex_literal::hex;
@@ -1226,6 +1228,37 @@ fn deposits_relay_parent_storage_root() {
 	);
 }
 
+#[test]
+fn collation_resources_reports_consumed_weight_and_limits() {
+	BlockTests::new()
+		.with_relay_sproof_builder(|_, _, sproof| {
+			sproof.host_config.max_head_data_size = 1024;
+			sproof.host_config.max_upward_message_num_per_candidate = 3;
+			sproof.host_config.hrmp_max_message_num_per_candidate = 4;
+		})
+		.with_inherent_data(|_, _, data| {
+			data.validation_data.max_pov_size = 5 * 1024 * 1024;
+		})
+		.add(1, || {
+			let before = ParachainSystem::collation_resources().unwrap();
+			assert_eq!(before.max_pov_size, 5 * 1024 * 1024);
+			assert_eq!(before.max_head_data_size, 1024);
+			assert_eq!(
+				before.message_limits(),
+				MessageLimits { max_upward_message_num: 3, max_horizontal_message_num: 4 }
+			);
+
+			System::register_extra_weight_unchecked(
+				Weight::from_parts(1_000, 2_000),
+				frame_support::dispatch::DispatchClass::Normal,
+			);
+
+			let after = ParachainSystem::collation_resources().unwrap();
+			assert_eq!(after.consumed_ref_time, before.consumed_ref_time + 1_000);
+			assert_eq!(after.consumed_proof_size, before.consumed_proof_size + 2_000);
+		});
+}
+
 #[test]
 fn ump_fee_factor_increases_and_decreases() {
 	BlockTests::new()
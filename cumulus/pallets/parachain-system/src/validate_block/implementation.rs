@@ -18,7 +18,8 @@
 
 use super::{trie_cache, trie_recorder, MemoryOptimizedValidationParams};
 use cumulus_primitives_core::{
-	relay_chain::Hash as RHash, ParachainBlockData, PersistedValidationData,
+	relay_chain::Hash as RHash, CollationInfo, MessageLimits, ParachainBlockData,
+	PersistedValidationData, MAX_BUNDLED_BLOCKS,
 };
 use cumulus_primitives_parachain_inherent::ParachainInherentData;
 
@@ -34,7 +35,8 @@ use sp_core::storage::{ChildInfo, StateVersion};
 use sp_externalities::{set_and_run_with_externalities, Externalities};
 use sp_io::KillStorageResult;
 use sp_runtime::traits::{Block as BlockT, ExtrinsicLike, HashingFor, Header as HeaderT};
-use sp_trie::{MemoryDB, ProofSizeProvider};
+use sp_state_machine::OverlayedChanges;
+use sp_trie::{HashDBT, MemoryDB, ProofSizeProvider, EMPTY_PREFIX};
 use trie_recorder::SizeOnlyRecorderProvider;
 
 type TrieBackend<B> = sp_state_machine::TrieBackend<
@@ -57,11 +59,14 @@ environmental::environmental!(recorder: trait ProofSizeProvider);
 ///
 /// This function is doing roughly the following:
 ///
-/// 1. We decode the [`ParachainBlockData`] from the `block_data` in `params`.
+/// 1. We decode the [`ParachainBlockData`] from the `block_data` in `params`. It contains one or
+/// more consecutive blocks that share one storage proof. The following steps are done for each of
+/// these blocks, where the state changes of each block are applied before the next one is checked.
 ///
-/// 2. We are doing some security checks like checking that the `parent_head` in `params`
-/// is the parent of the block we are going to check. We also ensure that the `set_validation_data`
-/// inherent is present in the block and that the validation data matches the values in `params`.
+/// 2. We are doing some security checks like checking that the `parent_head` in `params`, or
+/// the previous block of the bundle, is the parent of the block we are going to check. We also
+/// ensure that the `set_validation_data` inherent is present in the block and that the validation
+/// data matches the values in `params`.
 ///
 /// 3. We construct the sparse in-memory database from the storage proof inside the block data and
 /// then ensure that the storage root matches the storage root in the `parent_head`.
@@ -78,7 +83,8 @@ environmental::environmental!(recorder: trait ProofSizeProvider);
 /// 6. The last step is to execute the entire block in the machinery we just have setup. Executing
 /// the blocks include running all transactions in the block against our in-memory database and
 /// ensuring that the final storage root matches the storage root in the header of the block. In the
-/// end we return back the [`ValidationResult`] with all the required information for the validator.
+/// end we return back the [`ValidationResult`] with all the required information for the validator,
+/// merged over all blocks of the bundle.
 #[doc(hidden)]
 #[allow(deprecated)]
 pub fn validate_block<
@@ -101,25 +107,16 @@ where
 	let block_data = codec::decode_from_bytes::<ParachainBlockData<B>>(block_data)
 		.expect("Invalid parachain block data");
 
-	let parent_header =
+	let mut parent_header =
 		codec::decode_from_bytes::<B::Header>(parent_head.clone()).expect("Invalid parent head");
+	let mut parent_head = parent_head;
 
-	let (header, extrinsics, storage_proof) = block_data.deconstruct();
-
-	let block = B::new(header, extrinsics);
-	assert!(parent_header.hash() == *block.header().parent_hash(), "Invalid parent hash");
-
-	let inherent_data = extract_parachain_inherent_data(&block);
-
-	validate_validation_data(
-		&inherent_data.validation_data,
-		relay_parent_number,
-		relay_parent_storage_root,
-		parent_head,
-	);
+	let (blocks, storage_proof) = block_data.deconstruct();
+	let num_blocks = blocks.len();
+	assert!(num_blocks <= MAX_BUNDLED_BLOCKS as usize, "Too many bundled blocks");
 
 	// Create the db
-	let db = match storage_proof.to_memory_db(Some(parent_header.state_root())) {
+	let mut db = match storage_proof.to_memory_db(Some(parent_header.state_root())) {
 		Ok((db, _)) => db,
 		Err(_) => panic!("Compact proof decoding failure."),
 	};
@@ -127,16 +124,6 @@ where
 	core::mem::drop(storage_proof);
 
 	let mut recorder = SizeOnlyRecorderProvider::new();
-	let cache_provider = trie_cache::CacheProvider::new();
-	// We use the storage root of the `parent_head` to ensure that it is the correct root.
-	// This is already being done above while creating the in-memory db, but let's be paranoid!!
-	let backend = sp_state_machine::TrieBackendBuilder::new_with_cache(
-		db,
-		*parent_header.state_root(),
-		cache_provider,
-	)
-	.with_recorder(recorder.clone())
-	.build();
 
 	let _guard = (
 		// Replace storage calls with our own implementations
@@ -179,59 +166,152 @@ where
 			.replace_implementation(host_storage_proof_size),
 	);
 
-	run_with_externalities_and_recorder::<B, _, _>(&backend, &mut recorder, || {
-		let relay_chain_proof = crate::RelayChainStateProof::new(
-			PSC::SelfParaId::get(),
-			inherent_data.validation_data.relay_parent_storage_root,
-			inherent_data.relay_chain_state.clone(),
-		)
-		.expect("Invalid relay chain state proof");
+	let mut collation_info: Option<CollationInfo> = None;
+
+	for (index, block) in blocks.into_iter().enumerate() {
+		assert!(parent_header.hash() == *block.header().parent_hash(), "Invalid parent hash");
 
-		#[allow(deprecated)]
-		let res = CI::check_inherents(&block, &relay_chain_proof);
+		let inherent_data = extract_parachain_inherent_data(&block);
 
-		if !res.ok() {
-			if log::log_enabled!(log::Level::Error) {
-				res.into_errors().for_each(|e| {
-					log::error!("Checking inherent with identifier `{:?}` failed", e.0)
-				});
-			}
+		validate_validation_data(
+			&inherent_data.validation_data,
+			relay_parent_number,
+			relay_parent_storage_root,
+			parent_head,
+		);
+
+		// We use the storage root of the parent to ensure that it is the correct root.
+		// For the first block this is already being done above while creating the in-memory db,
+		// but let's be paranoid!!
+		let backend = sp_state_machine::TrieBackendBuilder::new_with_cache(
+			db,
+			*parent_header.state_root(),
+			trie_cache::CacheProvider::new(),
+		)
+		.with_recorder(recorder.clone())
+		.build();
+
+		let message_limits = run_with_externalities_and_recorder::<B, _, _>(
+			&backend,
+			&mut recorder,
+			&mut Default::default(),
+			|| {
+				let relay_chain_proof = crate::RelayChainStateProof::new(
+					PSC::SelfParaId::get(),
+					inherent_data.validation_data.relay_parent_storage_root,
+					inherent_data.relay_chain_state.clone(),
+				)
+				.expect("Invalid relay chain state proof");
+
+				#[allow(deprecated)]
+				let res = CI::check_inherents(&block, &relay_chain_proof);
+
+				if !res.ok() {
+					if log::log_enabled!(log::Level::Error) {
+						res.into_errors().for_each(|e| {
+							log::error!("Checking inherent with identifier `{:?}` failed", e.0)
+						});
+					}
+
+					panic!("Checking inherents failed");
+				}
+
+				relay_chain_proof
+					.read_abridged_host_configuration()
+					.map(|config| MessageLimits::from(&config))
+					.expect("Invalid host configuration in relay chain state proof")
+			},
+		);
 
-			panic!("Checking inherents failed");
-		}
-	});
+		let header = block.header().clone();
+		let mut overlay = OverlayedChanges::default();
+
+		let block_info = run_with_externalities_and_recorder::<B, _, _>(
+			&backend,
+			&mut recorder,
+			&mut overlay,
+			|| {
+				let head_data = header.encode();
+
+				E::execute_block(block);
+
+				CollationInfo {
+					upward_messages: crate::UpwardMessages::<PSC>::get(),
+					horizontal_messages: crate::HrmpOutboundMessages::<PSC>::get(),
+					new_validation_code: crate::NewValidationCode::<PSC>::get().map(Into::into),
+					processed_downward_messages: crate::ProcessedDownwardMessages::<PSC>::get(),
+					hrmp_watermark: crate::HrmpWatermark::<PSC>::get(),
+					head_data: HeadData(
+						crate::CustomValidationHeadData::<PSC>::get().unwrap_or(head_data),
+					),
+				}
+			},
+		);
 
-	run_with_externalities_and_recorder::<B, _, _>(&backend, &mut recorder, || {
-		let head_data = HeadData(block.header().encode());
+		collation_info = Some(match collation_info {
+			Some(mut info) => {
+				if let Err(err) = info.merge(block_info, &message_limits) {
+					panic!("Unable to merge the outputs of bundled blocks: {:?}", err)
+				}
+				info
+			},
+			None => block_info,
+		});
+
+		// Apply the changes of this block, so that the next block can be executed on top of them.
+		db = if index + 1 < num_blocks {
+			apply_block_changes::<B, PSC>(backend, &mut overlay, &header)
+		} else {
+			MemoryDB::default()
+		};
+
+		parent_head = header.encode().into();
+		parent_header = header;
+	}
 
-		E::execute_block(block);
+	let collation_info = collation_info.expect("There is at least one block; qed");
 
-		let new_validation_code = crate::NewValidationCode::<PSC>::get();
-		let upward_messages = crate::UpwardMessages::<PSC>::get().try_into().expect(
+	ValidationResult {
+		head_data: collation_info.head_data,
+		new_validation_code: collation_info.new_validation_code,
+		upward_messages: collation_info.upward_messages.try_into().expect(
 			"Number of upward messages should not be greater than `MAX_UPWARD_MESSAGE_NUM`",
-		);
-		let processed_downward_messages = crate::ProcessedDownwardMessages::<PSC>::get();
-		let horizontal_messages = crate::HrmpOutboundMessages::<PSC>::get().try_into().expect(
+		),
+		processed_downward_messages: collation_info.processed_downward_messages,
+		horizontal_messages: collation_info.horizontal_messages.try_into().expect(
 			"Number of horizontal messages should not be greater than `MAX_HORIZONTAL_MESSAGE_NUM`",
-		);
-		let hrmp_watermark = crate::HrmpWatermark::<PSC>::get();
-
-		let head_data =
-			if let Some(custom_head_data) = crate::CustomValidationHeadData::<PSC>::get() {
-				HeadData(custom_head_data)
-			} else {
-				head_data
-			};
-
-		ValidationResult {
-			head_data,
-			new_validation_code: new_validation_code.map(Into::into),
-			upward_messages,
-			processed_downward_messages,
-			horizontal_messages,
-			hrmp_watermark,
-		}
-	})
+		),
+		hrmp_watermark: collation_info.hrmp_watermark,
+	}
+}
+
+/// Apply the changes of an executed block to the database of its `backend`.
+///
+/// Returns the database, which then contains the state after the given block.
+fn apply_block_changes<B: BlockT, PSC: crate::Config>(
+	backend: TrieBackend<B>,
+	overlay: &mut OverlayedChanges<HashingFor<B>>,
+	header: &B::Header,
+) -> MemoryDB<HashingFor<B>> {
+	let state_version = PSC::Version::get().state_version();
+	let mut changes = overlay
+		.drain_storage_changes(&backend, state_version)
+		.expect("Draining the storage changes of an executed block works; qed");
+	assert!(
+		changes.transaction_storage_root == *header.state_root(),
+		"Invalid storage root of bundled block",
+	);
+
+	let mut db = backend.into_storage();
+	changes
+		.transaction
+		.drain()
+		.into_values()
+		.filter(|(_, rc)| *rc > 0)
+		.for_each(|(value, _)| {
+			db.insert(EMPTY_PREFIX, &value);
+		});
+	db
 }
 
 /// Extract the [`ParachainInherentData`].
@@ -277,10 +357,10 @@ fn validate_validation_data(
 fn run_with_externalities_and_recorder<B: BlockT, R, F: FnOnce() -> R>(
 	backend: &TrieBackend<B>,
 	recorder: &mut SizeOnlyRecorderProvider<HashingFor<B>>,
+	overlay: &mut OverlayedChanges<HashingFor<B>>,
 	execute: F,
 ) -> R {
-	let mut overlay = sp_state_machine::OverlayedChanges::default();
-	let mut ext = Ext::<B>::new(&mut overlay, backend);
+	let mut ext = Ext::<B>::new(overlay, backend);
 	recorder.reset();
 
 	recorder::using(recorder, || set_and_run_with_externalities(&mut ext, || execute()))
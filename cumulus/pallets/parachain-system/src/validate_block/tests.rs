@@ -28,7 +28,8 @@ use cumulus_test_client::{
 };
 use cumulus_test_relay_sproof_builder::RelayStateSproofBuilder;
 use sp_consensus_slots::Slot;
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use sp_runtime::traits::{BlakeTwo256, Block as BlockT, Header as HeaderT};
+use sp_trie::StorageProof;
 
 use std::{env, process::Command};
 
@@ -61,7 +62,13 @@ fn call_validate_block(
 }
 
 fn create_test_client() -> (Client, Header) {
-	let client = TestClientBuilder::new().enable_import_proof_recording().build();
+	create_test_client_with_wasm(None)
+}
+
+fn create_test_client_with_wasm(wasm: Option<&[u8]>) -> (Client, Header) {
+	let mut builder = TestClientBuilder::new().enable_import_proof_recording();
+	builder.genesis_init_mut().wasm = wasm.map(|w| w.to_vec());
+	let client = builder.build();
 
 	let genesis_header = client
 		.header(client.chain_info().genesis_hash)
@@ -186,6 +193,94 @@ fn validate_block_returns_custom_head_data() {
 	assert_eq!(expected_header, res_header);
 }
 
+#[test]
+fn validate_block_works_with_bundled_blocks() {
+	sp_tracing::try_init_simple();
+
+	// Bundled blocks share the relay parent, so the runtime needs to allow multiple blocks per
+	// relay chain slot.
+	let wasm = test_runtime::elastic_scaling_multi_block_slot::WASM_BINARY
+		.expect("You need to build the WASM binaries to run the tests!");
+	let (client, genesis_head) = create_test_client_with_wasm(Some(wasm));
+
+	let mut sproof_builder = RelayStateSproofBuilder::default();
+	sproof_builder.para_id = test_runtime::PARACHAIN_ID.into();
+	sproof_builder.included_para_head = Some(HeadData(genesis_head.encode()));
+
+	let timestamp = std::time::SystemTime::now()
+		.duration_since(std::time::SystemTime::UNIX_EPOCH)
+		.expect("Time is always after UNIX_EPOCH; qed")
+		.as_millis() as u64;
+
+	let mut parent_head = genesis_head.clone();
+	let mut blocks = Vec::new();
+	let mut proofs = Vec::new();
+	let mut relay_parent_storage_root = Default::default();
+
+	for extrinsic in [transfer(&client, Alice, Bob, 69), transfer(&client, Bob, Charlie, 100)] {
+		let validation_data = PersistedValidationData {
+			relay_parent_number: 1,
+			parent_head: parent_head.encode().into(),
+			..Default::default()
+		};
+
+		let cumulus_test_client::BlockBuilderAndSupportData {
+			mut block_builder,
+			persisted_validation_data,
+			slot,
+		} = client.init_block_builder_with_timestamp(
+			parent_head.hash(),
+			Some(validation_data),
+			sproof_builder.clone(),
+			timestamp,
+		);
+		block_builder.push(extrinsic).unwrap();
+
+		let block = block_builder.build_parachain_block(*parent_head.state_root());
+		let block = seal_block(block, slot, &client);
+		let proof = block.storage_proof().clone();
+		let block = block.into_block();
+
+		proofs.push(
+			proof
+				.to_storage_proof::<BlakeTwo256>(Some(parent_head.state_root()))
+				.expect("Decodes the compact proof")
+				.0,
+		);
+		futures::executor::block_on(client.import_as_best(BlockOrigin::Own, block.clone()))
+			.unwrap();
+
+		parent_head = block.header().clone();
+		relay_parent_storage_root = persisted_validation_data.relay_parent_storage_root;
+		blocks.push(block);
+	}
+
+	let storage_proof = StorageProof::merge(proofs)
+		.into_compact_proof::<BlakeTwo256>(*genesis_head.state_root())
+		.expect("Creates the compact proof");
+	let block_data = ParachainBlockData::new_bundle(blocks, storage_proof);
+	assert_eq!(
+		ParachainBlockData::<Block>::decode(&mut &block_data.encode()[..])
+			.unwrap()
+			.blocks()
+			.len(),
+		2,
+	);
+
+	let res = cumulus_test_client::validate_block(
+		ValidationParams {
+			block_data: BlockData(block_data.encode()),
+			parent_head: HeadData(genesis_head.encode()),
+			relay_parent_number: 1,
+			relay_parent_storage_root,
+		},
+		wasm,
+	)
+	.expect("Calls `validate_block`");
+
+	assert_eq!(Header::decode(&mut &res.head_data.0[..]).expect("Decodes `Header`."), parent_head);
+}
+
 #[test]
 fn validate_block_invalid_parent_hash() {
 	sp_tracing::try_init_simple();
@@ -194,7 +289,8 @@ fn validate_block_invalid_parent_hash() {
 		let (client, parent_head) = create_test_client();
 		let TestBlockData { block, validation_data, .. } =
 			build_block_with_witness(&client, Vec::new(), parent_head.clone(), Default::default());
-		let (mut header, extrinsics, witness) = block.deconstruct();
+		let witness = block.storage_proof().clone();
+		let (mut header, extrinsics) = block.into_block().deconstruct();
 		header.set_parent_hash(Hash::from_low_u64_be(1));
 
 		let block_data = ParachainBlockData::new(header, extrinsics, witness);
@@ -245,7 +341,8 @@ fn check_inherents_are_unsigned_and_before_all_other_extrinsics() {
 		let TestBlockData { block, validation_data, .. } =
 			build_block_with_witness(&client, Vec::new(), parent_head.clone(), Default::default());
 
-		let (header, mut extrinsics, proof) = block.deconstruct();
+		let proof = block.storage_proof().clone();
+		let (header, mut extrinsics) = block.into_block().deconstruct();
 
 		extrinsics.insert(0, transfer(&client, Alice, Bob, 69));
 
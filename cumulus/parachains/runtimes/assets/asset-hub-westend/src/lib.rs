@@ -47,7 +47,10 @@ use frame_support::{
 		fungible,
 		fungible::HoldConsideration,
 		fungibles,
-		tokens::{imbalance::ResolveAssetTo, nonfungibles_v2::Inspect},
+		tokens::{
+			imbalance::ResolveAssetTo,
+			nonfungibles_v2::{Inspect, Royalties},
+		},
 		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU64, ConstU8,
 		ConstantStoragePrice, Equals, InstanceFilter, Nothing, TransformOrigin,
 	},
@@ -440,7 +443,10 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LPFee = ConstU32<3>;
 	type PalletId = AssetConversionPalletId;
 	type MaxSwapPathLength = ConstU32<3>;
+	type PriceObservationPeriod = ConstU32<{ 10 * MINUTES }>;
+	type MaxPriceObservations = ConstU32<144>;
 	type MintMinLiquidity = ConstU128<100>;
+	type PoolCurveOrigin = EnsureRoot<AccountId>;
 	type WeightInfo = weights::pallet_asset_conversion::WeightInfo<Runtime>;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = assets_common::benchmarks::AssetPairFactory<
@@ -582,6 +588,7 @@ impl pallet_multisig::Config for Runtime {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = MaxSignatories;
+	type ProposalLifetime = ConstU32<{ 7 * DAYS }>;
 	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
 }
@@ -1027,6 +1034,7 @@ impl pallet_nfts::Config for Runtime {
 	type MaxTips = ConstU32<10>;
 	type MaxDeadlineDuration = NftsMaxDeadlineDuration;
 	type MaxAttributesPerCall = ConstU32<10>;
+	type MaxRoyaltyRecipients = ConstU32<10>;
 	type Features = NftsPalletFeatures;
 	type OffchainSignature = Signature;
 	type OffchainPublic = <Signature as Verify>::Signer;
@@ -1634,6 +1642,13 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_nfts_runtime_api::NftsRoyaltiesApi<Block, AccountId, u32, u32, Balance> for Runtime {
+		fn royalties(collection: u32, item: u32, sale_price: Balance) -> Vec<(AccountId, Balance)> {
+			<Nfts as Royalties<AccountId, Balance>>::royalties(&collection, &item, &sale_price)
+		}
+	}
+
+	#[api_version(2)]
 	impl pallet_asset_conversion::AssetConversionApi<
 		Block,
 		Balance,
@@ -1651,6 +1666,10 @@ impl_runtime_apis! {
 		fn get_reserves(asset1: xcm::v5::Location, asset2: xcm::v5::Location) -> Option<(Balance, Balance)> {
 			AssetConversion::get_reserves(asset1, asset2).ok()
 		}
+
+		fn get_twap(asset1: xcm::v5::Location, asset2: xcm::v5::Location, window: u32) -> Option<sp_runtime::FixedU128> {
+			AssetConversion::get_twap(&asset1, &asset2, window)
+		}
 	}
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
@@ -335,4 +335,5 @@ impl pallet_treasury::Config<FellowshipTreasuryInstance> for Runtime {
 		ConstU32<1000>,
 	>;
 	type BlockNumberProvider = crate::System;
+	type BudgetOrigin = EnsureRoot<AccountId>;
 }
@@ -261,6 +261,7 @@ impl pallet_multisig::Config for Runtime {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = ConstU32<100>;
+	type ProposalLifetime = ConstU32<{ 7 * DAYS }>;
 	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
 }
@@ -619,6 +620,10 @@ parameter_types! {
 	pub const MaxScheduledPerBlock: u32 = 200;
 }
 
+parameter_types! {
+	pub const MaxRecurringTasks: u32 = 100;
+}
+
 impl pallet_scheduler::Config for Runtime {
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeEvent = RuntimeEvent;
@@ -631,6 +636,8 @@ impl pallet_scheduler::Config for Runtime {
 	type OriginPrivilegeCmp = EqualOrGreatestRootCmp;
 	type Preimages = Preimage;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
+	type TimeProvider = Timestamp;
+	type MaxRecurringTasks = MaxRecurringTasks;
 }
 
 parameter_types! {
@@ -977,6 +984,28 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_scheduler::SchedulerApi<Block, BlockNumber, u64> for Runtime {
+		fn next_execution(
+			id: frame_support::traits::schedule::v3::TaskName,
+		) -> Option<pallet_scheduler::NextExecution<BlockNumber, u64>> {
+			Scheduler::next_execution(id)
+		}
+
+		fn recurring_tasks() -> Vec<(frame_support::traits::schedule::v3::TaskName, u64)> {
+			Scheduler::recurring_tasks()
+		}
+	}
+
+	impl pallet_treasury::TreasuryApi<Block, OriginCaller, VersionedLocatableAsset, Balance> for Runtime {
+		fn remaining_budget(origin: OriginCaller, asset_kind: VersionedLocatableAsset) -> Option<Balance> {
+			FellowshipTreasury::remaining_budget(&origin, &asset_kind)
+		}
+
+		fn budgets_of(origin: OriginCaller) -> Vec<(VersionedLocatableAsset, Balance)> {
+			FellowshipTreasury::budgets_of(&origin)
+		}
+	}
+
 	impl xcm_runtime_apis::fees::XcmPaymentApi<Block> for Runtime {
 		fn query_acceptable_payment_assets(xcm_version: xcm::Version) -> Result<Vec<VersionedAssetId>, XcmPaymentApiError> {
 			let acceptable_assets = vec![AssetId(xcm_config::WndLocation::get())];
@@ -56,14 +56,13 @@ impl<T: frame_system::Config> pallet_treasury::WeightInfo for WeightInfo<T> {
 	/// Proof: `FellowshipTreasury::Approvals` (`max_values`: Some(1), `max_size`: Some(402), added: 897, mode: `MaxEncodedLen`)
 	/// Storage: `FellowshipTreasury::Proposals` (r:0 w:1)
 	/// Proof: `FellowshipTreasury::Proposals` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `FellowshipTreasury::SpendBudgets` (r:1 w:0)
+	/// Proof: `FellowshipTreasury::SpendBudgets` (`max_values`: None, `max_size`: Some(1900), added: 4375, mode: `MaxEncodedLen`)
 	fn spend_local() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `76`
-		//  Estimated: `1887`
-		// Minimum execution time: 12_879_000 picoseconds.
+		// Not benchmarked yet: placeholder until this file is regenerated.
 		Weight::from_parts(13_346_000, 0)
-			.saturating_add(Weight::from_parts(0, 1887))
-			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(Weight::from_parts(0, 5365))
+			.saturating_add(T::DbWeight::get().reads(3))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
 	/// Storage: `FellowshipTreasury::Approvals` (r:1 w:1)
@@ -105,15 +104,16 @@ impl<T: frame_system::Config> pallet_treasury::WeightInfo for WeightInfo<T> {
 	/// Proof: `FellowshipTreasury::SpendCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
 	/// Storage: `FellowshipTreasury::Spends` (r:0 w:1)
 	/// Proof: `FellowshipTreasury::Spends` (`max_values`: None, `max_size`: Some(1853), added: 4328, mode: `MaxEncodedLen`)
+	/// Storage: `FellowshipTreasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `FellowshipTreasury::SpendBudgets` (`max_values`: None, `max_size`: Some(1900), added: 4375, mode: `MaxEncodedLen`)
+	/// Storage: `FellowshipTreasury::BudgetedSpends` (r:0 w:1)
+	/// Proof: `FellowshipTreasury::BudgetedSpends` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
 	fn spend() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `219`
-		//  Estimated: `4703`
-		// Minimum execution time: 24_150_000 picoseconds.
+		// Not benchmarked yet: placeholder until this file is regenerated.
 		Weight::from_parts(24_739_000, 0)
-			.saturating_add(Weight::from_parts(0, 4703))
-			.saturating_add(T::DbWeight::get().reads(3))
-			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(Weight::from_parts(0, 5365))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: `FellowshipTreasury::Spends` (r:1 w:1)
 	/// Proof: `FellowshipTreasury::Spends` (`max_values`: None, `max_size`: Some(1853), added: 4328, mode: `MaxEncodedLen`)
@@ -147,25 +147,45 @@ impl<T: frame_system::Config> pallet_treasury::WeightInfo for WeightInfo<T> {
 	/// Proof: `FellowshipTreasury::Spends` (`max_values`: None, `max_size`: Some(1853), added: 4328, mode: `MaxEncodedLen`)
 	/// Storage: `PolkadotXcm::Queries` (r:1 w:1)
 	/// Proof: `PolkadotXcm::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `FellowshipTreasury::BudgetedSpends` (r:1 w:1)
+	/// Proof: `FellowshipTreasury::BudgetedSpends` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
+	/// Storage: `FellowshipTreasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `FellowshipTreasury::SpendBudgets` (`max_values`: None, `max_size`: Some(1900), added: 4375, mode: `MaxEncodedLen`)
 	fn check_status() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `240`
-		//  Estimated: `5318`
-		// Minimum execution time: 25_460_000 picoseconds.
+		// Not benchmarked yet: placeholder until this file is regenerated.
 		Weight::from_parts(26_237_000, 0)
-			.saturating_add(Weight::from_parts(0, 5318))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(Weight::from_parts(0, 5365))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: `FellowshipTreasury::Spends` (r:1 w:1)
 	/// Proof: `FellowshipTreasury::Spends` (`max_values`: None, `max_size`: Some(1853), added: 4328, mode: `MaxEncodedLen`)
+	/// Storage: `FellowshipTreasury::BudgetedSpends` (r:1 w:1)
+	/// Proof: `FellowshipTreasury::BudgetedSpends` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
+	/// Storage: `FellowshipTreasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `FellowshipTreasury::SpendBudgets` (`max_values`: None, `max_size`: Some(1900), added: 4375, mode: `MaxEncodedLen`)
 	fn void_spend() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `212`
-		//  Estimated: `5318`
-		// Minimum execution time: 15_357_000 picoseconds.
+		// Not benchmarked yet: placeholder until this file is regenerated.
 		Weight::from_parts(15_787_000, 0)
-			.saturating_add(Weight::from_parts(0, 5318))
+			.saturating_add(Weight::from_parts(0, 5365))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `FellowshipTreasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `FellowshipTreasury::SpendBudgets` (`max_values`: None, `max_size`: Some(1900), added: 4375, mode: `MaxEncodedLen`)
+	fn set_spend_budget() -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(9_867_000, 0)
+			.saturating_add(Weight::from_parts(0, 5365))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `FellowshipTreasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `FellowshipTreasury::SpendBudgets` (`max_values`: None, `max_size`: Some(1900), added: 4375, mode: `MaxEncodedLen`)
+	fn remove_spend_budget() -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(10_683_000, 0)
+			.saturating_add(Weight::from_parts(0, 5365))
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
@@ -576,4 +576,41 @@ impl<T: frame_system::Config> pallet_identity::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(3))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `Identity::Registrars` (r:1 w:0)
+	/// Proof: `Identity::Registrars` (`max_values`: Some(1), `max_size`: Some(1141), added: 1636, mode: `MaxEncodedLen`)
+	/// Storage: `Identity::IdentityOf` (r:1 w:1)
+	/// Proof: `Identity::IdentityOf` (`max_values`: None, `max_size`: Some(7538), added: 10013, mode: `MaxEncodedLen`)
+	/// Storage: `Identity::AttestationNonce` (r:1 w:1)
+	/// Proof: `Identity::AttestationNonce` (`max_values`: None, `max_size`: Some(60), added: 2535, mode: `MaxEncodedLen`)
+	/// Storage: `Identity::JudgementExpiry` (r:1 w:1)
+	/// Proof: `Identity::JudgementExpiry` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Identity::ExpiringJudgements` (r:2 w:2)
+	/// Proof: `Identity::ExpiringJudgements` (`max_values`: None, `max_size`: Some(3614), added: 6089, mode: `MaxEncodedLen`)
+	/// The range of component `r` is `[1, 19]`.
+	fn submit_attestation(r: u32, ) -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(155_902_318, 0)
+			.saturating_add(Weight::from_parts(0, 13168))
+			.saturating_add(Weight::from_parts(104_811, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: `Identity::ExpiringJudgements` (r:1 w:1)
+	/// Proof: `Identity::ExpiringJudgements` (`max_values`: None, `max_size`: Some(3614), added: 6089, mode: `MaxEncodedLen`)
+	/// Storage: `Identity::JudgementExpiry` (r:100 w:100)
+	/// Proof: `Identity::JudgementExpiry` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Identity::IdentityOf` (r:100 w:100)
+	/// Proof: `Identity::IdentityOf` (`max_values`: None, `max_size`: Some(7538), added: 10013, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 100]`.
+	fn expire_judgements(n: u32, ) -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(4_502_000, 0)
+			.saturating_add(Weight::from_parts(0, 7079))
+			.saturating_add(Weight::from_parts(31_902_116, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 10013).saturating_mul(n.into()))
+	}
 }
@@ -649,7 +649,10 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LPFee = ConstU32<3>;
 	type PalletId = AssetConversionPalletId;
 	type MaxSwapPathLength = ConstU32<3>;
+	type PriceObservationPeriod = ConstU32<{ 10 * MINUTES }>;
+	type MaxPriceObservations = ConstU32<144>;
 	type MintMinLiquidity = ConstU128<100>;
+	type PoolCurveOrigin = EnsureRoot<AccountId>;
 	type WeightInfo = ();
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = assets_common::benchmarks::AssetPairFactory<
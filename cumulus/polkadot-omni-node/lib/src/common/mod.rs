@@ -112,4 +112,7 @@ pub struct NodeExtraArgs {
 
 	/// If set, each `PoV` build by the node will be exported to this folder.
 	pub export_pov: Option<PathBuf>,
+
+	/// The number of consecutive blocks the slot-based collator bundles into each `PoV`.
+	pub blocks_per_pov: u32,
 }
@@ -303,6 +303,7 @@ pub(crate) trait NodeSpec: BaseNodeSpec {
 					spawn_handle: task_manager.spawn_handle(),
 					relay_chain_interface: relay_chain_interface.clone(),
 					import_queue: params.import_queue,
+					state_sync_store: Some(client.clone()),
 					sybil_resistance_level: Self::SYBIL_RESISTANCE,
 				})
 				.await?;
@@ -31,8 +31,9 @@ use crate::{
 	},
 	nodes::DynNodeSpecExt,
 };
-use cumulus_client_collator::service::{
-	CollatorService, ServiceInterface as CollatorServiceInterface,
+use cumulus_client_collator::{
+	headroom::Metrics as HeadroomMetrics,
+	service::{CollatorService, ServiceInterface as CollatorServiceInterface},
 };
 #[docify::export(slot_based_colator_import)]
 use cumulus_client_consensus_aura::collators::slot_based::{
@@ -339,7 +340,8 @@ where
 			Arc::new(task_manager.spawn_handle()),
 			announce_block,
 			client.clone(),
-		);
+		)
+		.with_metrics(prometheus_registry.map(HeadroomMetrics::register).transpose()?);
 
 		let client_for_aura = client.clone();
 		let params = SlotBasedParams {
@@ -358,6 +360,7 @@ where
 			proposer,
 			collator_service,
 			authoring_duration: Duration::from_millis(2000),
+			blocks_per_pov: node_extra_args.blocks_per_pov,
 			reinitialize: false,
 			slot_offset: Duration::from_secs(1),
 			block_import_handle,
@@ -462,7 +465,8 @@ where
 			Arc::new(task_manager.spawn_handle()),
 			announce_block,
 			client.clone(),
-		);
+		)
+		.with_metrics(prometheus_registry.map(HeadroomMetrics::register).transpose()?);
 
 		let params = aura::ParamsWithExport {
 			export_pov: node_extra_args.export_pov,
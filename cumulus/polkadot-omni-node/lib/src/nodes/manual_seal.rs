@@ -117,6 +117,7 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 				net_config,
 				block_announce_validator_builder: None,
 				warp_sync_config: None,
+				state_sync_store: Some(client.clone()),
 				block_relay: None,
 				metrics,
 			})?;
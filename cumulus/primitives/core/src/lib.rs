@@ -22,7 +22,9 @@ extern crate alloc;
 
 use alloc::vec::Vec;
 use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
-use polkadot_parachain_primitives::primitives::HeadData;
+use polkadot_parachain_primitives::primitives::{
+	HeadData, MAX_HORIZONTAL_MESSAGE_NUM, MAX_UPWARD_MESSAGE_NUM,
+};
 use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
 
@@ -198,48 +200,84 @@ pub enum ServiceQuality {
 	Fast,
 }
 
+/// The prefix of the encoding of [`ParachainBlockData`] that bundles multiple blocks.
+///
+/// A PoV with a single block is encoded without any prefix, starting with the header of the
+/// block, to stay compatible with runtimes that don't support bundled blocks. Bundles are
+/// distinguished by this prefix, which a header would need to start with by chance.
+pub const BUNDLED_BLOCKS_PREFIX: [u8; 8] = *b"\xff\xff\xff\xffCMLS";
+
+/// The maximum number of blocks that can be bundled into a single [`ParachainBlockData`].
+pub const MAX_BUNDLED_BLOCKS: u32 = 16;
+
 /// The parachain block that is created by a collator.
 ///
 /// This is send as PoV (proof of validity block) to the relay-chain validators. There it will be
 /// passed to the parachain validation Wasm blob to be validated.
-#[derive(codec::Encode, codec::Decode, Clone)]
+///
+/// It contains one or more consecutive parachain blocks, each building on top of the previous
+/// one, and a single storage proof covering the state accessed by all of them. Bundling blocks
+/// this way lets them share the trie nodes they all access, instead of proving them once per
+/// block.
+#[derive(Clone)]
 pub struct ParachainBlockData<B: BlockT> {
-	/// The header of the parachain block.
-	header: B::Header,
-	/// The extrinsics of the parachain block.
-	extrinsics: alloc::vec::Vec<B::Extrinsic>,
-	/// The data that is required to emulate the storage accesses executed by all extrinsics.
+	/// The parachain blocks, in the order they are built on top of each other.
+	blocks: Vec<B>,
+	/// The data that is required to emulate the storage accesses executed by all extrinsics of
+	/// all blocks.
 	storage_proof: sp_trie::CompactProof,
 }
 
 impl<B: BlockT> ParachainBlockData<B> {
-	/// Creates a new instance of `Self`.
+	/// Creates a new instance of `Self` containing a single block.
 	pub fn new(
 		header: <B as BlockT>::Header,
-		extrinsics: alloc::vec::Vec<<B as BlockT>::Extrinsic>,
+		extrinsics: Vec<<B as BlockT>::Extrinsic>,
 		storage_proof: sp_trie::CompactProof,
 	) -> Self {
-		Self { header, extrinsics, storage_proof }
+		Self { blocks: alloc::vec![B::new(header, extrinsics)], storage_proof }
 	}
 
-	/// Convert `self` into the stored block.
-	pub fn into_block(self) -> B {
-		B::new(self.header, self.extrinsics)
+	/// Creates a new instance of `Self` bundling the given consecutive blocks.
+	///
+	/// The `storage_proof` needs to cover the state accessed by all blocks, starting at the
+	/// state of the parent of the first block.
+	///
+	/// # Panics
+	///
+	/// Panics if `blocks` is empty.
+	pub fn new_bundle(blocks: Vec<B>, storage_proof: sp_trie::CompactProof) -> Self {
+		assert!(!blocks.is_empty(), "A `ParachainBlockData` contains at least one block");
+		Self { blocks, storage_proof }
 	}
 
-	/// Convert `self` into the stored header.
-	pub fn into_header(self) -> B::Header {
-		self.header
+	/// Returns the blocks.
+	pub fn blocks(&self) -> &[B] {
+		&self.blocks
+	}
+
+	/// Convert `self` into the blocks.
+	pub fn into_blocks(self) -> Vec<B> {
+		self.blocks
 	}
 
-	/// Returns the header.
+	/// Convert `self` into the last block.
+	///
+	/// Any other bundled blocks are dropped, use [`Self::into_blocks`] to keep them.
+	pub fn into_block(mut self) -> B {
+		self.blocks.pop().expect("There is at least one block; qed")
+	}
+
+	/// Returns the header of the last block.
+	///
+	/// This is the block whose head data is the result of validating the PoV.
 	pub fn header(&self) -> &B::Header {
-		&self.header
+		self.blocks.last().expect("There is at least one block; qed").header()
 	}
 
-	/// Returns the extrinsics.
-	pub fn extrinsics(&self) -> &[B::Extrinsic] {
-		&self.extrinsics
+	/// Convert `self` into the header of the last block.
+	pub fn into_header(self) -> B::Header {
+		self.into_block().deconstruct().0
 	}
 
 	/// Returns the [`CompactProof`](sp_trie::CompactProof).
@@ -248,8 +286,67 @@ impl<B: BlockT> ParachainBlockData<B> {
 	}
 
 	/// Deconstruct into the inner parts.
-	pub fn deconstruct(self) -> (B::Header, alloc::vec::Vec<B::Extrinsic>, sp_trie::CompactProof) {
-		(self.header, self.extrinsics, self.storage_proof)
+	pub fn deconstruct(self) -> (Vec<B>, sp_trie::CompactProof) {
+		(self.blocks, self.storage_proof)
+	}
+}
+
+impl<B: BlockT> Encode for ParachainBlockData<B> {
+	fn encode_to<T: codec::Output + ?Sized>(&self, dest: &mut T) {
+		match &self.blocks[..] {
+			[block] => {
+				block.header().encode_to(dest);
+				block.extrinsics().encode_to(dest);
+			},
+			blocks => {
+				dest.write(&BUNDLED_BLOCKS_PREFIX);
+				blocks.encode_to(dest);
+			},
+		}
+
+		self.storage_proof.encode_to(dest);
+	}
+}
+
+impl<B: BlockT> Decode for ParachainBlockData<B> {
+	fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+		let mut prefix = [0u8; BUNDLED_BLOCKS_PREFIX.len()];
+		input.read(&mut prefix)?;
+
+		let blocks = if prefix == BUNDLED_BLOCKS_PREFIX {
+			let blocks = Vec::<B>::decode(input)?;
+			if blocks.is_empty() {
+				return Err("`ParachainBlockData` without blocks".into())
+			}
+			blocks
+		} else {
+			let mut input = PrefixedInput { prefix: &prefix, input };
+			let header = B::Header::decode(&mut input)?;
+			let extrinsics = Vec::<B::Extrinsic>::decode(&mut input)?;
+			alloc::vec![B::new(header, extrinsics)]
+		};
+
+		Ok(Self { blocks, storage_proof: sp_trie::CompactProof::decode(input)? })
+	}
+}
+
+/// An [`Input`](codec::Input) that yields the already read `prefix` before the rest of `input`.
+struct PrefixedInput<'a, I> {
+	prefix: &'a [u8],
+	input: &'a mut I,
+}
+
+impl<I: codec::Input> codec::Input for PrefixedInput<'_, I> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, codec::Error> {
+		Ok(self.input.remaining_len()?.map(|len| len + self.prefix.len()))
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), codec::Error> {
+		let from_prefix = into.len().min(self.prefix.len());
+		into[..from_prefix].copy_from_slice(&self.prefix[..from_prefix]);
+		self.prefix = &self.prefix[from_prefix..];
+
+		self.input.read(&mut into[from_prefix..])
 	}
 }
 
@@ -385,6 +482,128 @@ pub struct CollationInfo {
 	pub head_data: HeadData,
 }
 
+/// The per-candidate limits of the relay chain on the messages sent by a collation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MessageLimits {
+	/// The maximum number of upward messages, not counting UMP signals.
+	pub max_upward_message_num: u32,
+	/// The maximum number of horizontal messages.
+	pub max_horizontal_message_num: u32,
+}
+
+impl Default for MessageLimits {
+	/// The hard limits of the relay chain, which are never exceeded by the host configuration.
+	fn default() -> Self {
+		Self {
+			max_upward_message_num: MAX_UPWARD_MESSAGE_NUM,
+			max_horizontal_message_num: MAX_HORIZONTAL_MESSAGE_NUM,
+		}
+	}
+}
+
+impl From<&AbridgedHostConfiguration> for MessageLimits {
+	fn from(config: &AbridgedHostConfiguration) -> Self {
+		Self {
+			max_upward_message_num: config.max_upward_message_num_per_candidate,
+			max_horizontal_message_num: config.hrmp_max_message_num_per_candidate,
+		}
+	}
+}
+
+/// The reason why the collation info of two blocks can not be merged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeError {
+	/// Both blocks sent a horizontal message to the given recipient, while the relay chain only
+	/// accepts one message per recipient and candidate.
+	DuplicateHrmpRecipient(ParaId),
+	/// The merged upward messages exceed the limit per candidate.
+	TooManyUpwardMessages,
+	/// The merged horizontal messages exceed the limit per candidate.
+	TooManyHorizontalMessages,
+}
+
+impl CollationInfo {
+	/// Merge the collation info of the next bundled block into `self`.
+	///
+	/// This results in the collation info of both blocks validated as one candidate. Upward and
+	/// horizontal messages are concatenated and the processed downward messages are summed up,
+	/// while the HRMP watermark, the head data and the UMP signals are taken from the next block.
+	///
+	/// Returns an error and leaves `self` untouched if the merged messages would be rejected by
+	/// the relay chain under the given `limits`.
+	pub fn merge(&mut self, next: CollationInfo, limits: &MessageLimits) -> Result<(), MergeError> {
+		if let Some(recipient) = next
+			.horizontal_messages
+			.iter()
+			.map(|m| m.recipient)
+			.find(|r| self.horizontal_messages.iter().any(|m| m.recipient == *r))
+		{
+			return Err(MergeError::DuplicateHrmpRecipient(recipient))
+		}
+
+		let num_horizontal = self.horizontal_messages.len() + next.horizontal_messages.len();
+		if num_horizontal > limits.max_horizontal_message_num as usize ||
+			num_horizontal > MAX_HORIZONTAL_MESSAGE_NUM as usize
+		{
+			return Err(MergeError::TooManyHorizontalMessages)
+		}
+
+		let separator = relay_chain::vstaging::UMP_SEPARATOR;
+		let num_upward = self.upward_messages.iter().take_while(|m| **m != separator).count();
+		let num_next_upward =
+			relay_chain::vstaging::skip_ump_signals(next.upward_messages.iter()).count();
+		if num_upward + num_next_upward > limits.max_upward_message_num as usize ||
+			num_upward + next.upward_messages.len() > MAX_UPWARD_MESSAGE_NUM as usize
+		{
+			return Err(MergeError::TooManyUpwardMessages)
+		}
+
+		self.horizontal_messages.extend(next.horizontal_messages);
+		self.horizontal_messages.sort_by_key(|m| m.recipient);
+
+		self.upward_messages.truncate(num_upward);
+		self.upward_messages.extend(next.upward_messages);
+
+		self.new_validation_code = next.new_validation_code.or(self.new_validation_code.take());
+		self.processed_downward_messages += next.processed_downward_messages;
+		self.hrmp_watermark = next.hrmp_watermark;
+		self.head_data = next.head_data;
+
+		Ok(())
+	}
+}
+
+/// The resources consumed by a parachain block and the relay chain limits they are checked
+/// against.
+///
+/// Collators use this to forecast whether a collation will be accepted by the relay chain before
+/// submitting it.
+#[derive(Clone, Debug, codec::Decode, codec::Encode, PartialEq, Eq, TypeInfo)]
+pub struct CollationResources {
+	/// The `ref_time` weight consumed by the block, in picoseconds.
+	pub consumed_ref_time: u64,
+	/// The `proof_size` weight consumed by the block, in bytes.
+	pub consumed_proof_size: u64,
+	/// The maximum PoV size accepted by the relay chain for the block.
+	pub max_pov_size: u32,
+	/// The maximum head data size accepted by the relay chain.
+	pub max_head_data_size: u32,
+	/// The maximum number of upward messages accepted by the relay chain per candidate.
+	pub max_upward_message_num: u32,
+	/// The maximum number of horizontal messages accepted by the relay chain per candidate.
+	pub max_horizontal_message_num: u32,
+}
+
+impl CollationResources {
+	/// The limits on the messages of a candidate.
+	pub fn message_limits(&self) -> MessageLimits {
+		MessageLimits {
+			max_upward_message_num: self.max_upward_message_num,
+			max_horizontal_message_num: self.max_horizontal_message_num,
+		}
+	}
+}
+
 sp_api::decl_runtime_apis! {
 	/// Runtime api to collect information about a collation.
 	#[api_version(2)]
@@ -397,6 +616,18 @@ sp_api::decl_runtime_apis! {
 		/// The given `header` is the header of the built block for that
 		/// we are collecting the collation info for.
 		fn collect_collation_info(header: &Block::Header) -> CollationInfo;
+		/// Collect the resources consumed by the block and the relay chain limits they are
+		/// bound by.
+		///
+		/// Returns `None` if the relay chain limits are not known at this block.
+		#[api_version(3)]
+		fn collation_resources() -> Option<CollationResources>;
+		/// The maximum number of blocks `validate_block` accepts bundled into a single PoV.
+		///
+		/// Collators must not bundle blocks for runtimes that don't implement this, as they can't
+		/// decode bundled PoVs.
+		#[api_version(4)]
+		fn max_blocks_per_pov() -> u32;
 	}
 
 	/// Runtime api used to select the core for which the next block will be built.
@@ -405,3 +636,59 @@ sp_api::decl_runtime_apis! {
 		fn core_selector() -> (CoreSelector, ClaimQueueOffset);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+
+	fn collation_info(upward: usize, recipients: &[u32]) -> CollationInfo {
+		CollationInfo {
+			upward_messages: vec![vec![1]; upward],
+			horizontal_messages: recipients
+				.iter()
+				.map(|r| OutboundHrmpMessage { recipient: (*r).into(), data: vec![2] })
+				.collect(),
+			new_validation_code: None,
+			processed_downward_messages: 1,
+			hrmp_watermark: 1,
+			head_data: HeadData(vec![1]),
+		}
+	}
+
+	#[test]
+	fn merge_enforces_message_limits() {
+		let limits = MessageLimits { max_upward_message_num: 3, max_horizontal_message_num: 2 };
+
+		let mut info = collation_info(2, &[1]);
+		info.upward_messages.push(relay_chain::vstaging::UMP_SEPARATOR);
+		info.upward_messages.push(vec![3]);
+		let original = info.clone();
+
+		assert_eq!(
+			info.merge(collation_info(2, &[]), &limits),
+			Err(MergeError::TooManyUpwardMessages)
+		);
+		assert_eq!(
+			info.merge(collation_info(0, &[2, 3]), &limits),
+			Err(MergeError::TooManyHorizontalMessages)
+		);
+		assert_eq!(
+			info.merge(collation_info(0, &[1]), &limits),
+			Err(MergeError::DuplicateHrmpRecipient(1.into()))
+		);
+		assert_eq!(info, original);
+
+		// The UMP signals of the first block are dropped and don't count towards the limit.
+		let mut next = collation_info(1, &[2]);
+		next.upward_messages.push(relay_chain::vstaging::UMP_SEPARATOR);
+		next.upward_messages.push(vec![4]);
+		assert_eq!(info.merge(next, &limits), Ok(()));
+		assert_eq!(
+			info.upward_messages,
+			vec![vec![1], vec![1], vec![1], relay_chain::vstaging::UMP_SEPARATOR, vec![4]]
+		);
+		assert_eq!(info.horizontal_messages.len(), 2);
+		assert_eq!(info.processed_downward_messages, 2);
+	}
+}
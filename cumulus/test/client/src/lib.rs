@@ -39,7 +39,11 @@ use sp_consensus_aura::{AuraApi, Slot};
 use sp_core::Pair;
 use sp_io::TestExternalities;
 use sp_keystore::testing::MemoryKeystore;
-use sp_runtime::{generic::Era, traits::Header, BuildStorage, MultiAddress, SaturatedConversion};
+use sp_runtime::{
+	generic::Era,
+	traits::{Block as BlockT, Header},
+	BuildStorage, MultiAddress, SaturatedConversion,
+};
 use std::sync::Arc;
 pub use substrate_test_client::*;
 
@@ -72,6 +76,8 @@ pub type Client = client::Client<Backend, Executor, Block, runtime::RuntimeApi>;
 #[derive(Default)]
 pub struct GenesisParameters {
 	pub endowed_accounts: Vec<cumulus_test_runtime::AccountId>,
+	/// The runtime code to use instead of the default test runtime.
+	pub wasm: Option<Vec<u8>>,
 }
 
 impl substrate_test_client::GenesisInit for GenesisParameters {
@@ -79,7 +85,9 @@ impl substrate_test_client::GenesisInit for GenesisParameters {
 		cumulus_test_service::chain_spec::get_chain_spec_with_extra_endowed(
 			None,
 			self.endowed_accounts.clone(),
-			cumulus_test_runtime::WASM_BINARY.expect("WASM binary not compiled!"),
+			self.wasm.as_deref().unwrap_or_else(|| {
+				cumulus_test_runtime::WASM_BINARY.expect("WASM binary not compiled!")
+			}),
 		)
 		.build_storage()
 		.expect("Builds test runtime genesis storage")
@@ -231,7 +239,7 @@ fn get_keystore() -> sp_keystore::KeystorePtr {
 	Arc::new(keystore)
 }
 
-/// Given parachain block data and a slot, seal the block with an aura seal. Assumes that the
+/// Given parachain block data and a slot, seal the last block with an aura seal. Assumes that the
 /// authorities of the test runtime are present in the keyring.
 pub fn seal_block(
 	block: ParachainBlockData,
@@ -243,7 +251,9 @@ pub fn seal_block(
 	let expected_author = slot_author::<<AuraId as AppCrypto>::Pair>(parachain_slot, &authorities)
 		.expect("Should be able to find author");
 
-	let (mut header, extrinsics, proof) = block.deconstruct();
+	let (mut blocks, proof) = block.deconstruct();
+	let (mut header, extrinsics) =
+		blocks.pop().expect("There is at least one block; qed").deconstruct();
 	let keystore = get_keystore();
 	let seal_digest = seal::<_, sp_consensus_aura::sr25519::AuthorityPair>(
 		&header.hash(),
@@ -252,5 +262,6 @@ pub fn seal_block(
 	)
 	.expect("Should be able to create seal");
 	header.digest_mut().push(seal_digest);
-	ParachainBlockData::new(header, extrinsics, proof)
+	blocks.push(Block::new(header, extrinsics));
+	ParachainBlockData::new_bundle(blocks, proof)
 }
@@ -559,10 +559,19 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(4)]
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
 		}
+
+		fn collation_resources() -> Option<cumulus_primitives_core::CollationResources> {
+			ParachainSystem::collation_resources()
+		}
+
+		fn max_blocks_per_pov() -> u32 {
+			ParachainSystem::max_blocks_per_pov()
+		}
 	}
 
 	impl cumulus_primitives_core::GetCoreSelectorApi<Block> for Runtime {
@@ -384,6 +384,7 @@ where
 			spawn_handle: task_manager.spawn_handle(),
 			relay_chain_interface: relay_chain_interface.clone(),
 			import_queue: params.import_queue,
+			state_sync_store: Some(client.clone()),
 			sybil_resistance_level: CollatorSybilResistance::Resistant, /* Either Aura that is
 			                                                             * resistant or null that
 			                                                             * is not producing any
@@ -503,6 +504,7 @@ where
 					proposer,
 					collator_service,
 					authoring_duration: Duration::from_millis(2000),
+					blocks_per_pov: 1,
 					reinitialize: false,
 					slot_offset: Duration::from_secs(1),
 					block_import_handle: slot_based_handle,
@@ -871,6 +873,7 @@ pub fn node_config(
 		trie_cache_maximum_size: Some(64 * 1024 * 1024),
 		state_pruning: Some(PruningMode::ArchiveAll),
 		blocks_pruning: BlocksPruning::KeepAll,
+		blocks_retention: Default::default(),
 		chain_spec: spec,
 		executor: ExecutorConfiguration {
 			wasm_method: WasmExecutionMethod::Compiled {
@@ -56,6 +56,108 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Export the availability store data of candidates to a file.
+	ExportAvailability(ExportAvailabilityCmd),
+
+	/// Import availability store data exported from another node.
+	ImportAvailability(ImportAvailabilityCmd),
+
+	/// Replay the approval-voting database and print the approval timeline of every candidate of
+	/// the unfinalized and recently finalized blocks.
+	ReplayApprovals(ReplayApprovalsCmd),
+}
+
+/// The `export-availability` command used to export the availability store data of candidates,
+/// so it can be imported into the availability store of another node.
+///
+/// The node must not be running.
+#[derive(Debug, Parser)]
+pub struct ExportAvailabilityCmd {
+	/// The hash of a candidate to export. Can be given multiple times.
+	#[arg(long = "candidate", value_name = "HASH", conflicts_with_all = ["from", "all"])]
+	pub candidates: Vec<sp_core::H256>,
+
+	/// The first block of a range of blocks to export the included candidates of.
+	#[arg(long, value_name = "NUMBER", requires = "to", conflicts_with = "all")]
+	pub from: Option<u32>,
+
+	/// The last block of a range of blocks to export the included candidates of.
+	#[arg(long, value_name = "NUMBER", requires = "from")]
+	pub to: Option<u32>,
+
+	/// Export all candidates known to the availability store.
+	#[arg(long)]
+	pub all: bool,
+
+	/// The file to write the exported data to.
+	#[arg(long, short, value_name = "PATH")]
+	pub output: PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: sc_cli::PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+impl sc_cli::CliConfiguration for ExportAvailabilityCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&sc_cli::PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// The `import-availability` command used to import availability store data written by the
+/// `export-availability` command of another node.
+///
+/// The node must not be running. Candidates are checked against their receipts in the blocks
+/// they were included in, which must be known to the node. Imported data is kept for as long as
+/// data of finalized candidates, counting from the time of the import.
+#[derive(Debug, Parser)]
+pub struct ImportAvailabilityCmd {
+	/// The file to read the exported data from.
+	#[arg(long, short, value_name = "PATH")]
+	pub input: PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: sc_cli::PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+impl sc_cli::CliConfiguration for ImportAvailabilityCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&sc_cli::PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
 }
 
 #[allow(missing_docs)]
@@ -172,3 +274,43 @@ pub struct Cli {
 	#[clap(flatten)]
 	pub storage_monitor: sc_storage_monitor::StorageMonitorParams,
 }
+
+/// The `replay-approvals` command used to find out why approvals were slow, from the
+/// approval-voting database and the chain database of a validator.
+///
+/// The node must not be running, the command can be run on a copy of the data directory.
+#[derive(Debug, Parser)]
+pub struct ReplayApprovalsCmd {
+	/// The time the replay ends at, in seconds since the unix epoch. This should be the time the
+	/// node was stopped, assignments not followed by an approval before it count as no-shows.
+	///
+	/// Defaults to now.
+	#[arg(long, value_name = "SECONDS")]
+	pub until: Option<u64>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: sc_cli::PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+impl sc_cli::CliConfiguration for ReplayApprovalsCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&sc_cli::PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
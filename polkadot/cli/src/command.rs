@@ -390,7 +390,7 @@ pub fn run() -> Result<()> {
 					cmd.run(config, client.clone(), db, storage).map_err(Error::SubstrateCli)
 				}),
 				BenchmarkCmd::Block(cmd) => runner.sync_run(|mut config| {
-					let (client, _, _, _) = polkadot_service::new_chain_ops(&mut config)?;
+					let (client, _, _, _task_manager) = polkadot_service::new_chain_ops(&mut config)?;
 
 					cmd.run(client.clone()).map_err(Error::SubstrateCli)
 				}),
@@ -409,7 +409,7 @@ pub fn run() -> Result<()> {
 					.map_err(Error::SubstrateCli)
 				}),
 				BenchmarkCmd::Extrinsic(cmd) => runner.sync_run(|mut config| {
-					let (client, _, _, _) = polkadot_service::new_chain_ops(&mut config)?;
+					let (client, _, _, _task_manager) = polkadot_service::new_chain_ops(&mut config)?;
 					let header = client.header(client.info().genesis_hash).unwrap().unwrap();
 					let inherent_data = benchmark_inherent_data(header)
 						.map_err(|e| format!("generating inherent data: {:?}", e))?;
@@ -462,6 +462,57 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			Ok(runner.sync_run(|config| cmd.run::<polkadot_service::Block>(&config))?)
 		},
+		Some(Subcommand::ExportAvailability(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			let selection = match (cmd.all, cmd.from, cmd.to) {
+				(true, _, _) => polkadot_service::AvailabilityExport::All,
+				(false, Some(from), Some(to)) =>
+					polkadot_service::AvailabilityExport::Blocks(from, to),
+				_ => polkadot_service::AvailabilityExport::Candidates(
+					cmd.candidates
+						.iter()
+						.map(|hash| polkadot_service::CandidateHash(*hash))
+						.collect(),
+				),
+			};
+
+			Ok(runner.sync_run(|mut config| {
+				let (client, _, _, _task_manager) = polkadot_service::new_chain_ops(&mut config)?;
+				let exported = polkadot_service::export_availability(
+					&client,
+					&config,
+					selection,
+					&cmd.output,
+				)?;
+				info!("Exported {} candidates to {}", exported, cmd.output.display());
+				Ok::<_, Error>(())
+			})?)
+		},
+		Some(Subcommand::ReplayApprovals(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			let until = match cmd.until {
+				Some(secs) => std::time::Duration::from_secs(secs),
+				None => std::time::SystemTime::now()
+					.duration_since(std::time::UNIX_EPOCH)
+					.unwrap_or_default(),
+			};
+
+			Ok(runner.sync_run(|mut config| {
+				let (client, _, _, _task_manager) = polkadot_service::new_chain_ops(&mut config)?;
+				let replays = polkadot_service::replay_approvals(&client, &config, until)?;
+				print_approval_replays(&replays);
+				Ok::<_, Error>(())
+			})?)
+		},
+		Some(Subcommand::ImportAvailability(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			Ok(runner.sync_run(|mut config| {
+				let (client, _, _, _task_manager) = polkadot_service::new_chain_ops(&mut config)?;
+				let imported = polkadot_service::import_availability(&client, &config, &cmd.input)?;
+				info!("Imported {} candidates from {}", imported, cmd.input.display());
+				Ok::<_, Error>(())
+			})?)
+		},
 	}?;
 
 	#[cfg(feature = "pyroscope")]
@@ -471,3 +522,66 @@ pub fn run() -> Result<()> {
 	}
 	Ok(())
 }
+
+// Ticks are printed relative to the start of the slot of the block, i.e. as delay tranches.
+fn print_approval_replays(replays: &[polkadot_service::approval_replay::BlockReplay]) {
+	for block in replays {
+		println!(
+			"#{} {:?} (session {}, {}): {}",
+			block.block_number,
+			block.block_hash,
+			block.session,
+			if block.finalized { "finalized" } else { "unfinalized" },
+			if block.approved { "approved" } else { "not approved" },
+		);
+
+		let tranche = |tick: Option<u64>| {
+			tick.map(|tick| tick.saturating_sub(block.block_tick).to_string())
+				.unwrap_or_else(|| "?".into())
+		};
+		for candidate in &block.candidates {
+			let approved = match (candidate.approved, candidate.approved_at) {
+				(true, Some(_)) =>
+					format!("approved at tranche {}", tranche(candidate.approved_at)),
+				(true, None) => "approved".into(),
+				(false, _) => "not approved".into(),
+			};
+			println!(
+				"  candidate {:?} on core {}, backed by group {}: {}",
+				candidate.candidate_hash.0,
+				candidate.core_index.0,
+				candidate.backing_group.0,
+				approved,
+			);
+
+			// Approvals whose tick wasn't recorded come first.
+			let mut events = candidate
+				.assignments
+				.iter()
+				.map(|assignment| {
+					(
+						Some(assignment.tick),
+						format!(
+							"assignment of validator {} for tranche {}",
+							assignment.validator_index.0, assignment.tranche,
+						),
+					)
+				})
+				.chain(candidate.approvals.iter().map(|approval| {
+					(approval.tick, format!("approval of validator {}", approval.validator_index.0))
+				}))
+				.collect::<Vec<_>>();
+			events.sort_by_key(|(tick, _)| *tick);
+			for (tick, event) in events {
+				println!("    tranche {:>4}: {}", tranche(tick), event);
+			}
+
+			if !candidate.no_shows.is_empty() {
+				println!(
+					"    no-shows: {:?}",
+					candidate.no_shows.iter().map(|v| v.0).collect::<Vec<_>>()
+				);
+			}
+		}
+	}
+}
@@ -346,11 +346,12 @@ async fn run_main_loop<Context>(
 					FromOrchestra::Communication { msg } => match msg {
 						// The message the approval voting subsystem would've handled.
 						ApprovalVotingParallelMessage::ApprovedAncestor(_, _,_) |
-						ApprovalVotingParallelMessage::GetApprovalSignaturesForCandidate(_, _)  => {
+						ApprovalVotingParallelMessage::GetApprovalSignaturesForCandidate(_, _) |
+						ApprovalVotingParallelMessage::GetApprovalStats(_, _, _) => {
 							to_approval_voting_worker.send_message(
 								msg.try_into().expect(
-									"Message is one of ApprovedAncestor, GetApprovalSignaturesForCandidate
-									 and that can be safely converted to ApprovalVotingMessage; qed"
+									"Message is one of ApprovedAncestor, GetApprovalSignaturesForCandidate,
+									 GetApprovalStats and that can be safely converted to ApprovalVotingMessage; qed"
 								)
 							).await;
 						},
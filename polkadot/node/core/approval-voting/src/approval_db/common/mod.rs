@@ -18,9 +18,14 @@
 use std::sync::Arc;
 
 use codec::{Decode, Encode};
+use polkadot_node_primitives::approval::{time::Tick, v1::DelayTranche};
 use polkadot_node_subsystem::{SubsystemError, SubsystemResult};
 use polkadot_node_subsystem_util::database::{DBTransaction, Database};
-use polkadot_primitives::{BlockNumber, CandidateHash, CandidateIndex, Hash};
+use polkadot_primitives::{
+	BlockNumber, CandidateHash, CandidateIndex, CoreIndex, GroupIndex, Hash, SessionIndex,
+	ValidatorIndex,
+};
+use sp_consensus_slots::Slot;
 
 use crate::{
 	backend::{Backend, BackendWriteOp, V1ReadBackend, V2ReadBackend},
@@ -39,6 +44,50 @@ const STORED_BLOCKS_KEY: &[u8] = b"Approvals_StoredBlocks";
 /// A range from earliest..last block number stored within the DB.
 #[derive(Encode, Decode, Debug, Clone, PartialEq)]
 pub struct StoredBlockRange(pub BlockNumber, pub BlockNumber);
+
+/// Number of finalized blocks whose [`BlockHistory`] is kept.
+pub const HISTORY_DEPTH: BlockNumber = 600;
+
+/// When the approvals of a candidate were received and when it was approved.
+///
+/// Unlike the entries, this isn't versioned: it is only read to explain approvals after the
+/// fact, so it doesn't need to be migrated.
+#[derive(Encode, Decode, Debug, Clone, Default, PartialEq)]
+pub struct ApprovalTimes {
+	/// The ticks the approvals of validators were received at.
+	pub approvals: Vec<(ValidatorIndex, Tick)>,
+	/// The ticks the candidate was approved under blocks at.
+	pub approved: Vec<(Hash, Tick)>,
+}
+
+/// The approval state of a finalized block, kept for [`HISTORY_DEPTH`] blocks after the block
+/// entry has been pruned.
+#[derive(Encode, Decode, Debug, Clone, PartialEq)]
+pub struct BlockHistory {
+	pub block_hash: Hash,
+	pub block_number: BlockNumber,
+	pub parent_hash: Hash,
+	pub session: SessionIndex,
+	pub slot: Slot,
+	pub approved: bool,
+	pub candidates: Vec<CandidateHistory>,
+}
+
+/// The approval state of a candidate under a block of a [`BlockHistory`].
+#[derive(Encode, Decode, Debug, Clone, PartialEq)]
+pub struct CandidateHistory {
+	pub candidate_hash: CandidateHash,
+	pub core_index: CoreIndex,
+	pub backing_group: GroupIndex,
+	/// The assignments received, along with their tranche and the tick they were received at.
+	pub assignments: Vec<(ValidatorIndex, DelayTranche, Tick)>,
+	/// The approvals received, along with the tick they were received at if it was recorded.
+	pub approvals: Vec<(ValidatorIndex, Option<Tick>)>,
+	pub approved: bool,
+	/// The tick the candidate was approved at, if it was recorded.
+	pub approved_at: Option<Tick>,
+}
+
 /// The database config.
 #[derive(Debug, Clone, Copy)]
 pub struct Config {
@@ -100,6 +149,13 @@ impl Backend for DbBackend {
 		load_stored_blocks(&*self.inner, &self.config)
 	}
 
+	fn load_approval_times(
+		&self,
+		candidate_hash: &CandidateHash,
+	) -> SubsystemResult<Option<ApprovalTimes>> {
+		load_approval_times(&*self.inner, &self.config, candidate_hash)
+	}
+
 	/// Atomically write the list of operations, with later operations taking precedence over prior.
 	fn write<I>(&mut self, ops: I) -> SubsystemResult<()>
 	where
@@ -150,6 +206,26 @@ impl Backend for DbBackend {
 				BackendWriteOp::DeleteCandidateEntry(candidate_hash) => {
 					tx.delete(self.config.col_approval_data, &candidate_entry_key(&candidate_hash));
 				},
+				BackendWriteOp::WriteApprovalTimes(candidate_hash, approval_times) => {
+					tx.put_vec(
+						self.config.col_approval_data,
+						&approval_times_key(&candidate_hash),
+						approval_times.encode(),
+					);
+				},
+				BackendWriteOp::DeleteApprovalTimes(candidate_hash) => {
+					tx.delete(self.config.col_approval_data, &approval_times_key(&candidate_hash));
+				},
+				BackendWriteOp::WriteBlockHistory(block_history) => {
+					tx.put_vec(
+						self.config.col_approval_data,
+						&block_history_key(block_history.block_number),
+						block_history.encode(),
+					);
+				},
+				BackendWriteOp::DeleteBlockHistory(block_number) => {
+					tx.delete(self.config.col_approval_data, &block_history_key(block_number));
+				},
 			}
 		}
 
@@ -238,6 +314,26 @@ pub(crate) fn blocks_at_height_key(block_number: BlockNumber) -> [u8; 16] {
 }
 
 /// Return all blocks which have entries in the DB, ascending, by height.
+pub(crate) fn approval_times_key(candidate_hash: &CandidateHash) -> [u8; 46] {
+	const APPROVAL_TIMES_PREFIX: [u8; 14] = *b"Approvals_time";
+
+	let mut key = [0u8; 14 + 32];
+	key[0..14].copy_from_slice(&APPROVAL_TIMES_PREFIX);
+	key[14..][..32].copy_from_slice(candidate_hash.0.as_ref());
+
+	key
+}
+
+pub(crate) fn block_history_key(block_number: BlockNumber) -> [u8; 18] {
+	const BLOCK_HISTORY_PREFIX: [u8; 14] = *b"Approvals_hist";
+
+	let mut key = [0u8; 14 + 4];
+	key[0..14].copy_from_slice(&BLOCK_HISTORY_PREFIX);
+	block_number.using_encoded(|s| key[14..18].copy_from_slice(s));
+
+	key
+}
+
 pub fn load_all_blocks(store: &dyn Database, config: &Config) -> SubsystemResult<Vec<Hash>> {
 	let mut hashes = Vec::new();
 	if let Some(stored_blocks) = load_stored_blocks(store, config)? {
@@ -291,3 +387,21 @@ pub fn load_candidate_entry(
 		.map(|u: Option<CandidateEntry>| u.map(|v| v.into()))
 		.map_err(|e| SubsystemError::with_origin("approval-voting", e))
 }
+
+pub fn load_approval_times(
+	store: &dyn Database,
+	config: &Config,
+	candidate_hash: &CandidateHash,
+) -> SubsystemResult<Option<ApprovalTimes>> {
+	load_decode(store, config.col_approval_data, &approval_times_key(candidate_hash))
+		.map_err(|e| SubsystemError::with_origin("approval-voting", e))
+}
+
+pub fn load_block_history(
+	store: &dyn Database,
+	config: &Config,
+	block_number: BlockNumber,
+) -> SubsystemResult<Option<BlockHistory>> {
+	load_decode(store, config.col_approval_data, &block_history_key(block_number))
+		.map_err(|e| SubsystemError::with_origin("approval-voting", e))
+}
@@ -27,7 +27,7 @@ use polkadot_primitives::{BlockNumber, CandidateHash, CandidateIndex, Hash};
 use std::collections::HashMap;
 
 use super::{
-	approval_db::common::StoredBlockRange,
+	approval_db::common::{ApprovalTimes, BlockHistory, StoredBlockRange},
 	persisted_entries::{BlockEntry, CandidateEntry},
 };
 
@@ -41,6 +41,10 @@ pub enum BackendWriteOp {
 	DeleteBlocksAtHeight(BlockNumber),
 	DeleteBlockEntry(Hash),
 	DeleteCandidateEntry(CandidateHash),
+	WriteApprovalTimes(CandidateHash, ApprovalTimes),
+	DeleteApprovalTimes(CandidateHash),
+	WriteBlockHistory(BlockHistory),
+	DeleteBlockHistory(BlockNumber),
 }
 
 /// An abstraction over backend storage for the logic of this subsystem.
@@ -60,6 +64,11 @@ pub trait Backend {
 	fn load_all_blocks(&self) -> SubsystemResult<Vec<Hash>>;
 	/// Load stored block range form the DB.
 	fn load_stored_blocks(&self) -> SubsystemResult<Option<StoredBlockRange>>;
+	/// Load the approval times of a candidate from the DB.
+	fn load_approval_times(
+		&self,
+		candidate_hash: &CandidateHash,
+	) -> SubsystemResult<Option<ApprovalTimes>>;
 	/// Atomically write the list of operations, with later operations taking precedence over prior.
 	fn write<I>(&mut self, ops: I) -> SubsystemResult<()>
 	where
@@ -118,6 +127,10 @@ pub struct OverlayedBackend<'a, B: 'a> {
 	block_entries: HashMap<Hash, Option<BlockEntry>>,
 	// `None` means 'deleted', missing means query inner.
 	candidate_entries: HashMap<CandidateHash, Option<CandidateEntry>>,
+	// `None` means 'deleted', missing means query inner.
+	approval_times: HashMap<CandidateHash, Option<ApprovalTimes>>,
+	// `None` means 'deleted'.
+	block_history: HashMap<BlockNumber, Option<BlockHistory>>,
 }
 
 impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
@@ -128,6 +141,8 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 			blocks_at_height: HashMap::new(),
 			block_entries: HashMap::new(),
 			candidate_entries: HashMap::new(),
+			approval_times: HashMap::new(),
+			block_history: HashMap::new(),
 		}
 	}
 
@@ -135,6 +150,8 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 		self.block_entries.is_empty() &&
 			self.candidate_entries.is_empty() &&
 			self.blocks_at_height.is_empty() &&
+			self.approval_times.is_empty() &&
+			self.block_history.is_empty() &&
 			self.stored_block_range == BlockRangeStatus::NotModified
 	}
 
@@ -184,6 +201,17 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 		self.inner.load_candidate_entry(candidate_hash)
 	}
 
+	pub fn load_approval_times(
+		&self,
+		candidate_hash: &CandidateHash,
+	) -> SubsystemResult<Option<ApprovalTimes>> {
+		if let Some(val) = self.approval_times.get(&candidate_hash) {
+			return Ok(val.clone())
+		}
+
+		self.inner.load_approval_times(candidate_hash)
+	}
+
 	pub fn write_stored_block_range(&mut self, range: StoredBlockRange) {
 		self.stored_block_range = BlockRangeStatus::Inserted(range);
 	}
@@ -216,6 +244,22 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 		self.candidate_entries.insert(*hash, None);
 	}
 
+	pub fn write_approval_times(&mut self, hash: CandidateHash, times: ApprovalTimes) {
+		self.approval_times.insert(hash, Some(times));
+	}
+
+	pub fn delete_approval_times(&mut self, hash: &CandidateHash) {
+		self.approval_times.insert(*hash, None);
+	}
+
+	pub fn write_block_history(&mut self, history: BlockHistory) {
+		self.block_history.insert(history.block_number, Some(history));
+	}
+
+	pub fn delete_block_history(&mut self, block_number: BlockNumber) {
+		self.block_history.insert(block_number, None);
+	}
+
 	/// Transform this backend into a set of write-ops to be written to the
 	/// inner backend.
 	pub fn into_write_ops(self) -> impl Iterator<Item = BackendWriteOp> {
@@ -234,6 +278,16 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 			None => BackendWriteOp::DeleteCandidateEntry(h),
 		});
 
+		let approval_times_ops = self.approval_times.into_iter().map(|(h, v)| match v {
+			Some(v) => BackendWriteOp::WriteApprovalTimes(h, v),
+			None => BackendWriteOp::DeleteApprovalTimes(h),
+		});
+
+		let block_history_ops = self.block_history.into_iter().map(|(n, v)| match v {
+			Some(v) => BackendWriteOp::WriteBlockHistory(v),
+			None => BackendWriteOp::DeleteBlockHistory(n),
+		});
+
 		let stored_block_range_ops = match self.stored_block_range {
 			BlockRangeStatus::Inserted(val) => Some(BackendWriteOp::WriteStoredBlockRange(val)),
 			BlockRangeStatus::Deleted => Some(BackendWriteOp::DeleteStoredBlockRange),
@@ -245,5 +299,7 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 			.chain(blocks_at_height_ops)
 			.chain(block_entry_ops)
 			.chain(candidate_entry_ops)
+			.chain(approval_times_ops)
+			.chain(block_history_ops)
 	}
 }
@@ -604,7 +604,8 @@ pub(crate) mod tests {
 	use super::*;
 	use crate::{
 		approval_db::common::{load_block_entry, DbBackend},
-		RuntimeInfo, RuntimeInfoConfig, MAX_BLOCKS_WITH_ASSIGNMENT_TIMESTAMPS,
+		RuntimeInfo, RuntimeInfoConfig, APPROVAL_STATS_SESSIONS,
+		MAX_BLOCKS_WITH_ASSIGNMENT_TIMESTAMPS,
 	};
 	use approval_types::time::Clock;
 	use assert_matches::assert_matches;
@@ -663,6 +664,7 @@ pub(crate) mod tests {
 				MAX_BLOCKS_WITH_ASSIGNMENT_TIMESTAMPS,
 			)),
 			no_show_stats: Default::default(),
+			approval_stats: LruMap::new(ByLength::new(APPROVAL_STATS_SESSIONS)),
 		}
 	}
 
@@ -35,7 +35,7 @@ use polkadot_node_primitives::{
 use polkadot_node_subsystem::{
 	errors::RecoveryError,
 	messages::{
-		ApprovalCheckError, ApprovalCheckResult, ApprovalDistributionMessage,
+		ApprovalCheckError, ApprovalCheckResult, ApprovalDistributionMessage, ApprovalStats,
 		ApprovalVotingMessage, AssignmentCheckError, AssignmentCheckResult,
 		AvailabilityRecoveryMessage, BlockDescription, CandidateValidationMessage, ChainApiMessage,
 		ChainSelectionMessage, CheckedIndirectAssignment, CheckedIndirectSignedApprovalVote,
@@ -103,6 +103,7 @@ pub mod criteria;
 mod import;
 mod ops;
 mod persisted_entries;
+pub mod replay;
 
 use crate::{
 	approval_checking::{Check, TranchesToApproveResult},
@@ -898,8 +899,14 @@ struct State {
 	per_block_assignments_gathering_times:
 		LruMap<BlockNumber, HashMap<(Hash, CandidateHash), AssignmentGatheringRecord>>,
 	no_show_stats: NoShowStats,
+	// Approval work of the validators in the most recent sessions, as served to
+	// `ApprovalVotingMessage::GetApprovalStats`.
+	approval_stats: LruMap<SessionIndex, HashMap<ValidatorIndex, ApprovalStats>>,
 }
 
+// The number of sessions we keep the approval stats of the validators for.
+const APPROVAL_STATS_SESSIONS: u32 = 6;
+
 // Regularly dump the no-show stats at this block number frequency.
 const NO_SHOW_DUMP_FREQUENCY: BlockNumber = 50;
 // The maximum number of validators we record no-shows for, per candidate.
@@ -1159,6 +1166,18 @@ impl State {
 		}
 	}
 
+	fn approval_stats_mut(
+		&mut self,
+		session_index: SessionIndex,
+		validator_index: ValidatorIndex,
+	) -> &mut ApprovalStats {
+		self.approval_stats
+			.get_or_insert(session_index, HashMap::new)
+			.expect("Length limited cache always accepts new entries; qed")
+			.entry(validator_index)
+			.or_default()
+	}
+
 	fn record_no_shows(
 		&mut self,
 		session_index: SessionIndex,
@@ -1176,6 +1195,7 @@ impl State {
 				.or_default()
 				.entry(*validator_index)
 				.or_default() += 1;
+			self.approval_stats_mut(session_index, *validator_index).no_shows += 1;
 		}
 	}
 }
@@ -1257,6 +1277,7 @@ where
 			MAX_BLOCKS_WITH_ASSIGNMENT_TIMESTAMPS,
 		)),
 		no_show_stats: NoShowStats::default(),
+		approval_stats: LruMap::new(ByLength::new(APPROVAL_STATS_SESSIONS)),
 	};
 
 	let mut last_finalized_height: Option<BlockNumber> = {
@@ -1621,6 +1642,7 @@ async fn handle_actions<
 				metrics.on_assignment_produced(assignment_tranche);
 				let block_hash = indirect_cert.block_hash;
 				let validator_index = indirect_cert.validator;
+				state.approval_stats_mut(session, validator_index).assignments += 1;
 
 				if distribute_assignment {
 					approval_voting_sender.send_unbounded_message(
@@ -2163,6 +2185,16 @@ async fn handle_from_overseer<
 				.await?;
 				Vec::new()
 			},
+			ApprovalVotingMessage::GetApprovalStats(session_index, validator_index, tx) => {
+				let stats = state
+					.approval_stats
+					.peek(&session_index)
+					.and_then(|stats| stats.get(&validator_index))
+					.copied()
+					.unwrap_or_default();
+				let _ = tx.send(stats);
+				Vec::new()
+			},
 		},
 	};
 
@@ -3211,6 +3243,14 @@ where
 				}
 			}
 		}
+
+		// Record when the approval was received and when the candidate was approved, to explain
+		// slow approvals after the fact.
+		let approver = validator_index.filter(|_| !already_approved_by.unwrap_or(true));
+		if approver.is_some() || newly_approved {
+			note_approval_times(db, candidate_hash, block_hash, approver, newly_approved, tick_now);
+		}
+
 		// We have no need to write the candidate entry if all of the following
 		// is true:
 		//
@@ -3229,6 +3269,37 @@ where
 	actions
 }
 
+fn note_approval_times(
+	db: &mut OverlayedBackend<'_, impl Backend>,
+	candidate_hash: CandidateHash,
+	block_hash: Hash,
+	approver: Option<ValidatorIndex>,
+	newly_approved: bool,
+	tick_now: Tick,
+) {
+	let mut approval_times = match db.load_approval_times(&candidate_hash) {
+		Ok(approval_times) => approval_times.unwrap_or_default(),
+		Err(err) => {
+			gum::debug!(
+				target: LOG_TARGET,
+				?candidate_hash,
+				?err,
+				"Failed to load approval times",
+			);
+			return
+		},
+	};
+
+	if let Some(approver) = approver {
+		approval_times.approvals.push((approver, tick_now));
+	}
+	if newly_approved {
+		approval_times.approved.push((block_hash, tick_now));
+	}
+
+	db.write_approval_times(candidate_hash, approval_times);
+}
+
 fn should_trigger_assignment(
 	approval_entry: &ApprovalEntry,
 	candidate_entry: &CandidateEntry,
@@ -3950,6 +4021,7 @@ async fn maybe_create_signature<
 	}
 
 	metrics.on_approval_produced();
+	state.approval_stats_mut(block_entry.session(), validator_index).approvals += 1;
 
 	approval_voting_sender.send_unbounded_message(ApprovalDistributionMessage::DistributeApproval(
 		IndirectSignedApprovalVoteV2 {
@@ -22,12 +22,16 @@ use polkadot_node_subsystem::{SubsystemError, SubsystemResult};
 use bitvec::order::Lsb0 as BitOrderLsb0;
 use polkadot_primitives::{
 	vstaging::CandidateReceiptV2 as CandidateReceipt, BlockNumber, CandidateHash, GroupIndex, Hash,
+	ValidatorIndex,
 };
 
 use std::collections::{hash_map::Entry, BTreeMap, HashMap};
 
 use super::{
-	approval_db::{common::StoredBlockRange, v2::OurAssignment},
+	approval_db::{
+		common::{ApprovalTimes, BlockHistory, CandidateHistory, StoredBlockRange, HISTORY_DEPTH},
+		v2::OurAssignment,
+	},
 	backend::{Backend, OverlayedBackend},
 	persisted_entries::{ApprovalEntry, BlockEntry, CandidateEntry},
 	LOG_TARGET,
@@ -94,6 +98,29 @@ pub fn canonicalize(
 		Some(range) => range,
 	};
 
+	// Keep the approval history of the blocks being finalized, pruning the history of the blocks
+	// which are now more than `HISTORY_DEPTH` blocks deep.
+	let mut finalized_hash = canon_hash;
+	while let Some(block_entry) = overlay_db.load_block_entry(&finalized_hash)? {
+		finalized_hash = block_entry.parent_hash();
+
+		let history = block_history(&block_entry, |candidate_hash| {
+			let candidate_entry = match overlay_db.load_candidate_entry(candidate_hash)? {
+				None => return Ok(None),
+				Some(c) => c,
+			};
+			let approval_times = overlay_db.load_approval_times(candidate_hash)?;
+			Ok(Some((candidate_entry, approval_times.unwrap_or_default())))
+		})?;
+		overlay_db.write_block_history(history);
+	}
+
+	for i in range.0..=canon_number {
+		if let Some(pruned) = i.checked_sub(HISTORY_DEPTH) {
+			overlay_db.delete_block_history(pruned);
+		}
+	}
+
 	// Storing all candidates in memory is potentially heavy, but should be fine
 	// as long as finality doesn't stall for a long while. We could optimize this
 	// by keeping only the metadata about which blocks reference each candidate.
@@ -158,6 +185,7 @@ pub fn canonicalize(
 	for (candidate_hash, candidate) in visited_candidates.into_iter() {
 		if candidate.block_assignments.is_empty() {
 			overlay_db.delete_candidate_entry(&candidate_hash);
+			overlay_db.delete_approval_times(&candidate_hash);
 		} else {
 			overlay_db.write_candidate_entry(candidate);
 		}
@@ -183,6 +211,74 @@ pub fn canonicalize(
 	Ok(())
 }
 
+/// The approval state of a block, as kept in the approval history once it is finalized.
+///
+/// `load_candidate` returns the entry and the approval times of a candidate of the block.
+pub fn block_history(
+	block_entry: &BlockEntry,
+	mut load_candidate: impl FnMut(
+		&CandidateHash,
+	) -> SubsystemResult<Option<(CandidateEntry, ApprovalTimes)>>,
+) -> SubsystemResult<BlockHistory> {
+	let block_hash = block_entry.block_hash();
+
+	let mut candidates = Vec::new();
+	for (core_index, candidate_hash) in block_entry.candidates() {
+		let (candidate_entry, approval_times) = match load_candidate(candidate_hash)? {
+			None => continue,
+			Some(c) => c,
+		};
+		let approval_entry = match candidate_entry.approval_entry(&block_hash) {
+			None => continue,
+			Some(a) => a,
+		};
+
+		let assignments = approval_entry
+			.tranches()
+			.iter()
+			.flat_map(|t| t.assignments().iter().map(move |(v, tick)| (*v, t.tranche(), *tick)))
+			.collect();
+		let approvals = candidate_entry
+			.approvals()
+			.iter_ones()
+			.map(|i| {
+				let validator_index = ValidatorIndex(i as _);
+				let tick = approval_times
+					.approvals
+					.iter()
+					.find(|(v, _)| *v == validator_index)
+					.map(|(_, tick)| *tick);
+				(validator_index, tick)
+			})
+			.collect();
+		let approved_at = approval_times
+			.approved
+			.iter()
+			.find(|(h, _)| *h == block_hash)
+			.map(|(_, tick)| *tick);
+
+		candidates.push(CandidateHistory {
+			candidate_hash: *candidate_hash,
+			core_index: *core_index,
+			backing_group: approval_entry.backing_group(),
+			assignments,
+			approvals,
+			approved: approval_entry.is_approved(),
+			approved_at,
+		});
+	}
+
+	Ok(BlockHistory {
+		block_hash,
+		block_number: block_entry.block_number(),
+		parent_hash: block_entry.parent_hash(),
+		session: block_entry.session(),
+		slot: block_entry.slot(),
+		approved: block_entry.is_fully_approved(),
+		candidates,
+	})
+}
+
 /// Record a new block entry.
 ///
 /// This will update the blocks-at-height mapping, the stored block range, if necessary,
@@ -404,6 +500,7 @@ pub fn revert_to(
 					candidate_entry.block_assignments.remove(&hash);
 					if candidate_entry.block_assignments.is_empty() {
 						overlay.delete_candidate_entry(candidate_hash);
+						overlay.delete_approval_times(candidate_hash);
 					} else {
 						overlay.write_candidate_entry(candidate_entry);
 					}
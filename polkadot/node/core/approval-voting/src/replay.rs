@@ -0,0 +1,482 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline replay of the approval-voting database.
+//!
+//! For every candidate of the blocks held by the database the replay reports the assignments and
+//! the approvals in the order they were received, the no-shows and when the candidate was approved.
+//! Besides the unfinalized blocks, the database keeps the approval state of the last
+//! [`HISTORY_DEPTH`] finalized blocks, along with when approvals were received and candidates
+//! approved.
+//!
+//! Approvals received before the node recorded approval times are reported without a tick.
+
+use polkadot_node_primitives::approval::{
+	time::{slot_number_to_tick, Tick},
+	v1::DelayTranche,
+};
+use polkadot_node_subsystem::SubsystemResult;
+use polkadot_node_subsystem_util::database::Database;
+use polkadot_primitives::{
+	BlockNumber, CandidateHash, CoreIndex, GroupIndex, Hash, SessionIndex, SessionInfo,
+	ValidatorIndex,
+};
+use sp_consensus_slots::Slot;
+
+use crate::{
+	approval_db::common::{
+		load_all_blocks, load_approval_times, load_block_entry, load_block_history,
+		load_candidate_entry, load_stored_blocks, CandidateHistory, Config, StoredBlockRange,
+		HISTORY_DEPTH,
+	},
+	ops,
+	persisted_entries::BlockEntry,
+};
+
+/// The replayed approval state of a block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockReplay {
+	/// The hash of the block.
+	pub block_hash: Hash,
+	/// The number of the block.
+	pub block_number: BlockNumber,
+	/// The session the block belongs to.
+	pub session: SessionIndex,
+	/// The tick at the start of the slot of the block, which is the start of tranche 0.
+	pub block_tick: Tick,
+	/// Whether the block was finalized.
+	pub finalized: bool,
+	/// Whether all candidates of the block were approved.
+	pub approved: bool,
+	/// The timelines of the candidates included by the block.
+	pub candidates: Vec<CandidateTimeline>,
+}
+
+/// The replayed approval timeline of a candidate in the context of a block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidateTimeline {
+	/// The hash of the candidate.
+	pub candidate_hash: CandidateHash,
+	/// The core the candidate was occupying.
+	pub core_index: CoreIndex,
+	/// The group which backed the candidate.
+	pub backing_group: GroupIndex,
+	/// The assignments received for the candidate, ordered by the tick they were received at.
+	pub assignments: Vec<AssignmentRecord>,
+	/// The approvals received for the candidate, ordered by the tick they were received at.
+	pub approvals: Vec<ApprovalRecord>,
+	/// The assigned validators whose approval wasn't received before their no-show deadline,
+	/// while the candidate was waiting for approvals.
+	pub no_shows: Vec<ValidatorIndex>,
+	/// Whether the node considered the candidate approved.
+	pub approved: bool,
+	/// The tick the candidate was approved at.
+	///
+	/// `None` if the candidate wasn't approved, or was approved before the node recorded it.
+	pub approved_at: Option<Tick>,
+}
+
+/// An assignment of a validator to check a candidate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AssignmentRecord {
+	/// The assigned validator.
+	pub validator_index: ValidatorIndex,
+	/// The delay tranche of the assignment.
+	pub tranche: DelayTranche,
+	/// The tick the assignment was received at.
+	pub tick: Tick,
+	/// Whether the validator approved the candidate.
+	pub approved: bool,
+}
+
+/// An approval of a candidate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ApprovalRecord {
+	/// The approving validator.
+	pub validator_index: ValidatorIndex,
+	/// The tick the approval was received at, `None` if the node didn't record it.
+	pub tick: Option<Tick>,
+}
+
+/// Replay the approval state machine for all blocks held by the approval-voting database.
+///
+/// The replay ends at `end_tick`, which should be the time the node was stopped. Blocks are
+/// returned in ascending order of their number. `session_info` is called with the parent hash of
+/// a block and its session, blocks for which it returns `None` are skipped.
+pub fn replay(
+	db: &dyn Database,
+	config: &Config,
+	slot_duration_millis: u64,
+	end_tick: Tick,
+	mut session_info: impl FnMut(Hash, SessionIndex) -> Option<SessionInfo>,
+) -> SubsystemResult<Vec<BlockReplay>> {
+	let mut blocks = Vec::new();
+	if let Some(StoredBlockRange(first_unfinalized, _)) = load_stored_blocks(db, config)? {
+		for block_number in first_unfinalized.saturating_sub(HISTORY_DEPTH)..first_unfinalized {
+			if let Some(history) = load_block_history(db, config, block_number)? {
+				blocks.push((history, true));
+			}
+		}
+	}
+
+	for block_hash in load_all_blocks(db, config)? {
+		let block_entry: BlockEntry = match load_block_entry(db, config, &block_hash)? {
+			Some(e) => e.into(),
+			None => continue,
+		};
+
+		let history = ops::block_history(&block_entry, |candidate_hash| {
+			let candidate_entry = match load_candidate_entry(db, config, candidate_hash)? {
+				Some(e) => e.into(),
+				None => return Ok(None),
+			};
+			let approval_times = load_approval_times(db, config, candidate_hash)?;
+			Ok(Some((candidate_entry, approval_times.unwrap_or_default())))
+		})?;
+		blocks.push((history, false));
+	}
+
+	let mut replays = Vec::new();
+	for (history, finalized) in blocks {
+		let session_info = match session_info(history.parent_hash, history.session) {
+			Some(s) => s,
+			None => continue,
+		};
+
+		let block_tick = slot_number_to_tick(slot_duration_millis, history.slot);
+		let no_show_duration = slot_number_to_tick(
+			slot_duration_millis,
+			Slot::from(u64::from(session_info.no_show_slots)),
+		);
+
+		replays.push(BlockReplay {
+			block_hash: history.block_hash,
+			block_number: history.block_number,
+			session: history.session,
+			block_tick,
+			finalized,
+			approved: history.approved,
+			candidates: history
+				.candidates
+				.iter()
+				.map(|c| replay_candidate(c, block_tick, no_show_duration, end_tick))
+				.collect(),
+		});
+	}
+
+	replays.sort_by_key(|r| r.block_number);
+	Ok(replays)
+}
+
+fn replay_candidate(
+	candidate: &CandidateHistory,
+	block_tick: Tick,
+	no_show_duration: Tick,
+	end_tick: Tick,
+) -> CandidateTimeline {
+	let mut approvals: Vec<_> = candidate
+		.approvals
+		.iter()
+		.map(|(validator_index, tick)| ApprovalRecord {
+			validator_index: *validator_index,
+			tick: *tick,
+		})
+		.collect();
+	approvals.sort_by_key(|a| (a.tick, a.validator_index));
+
+	let approval_of = |validator_index: ValidatorIndex| {
+		approvals.iter().find(|a| a.validator_index == validator_index)
+	};
+
+	let mut assignments: Vec<_> = candidate
+		.assignments
+		.iter()
+		.map(|(validator_index, tranche, tick)| AssignmentRecord {
+			validator_index: *validator_index,
+			tranche: *tranche,
+			tick: *tick,
+			approved: approval_of(*validator_index).is_some(),
+		})
+		.collect();
+	assignments.sort_by_key(|a| (a.tick, a.tranche, a.validator_index));
+
+	// Assignments only turn into no-shows while the candidate is waiting for approvals, approvals
+	// received once it is approved are not recorded.
+	let waiting_until = candidate.approved_at.unwrap_or(end_tick);
+	let no_shows = assignments
+		.iter()
+		.filter(|a| {
+			let deadline = a.tick.max(block_tick) + no_show_duration;
+			deadline <= waiting_until &&
+				match approval_of(a.validator_index) {
+					None => true,
+					Some(approval) => approval.tick.map_or(false, |tick| tick > deadline),
+				}
+		})
+		.map(|a| a.validator_index)
+		.collect();
+
+	CandidateTimeline {
+		candidate_hash: candidate.candidate_hash,
+		core_index: candidate.core_index,
+		backing_group: candidate.backing_group,
+		assignments,
+		approvals,
+		no_shows,
+		approved: candidate.approved,
+		approved_at: candidate.approved_at,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		approval_db::{
+			common::{migration_helpers::make_bitvec, ApprovalTimes, DbBackend},
+			v3,
+		},
+		backend::{Backend, OverlayedBackend},
+		ops::{add_block_entry, canonicalize, NewCandidateInfo},
+	};
+	use polkadot_primitives::{vstaging::MutateDescriptorV2, Id as ParaId};
+	use polkadot_primitives_test_helpers::{dummy_candidate_receipt_v2, dummy_hash};
+	use std::sync::Arc;
+
+	const BLOCK_TICK: Tick = 100;
+	const NO_SHOW_DURATION: Tick = 24;
+
+	fn candidate(
+		assignments: &[(u32, DelayTranche, Tick)],
+		approvals: &[(u32, Option<Tick>)],
+		approved_at: Option<Tick>,
+	) -> CandidateHistory {
+		CandidateHistory {
+			candidate_hash: CandidateHash(dummy_hash()),
+			core_index: CoreIndex(0),
+			backing_group: GroupIndex(0),
+			assignments: assignments
+				.iter()
+				.map(|(v, tranche, tick)| (ValidatorIndex(*v), *tranche, *tick))
+				.collect(),
+			approvals: approvals.iter().map(|(v, tick)| (ValidatorIndex(*v), *tick)).collect(),
+			approved: approved_at.is_some(),
+			approved_at,
+		}
+	}
+
+	#[test]
+	fn approvals_are_reported_when_received() {
+		let timeline = replay_candidate(
+			&candidate(&[(0, 0, 101), (1, 0, 103)], &[(1, Some(110)), (0, Some(105))], Some(112)),
+			BLOCK_TICK,
+			NO_SHOW_DURATION,
+			200,
+		);
+
+		assert_eq!(
+			timeline.approvals,
+			vec![
+				ApprovalRecord { validator_index: ValidatorIndex(0), tick: Some(105) },
+				ApprovalRecord { validator_index: ValidatorIndex(1), tick: Some(110) },
+			],
+		);
+		assert!(timeline.assignments.iter().all(|a| a.approved));
+		assert!(timeline.no_shows.is_empty());
+		assert_eq!(timeline.approved_at, Some(112));
+	}
+
+	#[test]
+	fn late_and_missing_approvals_are_no_shows() {
+		// The approval of validator 1 arrives after its deadline, validator 2 never approves.
+		let history = candidate(
+			&[(0, 0, 101), (1, 0, 103), (2, 1, 104)],
+			&[(0, Some(105)), (1, Some(130))],
+			Some(132),
+		);
+		let timeline = replay_candidate(&history, BLOCK_TICK, NO_SHOW_DURATION, 200);
+
+		assert_eq!(
+			timeline.assignments.iter().map(|a| a.validator_index).collect::<Vec<_>>(),
+			vec![ValidatorIndex(0), ValidatorIndex(1), ValidatorIndex(2)],
+		);
+		assert_eq!(timeline.no_shows, vec![ValidatorIndex(1), ValidatorIndex(2)]);
+
+		// Assignments whose deadline passed after the candidate was approved are no no-shows.
+		let history =
+			candidate(&[(0, 0, 101), (1, 0, 103), (2, 1, 104)], &[(0, Some(105))], Some(110));
+		let timeline = replay_candidate(&history, BLOCK_TICK, NO_SHOW_DURATION, 200);
+		assert!(timeline.no_shows.is_empty());
+	}
+
+	#[test]
+	fn no_shows_wait_for_the_deadline() {
+		let history = candidate(&[(0, 0, 101)], &[], None);
+
+		let timeline = replay_candidate(&history, BLOCK_TICK, NO_SHOW_DURATION, 110);
+		assert!(timeline.no_shows.is_empty());
+		assert_eq!(timeline.approved_at, None);
+
+		let timeline = replay_candidate(&history, BLOCK_TICK, NO_SHOW_DURATION, 200);
+		assert_eq!(timeline.no_shows, vec![ValidatorIndex(0)]);
+	}
+
+	#[test]
+	fn approvals_without_recorded_ticks_are_no_no_shows() {
+		let timeline = replay_candidate(
+			&candidate(&[(0, 0, 101), (1, 0, 103)], &[(1, Some(105)), (0, None)], None),
+			BLOCK_TICK,
+			NO_SHOW_DURATION,
+			200,
+		);
+
+		assert_eq!(
+			timeline.approvals,
+			vec![
+				ApprovalRecord { validator_index: ValidatorIndex(0), tick: None },
+				ApprovalRecord { validator_index: ValidatorIndex(1), tick: Some(105) },
+			],
+		);
+		assert!(timeline.no_shows.is_empty());
+	}
+
+	#[test]
+	fn finalized_blocks_are_replayed() {
+		const TEST_CONFIG: Config = Config { col_approval_data: 0 };
+		const SLOT_DURATION_MILLIS: u64 = 6_000;
+
+		let db = kvdb_memorydb::create(1);
+		let db = polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter::new(db, &[]);
+		let store: Arc<dyn Database> = Arc::new(db);
+		let mut backend = DbBackend::new(store.clone(), TEST_CONFIG);
+
+		let hash_a = Hash::repeat_byte(1);
+		let hash_b = Hash::repeat_byte(2);
+		let block_entry =
+			|block_hash, parent_hash, block_number: BlockNumber, candidate_hash| v3::BlockEntry {
+				block_hash,
+				parent_hash,
+				block_number,
+				session: 1,
+				slot: Slot::from(u64::from(block_number)),
+				relay_vrf_story: [0u8; 32],
+				approved_bitfield: make_bitvec(1),
+				candidates: vec![(CoreIndex(0), candidate_hash)],
+				children: Vec::new(),
+				candidates_pending_signature: Default::default(),
+				distributed_assignments: Default::default(),
+			};
+		let receipt = |para_id: u32| {
+			let mut receipt = dummy_candidate_receipt_v2(dummy_hash());
+			receipt.descriptor.set_para_id(ParaId::from(para_id));
+			receipt
+		};
+		let (receipt_a, receipt_b) = (receipt(1), receipt(2));
+		let (candidate_a, candidate_b) = (receipt_a.hash(), receipt_b.hash());
+
+		let mut overlay_db = OverlayedBackend::new(&backend);
+		overlay_db.write_stored_block_range(StoredBlockRange(1, 2));
+		for (entry, receipt) in [
+			(block_entry(hash_a, Hash::repeat_byte(0), 1, candidate_a), receipt_a),
+			(block_entry(hash_b, hash_a, 2, candidate_b), receipt_b),
+		] {
+			let candidate_info = NewCandidateInfo::new(receipt, GroupIndex(0), None);
+			add_block_entry(&mut overlay_db, entry.into(), 4, |_| Some(candidate_info.clone()))
+				.unwrap();
+		}
+
+		// Block A starts at tick 12. Validators 0 and 1 approve candidate A, which is approved
+		// at tick 24. Block B starts at tick 24 and validator 2 never approves candidate B.
+		let mut entry_a = overlay_db.load_block_entry(&hash_a).unwrap().unwrap();
+		entry_a.mark_approved_by_hash(&candidate_a);
+		overlay_db.write_block_entry(entry_a);
+
+		let mut entry = overlay_db.load_candidate_entry(&candidate_a).unwrap().unwrap();
+		let approval_entry = entry.approval_entry_mut(&hash_a).unwrap();
+		approval_entry.import_assignment(0, ValidatorIndex(0), 13, false);
+		approval_entry.import_assignment(0, ValidatorIndex(1), 14, false);
+		approval_entry.mark_approved();
+		entry.mark_approval(ValidatorIndex(0));
+		entry.mark_approval(ValidatorIndex(1));
+		overlay_db.write_candidate_entry(entry);
+		overlay_db.write_approval_times(
+			candidate_a,
+			ApprovalTimes {
+				approvals: vec![(ValidatorIndex(0), 20), (ValidatorIndex(1), 22)],
+				approved: vec![(hash_a, 24)],
+			},
+		);
+
+		let mut entry = overlay_db.load_candidate_entry(&candidate_b).unwrap().unwrap();
+		entry.approval_entry_mut(&hash_b).unwrap().import_assignment(
+			0,
+			ValidatorIndex(2),
+			25,
+			false,
+		);
+		overlay_db.write_candidate_entry(entry);
+
+		canonicalize(&mut overlay_db, 1, hash_a).unwrap();
+		let write_ops = overlay_db.into_write_ops();
+		backend.write(write_ops).unwrap();
+
+		let session_info = SessionInfo {
+			validators: Vec::new().into(),
+			discovery_keys: Vec::new(),
+			assignment_keys: Vec::new(),
+			validator_groups: Vec::new().into(),
+			n_cores: 1,
+			needed_approvals: 2,
+			zeroth_delay_tranche_width: 0,
+			relay_vrf_modulo_samples: 1,
+			n_delay_tranches: 40,
+			no_show_slots: 2,
+			active_validator_indices: Vec::new(),
+			dispute_period: 6,
+			random_seed: [0u8; 32],
+		};
+		let replays = replay(&*store, &TEST_CONFIG, SLOT_DURATION_MILLIS, 100, |_, _| {
+			Some(session_info.clone())
+		})
+		.unwrap();
+
+		assert_eq!(replays.len(), 2);
+		let (block_a, block_b) = (&replays[0], &replays[1]);
+
+		assert_eq!((block_a.block_hash, block_a.finalized, block_a.approved), (hash_a, true, true));
+		assert_eq!(block_a.block_tick, 12);
+		assert_eq!(
+			block_a.candidates[0].approvals,
+			vec![
+				ApprovalRecord { validator_index: ValidatorIndex(0), tick: Some(20) },
+				ApprovalRecord { validator_index: ValidatorIndex(1), tick: Some(22) },
+			],
+		);
+		assert!(block_a.candidates[0].no_shows.is_empty());
+		assert_eq!(block_a.candidates[0].approved_at, Some(24));
+
+		assert_eq!(
+			(block_b.block_hash, block_b.finalized, block_b.approved),
+			(hash_b, false, false)
+		);
+		assert_eq!(block_b.candidates[0].no_shows, vec![ValidatorIndex(2)]);
+		assert_eq!(block_b.candidates[0].approved_at, None);
+
+		// The approval times of candidate A were pruned with its entry, the history remains.
+		assert_eq!(load_approval_times(&*store, &TEST_CONFIG, &candidate_a).unwrap(), None);
+		assert!(load_block_history(&*store, &TEST_CONFIG, 1).unwrap().is_some());
+	}
+}
@@ -62,7 +62,7 @@ use std::{
 };
 
 use super::{
-	approval_db::common::StoredBlockRange,
+	approval_db::common::{ApprovalTimes, BlockHistory, StoredBlockRange},
 	backend::BackendWriteOp,
 	import::tests::{
 		garbage_vrf_signature, AllowedSlots, BabeEpoch, BabeEpochConfiguration,
@@ -280,6 +280,8 @@ struct TestStoreInner {
 	blocks_at_height: HashMap<BlockNumber, Vec<Hash>>,
 	block_entries: HashMap<Hash, BlockEntry>,
 	candidate_entries: HashMap<CandidateHash, CandidateEntry>,
+	approval_times: HashMap<CandidateHash, ApprovalTimes>,
+	block_history: HashMap<BlockNumber, BlockHistory>,
 }
 
 impl V1ReadBackend for TestStoreInner {
@@ -323,6 +325,13 @@ impl Backend for TestStoreInner {
 		Ok(self.stored_block_range.clone())
 	}
 
+	fn load_approval_times(
+		&self,
+		candidate_hash: &CandidateHash,
+	) -> SubsystemResult<Option<ApprovalTimes>> {
+		Ok(self.approval_times.get(candidate_hash).cloned())
+	}
+
 	fn write<I>(&mut self, ops: I) -> SubsystemResult<()>
 	where
 		I: IntoIterator<Item = BackendWriteOp>,
@@ -354,6 +363,18 @@ impl Backend for TestStoreInner {
 				BackendWriteOp::DeleteCandidateEntry(candidate_hash) => {
 					let _ = self.candidate_entries.remove(&candidate_hash);
 				},
+				BackendWriteOp::WriteApprovalTimes(candidate_hash, approval_times) => {
+					self.approval_times.insert(candidate_hash, approval_times);
+				},
+				BackendWriteOp::DeleteApprovalTimes(candidate_hash) => {
+					let _ = self.approval_times.remove(&candidate_hash);
+				},
+				BackendWriteOp::WriteBlockHistory(block_history) => {
+					self.block_history.insert(block_history.block_number, block_history);
+				},
+				BackendWriteOp::DeleteBlockHistory(block_number) => {
+					let _ = self.block_history.remove(&block_number);
+				},
 			}
 		}
 
@@ -408,6 +429,14 @@ impl Backend for TestStore {
 		store.load_stored_blocks()
 	}
 
+	fn load_approval_times(
+		&self,
+		candidate_hash: &CandidateHash,
+	) -> SubsystemResult<Option<ApprovalTimes>> {
+		let store = self.store.lock();
+		store.load_approval_times(candidate_hash)
+	}
+
 	fn write<I>(&mut self, ops: I) -> SubsystemResult<()>
 	where
 		I: IntoIterator<Item = BackendWriteOp>,
@@ -5638,6 +5667,7 @@ fn test_gathering_assignments_statements() {
 			MAX_BLOCKS_WITH_ASSIGNMENT_TIMESTAMPS,
 		)),
 		no_show_stats: NoShowStats::default(),
+		approval_stats: LruMap::new(ByLength::new(APPROVAL_STATS_SESSIONS)),
 	};
 
 	for i in 0..200i32 {
@@ -5719,6 +5749,43 @@ fn test_gathering_assignments_statements() {
 	assert_eq!(state.per_block_assignments_gathering_times.len(), 0);
 }
 
+// Test the approval stats are kept per session and validator, only for the most recent sessions.
+#[test]
+fn test_approval_stats_per_session() {
+	let mut state = State {
+		keystore: Arc::new(LocalKeystore::in_memory()),
+		slot_duration_millis: 6_000,
+		clock: Arc::new(MockClock::default()),
+		assignment_criteria: Box::new(MockAssignmentCriteria::check_only(|_| Ok(0))),
+		per_block_assignments_gathering_times: LruMap::new(ByLength::new(
+			MAX_BLOCKS_WITH_ASSIGNMENT_TIMESTAMPS,
+		)),
+		no_show_stats: NoShowStats::default(),
+		approval_stats: LruMap::new(ByLength::new(APPROVAL_STATS_SESSIONS)),
+	};
+
+	state.approval_stats_mut(1, ValidatorIndex(0)).assignments += 1;
+	state.approval_stats_mut(1, ValidatorIndex(0)).approvals += 1;
+	state.record_no_shows(1, 100, &vec![ValidatorIndex(0), ValidatorIndex(1)]);
+	state.record_no_shows(1, 101, &vec![ValidatorIndex(1)]);
+
+	assert_eq!(
+		state.approval_stats.peek(&1).unwrap().get(&ValidatorIndex(0)),
+		Some(&ApprovalStats { assignments: 1, approvals: 1, no_shows: 1 })
+	);
+	assert_eq!(
+		state.approval_stats.peek(&1).unwrap().get(&ValidatorIndex(1)),
+		Some(&ApprovalStats { assignments: 0, approvals: 0, no_shows: 2 })
+	);
+
+	// The stats of the oldest session are dropped once enough newer sessions are seen.
+	for session in 2..=APPROVAL_STATS_SESSIONS + 1 {
+		state.record_no_shows(session, 100, &vec![ValidatorIndex(0)]);
+	}
+	assert!(state.approval_stats.peek(&1).is_none());
+	assert_eq!(state.approval_stats.len(), APPROVAL_STATS_SESSIONS as usize);
+}
+
 // Test we note the time we took to transition RequiredTranche  from Pending to Exact and
 // that we increase the stage when we transition from Exact to Pending.
 #[test]
@@ -5732,6 +5799,7 @@ fn test_observe_assignment_gathering_status() {
 			MAX_BLOCKS_WITH_ASSIGNMENT_TIMESTAMPS,
 		)),
 		no_show_stats: NoShowStats::default(),
+		approval_stats: LruMap::new(ByLength::new(APPROVAL_STATS_SESSIONS)),
 	};
 
 	let metrics_inner = MetricsInner {
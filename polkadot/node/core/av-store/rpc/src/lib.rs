@@ -0,0 +1,267 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface to the availability store.
+//!
+//! Allows moving the available data and chunks of candidates from the availability store of one
+//! node to another, e.g. when migrating a validator to a new machine while it is still needed to
+//! serve chunks of recent candidates. The data is exchanged as the SCALE encoding of a
+//! `Vec<ExportedCandidate>`, the same format used by the `export-availability` and
+//! `import-availability` commands of the node. The methods are unsafe.
+
+#![warn(missing_docs)]
+
+use codec::{Decode, Encode};
+use futures::channel::oneshot;
+use jsonrpsee::{
+	core::async_trait,
+	proc_macros::rpc,
+	types::{ErrorObject, ErrorObjectOwned},
+	Extensions,
+};
+use polkadot_node_primitives::ExportedCandidate;
+use polkadot_node_subsystem::messages::{
+	AvailabilityStoreMessage, ChainApiMessage, ImportCandidateError, RuntimeApiMessage,
+	RuntimeApiRequest,
+};
+use polkadot_overseer::Handle;
+use polkadot_primitives::{vstaging::CandidateEvent, BlockNumber, CandidateHash, Hash};
+use sc_rpc_api::{check_if_safe, UnsafeRpcError};
+use sp_core::Bytes;
+
+const AV_STORE_ERROR: i32 = 9800;
+
+const RPC_ORIGIN: &str = "av-store-rpc";
+
+/// The maximum number of blocks that can be exported at once.
+pub const MAX_EXPORTED_BLOCKS: BlockNumber = 256;
+
+/// Provides RPC methods to export and import the data of the availability store.
+#[rpc(client, server)]
+pub trait AvailabilityStoreApi {
+	/// Exports the available data and chunks stored for the given candidates.
+	///
+	/// Candidates unknown to the availability store are skipped. The inclusion block needed to
+	/// import a candidate is only known while it is unfinalized, use
+	/// `availabilityStore_exportBlockRange` for finalized candidates.
+	#[method(name = "availabilityStore_exportCandidates", with_extensions)]
+	async fn export_candidates(&self, candidate_hashes: Vec<Hash>) -> Result<Bytes, Error>;
+
+	/// Exports the available data and chunks stored for the candidates included in the given
+	/// range of finalized blocks, both ends inclusive.
+	#[method(name = "availabilityStore_exportBlockRange", with_extensions)]
+	async fn export_block_range(&self, from: BlockNumber, to: BlockNumber) -> Result<Bytes, Error>;
+
+	/// Imports previously exported candidates and returns how many were imported.
+	///
+	/// Every candidate must name a relay chain block it was included in, known to this node.
+	/// The data is checked against the erasure root of the candidate receipt in that block.
+	#[method(name = "availabilityStore_import", with_extensions)]
+	async fn import(&self, data: Bytes) -> Result<u32, Error>;
+}
+
+/// Provides RPC methods to export and import the data of the availability store.
+pub struct AvailabilityStore {
+	/// A handle to the overseer, to query the availability store subsystem.
+	overseer: Handle,
+}
+
+impl AvailabilityStore {
+	/// Creates a new instance of the availability store RPC handler.
+	pub fn new(overseer: Handle) -> Self {
+		Self { overseer }
+	}
+
+	async fn export_candidate(
+		&self,
+		candidate_hash: CandidateHash,
+	) -> Result<Option<ExportedCandidate>, Error> {
+		let (tx, rx) = oneshot::channel();
+		self.overseer
+			.clone()
+			.send_msg(AvailabilityStoreMessage::ExportCandidate(candidate_hash, tx), RPC_ORIGIN)
+			.await;
+
+		rx.await.map_err(|_| Error::Unavailable)
+	}
+
+	// The hash of a finalized block.
+	async fn finalized_block_hash(&self, number: BlockNumber) -> Result<Hash, Error> {
+		let (tx, rx) = oneshot::channel();
+		self.overseer
+			.clone()
+			.send_msg(ChainApiMessage::FinalizedBlockHash(number, tx), RPC_ORIGIN)
+			.await;
+		rx.await
+			.map_err(|_| Error::Unavailable)?
+			.map_err(|e| Error::Internal(e.to_string()))?
+			.ok_or(Error::NotFinalized(number))
+	}
+
+	// The candidates included in a block, with their erasure roots.
+	async fn included_candidates(&self, hash: Hash) -> Result<Vec<(CandidateHash, Hash)>, Error> {
+		let (tx, rx) = oneshot::channel();
+		self.overseer
+			.clone()
+			.send_msg(
+				RuntimeApiMessage::Request(hash, RuntimeApiRequest::CandidateEvents(tx)),
+				RPC_ORIGIN,
+			)
+			.await;
+		let events = rx
+			.await
+			.map_err(|_| Error::Unavailable)?
+			.map_err(|e| Error::Internal(e.to_string()))?;
+
+		Ok(events
+			.into_iter()
+			.filter_map(|event| match event {
+				CandidateEvent::CandidateIncluded(receipt, ..) =>
+					Some((receipt.hash(), receipt.descriptor.erasure_root())),
+				_ => None,
+			})
+			.collect())
+	}
+}
+
+#[async_trait]
+impl AvailabilityStoreApiServer for AvailabilityStore {
+	async fn export_candidates(
+		&self,
+		ext: &Extensions,
+		candidate_hashes: Vec<Hash>,
+	) -> Result<Bytes, Error> {
+		check_if_safe(ext)?;
+
+		let mut exported = Vec::new();
+		for candidate_hash in candidate_hashes {
+			if let Some(candidate) = self.export_candidate(CandidateHash(candidate_hash)).await? {
+				exported.push(candidate);
+			}
+		}
+
+		Ok(exported.encode().into())
+	}
+
+	async fn export_block_range(
+		&self,
+		ext: &Extensions,
+		from: BlockNumber,
+		to: BlockNumber,
+	) -> Result<Bytes, Error> {
+		check_if_safe(ext)?;
+
+		if to < from || to - from >= MAX_EXPORTED_BLOCKS {
+			return Err(Error::InvalidRange)
+		}
+
+		let mut exported = Vec::new();
+		for number in from..=to {
+			let hash = self.finalized_block_hash(number).await?;
+			for (candidate_hash, _) in self.included_candidates(hash).await? {
+				if let Some(mut candidate) = self.export_candidate(candidate_hash).await? {
+					candidate.included_at = Some(hash);
+					exported.push(candidate);
+				}
+			}
+		}
+
+		Ok(exported.encode().into())
+	}
+
+	async fn import(&self, ext: &Extensions, data: Bytes) -> Result<u32, Error> {
+		check_if_safe(ext)?;
+
+		let candidates = Vec::<ExportedCandidate>::decode(&mut &data[..])
+			.map_err(|e| Error::InvalidData(e.to_string()))?;
+
+		let mut imported = 0;
+		for candidate in candidates {
+			let candidate_hash = candidate.candidate_hash;
+			let included_at =
+				candidate.included_at.ok_or(Error::UnknownCandidate(candidate_hash))?;
+			let (_, expected_erasure_root) = self
+				.included_candidates(included_at)
+				.await?
+				.into_iter()
+				.find(|(hash, _)| *hash == candidate_hash)
+				.ok_or(Error::UnknownCandidate(candidate_hash))?;
+
+			let (tx, rx) = oneshot::channel();
+			self.overseer
+				.clone()
+				.send_msg(
+					AvailabilityStoreMessage::ImportCandidate {
+						candidate,
+						expected_erasure_root,
+						tx,
+					},
+					RPC_ORIGIN,
+				)
+				.await;
+			rx.await.map_err(|_| Error::Unavailable)??;
+			imported += 1;
+		}
+
+		Ok(imported)
+	}
+}
+
+/// Top-level error type for the RPC handler.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The subsystems didn't answer, e.g. because of an internal database error.
+	#[error("The availability store is not available")]
+	Unavailable,
+	/// The requested block range is empty or too large.
+	#[error("Invalid block range, at most {MAX_EXPORTED_BLOCKS} blocks can be exported at once")]
+	InvalidRange,
+	/// A block of the requested range is not finalized.
+	#[error("Block {0} is not finalized")]
+	NotFinalized(BlockNumber),
+	/// The data to import could not be decoded.
+	#[error("Invalid data: {0}")]
+	InvalidData(String),
+	/// An imported candidate was not found in the block it claims to be included in.
+	#[error("Candidate {0:?} is not included in a known block")]
+	UnknownCandidate(CandidateHash),
+	/// An imported candidate was rejected by the availability store.
+	#[error(transparent)]
+	Import(#[from] ImportCandidateError),
+	/// Querying the chain or the runtime failed.
+	#[error("Internal error: {0}")]
+	Internal(String),
+	/// Call to an unsafe RPC was denied.
+	#[error(transparent)]
+	UnsafeRpcCalled(#[from] UnsafeRpcError),
+}
+
+impl From<Error> for ErrorObjectOwned {
+	fn from(error: Error) -> Self {
+		let code = match error {
+			Error::Unavailable => AV_STORE_ERROR + 1,
+			Error::InvalidRange => AV_STORE_ERROR + 2,
+			Error::NotFinalized(_) => AV_STORE_ERROR + 3,
+			Error::InvalidData(_) => AV_STORE_ERROR + 4,
+			Error::Import(_) => AV_STORE_ERROR + 5,
+			Error::Internal(_) => AV_STORE_ERROR + 6,
+			Error::UnknownCandidate(_) => AV_STORE_ERROR + 7,
+			Error::UnsafeRpcCalled(e) => return e.into(),
+		};
+
+		ErrorObject::owned(code, error.to_string(), None::<()>)
+	}
+}
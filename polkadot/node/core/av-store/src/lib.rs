@@ -39,16 +39,19 @@ use polkadot_node_subsystem_util::database::{DBTransaction, Database};
 use sp_consensus::SyncOracle;
 
 use bitvec::{order::Lsb0 as BitOrderLsb0, vec::BitVec};
-use polkadot_node_primitives::{AvailableData, ErasureChunk};
+use polkadot_node_primitives::{AvailableData, ErasureChunk, ExportedCandidate};
 use polkadot_node_subsystem::{
 	errors::{ChainApiError, RuntimeApiError},
-	messages::{AvailabilityStoreMessage, ChainApiMessage, StoreAvailableDataError},
+	messages::{
+		AvailabilityStoreMessage, ChainApiMessage, ImportCandidateError, StoreAvailableDataError,
+	},
 	overseer, ActiveLeavesUpdate, FromOrchestra, OverseerSignal, SpawnedSubsystem, SubsystemError,
 };
 use polkadot_node_subsystem_util as util;
 use polkadot_primitives::{
 	vstaging::{CandidateEvent, CandidateReceiptV2 as CandidateReceipt},
-	BlockNumber, CandidateHash, ChunkIndex, CoreIndex, Hash, Header, NodeFeatures, ValidatorIndex,
+	BlakeTwo256, BlockNumber, CandidateHash, ChunkIndex, CoreIndex, Hash, HashT, Header,
+	NodeFeatures, ValidatorIndex,
 };
 use util::availability_chunks::availability_chunk_indices;
 
@@ -375,6 +378,12 @@ pub enum Error {
 
 	#[error("Erasure root does not match expected one")]
 	InvalidErasureRoot,
+
+	#[error("Chunk of validator {0:?} does not match the erasure root")]
+	InvalidChunk(ValidatorIndex),
+
+	#[error("Number of validators does not match the stored candidate")]
+	ValidatorCountMismatch,
 }
 
 impl Error {
@@ -1232,6 +1241,36 @@ fn process_message(
 				},
 			}
 		},
+		AvailabilityStoreMessage::ExportCandidate(candidate, tx) => {
+			let _ = tx.send(export_candidate(&subsystem.db, &subsystem.config, candidate)?);
+		},
+		AvailabilityStoreMessage::ImportCandidate { candidate, expected_erasure_root, tx } => {
+			let candidate_hash = candidate.candidate_hash;
+			let res = import_candidate_inner(
+				&subsystem.db,
+				&subsystem.config,
+				subsystem.clock.now()?,
+				subsystem.pruning_config.keep_finalized_for,
+				candidate,
+				expected_erasure_root,
+			);
+
+			let err = match res {
+				Ok(()) => {
+					let _ = tx.send(Ok(()));
+					return Ok(())
+				},
+				Err(Error::InvalidErasureRoot) => ImportCandidateError::InvalidErasureRoot,
+				Err(Error::InvalidChunk(validator_index)) =>
+					ImportCandidateError::InvalidChunk(validator_index),
+				Err(Error::ValidatorCountMismatch) => ImportCandidateError::ValidatorCountMismatch,
+				// Internal errors drop the channel, same as for `StoreAvailableData`.
+				Err(e) => return Err(e),
+			};
+
+			gum::debug!(target: LOG_TARGET, ?candidate_hash, ?err, "Rejected imported candidate");
+			let _ = tx.send(Err(err));
+		},
 	}
 
 	Ok(())
@@ -1353,6 +1392,180 @@ fn store_available_data(
 	Ok(())
 }
 
+/// Export the available data and all chunks stored for a candidate.
+///
+/// Returns `None` if the candidate is not known. The block the candidate was included in is only
+/// filled in while the candidate is unfinalized, as it isn't stored for finalized candidates.
+pub fn export_candidate(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	candidate_hash: CandidateHash,
+) -> Result<Option<ExportedCandidate>, Error> {
+	let meta = match load_meta(db, config, &candidate_hash)? {
+		Some(m) => m,
+		None => return Ok(None),
+	};
+
+	let n_validators = meta.chunks_stored.len();
+	let available_data =
+		if meta.data_available { load_available_data(db, config, &candidate_hash)? } else { None };
+	let included_at = match &meta.state {
+		State::Unfinalized(_, blocks) => blocks.first().map(|(_, hash)| *hash),
+		State::Unavailable(_) | State::Finalized(_) => None,
+	};
+
+	let mut chunks = Vec::new();
+	for (validator_index, _) in meta.chunks_stored.iter().enumerate().filter(|(_, b)| **b) {
+		let validator_index = ValidatorIndex(validator_index as _);
+		match load_chunk(db, config, &candidate_hash, validator_index)? {
+			Some(c) => chunks.push((validator_index, c)),
+			None => {
+				gum::warn!(
+					target: LOG_TARGET,
+					?candidate_hash,
+					?validator_index,
+					"No chunk found for set bit in meta"
+				);
+			},
+		}
+	}
+
+	Ok(Some(ExportedCandidate {
+		candidate_hash,
+		n_validators: n_validators as u32,
+		included_at,
+		available_data,
+		chunks,
+	}))
+}
+
+/// The hashes of all candidates known to the availability store.
+pub fn stored_candidates(
+	db: &Arc<dyn Database>,
+	config: &Config,
+) -> Result<Vec<CandidateHash>, Error> {
+	let mut candidates = Vec::new();
+	for r in db.iter_with_prefix(config.col_meta, &META_PREFIX[..]) {
+		let (k, _v) = r?;
+		match <([u8; 4], CandidateHash)>::decode(&mut &k[..]) {
+			Ok((_, candidate_hash)) => candidates.push(candidate_hash),
+			Err(_) => continue, // sanity
+		}
+	}
+
+	Ok(candidates)
+}
+
+/// Import a previously exported candidate into the availability store.
+///
+/// This is meant for a store which is not in use by a running node. `expected_erasure_root` must
+/// be taken from the on-chain receipt of the candidate, never from the exported data. Imported
+/// data is kept for as long as data of finalized candidates, counting from now.
+pub fn import_candidate(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	candidate: ExportedCandidate,
+	expected_erasure_root: Hash,
+) -> Result<(), Error> {
+	let keep_for = Duration::from_secs(config.keep_finalized_for as u64 * 3600);
+	import_candidate_inner(
+		db,
+		config,
+		SystemClock.now()?,
+		keep_for,
+		candidate,
+		expected_erasure_root,
+	)
+}
+
+// Both the available data and every chunk are checked against the erasure root of the on-chain
+// receipt. Only the exported chunks are stored, as the validator to chunk mapping can't be
+// computed without the core index and node features of the candidate.
+fn import_candidate_inner(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	now: Duration,
+	keep_for: Duration,
+	candidate: ExportedCandidate,
+	expected_erasure_root: Hash,
+) -> Result<(), Error> {
+	let ExportedCandidate { candidate_hash, n_validators, available_data, chunks, .. } = candidate;
+	let n_validators = n_validators as usize;
+
+	if let Some(data) = &available_data {
+		let data_chunks = polkadot_erasure_coding::obtain_chunks_v1(n_validators, data)?;
+		let branches = polkadot_erasure_coding::branches(data_chunks.as_ref());
+		if branches.root() != expected_erasure_root {
+			return Err(Error::InvalidErasureRoot)
+		}
+	}
+
+	for (validator_index, chunk) in &chunks {
+		if validator_index.0 as usize >= n_validators {
+			return Err(Error::InvalidChunk(*validator_index))
+		}
+
+		let anticipated_hash = polkadot_erasure_coding::branch_hash(
+			&expected_erasure_root,
+			chunk.proof(),
+			chunk.index.0 as usize,
+		)
+		.map_err(|_| Error::InvalidChunk(*validator_index))?;
+
+		if anticipated_hash != BlakeTwo256::hash(&chunk.chunk) {
+			return Err(Error::InvalidChunk(*validator_index))
+		}
+	}
+
+	let mut tx = DBTransaction::new();
+
+	let mut meta = match load_meta(db, config, &candidate_hash)? {
+		Some(mut m) => {
+			if m.chunks_stored.is_empty() {
+				m.chunks_stored = bitvec::bitvec![u8, BitOrderLsb0; 0; n_validators];
+			} else if m.chunks_stored.len() != n_validators {
+				return Err(Error::ValidatorCountMismatch)
+			}
+
+			m
+		},
+		None => {
+			// Write a pruning record.
+			let prune_at = now + keep_for;
+			write_pruning_key(&mut tx, config, prune_at, &candidate_hash);
+
+			CandidateMeta {
+				state: State::Finalized(now.into()),
+				data_available: false,
+				chunks_stored: bitvec::bitvec![u8, BitOrderLsb0; 0; n_validators],
+			}
+		},
+	};
+
+	for (validator_index, chunk) in chunks {
+		if meta.chunks_stored[validator_index.0 as usize] {
+			continue
+		}
+
+		meta.chunks_stored.set(validator_index.0 as usize, true);
+		write_chunk(&mut tx, config, &candidate_hash, validator_index, &chunk);
+	}
+
+	if let Some(available_data) = available_data {
+		if !meta.data_available {
+			meta.data_available = true;
+			write_available_data(&mut tx, config, &candidate_hash, &available_data);
+		}
+	}
+
+	write_meta(&mut tx, config, &candidate_hash, &meta);
+	db.write(tx)?;
+
+	gum::debug!(target: LOG_TARGET, ?candidate_hash, "Imported candidate");
+
+	Ok(())
+}
+
 fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(), Error> {
 	let (range_start, range_end) = pruning_range(now);
 
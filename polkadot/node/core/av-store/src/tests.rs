@@ -1384,3 +1384,175 @@ fn query_chunk_size_works() {
 		virtual_overseer
 	});
 }
+
+#[test]
+fn exported_candidate_can_be_imported_into_another_store() {
+	let store = test_store();
+	let test_state = TestState::default();
+	let candidate_hash = CandidateHash(Hash::repeat_byte(1));
+	let n_validators = 10;
+
+	let pov = PoV { block_data: BlockData(vec![4, 5, 6]) };
+	let available_data = AvailableData {
+		pov: Arc::new(pov),
+		validation_data: test_state.persisted_validation_data.clone(),
+	};
+	let chunks = polkadot_erasure_coding::obtain_chunks_v1(n_validators, &available_data).unwrap();
+	let erasure_root = polkadot_erasure_coding::branches(chunks.as_ref()).root();
+
+	let exported = Arc::new(Mutex::new(None));
+	let exported_clone = exported.clone();
+	test_harness(test_state.clone(), store.clone(), |mut virtual_overseer| async move {
+		let (tx, rx) = oneshot::channel();
+		let block_msg = AvailabilityStoreMessage::StoreAvailableData {
+			candidate_hash,
+			n_validators: n_validators as _,
+			available_data: available_data.clone(),
+			tx,
+			core_index: CoreIndex(1),
+			node_features: NodeFeatures::EMPTY,
+			expected_erasure_root: erasure_root,
+		};
+		overseer_send(&mut virtual_overseer, block_msg).await;
+		assert_eq!(rx.await.unwrap(), Ok(()));
+
+		let (tx, rx) = oneshot::channel();
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityStoreMessage::ExportCandidate(candidate_hash, tx),
+		)
+		.await;
+		*exported_clone.lock() = rx.await.unwrap();
+
+		virtual_overseer
+	});
+
+	let exported = exported.lock().take().expect("candidate was stored");
+	assert_eq!(exported.included_at, None);
+	assert_eq!(exported.n_validators, n_validators as u32);
+	assert_eq!(exported.chunks.len(), n_validators);
+	assert_eq!(stored_candidates(&store, &TEST_CONFIG).unwrap(), vec![candidate_hash]);
+
+	let other_store = test_store();
+	test_harness(test_state.clone(), other_store.clone(), |mut virtual_overseer| async move {
+		let (tx, rx) = oneshot::channel();
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityStoreMessage::ImportCandidate {
+				candidate: exported.clone(),
+				expected_erasure_root: erasure_root,
+				tx,
+			},
+		)
+		.await;
+		assert_eq!(rx.await.unwrap(), Ok(()));
+
+		assert_eq!(
+			query_available_data(&mut virtual_overseer, candidate_hash).await.unwrap(),
+			available_data
+		);
+		for (validator_index, chunk) in &exported.chunks {
+			assert_eq!(
+				query_chunk(&mut virtual_overseer, candidate_hash, *validator_index)
+					.await
+					.as_ref(),
+				Some(chunk)
+			);
+		}
+
+		virtual_overseer
+	});
+
+	// Imported data is kept like finalized data.
+	let meta = load_meta(&other_store, &TEST_CONFIG, &candidate_hash).unwrap().unwrap();
+	assert_matches!(meta.state, State::Finalized(_));
+}
+
+#[test]
+fn import_candidate_checks_erasure_root() {
+	let store = test_store();
+	let test_state = TestState::default();
+	let candidate_hash = CandidateHash(Hash::repeat_byte(1));
+	let n_validators = 10;
+
+	let pov = PoV { block_data: BlockData(vec![4, 5, 6]) };
+	let available_data = AvailableData {
+		pov: Arc::new(pov),
+		validation_data: test_state.persisted_validation_data.clone(),
+	};
+	let chunks = polkadot_erasure_coding::obtain_chunks_v1(n_validators, &available_data).unwrap();
+	let branches = polkadot_erasure_coding::branches(chunks.as_ref());
+	let erasure_root = branches.root();
+	let mut chunk = branches
+		.map(|(proof, chunk)| ErasureChunk { chunk: chunk.to_vec(), index: ChunkIndex(0), proof })
+		.next()
+		.unwrap();
+
+	test_harness(test_state.clone(), store.clone(), |mut virtual_overseer| async move {
+		let (tx, rx) = oneshot::channel();
+		let candidate = ExportedCandidate {
+			candidate_hash,
+			n_validators: n_validators as _,
+			included_at: None,
+			available_data: Some(available_data.clone()),
+			chunks: Vec::new(),
+		};
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityStoreMessage::ImportCandidate {
+				candidate,
+				expected_erasure_root: Hash::repeat_byte(2),
+				tx,
+			},
+		)
+		.await;
+		assert_eq!(rx.await.unwrap(), Err(ImportCandidateError::InvalidErasureRoot));
+
+		// Self-consistent chunks are still checked against the expected root.
+		let (tx, rx) = oneshot::channel();
+		let candidate = ExportedCandidate {
+			candidate_hash,
+			n_validators: n_validators as _,
+			included_at: None,
+			available_data: None,
+			chunks: vec![(ValidatorIndex(0), chunk.clone())],
+		};
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityStoreMessage::ImportCandidate {
+				candidate,
+				expected_erasure_root: Hash::repeat_byte(2),
+				tx,
+			},
+		)
+		.await;
+		assert_eq!(rx.await.unwrap(), Err(ImportCandidateError::InvalidChunk(ValidatorIndex(0))));
+
+		chunk.chunk[0] ^= 1;
+		let (tx, rx) = oneshot::channel();
+		let candidate = ExportedCandidate {
+			candidate_hash,
+			n_validators: n_validators as _,
+			included_at: None,
+			available_data: None,
+			chunks: vec![(ValidatorIndex(0), chunk)],
+		};
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityStoreMessage::ImportCandidate {
+				candidate,
+				expected_erasure_root: erasure_root,
+				tx,
+			},
+		)
+		.await;
+		assert_eq!(rx.await.unwrap(), Err(ImportCandidateError::InvalidChunk(ValidatorIndex(0))));
+
+		assert!(query_available_data(&mut virtual_overseer, candidate_hash).await.is_none());
+		assert!(query_chunk(&mut virtual_overseer, candidate_hash, ValidatorIndex(0))
+			.await
+			.is_none());
+
+		virtual_overseer
+	});
+}
@@ -27,7 +27,7 @@ use futures::{
 	FutureExt,
 };
 use polkadot_node_subsystem::{
-	messages::{AvailabilityStoreMessage, BitfieldDistributionMessage},
+	messages::{AvailabilityStoreMessage, BitfieldDistributionMessage, ValidatorHealthMessage},
 	overseer, ActivatedLeaf, FromOrchestra, OverseerSignal, SpawnedSubsystem, SubsystemError,
 	SubsystemResult,
 };
@@ -272,6 +272,7 @@ where
 	sender
 		.send_message(BitfieldDistributionMessage::DistributeBitfield(leaf.hash, signed_bitfield))
 		.await;
+	sender.send_message(ValidatorHealthMessage::BitfieldSigned(leaf.hash)).await;
 
 	Ok(())
 }
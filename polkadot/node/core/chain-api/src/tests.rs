@@ -126,6 +126,7 @@ impl sp_blockchain::HeaderBackend<Block> for TestClient {
 			number_leaves: 0,
 			finalized_state: None,
 			block_gap: None,
+			pruned_blocks: None,
 		}
 	}
 	fn number(&self, hash: Hash) -> sp_blockchain::Result<Option<BlockNumber>> {
@@ -0,0 +1,303 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Prepare and execute PVFs without a relay chain.
+//!
+//! The tool starts the same PVF host as validators do, with the same prepare and execute workers,
+//! executor parameters, timeouts and sandboxing. This allows parachain teams to reproduce
+//! preparation and validation failures of their candidates locally.
+//!
+//! PoVs to execute are read in the format exported by collators started with
+//! `--export-pov-to-path`: the SCALE-encoded `PoV`, followed by the SCALE-encoded
+//! `PersistedValidationData` it was built on.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use codec::Decode;
+use futures::channel::oneshot;
+use polkadot_node_core_pvf::{
+	start, Config, Metrics, PrepareJobKind, Priority, PvfPrepData, EXECUTE_BINARY_NAME,
+	PREPARE_BINARY_NAME,
+};
+use polkadot_node_metrics::metrics::{prometheus::Registry, Metrics as _};
+use polkadot_node_primitives::PoV;
+use polkadot_node_subsystem::messages::PvfExecKind;
+use polkadot_primitives::{
+	executor_params::{
+		DEFAULT_APPROVAL_EXECUTION_TIMEOUT, DEFAULT_BACKING_EXECUTION_TIMEOUT,
+		DEFAULT_LENIENT_PREPARATION_TIMEOUT, DEFAULT_PRECHECK_PREPARATION_TIMEOUT,
+	},
+	ExecutorParams, Hash, PersistedValidationData, PvfExecKind as RuntimePvfExecKind, PvfPrepKind,
+};
+use std::{
+	path::{Path, PathBuf},
+	sync::Arc,
+	time::Instant,
+};
+
+/// Default limit of the decompressed validation code size, `max_code_size * 10` of the
+/// production networks. Use the `validation_code_bomb_limit` runtime API to get the actual one.
+const DEFAULT_VALIDATION_CODE_BOMB_LIMIT: u32 = 30 * 1024 * 1024;
+
+/// Metrics of the PVF host reported after each run, with their description and unit.
+const REPORTED_METRICS: &[(&str, &str, &str)] = &[
+	("polkadot_pvf_preparation_time", "preparation time", "s"),
+	("polkadot_pvf_preparation_max_rss", "preparation max RSS", "KiB"),
+	("polkadot_pvf_preparation_peak_tracked_allocation", "preparation peak allocation", "KiB"),
+	("polkadot_pvf_execution_time", "execution time", "s"),
+];
+
+/// Prepare and execute PVFs like validators do, without a relay chain.
+#[derive(Parser)]
+#[command(name = "polkadot-pvf", version)]
+struct Cli {
+	/// Directory containing the `polkadot-prepare-worker` and `polkadot-execute-worker` binaries.
+	///
+	/// Defaults to the directory of this binary.
+	#[arg(long)]
+	workers_path: Option<PathBuf>,
+
+	/// Directory to cache the compiled artifacts in.
+	///
+	/// A temporary directory, removed on exit, is used by default.
+	#[arg(long)]
+	cache_path: Option<PathBuf>,
+
+	/// Run the workers even if some of the security features required from validators are not
+	/// available on this machine.
+	#[arg(long)]
+	insecure: bool,
+
+	#[command(subcommand)]
+	command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+	/// Compile the validation code, like validators do when pre-checking a PVF.
+	Precheck(PvfArgs),
+	/// Compile the validation code and validate a candidate with it.
+	Execute {
+		#[command(flatten)]
+		pvf: PvfArgs,
+
+		/// Path to the SCALE-encoded `PoV`, followed by its SCALE-encoded
+		/// `PersistedValidationData`.
+		///
+		/// Collators write them to the directory passed to `--export-pov-to-path`.
+		#[arg(long)]
+		pov: PathBuf,
+
+		/// The kind of validation, which determines the execution timeout.
+		#[arg(long, value_enum, default_value_t = ExecKind::Backing)]
+		exec_kind: ExecKind,
+	},
+}
+
+#[derive(Args)]
+struct PvfArgs {
+	/// Path to the validation code, compressed or not.
+	#[arg(long)]
+	code: PathBuf,
+
+	/// Path to the SCALE-encoded `ExecutorParams` of the session, as returned by the
+	/// `ParachainHost_session_executor_params` runtime API, either binary or `0x`-prefixed hex.
+	///
+	/// The default executor parameters are used if omitted.
+	#[arg(long)]
+	executor_params: Option<PathBuf>,
+
+	/// Maximal size of the decompressed validation code.
+	#[arg(long, default_value_t = DEFAULT_VALIDATION_CODE_BOMB_LIMIT)]
+	validation_code_bomb_limit: u32,
+}
+
+impl PvfArgs {
+	fn prep_data(&self, kind: PvfPrepKind) -> Result<PvfPrepData, String> {
+		let code = read(&self.code)?;
+		let executor_params = match &self.executor_params {
+			Some(path) => {
+				let bytes = read(path)?;
+				let bytes = match std::str::from_utf8(&bytes) {
+					Ok(hex) if hex.trim().starts_with("0x") =>
+						array_bytes::hex2bytes(hex.trim())
+							.map_err(|err| format!("Invalid executor params hex: {:?}", err))?,
+					_ => bytes,
+				};
+				ExecutorParams::decode(&mut &bytes[..])
+					.map_err(|err| format!("Failed to decode executor params: {}", err))?
+			},
+			None => ExecutorParams::default(),
+		};
+		let prep_timeout = pvf_prep_timeout(&executor_params, kind);
+		let job_kind = match kind {
+			PvfPrepKind::Precheck => PrepareJobKind::Prechecking,
+			PvfPrepKind::Prepare => PrepareJobKind::Compilation,
+		};
+
+		Ok(PvfPrepData::from_code(
+			code,
+			executor_params,
+			prep_timeout,
+			job_kind,
+			self.validation_code_bomb_limit,
+		))
+	}
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExecKind {
+	/// Validation of a candidate by its backers.
+	Backing,
+	/// Validation of a candidate by approval checkers or dispute participants.
+	Approval,
+}
+
+/// The timeout of PVF preparation, as determined by candidate validation.
+fn pvf_prep_timeout(executor_params: &ExecutorParams, kind: PvfPrepKind) -> std::time::Duration {
+	executor_params.pvf_prep_timeout(kind).unwrap_or(match kind {
+		PvfPrepKind::Precheck => DEFAULT_PRECHECK_PREPARATION_TIMEOUT,
+		PvfPrepKind::Prepare => DEFAULT_LENIENT_PREPARATION_TIMEOUT,
+	})
+}
+
+/// The timeout of PVF execution, as determined by candidate validation.
+fn pvf_exec_timeout(
+	executor_params: &ExecutorParams,
+	kind: RuntimePvfExecKind,
+) -> std::time::Duration {
+	executor_params.pvf_exec_timeout(kind).unwrap_or(match kind {
+		RuntimePvfExecKind::Backing => DEFAULT_BACKING_EXECUTION_TIMEOUT,
+		RuntimePvfExecKind::Approval => DEFAULT_APPROVAL_EXECUTION_TIMEOUT,
+	})
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, String> {
+	std::fs::read(path).map_err(|err| format!("Failed to read {}: {}", path.display(), err))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), String> {
+	sp_tracing::try_init_simple();
+	let cli = Cli::parse();
+
+	let workers_path = match cli.workers_path {
+		Some(path) => path,
+		None => std::env::current_exe()
+			.ok()
+			.and_then(|exe| exe.parent().map(ToOwned::to_owned))
+			.ok_or("Failed to determine the workers path, use `--workers-path`")?,
+	};
+	let temp_cache_path;
+	let cache_path = match cli.cache_path {
+		Some(path) => path,
+		None => {
+			temp_cache_path = tempfile::tempdir()
+				.map_err(|err| format!("Failed to create the cache directory: {}", err))?;
+			temp_cache_path.path().to_owned()
+		},
+	};
+
+	let registry = Registry::new();
+	let metrics = Metrics::try_register(&registry)
+		.map_err(|err| format!("Failed to register metrics: {}", err))?;
+	let config = Config::new(
+		cache_path,
+		None,
+		!cli.insecure,
+		workers_path.join(PREPARE_BINARY_NAME),
+		workers_path.join(EXECUTE_BINARY_NAME),
+		1,
+		1,
+		1,
+	);
+	let (mut host, task) = start(config, metrics)
+		.await
+		.map_err(|err| format!("Failed to start the PVF host: {}", err))?;
+	tokio::spawn(task);
+
+	let started = Instant::now();
+	let result = match cli.command {
+		Command::Precheck(args) => {
+			let (result_tx, result_rx) = oneshot::channel();
+			host.precheck_pvf(args.prep_data(PvfPrepKind::Precheck)?, result_tx).await?;
+			let result = result_rx.await.map_err(|_| "The PVF host hung up")?;
+			result
+				.map(|()| println!("Preparation succeeded"))
+				.map_err(|err| format!("Preparation failed: {:?}", err))
+		},
+		Command::Execute { pvf, pov, exec_kind } => {
+			let pvf = pvf.prep_data(PvfPrepKind::Prepare)?;
+			let pov_file = read(&pov)?;
+			let mut input = &pov_file[..];
+			let pov = PoV::decode(&mut input)
+				.map_err(|err| format!("Failed to decode the PoV: {}", err))?;
+			let pvd = PersistedValidationData::decode(&mut input).map_err(|err| {
+				format!("Failed to decode the persisted validation data: {}", err)
+			})?;
+			let (exec_kind, priority) = match exec_kind {
+				ExecKind::Backing => (PvfExecKind::Backing(Hash::zero()), Priority::Normal),
+				ExecKind::Approval => (PvfExecKind::Approval, Priority::Critical),
+			};
+			let exec_timeout = pvf_exec_timeout(pvf.executor_params().as_ref(), exec_kind.into());
+
+			let (result_tx, result_rx) = oneshot::channel();
+			host.execute_pvf(
+				pvf,
+				exec_timeout,
+				Arc::new(pvd),
+				Arc::new(pov),
+				priority,
+				exec_kind,
+				result_tx,
+			)
+			.await?;
+			let result = result_rx.await.map_err(|_| "The PVF host hung up")?;
+			result
+				.map(|result| {
+					println!(
+						"Validation succeeded: new head {:?}, {} upward and {} horizontal messages, \
+						{} downward messages processed",
+						result.head_data.hash(),
+						result.upward_messages.len(),
+						result.horizontal_messages.len(),
+						result.processed_downward_messages,
+					)
+				})
+				.map_err(|err| format!("Validation failed: {:?}", err))
+		},
+	};
+	println!("Total time: {}ms", started.elapsed().as_millis());
+	report(&registry);
+
+	result
+}
+
+/// Print the preparation and execution stats observed by the PVF host.
+fn report(registry: &Registry) {
+	for family in registry.gather() {
+		let Some((_, description, unit)) =
+			REPORTED_METRICS.iter().find(|(name, ..)| *name == family.get_name())
+		else {
+			continue
+		};
+		for metric in family.get_metric() {
+			let histogram = metric.get_histogram();
+			if histogram.get_sample_count() > 0 {
+				println!("Host {}: {} {}", description, histogram.get_sample_sum(), unit);
+			}
+		}
+	}
+}
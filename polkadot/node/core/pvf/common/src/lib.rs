@@ -32,6 +32,11 @@ pub use sp_tracing;
 
 const LOG_TARGET: &str = "parachain::pvf-common";
 
+/// The version of wasmtime the PVFs are compiled with.
+///
+/// Compiled artifacts are only compatible with the wasmtime version that produced them.
+pub const RUNTIME_VERSION: &str = env!("SUBSTRATE_WASMTIME_VERSION");
+
 use codec::{Decode, Encode};
 use std::{
 	io::{self, Read, Write},
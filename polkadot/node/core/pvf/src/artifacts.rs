@@ -18,7 +18,9 @@
 //!
 //! # Lifecycle of an artifact
 //!
-//! 1. During node start-up, we prune all the cached artifacts, if any.
+//! 1. During node start-up, we load the artifacts cached by previous runs, if any. Artifacts are
+//!    only kept if they were compiled under the current [fingerprint][`artifact_fingerprint`] and
+//!    their content matches the checksum in their file name. Other artifacts are pruned.
 //!
 //! 2. In order to be executed, a PVF should be prepared first. This means that artifacts should
 //!    have an [`ArtifactState::Prepared`] entry for that artifact in the table. If not, the
@@ -54,9 +56,10 @@
 //!    older by a predefined parameter. This process is run very rarely (say, once a day). Once the
 //!    artifact is expired it is removed from disk eagerly atomically.
 
-use crate::{host::PrecheckResultSender, worker_interface::WORKER_DIR_PREFIX};
+use crate::{host::PrecheckResultSender, worker_interface::WORKER_DIR_PREFIX, LOG_TARGET};
 use always_assert::always;
-use polkadot_node_core_pvf_common::{error::PrepareError, pvf::PvfPrepData};
+use codec::Decode;
+use polkadot_node_core_pvf_common::{error::PrepareError, pvf::PvfPrepData, RUNTIME_VERSION};
 use polkadot_parachain_primitives::primitives::ValidationCodeHash;
 use polkadot_primitives::ExecutorParamsPrepHash;
 use std::{
@@ -72,17 +75,42 @@ const ARTIFACT_EXTENSION: &str = "pvf";
 /// The prefix that artifacts used to start with under the old naming scheme.
 const ARTIFACT_OLD_PREFIX: &str = "wasmtime_";
 
-pub fn generate_artifact_path(cache_path: &Path) -> PathBuf {
-	let file_name = {
+/// Returns the fingerprint of the node and wasmtime versions that artifacts are compiled with.
+///
+/// Artifacts are only compatible with the versions that compiled them, so artifacts with another
+/// fingerprint are never reused.
+pub fn artifact_fingerprint(node_version: Option<&str>) -> String {
+	format!("polkadot_v{}_wasmtime_v{}", node_version.unwrap_or_default(), RUNTIME_VERSION)
+}
+
+/// Generates the path of an artifact with the given fingerprint, ID and checksum.
+///
+/// The file name allows the artifact to be recognized across restarts. It ends with a random
+/// suffix, so that a re-prepared artifact never conflicts with a file that is still being swept.
+pub fn generate_artifact_path(
+	cache_path: &Path,
+	fingerprint: &str,
+	artifact_id: &ArtifactId,
+	checksum: &str,
+) -> PathBuf {
+	let suffix = {
 		use array_bytes::Hex;
 		use rand::RngCore;
-		let mut bytes = [0u8; 64];
+		let mut bytes = [0u8; 8];
 		rand::thread_rng().fill_bytes(&mut bytes);
-		bytes.hex("0x")
+		bytes.hex("")
 	};
-	let mut artifact_path = cache_path.join(file_name);
-	artifact_path.set_extension(ARTIFACT_EXTENSION);
-	artifact_path
+	// Versions in the fingerprint contain dots, so the extension can't be set with
+	// `PathBuf::set_extension`.
+	cache_path.join(format!(
+		"{}_{:#x}_{:#x}_0x{}_{}.{}",
+		fingerprint,
+		artifact_id.code_hash,
+		artifact_id.executor_params_prep_hash,
+		checksum,
+		suffix,
+		ARTIFACT_EXTENSION,
+	))
 }
 
 /// Identifier of an artifact. Encodes a code hash of the PVF and a hash of preparation-related
@@ -107,6 +135,28 @@ impl ArtifactId {
 	pub fn from_pvf_prep_data(pvf: &PvfPrepData) -> Self {
 		Self::new(pvf.code_hash(), pvf.executor_params().prep_hash())
 	}
+
+	/// Parses the ID and the checksum of an artifact from a file name produced by
+	/// [`generate_artifact_path`], if the artifact was compiled under the given fingerprint.
+	fn from_file_name(file_name: &str, fingerprint: &str) -> Option<(Self, String)> {
+		let name = file_name
+			.strip_suffix(ARTIFACT_EXTENSION)?
+			.strip_suffix('.')?
+			.strip_prefix(fingerprint)?
+			.strip_prefix('_')?;
+		let mut parts = name.split('_');
+		let code_hash = array_bytes::hex2array::<_, 32>(parts.next()?).ok()?;
+		let prep_hash = array_bytes::hex2array::<_, 32>(parts.next()?).ok()?;
+		let checksum = parts.next()?.strip_prefix("0x")?.to_owned();
+		// The random suffix must be the last part.
+		parts.next()?;
+		if parts.next().is_some() {
+			return None
+		}
+
+		let executor_params_prep_hash = ExecutorParamsPrepHash::decode(&mut &prep_hash[..]).ok()?;
+		Some((Self::new(code_hash.into(), executor_params_prep_hash), checksum))
+	}
 }
 
 /// A bundle of the artifact ID and the path.
@@ -212,27 +262,61 @@ impl Artifacts {
 		self.inner.keys().cloned().collect()
 	}
 
-	/// Create an empty table and the cache directory on-disk if it doesn't exist.
-	pub async fn new(cache_path: &Path) -> Self {
+	/// Create the table of the artifacts cached on-disk by previous runs, and the cache directory
+	/// if it doesn't exist.
+	///
+	/// Artifacts compiled under a different fingerprint than the one of `node_version`, or whose
+	/// content doesn't match their checksum, are removed.
+	pub async fn new(cache_path: &Path, node_version: Option<&str>) -> Self {
 		// Make sure that the cache path directory and all its parents are created.
 		let _ = tokio::fs::create_dir_all(cache_path).await;
 
-		// Delete any leftover artifacts and worker dirs from previous runs. We don't delete the
-		// entire cache directory in case the user made a mistake and set it to e.g. their home
-		// directory. This is a best-effort to do clean-up, so ignore any errors.
+		let fingerprint = artifact_fingerprint(node_version);
+		let now = SystemTime::now();
+		let mut inner = HashMap::new();
+
+		// Delete any leftover worker dirs and unusable artifacts from previous runs. We don't
+		// delete the entire cache directory in case the user made a mistake and set it to e.g.
+		// their home directory. This is a best-effort to do clean-up, so ignore any errors.
 		for entry in fs::read_dir(cache_path).into_iter().flatten().flatten() {
 			let path = entry.path();
 			let Some(file_name) = path.file_name().and_then(|f| f.to_str()) else { continue };
 			if path.is_dir() && file_name.starts_with(WORKER_DIR_PREFIX) {
 				let _ = fs::remove_dir_all(path);
-			} else if path.extension().map_or(false, |ext| ext == ARTIFACT_EXTENSION) ||
-				file_name.starts_with(ARTIFACT_OLD_PREFIX)
-			{
+			} else if file_name.starts_with(ARTIFACT_OLD_PREFIX) {
 				let _ = fs::remove_file(path);
+			} else if path.extension().is_some_and(|ext| ext == ARTIFACT_EXTENSION) {
+				let size = match ArtifactId::from_file_name(file_name, &fingerprint) {
+					// There may be several files of an artifact if the node was stopped before
+					// sweeping some of them. Only the first valid one is needed.
+					Some((artifact_id, checksum)) if !inner.contains_key(&artifact_id) =>
+						verify_checksum(&path, &checksum).await.map(|size| (artifact_id, size)),
+					_ => None,
+				};
+				let Some((artifact_id, size)) = size else {
+					gum::debug!(
+						target: LOG_TARGET,
+						"removing unusable artifact {}",
+						path.display(),
+					);
+					let _ = fs::remove_file(path);
+					continue
+				};
+
+				gum::debug!(
+					target: LOG_TARGET,
+					validation_code_hash = ?artifact_id.code_hash,
+					"reusing artifact {}",
+					path.display(),
+				);
+				inner.insert(
+					artifact_id,
+					ArtifactState::Prepared { path, last_time_needed: now, size },
+				);
 			}
 		}
 
-		Self { inner: HashMap::new() }
+		Self { inner }
 	}
 
 	/// Returns the state of the given artifact by its ID.
@@ -323,6 +407,22 @@ impl Artifacts {
 	}
 }
 
+/// Returns the size of the artifact at `path` if its content matches the `checksum`.
+async fn verify_checksum(path: &Path, checksum: &str) -> Option<u64> {
+	let bytes = match tokio::fs::read(path).await {
+		Ok(bytes) => bytes,
+		Err(err) => {
+			gum::warn!(target: LOG_TARGET, "failed to read artifact {}: {}", path.display(), err);
+			return None
+		},
+	};
+	if blake3::hash(&bytes).to_hex().as_str() != checksum {
+		gum::warn!(target: LOG_TARGET, "checksum mismatch of artifact {}", path.display());
+		return None
+	}
+	Some(bytes.len() as u64)
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::testing::artifact_id;
@@ -344,7 +444,7 @@ mod tests {
 		fs::write(cache_path.join("polkadot_..."), "test").unwrap();
 		fs::create_dir(cache_path.join("worker-prepare-test")).unwrap();
 
-		let artifacts = Artifacts::new(cache_path).await;
+		let artifacts = Artifacts::new(cache_path, None).await;
 
 		let entries: Vec<String> = fs::read_dir(&cache_path)
 			.unwrap()
@@ -357,20 +457,76 @@ mod tests {
 		assert_eq!(artifacts.len(), 0);
 	}
 
+	#[tokio::test]
+	async fn valid_artifacts_are_kept_on_startup() {
+		let tempdir = tempfile::tempdir().unwrap();
+		let cache_path = tempdir.path();
+		let content = b"artifact";
+		let checksum = blake3::hash(content).to_hex().to_string();
+		let write_artifact = |node_version: &str, artifact_id: ArtifactId, checksum: &str| {
+			let path = generate_artifact_path(
+				cache_path,
+				&artifact_fingerprint(Some(node_version)),
+				&artifact_id,
+				checksum,
+			);
+			fs::write(&path, content).unwrap();
+			path
+		};
+
+		// Should be kept.
+		let valid = write_artifact("1.0.0", artifact_id(1), &checksum);
+		// Should be removed, the node version changed.
+		let outdated = write_artifact("0.9.0", artifact_id(2), &checksum);
+		// Should be removed, the content doesn't match the checksum.
+		let corrupted = write_artifact("1.0.0", artifact_id(3), "00");
+		// Should be removed, only a single file is needed for each artifact.
+		let duplicate = write_artifact("1.0.0", artifact_id(1), &checksum);
+
+		let mut artifacts = Artifacts::new(cache_path, Some("1.0.0")).await;
+
+		assert!(!outdated.exists());
+		assert!(!corrupted.exists());
+		assert_eq!(artifacts.artifact_ids(), vec![artifact_id(1)]);
+		let Some(ArtifactState::Prepared { path, size, .. }) =
+			artifacts.artifact_state_mut(&artifact_id(1))
+		else {
+			panic!("artifact is not prepared");
+		};
+		assert_eq!(*size, content.len() as u64);
+		assert!(*path == valid || *path == duplicate);
+		assert!(valid.exists() != duplicate.exists());
+	}
+
+	#[test]
+	fn artifact_id_is_parsed_from_file_name() {
+		let fingerprint = artifact_fingerprint(Some("1.0.0"));
+		let path = generate_artifact_path(Path::new(""), &fingerprint, &artifact_id(1), "abcd");
+		let file_name = path.file_name().unwrap().to_str().unwrap();
+
+		assert_eq!(
+			ArtifactId::from_file_name(file_name, &fingerprint),
+			Some((artifact_id(1), String::from("abcd")))
+		);
+		assert_eq!(ArtifactId::from_file_name(file_name, &artifact_fingerprint(Some("1.0"))), None);
+		assert_eq!(ArtifactId::from_file_name("abcd.pvf", &fingerprint), None);
+	}
+
 	#[tokio::test]
 	async fn test_pruned_by_cache_size() {
 		let mock_now = SystemTime::now();
 		let tempdir = tempfile::tempdir().unwrap();
 		let cache_path = tempdir.path();
 
-		let path1 = generate_artifact_path(cache_path);
-		let path2 = generate_artifact_path(cache_path);
-		let path3 = generate_artifact_path(cache_path);
+		let fingerprint = artifact_fingerprint(None);
 		let artifact_id1 = artifact_id(1);
 		let artifact_id2 = artifact_id(2);
 		let artifact_id3 = artifact_id(3);
+		let path1 = generate_artifact_path(cache_path, &fingerprint, &artifact_id1, "");
+		let path2 = generate_artifact_path(cache_path, &fingerprint, &artifact_id2, "");
+		let path3 = generate_artifact_path(cache_path, &fingerprint, &artifact_id3, "");
 
-		let mut artifacts = Artifacts::new(cache_path).await;
+		let mut artifacts = Artifacts::new(cache_path, None).await;
 		let cleanup_config = ArtifactsCleanupConfig::new(1500, Duration::from_secs(0));
 
 		artifacts.insert_prepared(
@@ -408,14 +564,15 @@ mod tests {
 		let tempdir = tempfile::tempdir().unwrap();
 		let cache_path = tempdir.path();
 
-		let path1 = generate_artifact_path(cache_path);
-		let path2 = generate_artifact_path(cache_path);
-		let path3 = generate_artifact_path(cache_path);
+		let fingerprint = artifact_fingerprint(None);
 		let artifact_id1 = artifact_id(1);
 		let artifact_id2 = artifact_id(2);
 		let artifact_id3 = artifact_id(3);
+		let path1 = generate_artifact_path(cache_path, &fingerprint, &artifact_id1, "");
+		let path2 = generate_artifact_path(cache_path, &fingerprint, &artifact_id2, "");
+		let path3 = generate_artifact_path(cache_path, &fingerprint, &artifact_id3, "");
 
-		let mut artifacts = Artifacts::new(cache_path).await;
+		let mut artifacts = Artifacts::new(cache_path, None).await;
 		let cleanup_config = ArtifactsCleanupConfig::new(1500, Duration::from_secs(12));
 
 		artifacts.insert_prepared(
@@ -250,7 +250,7 @@ pub async fn start(
 	gum::debug!(target: LOG_TARGET, ?config, "starting PVF validation host");
 
 	// Make sure the cache is initialized before doing anything else.
-	let artifacts = Artifacts::new(&config.cache_path).await;
+	let artifacts = Artifacts::new(&config.cache_path, config.node_version.as_deref()).await;
 
 	// Run checks for supported security features once per host startup. If some checks fail, warn
 	// if Secure Validator Mode is disabled and return an error otherwise.
@@ -1017,7 +1017,11 @@ fn pulse_every(interval: std::time::Duration) -> impl futures::Stream<Item = ()>
 #[cfg(test)]
 pub(crate) mod tests {
 	use super::*;
-	use crate::{artifacts::generate_artifact_path, testing::artifact_id, PossiblyInvalidError};
+	use crate::{
+		artifacts::{artifact_fingerprint, generate_artifact_path},
+		testing::artifact_id,
+		PossiblyInvalidError,
+	};
 	use assert_matches::assert_matches;
 	use futures::future::BoxFuture;
 	use polkadot_node_primitives::BlockData;
@@ -1237,8 +1241,9 @@ pub(crate) mod tests {
 		let mut builder = Builder::default();
 		builder.cleanup_pulse_interval = Duration::from_millis(100);
 		builder.cleanup_config = ArtifactsCleanupConfig::new(1024, Duration::from_secs(0));
-		let path1 = generate_artifact_path(cache_path);
-		let path2 = generate_artifact_path(cache_path);
+		let fingerprint = artifact_fingerprint(None);
+		let path1 = generate_artifact_path(cache_path, &fingerprint, &artifact_id(1), "");
+		let path2 = generate_artifact_path(cache_path, &fingerprint, &artifact_id(2), "");
 		builder.artifacts.insert_prepared(artifact_id(1), path1.clone(), mock_now, 1024);
 		builder.artifacts.insert_prepared(artifact_id(2), path2.clone(), mock_now, 1024);
 		let mut test = builder.build();
@@ -17,7 +17,7 @@
 //! Host interface to the prepare worker.
 
 use crate::{
-	artifacts::generate_artifact_path,
+	artifacts::{artifact_fingerprint, generate_artifact_path, ArtifactId},
 	metrics::Metrics,
 	worker_interface::{
 		clear_worker_dir_path, framed_recv, framed_send, spawn_with_program_path, IdleWorker,
@@ -115,6 +115,7 @@ pub async fn start_work(
 	worker: IdleWorker,
 	pvf: PvfPrepData,
 	cache_path: PathBuf,
+	node_version: Option<String>,
 ) -> Outcome {
 	let IdleWorker { stream, pid, worker_dir } = worker;
 
@@ -165,6 +166,8 @@ pub async fn start_work(
 						pid,
 						tmp_artifact_file,
 						&cache_path,
+						&ArtifactId::from_pvf_prep_data(&pvf),
+						node_version.as_deref(),
 						preparation_timeout,
 					)
 					.await,
@@ -204,12 +207,12 @@ async fn handle_response(
 	worker_pid: u32,
 	tmp_file: PathBuf,
 	cache_path: &Path,
+	artifact_id: &ArtifactId,
+	node_version: Option<&str>,
 	preparation_timeout: Duration,
 ) -> Outcome {
-	// TODO: Add `checksum` to `ArtifactPathId`. See:
-	//       https://github.com/paritytech/polkadot-sdk/issues/2399
 	let PrepareWorkerSuccess {
-		checksum: _,
+		checksum,
 		stats: PrepareStats { cpu_time_elapsed, memory_stats, observed_wasm_code_len },
 	} = match result.clone() {
 		Ok(result) => result,
@@ -235,12 +238,12 @@ async fn handle_response(
 		return Outcome::TimedOut
 	}
 
-	let size = match tokio::fs::metadata(cache_path).await {
+	let size = match tokio::fs::metadata(&tmp_file).await {
 		Ok(metadata) => metadata.len(),
 		Err(err) => {
 			gum::warn!(
 				target: LOG_TARGET,
-				?cache_path,
+				?tmp_file,
 				"failed to read size of the artifact: {}",
 				err,
 			);
@@ -248,11 +251,14 @@ async fn handle_response(
 		},
 	};
 
-	// The file name should uniquely identify the artifact even across restarts. In case the cache
-	// for some reason is not cleared correctly, we cannot
-	// accidentally execute an artifact compiled under a different wasmtime version, host
-	// environment, etc.
-	let artifact_path = generate_artifact_path(cache_path);
+	// The file name identifies the artifact across restarts, so that it can be reused if it was
+	// compiled under the same wasmtime and node versions, and its content is left intact.
+	let artifact_path = generate_artifact_path(
+		cache_path,
+		&artifact_fingerprint(node_version),
+		artifact_id,
+		&checksum,
+	);
 
 	gum::debug!(
 		target: LOG_TARGET,
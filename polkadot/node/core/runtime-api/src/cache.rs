@@ -23,7 +23,8 @@ use polkadot_primitives::{
 	async_backing, slashing,
 	vstaging::{
 		self, async_backing::Constraints, CandidateEvent,
-		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreState, ScrapedOnChainVotes,
+		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreState,
+		ScrapedOnChainBitfields, ScrapedOnChainVotes,
 	},
 	ApprovalVotingParams, AuthorityDiscoveryId, BlockNumber, CandidateCommitments, CandidateHash,
 	CoreIndex, DisputeState, ExecutorParams, GroupRotationInfo, Hash, Id as ParaId,
@@ -61,6 +62,7 @@ pub(crate) struct RequestResultCache {
 		LruMap<(Hash, ParaId), BTreeMap<ParaId, Vec<InboundHrmpMessage<BlockNumber>>>>,
 	current_babe_epoch: LruMap<Hash, Epoch>,
 	on_chain_votes: LruMap<Hash, Option<ScrapedOnChainVotes>>,
+	on_chain_bitfields: LruMap<Hash, Option<ScrapedOnChainBitfields>>,
 	pvfs_require_precheck: LruMap<Hash, Vec<ValidationCodeHash>>,
 	validation_code_hash:
 		LruMap<(Hash, ParaId, OccupiedCoreAssumption), Option<ValidationCodeHash>>,
@@ -102,6 +104,7 @@ impl Default for RequestResultCache {
 			inbound_hrmp_channels_contents: LruMap::new(ByLength::new(DEFAULT_CACHE_CAP)),
 			current_babe_epoch: LruMap::new(ByLength::new(DEFAULT_CACHE_CAP)),
 			on_chain_votes: LruMap::new(ByLength::new(DEFAULT_CACHE_CAP)),
+			on_chain_bitfields: LruMap::new(ByLength::new(DEFAULT_CACHE_CAP)),
 			pvfs_require_precheck: LruMap::new(ByLength::new(DEFAULT_CACHE_CAP)),
 			validation_code_hash: LruMap::new(ByLength::new(DEFAULT_CACHE_CAP)),
 			version: LruMap::new(ByLength::new(DEFAULT_CACHE_CAP)),
@@ -376,6 +379,21 @@ impl RequestResultCache {
 		self.on_chain_votes.insert(relay_parent, scraped);
 	}
 
+	pub(crate) fn on_chain_bitfields(
+		&mut self,
+		relay_parent: &Hash,
+	) -> Option<&Option<ScrapedOnChainBitfields>> {
+		self.on_chain_bitfields.get(relay_parent).map(|v| &*v)
+	}
+
+	pub(crate) fn cache_on_chain_bitfields(
+		&mut self,
+		relay_parent: Hash,
+		scraped: Option<ScrapedOnChainBitfields>,
+	) {
+		self.on_chain_bitfields.insert(relay_parent, scraped);
+	}
+
 	pub(crate) fn pvfs_require_precheck(
 		&mut self,
 		relay_parent: &Hash,
@@ -655,4 +673,5 @@ pub(crate) enum RequestResult {
 	BackingConstraints(Hash, ParaId, Option<Constraints>),
 	SchedulingLookahead(SessionIndex, u32),
 	ValidationCodeBombLimit(SessionIndex, u32),
+	FetchOnChainBitfields(Hash, Option<ScrapedOnChainBitfields>),
 }
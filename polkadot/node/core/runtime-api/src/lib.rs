@@ -191,6 +191,8 @@ where
 				.cache_scheduling_lookahead(session_index, scheduling_lookahead),
 			ValidationCodeBombLimit(session_index, limit) =>
 				self.requests_cache.cache_validation_code_bomb_limit(session_index, limit),
+			FetchOnChainBitfields(relay_parent, scraped) =>
+				self.requests_cache.cache_on_chain_bitfields(relay_parent, scraped),
 		}
 	}
 
@@ -368,6 +370,8 @@ where
 					Some(Request::ValidationCodeBombLimit(index, sender))
 				}
 			},
+			Request::FetchOnChainBitfields(sender) => query!(on_chain_bitfields(), sender)
+				.map(|sender| Request::FetchOnChainBitfields(sender)),
 		}
 	}
 
@@ -702,5 +706,11 @@ where
 			sender,
 			result = (index)
 		),
+		Request::FetchOnChainBitfields(sender) => query!(
+			FetchOnChainBitfields,
+			on_chain_bitfields(),
+			ver = Request::ON_CHAIN_BITFIELDS_RUNTIME_REQUIREMENT,
+			sender
+		),
 	}
 }
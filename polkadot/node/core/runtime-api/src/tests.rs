@@ -23,7 +23,8 @@ use polkadot_primitives::{
 	async_backing, slashing, vstaging,
 	vstaging::{
 		async_backing::Constraints, CandidateEvent,
-		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreState, ScrapedOnChainVotes,
+		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreState,
+		ScrapedOnChainBitfields, ScrapedOnChainVotes,
 	},
 	ApprovalVotingParams, AuthorityDiscoveryId, BlockNumber, CandidateCommitments, CandidateHash,
 	CoreIndex, DisputeState, ExecutorParams, GroupRotationInfo, Id as ParaId,
@@ -323,6 +324,13 @@ impl RuntimeApiSubsystemClient for MockSubsystemClient {
 	async fn validation_code_bomb_limit(&self, _: Hash) -> Result<u32, ApiError> {
 		todo!("Not required for tests")
 	}
+
+	async fn on_chain_bitfields(
+		&self,
+		_: Hash,
+	) -> Result<Option<ScrapedOnChainBitfields<Hash>>, ApiError> {
+		todo!("Not required for tests")
+	}
 }
 
 #[test]
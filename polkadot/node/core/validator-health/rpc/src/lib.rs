@@ -0,0 +1,161 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface to the validator health subsystem.
+//!
+//! Lets operators check how their validator performed during the recent sessions, as observed by
+//! the node itself. The methods are unsafe, as they reveal which validator the node is.
+
+#![warn(missing_docs)]
+
+use futures::channel::oneshot;
+use jsonrpsee::{
+	core::async_trait,
+	proc_macros::rpc,
+	types::{ErrorObject, ErrorObjectOwned},
+	Extensions,
+};
+use polkadot_node_subsystem::messages::{self, ValidatorHealthMessage};
+use polkadot_overseer::Handle;
+use polkadot_primitives::SessionIndex;
+use sc_rpc_api::{check_if_safe, UnsafeRpcError};
+use serde::{Deserialize, Serialize};
+
+const VALIDATOR_HEALTH_ERROR: i32 = 9700;
+
+/// Provides RPC methods to check the health of the node as a parachain validator.
+#[rpc(client, server)]
+pub trait ValidatorHealthApi {
+	/// Returns how the node performed as a parachain validator during the given session, or
+	/// during the current session if none is given.
+	///
+	/// Returns `null` if the node is not a validator in the session, or if the session is too
+	/// old to be tracked.
+	#[method(name = "validatorHealth_session", with_extensions)]
+	async fn session(
+		&self,
+		session_index: Option<SessionIndex>,
+	) -> Result<Option<SessionHealth>, Error>;
+}
+
+/// Provides RPC methods to check the health of the node as a parachain validator.
+pub struct ValidatorHealth {
+	/// A handle to the overseer, to query the validator health subsystem.
+	overseer: Handle,
+}
+
+impl ValidatorHealth {
+	/// Creates a new instance of the validator health RPC handler.
+	pub fn new(overseer: Handle) -> Self {
+		Self { overseer }
+	}
+}
+
+#[async_trait]
+impl ValidatorHealthApiServer for ValidatorHealth {
+	async fn session(
+		&self,
+		ext: &Extensions,
+		session_index: Option<SessionIndex>,
+	) -> Result<Option<SessionHealth>, Error> {
+		check_if_safe(ext)?;
+
+		let (tx, rx) = oneshot::channel();
+		self.overseer
+			.clone()
+			.send_msg(
+				ValidatorHealthMessage::GetSessionHealth(session_index, tx),
+				"validator-health-rpc",
+			)
+			.await;
+		let health = rx.await.map_err(|_| Error::Unavailable)?;
+
+		Ok(health.map(Into::into))
+	}
+}
+
+/// The health of the node as a parachain validator during a session.
+///
+/// All the counters only cover the time the node was running during the session.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionHealth {
+	/// The session the report is about.
+	pub session_index: SessionIndex,
+	/// The index of the node in the validators of the session.
+	pub validator_index: u32,
+	/// Whether the node is in the disabled set of the session.
+	pub disabled: bool,
+	/// The number of candidates backed on chain with a statement of the node.
+	pub candidates_backed: u32,
+	/// The number of availability bitfields the node signed.
+	pub bitfields_signed: u32,
+	/// The number of bitfields of the node included in a block.
+	pub bitfields_included: u32,
+	/// The total number of bits set in the included bitfields of the node, i.e. the number of
+	/// times the node attested on chain to hold its chunk of a candidate pending availability.
+	pub availability_bits_set: u32,
+	/// The number of candidates the node triggered an approval assignment for.
+	pub approval_assignments: u32,
+	/// The number of candidates the node approved.
+	pub approvals: u32,
+	/// The number of approved candidates for which the node was a no-show.
+	pub no_shows: u32,
+	/// The number of recent disputes raised in the session.
+	pub disputes: u32,
+	/// The number of recent disputes in the session the node voted in.
+	pub disputes_participated: u32,
+}
+
+impl From<messages::SessionHealth> for SessionHealth {
+	fn from(health: messages::SessionHealth) -> Self {
+		Self {
+			session_index: health.session_index,
+			validator_index: health.validator_index.0,
+			disabled: health.disabled,
+			candidates_backed: health.candidates_backed,
+			bitfields_signed: health.bitfields_signed,
+			bitfields_included: health.bitfields_included,
+			availability_bits_set: health.availability_bits_set,
+			approval_assignments: health.approvals.assignments,
+			approvals: health.approvals.approvals,
+			no_shows: health.approvals.no_shows,
+			disputes: health.disputes,
+			disputes_participated: health.disputes_participated,
+		}
+	}
+}
+
+/// Top-level error type for the RPC handler.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The validator health subsystem didn't answer, the node is likely not a validator.
+	#[error("The validator health subsystem is not available")]
+	Unavailable,
+	/// Call to an unsafe RPC was denied.
+	#[error(transparent)]
+	UnsafeRpcCalled(#[from] UnsafeRpcError),
+}
+
+impl From<Error> for ErrorObjectOwned {
+	fn from(error: Error) -> Self {
+		match error {
+			Error::Unavailable =>
+				ErrorObject::owned(VALIDATOR_HEALTH_ERROR + 1, error.to_string(), None::<()>),
+			Error::UnsafeRpcCalled(e) => e.into(),
+		}
+	}
+}
@@ -0,0 +1,343 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The validator health subsystem.
+//!
+//! Keeps track of how this node performs as a parachain validator during the recent sessions, so
+//! operators can check it without piecing it together from metrics and logs: the candidates it
+//! backed, the bitfields it signed, its approval work, the disputes it participated in and whether
+//! it got disabled.
+//!
+//! Backing, disabling and the bitfields included on chain are scraped from the runtime at every
+//! active leaf. Bitfield signing reports the bitfields it signs. The approval and dispute figures
+//! are already tracked by the respective subsystems, so they are fetched when the health of a
+//! session is requested.
+
+#![deny(unused_crate_dependencies)]
+#![warn(missing_docs)]
+
+use futures::{channel::oneshot, FutureExt};
+use polkadot_node_subsystem::{
+	messages::{
+		ApprovalVotingMessage, ApprovalVotingParallelMessage, DisputeCoordinatorMessage,
+		RuntimeApiMessage, SessionHealth, ValidatorHealthMessage,
+	},
+	overseer, FromOrchestra, OverseerSignal, SpawnedSubsystem, SubsystemError, SubsystemResult,
+	SubsystemSender,
+};
+use polkadot_node_subsystem_util::runtime::{
+	self, get_on_chain_bitfields, get_on_chain_votes, RuntimeInfo,
+};
+use polkadot_primitives::{CandidateHash, Hash, SessionIndex, ValidatorIndex};
+use schnellru::{ByLength, LruMap};
+use sp_keystore::KeystorePtr;
+use std::collections::HashSet;
+
+#[cfg(test)]
+mod tests;
+
+const LOG_TARGET: &str = "parachain::validator-health";
+
+/// The number of sessions the health of the node is kept for.
+const SESSIONS_TO_KEEP: u32 = 6;
+
+/// The validator health subsystem.
+pub struct ValidatorHealthSubsystem {
+	keystore: KeystorePtr,
+	approval_voting_parallel_enabled: bool,
+}
+
+impl ValidatorHealthSubsystem {
+	/// Create a new instance of the `ValidatorHealthSubsystem`.
+	pub fn new(keystore: KeystorePtr, approval_voting_parallel_enabled: bool) -> Self {
+		Self { keystore, approval_voting_parallel_enabled }
+	}
+}
+
+#[overseer::subsystem(ValidatorHealth, error=SubsystemError, prefix=self::overseer)]
+impl<Context> ValidatorHealthSubsystem {
+	fn start(self, ctx: Context) -> SpawnedSubsystem {
+		let future = run(ctx, self).boxed();
+
+		SpawnedSubsystem { name: "validator-health-subsystem", future }
+	}
+}
+
+/// The health of the node during a session, as observed so far.
+struct SessionState {
+	health: SessionHealth,
+	/// The candidates counted in `health.candidates_backed`. Blocks of different forks might
+	/// include the same candidate.
+	backed: HashSet<CandidateHash>,
+	/// The relay parents of the bitfields counted in `health.bitfields_included`. Blocks of
+	/// different forks might include the same bitfield.
+	included_bitfields: HashSet<Hash>,
+}
+
+impl SessionState {
+	fn new(session_index: SessionIndex, validator_index: ValidatorIndex) -> Self {
+		Self {
+			health: SessionHealth { session_index, validator_index, ..Default::default() },
+			backed: HashSet::new(),
+			included_bitfields: HashSet::new(),
+		}
+	}
+}
+
+struct State {
+	runtime_info: RuntimeInfo,
+	/// The sessions in which this node is a validator.
+	sessions: LruMap<SessionIndex, SessionState>,
+	/// The session of the most recent active leaf.
+	current_session: Option<SessionIndex>,
+}
+
+impl State {
+	fn new(keystore: KeystorePtr) -> Self {
+		Self {
+			runtime_info: RuntimeInfo::new(Some(keystore)),
+			sessions: LruMap::new(ByLength::new(SESSIONS_TO_KEEP)),
+			current_session: None,
+		}
+	}
+
+	/// Scrape the disabled validators and the on-chain backing votes at a new active leaf.
+	async fn process_active_leaf<Sender>(
+		&mut self,
+		sender: &mut Sender,
+		leaf: Hash,
+	) -> Result<(), runtime::Error>
+	where
+		Sender: SubsystemSender<RuntimeApiMessage>,
+	{
+		let session_index = self.runtime_info.get_session_index_for_child(sender, leaf).await?;
+		self.current_session =
+			Some(self.current_session.map_or(session_index, |current| current.max(session_index)));
+
+		let Some(validator_index) = self
+			.runtime_info
+			.get_session_info_by_index(sender, leaf, session_index)
+			.await?
+			.validator_info
+			.our_index
+		else {
+			return Ok(())
+		};
+		let disabled = self
+			.runtime_info
+			.get_disabled_validators(sender, leaf)
+			.await?
+			.contains(&validator_index);
+		let on_chain_votes = get_on_chain_votes(sender, leaf).await?;
+
+		let session = self
+			.sessions
+			.get_or_insert(session_index, || SessionState::new(session_index, validator_index))
+			.expect("Length limited cache always accepts new entries; qed");
+		session.health.disabled = disabled;
+
+		let backed = on_chain_votes
+			.into_iter()
+			.filter(|votes| votes.session == session_index)
+			.flat_map(|votes| votes.backing_validators_per_candidate)
+			.filter(|(_, backers)| backers.iter().any(|(index, _)| *index == validator_index))
+			.map(|(receipt, _)| receipt.hash());
+		for candidate_hash in backed {
+			if session.backed.insert(candidate_hash) {
+				session.health.candidates_backed += 1;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Account for the bitfield of this node included on chain in the block of an active leaf.
+	async fn note_included_bitfield<Sender>(
+		&mut self,
+		sender: &mut Sender,
+		leaf: Hash,
+	) -> Result<(), runtime::Error>
+	where
+		Sender: SubsystemSender<RuntimeApiMessage>,
+	{
+		let Some(scraped) = get_on_chain_bitfields(sender, leaf).await? else { return Ok(()) };
+		let Some(session) = self.sessions.get(&scraped.session) else { return Ok(()) };
+		let validator_index = session.health.validator_index;
+		let Some((_, bitfield)) =
+			scraped.bitfields.iter().find(|(index, _)| *index == validator_index)
+		else {
+			return Ok(())
+		};
+		if session.included_bitfields.insert(scraped.relay_parent) {
+			session.health.bitfields_included += 1;
+			session.health.availability_bits_set += bitfield.0.count_ones() as u32;
+		}
+
+		Ok(())
+	}
+
+	/// Account for a bitfield signed by this node.
+	async fn note_bitfield_signed<Sender>(
+		&mut self,
+		sender: &mut Sender,
+		relay_parent: Hash,
+	) -> Result<(), runtime::Error>
+	where
+		Sender: SubsystemSender<RuntimeApiMessage>,
+	{
+		let session_index =
+			self.runtime_info.get_session_index_for_child(sender, relay_parent).await?;
+		if let Some(session) = self.sessions.get(&session_index) {
+			session.health.bitfields_signed += 1;
+		}
+
+		Ok(())
+	}
+}
+
+#[overseer::contextbounds(ValidatorHealth, prefix = self::overseer)]
+async fn run<Context>(
+	mut ctx: Context,
+	subsystem: ValidatorHealthSubsystem,
+) -> SubsystemResult<()> {
+	let mut state = State::new(subsystem.keystore);
+
+	loop {
+		match ctx.recv().await? {
+			FromOrchestra::Signal(OverseerSignal::ActiveLeaves(update)) =>
+				if let Some(leaf) = update.activated {
+					if let Err(err) = state.process_active_leaf(ctx.sender(), leaf.hash).await {
+						gum::debug!(
+							target: LOG_TARGET,
+							leaf = ?leaf.hash,
+							?err,
+							"Failed to scrape the active leaf",
+						);
+					}
+					if let Err(err) = state.note_included_bitfield(ctx.sender(), leaf.hash).await {
+						gum::debug!(
+							target: LOG_TARGET,
+							leaf = ?leaf.hash,
+							?err,
+							"Failed to account for the included bitfields",
+						);
+					}
+				},
+			FromOrchestra::Signal(OverseerSignal::BlockFinalized(..)) => {},
+			FromOrchestra::Signal(OverseerSignal::Conclude) => return Ok(()),
+			FromOrchestra::Communication { msg } => match msg {
+				ValidatorHealthMessage::BitfieldSigned(relay_parent) => {
+					if let Err(err) = state.note_bitfield_signed(ctx.sender(), relay_parent).await {
+						gum::debug!(
+							target: LOG_TARGET,
+							?relay_parent,
+							?err,
+							"Failed to account for a signed bitfield",
+						);
+					}
+				},
+				ValidatorHealthMessage::GetSessionHealth(session_index, tx) => {
+					let health = session_index
+						.or(state.current_session)
+						.and_then(|session_index| state.sessions.peek(&session_index))
+						.map(|session| session.health.clone());
+					match health {
+						Some(health) => ctx.spawn(
+							"validator-health-query",
+							complete_session_health(
+								ctx.sender().clone(),
+								health,
+								subsystem.approval_voting_parallel_enabled,
+								tx,
+							)
+							.boxed(),
+						)?,
+						None => {
+							let _ = tx.send(None);
+						},
+					}
+				},
+			},
+		}
+	}
+}
+
+/// Add the approval and dispute figures to the health of a session and send it back.
+///
+/// The figures of subsystems failing to answer are left at zero.
+async fn complete_session_health<Sender>(
+	mut sender: Sender,
+	mut health: SessionHealth,
+	approval_voting_parallel_enabled: bool,
+	tx: oneshot::Sender<Option<SessionHealth>>,
+) where
+	Sender: overseer::ValidatorHealthSenderTrait,
+{
+	let SessionHealth { session_index, validator_index, .. } = health;
+
+	let (approvals_tx, approvals_rx) = oneshot::channel();
+	if approval_voting_parallel_enabled {
+		sender
+			.send_message(ApprovalVotingParallelMessage::GetApprovalStats(
+				session_index,
+				validator_index,
+				approvals_tx,
+			))
+			.await;
+	} else {
+		sender
+			.send_message(ApprovalVotingMessage::GetApprovalStats(
+				session_index,
+				validator_index,
+				approvals_tx,
+			))
+			.await;
+	}
+	health.approvals = approvals_rx.await.unwrap_or_default();
+
+	let (disputes_tx, disputes_rx) = oneshot::channel();
+	sender
+		.send_message(DisputeCoordinatorMessage::RecentDisputes(disputes_tx))
+		.await;
+	let disputes: Vec<_> = disputes_rx
+		.await
+		.unwrap_or_default()
+		.into_iter()
+		.filter(|(session, _, _)| *session == session_index)
+		.map(|(session, candidate_hash, _)| (session, candidate_hash))
+		.collect();
+	health.disputes = disputes.len() as u32;
+
+	if !disputes.is_empty() {
+		let (votes_tx, votes_rx) = oneshot::channel();
+		sender
+			.send_message(DisputeCoordinatorMessage::QueryCandidateVotes(disputes, votes_tx))
+			.await;
+		health.disputes_participated = votes_rx
+			.await
+			.unwrap_or_default()
+			.iter()
+			.filter(|(_, _, votes)| {
+				votes.valid.raw().contains_key(&validator_index) ||
+					votes.invalid.contains_key(&validator_index)
+			})
+			.count() as u32;
+	}
+
+	if tx.send(Some(health)).is_err() {
+		gum::debug!(target: LOG_TARGET, "Session health requester went away");
+	}
+}
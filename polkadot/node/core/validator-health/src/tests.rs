@@ -0,0 +1,361 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use assert_matches::assert_matches;
+use futures::{executor, future, pin_mut};
+use polkadot_node_primitives::{CandidateVotes, DisputeStatus};
+use polkadot_node_subsystem::{
+	messages::{AllMessages, ApprovalStats, RuntimeApiRequest},
+	ActiveLeavesUpdate,
+};
+use polkadot_node_subsystem_test_helpers::{
+	make_subsystem_context, mock::new_leaf, TestSubsystemContextHandle,
+};
+use polkadot_primitives::{
+	vstaging::{ScrapedOnChainBitfields, ScrapedOnChainVotes},
+	AvailabilityBitfield, ExecutorParams, NodeFeatures, SessionInfo, ValidDisputeStatementKind,
+	ValidatorId, ValidityAttestation,
+};
+use polkadot_primitives_test_helpers::{dummy_candidate_receipt_v2, dummy_signature};
+use sp_application_crypto::AppCrypto;
+use sp_core::testing::TaskExecutor;
+use sp_keyring::Sr25519Keyring;
+use sp_keystore::Keystore;
+use std::{collections::BTreeMap, sync::Arc};
+
+type VirtualOverseer = TestSubsystemContextHandle<ValidatorHealthMessage>;
+
+const SESSION: SessionIndex = 2;
+const OUR_VALIDATOR: Sr25519Keyring = Sr25519Keyring::Alice;
+
+fn make_session_info() -> SessionInfo {
+	let validators = [OUR_VALIDATOR, Sr25519Keyring::Bob, Sr25519Keyring::Charlie];
+	SessionInfo {
+		validators: validators.iter().map(|k| k.public().into()).collect(),
+		discovery_keys: validators.iter().map(|k| k.public().into()).collect(),
+		assignment_keys: vec![],
+		validator_groups: Default::default(),
+		n_cores: 1,
+		zeroth_delay_tranche_width: 0,
+		relay_vrf_modulo_samples: 0,
+		n_delay_tranches: 2,
+		no_show_slots: 0,
+		needed_approvals: 1,
+		active_validator_indices: vec![],
+		dispute_period: 6,
+		random_seed: [0u8; 32],
+	}
+}
+
+fn test_harness(
+	approval_voting_parallel_enabled: bool,
+	test: impl FnOnce(VirtualOverseer) -> future::BoxFuture<'static, VirtualOverseer>,
+) {
+	let pool = TaskExecutor::new();
+	let (ctx, handle) = make_subsystem_context::<ValidatorHealthMessage, _>(pool);
+	let keystore: KeystorePtr = Arc::new(sc_keystore::LocalKeystore::in_memory());
+	Keystore::sr25519_generate_new(&*keystore, ValidatorId::ID, Some(&OUR_VALIDATOR.to_seed()))
+		.expect("Generating keys for our node failed");
+
+	let subsystem =
+		run(ctx, ValidatorHealthSubsystem::new(keystore, approval_voting_parallel_enabled));
+	let test_fut = async move {
+		let mut handle = test(handle).await;
+		handle.send(FromOrchestra::Signal(OverseerSignal::Conclude)).await;
+	};
+
+	pin_mut!(subsystem);
+	pin_mut!(test_fut);
+	executor::block_on(future::join(subsystem.map(|res| res.unwrap()), test_fut));
+}
+
+/// Activate a leaf in `SESSION` at which `backers` backed a candidate and the `bitfields` signed
+/// at `relay_parent` were included.
+async fn activate_leaf(
+	handle: &mut VirtualOverseer,
+	leaf: Hash,
+	first_in_session: bool,
+	disabled: Vec<ValidatorIndex>,
+	backers: Vec<ValidatorIndex>,
+	(relay_parent, bitfields): (Hash, Vec<(ValidatorIndex, AvailabilityBitfield)>),
+) {
+	handle
+		.send(FromOrchestra::Signal(OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(
+			new_leaf(leaf, 1),
+		))))
+		.await;
+
+	assert_matches!(
+		handle.recv().await,
+		AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+			relay_parent,
+			RuntimeApiRequest::SessionIndexForChild(tx),
+		)) => {
+			assert_eq!(relay_parent, leaf);
+			tx.send(Ok(SESSION)).unwrap();
+		}
+	);
+	if first_in_session {
+		assert_matches!(
+			handle.recv().await,
+			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+				_,
+				RuntimeApiRequest::SessionInfo(SESSION, tx),
+			)) => {
+				tx.send(Ok(Some(make_session_info()))).unwrap();
+			}
+		);
+		assert_matches!(
+			handle.recv().await,
+			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+				_,
+				RuntimeApiRequest::SessionExecutorParams(SESSION, tx),
+			)) => {
+				tx.send(Ok(Some(ExecutorParams::default()))).unwrap();
+			}
+		);
+		assert_matches!(
+			handle.recv().await,
+			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+				_,
+				RuntimeApiRequest::NodeFeatures(SESSION, tx),
+			)) => {
+				tx.send(Ok(NodeFeatures::new())).unwrap();
+			}
+		);
+	}
+	assert_matches!(
+		handle.recv().await,
+		AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+			_,
+			RuntimeApiRequest::DisabledValidators(tx),
+		)) => {
+			tx.send(Ok(disabled)).unwrap();
+		}
+	);
+	assert_matches!(
+		handle.recv().await,
+		AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+			_,
+			RuntimeApiRequest::FetchOnChainVotes(tx),
+		)) => {
+			let attestations = backers
+				.into_iter()
+				.map(|index| (index, ValidityAttestation::Explicit(dummy_signature())))
+				.collect();
+			tx.send(Ok(Some(ScrapedOnChainVotes {
+				session: SESSION,
+				backing_validators_per_candidate: vec![(
+					dummy_candidate_receipt_v2(Hash::zero()),
+					attestations,
+				)],
+				disputes: vec![],
+			})))
+			.unwrap();
+		}
+	);
+	assert_matches!(
+		handle.recv().await,
+		AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+			relay_parent,
+			RuntimeApiRequest::FetchOnChainBitfields(tx),
+		)) => {
+			assert_eq!(relay_parent, leaf);
+			tx.send(Ok(Some(ScrapedOnChainBitfields { session: SESSION, relay_parent, bitfields })))
+				.unwrap();
+		}
+	);
+}
+
+fn bitfield(bits: &[bool]) -> AvailabilityBitfield {
+	AvailabilityBitfield(bits.iter().copied().collect())
+}
+
+#[test]
+fn reports_session_health() {
+	test_harness(false, |mut handle| {
+		async move {
+			let leaf_a = Hash::repeat_byte(1);
+			let ours = (ValidatorIndex(0), bitfield(&[true, true]));
+			let bobs = (ValidatorIndex(1), bitfield(&[true]));
+
+			// The same candidate is backed and the same bitfield signed at `leaf_a` is included on
+			// the forks, but they must only be counted once.
+			activate_leaf(
+				&mut handle,
+				leaf_a,
+				true,
+				vec![],
+				vec![ValidatorIndex(0)],
+				(Hash::zero(), vec![]),
+			)
+			.await;
+			activate_leaf(
+				&mut handle,
+				Hash::repeat_byte(2),
+				false,
+				vec![ValidatorIndex(0)],
+				vec![ValidatorIndex(1), ValidatorIndex(0)],
+				(leaf_a, vec![ours.clone(), bobs]),
+			)
+			.await;
+			activate_leaf(
+				&mut handle,
+				Hash::repeat_byte(3),
+				false,
+				vec![ValidatorIndex(0)],
+				vec![],
+				(leaf_a, vec![ours]),
+			)
+			.await;
+
+			handle
+				.send(FromOrchestra::Communication {
+					msg: ValidatorHealthMessage::BitfieldSigned(leaf_a),
+				})
+				.await;
+
+			let (tx, rx) = oneshot::channel();
+			handle
+				.send(FromOrchestra::Communication {
+					msg: ValidatorHealthMessage::GetSessionHealth(None, tx),
+				})
+				.await;
+
+			let approvals = ApprovalStats { assignments: 4, approvals: 3, no_shows: 1 };
+			assert_matches!(
+				handle.recv().await,
+				AllMessages::ApprovalVoting(ApprovalVotingMessage::GetApprovalStats(
+					SESSION,
+					ValidatorIndex(0),
+					tx,
+				)) => {
+					tx.send(approvals).unwrap();
+				}
+			);
+
+			let participated = CandidateHash(Hash::repeat_byte(10));
+			let missed = CandidateHash(Hash::repeat_byte(11));
+			assert_matches!(
+				handle.recv().await,
+				AllMessages::DisputeCoordinator(DisputeCoordinatorMessage::RecentDisputes(tx)) => {
+					tx.send(vec![
+						(SESSION, participated, DisputeStatus::Active),
+						(SESSION, missed, DisputeStatus::Active),
+						(SESSION - 1, CandidateHash(Hash::repeat_byte(12)), DisputeStatus::Active),
+					])
+					.unwrap();
+				}
+			);
+			assert_matches!(
+				handle.recv().await,
+				AllMessages::DisputeCoordinator(DisputeCoordinatorMessage::QueryCandidateVotes(
+					query,
+					tx,
+				)) => {
+					assert_eq!(query, vec![(SESSION, participated), (SESSION, missed)]);
+					let votes = |voter| CandidateVotes {
+						candidate_receipt: dummy_candidate_receipt_v2(Hash::zero()),
+						valid: std::iter::once((
+							voter,
+							(ValidDisputeStatementKind::Explicit, dummy_signature()),
+						))
+						.collect(),
+						invalid: BTreeMap::new(),
+					};
+					tx.send(vec![
+						(SESSION, participated, votes(ValidatorIndex(0))),
+						(SESSION, missed, votes(ValidatorIndex(1))),
+					])
+					.unwrap();
+				}
+			);
+
+			assert_eq!(
+				rx.await.unwrap(),
+				Some(SessionHealth {
+					session_index: SESSION,
+					validator_index: ValidatorIndex(0),
+					disabled: true,
+					candidates_backed: 1,
+					bitfields_signed: 1,
+					bitfields_included: 1,
+					availability_bits_set: 2,
+					approvals,
+					disputes: 2,
+					disputes_participated: 1,
+				})
+			);
+
+			handle
+		}
+		.boxed()
+	});
+}
+
+#[test]
+fn unknown_sessions_are_not_reported() {
+	test_harness(true, |mut handle| {
+		async move {
+			activate_leaf(
+				&mut handle,
+				Hash::repeat_byte(1),
+				true,
+				vec![],
+				vec![],
+				(Hash::zero(), vec![]),
+			)
+			.await;
+
+			let (tx, rx) = oneshot::channel();
+			handle
+				.send(FromOrchestra::Communication {
+					msg: ValidatorHealthMessage::GetSessionHealth(Some(SESSION - 1), tx),
+				})
+				.await;
+			assert_eq!(rx.await.unwrap(), None);
+
+			let (tx, rx) = oneshot::channel();
+			handle
+				.send(FromOrchestra::Communication {
+					msg: ValidatorHealthMessage::GetSessionHealth(Some(SESSION), tx),
+				})
+				.await;
+			assert_matches!(
+				handle.recv().await,
+				AllMessages::ApprovalVotingParallel(
+					ApprovalVotingParallelMessage::GetApprovalStats(SESSION, ValidatorIndex(0), tx)
+				) => {
+					tx.send(Default::default()).unwrap();
+				}
+			);
+			assert_matches!(
+				handle.recv().await,
+				AllMessages::DisputeCoordinator(DisputeCoordinatorMessage::RecentDisputes(tx)) => {
+					tx.send(vec![]).unwrap();
+				}
+			);
+			assert_matches!(
+				rx.await.unwrap(),
+				Some(SessionHealth { candidates_backed: 0, disabled: false, .. })
+			);
+
+			handle
+		}
+		.boxed()
+	});
+}
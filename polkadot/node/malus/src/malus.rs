@@ -42,6 +42,10 @@ enum NemesisVariant {
 	DisputeFinalizedCandidates(DisputeFinalizedCandidatesOptions),
 	/// Spam many request statements instead of sending a single one.
 	SpamStatementRequests(SpamStatementRequestsOptions),
+	/// Back valid candidates, but refuse to serve their PoVs and erasure chunks.
+	WithholdAvailability(WithholdAvailabilityOptions),
+	/// Trigger approval checks, but never send the approvals.
+	StallApprovals(StallApprovalsOptions),
 }
 
 #[derive(Debug, Parser)]
@@ -105,6 +109,20 @@ impl MalusCli {
 
 				polkadot_cli::run_node(cli, SpamStatementRequests { spam_factor }, finality_delay)?
 			},
+			NemesisVariant::WithholdAvailability(opts) => {
+				let WithholdAvailabilityOptions { percentage, withhold, cli } = opts;
+
+				polkadot_cli::run_node(
+					cli,
+					WithholdAvailability { percentage, withhold },
+					finality_delay,
+				)?
+			},
+			NemesisVariant::StallApprovals(opts) => {
+				let StallApprovalsOptions { percentage, cli } = opts;
+
+				polkadot_cli::run_node(cli, StallApprovals { percentage }, finality_delay)?
+			},
 		}
 		Ok(())
 	}
@@ -244,4 +262,43 @@ mod tests {
 			assert!(opts.cli.run.base.bob);
 		});
 	}
+
+	#[test]
+	fn withhold_availability_works() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"withhold-availability",
+			"--percentage",
+			"50",
+			"--withhold",
+			"chunks",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::WithholdAvailability(opts),
+			..
+		} => {
+			assert_eq!(opts.percentage, 50);
+			assert_eq!(opts.withhold, variants::WithheldData::Chunks);
+			assert!(opts.cli.run.base.bob);
+		});
+	}
+
+	#[test]
+	fn stall_approvals_works() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"stall-approvals",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::StallApprovals(opts),
+			..
+		} => {
+			assert_eq!(opts.percentage, 100);
+			assert!(opts.cli.run.base.bob);
+		});
+	}
 }
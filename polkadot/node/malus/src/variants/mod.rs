@@ -21,15 +21,19 @@ mod common;
 mod dispute_finalized_candidates;
 mod dispute_valid_candidates;
 mod spam_statement_requests;
+mod stall_approvals;
 mod suggest_garbage_candidate;
 mod support_disabled;
+mod withhold_availability;
 
 pub(crate) use self::{
 	back_garbage_candidate::{BackGarbageCandidateOptions, BackGarbageCandidates},
 	dispute_finalized_candidates::{DisputeFinalizedCandidates, DisputeFinalizedCandidatesOptions},
 	dispute_valid_candidates::{DisputeAncestorOptions, DisputeValidCandidates},
 	spam_statement_requests::{SpamStatementRequests, SpamStatementRequestsOptions},
+	stall_approvals::{StallApprovals, StallApprovalsOptions},
 	suggest_garbage_candidate::{SuggestGarbageCandidateOptions, SuggestGarbageCandidates},
 	support_disabled::{SupportDisabled, SupportDisabledOptions},
+	withhold_availability::{WithheldData, WithholdAvailability, WithholdAvailabilityOptions},
 };
 pub(crate) use common::*;
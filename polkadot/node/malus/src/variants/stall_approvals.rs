@@ -0,0 +1,148 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node variant that goes silent after triggering approval checks.
+//!
+//! This malus variant behaves honestly in everything except when distributing its own approval
+//! votes. It still distributes its assignments and checks the candidates, but never sends out the
+//! approvals of the chosen candidates, so other validators consider it a no-show and have to cover
+//! it by assigning more checkers.
+//!
+//! Attention: For usage with `zombienet` only!
+
+#![allow(missing_docs)]
+
+use polkadot_cli::{
+	service::{
+		AuxStore, Error, ExtendedOverseerGenArgs, Overseer, OverseerConnector, OverseerGen,
+		OverseerGenArgs, OverseerHandle,
+	},
+	validator_overseer_builder, Cli,
+};
+use polkadot_node_subsystem::SpawnGlue;
+use polkadot_node_subsystem_types::{ChainApiBackend, RuntimeApiSubsystemClient};
+use polkadot_primitives::Hash;
+use sp_core::traits::SpawnNamed;
+
+// Filter wrapping related types.
+use crate::{interceptor::*, shared::MALUS};
+
+use std::sync::Arc;
+
+/// Wraps around approval distribution and drops our approvals of the chosen candidates.
+#[derive(Clone)]
+struct ApprovalStaller {
+	/// The percentage of candidates for which no approval is sent.
+	percentage: u8,
+}
+
+impl ApprovalStaller {
+	/// Whether the approval of the candidate at `candidate_index` in the block is withheld.
+	///
+	/// The choice only depends on the block and the candidate index, so it stays the same across
+	/// all malus nodes running this variant.
+	fn stalls(&self, block_hash: &Hash, candidate_index: usize) -> bool {
+		let bytes = block_hash.as_bytes();
+		let seed = usize::from(u16::from_le_bytes([bytes[0], bytes[1]]));
+		seed.wrapping_add(candidate_index) % 100 < usize::from(self.percentage)
+	}
+}
+
+impl<Sender> MessageInterceptor<Sender> for ApprovalStaller
+where
+	Sender: overseer::ApprovalDistributionSenderTrait + Clone + Send + 'static,
+{
+	type Message = ApprovalDistributionMessage;
+
+	/// Drop our approval votes covering any of the chosen candidates. Coalesced approvals are
+	/// signed for all the candidates at once, so they are dropped as a whole.
+	fn intercept_incoming(
+		&self,
+		_subsystem_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		match msg {
+			FromOrchestra::Communication {
+				msg: ApprovalDistributionMessage::DistributeApproval(vote),
+			} if vote
+				.candidate_indices
+				.iter_ones()
+				.any(|candidate_index| self.stalls(&vote.block_hash, candidate_index)) =>
+			{
+				gum::info!(
+					target: MALUS,
+					block_hash = ?vote.block_hash,
+					candidate_indices = ?vote.candidate_indices,
+					"😈 Not sending the approval of the candidates.",
+				);
+				None
+			},
+			msg => Some(msg),
+		}
+	}
+}
+
+//----------------------------------------------------------------------------------
+
+#[derive(Debug, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct StallApprovalsOptions {
+	/// Determines the percentage of candidates for which the approval is not sent.
+	/// Defaults to not approving any candidate.
+	#[clap(short, long, ignore_case = true, default_value_t = 100, value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub percentage: u8,
+
+	#[clap(flatten)]
+	pub cli: Cli,
+}
+
+/// StallApprovals implementation wrapper which implements `OverseerGen` glue.
+pub(crate) struct StallApprovals {
+	/// The percentage of candidates for which the approval is not sent.
+	pub percentage: u8,
+}
+
+impl OverseerGen for StallApprovals {
+	fn generate<Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'_, Spawner, RuntimeClient>,
+		ext_args: Option<ExtendedOverseerGenArgs>,
+	) -> Result<(Overseer<SpawnGlue<Spawner>, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: RuntimeApiSubsystemClient + ChainApiBackend + AuxStore + 'static,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		gum::info!(
+			target: MALUS,
+			"😈 Started Malus node that does not send approvals for {}% of the candidates.",
+			self.percentage,
+		);
+
+		let staller = ApprovalStaller { percentage: self.percentage };
+
+		validator_overseer_builder(
+			args,
+			ext_args.expect("Extended arguments required to build validator overseer are provided"),
+		)?
+		.replace_approval_distribution(move |approval_distribution| {
+			InterceptedSubsystem::new(approval_distribution, staller)
+		})
+		.build_with_connector(connector)
+		.map_err(|e| e.into())
+	}
+}
@@ -0,0 +1,197 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node variant that backs valid candidates, but refuses to serve their data.
+//!
+//! This malus variant behaves honestly in backing, availability and bitfield signing, so it
+//! stores the PoVs and erasure chunks and claims to have them. However, the availability store
+//! pretends not to have the full available data or the erasure chunks of the chosen candidates
+//! whenever they are queried. Hence the node neither serves PoVs to other backers, nor full data
+//! or chunks to validators recovering the candidate, which forces them to use the fallback
+//! recovery strategies (systematic chunks, regular chunks, other backers).
+//!
+//! Attention: For usage with `zombienet` only!
+
+#![allow(missing_docs)]
+
+use polkadot_cli::{
+	service::{
+		AuxStore, Error, ExtendedOverseerGenArgs, Overseer, OverseerConnector, OverseerGen,
+		OverseerGenArgs, OverseerHandle,
+	},
+	validator_overseer_builder, Cli,
+};
+use polkadot_node_subsystem::SpawnGlue;
+use polkadot_node_subsystem_types::{ChainApiBackend, RuntimeApiSubsystemClient};
+use polkadot_primitives::CandidateHash;
+use sp_core::traits::SpawnNamed;
+
+// Filter wrapping related types.
+use crate::{interceptor::*, shared::MALUS};
+
+use std::sync::Arc;
+
+/// The data of the chosen candidates which is withheld.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum WithheldData {
+	/// The full available data: PoVs requested by other backers and full recoveries from backers.
+	FullData,
+	/// The erasure chunks, including the systematic ones.
+	Chunks,
+	/// Both the full available data and the erasure chunks.
+	All,
+}
+
+impl WithheldData {
+	fn includes_full_data(&self) -> bool {
+		matches!(self, Self::FullData | Self::All)
+	}
+
+	fn includes_chunks(&self) -> bool {
+		matches!(self, Self::Chunks | Self::All)
+	}
+}
+
+/// Wraps around the availability store and replaces the answers to data queries.
+#[derive(Clone)]
+struct AvailabilityWithholder {
+	/// The percentage of candidates for which the data is withheld.
+	percentage: u8,
+	/// The data to withhold.
+	withhold: WithheldData,
+}
+
+impl AvailabilityWithholder {
+	/// Whether the data of the candidate is withheld.
+	///
+	/// The choice only depends on the candidate hash, so it stays the same for all queries and
+	/// across all malus nodes running this variant.
+	fn withholds(&self, candidate_hash: &CandidateHash) -> bool {
+		let bytes = candidate_hash.0.as_bytes();
+		u16::from_le_bytes([bytes[0], bytes[1]]) % 100 < u16::from(self.percentage)
+	}
+}
+
+impl<Sender> MessageInterceptor<Sender> for AvailabilityWithholder
+where
+	Sender: overseer::AvailabilityStoreSenderTrait + Clone + Send + 'static,
+{
+	type Message = AvailabilityStoreMessage;
+
+	/// Answer the data queries of the chosen candidates as if the data is missing, pass through
+	/// everything else, including the availability checks used for bitfield signing.
+	fn intercept_incoming(
+		&self,
+		_subsystem_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		match msg {
+			FromOrchestra::Communication {
+				msg: AvailabilityStoreMessage::QueryAvailableData(candidate_hash, tx),
+			} if self.withhold.includes_full_data() && self.withholds(&candidate_hash) => {
+				gum::info!(
+					target: MALUS,
+					?candidate_hash,
+					"😈 Withholding the available data of the candidate.",
+				);
+				let _ = tx.send(None);
+				None
+			},
+			FromOrchestra::Communication {
+				msg: AvailabilityStoreMessage::QueryChunk(candidate_hash, validator_index, tx),
+			} if self.withhold.includes_chunks() && self.withholds(&candidate_hash) => {
+				gum::info!(
+					target: MALUS,
+					?candidate_hash,
+					?validator_index,
+					"😈 Withholding an erasure chunk of the candidate.",
+				);
+				let _ = tx.send(None);
+				None
+			},
+			FromOrchestra::Communication {
+				msg: AvailabilityStoreMessage::QueryAllChunks(candidate_hash, tx),
+			} if self.withhold.includes_chunks() && self.withholds(&candidate_hash) => {
+				gum::info!(
+					target: MALUS,
+					?candidate_hash,
+					"😈 Withholding all erasure chunks of the candidate.",
+				);
+				let _ = tx.send(Vec::new());
+				None
+			},
+			msg => Some(msg),
+		}
+	}
+}
+
+//----------------------------------------------------------------------------------
+
+#[derive(Debug, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct WithholdAvailabilityOptions {
+	/// Determines the percentage of candidates whose data should be withheld.
+	/// Defaults to withholding the data of all candidates.
+	#[clap(short, long, ignore_case = true, default_value_t = 100, value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub percentage: u8,
+
+	/// The data to withhold.
+	#[clap(long, value_enum, ignore_case = true, default_value_t = WithheldData::All)]
+	pub withhold: WithheldData,
+
+	#[clap(flatten)]
+	pub cli: Cli,
+}
+
+/// WithholdAvailability implementation wrapper which implements `OverseerGen` glue.
+pub(crate) struct WithholdAvailability {
+	/// The percentage of candidates whose data is withheld.
+	pub percentage: u8,
+	/// The data to withhold.
+	pub withhold: WithheldData,
+}
+
+impl OverseerGen for WithholdAvailability {
+	fn generate<Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'_, Spawner, RuntimeClient>,
+		ext_args: Option<ExtendedOverseerGenArgs>,
+	) -> Result<(Overseer<SpawnGlue<Spawner>, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: RuntimeApiSubsystemClient + ChainApiBackend + AuxStore + 'static,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		gum::info!(
+			target: MALUS,
+			"😈 Started Malus node that withholds {:?} of {}% of the candidates.",
+			self.withhold,
+			self.percentage,
+		);
+
+		let withholder =
+			AvailabilityWithholder { percentage: self.percentage, withhold: self.withhold };
+
+		validator_overseer_builder(
+			args,
+			ext_args.expect("Extended arguments required to build validator overseer are provided"),
+		)?
+		.replace_availability_store(move |av_store| InterceptedSubsystem::new(av_store, withholder))
+		.build_with_connector(connector)
+		.map_err(|e| e.into())
+	}
+}
@@ -84,7 +84,7 @@ use polkadot_node_subsystem_types::messages::{
 	ChainApiMessage, ChainSelectionMessage, CollationGenerationMessage, CollatorProtocolMessage,
 	DisputeCoordinatorMessage, DisputeDistributionMessage, GossipSupportMessage,
 	NetworkBridgeRxMessage, NetworkBridgeTxMessage, ProspectiveParachainsMessage,
-	ProvisionerMessage, RuntimeApiMessage, StatementDistributionMessage,
+	ProvisionerMessage, RuntimeApiMessage, StatementDistributionMessage, ValidatorHealthMessage,
 };
 
 pub use polkadot_node_subsystem_types::{
@@ -521,6 +521,7 @@ pub struct Overseer<SupportsParachains> {
 		AvailabilityStoreMessage,
 		RuntimeApiMessage,
 		BitfieldDistributionMessage,
+		ValidatorHealthMessage,
 	])]
 	bitfield_signing: BitfieldSigning,
 
@@ -649,6 +650,14 @@ pub struct Overseer<SupportsParachains> {
 	])]
 	prospective_parachains: ProspectiveParachains,
 
+	#[subsystem(ValidatorHealthMessage, sends: [
+		RuntimeApiMessage,
+		ApprovalVotingMessage,
+		ApprovalVotingParallelMessage,
+		DisputeCoordinatorMessage,
+	])]
+	validator_health: ValidatorHealth,
+
 	/// External listeners waiting for a hash to be in the active-leave set.
 	pub activation_external_listeners: HashMap<Hash, Vec<oneshot::Sender<SubsystemResult<()>>>>,
 
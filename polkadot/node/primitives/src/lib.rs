@@ -33,7 +33,8 @@ use polkadot_primitives::{
 	vstaging::CommittedCandidateReceiptV2 as CommittedCandidateReceipt, BlakeTwo256, BlockNumber,
 	CandidateCommitments, CandidateHash, ChunkIndex, CollatorPair, CompactStatement, CoreIndex,
 	EncodeAs, Hash, HashT, HeadData, Id as ParaId, PersistedValidationData, SessionIndex, Signed,
-	UncheckedSigned, ValidationCode, ValidationCodeHash, MAX_CODE_SIZE, MAX_POV_SIZE,
+	UncheckedSigned, ValidationCode, ValidationCodeHash, ValidatorIndex, MAX_CODE_SIZE,
+	MAX_POV_SIZE,
 };
 pub use sp_consensus_babe::{
 	AllowedSlots as BabeAllowedSlots, BabeEpochConfiguration, Epoch as BabeEpoch,
@@ -660,6 +661,25 @@ impl ErasureChunk {
 	}
 }
 
+/// Everything the availability store holds for a single candidate, in a form that can be moved
+/// to the availability store of another node.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Debug)]
+pub struct ExportedCandidate {
+	/// The hash of the candidate.
+	pub candidate_hash: CandidateHash,
+	/// The number of validators in the session of the candidate, i.e. the number of chunks.
+	pub n_validators: u32,
+	/// The hash of a relay chain block the candidate was included in, if known.
+	///
+	/// This is only a hint used on import to find the candidate receipt on chain. The data is
+	/// checked against the erasure root of that receipt and candidates without it are rejected.
+	pub included_at: Option<Hash>,
+	/// The full available data of the candidate, if it was stored.
+	pub available_data: Option<AvailableData>,
+	/// The erasure chunks that were stored, by the index of the validator holding them.
+	pub chunks: Vec<(ValidatorIndex, ErasureChunk)>,
+}
+
 /// Compress a PoV, unless it exceeds the [`POV_BOMB_LIMIT`].
 #[cfg(not(target_os = "unknown"))]
 pub fn maybe_compress_pov(pov: PoV) -> PoV {
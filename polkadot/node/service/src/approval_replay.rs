@@ -0,0 +1,61 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline replay of the approval-voting database.
+
+use super::{open_database, parachains_db, Error, FullClient};
+use polkadot_node_core_approval_voting::{
+	approval_db::common::Config as ApprovalDatabaseConfig, replay,
+};
+use polkadot_node_primitives::approval::time::{Tick, TICK_DURATION_MILLIS};
+use polkadot_primitives::runtime_api::ParachainHost;
+use sc_service::Configuration;
+use sp_api::ProvideRuntimeApi;
+use std::{collections::HashMap, time::Duration};
+
+pub use polkadot_node_core_approval_voting::replay::{
+	ApprovalRecord, AssignmentRecord, BlockReplay, CandidateTimeline,
+};
+
+/// Replay the approval state machine for the unfinalized and recently finalized blocks held by
+/// the approval-voting database, up to `end`, the time since the unix epoch.
+///
+/// The node must not be running. See [`replay::replay`] for the limitations of the replay.
+pub fn replay_approvals(
+	client: &FullClient,
+	config: &Configuration,
+	end: Duration,
+) -> Result<Vec<BlockReplay>, Error> {
+	let db = open_database(&config.database)?;
+	let db_config =
+		ApprovalDatabaseConfig { col_approval_data: parachains_db::REAL_COLUMNS.col_approval_data };
+	let slot_duration_millis =
+		sc_consensus_babe::configuration(client)?.slot_duration().as_millis() as u64;
+	let end_tick = end.as_millis() as Tick / TICK_DURATION_MILLIS;
+
+	let mut session_infos = HashMap::new();
+	let replays =
+		replay::replay(&*db, &db_config, slot_duration_millis, end_tick, |parent, session| {
+			session_infos
+				.entry(session)
+				.or_insert_with(|| {
+					client.runtime_api().session_info(parent, session).ok().flatten()
+				})
+				.clone()
+		})?;
+
+	Ok(replays)
+}
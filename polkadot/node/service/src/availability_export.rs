@@ -0,0 +1,160 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Moving the availability store data of candidates between nodes.
+//!
+//! The data is written as the SCALE encoding of a `Vec<ExportedCandidate>`, the same format used
+//! by the `availabilityStore_*` RPC methods.
+
+use super::{open_database, parachains_db, Error, FullClient, KEEP_FINALIZED_FOR_LIVE_NETWORKS};
+use codec::{Decode, Encode};
+use polkadot_node_core_av_store::{self as av_store, Config as AvailabilityConfig};
+use polkadot_primitives::{
+	runtime_api::ParachainHost, vstaging::CandidateEvent, BlockNumber, CandidateHash, Hash,
+};
+use sc_service::Configuration;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use std::path::Path;
+
+/// The candidates to export the availability data of, see [`export_availability`].
+#[derive(Debug, Clone)]
+pub enum AvailabilityExport {
+	/// The given candidates.
+	Candidates(Vec<CandidateHash>),
+	/// The candidates included in the given range of blocks of the best chain, both ends
+	/// inclusive.
+	Blocks(BlockNumber, BlockNumber),
+	/// All candidates known to the availability store.
+	All,
+}
+
+fn availability_config() -> AvailabilityConfig {
+	AvailabilityConfig {
+		col_data: parachains_db::REAL_COLUMNS.col_availability_data,
+		col_meta: parachains_db::REAL_COLUMNS.col_availability_meta,
+		keep_finalized_for: KEEP_FINALIZED_FOR_LIVE_NETWORKS,
+	}
+}
+
+/// Export the availability store data of the selected candidates to the file at `path`.
+///
+/// The node must not be running. Returns the number of exported candidates, candidates unknown
+/// to the availability store are skipped.
+pub fn export_availability(
+	client: &FullClient,
+	config: &Configuration,
+	selection: AvailabilityExport,
+	path: &Path,
+) -> Result<usize, Error> {
+	let db = open_database(&config.database)?;
+	let av_config = availability_config();
+
+	// The inclusion block is only known from the meta data for unfinalized candidates, unless
+	// exporting by block.
+	let candidates = match selection {
+		AvailabilityExport::Candidates(candidates) =>
+			candidates.into_iter().map(|c| (c, None)).collect(),
+		AvailabilityExport::All => av_store::stored_candidates(&db, &av_config)?
+			.into_iter()
+			.map(|c| (c, None))
+			.collect(),
+		AvailabilityExport::Blocks(from, to) => {
+			let mut candidates = Vec::new();
+			for number in from..=to {
+				let hash = client.hash(number)?.ok_or_else(|| {
+					sp_blockchain::Error::UnknownBlock(format!("block number {number}"))
+				})?;
+				candidates.extend(
+					included_candidates(client, hash)?.into_iter().map(|(c, _)| (c, Some(hash))),
+				);
+			}
+			candidates
+		},
+	};
+
+	let mut exported = Vec::new();
+	for (candidate_hash, included_at) in candidates {
+		if let Some(mut candidate) = av_store::export_candidate(&db, &av_config, candidate_hash)? {
+			candidate.included_at = included_at.or(candidate.included_at);
+			exported.push(candidate);
+		}
+	}
+
+	std::fs::write(path, exported.encode())?;
+	Ok(exported.len())
+}
+
+/// Import the availability store data from a file previously written by
+/// [`export_availability`].
+///
+/// The node must not be running. Every candidate must name a block it was included in which is
+/// known to `client`, and its data is checked against the erasure root of the candidate receipt
+/// in that block. Returns the number of imported candidates. Imported data is kept for as long
+/// as data of finalized candidates.
+pub fn import_availability(
+	client: &FullClient,
+	config: &Configuration,
+	path: &Path,
+) -> Result<usize, Error> {
+	let db = open_database(&config.database)?;
+	let av_config = availability_config();
+
+	let data = std::fs::read(path)?;
+	let candidates = Vec::<polkadot_node_primitives::ExportedCandidate>::decode(&mut &data[..])
+		.map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+	let n_candidates = candidates.len();
+	for candidate in candidates {
+		let candidate_hash = candidate.candidate_hash;
+		let unknown_candidate = || {
+			std::io::Error::new(
+				std::io::ErrorKind::InvalidData,
+				format!("candidate {candidate_hash:?} is not included in a known block"),
+			)
+		};
+
+		let included_at = candidate.included_at.ok_or_else(unknown_candidate)?;
+		let (_, expected_erasure_root) = included_candidates(client, included_at)?
+			.into_iter()
+			.find(|(hash, _)| *hash == candidate_hash)
+			.ok_or_else(unknown_candidate)?;
+
+		av_store::import_candidate(&db, &av_config, candidate, expected_erasure_root)?;
+	}
+
+	Ok(n_candidates)
+}
+
+// The candidates included in a block, with their erasure roots.
+fn included_candidates(
+	client: &FullClient,
+	hash: Hash,
+) -> Result<Vec<(CandidateHash, Hash)>, Error> {
+	let events = client
+		.runtime_api()
+		.candidate_events(hash)
+		.map_err(sp_blockchain::Error::RuntimeApiError)?;
+
+	Ok(events
+		.into_iter()
+		.filter_map(|event| match event {
+			CandidateEvent::CandidateIncluded(receipt, ..) =>
+				Some((receipt.hash(), receipt.descriptor.erasure_root())),
+			_ => None,
+		})
+		.collect())
+}
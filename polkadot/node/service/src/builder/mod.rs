@@ -36,11 +36,13 @@ use mmr_gadget::MmrGadget;
 use polkadot_availability_recovery::FETCH_CHUNKS_THRESHOLD;
 use polkadot_node_core_approval_voting::Config as ApprovalVotingConfig;
 use polkadot_node_core_av_store::Config as AvailabilityConfig;
+use polkadot_node_core_av_store_rpc::{AvailabilityStore, AvailabilityStoreApiServer};
 use polkadot_node_core_candidate_validation::Config as CandidateValidationConfig;
 use polkadot_node_core_chain_selection::{
 	self as chain_selection_subsystem, Config as ChainSelectionConfig,
 };
 use polkadot_node_core_dispute_coordinator::Config as DisputeCoordinatorConfig;
+use polkadot_node_core_validator_health_rpc::{ValidatorHealth, ValidatorHealthApiServer};
 use polkadot_node_network_protocol::{
 	peer_set::{PeerSet, PeerSetProtocolNames},
 	request_response::{IncomingRequest, ReqProtocolNames},
@@ -462,6 +464,7 @@ where
 				import_queue,
 				block_announce_validator_builder: None,
 				warp_sync_config: Some(WarpSyncConfig::WithProvider(warp_sync)),
+				state_sync_store: Some(client.clone()),
 				block_relay: None,
 				metrics,
 			})?;
@@ -489,6 +492,27 @@ where
 			);
 		}
 
+		let rpc_extensions_builder = {
+			let overseer_handle = Handle::new(overseer_connector.handle());
+			let is_authority = role.is_authority();
+
+			move |subscription_executor: polkadot_rpc::SubscriptionTaskExecutor|
+			      -> Result<polkadot_rpc::RpcExtension, sc_service::Error> {
+				let mut io = rpc_extensions_builder(subscription_executor)?;
+				if is_authority {
+					io.merge(ValidatorHealthApiServer::into_rpc(ValidatorHealth::new(
+						overseer_handle.clone(),
+					)))
+					.map_err(|e| sc_service::Error::Application(e.into()))?;
+					io.merge(AvailabilityStoreApiServer::into_rpc(AvailabilityStore::new(
+						overseer_handle.clone(),
+					)))
+					.map_err(|e| sc_service::Error::Application(e.into()))?;
+				}
+				Ok(io)
+			}
+		};
+
 		let rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 			config,
 			backend: backend.clone(),
@@ -51,6 +51,8 @@ pub struct Extensions {
 	///
 	/// This value will be set by the `sync-state rpc` implementation.
 	pub light_sync_state: sc_sync_state_rpc::LightSyncStateExtension,
+	/// Trusted block to start warp sync from instead of genesis.
+	pub warp_sync_checkpoint: sc_service::WarpSyncCheckpointExtension<polkadot_primitives::Block>,
 }
 
 // Generic chain spec, in case when we don't have the native runtime.
@@ -25,6 +25,10 @@ mod grandpa_support;
 mod parachains_db;
 mod relay_chain_selection;
 
+#[cfg(feature = "full-node")]
+pub mod approval_replay;
+#[cfg(feature = "full-node")]
+mod availability_export;
 #[cfg(feature = "full-node")]
 pub mod builder;
 #[cfg(feature = "full-node")]
@@ -32,6 +36,12 @@ pub mod overseer;
 #[cfg(feature = "full-node")]
 pub mod workers;
 
+#[cfg(feature = "full-node")]
+pub use crate::approval_replay::replay_approvals;
+#[cfg(feature = "full-node")]
+pub use crate::availability_export::{
+	export_availability, import_availability, AvailabilityExport,
+};
 #[cfg(feature = "full-node")]
 pub use crate::builder::{new_full, NewFull, NewFullParams};
 
@@ -74,7 +84,9 @@ use prometheus_endpoint::Registry;
 use sc_service::SpawnTaskHandle;
 
 pub use chain_spec::{GenericChainSpec, RococoChainSpec, WestendChainSpec};
-pub use polkadot_primitives::{Block, BlockId, BlockNumber, CollatorPair, Hash, Id as ParaId};
+pub use polkadot_primitives::{
+	Block, BlockId, BlockNumber, CandidateHash, CollatorPair, Hash, Id as ParaId,
+};
 pub use sc_client_api::{Backend, CallExecutor};
 pub use sc_consensus::{BlockImport, LongestChain};
 pub use sc_executor::NativeExecutionDispatch;
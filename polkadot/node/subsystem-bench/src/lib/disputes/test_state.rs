@@ -0,0 +1,291 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::DisputesOptions;
+use crate::{
+	configuration::{TestAuthorities, TestConfiguration},
+	mock::runtime_api::session_info_for_peers,
+	network::{HandleNetworkMessage, NetworkMessage},
+	NODE_UNDER_TEST,
+};
+use codec::Encode;
+use itertools::Itertools;
+use polkadot_node_network_protocol::request_response::{
+	v1::{DisputeRequest, DisputeResponse},
+	Requests,
+};
+use polkadot_node_primitives::{DisputeMessage, SignedDisputeStatement};
+use polkadot_node_subsystem_test_helpers::mock::new_block_import_info;
+use polkadot_overseer::BlockInfo;
+use polkadot_primitives::{
+	vstaging::{CandidateEvent, CandidateReceiptV2 as CandidateReceipt, MutateDescriptorV2},
+	BlockNumber, CandidateHash, CoreIndex, DisputeStatement, ExplicitDisputeStatement, GroupIndex,
+	Hash, Header, Id, InvalidDisputeStatementKind, SessionInfo, ValidDisputeStatementKind,
+	ValidatorIndex, ValidatorPair,
+};
+use polkadot_primitives_test_helpers::dummy_candidate_receipt_v2;
+use sc_network::ProtocolName;
+use sp_core::{Pair, H256};
+use std::{
+	collections::HashMap,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+};
+
+const SESSION_INDEX: u32 = 0;
+
+/// A dispute request sent by an emulated peer to the node under test.
+#[derive(Clone)]
+pub struct PeerDisputeRequest {
+	/// The validator sending the request.
+	pub sender: ValidatorIndex,
+	/// The disputed candidate.
+	pub candidate_hash: CandidateHash,
+	/// The request, carrying the invalid vote of the sender.
+	pub request: DisputeRequest,
+}
+
+#[derive(Clone)]
+pub struct TestState {
+	// Full test config
+	pub config: TestConfiguration,
+	// Benchmark specific options
+	pub options: DisputesOptions,
+	// Authority keys for the network emulation.
+	pub test_authorities: TestAuthorities,
+	// Relay chain block infos
+	pub block_infos: Vec<BlockInfo>,
+	// Relay chain block headers
+	pub block_headers: HashMap<H256, Header>,
+	// Session info
+	pub session_info: SessionInfo,
+	// Candidates included in each block
+	pub candidate_receipts: HashMap<H256, Vec<CandidateReceipt>>,
+	// Inclusion events of the candidates, served by the runtime
+	pub candidate_events: HashMap<H256, Vec<CandidateEvent>>,
+	// Pregenerated dispute requests sent by the peers in each block
+	pub dispute_requests: HashMap<H256, Vec<PeerDisputeRequest>>,
+	// Tracks if the node under test sent its vote for an included disputed candidate
+	pub participation_tracker: HashMap<CandidateHash, Arc<AtomicBool>>,
+}
+
+impl TestState {
+	pub fn new(config: &TestConfiguration, options: &DisputesOptions) -> Self {
+		assert!(options.n_disputes <= config.n_cores, "Only included candidates can be disputed");
+		assert!(
+			options.n_invalid_votes + 1 < config.n_validators,
+			"Votes are cast by validators other than the node under test"
+		);
+
+		let test_authorities = config.generate_authorities();
+		let session_info = session_info_for_peers(config, &test_authorities);
+		let mut state = Self {
+			config: config.clone(),
+			options: options.clone(),
+			test_authorities,
+			block_infos: (1..=config.num_blocks).map(generate_block_info).collect(),
+			block_headers: Default::default(),
+			session_info,
+			candidate_receipts: Default::default(),
+			candidate_events: Default::default(),
+			dispute_requests: Default::default(),
+			participation_tracker: Default::default(),
+		};
+
+		state.block_headers = state.block_infos.iter().map(generate_block_header).collect();
+
+		// Peers disputing included candidates take turns, so the load is spread across them.
+		let mut honest_voters = (1..config.n_validators as u32).map(ValidatorIndex).cycle();
+		// Spam is sent by the same few validators, so they run out of spam slots eventually.
+		let spam_voters = (config.n_validators - options.n_invalid_votes - 1..config.n_validators)
+			.map(|index| ValidatorIndex(index as u32))
+			.collect_vec();
+
+		for block_info in state.block_infos.iter() {
+			let candidates = (0..config.n_cores)
+				.map(|core_idx| {
+					generate_candidate(block_info.hash, Id::new(core_idx as u32 + 1), core_idx)
+				})
+				.collect_vec();
+			state.candidate_events.insert(
+				block_info.hash,
+				candidates
+					.iter()
+					.enumerate()
+					.map(|(core_idx, receipt)| {
+						CandidateEvent::CandidateIncluded(
+							receipt.clone(),
+							Vec::new().into(),
+							CoreIndex(core_idx as u32),
+							GroupIndex(core_idx as u32),
+						)
+					})
+					.collect(),
+			);
+
+			let mut requests = Vec::new();
+			for receipt in candidates.iter().take(options.n_disputes) {
+				let voters = honest_voters.by_ref().take(options.n_invalid_votes + 1).collect_vec();
+				requests.extend(state.generate_dispute_requests(receipt, &voters));
+				state
+					.participation_tracker
+					.insert(receipt.hash(), Arc::new(AtomicBool::new(false)));
+			}
+			// Spam candidates were never backed or included, so they are not known to the node.
+			for spam_idx in 0..options.n_spam_disputes {
+				let receipt = generate_candidate(
+					block_info.hash,
+					Id::new((config.n_cores + spam_idx) as u32 + 1),
+					spam_idx % config.n_cores,
+				);
+				requests.extend(state.generate_dispute_requests(&receipt, &spam_voters));
+			}
+
+			state.candidate_receipts.insert(block_info.hash, candidates);
+			state.dispute_requests.insert(block_info.hash, requests);
+		}
+
+		state
+	}
+
+	pub fn reset_trackers(&self) {
+		self.participation_tracker
+			.values()
+			.for_each(|v| v.as_ref().store(false, Ordering::SeqCst));
+	}
+
+	/// The first voter considers the candidate valid, all others send a dispute request with
+	/// their invalid vote and the valid one.
+	fn generate_dispute_requests(
+		&self,
+		receipt: &CandidateReceipt,
+		voters: &[ValidatorIndex],
+	) -> Vec<PeerDisputeRequest> {
+		let candidate_hash = receipt.hash();
+		let (valid_voter, invalid_voters) = voters.split_first().expect("There are voters; qed");
+		let valid_vote = sign_dispute_statement(
+			true,
+			candidate_hash,
+			self.test_authorities.validator_pairs.get(valid_voter.0 as usize).unwrap(),
+		);
+
+		invalid_voters
+			.iter()
+			.map(|&invalid_voter| {
+				let invalid_vote = sign_dispute_statement(
+					false,
+					candidate_hash,
+					self.test_authorities.validator_pairs.get(invalid_voter.0 as usize).unwrap(),
+				);
+				let message = DisputeMessage::from_signed_statements(
+					valid_vote.clone(),
+					*valid_voter,
+					invalid_vote,
+					invalid_voter,
+					receipt.clone(),
+					&self.session_info,
+				)
+				.expect("Votes are signed by validators of the session; qed");
+
+				PeerDisputeRequest {
+					sender: invalid_voter,
+					candidate_hash,
+					request: DisputeRequest::from(message),
+				}
+			})
+			.collect()
+	}
+}
+
+fn sign_dispute_statement(
+	valid: bool,
+	candidate_hash: CandidateHash,
+	pair: &ValidatorPair,
+) -> SignedDisputeStatement {
+	let payload = ExplicitDisputeStatement { valid, candidate_hash, session: SESSION_INDEX }
+		.signing_payload();
+	let statement = if valid {
+		DisputeStatement::Valid(ValidDisputeStatementKind::Explicit)
+	} else {
+		DisputeStatement::Invalid(InvalidDisputeStatementKind::Explicit)
+	};
+
+	SignedDisputeStatement::new_checked(
+		statement,
+		candidate_hash,
+		SESSION_INDEX,
+		pair.public(),
+		pair.sign(&payload[..]),
+	)
+	.unwrap()
+}
+
+fn generate_candidate(relay_parent: H256, para_id: Id, core_idx: usize) -> CandidateReceipt {
+	let mut receipt = dummy_candidate_receipt_v2(relay_parent);
+	receipt.descriptor.set_para_id(para_id);
+	receipt.descriptor.set_core_index(CoreIndex(core_idx as u32));
+	receipt.descriptor.set_session_index(SESSION_INDEX);
+	receipt
+}
+
+fn generate_block_info(block_num: usize) -> BlockInfo {
+	new_block_import_info(Hash::repeat_byte(block_num as u8), block_num as BlockNumber)
+}
+
+fn generate_block_header(info: &BlockInfo) -> (H256, Header) {
+	(
+		info.hash,
+		Header {
+			digest: Default::default(),
+			number: info.number,
+			parent_hash: info.parent_hash,
+			extrinsics_root: Default::default(),
+			state_root: Default::default(),
+		},
+	)
+}
+
+#[async_trait::async_trait]
+impl HandleNetworkMessage for TestState {
+	async fn handle(
+		&self,
+		message: NetworkMessage,
+		_node_sender: &mut futures::channel::mpsc::UnboundedSender<NetworkMessage>,
+	) -> Option<NetworkMessage> {
+		match message {
+			NetworkMessage::RequestFromNode(_authority_id, Requests::DisputeSendingV1(req)) => {
+				let message = &req.payload.0;
+				let own_vote = ValidatorIndex(NODE_UNDER_TEST);
+				if message.valid_vote.validator_index == own_vote ||
+					message.invalid_vote.validator_index == own_vote
+				{
+					if let Some(participated) =
+						self.participation_tracker.get(&message.candidate_receipt.hash())
+					{
+						participated.store(true, Ordering::SeqCst);
+					}
+				}
+				let _ = req
+					.pending_response
+					.send(Ok((DisputeResponse::Confirmed.encode(), ProtocolName::from(""))));
+				None
+			},
+			_ => Some(message),
+		}
+	}
+}
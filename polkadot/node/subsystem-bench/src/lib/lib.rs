@@ -20,6 +20,7 @@ pub const NODE_UNDER_TEST: u32 = 0;
 pub mod approval;
 pub mod availability;
 pub mod configuration;
+pub mod disputes;
 pub(crate) mod display;
 pub(crate) mod environment;
 pub(crate) mod keyring;
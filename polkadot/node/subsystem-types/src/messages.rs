@@ -37,7 +37,7 @@ use polkadot_node_primitives::{
 		v2::{CandidateBitfield, IndirectAssignmentCertV2, IndirectSignedApprovalVoteV2},
 	},
 	AvailableData, BabeEpoch, BlockWeight, CandidateVotes, CollationGenerationConfig,
-	CollationSecondedSignal, DisputeMessage, DisputeStatus, ErasureChunk, PoV,
+	CollationSecondedSignal, DisputeMessage, DisputeStatus, ErasureChunk, ExportedCandidate, PoV,
 	SignedDisputeStatement, SignedFullStatement, SignedFullStatementWithPVD, SubmitCollationParams,
 	ValidationResult,
 };
@@ -587,6 +587,25 @@ pub enum AvailabilityStoreMessage {
 		/// Sending side of the channel to send result to.
 		tx: oneshot::Sender<Result<(), StoreAvailableDataError>>,
 	},
+
+	/// Export the available data and all chunks stored for a candidate, so they can be imported
+	/// into the AV store of another node.
+	///
+	/// Returns `None` if the candidate is not known to the AV store.
+	ExportCandidate(CandidateHash, oneshot::Sender<Option<ExportedCandidate>>),
+
+	/// Import a previously exported candidate into the AV store.
+	///
+	/// The available data and the chunks are checked against the erasure root and are kept for
+	/// as long as data of finalized candidates.
+	ImportCandidate {
+		/// The exported candidate.
+		candidate: ExportedCandidate,
+		/// The erasure root from the on-chain receipt of the candidate.
+		expected_erasure_root: Hash,
+		/// Sending side of the channel to send result to.
+		tx: oneshot::Sender<Result<(), ImportCandidateError>>,
+	},
 }
 
 /// The error result type of a [`AvailabilityStoreMessage::StoreAvailableData`] request.
@@ -597,6 +616,18 @@ pub enum StoreAvailableDataError {
 	InvalidErasureRoot,
 }
 
+/// The error result type of a [`AvailabilityStoreMessage::ImportCandidate`] request.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum ImportCandidateError {
+	#[error("The computed erasure root did not match expected one")]
+	InvalidErasureRoot,
+	#[error("The chunk of validator {0:?} does not match the erasure root")]
+	InvalidChunk(ValidatorIndex),
+	#[error("The number of validators does not match the stored candidate")]
+	ValidatorCountMismatch,
+}
+
 /// A response channel for the result of a chain API request.
 pub type ChainApiResponseChannel<T> = oneshot::Sender<Result<T, crate::errors::ChainApiError>>;
 
@@ -781,6 +812,11 @@ pub enum RuntimeApiRequest {
 	/// Get the maximum uncompressed code size.
 	/// `V12`
 	ValidationCodeBombLimit(SessionIndex, RuntimeApiSender<u32>),
+	/// Get the availability bitfields included on chain in a block.
+	/// `V13`
+	FetchOnChainBitfields(
+		RuntimeApiSender<Option<polkadot_primitives::vstaging::ScrapedOnChainBitfields>>,
+	),
 }
 
 impl RuntimeApiRequest {
@@ -830,6 +866,9 @@ impl RuntimeApiRequest {
 
 	/// `ValidationCodeBombLimit`
 	pub const VALIDATION_CODE_BOMB_LIMIT_RUNTIME_REQUIREMENT: u32 = 12;
+
+	/// `FetchOnChainBitfields`
+	pub const ON_CHAIN_BITFIELDS_RUNTIME_REQUIREMENT: u32 = 13;
 }
 
 /// A message to the Runtime API subsystem.
@@ -1008,6 +1047,8 @@ pub enum ApprovalVotingParallelMessage {
 		CandidateHash,
 		oneshot::Sender<HashMap<ValidatorIndex, (Vec<CandidateHash>, ValidatorSignature)>>,
 	),
+	/// Gets mapped into `ApprovalVotingMessage::GetApprovalStats`
+	GetApprovalStats(SessionIndex, ValidatorIndex, oneshot::Sender<ApprovalStats>),
 	/// Gets mapped into `ApprovalDistributionMessage::NewBlocks`
 	NewBlocks(Vec<BlockApprovalMeta>),
 	/// Gets mapped into `ApprovalDistributionMessage::DistributeAssignment`
@@ -1037,6 +1078,8 @@ impl TryFrom<ApprovalVotingParallelMessage> for ApprovalVotingMessage {
 				Ok(ApprovalVotingMessage::ApprovedAncestor(hash, number, tx)),
 			ApprovalVotingParallelMessage::GetApprovalSignaturesForCandidate(candidate, tx) =>
 				Ok(ApprovalVotingMessage::GetApprovalSignaturesForCandidate(candidate, tx)),
+			ApprovalVotingParallelMessage::GetApprovalStats(session, validator, tx) =>
+				Ok(ApprovalVotingMessage::GetApprovalStats(session, validator, tx)),
 			_ => Err(()),
 		}
 	}
@@ -1083,6 +1126,21 @@ impl From<ApprovalDistributionMessage> for ApprovalVotingParallelMessage {
 	}
 }
 
+/// Response type to `ApprovalVotingMessage::GetApprovalStats`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ApprovalStats {
+	/// The number of candidates the validator triggered an assignment for.
+	///
+	/// Only known for the validators of this node.
+	pub assignments: u32,
+	/// The number of candidates the validator issued an approval for.
+	///
+	/// Only known for the validators of this node.
+	pub approvals: u32,
+	/// The number of approved candidates for which the validator was a no-show.
+	pub no_shows: u32,
+}
+
 /// Response type to `ApprovalVotingMessage::ApprovedAncestor`.
 #[derive(Clone, Debug)]
 pub struct HighestApprovedAncestorBlock {
@@ -1176,6 +1234,11 @@ pub enum ApprovalVotingMessage {
 		CandidateHash,
 		oneshot::Sender<HashMap<ValidatorIndex, (Vec<CandidateHash>, ValidatorSignature)>>,
 	),
+
+	/// Retrieve the approval work of a validator during a session, as observed by this node.
+	///
+	/// Only a few recent sessions are kept, older ones are reported as empty.
+	GetApprovalStats(SessionIndex, ValidatorIndex, oneshot::Sender<ApprovalStats>),
 }
 
 /// Message to the Approval Distribution subsystem.
@@ -1451,3 +1514,44 @@ pub enum ProspectiveParachainsMessage {
 		oneshot::Sender<Option<PersistedValidationData>>,
 	),
 }
+
+/// Message to the Validator Health subsystem.
+#[derive(Debug)]
+pub enum ValidatorHealthMessage {
+	/// A bitfield was signed by this node at the given relay parent and is being distributed.
+	BitfieldSigned(Hash),
+	/// Get the health of this node as a parachain validator during the given session, or during
+	/// the current session if `None`.
+	///
+	/// Returns `None` if the node is not a validator in the session, or if the session is too old
+	/// to be tracked.
+	GetSessionHealth(Option<SessionIndex>, oneshot::Sender<Option<SessionHealth>>),
+}
+
+/// Response type to `ValidatorHealthMessage::GetSessionHealth`.
+///
+/// All the counters only cover the time the node was running during the session.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SessionHealth {
+	/// The session the report is about.
+	pub session_index: SessionIndex,
+	/// The index of this node in the validators of the session.
+	pub validator_index: ValidatorIndex,
+	/// Whether the node is in the disabled set of the session, as of the latest block seen.
+	pub disabled: bool,
+	/// The number of candidates backed on chain with a statement of this node.
+	pub candidates_backed: u32,
+	/// The number of bitfields this node signed.
+	pub bitfields_signed: u32,
+	/// The number of bitfields of this node included on chain.
+	pub bitfields_included: u32,
+	/// The total number of bits set in the included bitfields of this node, i.e. the number of
+	/// times the node attested on chain to hold its chunk of a candidate pending availability.
+	pub availability_bits_set: u32,
+	/// The approval work of this node.
+	pub approvals: ApprovalStats,
+	/// The number of recent disputes raised in the session.
+	pub disputes: u32,
+	/// The number of recent disputes in the session this node voted in.
+	pub disputes_participated: u32,
+}
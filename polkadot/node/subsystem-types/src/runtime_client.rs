@@ -21,7 +21,8 @@ use polkadot_primitives::{
 	slashing,
 	vstaging::{
 		self, async_backing::Constraints, CandidateEvent,
-		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreState, ScrapedOnChainVotes,
+		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreState,
+		ScrapedOnChainBitfields, ScrapedOnChainVotes,
 	},
 	ApprovalVotingParams, Block, BlockNumber, CandidateCommitments, CandidateHash, CoreIndex,
 	DisputeState, ExecutorParams, GroupRotationInfo, Hash, Header, Id, InboundDownwardMessage,
@@ -357,6 +358,13 @@ pub trait RuntimeApiSubsystemClient {
 	// === v12 ===
 	/// Fetch the maximum uncompressed code size.
 	async fn validation_code_bomb_limit(&self, at: Hash) -> Result<u32, ApiError>;
+
+	// === v13 ===
+	/// Scrape the availability bitfields included on chain.
+	async fn on_chain_bitfields(
+		&self,
+		at: Hash,
+	) -> Result<Option<ScrapedOnChainBitfields<Hash>>, ApiError>;
 }
 
 /// Default implementation of [`RuntimeApiSubsystemClient`] using the client.
@@ -650,6 +658,13 @@ where
 	async fn validation_code_bomb_limit(&self, at: Hash) -> Result<u32, ApiError> {
 		self.client.runtime_api().validation_code_bomb_limit(at)
 	}
+
+	async fn on_chain_bitfields(
+		&self,
+		at: Hash,
+	) -> Result<Option<ScrapedOnChainBitfields<Hash>>, ApiError> {
+		self.client.runtime_api().on_chain_bitfields(at)
+	}
 }
 
 impl<Client, Block> HeaderBackend<Block> for DefaultSubsystemClient<Client>
@@ -32,7 +32,9 @@ use polkadot_node_subsystem_types::UnpinHandle;
 use polkadot_primitives::{
 	node_features::FeatureIndex,
 	slashing,
-	vstaging::{CandidateEvent, CoreState, OccupiedCore, ScrapedOnChainVotes},
+	vstaging::{
+		CandidateEvent, CoreState, OccupiedCore, ScrapedOnChainBitfields, ScrapedOnChainVotes,
+	},
 	CandidateHash, CoreIndex, EncodeAs, ExecutorParams, GroupIndex, GroupRotationInfo, Hash,
 	Id as ParaId, IndexedVec, NodeFeatures, SessionIndex, SessionInfo, Signed, SigningContext,
 	UncheckedSigned, ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
@@ -44,9 +46,10 @@ use std::collections::{BTreeMap, VecDeque};
 use crate::{
 	request_availability_cores, request_candidate_events, request_claim_queue,
 	request_disabled_validators, request_from_runtime, request_key_ownership_proof,
-	request_node_features, request_on_chain_votes, request_session_executor_params,
-	request_session_index_for_child, request_session_info, request_submit_report_dispute_lost,
-	request_unapplied_slashes, request_validation_code_by_hash, request_validator_groups,
+	request_node_features, request_on_chain_bitfields, request_on_chain_votes,
+	request_session_executor_params, request_session_index_for_child, request_session_info,
+	request_submit_report_dispute_lost, request_unapplied_slashes, request_validation_code_by_hash,
+	request_validator_groups,
 };
 
 /// Errors that can happen on runtime fetches.
@@ -406,6 +409,17 @@ where
 	recv_runtime(request_on_chain_votes(relay_parent, sender).await).await
 }
 
+/// Get the availability bitfields included on chain.
+pub async fn get_on_chain_bitfields<Sender>(
+	sender: &mut Sender,
+	relay_parent: Hash,
+) -> Result<Option<ScrapedOnChainBitfields>>
+where
+	Sender: SubsystemSender<RuntimeApiMessage>,
+{
+	recv_runtime(request_on_chain_bitfields(relay_parent, sender).await).await
+}
+
 /// Fetch `ValidationCode` by hash from the runtime.
 pub async fn get_validation_code_by_hash<Sender>(
 	sender: &mut Sender,
@@ -117,7 +117,8 @@ use crate::{
 	slashing,
 	vstaging::{
 		self, async_backing::Constraints, CandidateEvent,
-		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreState, ScrapedOnChainVotes,
+		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreState,
+		ScrapedOnChainBitfields, ScrapedOnChainVotes,
 	},
 	ApprovalVotingParams, AsyncBackingParams, BlockNumber, CandidateCommitments, CandidateHash,
 	CoreIndex, DisputeState, ExecutorParams, GroupRotationInfo, Hash, NodeFeatures,
@@ -313,5 +314,10 @@ sp_api::decl_runtime_apis! {
 		/// Retrieve the maximum uncompressed code size.
 		#[api_version(12)]
 		fn validation_code_bomb_limit() -> u32;
+
+		/***** Added in v13 *****/
+		/// Scrape the availability bitfields included on chain in this block.
+		#[api_version(13)]
+		fn on_chain_bitfields() -> Option<ScrapedOnChainBitfields<Hash>>;
 	}
 }
@@ -20,10 +20,10 @@ use crate::{ValidatorIndex, ValidityAttestation};
 // Put any primitives used by staging APIs functions here
 use super::{
 	async_backing::{InboundHrmpLimitations, OutboundHrmpChannelLimitations},
-	BlakeTwo256, BlockNumber, CandidateCommitments, CandidateDescriptor, CandidateHash, CollatorId,
-	CollatorSignature, CoreIndex, GroupIndex, Hash, HashT, HeadData, Header, Id, Id as ParaId,
-	MultiDisputeStatementSet, ScheduledCore, UncheckedSignedAvailabilityBitfields,
-	UpgradeRestriction, ValidationCodeHash,
+	AvailabilityBitfield, BlakeTwo256, BlockNumber, CandidateCommitments, CandidateDescriptor,
+	CandidateHash, CollatorId, CollatorSignature, CoreIndex, GroupIndex, Hash, HashT, HeadData,
+	Header, Id, Id as ParaId, MultiDisputeStatementSet, ScheduledCore,
+	UncheckedSignedAvailabilityBitfields, UpgradeRestriction, ValidationCodeHash,
 };
 use alloc::{
 	collections::{BTreeMap, BTreeSet, VecDeque},
@@ -839,6 +839,18 @@ impl<H: Encode + Decode + Copy> From<ScrapedOnChainVotes<H>> for super::v8::Scra
 	}
 }
 
+/// Scraped availability bitfields included on chain.
+#[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(PartialEq))]
+pub struct ScrapedOnChainBitfields<H: Encode + Decode = Hash> {
+	/// The session in which the block was included.
+	pub session: SessionIndex,
+	/// The relay parent the bitfields were signed at, i.e. the parent of the block.
+	pub relay_parent: H,
+	/// The payload of the bitfields that passed the checks, by the index of their signer.
+	pub bitfields: Vec<(ValidatorIndex, AvailabilityBitfield)>,
+}
+
 /// Information about a core which is currently occupied.
 #[derive(Clone, Encode, Decode, TypeInfo, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(PartialEq))]
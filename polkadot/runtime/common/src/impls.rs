@@ -396,6 +396,7 @@ mod tests {
 		type BalanceConverter = UnityAssetBalanceConversion;
 		type PayoutPeriod = ConstU64<0>;
 		type BlockNumberProvider = System;
+		type BudgetOrigin = frame_system::EnsureRoot<AccountId>;
 		#[cfg(feature = "runtime-benchmarks")]
 		type BenchmarkHelper = ();
 	}
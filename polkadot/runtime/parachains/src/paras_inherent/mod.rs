@@ -53,7 +53,7 @@ use polkadot_primitives::{
 	node_features::FeatureIndex,
 	vstaging::{
 		BackedCandidate, CandidateDescriptorVersion, CandidateReceiptV2 as CandidateReceipt,
-		InherentData as ParachainsInherentData, ScrapedOnChainVotes,
+		InherentData as ParachainsInherentData, ScrapedOnChainBitfields, ScrapedOnChainVotes,
 	},
 	CandidateHash, CheckedDisputeStatementSet, CheckedMultiDisputeStatementSet, CoreIndex,
 	DisputeStatementSet, HeadData, MultiDisputeStatementSet, SessionIndex,
@@ -151,6 +151,10 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type OnChainVotes<T: Config> = StorageValue<_, ScrapedOnChainVotes<T::Hash>>;
 
+	/// Scraped on chain availability bitfields, for validators to keep track of their own.
+	#[pallet::storage]
+	pub type OnChainBitfields<T: Config> = StorageValue<_, ScrapedOnChainBitfields<T::Hash>>;
+
 	/// Update the disputes statements set part of the on-chain votes.
 	pub(crate) fn set_scrapable_on_chain_disputes<T: Config>(
 		session: SessionIndex,
@@ -192,6 +196,19 @@ pub mod pallet {
 		})
 	}
 
+	/// Update the availability bitfields part of the on-chain data.
+	pub(crate) fn set_scrapable_on_chain_bitfields<T: Config>(
+		session: SessionIndex,
+		relay_parent: T::Hash,
+		bitfields: &SignedAvailabilityBitfields,
+	) {
+		let bitfields = bitfields
+			.iter()
+			.map(|bitfield| (bitfield.validator_index(), bitfield.payload().clone()))
+			.collect();
+		OnChainBitfields::<T>::put(ScrapedOnChainBitfields { session, relay_parent, bitfields });
+	}
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
@@ -460,6 +477,8 @@ impl<T: Config> Pallet<T> {
 				parent_header,
 			};
 
+			set_scrapable_on_chain_bitfields::<T>(current_session, parent_hash, &Vec::new());
+
 			// The relay chain we are currently on is invalid. Proceed no further on parachains.
 			return Ok((processed, Some(checked_disputes_sets_consumed_weight).into()))
 		}
@@ -503,6 +522,8 @@ impl<T: Config> Pallet<T> {
 			&validator_public[..],
 		);
 		METRICS.on_bitfields_processed(bitfields.len() as u64);
+		set_scrapable_on_chain_bitfields::<T>(current_session, parent_hash, &bitfields);
+		all_weight_after.saturating_accrue(T::DbWeight::get().writes(1));
 
 		// Process new availability bitfields, yielding any availability cores whose
 		// work has now concluded.
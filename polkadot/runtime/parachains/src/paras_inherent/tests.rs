@@ -204,6 +204,11 @@ mod enter {
 				2
 			);
 
+			// Both bitfields are scraped in the current session.
+			let on_chain_bitfields = OnChainBitfields::<Test>::get().unwrap();
+			assert_eq!(on_chain_bitfields.bitfields.len(), 2);
+			assert_eq!(on_chain_bitfields.session, 2);
+
 			assert_eq!(
 				inclusion::PendingAvailability::<Test>::get(ParaId::from(0))
 					.unwrap()
@@ -50,7 +50,8 @@ use polkadot_primitives::{
 	slashing,
 	vstaging::{
 		async_backing::Constraints, CandidateEvent,
-		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreState, ScrapedOnChainVotes,
+		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreState,
+		ScrapedOnChainBitfields, ScrapedOnChainVotes,
 	},
 	AccountId, AccountIndex, ApprovalVotingParams, Balance, BlockNumber, CandidateHash, CoreIndex,
 	DisputeState, ExecutorParams, GroupRotationInfo, Hash, Id as ParaId, InboundDownwardMessage,
@@ -244,6 +245,7 @@ parameter_types! {
 	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
 		BlockWeights::get().max_block;
 	pub const MaxScheduledPerBlock: u32 = 50;
+	pub const MaxRecurringTasks: u32 = 100;
 	pub const NoPreimagePostponement: Option<u32> = Some(10);
 }
 
@@ -345,6 +347,8 @@ impl pallet_scheduler::Config for Runtime {
 	type OriginPrivilegeCmp = OriginPrivilegeCmp;
 	type Preimages = Preimage;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
+	type TimeProvider = Timestamp;
+	type MaxRecurringTasks = MaxRecurringTasks;
 }
 
 parameter_types! {
@@ -559,6 +563,7 @@ impl pallet_treasury::Config for Runtime {
 	>;
 	type PayoutPeriod = PayoutSpendPeriod;
 	type BlockNumberProvider = System;
+	type BudgetOrigin = EnsureRoot<AccountId>;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = polkadot_runtime_common::impls::benchmarks::TreasuryArguments;
 }
@@ -747,6 +752,7 @@ impl pallet_identity::Config for Runtime {
 	type MaxSubAccounts = MaxSubAccounts;
 	type IdentityInformation = IdentityInfo<MaxAdditionalFields>;
 	type MaxRegistrars = MaxRegistrars;
+	type MaxExpiriesPerBlock = ConstU32<100>;
 	type Slashed = Treasury;
 	type ForceOrigin = EitherOf<EnsureRoot<Self::AccountId>, GeneralAdmin>;
 	type RegistrarOrigin = EitherOf<EnsureRoot<Self::AccountId>, GeneralAdmin>;
@@ -782,6 +788,7 @@ impl pallet_multisig::Config for Runtime {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = MaxSignatories;
+	type ProposalLifetime = ConstU32<{ 7 * DAYS }>;
 	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
 }
@@ -1886,6 +1893,28 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_scheduler::SchedulerApi<Block, BlockNumber, Moment> for Runtime {
+		fn next_execution(
+			id: frame_support::traits::schedule::v3::TaskName,
+		) -> Option<pallet_scheduler::NextExecution<BlockNumber, Moment>> {
+			Scheduler::next_execution(id)
+		}
+
+		fn recurring_tasks() -> Vec<(frame_support::traits::schedule::v3::TaskName, Moment)> {
+			Scheduler::recurring_tasks()
+		}
+	}
+
+	impl pallet_treasury::TreasuryApi<Block, OriginCaller, VersionedLocatableAsset, Balance> for Runtime {
+		fn remaining_budget(origin: OriginCaller, asset_kind: VersionedLocatableAsset) -> Option<Balance> {
+			Treasury::remaining_budget(&origin, &asset_kind)
+		}
+
+		fn budgets_of(origin: OriginCaller) -> Vec<(VersionedLocatableAsset, Balance)> {
+			Treasury::budgets_of(&origin)
+		}
+	}
+
 	impl xcm_runtime_apis::fees::XcmPaymentApi<Block> for Runtime {
 		fn query_acceptable_payment_assets(xcm_version: xcm::Version) -> Result<Vec<VersionedAssetId>, XcmPaymentApiError> {
 			let acceptable_assets = vec![AssetId(xcm_config::TokenLocation::get())];
@@ -1992,7 +2021,7 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
-	#[api_version(12)]
+	#[api_version(13)]
 	impl polkadot_primitives::runtime_api::ParachainHost<Block> for Runtime {
 		fn validators() -> Vec<ValidatorId> {
 			parachains_runtime_api_impl::validators::<Runtime>()
@@ -2170,6 +2199,10 @@ sp_api::impl_runtime_apis! {
 		fn validation_code_bomb_limit() -> u32 {
 			parachains_staging_runtime_api_impl::validation_code_bomb_limit::<Runtime>()
 		}
+
+		fn on_chain_bitfields() -> Option<ScrapedOnChainBitfields<Hash>> {
+			parachains_staging_runtime_api_impl::on_chain_bitfields::<Runtime>()
+		}
 	}
 
 	#[api_version(5)]
@@ -288,4 +288,71 @@ impl<T: frame_system::Config> pallet_scheduler::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `Scheduler::Lookup` (r:1 w:0)
+	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringTasks` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringTasks` (`max_values`: None, `max_size`: Some(136), added: 2611, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(4004), added: 4499, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 99]`.
+	fn schedule_recurring(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76 + s * (40 ±0)`
+		//  Estimated: `5489`
+		// Minimum execution time: 26_648_000 picoseconds.
+		Weight::from_parts(29_048_325, 0)
+			.saturating_add(Weight::from_parts(0, 5489))
+			// Standard Error: 2_016
+			.saturating_add(Weight::from_parts(75_606, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Scheduler::RecurringTasks` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringTasks` (`max_values`: None, `max_size`: Some(136), added: 2611, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(4004), added: 4499, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn cancel_recurring(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212 + s * (40 ±0)`
+		//  Estimated: `5489`
+		// Minimum execution time: 31_491_000 picoseconds.
+		Weight::from_parts(33_656_911, 0)
+			.saturating_add(Weight::from_parts(0, 5489))
+			// Standard Error: 2_459
+			.saturating_add(Weight::from_parts(96_636, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(4004), added: 4499, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 100]`.
+	fn service_recurring_base(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76 + s * (40 ±0)`
+		//  Estimated: `5489`
+		// Minimum execution time: 5_680_000 picoseconds.
+		Weight::from_parts(6_611_588, 0)
+			.saturating_add(Weight::from_parts(0, 5489))
+			// Standard Error: 735
+			.saturating_add(Weight::from_parts(23_004, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Scheduler::RecurringTasks` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringTasks` (`max_values`: None, `max_size`: Some(136), added: 2611, mode: `MaxEncodedLen`)
+	fn service_recurring_task() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212`
+		//  Estimated: `3601`
+		// Minimum execution time: 16_344_000 picoseconds.
+		Weight::from_parts(17_217_000, 0)
+			.saturating_add(Weight::from_parts(0, 3601))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
@@ -57,14 +57,13 @@ impl<T: frame_system::Config> pallet_treasury::WeightInfo for WeightInfo<T> {
 	/// Proof: `Treasury::Approvals` (`max_values`: Some(1), `max_size`: Some(402), added: 897, mode: `MaxEncodedLen`)
 	/// Storage: `Treasury::Proposals` (r:0 w:1)
 	/// Proof: `Treasury::Proposals` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:0)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(1900), added: 4375, mode: `MaxEncodedLen`)
 	fn spend_local() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `142`
-		//  Estimated: `1887`
-		// Minimum execution time: 12_748_000 picoseconds.
+		// Not benchmarked yet: placeholder until this file is regenerated.
 		Weight::from_parts(13_519_000, 0)
-			.saturating_add(Weight::from_parts(0, 1887))
-			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(Weight::from_parts(0, 5365))
+			.saturating_add(T::DbWeight::get().reads(3))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
 	/// Storage: `Treasury::Approvals` (r:1 w:1)
@@ -102,15 +101,16 @@ impl<T: frame_system::Config> pallet_treasury::WeightInfo for WeightInfo<T> {
 	/// Proof: `Treasury::SpendCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
 	/// Storage: `Treasury::Spends` (r:0 w:1)
 	/// Proof: `Treasury::Spends` (`max_values`: None, `max_size`: Some(1853), added: 4328, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(1900), added: 4375, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::BudgetedSpends` (r:0 w:1)
+	/// Proof: `Treasury::BudgetedSpends` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
 	fn spend() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `214`
-		//  Estimated: `4703`
-		// Minimum execution time: 20_171_000 picoseconds.
+		// Not benchmarked yet: placeholder until this file is regenerated.
 		Weight::from_parts(21_114_000, 0)
-			.saturating_add(Weight::from_parts(0, 4703))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(Weight::from_parts(0, 5365))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: `Treasury::Spends` (r:1 w:1)
 	/// Proof: `Treasury::Spends` (`max_values`: None, `max_size`: Some(1853), added: 4328, mode: `MaxEncodedLen`)
@@ -142,25 +142,45 @@ impl<T: frame_system::Config> pallet_treasury::WeightInfo for WeightInfo<T> {
 	/// Proof: `Treasury::Spends` (`max_values`: None, `max_size`: Some(1853), added: 4328, mode: `MaxEncodedLen`)
 	/// Storage: `XcmPallet::Queries` (r:1 w:1)
 	/// Proof: `XcmPallet::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Treasury::BudgetedSpends` (r:1 w:1)
+	/// Proof: `Treasury::BudgetedSpends` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(1900), added: 4375, mode: `MaxEncodedLen`)
 	fn check_status() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `305`
-		//  Estimated: `5318`
-		// Minimum execution time: 25_238_000 picoseconds.
+		// Not benchmarked yet: placeholder until this file is regenerated.
 		Weight::from_parts(25_654_000, 0)
-			.saturating_add(Weight::from_parts(0, 5318))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(Weight::from_parts(0, 5365))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: `Treasury::Spends` (r:1 w:1)
 	/// Proof: `Treasury::Spends` (`max_values`: None, `max_size`: Some(1853), added: 4328, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::BudgetedSpends` (r:1 w:1)
+	/// Proof: `Treasury::BudgetedSpends` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(1900), added: 4375, mode: `MaxEncodedLen`)
 	fn void_spend() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `277`
-		//  Estimated: `5318`
-		// Minimum execution time: 14_929_000 picoseconds.
+		// Not benchmarked yet: placeholder until this file is regenerated.
 		Weight::from_parts(15_441_000, 0)
-			.saturating_add(Weight::from_parts(0, 5318))
+			.saturating_add(Weight::from_parts(0, 5365))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(1900), added: 4375, mode: `MaxEncodedLen`)
+	fn set_spend_budget() -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(9_867_000, 0)
+			.saturating_add(Weight::from_parts(0, 5365))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(1900), added: 4375, mode: `MaxEncodedLen`)
+	fn remove_spend_budget() -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(10_683_000, 0)
+			.saturating_add(Weight::from_parts(0, 5365))
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
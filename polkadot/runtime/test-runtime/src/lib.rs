@@ -64,7 +64,8 @@ use polkadot_primitives::{
 	slashing,
 	vstaging::{
 		async_backing::Constraints, CandidateEvent,
-		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreState, ScrapedOnChainVotes,
+		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, CoreState,
+		ScrapedOnChainBitfields, ScrapedOnChainVotes,
 	},
 	AccountId, AccountIndex, Balance, BlockNumber, CandidateHash, CoreIndex, DisputeState,
 	ExecutorParams, GroupRotationInfo, Hash as HashT, Id as ParaId, InboundDownwardMessage,
@@ -939,7 +940,7 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
-	#[api_version(12)]
+	#[api_version(13)]
 	impl polkadot_primitives::runtime_api::ParachainHost<Block> for Runtime {
 		fn validators() -> Vec<ValidatorId> {
 			runtime_impl::validators::<Runtime>()
@@ -1114,6 +1115,10 @@ sp_api::impl_runtime_apis! {
 		fn validation_code_bomb_limit() -> u32 {
 			staging_runtime_impl::validation_code_bomb_limit::<Runtime>()
 		}
+
+		fn on_chain_bitfields() -> Option<ScrapedOnChainBitfields<Hash>> {
+			staging_runtime_impl::on_chain_bitfields::<Runtime>()
+		}
 	}
 
 	impl sp_consensus_beefy::BeefyApi<Block, BeefyId> for Runtime {
@@ -27,7 +27,7 @@ use frame_support::{
 	},
 	PalletId,
 };
-use sp_core::{ConstU128, ConstU32, Get};
+use sp_core::{ConstU128, ConstU32, ConstU64, Get};
 use sp_runtime::{
 	traits::{AccountIdConversion, IdentityLookup, MaybeEquivalence, TryConvert, TryConvertInto},
 	BuildStorage, Permill,
@@ -144,7 +144,10 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LPFee = ConstU32<3>;
 	type PalletId = AssetConversionPalletId;
 	type MaxSwapPathLength = ConstU32<3>;
+	type PriceObservationPeriod = ConstU64<10>;
+	type MaxPriceObservations = ConstU32<5>;
 	type MintMinLiquidity = ConstU128<100>;
+	type PoolCurveOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type WeightInfo = ();
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
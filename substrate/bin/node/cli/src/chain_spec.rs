@@ -55,6 +55,8 @@ pub struct Extensions {
 	pub bad_blocks: sc_client_api::BadBlocks<Block>,
 	/// The light sync state extension used by the sync-state rpc.
 	pub light_sync_state: sc_sync_state_rpc::LightSyncStateExtension,
+	/// Trusted block to start warp sync from instead of genesis.
+	pub warp_sync_checkpoint: sc_service::WarpSyncCheckpointExtension<Block>,
 }
 
 /// Specialized `ChainSpec`.
@@ -525,6 +525,7 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 			import_queue,
 			block_announce_validator_builder: None,
 			warp_sync_config: Some(WarpSyncConfig::WithProvider(warp_sync)),
+			state_sync_store: Some(client.clone()),
 			block_relay: None,
 			metrics,
 		})?;
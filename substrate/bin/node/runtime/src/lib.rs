@@ -53,8 +53,7 @@ use frame_support::{
 		},
 		tokens::{
 			imbalance::{ResolveAssetTo, ResolveTo},
-			nonfungibles_v2::Inspect,
-			pay::PayAssetFromAccount,
+			nonfungibles_v2::{Inspect, Royalties},
 			GetSalary, PayFromAccount,
 		},
 		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU16, ConstU32, ConstU64,
@@ -397,6 +396,7 @@ impl pallet_multisig::Config for Runtime {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = ConstU32<100>;
+	type ProposalLifetime = ConstU32<{ 7 * DAYS }>;
 	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
 }
@@ -510,6 +510,8 @@ impl pallet_scheduler::Config for Runtime {
 	type OriginPrivilegeCmp = EqualPrivilegeOnly;
 	type Preimages = Preimage;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
+	type TimeProvider = Timestamp;
+	type MaxRecurringTasks = ConstU32<100>;
 }
 
 impl pallet_glutton::Config for Runtime {
@@ -1541,6 +1543,7 @@ parameter_types! {
 	pub const MaxApprovals: u32 = 100;
 	pub const MaxBalance: Balance = Balance::max_value();
 	pub const SpendPayoutPeriod: BlockNumber = 30 * DAYS;
+	pub const SpendStreamDuration: BlockNumber = 7 * DAYS;
 }
 
 impl pallet_treasury::Config for Runtime {
@@ -1561,10 +1564,12 @@ impl pallet_treasury::Config for Runtime {
 	type AssetKind = NativeOrWithId<u32>;
 	type Beneficiary = AccountId;
 	type BeneficiaryLookup = Indices;
-	type Paymaster = PayAssetFromAccount<NativeAndAssets, TreasuryAccount>;
+	type Paymaster =
+		pallet_payment_streams::PayThroughStream<Runtime, TreasuryAccount, SpendStreamDuration>;
 	type BalanceConverter = AssetRate;
 	type PayoutPeriod = SpendPayoutPeriod;
 	type BlockNumberProvider = System;
+	type BudgetOrigin = EnsureRoot<AccountId>;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = PalletTreasuryArguments;
 }
@@ -1889,6 +1894,7 @@ impl pallet_identity::Config for Runtime {
 	type MaxSubAccounts = MaxSubAccounts;
 	type IdentityInformation = IdentityInfo<MaxAdditionalFields>;
 	type MaxRegistrars = MaxRegistrars;
+	type MaxExpiriesPerBlock = ConstU32<100>;
 	type Slashed = Treasury;
 	type ForceOrigin = EnsureRootOrHalfCouncil;
 	type RegistrarOrigin = EnsureRootOrHalfCouncil;
@@ -2035,8 +2041,8 @@ impl pallet_assets::Config<Instance1> for Runtime {
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
-	type Holder = ();
-	type Freezer = ();
+	type Holder = AssetsHolder;
+	type Freezer = AssetsFreezer;
 	type Extra = ();
 	type CallbackHandle = ();
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
@@ -2045,6 +2051,11 @@ impl pallet_assets::Config<Instance1> for Runtime {
 	type BenchmarkHelper = ();
 }
 
+impl pallet_assets_holder::Config<Instance1> for Runtime {
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeEvent = RuntimeEvent;
+}
+
 ord_parameter_types! {
 	pub const AssetConversionOrigin: AccountId = AccountIdConversion::<AccountId>::into_account_truncating(&AssetConversionPalletId::get());
 }
@@ -2112,16 +2123,172 @@ impl pallet_asset_conversion::Config for Runtime {
 	type PalletId = AssetConversionPalletId;
 	type LPFee = ConstU32<3>; // means 0.3%
 	type LiquidityWithdrawalFee = LiquidityWithdrawalFee;
+	type PoolCurveOrigin = EnsureRoot<AccountId>;
 	type WeightInfo = pallet_asset_conversion::weights::SubstrateWeight<Runtime>;
 	type MaxSwapPathLength = ConstU32<4>;
+	type PriceObservationPeriod = ConstU32<{ 10 * MINUTES }>;
+	type MaxPriceObservations = ConstU32<144>;
 	type MintMinLiquidity = MintMinLiquidity;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
 }
 
+parameter_types! {
+	pub const LimitOrderDeposit: Balance = deposit(1, 200);
+}
+
+impl pallet_asset_conversion_limit_orders::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type AssetKind = NativeOrWithId<u32>;
+	type Balance = u128;
+	type Assets = NativeAndAssets;
+	type Currency = Balances;
+	type OrderDeposit = LimitOrderDeposit;
+	type Swap = AssetConversion;
+	type Scheduler = Scheduler;
+	type Preimages = Preimage;
+	type MaxOrdersPerBook = ConstU32<64>;
+	type MaxFills = ConstU32<8>;
+	type MaxOrderLifetime = ConstU32<{ 28 * DAYS }>;
+	type WeightInfo = pallet_asset_conversion_limit_orders::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = LimitOrdersBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct LimitOrdersBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl
+	pallet_asset_conversion_limit_orders::benchmarking::BenchmarkHelper<
+		NativeOrWithId<u32>,
+		Balance,
+	> for LimitOrdersBenchmarkHelper
+{
+	fn asset_pair() -> (NativeOrWithId<u32>, NativeOrWithId<u32>) {
+		let asset_id = 1;
+		if !Assets::asset_exists(asset_id) {
+			frame_support::assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				asset_id.into(),
+				AccountId::from([0u8; 32]).into(),
+				true,
+				1,
+			));
+		}
+		(NativeOrWithId::Native, NativeOrWithId::WithId(asset_id))
+	}
+
+	fn setup_pool(asset1: NativeOrWithId<u32>, asset2: NativeOrWithId<u32>, amount: Balance) {
+		use frame_support::{assert_ok, traits::fungibles::Mutate};
+		let provider = AccountId::from([1u8; 32]);
+		let fee = PoolSetupFee::get();
+		assert_ok!(NativeAndAssets::mint_into(
+			asset1.clone(),
+			&provider,
+			amount.saturating_mul(2).saturating_add(fee)
+		));
+		assert_ok!(NativeAndAssets::mint_into(asset2.clone(), &provider, amount.saturating_mul(2)));
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(provider.clone()),
+			alloc::boxed::Box::new(asset1.clone()),
+			alloc::boxed::Box::new(asset2.clone()),
+		));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(provider.clone()),
+			alloc::boxed::Box::new(asset1),
+			alloc::boxed::Box::new(asset2),
+			amount,
+			amount,
+			1,
+			1,
+			provider,
+		));
+	}
+}
+
+parameter_types! {
+	pub const StreamDeposit: Balance = deposit(1, 120);
+}
+
+impl pallet_payment_streams::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type AssetKind = NativeOrWithId<u32>;
+	type Balance = u128;
+	type Assets = NativeAndAssets;
+	type Currency = Balances;
+	type StreamDeposit = StreamDeposit;
+	type WeightInfo = pallet_payment_streams::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = PaymentStreamsBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct PaymentStreamsBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_payment_streams::benchmarking::BenchmarkHelper<NativeOrWithId<u32>>
+	for PaymentStreamsBenchmarkHelper
+{
+	fn asset() -> NativeOrWithId<u32> {
+		let asset_id = 1;
+		if !Assets::asset_exists(asset_id) {
+			frame_support::assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				asset_id.into(),
+				AccountId::from([0u8; 32]).into(),
+				true,
+				1,
+			));
+		}
+		NativeOrWithId::WithId(asset_id)
+	}
+}
+
 pub type NativeAndAssetsFreezer =
 	UnionOf<Balances, AssetsFreezer, NativeFromLeft, NativeOrWithId<u32>, AccountId>;
 
+impl pallet_assets_vesting::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type AssetKind = NativeOrWithId<u32>;
+	type Balance = u128;
+	type Assets = NativeAndAssets;
+	type AssetsFreezer = NativeAndAssetsFreezer;
+	type VestedTransferOrigin =
+		AsEnsureOriginWithArg<EnsureRootWithSuccess<AccountId, TreasuryAccount>>;
+	type MaxVestingSchedules = ConstU32<28>;
+	type BlockNumberProvider = System;
+	type WeightInfo = pallet_assets_vesting::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = AssetsVestingBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct AssetsVestingBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_assets_vesting::benchmarking::BenchmarkHelper<NativeOrWithId<u32>>
+	for AssetsVestingBenchmarkHelper
+{
+	fn asset() -> NativeOrWithId<u32> {
+		let asset_id = 1;
+		if !Assets::asset_exists(asset_id) {
+			frame_support::assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				asset_id.into(),
+				AccountId::from([0u8; 32]).into(),
+				true,
+				1,
+			));
+		}
+		NativeOrWithId::WithId(asset_id)
+	}
+}
+
 /// Benchmark Helper
 #[cfg(feature = "runtime-benchmarks")]
 pub struct AssetRewardsBenchmarkHelper;
@@ -2332,6 +2499,7 @@ impl pallet_nft_fractionalization::Config for Runtime {
 parameter_types! {
 	pub Features: PalletFeatures = PalletFeatures::all_enabled();
 	pub const MaxAttributesPerCall: u32 = 10;
+	pub const MaxRoyaltyRecipients: u32 = 10;
 }
 
 impl pallet_nfts::Config for Runtime {
@@ -2353,6 +2521,7 @@ impl pallet_nfts::Config for Runtime {
 	type MaxTips = MaxTips;
 	type MaxDeadlineDuration = MaxDeadlineDuration;
 	type MaxAttributesPerCall = MaxAttributesPerCall;
+	type MaxRoyaltyRecipients = MaxRoyaltyRecipients;
 	type Features = Features;
 	type OffchainSignature = Signature;
 	type OffchainPublic = <Signature as traits::Verify>::Signer;
@@ -3018,6 +3187,18 @@ mod runtime {
 
 	#[runtime::pallet_index(89)]
 	pub type MetaTx = pallet_meta_tx::Pallet<Runtime>;
+
+	#[runtime::pallet_index(90)]
+	pub type AssetsHolder = pallet_assets_holder::Pallet<Runtime, Instance1>;
+
+	#[runtime::pallet_index(91)]
+	pub type LimitOrders = pallet_asset_conversion_limit_orders::Pallet<Runtime>;
+
+	#[runtime::pallet_index(92)]
+	pub type PaymentStreams = pallet_payment_streams::Pallet<Runtime>;
+
+	#[runtime::pallet_index(93)]
+	pub type AssetsVesting = pallet_assets_vesting::Pallet<Runtime>;
 }
 
 impl TryFrom<RuntimeCall> for pallet_revive::Call<Runtime> {
@@ -3228,6 +3409,8 @@ mod benches {
 		[pallet_example_tasks, TasksExample]
 		[pallet_democracy, Democracy]
 		[pallet_asset_conversion, AssetConversion]
+		[pallet_asset_conversion_limit_orders, LimitOrders]
+		[pallet_assets_vesting, AssetsVesting]
 		[pallet_asset_rewards, AssetRewards]
 		[pallet_asset_conversion_tx_payment, AssetConversionTxPayment]
 		[pallet_transaction_payment, TransactionPayment]
@@ -3241,6 +3424,7 @@ mod benches {
 		[pallet_fast_unstake, FastUnstake]
 		[pallet_nis, Nis]
 		[pallet_parameters, Parameters]
+		[pallet_payment_streams, PaymentStreams]
 		[pallet_grandpa, Grandpa]
 		[pallet_identity, Identity]
 		[pallet_im_online, ImOnline]
@@ -3342,6 +3526,17 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl sp_block_builder::BlockRetentionApi<Block> for Runtime {
+		fn retain_body(extrinsics: Vec<<Block as BlockT>::Extrinsic>) -> bool {
+			extrinsics.iter().any(|xt| {
+				matches!(
+					xt.function,
+					RuntimeCall::Assets(_) | RuntimeCall::PoolAssets(_) | RuntimeCall::Nfts(_)
+				)
+			})
+		}
+	}
+
 	impl sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block> for Runtime {
 		fn validate_transaction(
 			source: TransactionSource,
@@ -3532,6 +3727,28 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_scheduler::SchedulerApi<Block, BlockNumber, Moment> for Runtime {
+		fn next_execution(
+			id: frame_support::traits::schedule::v3::TaskName,
+		) -> Option<pallet_scheduler::NextExecution<BlockNumber, Moment>> {
+			Scheduler::next_execution(id)
+		}
+
+		fn recurring_tasks() -> Vec<(frame_support::traits::schedule::v3::TaskName, Moment)> {
+			Scheduler::recurring_tasks()
+		}
+	}
+
+	impl pallet_treasury::TreasuryApi<Block, OriginCaller, NativeOrWithId<u32>, Balance> for Runtime {
+		fn remaining_budget(origin: OriginCaller, asset_kind: NativeOrWithId<u32>) -> Option<Balance> {
+			Treasury::remaining_budget(&origin, &asset_kind)
+		}
+
+		fn budgets_of(origin: OriginCaller) -> Vec<(NativeOrWithId<u32>, Balance)> {
+			Treasury::budgets_of(&origin)
+		}
+	}
+
 	impl pallet_contracts::ContractsApi<Block, AccountId, Balance, BlockNumber, Hash, EventRecord> for Runtime
 	{
 		fn call(
@@ -3789,6 +4006,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl pallet_asset_conversion::AssetConversionApi<
 		Block,
 		Balance,
@@ -3806,6 +4024,35 @@ impl_runtime_apis! {
 		fn get_reserves(asset1: NativeOrWithId<u32>, asset2: NativeOrWithId<u32>) -> Option<(Balance, Balance)> {
 			AssetConversion::get_reserves(asset1, asset2).ok()
 		}
+
+		fn get_twap(asset1: NativeOrWithId<u32>, asset2: NativeOrWithId<u32>, window: u32) -> Option<sp_runtime::FixedU128> {
+			AssetConversion::get_twap(&asset1, &asset2, window)
+		}
+	}
+
+	impl pallet_asset_conversion_limit_orders::LimitOrdersApi<
+		Block,
+		NativeOrWithId<u32>,
+		Balance,
+	> for Runtime
+	{
+		fn order_book_depth(
+			asset_in: NativeOrWithId<u32>,
+			asset_out: NativeOrWithId<u32>,
+			max_levels: u32,
+		) -> Vec<pallet_asset_conversion_limit_orders::PriceLevel<Balance>> {
+			LimitOrders::order_book_depth(asset_in, asset_out, max_levels)
+		}
+	}
+
+	impl pallet_assets_vesting::AssetsVestingApi<Block, AccountId, NativeOrWithId<u32>, Balance>
+		for Runtime
+	{
+		fn vesting_balances(
+			who: AccountId,
+		) -> Vec<(NativeOrWithId<u32>, pallet_assets_vesting::VestingBalance<Balance>)> {
+			AssetsVesting::vesting_balances(&who)
+		}
 	}
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
@@ -3869,6 +4116,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_nfts_runtime_api::NftsRoyaltiesApi<Block, AccountId, u32, u32, Balance> for Runtime {
+		fn royalties(collection: u32, item: u32, sale_price: Balance) -> Vec<(AccountId, Balance)> {
+			<Nfts as Royalties<AccountId, Balance>>::royalties(&collection, &item, &sale_price)
+		}
+	}
+
 	#[api_version(5)]
 	impl sp_consensus_beefy::BeefyApi<Block, BeefyId> for Runtime {
 		fn beefy_genesis() -> Option<BlockNumber> {
@@ -235,6 +235,10 @@ pub trait BlockImportOperation<Block: BlockT> {
 
 	/// Configure whether to create a block gap if newly imported block is missing parent
 	fn set_create_gap(&mut self, create_gap: bool);
+
+	/// Configure whether to keep the body of the imported block when blocks are pruned, as
+	/// selected by the runtime.
+	fn set_body_retained(&mut self, retained: bool);
 }
 
 /// Interface for performing operations on the backend.
@@ -350,6 +350,7 @@ impl<Block: BlockT> HeaderBackend<Block> for Blockchain<Block> {
 			},
 			number_leaves: storage.leaves.count(),
 			block_gap: None,
+			pruned_blocks: None,
 		}
 	}
 
@@ -586,6 +587,8 @@ impl<Block: BlockT> backend::BlockImportOperation<Block> for BlockImportOperatio
 	}
 
 	fn set_create_gap(&mut self, _create_gap: bool) {}
+
+	fn set_body_retained(&mut self, _retained: bool) {}
 }
 
 /// In-memory backend. Keeps all states and blocks in memory.
@@ -80,6 +80,7 @@ impl<Block: BlockT> HeaderBackend<Block> for TestApi {
 			number_leaves: Default::default(),
 			finalized_state: None,
 			block_gap: None,
+			pruned_blocks: None,
 		}
 	}
 
@@ -19,7 +19,7 @@
 use crate::{CliConfiguration, DatabaseParams, PruningParams, Result as CliResult, SharedParams};
 use codec::{Decode, Encode};
 use sc_client_api::{backend::Backend as BackendT, blockchain::HeaderBackend};
-use sp_blockchain::Info;
+use sp_blockchain::{Info, PrunedBlocks};
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 use std::{fmt::Debug, io};
 
@@ -52,6 +52,32 @@ struct ChainInfo<B: BlockT> {
 	finalized_hash: B::Hash,
 	/// Last finalized block number.
 	finalized_number: <<B as BlockT>::Header as HeaderT>::Number,
+	/// Finalized blocks removed by block pruning.
+	pruned_blocks: Option<PrunedBlocksInfo<<<B as BlockT>::Header as HeaderT>::Number>>,
+}
+
+/// Serializable range of pruned blocks.
+#[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, serde::Serialize)]
+struct PrunedBlocksInfo<N> {
+	/// The first pruned block number (inclusive).
+	start: N,
+	/// The last pruned block number (inclusive).
+	end: N,
+	/// Whether justifications of all blocks in the range were retained.
+	justifications_retained: bool,
+	/// Whether bodies of the blocks in the range selected by the runtime were retained.
+	bodies_retained: bool,
+}
+
+impl<N> From<PrunedBlocks<N>> for PrunedBlocksInfo<N> {
+	fn from(pruned: PrunedBlocks<N>) -> Self {
+		PrunedBlocksInfo {
+			start: pruned.start,
+			end: pruned.end,
+			justifications_retained: pruned.justifications_retained,
+			bodies_retained: pruned.bodies_retained,
+		}
+	}
 }
 
 impl<B: BlockT> From<Info<B>> for ChainInfo<B> {
@@ -62,6 +88,7 @@ impl<B: BlockT> From<Info<B>> for ChainInfo<B> {
 			genesis_hash: info.genesis_hash,
 			finalized_hash: info.finalized_hash,
 			finalized_number: info.finalized_number,
+			pruned_blocks: info.pruned_blocks.map(Into::into),
 		}
 	}
 }
@@ -77,6 +104,7 @@ impl ChainInfoCmd {
 			state_pruning: config.state_pruning.clone(),
 			source: config.database.clone(),
 			blocks_pruning: config.blocks_pruning,
+			blocks_retention: config.blocks_retention,
 		};
 		let backend = sc_service::new_db_backend::<B>(db_config)?;
 		let info: ChainInfo<B> = backend.blockchain().info().into();
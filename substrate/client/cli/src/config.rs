@@ -32,7 +32,7 @@ use sc_service::{
 		Role, RpcBatchRequestConfig, RpcConfiguration, RpcMethods, TelemetryEndpoints,
 		TransactionPoolOptions, WasmExecutionMethod,
 	},
-	BlocksPruning, ChainSpec, TracingReceiver,
+	BlocksPruning, BlocksRetention, ChainSpec, TracingReceiver,
 };
 use sc_tracing::logging::LoggerBuilder;
 use std::{num::NonZeroU32, path::PathBuf};
@@ -272,6 +272,16 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			.unwrap_or_else(|| Ok(BlocksPruning::KeepFinalized))
 	}
 
+	/// Get the data retained from pruned blocks.
+	///
+	/// By default this is retrieved from `PruningParams` if it is available. Otherwise nothing is
+	/// retained.
+	fn blocks_retention(&self) -> Result<BlocksRetention> {
+		self.pruning_params()
+			.map(|x| x.blocks_retention())
+			.unwrap_or_else(|| Ok(BlocksRetention::default()))
+	}
+
 	/// Get the chain ID (string).
 	///
 	/// By default this is retrieved from `SharedParams`.
@@ -530,6 +540,7 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			trie_cache_maximum_size: self.trie_cache_maximum_size()?,
 			state_pruning: self.state_pruning()?,
 			blocks_pruning: self.blocks_pruning()?,
+			blocks_retention: self.blocks_retention()?,
 			executor: ExecutorConfiguration {
 				wasm_method: self.wasm_method()?,
 				default_heap_pages: self.default_heap_pages()?,
@@ -163,6 +163,15 @@ pub struct NetworkParams {
 	)]
 	pub sync: SyncMode,
 
+	/// Path to a JSON file with a trusted checkpoint to start warp sync from.
+	///
+	/// The checkpoint contains the block `hash` and `number`, the `setId` of the GRANDPA
+	/// authority set finalizing the following blocks and its `authorities`. Peers only prove
+	/// the authority set changes after the checkpoint. Overrides the checkpoint of the chain spec.
+	/// Only used with `--sync warp`.
+	#[arg(long, value_name = "PATH")]
+	pub warp_sync_checkpoint: Option<PathBuf>,
+
 	/// Maximum number of blocks per request.
 	///
 	/// Try reducing this number from the default value if you have a slow network connection
@@ -276,6 +285,7 @@ impl NetworkParams {
 			kademlia_replication_factor: self.kademlia_replication_factor,
 			ipfs_server: self.ipfs_server,
 			sync_mode: self.sync.into(),
+			warp_sync_checkpoint: self.warp_sync_checkpoint.clone(),
 			network_backend: self.network_backend.map(Into::into),
 		}
 	}
@@ -331,4 +341,13 @@ mod tests {
 
 		assert_eq!(SyncMode::Warp, params.network_params.sync);
 	}
+
+	#[test]
+	fn warp_sync_checkpoint_path_is_parsed() {
+		let params =
+			Cli::try_parse_from(["", "--sync", "warp", "--warp-sync-checkpoint", "cp.json"])
+				.expect("Parses network params");
+
+		assert_eq!(Some(PathBuf::from("cp.json")), params.network_params.warp_sync_checkpoint);
+	}
 }
@@ -18,7 +18,7 @@
 
 use crate::error;
 use clap::Args;
-use sc_service::{BlocksPruning, PruningMode};
+use sc_service::{BlocksPruning, BlocksRetention, PruningMode};
 
 /// Parameters to define the pruning mode
 #[derive(Debug, Clone, Args)]
@@ -64,6 +64,20 @@ pub struct PruningParams {
 		default_value = "archive-canonical"
 	)]
 	pub blocks_pruning: DatabasePruningMode,
+
+	/// Keep the justifications of blocks removed by `--blocks-pruning`.
+	///
+	/// Useful for nodes serving finality proofs, e.g. to bridges.
+	#[arg(long)]
+	pub blocks_pruning_keep_justifications: bool,
+
+	/// Keep the bodies of blocks removed by `--blocks-pruning` which the runtime selects.
+	///
+	/// The runtime is asked for each block as it is executed on import, so the bodies of blocks
+	/// imported before setting this flag, or without execution by warp or gap sync, are not kept.
+	/// Has no effect unless `--blocks-pruning` is set to a number of blocks.
+	#[arg(long)]
+	pub blocks_pruning_keep_bodies: bool,
 }
 
 impl PruningParams {
@@ -76,6 +90,14 @@ impl PruningParams {
 	pub fn blocks_pruning(&self) -> error::Result<BlocksPruning> {
 		Ok(self.blocks_pruning.into())
 	}
+
+	/// Get the data retained from pruned blocks.
+	pub fn blocks_retention(&self) -> error::Result<BlocksRetention> {
+		Ok(BlocksRetention {
+			justifications: self.blocks_pruning_keep_justifications,
+			bodies: self.blocks_pruning_keep_bodies,
+		})
+	}
 }
 
 /// Specifies the pruning mode of the database.
@@ -162,4 +184,22 @@ mod tests {
 		assert!(matches!(dbg!(pruning.state_pruning), Some(DatabasePruningMode::ArchiveCanonical)));
 		assert!(matches!(pruning.blocks_pruning, DatabasePruningMode::ArchiveCanonical));
 	}
+
+	#[test]
+	fn blocks_retention_parse_works() {
+		let Cli { pruning } = Cli::parse_from(["", "--blocks-pruning=1000"]);
+		assert!(!pruning.blocks_retention().unwrap().justifications);
+
+		let Cli { pruning } =
+			Cli::parse_from(["", "--blocks-pruning=1000", "--blocks-pruning-keep-justifications"]);
+		let retention = pruning.blocks_retention().unwrap();
+		assert!(retention.justifications);
+		assert!(!retention.bodies);
+
+		let Cli { pruning } =
+			Cli::parse_from(["", "--blocks-pruning=1000", "--blocks-pruning-keep-bodies"]);
+		let retention = pruning.blocks_retention().unwrap();
+		assert!(!retention.justifications);
+		assert!(retention.bodies);
+	}
 }
@@ -45,6 +45,9 @@ const REBROADCAST_AFTER: Duration = Duration::from_secs(60);
 #[cfg(test)]
 const REBROADCAST_AFTER: Duration = Duration::from_secs(5);
 
+/// Maximum number of votes for future rounds buffered for the fisherman.
+const MAX_FUTURE_VOTES: usize = 128;
+
 #[derive(Debug, PartialEq)]
 pub(super) enum Action<H> {
 	// repropagate under given topic, to the given peers, applying cost/benefit to originator.
@@ -231,6 +234,11 @@ where
 	next_rebroadcast: Mutex<Instant>,
 	known_peers: Arc<Mutex<KnownPeers<B>>>,
 	network: Arc<N>,
+	/// Votes of the current validator set for rounds beyond the filter, buffered for equivocation
+	/// checks.
+	future_votes: Mutex<
+		Vec<VoteMessage<NumberFor<B>, AuthorityId, <AuthorityId as RuntimeAppPublic>::Signature>>,
+	>,
 }
 
 impl<B, N, AuthorityId> GossipValidator<B, N, AuthorityId>
@@ -246,6 +254,7 @@ where
 			next_rebroadcast: Mutex::new(Instant::now() + REBROADCAST_AFTER),
 			known_peers,
 			network,
+			future_votes: Mutex::new(Vec::new()),
 		}
 	}
 
@@ -260,6 +269,29 @@ where
 		);
 		self.gossip_filter.write().update(filter);
 	}
+
+	/// Take the buffered votes for rounds beyond the filter.
+	pub(crate) fn take_future_votes(
+		&self,
+	) -> Vec<VoteMessage<NumberFor<B>, AuthorityId, <AuthorityId as RuntimeAppPublic>::Signature>>
+	{
+		std::mem::take(&mut *self.future_votes.lock())
+	}
+
+	/// Buffer a vote for a round beyond the filter.
+	///
+	/// Honest validators only vote on finalized blocks, so such votes are either for blocks we
+	/// haven't imported yet, or future block voting equivocations. Their signatures are not
+	/// verified here, but only by the fisherman for the votes it reports.
+	fn note_future_vote(
+		&self,
+		vote: VoteMessage<NumberFor<B>, AuthorityId, <AuthorityId as RuntimeAppPublic>::Signature>,
+	) {
+		let mut future_votes = self.future_votes.lock();
+		if future_votes.len() < MAX_FUTURE_VOTES {
+			future_votes.push(vote);
+		}
+	}
 }
 
 impl<B, N, AuthorityId> GossipValidator<B, N, AuthorityId>
@@ -288,7 +320,14 @@ where
 
 			match filter.consider_vote(round, set_id) {
 				Consider::RejectPast => return Action::Discard(cost::OUTDATED_MESSAGE),
-				Consider::RejectFuture => return Action::Discard(cost::FUTURE_MESSAGE),
+				Consider::RejectFuture => {
+					if filter.validator_set().is_some_and(|set| {
+						set.id() == set_id && set.validators().contains(&vote.id)
+					}) {
+						self.note_future_vote(vote);
+					}
+					return Action::Discard(cost::FUTURE_MESSAGE)
+				},
 				// When we can't evaluate, it's our fault (e.g. filter not initialized yet), we
 				// discard the vote without punishing or rewarding the sending peer.
 				Consider::CannotEvaluate => return Action::DiscardNoReport,
@@ -724,6 +763,15 @@ pub(crate) mod tests {
 		assert!(matches!(res, ValidationResult::Discard));
 		expected_report.cost_benefit = cost::FUTURE_MESSAGE;
 		assert_eq!(report_stream.try_next().unwrap().unwrap(), expected_report);
+		// but keep the vote for equivocation checks
+		assert_eq!(gv.take_future_votes(), vec![vote.clone()]);
+		assert!(gv.take_future_votes().is_empty());
+
+		// future votes of unknown voters are not kept
+		let res = gv.validate(&mut context, &sender, &bad_vote);
+		assert!(matches!(res, ValidationResult::Discard));
+		assert_eq!(report_stream.try_next().unwrap().unwrap(), expected_report);
+		assert!(gv.take_future_votes().is_empty());
 
 		// reject if the round is not live anymore
 		gv.update_filter(GossipFilterCfg { start: 7, end: 10, validator_set: &validator_set });
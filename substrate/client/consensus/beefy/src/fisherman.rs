@@ -16,21 +16,49 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{error::Error, keystore::BeefyKeystore, round::Rounds, LOG_TARGET};
+use crate::{
+	error::Error, justification::BeefyVersionedFinalityProof, keystore::BeefyKeystore,
+	round::Rounds, LOG_TARGET,
+};
+use codec::Encode;
 use log::{debug, error, warn};
+use parking_lot::Mutex;
 use sc_client_api::Backend;
 use sp_api::ProvideRuntimeApi;
 use sp_application_crypto::RuntimeAppPublic;
 use sp_blockchain::HeaderBackend;
 use sp_consensus_beefy::{
 	check_double_voting_proof, AuthorityIdBound, BeefyApi, BeefySignatureHasher, DoubleVotingProof,
-	OpaqueKeyOwnershipProof, ValidatorSetId,
+	ForkVotingProof, FutureBlockVotingProof, OpaqueKeyOwnershipProof, Payload, PayloadProvider,
+	ValidatorSet, ValidatorSetId, VersionedFinalityProof, VoteMessage,
 };
 use sp_runtime::{
 	generic::BlockId,
-	traits::{Block, NumberFor},
+	traits::{Block, NumberFor, Saturating},
+};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	marker::PhantomData,
+	sync::Arc,
 };
-use std::{marker::PhantomData, sync::Arc};
+
+/// Number of blocks beyond our best block a vote may be for without being reported as a future
+/// block voting equivocation, since our best block may lag behind the one of the voter.
+pub(crate) const FUTURE_BLOCK_VOTING_MARGIN: u32 = 16;
+
+/// Maximum number of rounds for which reported offenders are remembered.
+const MAX_REPORTED_ROUNDS: usize = 128;
+
+/// Maximum number of finalized blocks for which the canonical payload is remembered.
+const MAX_CACHED_PAYLOADS: usize = 128;
+
+/// Kinds of equivocations found by checking single votes against our view of the chain.
+enum EquivocationKind {
+	/// A vote for a finalized block with a non-canonical payload.
+	ForkVoting,
+	/// A vote for a block number beyond our best block.
+	FutureBlockVoting,
+}
 
 /// Helper struct containing the key ownership proof for a validator.
 pub struct ProvedValidator {
@@ -38,16 +66,27 @@ pub struct ProvedValidator {
 }
 
 /// Helper used to check and report equivocations.
-pub struct Fisherman<B, BE, RuntimeApi, AuthorityId: AuthorityIdBound> {
+pub struct Fisherman<B, BE, P, RuntimeApi, AuthorityId: AuthorityIdBound> {
 	backend: Arc<BE>,
 	runtime: Arc<RuntimeApi>,
 	key_store: Arc<BeefyKeystore<AuthorityId>>,
+	payload_provider: P,
+	/// Offenders of fork and future block voting equivocations already reported, per round.
+	reported_votes: Mutex<BTreeMap<NumberFor<B>, BTreeSet<AuthorityId>>>,
+	/// Canonical payloads of finalized blocks already computed, so that they are computed once
+	/// per round rather than once per vote.
+	canonical_payloads: Mutex<BTreeMap<NumberFor<B>, Option<Payload>>>,
 
 	_phantom: PhantomData<B>,
 }
 
-impl<B: Block, BE: Backend<B>, RuntimeApi: ProvideRuntimeApi<B>, AuthorityId>
-	Fisherman<B, BE, RuntimeApi, AuthorityId>
+impl<
+		B: Block,
+		BE: Backend<B>,
+		P: PayloadProvider<B>,
+		RuntimeApi: ProvideRuntimeApi<B>,
+		AuthorityId,
+	> Fisherman<B, BE, P, RuntimeApi, AuthorityId>
 where
 	RuntimeApi::Api: BeefyApi<B, AuthorityId>,
 	AuthorityId: AuthorityIdBound,
@@ -56,8 +95,34 @@ where
 		backend: Arc<BE>,
 		runtime: Arc<RuntimeApi>,
 		keystore: Arc<BeefyKeystore<AuthorityId>>,
+		payload_provider: P,
 	) -> Self {
-		Self { backend, runtime, key_store: keystore, _phantom: Default::default() }
+		Self {
+			backend,
+			runtime,
+			key_store: keystore,
+			payload_provider,
+			reported_votes: Mutex::new(BTreeMap::new()),
+			canonical_payloads: Mutex::new(BTreeMap::new()),
+			_phantom: Default::default(),
+		}
+	}
+
+	fn active_validator_set_at(&self, hash: B::Hash) -> Result<ValidatorSet<AuthorityId>, Error> {
+		self.runtime
+			.runtime_api()
+			.validator_set(hash)
+			.map_err(Error::RuntimeApi)?
+			.ok_or_else(|| Error::Backend(format!("BEEFY is not enabled at block {:?}", hash)))
+	}
+
+	/// Returns `true` if the equivocation of `offender_id` is our own and shouldn't be reported.
+	fn is_own_equivocation(&self, offender_id: &AuthorityId, validators: &[AuthorityId]) -> bool {
+		if self.key_store.authority_id(validators).as_ref() == Some(offender_id) {
+			warn!(target: LOG_TARGET, "🥩 Skipping report for own equivocation");
+			return true
+		}
+		false
 	}
 
 	fn prove_offenders<'a>(
@@ -136,11 +201,8 @@ where
 			return Ok(());
 		}
 
-		if let Some(local_id) = self.key_store.authority_id(validators) {
-			if offender_id == &local_id {
-				warn!(target: LOG_TARGET, "🥩 Skipping report for own equivocation");
-				return Ok(());
-			}
+		if self.is_own_equivocation(offender_id, validators) {
+			return Ok(());
 		}
 
 		let key_owner_proofs = self.prove_offenders(
@@ -164,4 +226,251 @@ where
 
 		Ok(())
 	}
+
+	/// Report the given fork voting equivocation, i.e. a vote for a block that isn't part of the
+	/// canonical chain, to the BEEFY runtime module.
+	///
+	/// The ancestry proof and the key ownership proof are generated at our **best** block.
+	fn report_fork_voting(
+		&self,
+		vote: VoteMessage<NumberFor<B>, AuthorityId, <AuthorityId as RuntimeAppPublic>::Signature>,
+	) -> Result<(), Error> {
+		let best_hash = self.backend.blockchain().info().best_hash;
+		let validator_set = self.active_validator_set_at(best_hash)?;
+		if validator_set.id() != vote.commitment.validator_set_id {
+			debug!(target: LOG_TARGET, "🥩 Skipping report for fork voting of old set {:?}", vote);
+			return Ok(())
+		}
+		if self.is_own_equivocation(&vote.id, validator_set.validators()) {
+			return Ok(())
+		}
+
+		let runtime_api = self.runtime.runtime_api();
+		let Some(ancestry_proof) = runtime_api
+			.generate_ancestry_proof(best_hash, vote.commitment.block_number, None)
+			.map_err(Error::RuntimeApi)?
+		else {
+			debug!(target: LOG_TARGET, "🥩 Failed to generate ancestry proof for {:?}", vote);
+			return Ok(())
+		};
+		let header = self.backend.blockchain().expect_header(best_hash)?;
+		let key_owner_proofs = self.prove_offenders(
+			BlockId::Hash(best_hash),
+			vec![&vote.id].into_iter(),
+			validator_set.id(),
+		)?;
+
+		let proof = ForkVotingProof { vote, ancestry_proof, header };
+		for ProvedValidator { key_owner_proof, .. } in key_owner_proofs {
+			runtime_api
+				.submit_report_fork_voting_unsigned_extrinsic(
+					best_hash,
+					proof.clone(),
+					key_owner_proof,
+				)
+				.map_err(Error::RuntimeApi)?;
+		}
+
+		Ok(())
+	}
+
+	/// Report the given future block voting equivocation, i.e. a vote for a block number beyond
+	/// our best block, to the BEEFY runtime module.
+	fn report_future_block_voting(
+		&self,
+		vote: VoteMessage<NumberFor<B>, AuthorityId, <AuthorityId as RuntimeAppPublic>::Signature>,
+	) -> Result<(), Error> {
+		let best_hash = self.backend.blockchain().info().best_hash;
+		let validator_set = self.active_validator_set_at(best_hash)?;
+		if validator_set.id() != vote.commitment.validator_set_id {
+			debug!(
+				target: LOG_TARGET,
+				"🥩 Skipping report for future block voting of unknown set {:?}", vote
+			);
+			return Ok(())
+		}
+		if self.is_own_equivocation(&vote.id, validator_set.validators()) {
+			return Ok(())
+		}
+
+		let key_owner_proofs = self.prove_offenders(
+			BlockId::Hash(best_hash),
+			vec![&vote.id].into_iter(),
+			validator_set.id(),
+		)?;
+
+		let proof = FutureBlockVotingProof { vote };
+		for ProvedValidator { key_owner_proof, .. } in key_owner_proofs {
+			self.runtime
+				.runtime_api()
+				.submit_report_future_block_voting_unsigned_extrinsic(
+					best_hash,
+					proof.clone(),
+					key_owner_proof,
+				)
+				.map_err(Error::RuntimeApi)?;
+		}
+
+		Ok(())
+	}
+
+	/// Find the kind of equivocation `vote` is, according to our view of the chain.
+	///
+	/// A vote is a future block voting equivocation if it is for a block number more than
+	/// [`FUTURE_BLOCK_VOTING_MARGIN`] blocks beyond our best block, and a fork voting equivocation
+	/// if it is for a finalized block, but its payload doesn't match the payload of the canonical
+	/// block. Votes for non-finalized blocks are not checked, since their canonical payload is not
+	/// known yet.
+	fn equivocation_kind(
+		&self,
+		vote: &VoteMessage<NumberFor<B>, AuthorityId, <AuthorityId as RuntimeAppPublic>::Signature>,
+	) -> Result<Option<EquivocationKind>, Error> {
+		let info = self.backend.blockchain().info();
+		let number = vote.commitment.block_number;
+		if number > info.best_number.saturating_add(FUTURE_BLOCK_VOTING_MARGIN.into()) {
+			return Ok(Some(EquivocationKind::FutureBlockVoting))
+		}
+		if number > info.finalized_number {
+			return Ok(None)
+		}
+
+		match self.canonical_payload(number)? {
+			Some(payload) if payload != vote.commitment.payload =>
+				Ok(Some(EquivocationKind::ForkVoting)),
+			_ => Ok(None),
+		}
+	}
+
+	/// Returns the payload of the finalized block `number`.
+	///
+	/// The payload of a finalized block never changes, so it is only computed for the first vote
+	/// of a round and then served from the cache.
+	fn canonical_payload(&self, number: NumberFor<B>) -> Result<Option<Payload>, Error> {
+		if let Some(payload) = self.canonical_payloads.lock().get(&number) {
+			return Ok(payload.clone())
+		}
+
+		let hash = self.backend.blockchain().expect_block_hash_from_id(&BlockId::Number(number))?;
+		let header = self.backend.blockchain().expect_header(hash)?;
+		let payload = self.payload_provider.payload(&header);
+
+		let mut canonical_payloads = self.canonical_payloads.lock();
+		canonical_payloads.insert(number, payload.clone());
+		while canonical_payloads.len() > MAX_CACHED_PAYLOADS {
+			canonical_payloads.pop_first();
+		}
+		Ok(payload)
+	}
+
+	/// Returns `true` if an equivocation of the author of `vote` was already reported for its
+	/// round.
+	fn is_reported(
+		&self,
+		vote: &VoteMessage<NumberFor<B>, AuthorityId, <AuthorityId as RuntimeAppPublic>::Signature>,
+	) -> bool {
+		self.reported_votes
+			.lock()
+			.get(&vote.commitment.block_number)
+			.is_some_and(|offenders| offenders.contains(&vote.id))
+	}
+
+	/// Report `vote` as an equivocation of the given `kind`, unless an equivocation of its author
+	/// was already reported for its round.
+	fn report_vote(
+		&self,
+		vote: VoteMessage<NumberFor<B>, AuthorityId, <AuthorityId as RuntimeAppPublic>::Signature>,
+		kind: EquivocationKind,
+	) -> Result<(), Error> {
+		{
+			let mut reported_votes = self.reported_votes.lock();
+			if !reported_votes
+				.entry(vote.commitment.block_number)
+				.or_default()
+				.insert(vote.id.clone())
+			{
+				return Ok(())
+			}
+			while reported_votes.len() > MAX_REPORTED_ROUNDS {
+				reported_votes.pop_first();
+			}
+		}
+
+		match kind {
+			EquivocationKind::ForkVoting => self.report_fork_voting(vote),
+			EquivocationKind::FutureBlockVoting => self.report_future_block_voting(vote),
+		}
+	}
+
+	/// Check `vote` against our view of the chain and report it if it is an equivocation.
+	///
+	/// The signature of `vote` is expected to be already verified.
+	pub fn check_vote(
+		&self,
+		vote: VoteMessage<NumberFor<B>, AuthorityId, <AuthorityId as RuntimeAppPublic>::Signature>,
+	) -> Result<(), Error> {
+		match self.equivocation_kind(&vote)? {
+			Some(kind) => self.report_vote(vote, kind),
+			None => Ok(()),
+		}
+	}
+
+	/// Check `vote`, whose signature wasn't verified yet, like [`Self::check_vote`].
+	///
+	/// The signature is only verified if the vote is an equivocation that wasn't reported yet.
+	pub fn check_unverified_vote(
+		&self,
+		vote: VoteMessage<NumberFor<B>, AuthorityId, <AuthorityId as RuntimeAppPublic>::Signature>,
+	) -> Result<(), Error> {
+		let Some(kind) = self.equivocation_kind(&vote)? else { return Ok(()) };
+		if self.is_reported(&vote) {
+			return Ok(())
+		}
+		if !BeefyKeystore::verify(&vote.id, &vote.signature, &vote.commitment.encode()) {
+			debug!(target: LOG_TARGET, "🥩 Skipping check of vote with bad signature {:?}", vote);
+			return Ok(())
+		}
+		self.report_vote(vote, kind)
+	}
+
+	/// Check all signatures of the finality `proof` with [`Self::check_vote`].
+	///
+	/// The signatures of `proof` are expected to be already verified.
+	pub fn check_proof(
+		&self,
+		proof: BeefyVersionedFinalityProof<B, AuthorityId>,
+	) -> Result<(), Error> {
+		let VersionedFinalityProof::V1(signed_commitment) = proof;
+		let number = signed_commitment.commitment.block_number;
+		let info = self.backend.blockchain().info();
+		// Signatures are matched against the validator set of the justified block, or the best
+		// block for justifications of future blocks.
+		let hash = if number > info.best_number {
+			info.best_hash
+		} else {
+			self.backend.blockchain().expect_block_hash_from_id(&BlockId::Number(number))?
+		};
+		let validator_set = self.active_validator_set_at(hash)?;
+		if validator_set.id() != signed_commitment.commitment.validator_set_id ||
+			validator_set.len() != signed_commitment.signatures.len()
+		{
+			debug!(
+				target: LOG_TARGET,
+				"🥩 Skipping check of proof for unknown validator set {:?}", signed_commitment
+			);
+			return Ok(())
+		}
+
+		for (id, signature) in validator_set.validators().iter().zip(signed_commitment.signatures) {
+			if let Some(signature) = signature {
+				let vote = VoteMessage {
+					commitment: signed_commitment.commitment.clone(),
+					id: id.clone(),
+					signature,
+				};
+				self.check_vote(vote)?;
+			}
+		}
+
+		Ok(())
+	}
 }
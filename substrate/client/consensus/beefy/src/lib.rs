@@ -307,7 +307,7 @@ where
 	}
 
 	/// Takes rest of missing pieces as params and builds the `BeefyWorker`.
-	pub fn build<P, S, N>(
+	pub fn build<P: Clone, S, N>(
 		self,
 		payload_provider: P,
 		sync: Arc<S>,
@@ -321,9 +321,14 @@ where
 			backend: self.backend.clone(),
 			runtime: self.runtime.clone(),
 			key_store: key_store.clone(),
-			payload_provider,
+			payload_provider: payload_provider.clone(),
 			sync,
-			fisherman: Arc::new(Fisherman::new(self.backend, self.runtime, key_store)),
+			fisherman: Arc::new(Fisherman::new(
+				self.backend,
+				self.runtime,
+				key_store,
+				payload_provider,
+			)),
 			metrics: self.metrics,
 			persisted_state: self.persisted_state,
 			comms,
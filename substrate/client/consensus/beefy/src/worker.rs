@@ -387,7 +387,7 @@ pub(crate) struct BeefyWorker<B: Block, BE, P, RuntimeApi, S, N, AuthorityId: Au
 	pub key_store: Arc<BeefyKeystore<AuthorityId>>,
 	pub payload_provider: P,
 	pub sync: Arc<S>,
-	pub fisherman: Arc<Fisherman<B, BE, RuntimeApi, AuthorityId>>,
+	pub fisherman: Arc<Fisherman<B, BE, P, RuntimeApi, AuthorityId>>,
 
 	// communication (created once, but returned and reused if worker is restarted/reinitialized)
 	pub comms: BeefyComms<B, N, AuthorityId>,
@@ -522,6 +522,10 @@ where
 	where
 		<AuthorityId as RuntimeAppPublic>::Signature: Encode + Decode,
 	{
+		if let Err(err) = self.fisherman.check_vote(vote.clone()) {
+			debug!(target: LOG_TARGET, "🥩 Failed to check vote for equivocation: {}", err);
+		}
+
 		let block_num = vote.commitment.block_number;
 		match self.voting_oracle().triage_round(block_num)? {
 			RoundAction::Process =>
@@ -548,6 +552,10 @@ where
 		&mut self,
 		justification: BeefyVersionedFinalityProof<B, AuthorityId>,
 	) -> Result<(), Error> {
+		if let Err(err) = self.fisherman.check_proof(justification.clone()) {
+			debug!(target: LOG_TARGET, "🥩 Failed to check justification for equivocations: {}", err);
+		}
+
 		let signed_commitment = match justification {
 			VersionedFinalityProof::V1(ref sc) => sc,
 		};
@@ -960,6 +968,8 @@ where
 				},
 			}
 
+			// Check the votes for future rounds seen by the gossip validator.
+			self.check_future_votes();
 			// Act on changed 'state'.
 			self.process_new_state();
 		};
@@ -968,6 +978,21 @@ where
 		(error, self.comms)
 	}
 
+	/// Check votes for future rounds, that were rejected by the gossip validator, for
+	/// equivocations.
+	fn check_future_votes(&self) {
+		let votes = self.comms.gossip_validator.take_future_votes();
+		// While major syncing, our best block is expected to lag behind the votes.
+		if votes.is_empty() || self.sync.is_major_syncing() {
+			return
+		}
+		for vote in votes {
+			if let Err(err) = self.fisherman.check_unverified_vote(vote) {
+				debug!(target: LOG_TARGET, "🥩 Failed to check vote for equivocation: {}", err);
+			}
+		}
+	}
+
 	/// Report the given equivocation to the BEEFY runtime module.
 	fn report_double_voting(
 		&self,
@@ -1027,6 +1052,7 @@ pub(crate) mod tests {
 			notification::{BeefyBestBlockStream, BeefyVersionedFinalityProofStream},
 			request_response::outgoing_requests_engine::OnDemandJustificationsEngine,
 		},
+		fisherman::FUTURE_BLOCK_VOTING_MARGIN,
 		tests::{
 			create_beefy_keystore, get_beefy_streams, make_beefy_ids, BeefyPeer, BeefyTestNet,
 			TestApi,
@@ -1153,9 +1179,9 @@ pub(crate) mod tests {
 			runtime: api.clone(),
 			key_store: key_store.clone(),
 			metrics,
-			payload_provider,
+			payload_provider: payload_provider.clone(),
 			sync: Arc::new(sync),
-			fisherman: Arc::new(Fisherman::new(backend, api, key_store)),
+			fisherman: Arc::new(Fisherman::new(backend, api, key_store, payload_provider)),
 			links,
 			comms,
 			pending_justifications: BTreeMap::new(),
@@ -1582,6 +1608,7 @@ pub(crate) mod tests {
 			worker.backend.clone(),
 			worker.runtime.clone(),
 			worker.key_store.clone(),
+			MmrRootProvider::new(api_alice.clone()),
 		));
 
 		// let there be a block with num = 1:
@@ -1632,4 +1659,103 @@ pub(crate) mod tests {
 		// verify nothing reported to runtime
 		assert!(api_alice.reported_equivocations.as_ref().unwrap().lock().is_empty());
 	}
+
+	#[tokio::test]
+	async fn should_report_fork_and_future_block_voting() {
+		let set_id = 0;
+		let keys = [Keyring::Alice, Keyring::Bob];
+		let validator_set = ValidatorSet::new(make_beefy_ids(&keys), set_id).unwrap();
+		let mut api_alice = TestApi::with_validator_set(&validator_set);
+		api_alice.allow_equivocations();
+		let api_alice = Arc::new(api_alice);
+
+		let mut net = BeefyTestNet::new(1);
+		let mut worker = create_beefy_worker(net.peer(0), &keys[0], 1, validator_set.clone());
+		worker.runtime = api_alice.clone();
+		worker.payload_provider = MmrRootProvider::new(api_alice.clone());
+		worker.fisherman = Arc::new(Fisherman::new(
+			worker.backend.clone(),
+			worker.runtime.clone(),
+			worker.key_store.clone(),
+			worker.payload_provider.clone(),
+		));
+
+		// let there be blocks 1 to 5, with block 3 finalized
+		let hashes = net.peer(0).push_blocks(5, false);
+		net.peer(0).client().as_client().finalize_block(hashes[2], None).unwrap();
+
+		let vote =
+			|block_number: u64, payload: &Payload, keyring: &Keyring<ecdsa_crypto::AuthorityId>| {
+				let commitment =
+					Commitment { payload: payload.clone(), block_number, validator_set_id: set_id };
+				let signature = keyring.sign(&commitment.encode());
+				VoteMessage { commitment, id: keyring.public(), signature }
+			};
+		let header = worker.backend.blockchain().expect_header(hashes[1]).unwrap();
+		let good_payload = worker.payload_provider.payload(&header).unwrap();
+		let bad_payload = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+		let fork_votes = || api_alice.reported_fork_voting_equivocations.as_ref().unwrap().lock();
+		let future_votes =
+			|| api_alice.reported_future_block_voting_equivocations.as_ref().unwrap().lock();
+
+		// votes on the canonical payload of finalized blocks are fine
+		assert_eq!(worker.fisherman.check_vote(vote(2, &good_payload, &Keyring::Bob)), Ok(()));
+		// votes on non-finalized blocks are not checked
+		assert_eq!(worker.fisherman.check_vote(vote(4, &bad_payload, &Keyring::Bob)), Ok(()));
+		// votes on blocks within the margin beyond our best block are not reported
+		let within_margin = 5 + FUTURE_BLOCK_VOTING_MARGIN as u64;
+		assert_eq!(
+			worker.fisherman.check_vote(vote(within_margin, &good_payload, &Keyring::Bob)),
+			Ok(())
+		);
+		// our own equivocations are not reported
+		let future_block = within_margin + 1;
+		assert_eq!(worker.fisherman.check_vote(vote(2, &bad_payload, &Keyring::Alice)), Ok(()));
+		assert_eq!(
+			worker.fisherman.check_vote(vote(future_block, &good_payload, &Keyring::Alice)),
+			Ok(())
+		);
+		assert!(fork_votes().is_empty());
+		assert!(future_votes().is_empty());
+
+		// Bob votes on a different payload for finalized block 2
+		let bad_vote = vote(2, &bad_payload, &Keyring::Bob);
+		assert_eq!(worker.fisherman.check_vote(bad_vote.clone()), Ok(()));
+		{
+			let reported = fork_votes();
+			assert_eq!(reported.len(), 1);
+			assert_eq!(reported[0].vote, bad_vote);
+			assert_eq!(reported[0].header.hash(), hashes[4]);
+		}
+		// but is only reported once for the round
+		assert_eq!(worker.fisherman.check_vote(bad_vote), Ok(()));
+		assert_eq!(fork_votes().len(), 1);
+
+		// unverified votes with bad signatures are not reported
+		let mut forged_vote = vote(future_block, &good_payload, &Keyring::Bob);
+		forged_vote.signature = Keyring::Bob.sign(b"forged");
+		assert_eq!(worker.fisherman.check_unverified_vote(forged_vote), Ok(()));
+		assert!(future_votes().is_empty());
+
+		// Bob votes on a block beyond our best block and the margin
+		let future_vote = vote(future_block, &good_payload, &Keyring::Bob);
+		assert_eq!(worker.fisherman.check_unverified_vote(future_vote.clone()), Ok(()));
+		assert_eq!(future_votes().len(), 1);
+		assert_eq!(future_votes()[0].vote, future_vote);
+		assert_eq!(worker.fisherman.check_vote(future_vote), Ok(()));
+		assert_eq!(future_votes().len(), 1);
+		future_votes().clear();
+
+		// votes are extracted from justifications as well, skipping our own signature
+		let commitment = Commitment {
+			payload: good_payload.clone(),
+			block_number: future_block + 1,
+			validator_set_id: set_id,
+		};
+		let signatures = keys.iter().map(|key| Some(key.sign(&commitment.encode()))).collect();
+		let proof = VersionedFinalityProof::V1(SignedCommitment { commitment, signatures });
+		assert_eq!(worker.fisherman.check_proof(proof), Ok(()));
+		assert_eq!(future_votes().len(), 1);
+		assert_eq!(future_votes()[0].vote, vote(future_block + 1, &good_payload, &Keyring::Bob));
+	}
 }
@@ -336,27 +336,34 @@ mod tests {
 	use sc_block_builder::BlockBuilderBuilder;
 	use sp_blockchain::HeaderBackend;
 	use sp_consensus::BlockOrigin;
-	use sp_consensus_grandpa::GRANDPA_ENGINE_ID;
+	use sp_consensus_grandpa::{AuthorityList, GRANDPA_ENGINE_ID};
 	use sp_keyring::Ed25519Keyring;
 	use std::sync::Arc;
 	use substrate_test_runtime_client::{
-		BlockBuilderExt, ClientBlockImportExt, ClientExt, DefaultTestClientBuilderExt,
-		TestClientBuilder, TestClientBuilderExt,
+		Backend, BlockBuilderExt, ClientBlockImportExt, ClientExt, DefaultTestClientBuilderExt,
+		TestClient, TestClientBuilder, TestClientBuilderExt,
 	};
 
-	#[test]
-	fn warp_sync_proof_generate_verify() {
+	fn authority_list(keyrings: &[Ed25519Keyring]) -> AuthorityList {
+		keyrings.iter().map(|keyring| (keyring.public().into(), 1)).collect()
+	}
+
+	/// Build a chain of 100 blocks with an authority set change finalized every 10 blocks.
+	///
+	/// Returns the authorities of every set indexed by set id.
+	fn build_chain_with_set_changes(
+	) -> (Arc<TestClient>, Arc<Backend>, AuthoritySetChanges<u64>, Vec<AuthorityList>) {
 		let mut rng = rand::rngs::StdRng::from_seed([0; 32]);
 		let builder = TestClientBuilder::new();
 		let backend = builder.backend();
 		let client = Arc::new(builder.build());
 
 		let available_authorities = Ed25519Keyring::iter().collect::<Vec<_>>();
-		let genesis_authorities = vec![(Ed25519Keyring::Alice.public().into(), 1)];
 
 		let mut current_authorities = vec![Ed25519Keyring::Alice];
 		let mut current_set_id = 0;
 		let mut authority_set_changes = Vec::new();
+		let mut authorities = vec![authority_list(&current_authorities)];
 
 		for n in 1..=100 {
 			let mut builder = BlockBuilderBuilder::new(&*client)
@@ -434,10 +441,16 @@ mod tests {
 
 				current_set_id += 1;
 				current_authorities = new_authorities;
+				authorities.push(authority_list(&current_authorities));
 			}
 		}
 
-		let authority_set_changes = AuthoritySetChanges::from(authority_set_changes);
+		(client, backend, AuthoritySetChanges::from(authority_set_changes), authorities)
+	}
+
+	#[test]
+	fn warp_sync_proof_generate_verify() {
+		let (client, backend, authority_set_changes, authorities) = build_chain_with_set_changes();
 
 		// generate a warp sync proof
 		let genesis_hash = client.hash(0).unwrap().unwrap();
@@ -447,14 +460,45 @@ mod tests {
 
 		// verifying the proof should yield the last set id and authorities
 		let (new_set_id, new_authorities) =
-			warp_sync_proof.verify(0, genesis_authorities, &Default::default()).unwrap();
+			warp_sync_proof.verify(0, authorities[0].clone(), &Default::default()).unwrap();
+
+		assert_eq!(new_set_id, 10);
+		assert_eq!(new_authorities, authorities[10]);
+	}
+
+	#[test]
+	fn warp_sync_proof_from_checkpoint_generate_verify() {
+		let (client, backend, authority_set_changes, authorities) = build_chain_with_set_changes();
+
+		// Blocks #31 to #40 are finalized by set #3, so only the changes from block #40 onwards
+		// need to be proven.
+		let checkpoint_hash = client.hash(35).unwrap().unwrap();
+		let warp_sync_proof =
+			WarpSyncProof::generate(&*backend, checkpoint_hash, &authority_set_changes).unwrap();
+		assert_eq!(warp_sync_proof.proofs.len(), 7);
+
+		let (new_set_id, new_authorities) =
+			warp_sync_proof.verify(3, authorities[3].clone(), &Default::default()).unwrap();
+		assert_eq!(new_set_id, 10);
+		assert_eq!(new_authorities, authorities[10]);
 
-		let expected_authorities = current_authorities
-			.iter()
-			.map(|keyring| (keyring.public().into(), 1))
-			.collect::<Vec<_>>();
+		// Proofs starting at the checkpoint don't verify against an earlier set.
+		assert!(warp_sync_proof.verify(2, authorities[2].clone(), &Default::default()).is_err());
+	}
 
-		assert_eq!(new_set_id, current_set_id);
-		assert_eq!(new_authorities, expected_authorities);
+	#[test]
+	fn warp_sync_proof_from_set_change_checkpoint_generate_verify() {
+		let (client, backend, authority_set_changes, authorities) = build_chain_with_set_changes();
+
+		// Block #40 is the last block of set #3, the following blocks are finalized by set #4.
+		let checkpoint_hash = client.hash(40).unwrap().unwrap();
+		let warp_sync_proof =
+			WarpSyncProof::generate(&*backend, checkpoint_hash, &authority_set_changes).unwrap();
+		assert_eq!(warp_sync_proof.proofs.len(), 6);
+
+		let (new_set_id, new_authorities) =
+			warp_sync_proof.verify(4, authorities[4].clone(), &Default::default()).unwrap();
+		assert_eq!(new_set_id, 10);
+		assert_eq!(new_authorities, authorities[10]);
 	}
 }
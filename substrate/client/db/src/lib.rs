@@ -61,7 +61,7 @@ use codec::{Decode, Encode};
 use hash_db::Prefix;
 use sc_client_api::{
 	backend::NewBlockState,
-	blockchain::{BlockGap, BlockGapType},
+	blockchain::{BlockGap, BlockGapType, PrunedBlocks},
 	leaves::{FinalizationOutcome, LeafSet},
 	utils::is_descendent_of,
 	IoInfo, MemoryInfo, MemorySize, UsageInfo,
@@ -312,6 +312,8 @@ pub struct DatabaseSettings {
 	///
 	/// NOTE: only finalized blocks are subject for removal!
 	pub blocks_pruning: BlocksPruning,
+	/// Data of pruned blocks that is retained.
+	pub blocks_retention: BlocksRetention,
 }
 
 /// Block pruning settings.
@@ -335,6 +337,23 @@ impl BlocksPruning {
 	}
 }
 
+/// Data of finalized blocks that is kept when the blocks are pruned.
+///
+/// Only applies to [`BlocksPruning::Some`]. Blocks of displaced forks are always removed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BlocksRetention {
+	/// Keep the justifications of pruned blocks.
+	pub justifications: bool,
+	/// Keep the bodies of pruned blocks selected by the runtime.
+	///
+	/// The runtime selects the bodies through `sp_block_builder::BlockRetentionApi` when the
+	/// blocks are imported, see
+	/// [`BlockImportOperation::set_body_retained`](sc_client_api::backend::BlockImportOperation).
+	/// Blocks imported without being executed, e.g. by warp or gap sync, can't be checked and
+	/// their bodies are pruned.
+	pub bodies: bool,
+}
+
 /// Where to find the database..
 #[derive(Debug, Clone)]
 pub enum DatabaseSource {
@@ -529,6 +548,11 @@ impl<Block: BlockT> BlockchainDb<Block> {
 		meta.block_gap = gap;
 	}
 
+	fn update_pruned_blocks(&self, pruned_blocks: Option<PrunedBlocks<NumberFor<Block>>>) {
+		let mut meta = self.meta.write();
+		meta.pruned_blocks = pruned_blocks;
+	}
+
 	/// Empty the cache of pinned items.
 	fn clear_pinning_cache(&self) {
 		self.pinned_blocks_cache.write().clear();
@@ -690,6 +714,7 @@ impl<Block: BlockT> sc_client_api::blockchain::HeaderBackend<Block> for Blockcha
 			finalized_state: meta.finalized_state,
 			number_leaves: self.leaves.read().count(),
 			block_gap: meta.block_gap,
+			pruned_blocks: meta.pruned_blocks,
 		}
 	}
 
@@ -835,6 +860,7 @@ pub struct BlockImportOperation<Block: BlockT> {
 	set_head: Option<Block::Hash>,
 	commit_state: bool,
 	create_gap: bool,
+	body_retained: bool,
 	index_ops: Vec<IndexOperation>,
 }
 
@@ -993,6 +1019,10 @@ impl<Block: BlockT> sc_client_api::backend::BlockImportOperation<Block>
 	fn set_create_gap(&mut self, create_gap: bool) {
 		self.create_gap = create_gap;
 	}
+
+	fn set_body_retained(&mut self, retained: bool) {
+		self.body_retained = retained;
+	}
 }
 
 struct StorageDb<Block: BlockT> {
@@ -1111,6 +1141,7 @@ pub struct Backend<Block: BlockT> {
 	import_lock: Arc<RwLock<()>>,
 	is_archive: bool,
 	blocks_pruning: BlocksPruning,
+	blocks_retention: BlocksRetention,
 	io_stats: FrozenForDuration<(kvdb::IoStats, StateUsageInfo)>,
 	state_usage: Arc<StateUsageStats>,
 	genesis_state: RwLock<Option<Arc<DbGenesisStorage<Block>>>>,
@@ -1171,6 +1202,7 @@ impl<Block: BlockT> Backend<Block> {
 			state_pruning: Some(state_pruning),
 			source: DatabaseSource::Custom { db, require_create_flag: true },
 			blocks_pruning,
+			blocks_retention: Default::default(),
 		};
 
 		Self::new(db_setting, canonicalization_delay).expect("failed to create test-db")
@@ -1234,6 +1266,7 @@ impl<Block: BlockT> Backend<Block> {
 			io_stats: FrozenForDuration::new(std::time::Duration::from_secs(1)),
 			state_usage: Arc::new(StateUsageStats::new()),
 			blocks_pruning: config.blocks_pruning,
+			blocks_retention: config.blocks_retention,
 			genesis_state: RwLock::new(None),
 			shared_trie_cache: config.trie_cache_maximum_size.map(|maximum_size| {
 				SharedTrieCache::new(sp_trie::cache::CacheSize::new(maximum_size))
@@ -1364,6 +1397,7 @@ impl<Block: BlockT> Backend<Block> {
 		last_finalized: Option<Block::Hash>,
 		justification: Option<Justification>,
 		current_transaction_justifications: &mut HashMap<Block::Hash, Justification>,
+		pruned_blocks: &mut Option<PrunedBlocks<NumberFor<Block>>>,
 		remove_displaced: bool,
 	) -> ClientResult<MetaUpdate<Block>> {
 		// TODO: ensure best chain contains this block.
@@ -1377,6 +1411,7 @@ impl<Block: BlockT> Backend<Block> {
 			hash,
 			with_state,
 			current_transaction_justifications,
+			pruned_blocks,
 			remove_displaced,
 		)?;
 
@@ -1448,9 +1483,21 @@ impl<Block: BlockT> Backend<Block> {
 		operation.apply_offchain(&mut transaction);
 
 		let mut meta_updates = Vec::with_capacity(operation.finalized_blocks.len());
-		let (best_num, mut last_finalized_hash, mut last_finalized_num, mut block_gap) = {
+		let (
+			best_num,
+			mut last_finalized_hash,
+			mut last_finalized_num,
+			mut block_gap,
+			mut pruned_blocks,
+		) = {
 			let meta = self.blockchain.meta.read();
-			(meta.best_number, meta.finalized_hash, meta.finalized_number, meta.block_gap)
+			(
+				meta.best_number,
+				meta.finalized_hash,
+				meta.finalized_number,
+				meta.block_gap,
+				meta.pruned_blocks,
+			)
 		};
 
 		let mut block_gap_updated = false;
@@ -1467,6 +1514,7 @@ impl<Block: BlockT> Backend<Block> {
 				Some(last_finalized_hash),
 				justification,
 				&mut current_transaction_justifications,
+				&mut pruned_blocks,
 				finalized_blocks.peek().is_none(),
 			)?);
 			last_finalized_hash = block_hash;
@@ -1508,6 +1556,9 @@ impl<Block: BlockT> Backend<Block> {
 						apply_index_ops::<Block>(&mut transaction, body, operation.index_ops);
 					transaction.set_from_vec(columns::BODY_INDEX, &lookup_key, body);
 				}
+				if operation.body_retained {
+					transaction.set(columns::META, &utils::retained_body_key(hash), &[]);
+				}
 			}
 			if let Some(body) = pending_block.indexed_body {
 				apply_indexed_body::<Block>(&mut transaction, body);
@@ -1643,6 +1694,7 @@ impl<Block: BlockT> Backend<Block> {
 					hash,
 					operation.commit_state,
 					&mut current_transaction_justifications,
+					&mut pruned_blocks,
 					true,
 				)?;
 			} else {
@@ -1828,6 +1880,7 @@ impl<Block: BlockT> Backend<Block> {
 		if block_gap_updated {
 			self.blockchain.update_block_gap(block_gap);
 		}
+		self.blockchain.update_pruned_blocks(pruned_blocks);
 
 		Ok(())
 	}
@@ -1843,6 +1896,7 @@ impl<Block: BlockT> Backend<Block> {
 		f_hash: Block::Hash,
 		with_state: bool,
 		current_transaction_justifications: &mut HashMap<Block::Hash, Justification>,
+		pruned_blocks: &mut Option<PrunedBlocks<NumberFor<Block>>>,
 		remove_displaced: bool,
 	) -> ClientResult<()> {
 		let f_num = *f_header.number();
@@ -1880,7 +1934,7 @@ impl<Block: BlockT> Backend<Block> {
 			}
 		}
 
-		self.prune_blocks(transaction, f_num, current_transaction_justifications)?;
+		self.prune_blocks(transaction, f_num, current_transaction_justifications, pruned_blocks)?;
 
 		Ok(())
 	}
@@ -1890,14 +1944,17 @@ impl<Block: BlockT> Backend<Block> {
 		transaction: &mut Transaction<DbHash>,
 		finalized_number: NumberFor<Block>,
 		current_transaction_justifications: &mut HashMap<Block::Hash, Justification>,
+		pruned_blocks: &mut Option<PrunedBlocks<NumberFor<Block>>>,
 	) -> ClientResult<()> {
 		if let BlocksPruning::Some(blocks_pruning) = self.blocks_pruning {
 			// Always keep the last finalized block
 			let keep = std::cmp::max(blocks_pruning, 1);
 			if finalized_number >= keep.into() {
 				let number = finalized_number.saturating_sub(keep.into());
+				let id = BlockId::<Block>::number(number);
 
 				// Before we prune a block, check if it is pinned
+				let mut retain_body = false;
 				if let Some(hash) = self.blockchain.hash(number)? {
 					self.blockchain.insert_persisted_body_if_pinned(hash)?;
 
@@ -1908,14 +1965,92 @@ impl<Block: BlockT> Backend<Block> {
 					} else {
 						self.blockchain.insert_persisted_justifications_if_pinned(hash)?;
 					}
+
+					let retained_body_key = utils::retained_body_key(hash);
+					retain_body = self.blocks_retention.bodies &&
+						self.storage.db.contains(columns::META, &retained_body_key);
+					transaction.remove(columns::META, &retained_body_key);
 				};
 
-				self.prune_block(transaction, BlockId::<Block>::number(number))?;
+				if !retain_body {
+					self.remove_block_body(transaction, id)?;
+				}
+				if !self.blocks_retention.justifications {
+					self.remove_block_justifications(transaction, id)?;
+				}
+				self.note_pruned_block(transaction, number, pruned_blocks)?;
 			}
 		}
 		Ok(())
 	}
 
+	/// Extend the range of pruned blocks with `number`.
+	fn note_pruned_block(
+		&self,
+		transaction: &mut Transaction<DbHash>,
+		number: NumberFor<Block>,
+		pruned_blocks: &mut Option<PrunedBlocks<NumberFor<Block>>>,
+	) -> ClientResult<()> {
+		let updated = match *pruned_blocks {
+			Some(pruned) => PrunedBlocks {
+				start: pruned.start,
+				end: number,
+				justifications_retained: pruned.justifications_retained &&
+					self.blocks_retention.justifications,
+				bodies_retained: pruned.bodies_retained && self.blocks_retention.bodies,
+			},
+			None => {
+				let start = self.first_unrecorded_pruned_block(number)?;
+				// Blocks pruned before the range was recorded kept neither.
+				let recorded = start == number;
+				PrunedBlocks {
+					start,
+					end: number,
+					justifications_retained: recorded && self.blocks_retention.justifications,
+					bodies_retained: recorded && self.blocks_retention.bodies,
+				}
+			},
+		};
+		transaction.set(columns::META, meta_keys::PRUNED_BLOCKS, &updated.encode());
+		*pruned_blocks = Some(updated);
+		Ok(())
+	}
+
+	/// Find the first of the blocks right before `number` which were pruned without the range of
+	/// pruned blocks being recorded, e.g. by an older version of the node. Returns `number` if
+	/// there are none.
+	///
+	/// Finalized blocks are pruned in order, so the pruned blocks are contiguous.
+	fn first_unrecorded_pruned_block(
+		&self,
+		number: NumberFor<Block>,
+	) -> ClientResult<NumberFor<Block>> {
+		let is_pruned = |number: NumberFor<Block>| -> ClientResult<bool> {
+			// Blocks missing altogether, e.g. below a block gap, were never pruned.
+			if self.blockchain.hash(number)?.is_none() {
+				return Ok(false)
+			}
+			let id = BlockId::<Block>::number(number);
+			Ok(read_db(&*self.storage.db, columns::KEY_LOOKUP, columns::BODY, id)?.is_none() &&
+				read_db(&*self.storage.db, columns::KEY_LOOKUP, columns::BODY_INDEX, id)?
+					.is_none())
+		};
+
+		if number.is_zero() || !is_pruned(number - One::one())? {
+			return Ok(number)
+		}
+		let (mut low, mut high) = (Zero::zero(), number - One::one());
+		while low < high {
+			let middle = low + (high - low) / 2u32.into();
+			if is_pruned(middle)? {
+				high = middle;
+			} else {
+				low = middle + One::one();
+			}
+		}
+		Ok(high)
+	}
+
 	fn prune_displaced_branches(
 		&self,
 		transaction: &mut Transaction<DbHash>,
@@ -1925,6 +2060,7 @@ impl<Block: BlockT> Backend<Block> {
 		for &hash in displaced.displaced_blocks.iter() {
 			self.blockchain.insert_persisted_body_if_pinned(hash)?;
 			self.prune_block(transaction, BlockId::<Block>::hash(hash))?;
+			transaction.remove(columns::META, &utils::retained_body_key(hash));
 		}
 		Ok(())
 	}
@@ -1934,19 +2070,36 @@ impl<Block: BlockT> Backend<Block> {
 		transaction: &mut Transaction<DbHash>,
 		id: BlockId<Block>,
 	) -> ClientResult<()> {
-		debug!(target: "db", "Removing block #{id}");
+		self.remove_block_body(transaction, id)?;
+		self.remove_block_justifications(transaction, id)
+	}
+
+	fn remove_block_justifications(
+		&self,
+		transaction: &mut Transaction<DbHash>,
+		id: BlockId<Block>,
+	) -> ClientResult<()> {
+		debug!(target: "db", "Removing justifications of block #{id}");
 		utils::remove_from_db(
 			transaction,
 			&*self.storage.db,
 			columns::KEY_LOOKUP,
-			columns::BODY,
+			columns::JUSTIFICATIONS,
 			id,
-		)?;
+		)
+	}
+
+	fn remove_block_body(
+		&self,
+		transaction: &mut Transaction<DbHash>,
+		id: BlockId<Block>,
+	) -> ClientResult<()> {
+		debug!(target: "db", "Removing body of block #{id}");
 		utils::remove_from_db(
 			transaction,
 			&*self.storage.db,
 			columns::KEY_LOOKUP,
-			columns::JUSTIFICATIONS,
+			columns::BODY,
 			id,
 		)?;
 		if let Some(index) =
@@ -2119,6 +2272,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 			set_head: None,
 			commit_state: false,
 			create_gap: true,
+			body_retained: false,
 			index_ops: Default::default(),
 		})
 	}
@@ -2165,6 +2319,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 		let header = self.blockchain.expect_header(hash)?;
 
 		let mut current_transaction_justifications = HashMap::new();
+		let mut pruned_blocks = self.blockchain.meta.read().pruned_blocks;
 		let m = self.finalize_block_with_transaction(
 			&mut transaction,
 			hash,
@@ -2172,11 +2327,13 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 			None,
 			justification,
 			&mut current_transaction_justifications,
+			&mut pruned_blocks,
 			true,
 		)?;
 
 		self.storage.db.commit(transaction)?;
 		self.blockchain.update_meta(m);
+		self.blockchain.update_pruned_blocks(pruned_blocks);
 		Ok(())
 	}
 
@@ -2637,6 +2794,26 @@ pub(crate) mod tests {
 		extrinsics_root: H256,
 		body: Vec<UncheckedXt>,
 		transaction_index: Option<Vec<IndexOperation>>,
+	) -> Result<H256, sp_blockchain::Error> {
+		insert_block_with_body_retained(
+			backend,
+			number,
+			parent_hash,
+			extrinsics_root,
+			body,
+			transaction_index,
+			false,
+		)
+	}
+
+	fn insert_block_with_body_retained(
+		backend: &Backend<Block>,
+		number: u64,
+		parent_hash: H256,
+		extrinsics_root: H256,
+		body: Vec<UncheckedXt>,
+		transaction_index: Option<Vec<IndexOperation>>,
+		body_retained: bool,
 	) -> Result<H256, sp_blockchain::Error> {
 		use sp_runtime::testing::Digest;
 
@@ -2661,6 +2838,7 @@ pub(crate) mod tests {
 
 		op.set_block_data(header.clone(), Some(body), None, None, NewBlockState::Best)
 			.unwrap();
+		op.set_body_retained(body_retained);
 
 		backend.commit_operation(op)?;
 
@@ -2772,6 +2950,7 @@ pub(crate) mod tests {
 				state_pruning: Some(PruningMode::blocks_pruning(1)),
 				source: DatabaseSource::Custom { db: backing, require_create_flag: false },
 				blocks_pruning: BlocksPruning::KeepFinalized,
+				blocks_retention: Default::default(),
 			},
 			0,
 		)
@@ -3762,6 +3941,193 @@ pub(crate) mod tests {
 		}
 	}
 
+	fn new_test_with_retention(
+		blocks_pruning: u32,
+		blocks_retention: BlocksRetention,
+		db: Arc<dyn Database<DbHash>>,
+		require_create_flag: bool,
+	) -> Backend<Block> {
+		Backend::<Block>::new(
+			DatabaseSettings {
+				trie_cache_maximum_size: Some(16 * 1024 * 1024),
+				state_pruning: Some(PruningMode::blocks_pruning(blocks_pruning)),
+				source: DatabaseSource::Custom { db, require_create_flag },
+				blocks_pruning: BlocksPruning::Some(blocks_pruning),
+				blocks_retention,
+			},
+			0,
+		)
+		.unwrap()
+	}
+
+	fn insert_and_finalize_blocks(
+		backend: &Backend<Block>,
+		count: u64,
+		retained_bodies: &[u64],
+	) -> Vec<H256> {
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0..count {
+			let hash = insert_block_with_body_retained(
+				backend,
+				i,
+				prev_hash,
+				Default::default(),
+				vec![UncheckedXt::new_transaction(i.into(), ())],
+				None,
+				retained_bodies.contains(&i),
+			)
+			.unwrap();
+			blocks.push(hash);
+			prev_hash = hash;
+		}
+
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, prev_hash).unwrap();
+		for (i, hash) in blocks.iter().enumerate().skip(1) {
+			op.mark_finalized(*hash, Some((CONS0_ENGINE_ID, vec![i as u8]))).unwrap();
+		}
+		backend.commit_operation(op).unwrap();
+
+		blocks
+	}
+
+	#[test]
+	fn prune_blocks_retains_justifications() {
+		let db = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let retention = BlocksRetention { justifications: true, bodies: false };
+		let backend = new_test_with_retention(2, retention, db.clone(), true);
+		let blocks = insert_and_finalize_blocks(&backend, 5, &[1]);
+
+		let bc = backend.blockchain();
+		for i in 1..3 {
+			assert_eq!(None, bc.body(blocks[i]).unwrap());
+			assert_eq!(
+				Some(Justifications::from((CONS0_ENGINE_ID, vec![i as u8]))),
+				bc.justifications(blocks[i]).unwrap()
+			);
+		}
+		assert_eq!(
+			Some(vec![UncheckedXt::new_transaction(3.into(), ())]),
+			bc.body(blocks[3]).unwrap()
+		);
+
+		let pruned_blocks = PrunedBlocks {
+			start: 0,
+			end: 2,
+			justifications_retained: true,
+			bodies_retained: false,
+		};
+		assert_eq!(Some(pruned_blocks), bc.info().pruned_blocks);
+
+		// The pruned range survives a restart.
+		drop(backend);
+		let backend = new_test_with_retention(2, retention, db, false);
+		assert_eq!(Some(pruned_blocks), backend.blockchain().info().pruned_blocks);
+	}
+
+	#[test]
+	fn prune_blocks_retains_selected_bodies() {
+		let db = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let retention = BlocksRetention { justifications: false, bodies: true };
+		let backend = new_test_with_retention(2, retention, db.clone(), true);
+		let blocks = insert_and_finalize_blocks(&backend, 5, &[1]);
+
+		let bc = backend.blockchain();
+		assert_eq!(None, bc.body(blocks[0]).unwrap());
+		assert_eq!(
+			Some(vec![UncheckedXt::new_transaction(1.into(), ())]),
+			bc.body(blocks[1]).unwrap()
+		);
+		assert_eq!(None, bc.justifications(blocks[1]).unwrap());
+		assert_eq!(None, bc.body(blocks[2]).unwrap());
+		assert_eq!(None, bc.justifications(blocks[2]).unwrap());
+
+		assert_eq!(
+			Some(PrunedBlocks {
+				start: 0,
+				end: 2,
+				justifications_retained: false,
+				bodies_retained: true,
+			}),
+			bc.info().pruned_blocks
+		);
+		// The marker is removed along with the other data of the pruned block.
+		assert!(!db.contains(columns::META, &utils::retained_body_key(blocks[1])));
+	}
+
+	#[test]
+	fn pruned_blocks_range_includes_blocks_pruned_before_it_was_recorded() {
+		let db = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let retention = BlocksRetention { justifications: true, bodies: true };
+		let backend = new_test_with_retention(2, retention, db.clone(), true);
+		let blocks = insert_and_finalize_blocks(&backend, 5, &[]);
+		drop(backend);
+
+		// As if blocks #0 to #2 had been pruned by a node not recording the range.
+		let mut transaction = Transaction::new();
+		transaction.remove(columns::META, meta_keys::PRUNED_BLOCKS);
+		db.commit(transaction).unwrap();
+
+		let backend = new_test_with_retention(2, retention, db, false);
+		assert_eq!(None, backend.blockchain().info().pruned_blocks);
+		let hash = insert_block(
+			&backend,
+			5,
+			blocks[4],
+			None,
+			Default::default(),
+			vec![UncheckedXt::new_transaction(5.into(), ())],
+			None,
+		)
+		.unwrap();
+		backend.finalize_block(hash, None).unwrap();
+
+		assert_eq!(
+			Some(PrunedBlocks {
+				start: 0,
+				end: 3,
+				justifications_retained: false,
+				bodies_retained: false,
+			}),
+			backend.blockchain().info().pruned_blocks
+		);
+	}
+
+	#[test]
+	fn pruned_blocks_range_is_extended_on_finalization() {
+		let backend = Backend::<Block>::new_test_with_tx_storage(BlocksPruning::Some(2), 0);
+		let blocks = insert_and_finalize_blocks(&backend, 3, &[]);
+		assert_eq!(
+			Some((0, 0)),
+			backend.blockchain().info().pruned_blocks.map(|p| (p.start, p.end))
+		);
+
+		let mut prev_hash = blocks[2];
+		for i in 3..6 {
+			prev_hash = insert_block(
+				&backend,
+				i,
+				prev_hash,
+				None,
+				Default::default(),
+				vec![UncheckedXt::new_transaction(i.into(), ())],
+				None,
+			)
+			.unwrap();
+			backend.finalize_block(prev_hash, None).unwrap();
+		}
+		assert_eq!(
+			Some((0, 3)),
+			backend.blockchain().info().pruned_blocks.map(|p| (p.start, p.end))
+		);
+
+		// Nothing is pruned in archive mode.
+		let backend = Backend::<Block>::new_test_with_tx_storage(BlocksPruning::KeepFinalized, 0);
+		insert_and_finalize_blocks(&backend, 5, &[]);
+		assert_eq!(None, backend.blockchain().info().pruned_blocks);
+	}
+
 	#[test]
 	fn prune_blocks_on_finalize_with_fork() {
 		sp_tracing::try_init_simple();
@@ -25,7 +25,7 @@ use log::{debug, info};
 
 use crate::{Database, DatabaseSource, DbHash};
 use codec::Decode;
-use sc_client_api::blockchain::{BlockGap, BlockGapType};
+use sc_client_api::blockchain::{BlockGap, BlockGapType, PrunedBlocks};
 use sp_database::Transaction;
 use sp_runtime::{
 	generic::BlockId,
@@ -59,12 +59,16 @@ pub mod meta_keys {
 	pub const BLOCK_GAP: &[u8; 3] = b"gap";
 	/// Block gap version.
 	pub const BLOCK_GAP_VERSION: &[u8; 7] = b"gap_ver";
+	/// Range of pruned blocks.
+	pub const PRUNED_BLOCKS: &[u8; 6] = b"pruned";
 	/// Genesis block hash.
 	pub const GENESIS_HASH: &[u8; 3] = b"gen";
 	/// Leaves prefix list key.
 	pub const LEAF_PREFIX: &[u8; 4] = b"leaf";
 	/// Children prefix list key.
 	pub const CHILDREN_PREFIX: &[u8; 8] = b"children";
+	/// Prefix of the keys marking the blocks whose body is kept when they are pruned.
+	pub const RETAINED_BODY_PREFIX: &[u8; 8] = b"retained";
 }
 
 /// Database metadata.
@@ -84,6 +88,8 @@ pub struct Meta<N, H> {
 	pub finalized_state: Option<(H, N)>,
 	/// Block gap, if any.
 	pub block_gap: Option<BlockGap<N>>,
+	/// Range of pruned blocks, if any.
+	pub pruned_blocks: Option<PrunedBlocks<N>>,
 }
 
 /// A block lookup key: used for canonical lookup from block number to hash
@@ -121,6 +127,13 @@ where
 	Ok(lookup_key)
 }
 
+/// Key in COLUMN_META marking the block `hash` to keep its body when it is pruned.
+pub fn retained_body_key<H: AsRef<[u8]>>(hash: H) -> Vec<u8> {
+	let mut key = meta_keys::RETAINED_BODY_PREFIX.to_vec();
+	key.extend_from_slice(hash.as_ref());
+	key
+}
+
 /// Delete number to hash mapping in DB transaction.
 pub fn remove_number_to_key_mapping<N: TryInto<u32>>(
 	transaction: &mut Transaction<DbHash>,
@@ -493,6 +506,7 @@ where
 				genesis_hash: Default::default(),
 				finalized_state: None,
 				block_gap: None,
+				pruned_blocks: None,
 			}),
 	};
 
@@ -550,6 +564,9 @@ where
 		},
 	};
 	debug!(target: "db", "block_gap={:?}", block_gap);
+	let pruned_blocks = db
+		.get(COLUMN_META, meta_keys::PRUNED_BLOCKS)
+		.and_then(|d| Decode::decode(&mut d.as_slice()).ok());
 
 	Ok(Meta {
 		best_hash,
@@ -559,6 +576,7 @@ where
 		genesis_hash,
 		finalized_state,
 		block_gap,
+		pruned_blocks,
 	})
 }
 
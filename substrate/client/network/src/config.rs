@@ -629,6 +629,11 @@ pub struct NetworkConfiguration {
 	/// Initial syncing mode.
 	pub sync_mode: SyncMode,
 
+	/// Path to a JSON file with a trusted block to start warp sync from.
+	///
+	/// Takes precedence over the checkpoint provided by the chain spec.
+	pub warp_sync_checkpoint: Option<PathBuf>,
+
 	/// True if Kademlia random discovery should be enabled.
 	///
 	/// If true, the node will automatically randomly walk the DHT in order to find new peers.
@@ -677,6 +682,7 @@ impl NetworkConfiguration {
 			max_parallel_downloads: 5,
 			max_blocks_per_request: 64,
 			sync_mode: SyncMode::Full,
+			warp_sync_checkpoint: None,
 			enable_dht_random_walk: true,
 			allow_non_globals_in_dht: false,
 			kademlia_disjoint_query_paths: false,
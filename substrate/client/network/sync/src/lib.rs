@@ -20,7 +20,13 @@
 
 pub use schema::v1::*;
 pub use service::syncing_service::SyncingService;
-pub use strategy::warp::{WarpSyncConfig, WarpSyncPhase, WarpSyncProgress};
+pub use strategy::{
+	state_sync::StateSyncStore,
+	warp::{
+		WarpSyncCheckpoint, WarpSyncCheckpointExtension, WarpSyncConfig, WarpSyncPhase,
+		WarpSyncProgress,
+	},
+};
 pub use types::{SyncEvent, SyncEventStream, SyncState, SyncStatus, SyncStatusProvider};
 
 mod block_announce_validator;
@@ -36,7 +36,10 @@ use crate::{
 	service::network::NetworkServiceHandle,
 	strategy::{
 		disconnected_peers::DisconnectedPeers,
-		state_sync::{ImportResult, StateSync, StateSyncProvider},
+		state_sync::{
+			resumable_target, ImportResult, PersistedTarget, StateSync, StateSyncProvider,
+			StateSyncStore, STATE_SYNC_RESUME_WINDOW,
+		},
 		warp::{WarpSyncPhase, WarpSyncProgress},
 		StrategyKey, SyncingAction, SyncingStrategy,
 	},
@@ -277,8 +280,8 @@ pub(crate) enum PeerSyncState<B: BlockT> {
 	DownloadingStale(B::Hash),
 	/// Downloading justification for given block hash.
 	DownloadingJustification(B::Hash),
-	/// Downloading state.
-	DownloadingState,
+	/// Downloading the given key range of the state.
+	DownloadingState(usize),
 	/// Actively downloading block history after warp sync.
 	DownloadingGap(NumberFor<B>),
 }
@@ -332,6 +335,8 @@ pub struct ChainSync<B: BlockT, Client> {
 	downloaded_blocks: usize,
 	/// State sync in progress, if any.
 	state_sync: Option<StateSync<B, Client>>,
+	/// Storage used to resume an interrupted state sync, if any.
+	state_sync_store: Option<Arc<dyn StateSyncStore>>,
 	/// Enable importing existing blocks. This is used after the state download to
 	/// catch up to the latest state while re-importing blocks.
 	import_existing: bool,
@@ -716,7 +721,9 @@ where
 							"State sync is complete ({} MiB), restarting block sync.",
 							self.state_sync.as_ref().map_or(0, |s| s.progress().size / (1024 * 1024)),
 						);
-						self.state_sync = None;
+						if let Some(mut state_sync) = self.state_sync.take() {
+							state_sync.on_state_imported();
+						}
 						self.mode = ChainSyncMode::Full;
 						self.restart();
 					}
@@ -891,7 +898,7 @@ where
 			.collect::<Vec<_>>();
 		self.actions.extend(justification_requests);
 
-		let state_request = self.state_request().into_iter().map(|(peer_id, request)| {
+		let state_requests = self.state_requests().into_iter().map(|(peer_id, request)| {
 			trace!(
 				target: LOG_TARGET,
 				"Created `StrategyRequest` to {peer_id}.",
@@ -919,7 +926,7 @@ where
 				remove_obsolete: false,
 			}
 		});
-		self.actions.extend(state_request);
+		self.actions.extend(state_requests);
 
 		Ok(std::mem::take(&mut self.actions))
 	}
@@ -947,6 +954,7 @@ where
 		max_blocks_per_request: u32,
 		state_request_protocol_name: ProtocolName,
 		block_downloader: Arc<dyn BlockDownloader<B>>,
+		state_sync_store: Option<Arc<dyn StateSyncStore>>,
 		metrics_registry: Option<&Registry>,
 		initial_peers: impl Iterator<Item = (PeerId, B::Hash, NumberFor<B>)>,
 	) -> Result<Self, ClientError> {
@@ -968,6 +976,7 @@ where
 			state_request_protocol_name,
 			downloaded_blocks: 0,
 			state_sync: None,
+			state_sync_store,
 			import_existing: false,
 			block_downloader,
 			gap_sync: None,
@@ -1357,7 +1366,7 @@ where
 					},
 					PeerSyncState::Available |
 					PeerSyncState::DownloadingJustification(..) |
-					PeerSyncState::DownloadingState => Vec::new(),
+					PeerSyncState::DownloadingState(_) => Vec::new(),
 				}
 			} else {
 				// When request.is_none() this is a block announcement. Just accept blocks.
@@ -1648,7 +1657,7 @@ where
 				PeerSyncState::DownloadingNew(_) |
 				PeerSyncState::DownloadingStale(_) |
 				PeerSyncState::DownloadingGap(_) |
-				PeerSyncState::DownloadingState => {
+				PeerSyncState::DownloadingState(_) => {
 					// Cancel a request first, as `add_peer` may generate a new request.
 					self.actions
 						.push(SyncingAction::CancelRequest { peer_id, key: Self::STRATEGY_KEY });
@@ -1930,36 +1939,47 @@ where
 		requests
 	}
 
-	/// Get a state request scheduled by sync to be sent out (if any).
-	fn state_request(&mut self) -> Option<(PeerId, StateRequest)> {
+	/// Get state requests scheduled by sync to be sent out, at most one per key range.
+	fn state_requests(&mut self) -> Vec<(PeerId, StateRequest)> {
 		if self.allowed_requests.is_empty() {
-			return None;
+			return Vec::new();
 		}
-		if self.state_sync.is_some() &&
-			self.peers.iter().any(|(_, peer)| peer.state == PeerSyncState::DownloadingState)
-		{
-			// Only one pending state request is allowed.
-			return None;
+		let Some(sync) = &self.state_sync else { return Vec::new() };
+		if sync.is_complete() {
+			return Vec::new();
 		}
-		if let Some(sync) = &self.state_sync {
-			if sync.is_complete() {
-				return None;
-			}
 
-			for (id, peer) in self.peers.iter_mut() {
-				if peer.state.is_available() &&
-					peer.common_number >= sync.target_number() &&
-					self.disconnected_peers.is_peer_available(&id)
-				{
-					peer.state = PeerSyncState::DownloadingState;
-					let request = sync.next_request();
-					trace!(target: LOG_TARGET, "New StateRequest for {}: {:?}", id, request);
-					self.allowed_requests.clear();
-					return Some((*id, request));
-				}
+		// Only one pending state request per range is allowed.
+		let ranges = sync
+			.pending_ranges()
+			.into_iter()
+			.filter(|range| {
+				!self
+					.peers
+					.values()
+					.any(|peer| peer.state == PeerSyncState::DownloadingState(*range))
+			})
+			.collect::<Vec<_>>();
+		let mut ranges = ranges.into_iter().peekable();
+
+		let mut requests = Vec::new();
+		for (id, peer) in self.peers.iter_mut() {
+			let Some(&range) = ranges.peek() else { break };
+			if peer.state.is_available() &&
+				peer.common_number >= sync.target_number() &&
+				self.disconnected_peers.is_peer_available(&id)
+			{
+				peer.state = PeerSyncState::DownloadingState(range);
+				let request = sync.next_request(range);
+				trace!(target: LOG_TARGET, "New StateRequest for {}: {:?}", id, request);
+				requests.push((*id, request));
+				ranges.next();
 			}
 		}
-		None
+		if !requests.is_empty() {
+			self.allowed_requests.clear();
+		}
+		requests
 	}
 
 	#[must_use]
@@ -1976,21 +1996,24 @@ where
 			},
 		};
 
+		let mut range = None;
 		if let Some(peer) = self.peers.get_mut(peer_id) {
-			if let PeerSyncState::DownloadingState = peer.state {
+			if let PeerSyncState::DownloadingState(requested) = peer.state {
 				peer.state = PeerSyncState::Available;
 				self.allowed_requests.set_all();
+				range = Some(requested);
 			}
 		}
-		let import_result = if let Some(sync) = &mut self.state_sync {
+		let import_result = if let (Some(sync), Some(range)) = (&mut self.state_sync, range) {
 			debug!(
 				target: LOG_TARGET,
-				"Importing state data from {} with {} keys, {} proof nodes.",
+				"Importing state data for range {} from {} with {} keys, {} proof nodes.",
+				range,
 				peer_id,
 				response.entries.len(),
 				response.proof.len(),
 			);
-			sync.import(response)
+			sync.import(range, response)
 		} else {
 			debug!(target: LOG_TARGET, "Ignored obsolete state response from {peer_id}");
 			return Err(BadPeer(*peer_id, rep::NOT_REQUESTED));
@@ -2033,13 +2056,37 @@ where
 		heads.sort();
 		let median = heads[heads.len() / 2];
 		if finalized_number + STATE_SYNC_FINALITY_THRESHOLD.saturated_into() >= median {
-			if let Ok(Some(header)) = self.client.header(finalized_hash) {
+			// Prefer finishing an interrupted state download of a recent enough finalized block
+			// over starting from scratch.
+			if let Some(target) = self.resumable_state_sync_target(finalized_number, skip_proofs) {
+				log::info!(
+					target: LOG_TARGET,
+					"Resuming interrupted state download of #{} ({}).",
+					target.header.number(),
+					target.header.hash(),
+				);
+				self.state_sync = Some(StateSync::new(
+					self.client.clone(),
+					self.state_sync_store.clone(),
+					target.header,
+					target.body,
+					target.justifications,
+					skip_proofs,
+				));
+				self.allowed_requests.set_all();
+			} else if let Ok(Some(header)) = self.client.header(finalized_hash) {
 				log::debug!(
 					target: LOG_TARGET,
 					"Starting state sync for #{finalized_number} ({finalized_hash})",
 				);
-				self.state_sync =
-					Some(StateSync::new(self.client.clone(), header, None, None, skip_proofs));
+				self.state_sync = Some(StateSync::new(
+					self.client.clone(),
+					self.state_sync_store.clone(),
+					header,
+					None,
+					None,
+					skip_proofs,
+				));
 				self.allowed_requests.set_all();
 			} else {
 				log::error!(
@@ -2052,6 +2099,21 @@ where
 		}
 	}
 
+	/// Returns the target of an interrupted state download, if it is finalized and at most
+	/// [`STATE_SYNC_RESUME_WINDOW`] blocks behind `finalized_number`.
+	fn resumable_state_sync_target(
+		&self,
+		finalized_number: NumberFor<B>,
+		skip_proofs: bool,
+	) -> Option<PersistedTarget<B>> {
+		let target = resumable_target::<B>(self.state_sync_store.as_deref()?, skip_proofs)?;
+		let number = *target.header.number();
+		let recent = number <= finalized_number &&
+			finalized_number - number <= STATE_SYNC_RESUME_WINDOW.saturated_into();
+		let finalized = self.client.hash(number).ok().flatten() == Some(target.header.hash());
+		(recent && finalized).then_some(target)
+	}
+
 	/// A version of `actions()` that doesn't schedule extra requests. For testing only.
 	#[cfg(test)]
 	#[must_use]
@@ -94,6 +94,7 @@ fn processes_empty_response_on_justification_request_for_unknown_block() {
 		ProtocolName::Static(""),
 		Arc::new(MockBlockDownloader::new()),
 		None,
+		None,
 		std::iter::empty(),
 	)
 	.unwrap();
@@ -159,6 +160,7 @@ fn restart_doesnt_affect_peers_downloading_finality_data() {
 		ProtocolName::Static(""),
 		Arc::new(MockBlockDownloader::new()),
 		None,
+		None,
 		std::iter::empty(),
 	)
 	.unwrap();
@@ -365,6 +367,7 @@ fn do_ancestor_search_when_common_block_to_best_queued_gap_is_to_big() {
 		ProtocolName::Static(""),
 		Arc::new(MockBlockDownloader::new()),
 		None,
+		None,
 		std::iter::empty(),
 	)
 	.unwrap();
@@ -523,6 +526,7 @@ fn can_sync_huge_fork() {
 		protocol_name,
 		proxy_block_downloader.clone(),
 		None,
+		None,
 		std::iter::empty(),
 	)
 	.unwrap();
@@ -671,6 +675,7 @@ fn syncs_fork_without_duplicate_requests() {
 		protocol_name,
 		proxy_block_downloader.clone(),
 		None,
+		None,
 		std::iter::empty(),
 	)
 	.unwrap();
@@ -819,6 +824,7 @@ fn removes_target_fork_on_disconnect() {
 		ProtocolName::Static(""),
 		Arc::new(MockBlockDownloader::new()),
 		None,
+		None,
 		std::iter::empty(),
 	)
 	.unwrap();
@@ -854,6 +860,7 @@ fn can_import_response_with_missing_blocks() {
 		ProtocolName::Static(""),
 		Arc::new(MockBlockDownloader::new()),
 		None,
+		None,
 		std::iter::empty(),
 	)
 	.unwrap();
@@ -895,6 +902,7 @@ fn sync_restart_removes_block_but_not_justification_requests() {
 		ProtocolName::Static(""),
 		Arc::new(MockBlockDownloader::new()),
 		None,
+		None,
 		std::iter::empty(),
 	)
 	.unwrap();
@@ -1047,6 +1055,7 @@ fn request_across_forks() {
 		ProtocolName::Static(""),
 		Arc::new(MockBlockDownloader::new()),
 		None,
+		None,
 		std::iter::empty(),
 	)
 	.unwrap();
@@ -1127,3 +1136,46 @@ fn request_across_forks() {
 		assert!(sync.is_known(&block.header.parent_hash()));
 	}
 }
+
+#[test]
+fn interrupted_state_sync_is_resumed() {
+	let client = Arc::new(TestClientBuilder::new().build());
+	let blocks = (0..4).map(|_| build_block(&client, None, false)).collect::<Vec<_>>();
+	let best = blocks.last().unwrap().clone();
+	client.finalize_block(best.hash(), None).unwrap();
+
+	// Persist the progress of a state download of an older finalized block.
+	let target = blocks[1].header().clone();
+	let mut state_sync =
+		StateSync::new(client.clone(), Some(client.clone()), target.clone(), None, None, false);
+	let request = state_sync.next_request(0);
+	let (proof, _) =
+		client.read_proof_collection(target.hash(), &request.start, 16 * 1024).unwrap();
+	let response = StateResponse { entries: Vec::new(), proof: codec::Encode::encode(&proof) };
+	assert!(matches!(state_sync.import(0, response), ImportResult::Continue));
+	drop(state_sync);
+
+	let mut sync = ChainSync::new(
+		ChainSyncMode::LightState { skip_proofs: false, storage_chain_mode: false },
+		client.clone(),
+		1,
+		64,
+		ProtocolName::Static(""),
+		Arc::new(MockBlockDownloader::new()),
+		Some(client.clone()),
+		None,
+		std::iter::empty(),
+	)
+	.unwrap();
+	sync.add_peer(PeerId::random(), best.hash(), *best.header().number());
+
+	// The state download continues with the persisted target instead of the new finalized block.
+	sync.attempt_state_sync(best.hash(), *best.header().number(), false);
+	assert_eq!(sync.state_sync.as_ref().map(|s| s.target_hash()), Some(target.hash()));
+	assert!(sync.state_sync.as_ref().unwrap().progress().size > 0);
+
+	// Without the store, the download starts from scratch.
+	sync.state_sync_store = None;
+	sync.attempt_state_sync(best.hash(), *best.header().number(), false);
+	assert_eq!(sync.state_sync.as_ref().map(|s| s.target_hash()), Some(best.hash()));
+}
@@ -26,6 +26,7 @@ use crate::{
 	strategy::{
 		chain_sync::{ChainSync, ChainSyncMode},
 		state::StateStrategy,
+		state_sync::{resumable_target, StateSyncStore, STATE_SYNC_RESUME_WINDOW},
 		warp::{WarpSync, WarpSyncConfig},
 		StrategyKey, SyncingAction, SyncingStrategy,
 	},
@@ -40,7 +41,10 @@ use sc_network::ProtocolName;
 use sc_network_common::sync::{message::BlockAnnounce, SyncMode};
 use sc_network_types::PeerId;
 use sp_blockchain::{Error as ClientError, HeaderBackend, HeaderMetadata};
-use sp_runtime::traits::{Block as BlockT, Header, NumberFor};
+use sp_runtime::{
+	traits::{Block as BlockT, Header, NumberFor},
+	SaturatedConversion,
+};
 use std::{any::Any, collections::HashMap, sync::Arc};
 
 /// Corresponding `ChainSync` mode.
@@ -71,6 +75,8 @@ where
 	pub state_request_protocol_name: ProtocolName,
 	/// Block downloader
 	pub block_downloader: Arc<dyn BlockDownloader<Block>>,
+	/// Storage used to resume an interrupted state download after a restart, if any.
+	pub state_sync_store: Option<Arc<dyn StateSyncStore>>,
 }
 
 /// Proxy to specific syncing strategies used in Polkadot.
@@ -377,6 +383,7 @@ where
 				config.max_blocks_per_request,
 				config.state_request_protocol_name.clone(),
 				config.block_downloader.clone(),
+				config.state_sync_store.clone(),
 				config.metrics_registry.as_ref(),
 				std::iter::empty(),
 			)?;
@@ -401,11 +408,36 @@ where
 						target: LOG_TARGET,
 						"Warp sync is complete, continuing with state sync."
 					);
+					// Prefer finishing an interrupted state download of a recent enough
+					// finalized block over starting from scratch.
+					let (target_header, target_body, target_justifications) = match self
+						.config
+						.state_sync_store
+						.as_deref()
+						.and_then(|store| resumable_target::<B>(store, false))
+					{
+						Some(target)
+							if target.justifications.is_some() &&
+								*target.header.number() <= *res.target_header.number() &&
+								*res.target_header.number() - *target.header.number() <=
+									STATE_SYNC_RESUME_WINDOW.saturated_into() =>
+						{
+							info!(
+								target: LOG_TARGET,
+								"Resuming interrupted state download of #{} ({}).",
+								target.header.number(),
+								target.header.hash(),
+							);
+							(target.header, target.body, target.justifications)
+						},
+						_ => (res.target_header, res.target_body, res.target_justifications),
+					};
 					let state_sync = StateStrategy::new(
 						self.client.clone(),
-						res.target_header,
-						res.target_body,
-						res.target_justifications,
+						self.config.state_sync_store.clone(),
+						target_header,
+						target_body,
+						target_justifications,
 						false,
 						self.peer_best_blocks
 							.iter()
@@ -429,6 +461,7 @@ where
 						self.config.max_blocks_per_request,
 						self.config.state_request_protocol_name.clone(),
 						self.config.block_downloader.clone(),
+						self.config.state_sync_store.clone(),
 						self.config.metrics_registry.as_ref(),
 						self.peer_best_blocks.iter().map(|(peer_id, (best_hash, best_number))| {
 							(*peer_id, *best_hash, *best_number)
@@ -459,6 +492,7 @@ where
 				self.config.max_blocks_per_request,
 				self.config.state_request_protocol_name.clone(),
 				self.config.block_downloader.clone(),
+				self.config.state_sync_store.clone(),
 				self.config.metrics_registry.as_ref(),
 				self.peer_best_blocks.iter().map(|(peer_id, (best_hash, best_number))| {
 					(*peer_id, *best_hash, *best_number)
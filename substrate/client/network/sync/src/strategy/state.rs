@@ -23,7 +23,7 @@ use crate::{
 	service::network::NetworkServiceHandle,
 	strategy::{
 		disconnected_peers::DisconnectedPeers,
-		state_sync::{ImportResult, StateSync, StateSyncProvider},
+		state_sync::{ImportResult, StateSync, StateSyncProvider, StateSyncStore},
 		StrategyKey, SyncingAction,
 	},
 	types::{BadPeer, SyncState, SyncStatus},
@@ -56,7 +56,8 @@ mod rep {
 
 enum PeerState {
 	Available,
-	DownloadingState,
+	/// Downloading the given key range of the state.
+	DownloadingState(usize),
 }
 
 impl PeerState {
@@ -87,6 +88,7 @@ impl<B: BlockT> StateStrategy<B> {
 	/// Create a new instance.
 	pub fn new<Client>(
 		client: Arc<Client>,
+		state_sync_store: Option<Arc<dyn StateSyncStore>>,
 		target_header: B::Header,
 		target_body: Option<Vec<B::Extrinsic>>,
 		target_justifications: Option<Justifications>,
@@ -105,6 +107,7 @@ impl<B: BlockT> StateStrategy<B> {
 		Self {
 			state_sync: Box::new(StateSync::new(
 				client,
+				state_sync_store,
 				target_header,
 				target_body,
 				target_justifications,
@@ -192,9 +195,17 @@ impl<B: BlockT> StateStrategy<B> {
 		peer_id: &PeerId,
 		response: &[u8],
 	) -> Result<(), BadPeer> {
-		if let Some(peer) = self.peers.get_mut(&peer_id) {
-			peer.state = PeerState::Available;
-		}
+		let range = match self.peers.get_mut(&peer_id) {
+			Some(peer) => match std::mem::replace(&mut peer.state, PeerState::Available) {
+				PeerState::DownloadingState(range) => Some(range),
+				PeerState::Available => None,
+			},
+			None => None,
+		};
+		let Some(range) = range else {
+			debug!(target: LOG_TARGET, "Ignored unexpected state response from {peer_id}");
+			return Ok(())
+		};
 
 		let response = match StateResponse::decode(response) {
 			Ok(response) => response,
@@ -210,13 +221,14 @@ impl<B: BlockT> StateStrategy<B> {
 
 		debug!(
 			target: LOG_TARGET,
-			"Importing state data from {} with {} keys, {} proof nodes.",
+			"Importing state data for range {} from {} with {} keys, {} proof nodes.",
+			range,
 			peer_id,
 			response.entries.len(),
 			response.proof.len(),
 		);
 
-		match self.state_sync.import(response) {
+		match self.state_sync.import(range, response) {
 			ImportResult::Import(hash, header, state, body, justifications) => {
 				let origin = BlockOrigin::NetworkInitialSync;
 				let block = IncomingBlock {
@@ -277,34 +289,45 @@ impl<B: BlockT> StateStrategy<B> {
 					"Failed to import target block with state: {e:?}."
 				);
 			});
-			self.succeeded |= results.into_iter().any(|result| result.is_ok());
+			if results.into_iter().any(|result| result.is_ok()) {
+				self.state_sync.on_state_imported();
+				self.succeeded = true;
+			}
 			self.actions.push(SyncingAction::Finished);
 		}
 	}
 
-	/// Produce state request.
-	fn state_request(&mut self) -> Option<(PeerId, StateRequest)> {
+	/// Produce state requests for the key ranges that are not being downloaded yet.
+	fn state_requests(&mut self) -> Vec<(PeerId, StateRequest)> {
 		if self.state_sync.is_complete() {
-			return None
+			return Vec::new()
 		}
 
-		if self
-			.peers
-			.values()
-			.any(|peer| matches!(peer.state, PeerState::DownloadingState))
-		{
-			// Only one state request at a time is possible.
-			return None
-		}
+		let mut requests = Vec::new();
+		for range in self.state_sync.pending_ranges() {
+			if self
+				.peers
+				.values()
+				.any(|peer| matches!(peer.state, PeerState::DownloadingState(r) if r == range))
+			{
+				// Only one request per range at a time is possible.
+				continue
+			}
 
-		let peer_id =
-			self.schedule_next_peer(PeerState::DownloadingState, self.state_sync.target_number())?;
-		let request = self.state_sync.next_request();
-		trace!(
-			target: LOG_TARGET,
-			"New state request to {peer_id}: {request:?}.",
-		);
-		Some((peer_id, request))
+			let Some(peer_id) = self.schedule_next_peer(
+				PeerState::DownloadingState(range),
+				self.state_sync.target_number(),
+			) else {
+				break
+			};
+			let request = self.state_sync.next_request(range);
+			trace!(
+				target: LOG_TARGET,
+				"New state request to {peer_id}: {request:?}.",
+			);
+			requests.push((peer_id, request));
+		}
+		requests
 	}
 
 	fn schedule_next_peer(
@@ -355,7 +378,7 @@ impl<B: BlockT> StateStrategy<B> {
 		&mut self,
 		network_service: &NetworkServiceHandle,
 	) -> impl Iterator<Item = SyncingAction<B>> {
-		let state_request = self.state_request().into_iter().map(|(peer_id, request)| {
+		let state_requests = self.state_requests().into_iter().map(|(peer_id, request)| {
 			let (tx, rx) = oneshot::channel();
 
 			network_service.start_request(
@@ -378,7 +401,7 @@ impl<B: BlockT> StateStrategy<B> {
 				remove_obsolete: false,
 			}
 		});
-		self.actions.extend(state_request);
+		self.actions.extend(state_requests);
 
 		std::mem::take(&mut self.actions).into_iter()
 	}
@@ -396,7 +419,9 @@ mod test {
 	use crate::{
 		schema::v1::{StateRequest, StateResponse},
 		service::network::NetworkServiceProvider,
-		strategy::state_sync::{ImportResult, StateSyncProgress, StateSyncProvider},
+		strategy::state_sync::{
+			ImportResult, StateSyncProgress, StateSyncProvider, STATE_SYNC_RANGES,
+		},
 	};
 	use codec::Decode;
 	use sc_block_builder::BlockBuilderBuilder;
@@ -404,6 +429,7 @@ mod test {
 	use sc_consensus::{ImportedAux, ImportedState};
 	use sp_core::H256;
 	use sp_runtime::traits::Zero;
+	use std::collections::HashSet;
 	use substrate_test_runtime_client::{
 		runtime::{Block, Hash},
 		BlockBuilderExt, DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
@@ -413,12 +439,14 @@ mod test {
 		pub StateSync<B: BlockT> {}
 
 		impl<B: BlockT> StateSyncProvider<B> for StateSync<B> {
-			fn import(&mut self, response: StateResponse) -> ImportResult<B>;
-			fn next_request(&self) -> StateRequest;
+			fn import(&mut self, range: usize, response: StateResponse) -> ImportResult<B>;
+			fn next_request(&self, range: usize) -> StateRequest;
+			fn pending_ranges(&self) -> Vec<usize>;
 			fn is_complete(&self) -> bool;
 			fn target_number(&self) -> NumberFor<B>;
 			fn target_hash(&self) -> B::Hash;
 			fn progress(&self) -> StateSyncProgress;
+			fn on_state_imported(&mut self);
 		}
 	}
 
@@ -437,6 +465,7 @@ mod test {
 
 		let mut state_strategy = StateStrategy::new(
 			client,
+			None,
 			target_header,
 			None,
 			None,
@@ -446,7 +475,7 @@ mod test {
 		);
 
 		assert!(state_strategy
-			.schedule_next_peer(PeerState::DownloadingState, Zero::zero())
+			.schedule_next_peer(PeerState::DownloadingState(0), Zero::zero())
 			.is_none());
 	}
 
@@ -470,6 +499,7 @@ mod test {
 
 			let mut state_strategy = StateStrategy::new(
 				client.clone(),
+				None,
 				target_block.header().clone(),
 				None,
 				None,
@@ -479,7 +509,7 @@ mod test {
 			);
 
 			let peer_id =
-				state_strategy.schedule_next_peer(PeerState::DownloadingState, Zero::zero());
+				state_strategy.schedule_next_peer(PeerState::DownloadingState(0), Zero::zero());
 			assert!(*peers.get(&peer_id.unwrap()).unwrap() >= 6);
 		}
 	}
@@ -504,6 +534,7 @@ mod test {
 
 			let mut state_strategy = StateStrategy::new(
 				client.clone(),
+				None,
 				target_block.header().clone(),
 				None,
 				None,
@@ -512,7 +543,7 @@ mod test {
 				ProtocolName::Static(""),
 			);
 
-			let peer_id = state_strategy.schedule_next_peer(PeerState::DownloadingState, 10);
+			let peer_id = state_strategy.schedule_next_peer(PeerState::DownloadingState(0), 10);
 			assert!(*peers.get(&peer_id.unwrap()).unwrap() == 10);
 		}
 	}
@@ -538,6 +569,7 @@ mod test {
 
 		let mut state_strategy = StateStrategy::new(
 			client.clone(),
+			None,
 			target_block.header().clone(),
 			None,
 			None,
@@ -553,7 +585,7 @@ mod test {
 		// Disconnect the peer with an inflight request.
 		state_strategy.add_peer(tenth_peer, H256::random(), 10);
 		let peer_id: Option<PeerId> =
-			state_strategy.schedule_next_peer(PeerState::DownloadingState, 10);
+			state_strategy.schedule_next_peer(PeerState::DownloadingState(0), 10);
 		assert_eq!(tenth_peer, peer_id.unwrap());
 		state_strategy.remove_peer(&tenth_peer);
 
@@ -563,12 +595,12 @@ mod test {
 		// No peer available for 10'th best block because of the backoff.
 		state_strategy.add_peer(tenth_peer, H256::random(), 10);
 		let peer_id: Option<PeerId> =
-			state_strategy.schedule_next_peer(PeerState::DownloadingState, 10);
+			state_strategy.schedule_next_peer(PeerState::DownloadingState(0), 10);
 		assert!(peer_id.is_none());
 
 		// Other requests can still happen.
 		let peer_id: Option<PeerId> =
-			state_strategy.schedule_next_peer(PeerState::DownloadingState, 9);
+			state_strategy.schedule_next_peer(PeerState::DownloadingState(0), 9);
 		assert_eq!(ninth_peer, peer_id.unwrap());
 	}
 
@@ -588,6 +620,7 @@ mod test {
 
 		let mut state_strategy = StateStrategy::new(
 			client.clone(),
+			None,
 			target_block.header().clone(),
 			None,
 			None,
@@ -596,14 +629,14 @@ mod test {
 			ProtocolName::Static(""),
 		);
 
-		let (_peer_id, request) = state_strategy.state_request().unwrap();
+		let (_peer_id, request) = state_strategy.state_requests().pop().unwrap();
 		let hash = Hash::decode(&mut &*request.block).unwrap();
 
 		assert_eq!(hash, target_block.header().hash());
 	}
 
 	#[test]
-	fn no_parallel_state_requests() {
+	fn parallel_state_requests_cover_distinct_ranges() {
 		let client = Arc::new(TestClientBuilder::new().set_no_genesis().build());
 		let target_block = BlockBuilderBuilder::new(&*client)
 			.on_parent_block(client.chain_info().best_hash)
@@ -614,10 +647,11 @@ mod test {
 			.unwrap()
 			.block;
 
-		let initial_peers = (1..=10).map(|best_number| (PeerId::random(), best_number));
+		let initial_peers = (0..10).map(|_| (PeerId::random(), 10));
 
 		let mut state_strategy = StateStrategy::new(
 			client.clone(),
+			None,
 			target_block.header().clone(),
 			None,
 			None,
@@ -626,17 +660,64 @@ mod test {
 			ProtocolName::Static(""),
 		);
 
-		// First request is sent.
-		assert!(state_strategy.state_request().is_some());
+		// One request per range is sent, each to a different peer.
+		let requests = state_strategy.state_requests();
+		assert_eq!(requests.len(), STATE_SYNC_RANGES);
+		let peers = requests.iter().map(|(peer_id, _)| *peer_id).collect::<HashSet<_>>();
+		assert_eq!(peers.len(), STATE_SYNC_RANGES);
+		let starts = requests
+			.iter()
+			.map(|(_, request)| request.start.clone())
+			.collect::<HashSet<_>>();
+		assert_eq!(starts.len(), STATE_SYNC_RANGES);
+
+		// No parallel request is sent for the same range.
+		assert!(state_strategy.state_requests().is_empty());
+	}
 
-		// No parallel request is sent.
-		assert!(state_strategy.state_request().is_none());
+	#[test]
+	fn response_for_unrequested_range_is_ignored() {
+		// Provider must not be called.
+		let state_sync_provider = MockStateSync::<Block>::new();
+		let peer_id = PeerId::random();
+		let initial_peers = std::iter::once((peer_id, 10));
+		let mut state_strategy = StateStrategy::new_with_provider(
+			Box::new(state_sync_provider),
+			initial_peers,
+			ProtocolName::Static(""),
+		);
+
+		let dummy_response = StateResponse::default().encode_to_vec();
+		assert!(state_strategy.on_state_response_inner(&peer_id, &dummy_response).is_ok());
+		assert_eq!(state_strategy.actions.len(), 0);
+	}
+
+	#[test]
+	fn response_is_imported_for_requested_range() {
+		let mut state_sync_provider = MockStateSync::<Block>::new();
+		state_sync_provider
+			.expect_import()
+			.withf(|range, _| *range == 3)
+			.return_once(|_, _| ImportResult::Continue);
+		let peer_id = PeerId::random();
+		let initial_peers = std::iter::once((peer_id, 10));
+		let mut state_strategy = StateStrategy::new_with_provider(
+			Box::new(state_sync_provider),
+			initial_peers,
+			ProtocolName::Static(""),
+		);
+		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState(3);
+
+		let dummy_response = StateResponse::default().encode_to_vec();
+		state_strategy.on_state_response(&peer_id, dummy_response);
+
+		assert!(state_strategy.peers.get(&peer_id).unwrap().state.is_available());
 	}
 
 	#[test]
 	fn received_state_response_makes_peer_available_again() {
 		let mut state_sync_provider = MockStateSync::<Block>::new();
-		state_sync_provider.expect_import().return_once(|_| ImportResult::Continue);
+		state_sync_provider.expect_import().return_once(|_, _| ImportResult::Continue);
 		let peer_id = PeerId::random();
 		let initial_peers = std::iter::once((peer_id, 10));
 		let mut state_strategy = StateStrategy::new_with_provider(
@@ -645,7 +726,7 @@ mod test {
 			ProtocolName::Static(""),
 		);
 		// Manually set the peer's state.
-		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState;
+		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState(0);
 
 		let dummy_response = StateResponse::default().encode_to_vec();
 		state_strategy.on_state_response(&peer_id, dummy_response);
@@ -657,7 +738,9 @@ mod test {
 	fn bad_state_response_drops_peer() {
 		let mut state_sync_provider = MockStateSync::<Block>::new();
 		// Provider says that state response is bad.
-		state_sync_provider.expect_import().return_once(|_| ImportResult::BadResponse);
+		state_sync_provider
+			.expect_import()
+			.return_once(|_, _| ImportResult::BadResponse);
 		let peer_id = PeerId::random();
 		let initial_peers = std::iter::once((peer_id, 10));
 		let mut state_strategy = StateStrategy::new_with_provider(
@@ -666,7 +749,7 @@ mod test {
 			ProtocolName::Static(""),
 		);
 		// Manually set the peer's state.
-		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState;
+		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState(0);
 		let dummy_response = StateResponse::default().encode_to_vec();
 		// Receiving response drops the peer.
 		assert!(matches!(
@@ -679,7 +762,7 @@ mod test {
 	fn partial_state_response_doesnt_generate_actions() {
 		let mut state_sync_provider = MockStateSync::<Block>::new();
 		// Sync provider says that the response is partial.
-		state_sync_provider.expect_import().return_once(|_| ImportResult::Continue);
+		state_sync_provider.expect_import().return_once(|_, _| ImportResult::Continue);
 		let peer_id = PeerId::random();
 		let initial_peers = std::iter::once((peer_id, 10));
 		let mut state_strategy = StateStrategy::new_with_provider(
@@ -688,7 +771,7 @@ mod test {
 			ProtocolName::Static(""),
 		);
 		// Manually set the peer's state .
-		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState;
+		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState(0);
 
 		let dummy_response = StateResponse::default().encode_to_vec();
 		state_strategy.on_state_response(&peer_id, dummy_response);
@@ -723,7 +806,7 @@ mod test {
 			body.clone(),
 			justifications.clone(),
 		);
-		state_sync_provider.expect_import().return_once(move |_| import);
+		state_sync_provider.expect_import().return_once(move |_, _| import);
 
 		// Reference values to check against.
 		let expected_origin = BlockOrigin::NetworkInitialSync;
@@ -750,7 +833,7 @@ mod test {
 			ProtocolName::Static(""),
 		);
 		// Manually set the peer's state .
-		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState;
+		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState(0);
 
 		// Receive response.
 		let dummy_response = StateResponse::default().encode_to_vec();
@@ -796,6 +879,7 @@ mod test {
 		let target_hash = Hash::random();
 		let mut state_sync_provider = MockStateSync::<Block>::new();
 		state_sync_provider.expect_target_hash().return_const(target_hash);
+		state_sync_provider.expect_on_state_imported().times(1).return_const(());
 
 		let mut state_strategy = StateStrategy::new_with_provider(
 			Box::new(state_sync_provider),
@@ -850,6 +934,7 @@ mod test {
 		let target_hash = Hash::random();
 		let mut state_sync_provider = MockStateSync::<Block>::new();
 		state_sync_provider.expect_target_hash().return_const(target_hash);
+		state_sync_provider.expect_on_state_imported().times(1).return_const(());
 		state_sync_provider.expect_is_complete().return_const(true);
 
 		// Get enough peers for possible spurious requests.
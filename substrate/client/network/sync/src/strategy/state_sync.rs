@@ -17,29 +17,78 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 //! State sync support.
+//!
+//! The top-level trie is split into several key ranges that are downloaded and verified
+//! independently, allowing them to be requested from different peers in parallel. Every
+//! accepted response is persisted in the aux-db, so that an interrupted download can be resumed
+//! after a restart.
+
+mod aux_schema;
 
 use crate::{
 	schema::v1::{KeyValueStateEntry, StateEntry, StateRequest, StateResponse},
 	LOG_TARGET,
 };
+use aux_schema::{PersistedProgress, StateChunk};
 use codec::{Decode, Encode};
-use log::debug;
-use sc_client_api::{CompactProof, KeyValueStates, ProofProvider};
+use log::{debug, info, warn};
+use sc_client_api::{AuxStore, CompactProof, ProofProvider};
 use sc_consensus::ImportedState;
 use smallvec::SmallVec;
+use sp_blockchain::Result as ClientResult;
 use sp_core::storage::well_known_keys;
 use sp_runtime::{
 	traits::{Block as BlockT, Header, NumberFor},
 	Justifications,
 };
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{
+	collections::{HashMap, HashSet},
+	fmt,
+	sync::Arc,
+};
+
+/// Number of key ranges the top-level trie is split into for parallel download.
+pub const STATE_SYNC_RANGES: usize = 8;
+
+/// Maximum number of blocks the target of an interrupted state download may be behind a new
+/// sync target to still be resumed. Peers are likely to have pruned older states.
+pub const STATE_SYNC_RESUME_WINDOW: u32 = 256;
+
+/// Storage the progress of a state download is persisted in, so that an interrupted download can
+/// be resumed after a restart.
+///
+/// Implemented for every [`AuxStore`], which keeps the progress in the aux-db of the client.
+pub trait StateSyncStore: Send + Sync {
+	/// Get the value stored under `key`, if any.
+	fn get(&self, key: &[u8]) -> ClientResult<Option<Vec<u8>>>;
+	/// Atomically insert the given key-values and delete the given keys.
+	fn update(&self, insert: &[(&[u8], &[u8])], delete: &[&[u8]]) -> ClientResult<()>;
+}
+
+impl fmt::Debug for dyn StateSyncStore {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "StateSyncStore")
+	}
+}
+
+impl<T: AuxStore + Send + Sync> StateSyncStore for T {
+	fn get(&self, key: &[u8]) -> ClientResult<Option<Vec<u8>>> {
+		self.get_aux(key)
+	}
+
+	fn update(&self, insert: &[(&[u8], &[u8])], delete: &[&[u8]]) -> ClientResult<()> {
+		self.insert_aux(insert, delete)
+	}
+}
 
 /// Generic state sync provider. Used for mocking in tests.
 pub trait StateSyncProvider<B: BlockT>: Send + Sync {
-	/// Validate and import a state response.
-	fn import(&mut self, response: StateResponse) -> ImportResult<B>;
-	/// Produce next state request.
-	fn next_request(&self) -> StateRequest;
+	/// Validate and import a state response for the given key range.
+	fn import(&mut self, range: usize, response: StateResponse) -> ImportResult<B>;
+	/// Produce next state request for the given key range.
+	fn next_request(&self, range: usize) -> StateRequest;
+	/// Returns the key ranges that are not downloaded yet.
+	fn pending_ranges(&self) -> Vec<usize>;
 	/// Check if the state is complete.
 	fn is_complete(&self) -> bool;
 	/// Returns target block number.
@@ -48,6 +97,8 @@ pub trait StateSyncProvider<B: BlockT>: Send + Sync {
 	fn target_hash(&self) -> B::Hash;
 	/// Returns state sync estimated progress.
 	fn progress(&self) -> StateSyncProgress;
+	/// Notify that the downloaded state has been imported.
+	fn on_state_imported(&mut self);
 }
 
 // Reported state sync phase.
@@ -89,8 +140,113 @@ pub enum ImportResult<B: BlockT> {
 	BadResponse,
 }
 
+/// Block whose state download was interrupted and can be resumed.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct PersistedTarget<B: BlockT> {
+	/// Header of the target block.
+	pub header: B::Header,
+	/// Body of the target block, if known.
+	pub body: Option<Vec<B::Extrinsic>>,
+	/// Justifications of the target block, if known.
+	pub justifications: Option<Justifications>,
+	/// Whether the state is downloaded without proofs.
+	pub skip_proof: bool,
+}
+
+/// Returns the target of an interrupted state download, if it was downloaded in the same
+/// `skip_proof` mode and has some progress worth resuming.
+pub fn resumable_target<B: BlockT>(
+	store: &dyn StateSyncStore,
+	skip_proof: bool,
+) -> Option<PersistedTarget<B>> {
+	let progress = aux_schema::load_progress(store).ok().flatten()?;
+	let target = aux_schema::load_target::<B>(store).ok().flatten()?;
+	(target.skip_proof == skip_proof && progress.chunks > 0).then_some(target)
+}
+
+/// A key range of the top-level trie that is downloaded independently of other ranges.
+///
+/// Range boundaries are single-byte keys. All child trie roots are stored under the
+/// `:child_storage:` prefix, so a child trie never spans multiple ranges.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+struct StateRange {
+	/// Position of the last imported key, initially the exclusive start of the range.
+	/// Multiple keys are used for nested state start.
+	last_key: Vec<Vec<u8>>,
+	/// Inclusive end of the range, `None` for the last range.
+	end: Option<Vec<u8>>,
+	/// First byte of the keys covered by the range.
+	first_byte: u8,
+	/// All keys of the range are imported.
+	complete: bool,
+}
+
+impl StateRange {
+	/// Split the key space into `count` ranges of equal width.
+	fn split(count: usize) -> Vec<Self> {
+		let count = count.clamp(1, 256);
+		let first_bytes = (0..count).map(|i| (i * 256 / count) as u8).collect::<Vec<_>>();
+		first_bytes
+			.iter()
+			.enumerate()
+			.map(|(i, &first_byte)| Self {
+				// The single-byte key is the end of the previous range.
+				last_key: if i == 0 { Vec::new() } else { vec![vec![first_byte]] },
+				end: first_bytes.get(i + 1).map(|next| vec![*next]),
+				first_byte,
+				complete: false,
+			})
+			.collect()
+	}
+
+	/// Width of the range in 1/256 of the key space.
+	fn width(&self) -> u32 {
+		self.end.as_ref().map_or(256, |end| end[0] as u32) - self.first_byte as u32
+	}
+
+	/// Estimated downloaded part of the range in 1/256 of the key space.
+	fn downloaded(&self) -> u32 {
+		if self.complete {
+			return self.width()
+		}
+		let cursor = self.last_key.first().and_then(|key| key.first()).copied();
+		cursor
+			.map_or(0, |cursor| (cursor as u32).saturating_sub(self.first_byte as u32))
+			.min(self.width())
+	}
+
+	/// Drop the key-values of `chunk` that are located beyond the end of the range.
+	///
+	/// The first entry of `chunk` must be the top-level trie. Child tries are kept only if
+	/// their root is referenced by a remaining top-level key, or if the range cursor points into
+	/// them. Returns `true` if the end of the range was reached.
+	fn restrict(&self, chunk: &mut StateChunk) -> bool {
+		let Some(end) = self.end.as_ref() else { return false };
+		let Some((_, top)) = chunk.first_mut() else { return false };
+		let in_range = top.iter().take_while(|(key, _)| key <= end).count();
+		if in_range == top.len() {
+			return false
+		}
+		top.truncate(in_range);
+
+		let child_roots = top
+			.iter()
+			.filter(|(key, _)| well_known_keys::is_child_storage_key(key))
+			.map(|(_, root)| root.clone())
+			.collect::<HashSet<_>>();
+		let resumed_child = self.last_key.len() == 2;
+		let mut index = 0;
+		chunk.retain(|(root, _)| {
+			let keep = index == 0 || (index == 1 && resumed_child) || child_roots.contains(root);
+			index += 1;
+			keep
+		});
+		true
+	}
+}
+
 struct StateSyncMetadata<B: BlockT> {
-	last_key: SmallVec<[Vec<u8>; 2]>,
+	ranges: Vec<StateRange>,
 	target_header: B::Header,
 	target_body: Option<Vec<B::Extrinsic>>,
 	target_justifications: Option<Justifications>,
@@ -113,19 +269,26 @@ impl<B: BlockT> StateSyncMetadata<B> {
 		*self.target_header.state_root()
 	}
 
-	fn next_request(&self) -> StateRequest {
+	fn next_request(&self, range: usize) -> StateRequest {
 		StateRequest {
 			block: self.target_hash().encode(),
-			start: self.last_key.clone().into_vec(),
+			start: self.ranges.get(range).map(|range| range.last_key.clone()).unwrap_or_default(),
 			no_proof: self.skip_proof,
 		}
 	}
 
+	fn pending_ranges(&self) -> Vec<usize> {
+		self.ranges
+			.iter()
+			.enumerate()
+			.filter_map(|(index, range)| (!range.complete).then_some(index))
+			.collect()
+	}
+
 	fn progress(&self) -> StateSyncProgress {
-		let cursor = *self.last_key.get(0).and_then(|last| last.get(0)).unwrap_or(&0u8);
-		let percent_done = cursor as u32 * 100 / 256;
+		let downloaded = self.ranges.iter().map(StateRange::downloaded).sum::<u32>();
 		StateSyncProgress {
-			percentage: percent_done,
+			percentage: downloaded * 100 / 256,
 			size: self.imported_bytes,
 			phase: if self.complete {
 				StateSyncPhase::ImportingState
@@ -143,6 +306,10 @@ pub struct StateSync<B: BlockT, Client> {
 	metadata: StateSyncMetadata<B>,
 	state: HashMap<Vec<u8>, (Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>)>,
 	client: Arc<Client>,
+	/// Storage the progress is persisted in, if any.
+	store: Option<Arc<dyn StateSyncStore>>,
+	/// Number of chunks of the current download persisted in the `store`.
+	persisted_chunks: u32,
 }
 
 impl<B, Client> StateSync<B, Client>
@@ -151,17 +318,20 @@ where
 	Client: ProofProvider<B> + Send + Sync + 'static,
 {
 	///  Create a new instance.
+	///
+	/// Resumes an interrupted download of the same target block persisted in `store`, if any.
 	pub fn new(
 		client: Arc<Client>,
+		store: Option<Arc<dyn StateSyncStore>>,
 		target_header: B::Header,
 		target_body: Option<Vec<B::Extrinsic>>,
 		target_justifications: Option<Justifications>,
 		skip_proof: bool,
 	) -> Self {
-		Self {
+		let mut state_sync = Self {
 			client,
 			metadata: StateSyncMetadata {
-				last_key: SmallVec::default(),
+				ranges: StateRange::split(STATE_SYNC_RANGES),
 				target_header,
 				target_body,
 				target_justifications,
@@ -170,7 +340,103 @@ where
 				skip_proof,
 			},
 			state: HashMap::default(),
+			store,
+			persisted_chunks: 0,
+		};
+		state_sync.resume();
+		state_sync
+	}
+
+	/// Restore the progress of an interrupted download of the same state, discarding the
+	/// progress made for any other state.
+	fn resume(&mut self) {
+		let Some(store) = self.store.clone() else { return };
+		let persisted = aux_schema::load_target::<B>(&*store).and_then(|target| {
+			let Some(target) = target else { return Ok(None) };
+			let Some(progress) = aux_schema::load_progress(&*store)? else { return Ok(None) };
+			Ok(Some((target, progress)))
+		});
+		let (target, progress) = match persisted {
+			Ok(Some(persisted)) => persisted,
+			Ok(None) => return self.clear_persisted(),
+			Err(e) => {
+				warn!(target: LOG_TARGET, "Failed to load state sync progress: {e}");
+				return self.clear_persisted()
+			},
+		};
+
+		if target.header.hash() != self.metadata.target_hash() ||
+			target.skip_proof != self.metadata.skip_proof
+		{
+			debug!(
+				target: LOG_TARGET,
+				"Discarding state sync progress for #{} ({})",
+				target.header.number(),
+				target.header.hash(),
+			);
+			return self.clear_persisted()
 		}
+
+		if let Err(e) =
+			aux_schema::for_each_chunk(&*store, &progress, |chunk| self.process_state_chunk(chunk))
+		{
+			warn!(target: LOG_TARGET, "Failed to load state sync progress: {e}");
+			self.state.clear();
+			self.metadata.imported_bytes = 0;
+			return self.clear_persisted()
+		}
+
+		let PersistedProgress { ranges, chunks, imported_bytes } = progress;
+		self.metadata.ranges = ranges;
+		self.metadata.imported_bytes = imported_bytes;
+		self.metadata.target_body = self.metadata.target_body.take().or(target.body);
+		self.metadata.target_justifications =
+			self.metadata.target_justifications.take().or(target.justifications);
+		self.persisted_chunks = chunks;
+
+		info!(
+			target: LOG_TARGET,
+			"Resuming state sync for #{} ({}) at {}%",
+			self.metadata.target_number(),
+			self.metadata.target_hash(),
+			self.metadata.progress().percentage,
+		);
+	}
+
+	/// Persist an encoded chunk of the state with the current progress.
+	fn persist_chunk(&mut self, chunk: &[u8]) {
+		let Some(store) = self.store.as_deref() else { return };
+		if self.persisted_chunks == 0 {
+			let target = PersistedTarget::<B> {
+				header: self.metadata.target_header.clone(),
+				body: self.metadata.target_body.clone(),
+				justifications: self.metadata.target_justifications.clone(),
+				skip_proof: self.metadata.skip_proof,
+			};
+			if let Err(e) = aux_schema::write_target(store, &target) {
+				warn!(target: LOG_TARGET, "Failed to persist state sync target: {e}");
+				return
+			}
+		}
+
+		let progress = PersistedProgress {
+			ranges: self.metadata.ranges.clone(),
+			chunks: self.persisted_chunks + 1,
+			imported_bytes: self.metadata.imported_bytes,
+		};
+		match aux_schema::write_chunk(store, &progress, chunk) {
+			Ok(()) => self.persisted_chunks = progress.chunks,
+			Err(e) => warn!(target: LOG_TARGET, "Failed to persist state sync progress: {e}"),
+		}
+	}
+
+	fn clear_persisted(&mut self) {
+		if let Some(store) = self.store.as_deref() {
+			if let Err(e) = aux_schema::clear(store) {
+				warn!(target: LOG_TARGET, "Failed to clear state sync progress: {e}");
+			}
+		}
+		self.persisted_chunks = 0;
 	}
 
 	fn process_state_key_values(
@@ -184,7 +450,8 @@ where
 
 		if entry.0.len() > 0 && entry.1.len() > 1 {
 			// Already imported child_trie with same root.
-			// Warning this will not work with parallel download.
+			// Child tries never span multiple key ranges, so this also holds when ranges
+			// are downloaded in parallel.
 			return;
 		}
 
@@ -205,25 +472,80 @@ where
 		}
 	}
 
-	fn process_state_verified(&mut self, values: KeyValueStates) {
-		for values in values.0 {
-			self.process_state_key_values(values.state_root, values.key_values);
+	fn process_state_chunk(&mut self, chunk: StateChunk) {
+		for (state_root, key_values) in chunk {
+			self.process_state_key_values(state_root, key_values);
 		}
 	}
 
-	fn process_state_unverified(&mut self, response: StateResponse) -> bool {
+	/// Verify the range proof of a response and advance the cursor of `range`.
+	///
+	/// Returns the verified key-values belonging to the range.
+	fn import_verified(&mut self, range: usize, proof: Vec<u8>) -> Option<StateChunk> {
+		debug!(target: LOG_TARGET, "Importing state from {} trie nodes", proof.len());
+		let proof_size = proof.len() as u64;
+		let proof = match CompactProof::decode(&mut proof.as_ref()) {
+			Ok(proof) => proof,
+			Err(e) => {
+				debug!(target: LOG_TARGET, "Error decoding proof: {:?}", e);
+				return None
+			},
+		};
+		let target_root = self.metadata.target_root();
+		let state_range = &mut self.metadata.ranges[range];
+		let (values, completed) =
+			match self.client.verify_range_proof(target_root, proof, &state_range.last_key) {
+				Err(e) => {
+					debug!(
+						target: LOG_TARGET,
+						"StateResponse failed proof verification: {}",
+						e,
+					);
+					return None
+				},
+				Ok(values) => values,
+			};
+		debug!(target: LOG_TARGET, "Imported with {} keys", values.len());
+
+		let mut last_key = SmallVec::from_vec(state_range.last_key.clone());
+		if completed != 0 && !values.update_last_key(completed, &mut last_key) {
+			debug!(target: LOG_TARGET, "Error updating key cursor, depth: {}", completed);
+		};
+
+		let mut chunk = values
+			.0
+			.into_iter()
+			.map(|level| (level.state_root, level.key_values))
+			.collect::<StateChunk>();
+		let reached_end = state_range.restrict(&mut chunk);
+		state_range.complete = completed == 0 || reached_end;
+		if !state_range.complete {
+			state_range.last_key = last_key.into_vec();
+		}
+		self.metadata.imported_bytes += proof_size;
+		Some(chunk)
+	}
+
+	/// Advance the cursor of `range` with unverified response entries.
+	///
+	/// Returns the key-values belonging to the range.
+	fn import_unverified(&mut self, range: usize, entries: Vec<KeyValueStateEntry>) -> StateChunk {
+		let state_range = &mut self.metadata.ranges[range];
+		let mut last_key = state_range.last_key.clone();
 		let mut complete = true;
 		// if the trie is a child trie and one of its parent trie is empty,
 		// the parent cursor stays valid.
 		// Empty parent trie content only happens when all the response content
 		// is part of a single child trie.
-		if self.metadata.last_key.len() == 2 && response.entries[0].entries.is_empty() {
+		if last_key.len() == 2 && entries.first().map_or(false, |top| top.entries.is_empty()) {
 			// Do not remove the parent trie position.
-			self.metadata.last_key.pop();
+			last_key.pop();
 		} else {
-			self.metadata.last_key.clear();
+			last_key.clear();
 		}
-		for state in response.entries {
+
+		let mut chunk = StateChunk::with_capacity(entries.len());
+		for state in entries {
 			debug!(
 				target: LOG_TARGET,
 				"Importing state from {:?} to {:?}",
@@ -233,18 +555,24 @@ where
 
 			if !state.complete {
 				if let Some(e) = state.entries.last() {
-					self.metadata.last_key.push(e.key.clone());
+					last_key.push(e.key.clone());
 				}
 				complete = false;
 			}
 
 			let KeyValueStateEntry { state_root, entries, complete: _ } = state;
-			self.process_state_key_values(
+			chunk.push((
 				state_root,
-				entries.into_iter().map(|StateEntry { key, value }| (key, value)),
-			);
+				entries.into_iter().map(|StateEntry { key, value }| (key, value)).collect(),
+			));
 		}
-		complete
+
+		let reached_end = state_range.restrict(&mut chunk);
+		state_range.complete = complete || reached_end;
+		if !state_range.complete {
+			state_range.last_key = last_key;
+		}
+		chunk
 	}
 }
 
@@ -254,7 +582,7 @@ where
 	Client: ProofProvider<B> + Send + Sync + 'static,
 {
 	///  Validate and import a state response.
-	fn import(&mut self, response: StateResponse) -> ImportResult<B> {
+	fn import(&mut self, range: usize, response: StateResponse) -> ImportResult<B> {
 		if response.entries.is_empty() && response.proof.is_empty() {
 			debug!(target: LOG_TARGET, "Bad state response");
 			return ImportResult::BadResponse
@@ -263,45 +591,31 @@ where
 			debug!(target: LOG_TARGET, "Missing proof");
 			return ImportResult::BadResponse
 		}
-		let complete = if !self.metadata.skip_proof {
-			debug!(target: LOG_TARGET, "Importing state from {} trie nodes", response.proof.len());
-			let proof_size = response.proof.len() as u64;
-			let proof = match CompactProof::decode(&mut response.proof.as_ref()) {
-				Ok(proof) => proof,
-				Err(e) => {
-					debug!(target: LOG_TARGET, "Error decoding proof: {:?}", e);
-					return ImportResult::BadResponse
-				},
-			};
-			let (values, completed) = match self.client.verify_range_proof(
-				self.metadata.target_root(),
-				proof,
-				self.metadata.last_key.as_slice(),
-			) {
-				Err(e) => {
-					debug!(
-						target: LOG_TARGET,
-						"StateResponse failed proof verification: {}",
-						e,
-					);
-					return ImportResult::BadResponse
-				},
-				Ok(values) => values,
-			};
-			debug!(target: LOG_TARGET, "Imported with {} keys", values.len());
-
-			let complete = completed == 0;
-			if !complete && !values.update_last_key(completed, &mut self.metadata.last_key) {
-				debug!(target: LOG_TARGET, "Error updating key cursor, depth: {}", completed);
-			};
+		match self.metadata.ranges.get(range) {
+			None => {
+				debug!(target: LOG_TARGET, "State response for unknown range {range}");
+				return ImportResult::BadResponse
+			},
+			Some(state_range) if state_range.complete => {
+				debug!(target: LOG_TARGET, "Ignored state response for complete range {range}");
+				return ImportResult::Continue
+			},
+			Some(_) => {},
+		}
 
-			self.process_state_verified(values);
-			self.metadata.imported_bytes += proof_size;
-			complete
+		let chunk = if !self.metadata.skip_proof {
+			match self.import_verified(range, response.proof) {
+				Some(chunk) => chunk,
+				None => return ImportResult::BadResponse,
+			}
 		} else {
-			self.process_state_unverified(response)
+			self.import_unverified(range, response.entries)
 		};
-		if complete {
+
+		if self.metadata.ranges.iter().all(|range| range.complete) {
+			// The last chunk is not persisted: the progress is only cleared once the state is
+			// imported, so an interrupted import re-downloads just the final range.
+			self.process_state_chunk(chunk);
 			self.metadata.complete = true;
 			let target_hash = self.metadata.target_hash();
 			ImportResult::Import(
@@ -312,13 +626,21 @@ where
 				self.metadata.target_justifications.clone(),
 			)
 		} else {
+			let encoded = chunk.encode();
+			self.process_state_chunk(chunk);
+			self.persist_chunk(&encoded);
 			ImportResult::Continue
 		}
 	}
 
 	/// Produce next state request.
-	fn next_request(&self) -> StateRequest {
-		self.metadata.next_request()
+	fn next_request(&self, range: usize) -> StateRequest {
+		self.metadata.next_request(range)
+	}
+
+	/// Returns the key ranges that are not downloaded yet.
+	fn pending_ranges(&self) -> Vec<usize> {
+		self.metadata.pending_ranges()
 	}
 
 	/// Check if the state is complete.
@@ -340,4 +662,195 @@ where
 	fn progress(&self) -> StateSyncProgress {
 		self.metadata.progress()
 	}
+
+	/// Drop the persisted download progress.
+	fn on_state_imported(&mut self) {
+		self.clear_persisted();
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use sc_block_builder::BlockBuilderBuilder;
+	use sc_client_api::{HeaderBackend, StorageProvider};
+	use substrate_test_runtime_client::{
+		runtime::{Block, Hash},
+		DefaultTestClientBuilderExt, TestClient, TestClientBuilder, TestClientBuilderExt,
+	};
+
+	const RESPONSE_SIZE_LIMIT: usize = 16 * 1024;
+
+	fn genesis_header(client: &TestClient) -> <Block as BlockT>::Header {
+		client.header(client.chain_info().genesis_hash).unwrap().unwrap()
+	}
+
+	fn respond(client: &TestClient, request: &StateRequest) -> StateResponse {
+		let hash = Hash::decode(&mut request.block.as_ref()).unwrap();
+		let (proof, _count) =
+			client.read_proof_collection(hash, &request.start, RESPONSE_SIZE_LIMIT).unwrap();
+		StateResponse { entries: Vec::new(), proof: proof.encode() }
+	}
+
+	/// Request every pending range once.
+	fn request_pending_ranges(
+		client: &TestClient,
+		state_sync: &mut StateSync<Block, TestClient>,
+	) -> Option<ImportedState<Block>> {
+		for range in state_sync.pending_ranges() {
+			let response = respond(client, &state_sync.next_request(range));
+			match state_sync.import(range, response) {
+				ImportResult::Import(_, _, state, _, _) => return Some(state),
+				ImportResult::Continue => {},
+				ImportResult::BadResponse => panic!("Valid response for range {range} rejected"),
+			}
+		}
+		None
+	}
+
+	#[test]
+	fn key_space_is_split_into_adjacent_ranges() {
+		let ranges = StateRange::split(4);
+		assert_eq!(ranges.len(), 4);
+		assert_eq!(ranges[0].last_key, Vec::<Vec<u8>>::new());
+		assert_eq!(ranges[0].end, Some(vec![64]));
+		assert_eq!(ranges[1].last_key, vec![vec![64]]);
+		assert_eq!(ranges[1].end, Some(vec![128]));
+		assert_eq!(ranges[3].last_key, vec![vec![192]]);
+		assert_eq!(ranges[3].end, None);
+		assert_eq!(ranges.iter().map(StateRange::width).sum::<u32>(), 256);
+	}
+
+	#[test]
+	fn keys_beyond_range_end_are_dropped() {
+		let range = StateRange::split(2).remove(0);
+		let child_key = well_known_keys::CHILD_STORAGE_KEY_PREFIX.to_vec();
+		let mut chunk = vec![
+			(
+				Vec::new(),
+				vec![
+					(vec![1], vec![1]),
+					(child_key, vec![42]),
+					(vec![128], vec![2]),
+					(vec![128, 0], vec![3]),
+					(vec![200], vec![4]),
+				],
+			),
+			(vec![42], vec![(vec![5], vec![5])]),
+		];
+
+		assert!(range.restrict(&mut chunk));
+		assert_eq!(chunk.len(), 2);
+		assert_eq!(chunk[0].1.len(), 3);
+		assert_eq!(chunk[0].1.last().unwrap().0, vec![128]);
+
+		// Nothing is dropped if the end is not reached.
+		let mut chunk = vec![(Vec::new(), vec![(vec![1], vec![1])])];
+		assert!(!range.restrict(&mut chunk));
+		assert_eq!(chunk[0].1.len(), 1);
+	}
+
+	#[test]
+	fn ranges_are_downloaded_independently() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let genesis_hash = client.chain_info().genesis_hash;
+		let mut state_sync = StateSync::new(
+			client.clone(),
+			Some(client.clone()),
+			genesis_header(&client),
+			None,
+			None,
+			false,
+		);
+		assert_eq!(state_sync.pending_ranges().len(), STATE_SYNC_RANGES);
+
+		let state = loop {
+			if let Some(state) = request_pending_ranges(&client, &mut state_sync) {
+				break state
+			}
+		};
+		assert!(state_sync.is_complete());
+		assert!(state_sync.pending_ranges().is_empty());
+
+		let mut imported = state
+			.state
+			.0
+			.into_iter()
+			.find(|level| level.state_root.is_empty())
+			.unwrap()
+			.key_values;
+		imported.sort();
+		let expected = client
+			.storage_pairs(genesis_hash, None, None)
+			.unwrap()
+			.filter(|(key, _)| !well_known_keys::is_child_storage_key(&key.0))
+			.map(|(key, value)| (key.0, value.0))
+			.collect::<Vec<_>>();
+		assert_eq!(imported, expected);
+
+		// Progress is kept until the state is imported.
+		assert!(resumable_target::<Block>(&*client, false).is_some());
+		state_sync.on_state_imported();
+		assert!(resumable_target::<Block>(&*client, false).is_none());
+	}
+
+	#[test]
+	fn interrupted_download_is_resumed() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let header = genesis_header(&client);
+		let mut state_sync =
+			StateSync::new(client.clone(), Some(client.clone()), header.clone(), None, None, false);
+
+		let range = STATE_SYNC_RANGES - 1;
+		let response = respond(&client, &state_sync.next_request(range));
+		assert!(matches!(state_sync.import(range, response), ImportResult::Continue));
+		let pending = state_sync.pending_ranges();
+		let progress = state_sync.progress();
+		let next_request = state_sync.next_request(range);
+		let state = state_sync.state.clone();
+		drop(state_sync);
+
+		let target = resumable_target::<Block>(&*client, false).unwrap();
+		assert_eq!(target.header, header);
+
+		// Restarted download of the same block continues where it left off.
+		let state_sync =
+			StateSync::new(client.clone(), Some(client.clone()), header, None, None, false);
+		assert_eq!(state_sync.pending_ranges(), pending);
+		assert_eq!(state_sync.progress(), progress);
+		assert_eq!(state_sync.next_request(range), next_request);
+		assert_eq!(state_sync.state, state);
+	}
+
+	#[test]
+	fn progress_of_other_block_is_discarded() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let mut state_sync = StateSync::new(
+			client.clone(),
+			Some(client.clone()),
+			genesis_header(&client),
+			None,
+			None,
+			false,
+		);
+		let response = respond(&client, &state_sync.next_request(0));
+		assert!(matches!(state_sync.import(0, response), ImportResult::Continue));
+		drop(state_sync);
+
+		let other_header = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(client.chain_info().best_hash)
+			.with_parent_block_number(client.chain_info().best_number)
+			.build()
+			.unwrap()
+			.build()
+			.unwrap()
+			.block
+			.header
+			.clone();
+		let state_sync =
+			StateSync::new(client.clone(), Some(client.clone()), other_header, None, None, false);
+		assert_eq!(state_sync.pending_ranges().len(), STATE_SYNC_RANGES);
+		assert_eq!(state_sync.progress().size, 0);
+		assert!(resumable_target::<Block>(&*client, false).is_none());
+	}
 }
@@ -0,0 +1,123 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Schema for the state sync progress persisted in the aux-db.
+//!
+//! Every accepted state response is written as a separate chunk together with the updated
+//! progress record, so that a restarted node can rebuild the partially downloaded state and
+//! continue requesting the key ranges that are still missing.
+
+use super::{PersistedTarget, StateRange, StateSyncStore};
+use codec::{Decode, Encode};
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use sp_runtime::traits::Block as BlockT;
+
+const TARGET_KEY: &[u8] = b"sync_state_target";
+const PROGRESS_KEY: &[u8] = b"sync_state_progress";
+const CHUNK_KEY_PREFIX: &[u8] = b"sync_state_chunk";
+
+/// Key-values of the tries touched by a single state response, keyed by trie root.
+pub(super) type StateChunk = Vec<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>)>;
+
+/// Progress of an interrupted state download.
+#[derive(Encode, Decode)]
+pub(super) struct PersistedProgress {
+	/// Download cursors of all key ranges.
+	pub ranges: Vec<StateRange>,
+	/// Number of chunks written so far.
+	pub chunks: u32,
+	/// Number of bytes imported so far.
+	pub imported_bytes: u64,
+}
+
+fn chunk_key(index: u32) -> Vec<u8> {
+	(CHUNK_KEY_PREFIX, index).encode()
+}
+
+fn load_decode<T: Decode>(store: &dyn StateSyncStore, key: &[u8]) -> ClientResult<Option<T>> {
+	match store.get(key)? {
+		None => Ok(None),
+		Some(t) => T::decode(&mut &t[..]).map(Some).map_err(|e| {
+			ClientError::Backend(format!("State sync progress is corrupted. Decode error: {e}"))
+		}),
+	}
+}
+
+/// Load the target of the persisted state download, if any.
+pub(super) fn load_target<B: BlockT>(
+	store: &dyn StateSyncStore,
+) -> ClientResult<Option<PersistedTarget<B>>> {
+	load_decode(store, TARGET_KEY)
+}
+
+/// Load the persisted state download progress, if any.
+pub(super) fn load_progress(store: &dyn StateSyncStore) -> ClientResult<Option<PersistedProgress>> {
+	load_decode(store, PROGRESS_KEY)
+}
+
+/// Load the chunks referenced by `progress` one at a time, passing each of them to `f`.
+///
+/// Only a single chunk is kept in memory while loading, as the downloaded state may be huge.
+pub(super) fn for_each_chunk(
+	store: &dyn StateSyncStore,
+	progress: &PersistedProgress,
+	mut f: impl FnMut(StateChunk),
+) -> ClientResult<()> {
+	for index in 0..progress.chunks {
+		let chunk = load_decode(store, &chunk_key(index))?.ok_or_else(|| {
+			ClientError::Backend(format!("State sync chunk #{index} is missing."))
+		})?;
+		f(chunk);
+	}
+	Ok(())
+}
+
+/// Write the target of a new state download.
+pub(super) fn write_target<B: BlockT>(
+	store: &dyn StateSyncStore,
+	target: &PersistedTarget<B>,
+) -> ClientResult<()> {
+	store.update(&[(TARGET_KEY, target.encode().as_slice())], &[])
+}
+
+/// Write an encoded [`StateChunk`] together with the progress that accounts for it.
+pub(super) fn write_chunk(
+	store: &dyn StateSyncStore,
+	progress: &PersistedProgress,
+	chunk: &[u8],
+) -> ClientResult<()> {
+	let index = progress.chunks.checked_sub(1).ok_or_else(|| {
+		ClientError::Backend("State sync progress doesn't account for the chunk.".into())
+	})?;
+	let chunk_key = chunk_key(index);
+	store
+		.update(&[(chunk_key.as_slice(), chunk), (PROGRESS_KEY, progress.encode().as_slice())], &[])
+}
+
+/// Remove the persisted state download.
+pub(super) fn clear(store: &dyn StateSyncStore) -> ClientResult<()> {
+	// Remove at least the target and progress records if the progress can't be decoded.
+	let chunks = load_progress(store).ok().flatten().map_or(0, |progress| progress.chunks);
+	let chunk_keys = (0..chunks).map(chunk_key).collect::<Vec<_>>();
+	let keys = chunk_keys
+		.iter()
+		.map(Vec::as_slice)
+		.chain([TARGET_KEY, PROGRESS_KEY])
+		.collect::<Vec<_>>();
+	store.update(&[], &keys)
+}
@@ -38,6 +38,7 @@ use sc_network_common::sync::message::{
 	BlockAnnounce, BlockAttributes, BlockData, BlockRequest, Direction, FromBlock,
 };
 use sc_network_types::PeerId;
+use serde::{Deserialize, Serialize};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{
 	traits::{Block as BlockT, Header, NumberFor, Zero},
@@ -151,25 +152,62 @@ pub struct WarpSyncProgress<Block: BlockT> {
 	pub total_bytes: u64,
 }
 
+/// Trusted finalized block warp sync starts from instead of genesis.
+///
+/// Peers only need to prove the authority set changes that happened after the checkpoint.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", bound = "")]
+pub struct WarpSyncCheckpoint<Block: BlockT> {
+	/// Hash of the checkpoint block.
+	pub hash: Block::Hash,
+	/// Number of the checkpoint block.
+	pub number: NumberFor<Block>,
+	/// Id of the authority set that finalizes the blocks following the checkpoint.
+	pub set_id: SetId,
+	/// Authorities of the set with `set_id`.
+	pub authorities: AuthorityList,
+}
+
+/// Chain spec extension with a [`WarpSyncCheckpoint`].
+pub type WarpSyncCheckpointExtension<Block> = Option<WarpSyncCheckpoint<Block>>;
+
 /// Warp sync configuration as accepted by [`WarpSync`].
 pub enum WarpSyncConfig<Block: BlockT> {
 	/// Standard warp sync for the chain.
 	WithProvider(Arc<dyn WarpSyncProvider<Block>>),
+	/// Warp sync starting from a trusted checkpoint instead of genesis.
+	WithCheckpoint(Arc<dyn WarpSyncProvider<Block>>, WarpSyncCheckpoint<Block>),
 	/// Skip downloading proofs and use provided header of the state that should be downloaded.
 	///
 	/// It is expected that the header provider ensures that the header is trusted.
 	WithTarget(<Block as BlockT>::Header),
 }
 
+impl<Block: BlockT> WarpSyncConfig<Block> {
+	/// Start warp sync from `checkpoint`. Has no effect if the proofs are not downloaded.
+	pub fn with_checkpoint(self, checkpoint: WarpSyncCheckpoint<Block>) -> Self {
+		match self {
+			Self::WithProvider(provider) | Self::WithCheckpoint(provider, _) =>
+				Self::WithCheckpoint(provider, checkpoint),
+			Self::WithTarget(header) => Self::WithTarget(header),
+		}
+	}
+}
+
 /// Warp sync phase used by warp sync state machine.
 enum Phase<B: BlockT> {
 	/// Waiting for enough peers to connect.
-	WaitingForPeers { warp_sync_provider: Arc<dyn WarpSyncProvider<B>> },
+	WaitingForPeers {
+		warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
+		checkpoint: Option<WarpSyncCheckpoint<B>>,
+	},
 	/// Downloading warp proofs.
 	WarpProof {
 		set_id: SetId,
 		authorities: AuthorityList,
 		last_hash: B::Hash,
+		/// Peers below the starting block can't provide proofs.
+		min_best_number: NumberFor<B>,
 		warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
 	},
 	/// Downloading target block.
@@ -253,7 +291,9 @@ where
 
 		let phase = match warp_sync_config {
 			WarpSyncConfig::WithProvider(warp_sync_provider) =>
-				Phase::WaitingForPeers { warp_sync_provider },
+				Phase::WaitingForPeers { warp_sync_provider, checkpoint: None },
+			WarpSyncConfig::WithCheckpoint(warp_sync_provider, checkpoint) =>
+				Phase::WaitingForPeers { warp_sync_provider, checkpoint: Some(checkpoint) },
 			WarpSyncConfig::WithTarget(target_header) => Phase::TargetBlock(target_header),
 		};
 
@@ -314,17 +354,34 @@ where
 
 	/// Start warp sync as soon as we have enough peers.
 	fn try_to_start_warp_sync(&mut self) {
-		let Phase::WaitingForPeers { warp_sync_provider } = &self.phase else { return };
+		let Phase::WaitingForPeers { warp_sync_provider, checkpoint } = &self.phase else { return };
 
 		if self.peers.len() < MIN_PEERS_TO_START_WARP_SYNC {
 			return
 		}
 
-		self.phase = Phase::WarpProof {
-			set_id: 0,
-			authorities: warp_sync_provider.current_authorities(),
-			last_hash: self.client.info().genesis_hash,
-			warp_sync_provider: Arc::clone(warp_sync_provider),
+		let warp_sync_provider = Arc::clone(warp_sync_provider);
+		self.phase = match checkpoint.clone() {
+			Some(WarpSyncCheckpoint { hash, number, set_id, authorities }) => {
+				debug!(
+					target: LOG_TARGET,
+					"Starting warp sync from checkpoint {hash} ({number}), set_id={set_id}.",
+				);
+				Phase::WarpProof {
+					set_id,
+					authorities,
+					last_hash: hash,
+					min_best_number: number,
+					warp_sync_provider,
+				}
+			},
+			None => Phase::WarpProof {
+				set_id: 0,
+				authorities: warp_sync_provider.current_authorities(),
+				last_hash: self.client.info().genesis_hash,
+				min_best_number: Zero::zero(),
+				warp_sync_provider,
+			},
 		};
 		trace!(target: LOG_TARGET, "Started warp sync with {} peers.", self.peers.len());
 	}
@@ -387,7 +444,7 @@ where
 			peer.state = PeerState::Available;
 		}
 
-		let Phase::WarpProof { set_id, authorities, last_hash, warp_sync_provider } =
+		let Phase::WarpProof { set_id, authorities, last_hash, warp_sync_provider, .. } =
 			&mut self.phase
 		else {
 			debug!(target: LOG_TARGET, "Unexpected warp proof response");
@@ -534,10 +591,11 @@ where
 
 	/// Produce warp proof request.
 	fn warp_proof_request(&mut self) -> Option<(PeerId, ProtocolName, WarpProofRequest<B>)> {
-		let Phase::WarpProof { last_hash, .. } = &self.phase else { return None };
+		let Phase::WarpProof { last_hash, min_best_number, .. } = &self.phase else { return None };
 
 		// Copy `last_hash` early to cut the borrowing tie.
 		let begin = *last_hash;
+		let min_best_number = *min_best_number;
 
 		if self
 			.peers
@@ -548,7 +606,8 @@ where
 			return None
 		}
 
-		let peer_id = self.schedule_next_peer(PeerState::DownloadingProofs, None)?;
+		let peer_id =
+			self.schedule_next_peer(PeerState::DownloadingProofs, Some(min_best_number))?;
 		trace!(target: LOG_TARGET, "New WarpProofRequest to {peer_id}, begin hash: {begin}.");
 
 		let request = WarpProofRequest { begin };
@@ -737,8 +796,8 @@ mod test {
 	use crate::{mock::MockBlockDownloader, service::network::NetworkServiceProvider};
 	use sc_block_builder::BlockBuilderBuilder;
 	use sp_blockchain::{BlockStatus, Error as BlockchainError, HeaderBackend, Info};
-	use sp_consensus_grandpa::{AuthorityList, SetId};
-	use sp_core::H256;
+	use sp_consensus_grandpa::{AuthorityId, AuthorityList, SetId};
+	use sp_core::{crypto::ByteArray, H256};
 	use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor};
 	use std::{io::ErrorKind, sync::Arc};
 	use substrate_test_runtime_client::{
@@ -792,6 +851,7 @@ mod test {
 			finalized_state: Some((genesis_hash, 0)),
 			number_leaves: 0,
 			block_gap: None,
+			pruned_blocks: None,
 		});
 
 		client
@@ -809,6 +869,7 @@ mod test {
 			finalized_state: None,
 			number_leaves: 0,
 			block_gap: None,
+			pruned_blocks: None,
 		});
 
 		client
@@ -1096,6 +1157,92 @@ mod test {
 		assert_eq!(request.begin, known_last_hash);
 	}
 
+	#[test]
+	fn warp_sync_is_started_from_checkpoint() {
+		let client = mock_client_without_state();
+		// Genesis authorities are not used when starting from a checkpoint.
+		let mut provider = MockWarpSyncProvider::<Block>::new();
+		provider.expect_current_authorities().never();
+		let checkpoint = WarpSyncCheckpoint::<Block> {
+			hash: Hash::random(),
+			number: 5,
+			set_id: 3,
+			authorities: vec![(AuthorityId::from_slice(&[1; 32]).unwrap(), 1)],
+		};
+		let config = WarpSyncConfig::WithCheckpoint(Arc::new(provider), checkpoint.clone());
+		let mut warp_sync = WarpSync::new(
+			Arc::new(client),
+			config,
+			Some(ProtocolName::Static("")),
+			Arc::new(MockBlockDownloader::new()),
+		);
+
+		for best_number in 1..11 {
+			warp_sync.add_peer(PeerId::random(), Hash::random(), best_number);
+		}
+
+		match &warp_sync.phase {
+			Phase::WarpProof { set_id, authorities, last_hash, min_best_number, .. } => {
+				assert_eq!(*set_id, checkpoint.set_id);
+				assert_eq!(*authorities, checkpoint.authorities);
+				assert_eq!(*last_hash, checkpoint.hash);
+				assert_eq!(*min_best_number, checkpoint.number);
+			},
+			_ => panic!("Invalid phase."),
+		}
+
+		let (_peer_id, _protocol_name, request) = warp_sync.warp_proof_request().unwrap();
+		assert_eq!(request.begin, checkpoint.hash);
+	}
+
+	#[test]
+	fn warp_proofs_are_not_requested_from_peers_below_checkpoint() {
+		let client = mock_client_without_state();
+		let provider = MockWarpSyncProvider::<Block>::new();
+		let checkpoint = WarpSyncCheckpoint::<Block> {
+			hash: Hash::random(),
+			number: 20,
+			set_id: 3,
+			authorities: AuthorityList::default(),
+		};
+		let config = WarpSyncConfig::WithCheckpoint(Arc::new(provider), checkpoint);
+		let mut warp_sync = WarpSync::new(
+			Arc::new(client),
+			config,
+			Some(ProtocolName::Static("")),
+			Arc::new(MockBlockDownloader::new()),
+		);
+
+		for best_number in 1..11 {
+			warp_sync.add_peer(PeerId::random(), Hash::random(), best_number);
+		}
+		assert!(matches!(warp_sync.phase, Phase::WarpProof { .. }));
+
+		// None of the peers has reached the checkpoint.
+		assert!(warp_sync.warp_proof_request().is_none());
+
+		// A peer that has is used.
+		let peer_id = PeerId::random();
+		warp_sync.add_peer(peer_id, Hash::random(), 20);
+		let (scheduled_peer_id, _protocol_name, _request) = warp_sync.warp_proof_request().unwrap();
+		assert_eq!(scheduled_peer_id, peer_id);
+	}
+
+	#[test]
+	fn warp_sync_checkpoint_serialization() {
+		let checkpoint = WarpSyncCheckpoint::<Block> {
+			hash: Hash::repeat_byte(0x42),
+			number: 5,
+			set_id: 3,
+			authorities: vec![(AuthorityId::from_slice(&[1; 32]).unwrap(), 1)],
+		};
+
+		let json = serde_json::to_value(&checkpoint).unwrap();
+		assert_eq!(json["number"], 5);
+		assert_eq!(json["setId"], 3);
+		assert_eq!(serde_json::from_value::<WarpSyncCheckpoint<Block>>(json).unwrap(), checkpoint,);
+	}
+
 	#[test]
 	fn no_parallel_warp_proof_requests() {
 		let client = mock_client_without_state();
@@ -917,6 +917,7 @@ pub trait TestNetFactory: Default + Sized + Send {
 			metrics_registry: None,
 			state_request_protocol_name: state_request_protocol_config.name.clone(),
 			block_downloader: block_relay_params.downloader,
+			state_sync_store: None,
 		};
 		// Initialize syncing strategy.
 		let syncing_strategy = Box::new(
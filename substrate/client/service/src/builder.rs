@@ -63,7 +63,8 @@ use sc_network_sync::{
 		SyncingStrategy,
 	},
 	warp_request_handler::RequestHandler as WarpSyncRequestHandler,
-	SyncingService, WarpSyncConfig,
+	StateSyncStore, SyncingService, WarpSyncCheckpoint, WarpSyncCheckpointExtension,
+	WarpSyncConfig,
 };
 use sc_rpc::{
 	author::AuthorApiServer,
@@ -260,6 +261,9 @@ where
 				no_genesis: config.no_genesis(),
 				wasm_runtime_substitutes,
 				enable_import_proof_recording,
+				// Bodies are only removed, and thus only retained, with a finite blocks pruning.
+				retain_block_bodies: config.blocks_retention.bodies &&
+					matches!(config.blocks_pruning, BlocksPruning::Some(_)),
 			},
 		)?;
 
@@ -830,6 +834,9 @@ where
 	>,
 	/// Optional warp sync config.
 	pub warp_sync_config: Option<WarpSyncConfig<Block>>,
+	/// Optional storage used to resume an interrupted state download after a restart, usually
+	/// the client.
+	pub state_sync_store: Option<Arc<dyn StateSyncStore>>,
 	/// User specified block relay params. If not specified, the default
 	/// block request handler will be used.
 	pub block_relay: Option<BlockRelayParams<Block, Net>>,
@@ -873,6 +880,7 @@ where
 		import_queue,
 		block_announce_validator_builder,
 		warp_sync_config,
+		state_sync_store,
 		block_relay,
 		metrics,
 	} = params;
@@ -912,12 +920,19 @@ where
 		),
 	};
 
+	let warp_sync_config = match (warp_sync_config, warp_sync_checkpoint::<Block>(config)?) {
+		(Some(warp_sync_config), Some(checkpoint)) =>
+			Some(warp_sync_config.with_checkpoint(checkpoint)),
+		(warp_sync_config, _) => warp_sync_config,
+	};
+
 	let syncing_strategy = build_polkadot_syncing_strategy(
 		protocol_id.clone(),
 		fork_id,
 		&mut net_config,
 		warp_sync_config,
 		block_downloader,
+		state_sync_store,
 		client.clone(),
 		&spawn_handle,
 		metrics_registry,
@@ -1178,6 +1193,8 @@ where
 	pub network_service_handle: NetworkServiceHandle,
 	/// Warp sync configuration (when used).
 	pub warp_sync_config: Option<WarpSyncConfig<Block>>,
+	/// Storage used to resume an interrupted state download after a restart, if any.
+	pub state_sync_store: Option<Arc<dyn StateSyncStore>>,
 	/// A shared client returned by `new_full_parts`.
 	pub client: Arc<Client>,
 	/// Blocks import queue API.
@@ -1192,6 +1209,27 @@ where
 	pub metrics: NotificationMetrics,
 }
 
+/// Load the trusted warp sync checkpoint from the file passed on the command line or, if there is
+/// none, from the chain spec.
+fn warp_sync_checkpoint<Block: BlockT>(
+	config: &Configuration,
+) -> Result<Option<WarpSyncCheckpoint<Block>>, Error> {
+	let Some(path) = &config.network.warp_sync_checkpoint else {
+		return Ok(get_extension::<WarpSyncCheckpointExtension<Block>>(
+			config.chain_spec.extensions(),
+		)
+		.cloned()
+		.flatten())
+	};
+
+	let file = std::fs::File::open(path).map_err(|e| {
+		Error::Other(format!("Failed to open warp sync checkpoint {}: {e}", path.display()))
+	})?;
+	serde_json::from_reader(std::io::BufReader::new(file)).map(Some).map_err(|e| {
+		Error::Other(format!("Failed to parse warp sync checkpoint {}: {e}", path.display()))
+	})
+}
+
 /// Build default syncing engine using [`build_default_block_downloader`] and
 /// [`build_polkadot_syncing_strategy`] internally.
 pub fn build_default_syncing_engine<Block, Client, Net>(
@@ -1216,6 +1254,7 @@ where
 		block_announce_validator,
 		network_service_handle,
 		warp_sync_config,
+		state_sync_store,
 		client,
 		import_queue_service,
 		num_peers_hint,
@@ -1239,6 +1278,7 @@ where
 		net_config,
 		warp_sync_config,
 		block_downloader,
+		state_sync_store,
 		client.clone(),
 		spawn_handle,
 		metrics_registry,
@@ -1306,6 +1346,7 @@ pub fn build_polkadot_syncing_strategy<Block, Client, Net>(
 	net_config: &mut FullNetworkConfiguration<Block, <Block as BlockT>::Hash, Net>,
 	warp_sync_config: Option<WarpSyncConfig<Block>>,
 	block_downloader: Arc<dyn BlockDownloader<Block>>,
+	state_sync_store: Option<Arc<dyn StateSyncStore>>,
 	client: Arc<Client>,
 	spawn_handle: &SpawnTaskHandle,
 	metrics_registry: Option<&Registry>,
@@ -1350,7 +1391,8 @@ where
 	net_config.add_request_response_protocol(state_request_protocol_config);
 
 	let (warp_sync_protocol_config, warp_sync_protocol_name) = match warp_sync_config.as_ref() {
-		Some(WarpSyncConfig::WithProvider(warp_with_provider)) => {
+		Some(WarpSyncConfig::WithProvider(warp_with_provider)) |
+		Some(WarpSyncConfig::WithCheckpoint(warp_with_provider, _)) => {
 			// Allow both outgoing and incoming requests.
 			let (handler, protocol_config) = WarpSyncRequestHandler::new::<_, Net>(
 				protocol_id,
@@ -1376,6 +1418,7 @@ where
 		metrics_registry: metrics_registry.cloned(),
 		state_request_protocol_name,
 		block_downloader,
+		state_sync_store,
 	};
 	Ok(Box::new(PolkadotSyncingStrategy::new(
 		syncing_config,
@@ -23,6 +23,7 @@ use super::{
 	CodeProvider,
 };
 use crate::client::notification_pinning::NotificationPinningWorker;
+use codec::{Decode, Encode};
 use log::{debug, info, trace, warn};
 use parking_lot::{Mutex, RwLock};
 use prometheus_endpoint::Registry;
@@ -52,6 +53,7 @@ use sp_api::{
 	ApiExt, ApiRef, CallApiAt, CallApiAtParams, ConstructRuntimeApi, Core as CoreApi,
 	ProvideRuntimeApi,
 };
+use sp_block_builder::BlockRetentionApi;
 use sp_blockchain::{
 	self as blockchain, Backend as ChainBackend, CachedHeaderMetadata, Error,
 	HeaderBackend as ChainHeaderBackend, HeaderMetadata, Info as BlockchainInfo,
@@ -166,6 +168,11 @@ pub struct ClientConfig<Block: BlockT> {
 	pub wasm_runtime_substitutes: HashMap<NumberFor<Block>, Vec<u8>>,
 	/// Enable recording of storage proofs during block import
 	pub enable_import_proof_recording: bool,
+	/// Ask the runtime which block bodies to keep when the blocks are pruned.
+	///
+	/// Only blocks executed on import are asked about. The bodies of blocks imported without
+	/// their parent state, e.g. by warp, state or gap sync, are not kept.
+	pub retain_block_bodies: bool,
 }
 
 impl<Block: BlockT> Default for ClientConfig<Block> {
@@ -177,6 +184,7 @@ impl<Block: BlockT> Default for ClientConfig<Block> {
 			no_genesis: false,
 			wasm_runtime_substitutes: HashMap::new(),
 			enable_import_proof_recording: false,
+			retain_block_bodies: false,
 		}
 	}
 }
@@ -709,6 +717,18 @@ where
 			origin,
 		);
 
+		// The state of the parent is only known to be available when the block is executed, so
+		// the bodies of blocks imported without execution, e.g. by warp or gap sync, are not
+		// retained.
+		let body_retained = match (&body, &storage_changes) {
+			(Some(body), Some(_)) if self.config.retain_block_bodies =>
+				self.retains_body(parent_hash, body).unwrap_or_else(|e| {
+					warn!("Failed to check whether to retain the body of block {hash:?}: {e}");
+					false
+				}),
+			_ => false,
+		};
+
 		operation.op.set_block_data(
 			import_headers.post().clone(),
 			body,
@@ -716,6 +736,7 @@ where
 			justifications,
 			leaf_state,
 		)?;
+		operation.op.set_body_retained(body_retained);
 
 		operation.op.insert_aux(aux)?;
 
@@ -866,6 +887,26 @@ where
 		Ok(PrepareStorageChangesResult::Import(storage_changes))
 	}
 
+	/// Ask the runtime whether to keep the body of the block built on top of `parent_hash` when
+	/// the block is pruned.
+	fn retains_body(
+		&self,
+		parent_hash: Block::Hash,
+		body: &[Block::Extrinsic],
+	) -> sp_blockchain::Result<bool>
+	where
+		Self: ProvideRuntimeApi<Block>,
+		<Self as ProvideRuntimeApi<Block>>::Api: ApiExt<Block>,
+	{
+		if !self.runtime_api().has_api::<dyn BlockRetentionApi<Block>>(parent_hash)? {
+			return Ok(false)
+		}
+		const METHOD: &str = "BlockRetentionApi_retain_body";
+		let result =
+			self.executor.call(parent_hash, METHOD, &body.encode(), CallContext::Offchain)?;
+		bool::decode(&mut &result[..]).map_err(|e| Error::CallResultDecode(METHOD, e))
+	}
+
 	fn apply_finality_with_block_hash(
 		&self,
 		operation: &mut ClientImportOperation<Block, B>,
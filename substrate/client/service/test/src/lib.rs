@@ -237,6 +237,7 @@ fn node_config<E: ChainSpecExtension + Clone + 'static + Send + Sync>(
 		trie_cache_maximum_size: Some(16 * 1024 * 1024),
 		state_pruning: Default::default(),
 		blocks_pruning: BlocksPruning::KeepFinalized,
+		blocks_retention: Default::default(),
 		chain_spec: Box::new((*spec).clone()),
 		executor: ExecutorConfiguration::default(),
 		wasm_runtime_overrides: Default::default(),
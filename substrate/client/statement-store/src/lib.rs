@@ -1008,6 +1008,7 @@ mod tests {
 				finalized_state: None,
 				number_leaves: 0,
 				block_gap: None,
+				pruned_blocks: None,
 			}
 		}
 		fn status(&self, _hash: Hash) -> sp_blockchain::Result<sp_blockchain::BlockStatus> {
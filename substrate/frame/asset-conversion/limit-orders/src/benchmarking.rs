@@ -0,0 +1,220 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Asset Conversion Limit Orders pallet benchmarking.
+
+use super::*;
+use crate::Pallet as LimitOrders;
+use frame_benchmarking::{account, v2::*, whitelisted_caller, BenchmarkError};
+use frame_support::{
+	assert_ok,
+	traits::{fungible, fungibles::Mutate},
+};
+use frame_system::{Pallet as System, RawOrigin};
+use sp_runtime::traits::One;
+
+/// Benchmark Helper
+pub trait BenchmarkHelper<AssetKind, Balance> {
+	/// Returns two distinct assets to trade.
+	///
+	/// If the assets do not exist, they are created by the helper.
+	fn asset_pair() -> (AssetKind, AssetKind);
+	/// Creates the pool of `asset1` and `asset2` and provides it with `amount` of each asset.
+	fn setup_pool(asset1: AssetKind, asset2: AssetKind, amount: Balance);
+}
+
+/// An amount of `asset` comfortably above its minimum balance.
+fn unit<T: Config>(asset: &T::AssetKind) -> T::Balance {
+	T::Assets::minimum_balance(asset.clone())
+		.max(T::Balance::one())
+		.saturating_mul(1000u32.into())
+}
+
+fn funded_account<T: Config>(
+	name: &'static str,
+	index: u32,
+	asset: &T::AssetKind,
+	amount: T::Balance,
+) -> T::AccountId
+where
+	T::Assets: Mutate<T::AccountId>,
+	T::Currency: fungible::Mutate<T::AccountId>,
+{
+	let who = account(name, index, 0);
+	fund_deposit::<T>(&who);
+	assert_ok!(T::Assets::mint_into(asset.clone(), &who, amount.saturating_mul(2u32.into())));
+	who
+}
+
+/// Funds `who` with enough of the native currency to open an order.
+fn fund_deposit<T: Config>(who: &T::AccountId)
+where
+	T::Currency: fungible::Mutate<T::AccountId>,
+{
+	let deposit = <T::Currency as fungible::Inspect<_>>::minimum_balance()
+		.saturating_add(T::OrderDeposit::get())
+		.saturating_mul(2u32.into());
+	assert_ok!(<T::Currency as fungible::Mutate<_>>::mint_into(who, deposit));
+}
+
+/// Opens `count` orders selling `amount` of `asset_in` for twice as much `asset_out`.
+fn open_orders<T: Config>(
+	count: u32,
+	asset_in: &T::AssetKind,
+	asset_out: &T::AssetKind,
+	amount: T::Balance,
+) where
+	T::Assets: Mutate<T::AccountId>,
+	T::Currency: fungible::Mutate<T::AccountId>,
+{
+	for i in 0..count {
+		let owner = funded_account::<T>("seller", i, asset_in, amount);
+		assert_ok!(LimitOrders::<T>::place_order(
+			RawOrigin::Signed(owner).into(),
+			Box::new(asset_in.clone()),
+			Box::new(asset_out.clone()),
+			amount,
+			amount.saturating_mul(2u32.into()),
+			T::MaxOrderLifetime::get(),
+		));
+	}
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	System::<T>::assert_last_event(generic_event.into());
+}
+
+#[benchmarks(where T::Assets: Mutate<T::AccountId>, T::Currency: fungible::Mutate<T::AccountId>)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn place_order(n: Linear<0, { T::MaxFills::get() }>) -> Result<(), BenchmarkError> {
+		let (asset_in, asset_out) = T::BenchmarkHelper::asset_pair();
+		let amount = unit::<T>(&asset_in).max(unit::<T>(&asset_out));
+		// the pool is quoted before every match, but pays less than the makers
+		T::BenchmarkHelper::setup_pool(
+			asset_in.clone(),
+			asset_out.clone(),
+			amount.saturating_mul(1000u32.into()),
+		);
+		open_orders::<T>(T::MaxOrdersPerBook::get() - 1, &asset_in, &asset_out, amount);
+		for i in 0..n {
+			let maker = funded_account::<T>("maker", i, &asset_out, amount);
+			assert_ok!(LimitOrders::<T>::place_order(
+				RawOrigin::Signed(maker).into(),
+				Box::new(asset_out.clone()),
+				Box::new(asset_in.clone()),
+				amount,
+				amount,
+				T::MaxOrderLifetime::get(),
+			));
+		}
+		// fills all the makers and leaves `amount` open
+		let amount_in = amount.saturating_mul((n + 1).into());
+		let caller: T::AccountId = whitelisted_caller();
+		fund_deposit::<T>(&caller);
+		assert_ok!(T::Assets::mint_into(
+			asset_in.clone(),
+			&caller,
+			amount_in.saturating_mul(2u32.into())
+		));
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			Box::new(asset_in.clone()),
+			Box::new(asset_out.clone()),
+			amount_in,
+			amount_in,
+			T::MaxOrderLifetime::get(),
+		);
+
+		assert_eq!(
+			OrderBooks::<T>::get((asset_in, asset_out)).len() as u32,
+			T::MaxOrdersPerBook::get()
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn route_order() -> Result<(), BenchmarkError> {
+		let (asset_in, asset_out) = T::BenchmarkHelper::asset_pair();
+		let amount = unit::<T>(&asset_in).max(unit::<T>(&asset_out));
+		T::BenchmarkHelper::setup_pool(
+			asset_in.clone(),
+			asset_out.clone(),
+			amount.saturating_mul(1000u32.into()),
+		);
+		let caller = funded_account::<T>("caller", 0, &asset_in, amount);
+		assert_ok!(T::Assets::hold(
+			asset_in.clone(),
+			&HoldReason::OpenOrder.into(),
+			&caller,
+			amount
+		));
+		let mut order = Order {
+			owner: caller,
+			asset_in,
+			asset_out,
+			amount_in: amount,
+			amount_out: One::one(),
+			remaining_in: amount,
+			expiry: T::MaxOrderLifetime::get(),
+			deposit: Zero::zero(),
+		};
+
+		#[block]
+		{
+			assert_ok!(LimitOrders::<T>::route_remainder(0, &mut order));
+		}
+
+		assert!(order.remaining_in.is_zero());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_order() -> Result<(), BenchmarkError> {
+		let (asset_in, asset_out) = T::BenchmarkHelper::asset_pair();
+		let amount = unit::<T>(&asset_in).max(unit::<T>(&asset_out));
+		open_orders::<T>(T::MaxOrdersPerBook::get(), &asset_in, &asset_out, amount);
+		let order_id = NextOrderId::<T>::get() - 1;
+		let owner = Orders::<T>::get(order_id).unwrap().owner;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(owner), order_id);
+
+		assert_last_event::<T>(Event::OrderCancelled { order_id, unfilled: amount }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn expire_order() -> Result<(), BenchmarkError> {
+		let (asset_in, asset_out) = T::BenchmarkHelper::asset_pair();
+		let amount = unit::<T>(&asset_in).max(unit::<T>(&asset_out));
+		open_orders::<T>(T::MaxOrdersPerBook::get(), &asset_in, &asset_out, amount);
+		let order_id = NextOrderId::<T>::get() - 1;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, order_id);
+
+		assert_last_event::<T>(Event::OrderExpired { order_id, unfilled: amount }.into());
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(LimitOrders, crate::mock::new_test_ext(), crate::mock::Test);
+}
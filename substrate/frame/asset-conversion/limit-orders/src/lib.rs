@@ -0,0 +1,809 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Asset Conversion Limit Orders
+//!
+//! An on-chain order book for `fungibles` assets, settling against the Asset Conversion pools.
+//!
+//! ## Overview
+//!
+//! An order offers an amount of one asset for at least an amount of another one, which sets its
+//! limit price. The offered amount is put on hold until the order is filled, cancelled or expires.
+//!
+//! A new order is first matched against the open orders on the opposite side of the book, best
+//! price first and oldest first within a price. Each match is settled at the price of the open
+//! order, moving the held funds of both sides to their counterparties. Orders may be filled
+//! partially, by several matches over time.
+//!
+//! Should the pool of the pair, via [`Config::Swap`], pay more than the next open order for the
+//! remainder of a new order, the remainder is swapped through the pool instead. Whatever remains of
+//! a new order after matching is swapped through the pool as well, if the pool pays at least the
+//! limit price for all of it. Otherwise the remainder stays open in the book. An open order that
+//! can't be settled, for instance because its funds are frozen, is skipped.
+//!
+//! Opening an order in the book holds [`Config::OrderDeposit`] of the native currency of its
+//! owner, until the order is closed. A full book evicts its worst-priced order for a new order
+//! offering a better price.
+//!
+//! Open orders expire after their lifetime, at which point the unfilled remainder is released.
+//! The expiry is scheduled through [`Config::Scheduler`] when the order is opened.
+//!
+//! The depth of the books can be read through the [`LimitOrdersApi`] runtime API.
+
+#![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+extern crate alloc;
+
+use alloc::{boxed::Box, vec, vec::Vec};
+use codec::{Codec, Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	storage::with_storage_layer,
+	traits::{
+		fungible,
+		fungibles::{Inspect, MutateHold},
+		schedule::{
+			v3::{Named as ScheduleNamed, TaskName},
+			DispatchTime, LOWEST_PRIORITY,
+		},
+		tokens::{Balance, Fortitude, Precision, Restriction},
+		OriginTrait, QueryPreimage, StorePreimage,
+	},
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_asset_conversion::{QuotePrice, Swap};
+use scale_info::TypeInfo;
+use sp_core::U256;
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{Saturating, Zero},
+	ArithmeticError, FixedPointNumber, FixedU128, Rounding, SaturatedConversion,
+};
+
+/// Identifier of an order.
+pub type OrderId = u64;
+
+/// Prefix of the names of the scheduled expiries of orders.
+const LIMIT_ORDERS_ID: [u8; 8] = *b"lmtorder";
+
+/// The balance of the native currency, in which order deposits are held.
+pub type DepositBalanceOf<T> =
+	<<T as Config>::Currency as fungible::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Convenience alias for `Order`.
+pub type OrderOf<T> = Order<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::AssetKind,
+	<T as Config>::Balance,
+	BlockNumberFor<T>,
+	DepositBalanceOf<T>,
+>;
+
+type CallOf<T> = <T as Config>::RuntimeCall;
+type PalletsOriginOf<T> =
+	<<T as frame_system::Config>::RuntimeOrigin as OriginTrait>::PalletsOrigin;
+
+/// An order selling `amount_in` of `asset_in` for at least `amount_out` of `asset_out`.
+#[derive(Clone, Decode, Encode, Debug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub struct Order<AccountId, AssetKind, Balance, BlockNumber, DepositBalance> {
+	/// The account that placed the order, whose offered funds are on hold.
+	pub owner: AccountId,
+	/// The asset offered.
+	pub asset_in: AssetKind,
+	/// The asset wanted in exchange.
+	pub asset_out: AssetKind,
+	/// The amount of `asset_in` initially offered.
+	pub amount_in: Balance,
+	/// The least amount of `asset_out` accepted for the whole of `amount_in`.
+	pub amount_out: Balance,
+	/// The amount of `asset_in` not sold yet.
+	pub remaining_in: Balance,
+	/// The block the order expires at.
+	pub expiry: BlockNumber,
+	/// The deposit held from the owner while the order is open in the book.
+	pub deposit: DepositBalance,
+}
+
+/// The open orders of a book at one price.
+#[derive(Clone, Decode, Encode, Debug, PartialEq, Eq, TypeInfo)]
+pub struct PriceLevel<Balance> {
+	/// The limit price, in units of the wanted asset per unit of the offered asset.
+	pub price: FixedU128,
+	/// The amount of the offered asset still for sale at `price`.
+	pub amount: Balance,
+	/// The number of orders at `price`.
+	pub orders: u32,
+}
+
+sp_api::decl_runtime_apis! {
+	/// This runtime api allows people to query the order books of the limit orders pallet.
+	pub trait LimitOrdersApi<AssetKind, Balance> where
+		AssetKind: Codec,
+		Balance: Codec,
+	{
+		/// Returns up to `max_levels` price levels of the book of orders selling `asset_in` for
+		/// `asset_out`, best price first.
+		fn order_book_depth(
+			asset_in: AssetKind,
+			asset_out: AssetKind,
+			max_levels: u32,
+		) -> Vec<PriceLevel<Balance>>;
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Funds are offered by an open order.
+		#[codec(index = 0)]
+		OpenOrder,
+		/// The storage deposit of an open order.
+		#[codec(index = 1)]
+		OrderDeposit,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The overarching call type, used to schedule the expiry of orders.
+		type RuntimeCall: Parameter + From<Call<Self>>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// Type of asset class, sourced from [`Config::Assets`], utilized to identify the assets
+		/// traded.
+		type AssetKind: Parameter + MaxEncodedLen;
+
+		/// The type in which the assets are measured.
+		type Balance: Balance;
+
+		/// Registry of the assets traded, holding the offered funds of open orders.
+		type Assets: Inspect<Self::AccountId, AssetId = Self::AssetKind, Balance = Self::Balance>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// The native currency, in which the deposits of open orders are held.
+		type Currency: fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// The deposit held from the owner of an order while it is open in the book.
+		#[pallet::constant]
+		type OrderDeposit: Get<DepositBalanceOf<Self>>;
+
+		/// The pools the remainders of new orders are routed through, usually
+		/// `pallet-asset-conversion`.
+		type Swap: Swap<Self::AccountId, Balance = Self::Balance, AssetKind = Self::AssetKind>
+			+ QuotePrice<Balance = Self::Balance, AssetKind = Self::AssetKind>;
+
+		/// The scheduler expiring open orders.
+		type Scheduler: ScheduleNamed<
+			BlockNumberFor<Self>,
+			CallOf<Self>,
+			PalletsOriginOf<Self>,
+			Hasher = Self::Hashing,
+		>;
+
+		/// The preimage provider for the scheduled expiries.
+		type Preimages: QueryPreimage<H = Self::Hashing> + StorePreimage;
+
+		/// The maximum number of open orders in a book, that is for one asset pair and direction.
+		///
+		/// A new order offering a better price than the worst one of a full book evicts it.
+		#[pallet::constant]
+		type MaxOrdersPerBook: Get<u32>;
+
+		/// The maximum number of open orders a new order is matched against, including the ones
+		/// skipped because they can't be settled.
+		#[pallet::constant]
+		type MaxFills: Get<u32>;
+
+		/// The maximum number of blocks an order stays open for.
+		#[pallet::constant]
+		type MaxOrderLifetime: Get<BlockNumberFor<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Helper for benchmarking.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: benchmarking::BenchmarkHelper<Self::AssetKind, Self::Balance>;
+	}
+
+	/// Open orders.
+	#[pallet::storage]
+	pub type Orders<T: Config> = StorageMap<_, Blake2_128Concat, OrderId, OrderOf<T>>;
+
+	/// The open orders selling the first asset of the key for the second one, with their limit
+	/// prices, best price first and oldest first within a price.
+	#[pallet::storage]
+	pub type OrderBooks<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::AssetKind, T::AssetKind),
+		BoundedVec<(OrderId, FixedU128), T::MaxOrdersPerBook>,
+		ValueQuery,
+	>;
+
+	/// Stores the [`OrderId`] to use for the next order.
+	///
+	/// Incremented when a new order is placed.
+	#[pallet::storage]
+	pub type NextOrderId<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A new order was placed.
+		OrderPlaced {
+			/// The order.
+			order_id: OrderId,
+			/// The account that placed the order.
+			owner: T::AccountId,
+			/// The asset offered.
+			asset_in: T::AssetKind,
+			/// The asset wanted.
+			asset_out: T::AssetKind,
+			/// The amount offered.
+			amount_in: T::Balance,
+			/// The least amount accepted for the whole of `amount_in`.
+			amount_out: T::Balance,
+			/// The block the order expires at, if not filled before.
+			expiry: BlockNumberFor<T>,
+		},
+		/// A new order was matched against an open one, at the price of the open order.
+		OrdersMatched {
+			/// The open order.
+			maker_order_id: OrderId,
+			/// The new order.
+			taker_order_id: OrderId,
+			/// The amount the open order sold.
+			maker_paid: T::Balance,
+			/// The amount the new order sold.
+			taker_paid: T::Balance,
+		},
+		/// The remainder of a new order was swapped through the pool of its asset pair.
+		OrderRouted {
+			/// The order.
+			order_id: OrderId,
+			/// The amount swapped.
+			amount_in: T::Balance,
+			/// The amount received.
+			amount_out: T::Balance,
+		},
+		/// An order was filled completely.
+		OrderFilled {
+			/// The order.
+			order_id: OrderId,
+		},
+		/// An open order was cancelled by its owner.
+		OrderCancelled {
+			/// The order.
+			order_id: OrderId,
+			/// The unsold amount released.
+			unfilled: T::Balance,
+		},
+		/// An open order expired.
+		OrderExpired {
+			/// The order.
+			order_id: OrderId,
+			/// The unsold amount released.
+			unfilled: T::Balance,
+		},
+		/// An open order was evicted from its full book by a new order offering a better price.
+		OrderEvicted {
+			/// The order.
+			order_id: OrderId,
+			/// The unsold amount released.
+			unfilled: T::Balance,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The offered and wanted assets must differ.
+		SameAsset,
+		/// The offered amount is below the minimum balance of the asset, or the wanted amount is
+		/// zero.
+		AmountTooLow,
+		/// The lifetime is zero or exceeds [`Config::MaxOrderLifetime`].
+		InvalidLifetime,
+		/// The book of the order is full of orders offering the same or a better price.
+		OrderBookFull,
+		/// The order does not exist or is no longer open.
+		UnknownOrder,
+		/// The order was placed by another account.
+		NotOrderOwner,
+		/// The order has not expired yet.
+		NotExpired,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(T::MaxOrdersPerBook::get() > 0, "`MaxOrdersPerBook` must be greater than 0");
+			assert!(T::MaxFills::get() > 0, "`MaxFills` must be greater than 0");
+		}
+	}
+
+	/// Pallet's callable functions.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Place an order selling `amount_in` of `asset_in` for at least `amount_out` of
+		/// `asset_out`.
+		///
+		/// The offered amount is put on hold and matched against the open orders selling
+		/// `asset_out` for `asset_in`, at their prices, unless the pool of the pair pays more
+		/// for the remainder. The remainder is swapped through the pool of the pair if it pays at
+		/// least the limit price for all of it. Otherwise it stays open until filled, cancelled,
+		/// or `lifetime` blocks have passed, and [`Config::OrderDeposit`] is held from the caller
+		/// meanwhile.
+		#[pallet::call_index(0)]
+		#[pallet::weight(
+			T::WeightInfo::place_order(T::MaxFills::get())
+				.saturating_add(T::WeightInfo::route_order())
+				.saturating_add(T::WeightInfo::cancel_order())
+		)]
+		pub fn place_order(
+			origin: OriginFor<T>,
+			asset_in: Box<T::AssetKind>,
+			asset_out: Box<T::AssetKind>,
+			amount_in: T::Balance,
+			amount_out: T::Balance,
+			lifetime: BlockNumberFor<T>,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			ensure!(asset_in != asset_out, Error::<T>::SameAsset);
+			ensure!(
+				amount_in >= T::Assets::minimum_balance(*asset_in.clone()).max(One::one()) &&
+					!amount_out.is_zero(),
+				Error::<T>::AmountTooLow
+			);
+			ensure!(
+				!lifetime.is_zero() && lifetime <= T::MaxOrderLifetime::get(),
+				Error::<T>::InvalidLifetime
+			);
+
+			T::Assets::hold(*asset_in.clone(), &HoldReason::OpenOrder.into(), &owner, amount_in)?;
+
+			let order_id = NextOrderId::<T>::get();
+			NextOrderId::<T>::put(order_id.checked_add(1).ok_or(ArithmeticError::Overflow)?);
+			let expiry = frame_system::Pallet::<T>::block_number().saturating_add(lifetime);
+			let mut order = Order {
+				owner: owner.clone(),
+				asset_in: *asset_in.clone(),
+				asset_out: *asset_out.clone(),
+				amount_in,
+				amount_out,
+				remaining_in: amount_in,
+				expiry,
+				deposit: Zero::zero(),
+			};
+			Self::deposit_event(Event::OrderPlaced {
+				order_id,
+				owner,
+				asset_in: *asset_in,
+				asset_out: *asset_out,
+				amount_in,
+				amount_out,
+				expiry,
+			});
+
+			let (fills, mut routed) = Self::match_order(order_id, &mut order)?;
+			if !routed && !order.remaining_in.is_zero() {
+				Self::route_remainder(order_id, &mut order)?;
+				routed = true;
+			}
+			let mut actual_weight = T::WeightInfo::place_order(fills);
+			if routed {
+				actual_weight.saturating_accrue(T::WeightInfo::route_order());
+			}
+
+			if order.remaining_in.is_zero() {
+				Self::deposit_event(Event::OrderFilled { order_id });
+			} else if Self::open_order(order_id, order)? {
+				actual_weight.saturating_accrue(T::WeightInfo::cancel_order());
+			}
+			Ok(Some(actual_weight).into())
+		}
+
+		/// Cancel an open order of the caller, releasing its unsold amount.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::cancel_order())]
+		pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let order = Orders::<T>::get(order_id).ok_or(Error::<T>::UnknownOrder)?;
+			ensure!(order.owner == who, Error::<T>::NotOrderOwner);
+
+			let unfilled = Self::close_order(order_id, &order)?;
+			Self::deposit_event(Event::OrderCancelled { order_id, unfilled });
+			Ok(())
+		}
+
+		/// Close an expired order, releasing its unsold amount.
+		///
+		/// Dispatched by the scheduler with the root origin when the order expires. Any signed
+		/// origin may dispatch it once the expiry block has been reached.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::expire_order())]
+		pub fn expire_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+			let maybe_who = ensure_signed_or_root(origin)?;
+			let order = Orders::<T>::get(order_id).ok_or(Error::<T>::UnknownOrder)?;
+			if maybe_who.is_some() {
+				ensure!(
+					frame_system::Pallet::<T>::block_number() >= order.expiry,
+					Error::<T>::NotExpired
+				);
+			}
+
+			let unfilled = Self::close_order(order_id, &order)?;
+			Self::deposit_event(Event::OrderExpired { order_id, unfilled });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Matches `taker` against the open orders on the opposite side of its book, at their
+		/// prices, until it is filled, the prices no longer cross or [`Config::MaxFills`] orders
+		/// were matched.
+		///
+		/// Should the pool of the pair pay more than the next open order for the remainder of
+		/// `taker`, the remainder is routed through the pool first. Open orders that can't be
+		/// settled are skipped.
+		///
+		/// Returns the number of open orders matched or skipped, and whether the remainder was
+		/// routed.
+		pub(crate) fn match_order(
+			taker_order_id: OrderId,
+			taker: &mut OrderOf<T>,
+		) -> Result<(u32, bool), DispatchError> {
+			let book_key = (taker.asset_out.clone(), taker.asset_in.clone());
+			let reason: T::RuntimeHoldReason = HoldReason::OpenOrder.into();
+			let mut closed = Vec::new();
+			let mut fills = 0;
+			let mut routed = false;
+
+			for (maker_order_id, _) in OrderBooks::<T>::get(&book_key) {
+				if fills >= T::MaxFills::get() || taker.remaining_in.is_zero() {
+					break
+				}
+				let Some(mut maker) = Orders::<T>::get(maker_order_id) else {
+					closed.push(maker_order_id);
+					continue
+				};
+				if !Self::crosses(taker, &maker) {
+					break
+				}
+				if !routed && Self::pool_pays_more(taker, &maker) {
+					Self::route_remainder(taker_order_id, taker)?;
+					routed = true;
+					if taker.remaining_in.is_zero() {
+						break
+					}
+				}
+
+				// the most the taker can buy at the price of the maker
+				let affordable = Self::mul_div(
+					taker.remaining_in,
+					maker.amount_in,
+					maker.amount_out,
+					Rounding::Down,
+				)
+				.unwrap_or(maker.remaining_in);
+				let maker_paid = affordable.min(maker.remaining_in);
+				let Some(taker_paid) =
+					Self::mul_div(maker_paid, maker.amount_out, maker.amount_in, Rounding::Up)
+				else {
+					break
+				};
+				if maker_paid.is_zero() ||
+					taker_paid > taker.remaining_in ||
+					!Self::within_limit(taker, taker_paid, maker_paid)
+				{
+					break
+				}
+
+				fills += 1;
+				// an open order that can't be settled, e.g. with frozen funds, must not block the
+				// new order
+				let settled = with_storage_layer(|| -> DispatchResult {
+					T::Assets::transfer_on_hold(
+						maker.asset_in.clone(),
+						&reason,
+						&maker.owner,
+						&taker.owner,
+						maker_paid,
+						Precision::Exact,
+						Restriction::Free,
+						Fortitude::Polite,
+					)?;
+					T::Assets::transfer_on_hold(
+						taker.asset_in.clone(),
+						&reason,
+						&taker.owner,
+						&maker.owner,
+						taker_paid,
+						Precision::Exact,
+						Restriction::Free,
+						Fortitude::Polite,
+					)?;
+					Ok(())
+				});
+				if settled.is_err() {
+					continue
+				}
+				taker.remaining_in.saturating_reduce(taker_paid);
+				maker.remaining_in.saturating_reduce(maker_paid);
+				Self::deposit_event(Event::OrdersMatched {
+					maker_order_id,
+					taker_order_id,
+					maker_paid,
+					taker_paid,
+				});
+
+				if maker.remaining_in.is_zero() {
+					Self::release_deposit(&maker);
+					Orders::<T>::remove(maker_order_id);
+					let _ = T::Scheduler::cancel_named(Self::expiry_task_name(maker_order_id));
+					Self::deposit_event(Event::OrderFilled { order_id: maker_order_id });
+					closed.push(maker_order_id);
+				} else {
+					Orders::<T>::insert(maker_order_id, maker);
+				}
+			}
+
+			if !closed.is_empty() {
+				OrderBooks::<T>::mutate(&book_key, |book| {
+					book.retain(|(order_id, _)| !closed.contains(order_id))
+				});
+			}
+			Ok((fills, routed))
+		}
+
+		/// Swaps the remainder of `order` through the pool of its asset pair, if the pool pays at
+		/// least the limit price for all of it.
+		pub(crate) fn route_remainder(order_id: OrderId, order: &mut OrderOf<T>) -> DispatchResult {
+			let amount_in = order.remaining_in;
+			let Some(min_amount_out) =
+				Self::mul_div(amount_in, order.amount_out, order.amount_in, Rounding::Up)
+			else {
+				return Ok(())
+			};
+			let quote = T::Swap::quote_price_exact_tokens_for_tokens(
+				order.asset_in.clone(),
+				order.asset_out.clone(),
+				amount_in,
+				true,
+			);
+			if !quote.is_some_and(|quote| quote >= min_amount_out) {
+				return Ok(())
+			}
+
+			let reason: T::RuntimeHoldReason = HoldReason::OpenOrder.into();
+			T::Assets::release(
+				order.asset_in.clone(),
+				&reason,
+				&order.owner,
+				amount_in,
+				Precision::Exact,
+			)?;
+			match T::Swap::swap_exact_tokens_for_tokens(
+				order.owner.clone(),
+				vec![order.asset_in.clone(), order.asset_out.clone()],
+				amount_in,
+				Some(min_amount_out),
+				order.owner.clone(),
+				true,
+			) {
+				Ok(amount_out) => {
+					order.remaining_in = Zero::zero();
+					Self::deposit_event(Event::OrderRouted { order_id, amount_in, amount_out });
+				},
+				// the swap is atomic, the remainder stays open
+				Err(_) =>
+					T::Assets::hold(order.asset_in.clone(), &reason, &order.owner, amount_in)?,
+			}
+			Ok(())
+		}
+
+		/// Inserts `order` into its book behind the open orders with the same or a better price,
+		/// holds its deposit and schedules its expiry.
+		///
+		/// Should the book be full, its worst-priced order is evicted if `order` offers a better
+		/// price. Returns whether an order was evicted.
+		fn open_order(order_id: OrderId, mut order: OrderOf<T>) -> Result<bool, DispatchError> {
+			let price = Self::price(&order);
+			let book_key = (order.asset_in.clone(), order.asset_out.clone());
+			let book = OrderBooks::<T>::get(&book_key);
+			let evicted = match book.last() {
+				Some((worst_id, worst_price)) if book.is_full() => {
+					ensure!(*worst_price > price, Error::<T>::OrderBookFull);
+					let worst_id = *worst_id;
+					let worst = Orders::<T>::get(worst_id).ok_or(Error::<T>::UnknownOrder)?;
+					let unfilled = Self::close_order(worst_id, &worst)?;
+					Self::deposit_event(Event::OrderEvicted { order_id: worst_id, unfilled });
+					true
+				},
+				_ => false,
+			};
+			OrderBooks::<T>::try_mutate(&book_key, |book| -> DispatchResult {
+				let index = book.iter().position(|(_, open)| *open > price).unwrap_or(book.len());
+				book.try_insert(index, (order_id, price))
+					.map_err(|_| Error::<T>::OrderBookFull.into())
+			})?;
+
+			let deposit = T::OrderDeposit::get();
+			<T::Currency as fungible::MutateHold<_>>::hold(
+				&HoldReason::OrderDeposit.into(),
+				&order.owner,
+				deposit,
+			)?;
+			order.deposit = deposit;
+
+			// should the agenda of the expiry block be full, anyone may expire the order instead
+			let _ = T::Preimages::bound(CallOf::<T>::from(Call::expire_order { order_id }))
+				.and_then(|call| {
+					T::Scheduler::schedule_named(
+						Self::expiry_task_name(order_id),
+						DispatchTime::At(order.expiry),
+						None,
+						LOWEST_PRIORITY,
+						frame_system::RawOrigin::Root.into(),
+						call,
+					)
+				});
+			Orders::<T>::insert(order_id, order);
+			Ok(evicted)
+		}
+
+		/// Removes an open order, releasing its unsold amount and its deposit, and returns the
+		/// unsold amount.
+		fn close_order(order_id: OrderId, order: &OrderOf<T>) -> Result<T::Balance, DispatchError> {
+			let unfilled = T::Assets::release(
+				order.asset_in.clone(),
+				&HoldReason::OpenOrder.into(),
+				&order.owner,
+				order.remaining_in,
+				Precision::BestEffort,
+			)?;
+			Self::release_deposit(order);
+			OrderBooks::<T>::mutate((order.asset_in.clone(), order.asset_out.clone()), |book| {
+				book.retain(|(open, _)| *open != order_id)
+			});
+			Orders::<T>::remove(order_id);
+			// fails when dispatched by the scheduled expiry itself
+			let _ = T::Scheduler::cancel_named(Self::expiry_task_name(order_id));
+			Ok(unfilled)
+		}
+
+		/// Releases the deposit of an open order.
+		fn release_deposit(order: &OrderOf<T>) {
+			if order.deposit.is_zero() {
+				return
+			}
+			let _ = <T::Currency as fungible::MutateHold<_>>::release(
+				&HoldReason::OrderDeposit.into(),
+				&order.owner,
+				order.deposit,
+				Precision::BestEffort,
+			);
+		}
+
+		/// Returns up to `max_levels` price levels of the book of orders selling `asset_in` for
+		/// `asset_out`, best price first.
+		pub fn order_book_depth(
+			asset_in: T::AssetKind,
+			asset_out: T::AssetKind,
+			max_levels: u32,
+		) -> Vec<PriceLevel<T::Balance>> {
+			let mut levels: Vec<PriceLevel<T::Balance>> = Vec::new();
+			for (order_id, price) in OrderBooks::<T>::get((asset_in, asset_out)) {
+				let Some(order) = Orders::<T>::get(order_id) else { continue };
+				match levels.last_mut() {
+					Some(level) if level.price == price => {
+						level.amount.saturating_accrue(order.remaining_in);
+						level.orders.saturating_inc();
+					},
+					_ if levels.len() as u32 >= max_levels => break,
+					_ => levels.push(PriceLevel { price, amount: order.remaining_in, orders: 1 }),
+				}
+			}
+			levels
+		}
+
+		/// The limit price of `order`, in units of `asset_out` per unit of `asset_in`.
+		pub fn price(order: &OrderOf<T>) -> FixedU128 {
+			FixedU128::checked_from_rational(
+				order.amount_out.saturated_into::<u128>(),
+				order.amount_in.saturated_into::<u128>(),
+			)
+			.unwrap_or(FixedU128::from_inner(u128::MAX))
+		}
+
+		/// Whether the pool of the pair pays more for the remainder of `taker` than `maker`, an
+		/// order on the opposite side of the book, does at its price.
+		fn pool_pays_more(taker: &OrderOf<T>, maker: &OrderOf<T>) -> bool {
+			let Some(quote) = T::Swap::quote_price_exact_tokens_for_tokens(
+				taker.asset_in.clone(),
+				taker.asset_out.clone(),
+				taker.remaining_in,
+				true,
+			) else {
+				return false
+			};
+			Self::widen(quote) * Self::widen(maker.amount_out) >
+				Self::widen(taker.remaining_in) * Self::widen(maker.amount_in)
+		}
+
+		/// Whether `taker` and `maker`, an order on the opposite side of the book, accept each
+		/// other's price.
+		fn crosses(taker: &OrderOf<T>, maker: &OrderOf<T>) -> bool {
+			Self::widen(taker.amount_out) * Self::widen(maker.amount_out) <=
+				Self::widen(taker.amount_in) * Self::widen(maker.amount_in)
+		}
+
+		/// Whether receiving `received` for `paid` respects the limit price of `order`.
+		fn within_limit(order: &OrderOf<T>, paid: T::Balance, received: T::Balance) -> bool {
+			Self::widen(received) * Self::widen(order.amount_in) >=
+				Self::widen(paid) * Self::widen(order.amount_out)
+		}
+
+		/// Computes `a * b / c` with the given rounding, returning `None` on overflow or when `c`
+		/// is zero.
+		fn mul_div(
+			a: T::Balance,
+			b: T::Balance,
+			c: T::Balance,
+			rounding: Rounding,
+		) -> Option<T::Balance> {
+			multiply_by_rational_with_rounding(
+				a.saturated_into(),
+				b.saturated_into(),
+				c.saturated_into(),
+				rounding,
+			)
+			.and_then(|result| result.try_into().ok())
+		}
+
+		fn widen(amount: T::Balance) -> U256 {
+			U256::from(amount.saturated_into::<u128>())
+		}
+
+		fn expiry_task_name(order_id: OrderId) -> TaskName {
+			(LIMIT_ORDERS_ID, order_id).using_encoded(sp_io::hashing::blake2_256)
+		}
+	}
+}
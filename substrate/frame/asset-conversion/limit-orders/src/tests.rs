@@ -0,0 +1,499 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{mock::*, *};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungible::{InspectHold as _, NativeOrWithId},
+		fungibles::{Inspect as _, InspectHold, MutateHold},
+		tokens::Precision,
+	},
+};
+use sp_runtime::traits::BadOrigin;
+
+const NATIVE: NativeOrWithId<u32> = NativeOrWithId::Native;
+const ASSET: NativeOrWithId<u32> = NativeOrWithId::WithId(1);
+
+fn events() -> Vec<Event<Test>> {
+	let result = System::events()
+		.into_iter()
+		.map(|r| r.event)
+		.filter_map(|e| if let RuntimeEvent::LimitOrders(inner) = e { Some(inner) } else { None })
+		.collect();
+
+	System::reset_events();
+
+	result
+}
+
+fn place(
+	who: u128,
+	asset_in: NativeOrWithId<u32>,
+	asset_out: NativeOrWithId<u32>,
+	amount_in: u128,
+	amount_out: u128,
+) -> OrderId {
+	let order_id = NextOrderId::<Test>::get();
+	assert_ok!(LimitOrders::place_order(
+		RuntimeOrigin::signed(who),
+		Box::new(asset_in),
+		Box::new(asset_out),
+		amount_in,
+		amount_out,
+		10,
+	));
+	order_id
+}
+
+fn held(who: u128, asset: NativeOrWithId<u32>) -> u128 {
+	NativeAndAssets::balance_on_hold(asset, &HoldReason::OpenOrder.into(), &who)
+}
+
+fn deposit(who: u128) -> u128 {
+	Balances::balance_on_hold(&HoldReason::OrderDeposit.into(), &who)
+}
+
+fn balance(who: u128, asset: NativeOrWithId<u32>) -> u128 {
+	NativeAndAssets::balance(asset, &who)
+}
+
+fn book(asset_in: NativeOrWithId<u32>, asset_out: NativeOrWithId<u32>) -> Vec<OrderId> {
+	OrderBooks::<Test>::get((asset_in, asset_out))
+		.into_iter()
+		.map(|(id, _)| id)
+		.collect()
+}
+
+fn create_pool(liquidity: u128) {
+	assert_ok!(AssetConversion::create_pool(
+		RuntimeOrigin::signed(4),
+		Box::new(NATIVE),
+		Box::new(ASSET),
+	));
+	assert_ok!(AssetConversion::add_liquidity(
+		RuntimeOrigin::signed(4),
+		Box::new(NATIVE),
+		Box::new(ASSET),
+		liquidity,
+		liquidity,
+		1,
+		1,
+		4,
+	));
+}
+
+#[test]
+fn place_order_opens_and_holds() {
+	new_test_ext().execute_with(|| {
+		let order_id = place(1, NATIVE, ASSET, 1_000, 2_000);
+
+		assert_eq!(held(1, NATIVE), 1_000);
+		assert_eq!(deposit(1), 10);
+		assert_eq!(book(NATIVE, ASSET), vec![order_id]);
+		assert_eq!(
+			Orders::<Test>::get(order_id),
+			Some(Order {
+				owner: 1,
+				asset_in: NATIVE,
+				asset_out: ASSET,
+				amount_in: 1_000,
+				amount_out: 2_000,
+				remaining_in: 1_000,
+				expiry: 11,
+				deposit: 10,
+			})
+		);
+		assert_eq!(
+			events(),
+			vec![Event::OrderPlaced {
+				order_id,
+				owner: 1,
+				asset_in: NATIVE,
+				asset_out: ASSET,
+				amount_in: 1_000,
+				amount_out: 2_000,
+				expiry: 11,
+			}]
+		);
+	});
+}
+
+#[test]
+fn place_order_checks_parameters() {
+	new_test_ext().execute_with(|| {
+		let place_order = |asset_out, amount_in, amount_out, lifetime| {
+			LimitOrders::place_order(
+				RuntimeOrigin::signed(1),
+				Box::new(NATIVE),
+				Box::new(asset_out),
+				amount_in,
+				amount_out,
+				lifetime,
+			)
+		};
+		assert_noop!(place_order(NATIVE, 1_000, 1_000, 10), Error::<Test>::SameAsset);
+		// below the existential deposit
+		assert_noop!(place_order(ASSET, 99, 1_000, 10), Error::<Test>::AmountTooLow);
+		assert_noop!(place_order(ASSET, 1_000, 0, 10), Error::<Test>::AmountTooLow);
+		assert_noop!(place_order(ASSET, 1_000, 1_000, 0), Error::<Test>::InvalidLifetime);
+		assert_noop!(place_order(ASSET, 1_000, 1_000, 101), Error::<Test>::InvalidLifetime);
+		assert_noop!(
+			LimitOrders::place_order(
+				RuntimeOrigin::none(),
+				Box::new(NATIVE),
+				Box::new(ASSET),
+				1_000,
+				1_000,
+				10
+			),
+			BadOrigin
+		);
+
+		// the book holds up to `MaxOrdersPerBook` orders
+		for _ in 0..4 {
+			place(1, NATIVE, ASSET, 1_000, 1_000);
+		}
+		assert_noop!(place_order(ASSET, 1_000, 1_000, 10), Error::<Test>::OrderBookFull);
+	});
+}
+
+#[test]
+fn orders_are_matched_at_the_maker_price() {
+	new_test_ext().execute_with(|| {
+		// sells 1_000 of the asset for 2 native each
+		let maker = place(1, ASSET, NATIVE, 1_000, 2_000);
+		// accepts up to 3 native for each unit of the asset
+		let taker = place(2, NATIVE, ASSET, 3_000, 1_000);
+
+		// the maker is filled at its price, the rest of the taker stays open
+		assert_eq!(Orders::<Test>::get(maker), None);
+		assert_eq!(book(ASSET, NATIVE), vec![]);
+		assert_eq!(book(NATIVE, ASSET), vec![taker]);
+		assert_eq!(Orders::<Test>::get(taker).unwrap().remaining_in, 1_000);
+
+		assert_eq!(held(1, ASSET), 0);
+		assert_eq!(balance(1, ASSET), 99_000);
+		assert_eq!(balance(1, NATIVE), 102_000);
+		assert_eq!(held(2, NATIVE), 1_000);
+		assert_eq!(deposit(2), 10);
+		assert_eq!(balance(2, NATIVE), 96_990);
+		assert_eq!(balance(2, ASSET), 101_000);
+
+		let events = events();
+		assert!(events.contains(&Event::OrdersMatched {
+			maker_order_id: maker,
+			taker_order_id: taker,
+			maker_paid: 1_000,
+			taker_paid: 2_000,
+		}));
+		assert!(events.contains(&Event::OrderFilled { order_id: maker }));
+	});
+}
+
+#[test]
+fn orders_are_filled_partially() {
+	new_test_ext().execute_with(|| {
+		let maker = place(1, ASSET, NATIVE, 1_000, 2_000);
+		let taker = place(2, NATIVE, ASSET, 1_000, 400);
+
+		assert_eq!(Orders::<Test>::get(taker), None);
+		assert_eq!(Orders::<Test>::get(maker).unwrap().remaining_in, 500);
+		assert_eq!(book(ASSET, NATIVE), vec![maker]);
+		assert_eq!(held(1, ASSET), 500);
+		assert_eq!(balance(2, ASSET), 100_500);
+
+		// the rest of the maker is filled by a later order
+		let taker = place(3, NATIVE, ASSET, 1_000, 500);
+		assert_eq!(Orders::<Test>::get(maker), None);
+		assert_eq!(Orders::<Test>::get(taker), None);
+		assert_eq!(held(1, ASSET), 0);
+		assert_eq!(deposit(1), 0);
+		assert_eq!(balance(1, NATIVE), 102_000);
+		assert_eq!(balance(3, ASSET), 100_500);
+	});
+}
+
+#[test]
+fn orders_are_matched_best_price_first() {
+	new_test_ext().execute_with(|| {
+		let expensive = place(1, ASSET, NATIVE, 1_000, 3_000);
+		let cheap = place(2, ASSET, NATIVE, 1_000, 2_000);
+		let cheap_later = place(3, ASSET, NATIVE, 1_000, 2_000);
+		assert_eq!(book(ASSET, NATIVE), vec![cheap, cheap_later, expensive]);
+
+		// orders not crossing the best price stay open
+		let below = place(4, NATIVE, ASSET, 1_000, 1_000);
+		assert_eq!(book(NATIVE, ASSET), vec![below]);
+		assert_eq!(book(ASSET, NATIVE), vec![cheap, cheap_later, expensive]);
+
+		// the taker crosses the cheap orders only
+		place(4, NATIVE, ASSET, 5_000, 2_000);
+		assert_eq!(book(ASSET, NATIVE), vec![expensive]);
+		assert_eq!(balance(2, NATIVE), 102_000);
+		assert_eq!(balance(3, NATIVE), 102_000);
+		assert_eq!(balance(1, NATIVE), 99_990);
+	});
+}
+
+#[test]
+fn matching_is_bounded_by_max_fills() {
+	new_test_ext().execute_with(|| {
+		let makers: Vec<_> = (0..4).map(|_| place(1, ASSET, NATIVE, 1_000, 1_000)).collect();
+
+		let taker = place(2, NATIVE, ASSET, 4_000, 4_000);
+
+		assert_eq!(book(ASSET, NATIVE), vec![makers[3]]);
+		assert_eq!(Orders::<Test>::get(taker).unwrap().remaining_in, 1_000);
+		assert_eq!(book(NATIVE, ASSET), vec![taker]);
+	});
+}
+
+#[test]
+fn remainder_is_routed_through_the_pool_when_better() {
+	new_test_ext().execute_with(|| {
+		create_pool(10_000);
+		System::reset_events();
+
+		// the pool pays about 98 for 100
+		let order_id = place(1, NATIVE, ASSET, 100, 90);
+		assert_eq!(Orders::<Test>::get(order_id), None);
+		assert_eq!(book(NATIVE, ASSET), vec![]);
+		assert_eq!(held(1, NATIVE), 0);
+		let events = events();
+		assert!(events.contains(&Event::OrderRouted { order_id, amount_in: 100, amount_out: 98 }));
+		assert!(events.contains(&Event::OrderFilled { order_id }));
+		assert_eq!(balance(1, ASSET), 100_098);
+
+		// the pool does not pay the limit price
+		let order_id = place(1, NATIVE, ASSET, 100, 100);
+		assert_eq!(book(NATIVE, ASSET), vec![order_id]);
+		assert_eq!(held(1, NATIVE), 100);
+	});
+}
+
+#[test]
+fn remainder_after_matching_is_routed_through_the_pool() {
+	new_test_ext().execute_with(|| {
+		create_pool(10_000);
+		let maker = place(2, ASSET, NATIVE, 100, 100);
+
+		let taker = place(1, NATIVE, ASSET, 200, 180);
+
+		assert_eq!(Orders::<Test>::get(maker), None);
+		assert_eq!(Orders::<Test>::get(taker), None);
+		let events = events();
+		assert!(events.contains(&Event::OrdersMatched {
+			maker_order_id: maker,
+			taker_order_id: taker,
+			maker_paid: 100,
+			taker_paid: 100,
+		}));
+		assert!(events.contains(&Event::OrderRouted {
+			order_id: taker,
+			amount_in: 100,
+			amount_out: 98
+		}));
+	});
+}
+
+#[test]
+fn pool_is_used_when_it_pays_more_than_the_book() {
+	new_test_ext().execute_with(|| {
+		create_pool(10_000);
+		// sells 100 of the asset for 2 native each, the pool pays about 1
+		let maker = place(2, ASSET, NATIVE, 100, 200);
+		System::reset_events();
+
+		let taker = place(1, NATIVE, ASSET, 100, 40);
+
+		assert_eq!(Orders::<Test>::get(taker), None);
+		assert_eq!(Orders::<Test>::get(maker).unwrap().remaining_in, 100);
+		assert_eq!(book(ASSET, NATIVE), vec![maker]);
+		assert_eq!(
+			events(),
+			vec![
+				Event::OrderPlaced {
+					order_id: taker,
+					owner: 1,
+					asset_in: NATIVE,
+					asset_out: ASSET,
+					amount_in: 100,
+					amount_out: 40,
+					expiry: 11,
+				},
+				Event::OrderRouted { order_id: taker, amount_in: 100, amount_out: 98 },
+				Event::OrderFilled { order_id: taker },
+			]
+		);
+	});
+}
+
+#[test]
+fn orders_that_cannot_be_settled_are_skipped() {
+	new_test_ext().execute_with(|| {
+		let broken = place(1, ASSET, NATIVE, 1_000, 1_000);
+		let maker = place(3, ASSET, NATIVE, 1_000, 1_000);
+		// the funds of the first order are no longer on hold
+		assert_ok!(NativeAndAssets::release(
+			ASSET,
+			&HoldReason::OpenOrder.into(),
+			&1,
+			1_000,
+			Precision::Exact
+		));
+		System::reset_events();
+
+		let taker = place(2, NATIVE, ASSET, 1_000, 1_000);
+
+		assert_eq!(Orders::<Test>::get(taker), None);
+		assert_eq!(Orders::<Test>::get(maker), None);
+		assert_eq!(book(ASSET, NATIVE), vec![broken]);
+		assert_eq!(balance(2, ASSET), 101_000);
+		assert_eq!(balance(3, NATIVE), 101_000);
+		let events = events();
+		assert!(events.contains(&Event::OrdersMatched {
+			maker_order_id: maker,
+			taker_order_id: taker,
+			maker_paid: 1_000,
+			taker_paid: 1_000,
+		}));
+		assert!(!events.iter().any(|event| matches!(
+			event,
+			Event::OrdersMatched { maker_order_id, .. } if *maker_order_id == broken
+		)));
+	});
+}
+
+#[test]
+fn full_book_evicts_its_worst_order() {
+	new_test_ext().execute_with(|| {
+		let best = place(1, NATIVE, ASSET, 1_000, 1_000);
+		let worst = place(3, NATIVE, ASSET, 1_000, 2_000);
+		let others: Vec<_> = (0..2).map(|_| place(1, NATIVE, ASSET, 1_000, 1_000)).collect();
+		assert_eq!(book(NATIVE, ASSET), vec![best, others[0], others[1], worst]);
+		System::reset_events();
+
+		// an order at the worst price does not fit
+		assert_noop!(
+			LimitOrders::place_order(
+				RuntimeOrigin::signed(2),
+				Box::new(NATIVE),
+				Box::new(ASSET),
+				1_000,
+				2_000,
+				10
+			),
+			Error::<Test>::OrderBookFull
+		);
+
+		let order_id = place(2, NATIVE, ASSET, 1_000, 1_500);
+		assert_eq!(book(NATIVE, ASSET), vec![best, others[0], others[1], order_id]);
+		assert_eq!(Orders::<Test>::get(worst), None);
+		assert_eq!(held(3, NATIVE), 0);
+		assert_eq!(deposit(3), 0);
+		assert_eq!(balance(3, NATIVE), 100_000);
+		assert!(events().contains(&Event::OrderEvicted { order_id: worst, unfilled: 1_000 }));
+	});
+}
+
+#[test]
+fn cancel_order_works() {
+	new_test_ext().execute_with(|| {
+		let order_id = place(1, NATIVE, ASSET, 1_000, 2_000);
+		System::reset_events();
+
+		assert_noop!(
+			LimitOrders::cancel_order(RuntimeOrigin::signed(2), order_id),
+			Error::<Test>::NotOrderOwner
+		);
+		assert_noop!(
+			LimitOrders::cancel_order(RuntimeOrigin::signed(1), order_id + 1),
+			Error::<Test>::UnknownOrder
+		);
+
+		assert_ok!(LimitOrders::cancel_order(RuntimeOrigin::signed(1), order_id));
+		assert_eq!(Orders::<Test>::get(order_id), None);
+		assert_eq!(book(NATIVE, ASSET), vec![]);
+		assert_eq!(held(1, NATIVE), 0);
+		assert_eq!(deposit(1), 0);
+		assert_eq!(balance(1, NATIVE), 100_000);
+		assert_eq!(events(), vec![Event::OrderCancelled { order_id, unfilled: 1_000 }]);
+
+		// the expiry was unscheduled
+		System::run_to_block::<AllPalletsWithSystem>(12);
+		assert_eq!(events(), vec![]);
+	});
+}
+
+#[test]
+fn orders_expire_through_the_scheduler() {
+	new_test_ext().execute_with(|| {
+		let order_id = place(1, NATIVE, ASSET, 1_000, 2_000);
+		place(2, ASSET, NATIVE, 500, 250);
+		assert_eq!(Orders::<Test>::get(order_id).unwrap().remaining_in, 750);
+		System::reset_events();
+
+		System::run_to_block::<AllPalletsWithSystem>(10);
+		assert!(Orders::<Test>::get(order_id).is_some());
+
+		System::run_to_block::<AllPalletsWithSystem>(11);
+		assert_eq!(Orders::<Test>::get(order_id), None);
+		assert_eq!(book(NATIVE, ASSET), vec![]);
+		assert_eq!(held(1, NATIVE), 0);
+		assert_eq!(events(), vec![Event::OrderExpired { order_id, unfilled: 750 }]);
+	});
+}
+
+#[test]
+fn anyone_may_expire_an_expired_order() {
+	new_test_ext().execute_with(|| {
+		let order_id = place(1, NATIVE, ASSET, 1_000, 2_000);
+
+		assert_noop!(
+			LimitOrders::expire_order(RuntimeOrigin::signed(2), order_id),
+			Error::<Test>::NotExpired
+		);
+
+		System::set_block_number(11);
+		assert_ok!(LimitOrders::expire_order(RuntimeOrigin::signed(2), order_id));
+		assert_eq!(Orders::<Test>::get(order_id), None);
+		assert_eq!(held(1, NATIVE), 0);
+	});
+}
+
+#[test]
+fn order_book_depth_aggregates_price_levels() {
+	new_test_ext().execute_with(|| {
+		place(1, ASSET, NATIVE, 1_000, 2_000);
+		place(2, ASSET, NATIVE, 500, 1_000);
+		place(3, ASSET, NATIVE, 1_000, 3_000);
+
+		assert_eq!(
+			LimitOrders::order_book_depth(ASSET, NATIVE, 10),
+			vec![
+				PriceLevel { price: FixedU128::from_u32(2), amount: 1_500, orders: 2 },
+				PriceLevel { price: FixedU128::from_u32(3), amount: 1_000, orders: 1 },
+			]
+		);
+		assert_eq!(
+			LimitOrders::order_book_depth(ASSET, NATIVE, 1),
+			vec![PriceLevel { price: FixedU128::from_u32(2), amount: 1_500, orders: 2 }]
+		);
+		assert_eq!(LimitOrders::order_book_depth(NATIVE, ASSET, 10), vec![]);
+	});
+}
@@ -166,6 +166,21 @@ where
 	(lp_token, liquidity1, liquidity2)
 }
 
+/// Moves on by a price observation period, so that the next update of the price oracle of a pool
+/// records an observation, the worst case of the update.
+fn skip_price_observation_period<T: Config>() {
+	let now = frame_system::Pallet::<T>::block_number();
+	frame_system::Pallet::<T>::set_block_number(now + T::PriceObservationPeriod::get());
+}
+
+/// Checks that the price oracle of the pool of `asset1` and `asset2` recorded an observation in
+/// the current block.
+fn assert_price_observed<T: Config>(asset1: &T::AssetKind, asset2: &T::AssetKind) {
+	let pool_id = T::PoolLocator::pool_id(asset1, asset2).unwrap();
+	let accumulator = PriceAccumulators::<T>::get(&pool_id).unwrap();
+	assert_eq!(accumulator.last_observed, frame_system::Pallet::<T>::block_number());
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -251,12 +266,13 @@ mod benchmarks {
 		));
 		let total_supply =
 			<T::PoolAssets as Inspect<T::AccountId>>::total_issuance(lp_token.clone());
+		skip_price_observation_period::<T>();
 
 		#[extrinsic_call]
 		_(
 			SystemOrigin::Signed(caller.clone()),
-			Box::new(asset1),
-			Box::new(asset2),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
 			remove_lp_amount,
 			T::Balance::zero(),
 			T::Balance::zero(),
@@ -265,6 +281,7 @@ mod benchmarks {
 
 		let new_total_supply = <T::PoolAssets as Inspect<T::AccountId>>::total_issuance(lp_token);
 		assert_eq!(new_total_supply, total_supply - remove_lp_amount);
+		assert_price_observed::<T>(&asset1, &asset2);
 	}
 
 	#[benchmark]
@@ -304,6 +321,9 @@ mod benchmarks {
 			swap_amount + T::Balance::one()
 		));
 		let init_caller_balance = T::Assets::balance(asset_in.clone(), &caller);
+		let asset_out = *path.last().unwrap().clone();
+		let last_hop_in = *path[path.len() - 2].clone();
+		skip_price_observation_period::<T>();
 
 		#[extrinsic_call]
 		_(
@@ -317,6 +337,7 @@ mod benchmarks {
 
 		let actual_balance = T::Assets::balance(asset_in, &caller);
 		assert_eq!(actual_balance, init_caller_balance - swap_amount);
+		assert_price_observed::<T>(&last_hop_in, &asset_out);
 	}
 
 	#[benchmark]
@@ -353,6 +374,8 @@ mod benchmarks {
 		let asset_out = *path.last().unwrap().clone();
 		assert_ok!(T::Assets::mint_into(asset_in, &caller, max_swap_amount));
 		let init_caller_balance = T::Assets::balance(asset_out.clone(), &caller);
+		let last_hop_in = *path[path.len() - 2].clone();
+		skip_price_observation_period::<T>();
 
 		#[extrinsic_call]
 		_(
@@ -364,8 +387,9 @@ mod benchmarks {
 			true,
 		);
 
-		let actual_balance = T::Assets::balance(asset_out, &caller);
+		let actual_balance = T::Assets::balance(asset_out.clone(), &caller);
 		assert_eq!(actual_balance, init_caller_balance + T::Balance::one());
+		assert_price_observed::<T>(&last_hop_in, &asset_out);
 	}
 
 	#[benchmark]
@@ -410,5 +434,81 @@ mod benchmarks {
 		assert_last_event::<T>(Event::Touched { pool_id, who: caller }.into());
 	}
 
+	#[benchmark]
+	fn create_pool_with_curve() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		create_asset::<T>(&caller, &asset1, T::Assets::minimum_balance(asset1.clone()), true);
+		create_asset::<T>(&caller, &asset2, T::Assets::minimum_balance(asset2.clone()), true);
+
+		let lp_token = AssetConversion::<T>::get_next_pool_asset_id();
+		create_fee_asset::<T>(&caller);
+		mint_setup_fee_asset::<T>(&caller, &asset1, &asset2, &lp_token);
+		let curve = PoolCurve::StableSwap { amplification: curve::MAX_AMPLIFICATION };
+
+		#[extrinsic_call]
+		_(
+			SystemOrigin::Signed(caller.clone()),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			curve,
+		);
+
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		assert_last_event::<T>(Event::PoolCurveSet { pool_id, curve }.into());
+	}
+
+	#[benchmark]
+	fn set_pool_curve() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		create_fee_asset::<T>(&caller);
+		create_asset_and_pool::<T>(&caller, &asset1, &asset2);
+		let origin =
+			T::PoolCurveOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let curve = PoolCurve::StableSwap { amplification: curve::MAX_AMPLIFICATION };
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Box::new(asset1.clone()), Box::new(asset2.clone()), curve);
+
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		assert_last_event::<T>(Event::PoolCurveSet { pool_id, curve }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn stable_swap_hop() {
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		PoolCurves::<T>::insert(
+			pool_id,
+			PoolCurve::StableSwap { amplification: curve::MAX_AMPLIFICATION },
+		);
+		// a heavily imbalanced pool takes the most iterations to solve
+		let reserve_in: T::Balance = 1_000u32.into();
+		let reserve_out: T::Balance = 1_000_000_000u32.into();
+		let amount_in: T::Balance = 1_000_000u32.into();
+
+		// a hop solves the invariant for either the amount out or the amount in, solving both
+		// bounds whichever one a swap needs
+		#[block]
+		{
+			let curve = AssetConversion::<T>::get_pool_curve(&asset1, &asset2).unwrap();
+			let amount_out = AssetConversion::<T>::get_curve_amount_out(
+				&curve,
+				&amount_in,
+				&reserve_in,
+				&reserve_out,
+			)
+			.unwrap();
+			let _ = AssetConversion::<T>::get_curve_amount_in(
+				&curve,
+				&amount_out,
+				&reserve_in,
+				&reserve_out,
+			);
+		}
+	}
+
 	impl_benchmark_test_suite!(AssetConversion, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -0,0 +1,423 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pricing curves (invariants) a pool can follow when quoting swaps.
+//!
+//! Every pool is assigned a [`PoolCurve`] when it is created. The curve only decides how much of
+//! one asset a pool gives for the other; adding and removing liquidity stays proportional to the
+//! pool reserves for all curves.
+
+use super::*;
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_arithmetic::traits::Unsigned;
+
+/// The highest amplification coefficient accepted for a [`StableSwap`] pool.
+pub const MAX_AMPLIFICATION: u32 = 1_000_000;
+
+/// The maximum number of Newton iterations used to solve the [`StableSwap`] invariant.
+const MAX_ITERATIONS: u32 = 255;
+
+/// Number of assets in a pool.
+const N_COINS: u32 = 2;
+
+/// Denominator of the per mille liquidity provider fee.
+const FEE_DENOMINATOR: u32 = 1000;
+
+/// Reasons for a curve computation to fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveError {
+	/// One of the pool reserves is zero.
+	ZeroLiquidity,
+	/// The requested amount out is not smaller than the reserve.
+	AmountOutTooHigh,
+	/// An arithmetic overflow happened.
+	Overflow,
+	/// The invariant could not be solved within [`MAX_ITERATIONS`].
+	NotConverged,
+}
+
+/// A pricing function mapping amounts of one pool asset to amounts of the other.
+///
+/// `fee` is the liquidity provider fee in 10ths of a percent, see [`Config::LPFee`].
+pub trait SwapCurve<Balance> {
+	/// Given an input amount and the pool reserves, returns the maximum output amount.
+	fn amount_out(
+		&self,
+		amount_in: Balance,
+		reserve_in: Balance,
+		reserve_out: Balance,
+		fee: u32,
+	) -> Result<Balance, CurveError>;
+
+	/// Given an output amount and the pool reserves, returns the required input amount.
+	fn amount_in(
+		&self,
+		amount_out: Balance,
+		reserve_in: Balance,
+		reserve_out: Balance,
+		fee: u32,
+	) -> Result<Balance, CurveError>;
+
+	/// Values `amount` of the input asset at the current marginal price of the pool, without
+	/// fees and price impact.
+	fn spot_quote(
+		&self,
+		amount: Balance,
+		reserve_in: Balance,
+		reserve_out: Balance,
+	) -> Result<Balance, CurveError>;
+}
+
+/// The invariant followed by a pool.
+#[derive(Clone, Copy, Debug, Decode, Encode, Default, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub enum PoolCurve {
+	/// Uniswap V2 style `x * y = k` invariant.
+	#[default]
+	ConstantProduct,
+	/// Curve style invariant for assets expected to trade close to parity.
+	StableSwap {
+		/// The amplification coefficient, in `1..=MAX_AMPLIFICATION`. The higher it is, the
+		/// flatter the curve around the balanced point.
+		amplification: u32,
+	},
+}
+
+impl PoolCurve {
+	/// Whether the curve parameters are within the accepted bounds.
+	pub fn is_valid(&self) -> bool {
+		match self {
+			PoolCurve::ConstantProduct => true,
+			PoolCurve::StableSwap { amplification } =>
+				(1..=MAX_AMPLIFICATION).contains(amplification),
+		}
+	}
+}
+
+impl<B> SwapCurve<B> for PoolCurve
+where
+	B: Unsigned + Ensure + From<u32> + Copy,
+{
+	fn amount_out(
+		&self,
+		amount_in: B,
+		reserve_in: B,
+		reserve_out: B,
+		fee: u32,
+	) -> Result<B, CurveError> {
+		match self {
+			PoolCurve::ConstantProduct =>
+				ConstantProduct.amount_out(amount_in, reserve_in, reserve_out, fee),
+			PoolCurve::StableSwap { amplification } => StableSwap { amplification: *amplification }
+				.amount_out(amount_in, reserve_in, reserve_out, fee),
+		}
+	}
+
+	fn amount_in(
+		&self,
+		amount_out: B,
+		reserve_in: B,
+		reserve_out: B,
+		fee: u32,
+	) -> Result<B, CurveError> {
+		match self {
+			PoolCurve::ConstantProduct =>
+				ConstantProduct.amount_in(amount_out, reserve_in, reserve_out, fee),
+			PoolCurve::StableSwap { amplification } => StableSwap { amplification: *amplification }
+				.amount_in(amount_out, reserve_in, reserve_out, fee),
+		}
+	}
+
+	fn spot_quote(&self, amount: B, reserve_in: B, reserve_out: B) -> Result<B, CurveError> {
+		match self {
+			PoolCurve::ConstantProduct =>
+				ConstantProduct.spot_quote(amount, reserve_in, reserve_out),
+			PoolCurve::StableSwap { amplification } => StableSwap { amplification: *amplification }
+				.spot_quote(amount, reserve_in, reserve_out),
+		}
+	}
+}
+
+/// The `x * y = k` invariant.
+pub struct ConstantProduct;
+
+impl<B> SwapCurve<B> for ConstantProduct
+where
+	B: Unsigned + Ensure + From<u32> + Copy,
+{
+	fn amount_out(
+		&self,
+		amount_in: B,
+		reserve_in: B,
+		reserve_out: B,
+		fee: u32,
+	) -> Result<B, CurveError> {
+		ensure_liquidity(reserve_in, reserve_out)?;
+
+		let amount_in_with_fee = amount_in
+			.checked_mul(&(B::from(FEE_DENOMINATOR) - B::from(fee)))
+			.ok_or(CurveError::Overflow)?;
+
+		let numerator = amount_in_with_fee.checked_mul(&reserve_out).ok_or(CurveError::Overflow)?;
+
+		let denominator = reserve_in
+			.checked_mul(&B::from(FEE_DENOMINATOR))
+			.ok_or(CurveError::Overflow)?
+			.checked_add(&amount_in_with_fee)
+			.ok_or(CurveError::Overflow)?;
+
+		numerator.checked_div(&denominator).ok_or(CurveError::Overflow)
+	}
+
+	fn amount_in(
+		&self,
+		amount_out: B,
+		reserve_in: B,
+		reserve_out: B,
+		fee: u32,
+	) -> Result<B, CurveError> {
+		ensure_liquidity(reserve_in, reserve_out)?;
+
+		if amount_out >= reserve_out {
+			return Err(CurveError::AmountOutTooHigh)
+		}
+
+		let numerator = reserve_in
+			.checked_mul(&amount_out)
+			.ok_or(CurveError::Overflow)?
+			.checked_mul(&B::from(FEE_DENOMINATOR))
+			.ok_or(CurveError::Overflow)?;
+
+		let denominator = reserve_out
+			.checked_sub(&amount_out)
+			.ok_or(CurveError::Overflow)?
+			.checked_mul(&(B::from(FEE_DENOMINATOR) - B::from(fee)))
+			.ok_or(CurveError::Overflow)?;
+
+		numerator
+			.checked_div(&denominator)
+			.ok_or(CurveError::Overflow)?
+			.checked_add(&One::one())
+			.ok_or(CurveError::Overflow)
+	}
+
+	fn spot_quote(&self, amount: B, reserve_in: B, reserve_out: B) -> Result<B, CurveError> {
+		// (amount * reserve_out) / reserve_in
+		mul_div(amount, reserve_out, reserve_in)
+	}
+}
+
+/// The two asset StableSwap invariant from the Curve whitepaper:
+///
+/// `A * n^n * (x + y) + D = A * D * n^n + D^(n + 1) / (n^n * x * y)`, with `n = 2`.
+///
+/// The liquidity provider fee is taken from the input amount, as for [`ConstantProduct`].
+pub struct StableSwap {
+	/// The amplification coefficient `A`.
+	pub amplification: u32,
+}
+
+impl StableSwap {
+	/// `A * n^n`.
+	fn ann<B: Unsigned + Ensure + From<u32> + Copy>(&self) -> Result<B, CurveError> {
+		B::from(self.amplification)
+			.checked_mul(&B::from(N_COINS * N_COINS))
+			.ok_or(CurveError::Overflow)
+	}
+
+	/// `D^(n + 1) / (n^n * x * y)`.
+	fn d_product<B: Unsigned + Ensure + From<u32> + Copy>(
+		d: B,
+		x: B,
+		y: B,
+	) -> Result<B, CurveError> {
+		let n = B::from(N_COINS);
+		let x_n = x.checked_mul(&n).ok_or(CurveError::Overflow)?;
+		let y_n = y.checked_mul(&n).ok_or(CurveError::Overflow)?;
+		mul_div(mul_div(d, d, x_n)?, d, y_n)
+	}
+
+	/// Solves the invariant for `D` given the reserves `x` and `y`.
+	fn compute_d<B: Unsigned + Ensure + From<u32> + Copy>(
+		&self,
+		x: B,
+		y: B,
+	) -> Result<B, CurveError> {
+		let sum = x.checked_add(&y).ok_or(CurveError::Overflow)?;
+		if sum.is_zero() {
+			return Ok(Zero::zero())
+		}
+
+		let n = B::from(N_COINS);
+		let ann = self.ann::<B>()?;
+		let ann_sum = ann.checked_mul(&sum).ok_or(CurveError::Overflow)?;
+		let mut d = sum;
+		for _ in 0..MAX_ITERATIONS {
+			let d_p = Self::d_product(d, x, y)?;
+			let previous = d;
+			// d = (ann * sum + d_p * n) * d / ((ann - 1) * d + (n + 1) * d_p)
+			let numerator = d_p
+				.checked_mul(&n)
+				.and_then(|v| v.checked_add(&ann_sum))
+				.and_then(|v| v.checked_mul(&d))
+				.ok_or(CurveError::Overflow)?;
+			let denominator = (ann - One::one())
+				.checked_mul(&d)
+				.and_then(|v| d_p.checked_mul(&(n + One::one())).and_then(|w| v.checked_add(&w)))
+				.ok_or(CurveError::Overflow)?;
+			d = numerator.checked_div(&denominator).ok_or(CurveError::Overflow)?;
+			if abs_diff(d, previous) <= One::one() {
+				return Ok(d)
+			}
+		}
+		Err(CurveError::NotConverged)
+	}
+
+	/// Solves the invariant for the reserve of one asset, given `D` and the reserve `x` of the
+	/// other one.
+	fn compute_y<B: Unsigned + Ensure + From<u32> + Copy>(
+		&self,
+		x: B,
+		d: B,
+	) -> Result<B, CurveError> {
+		let n = B::from(N_COINS);
+		let ann = self.ann::<B>()?;
+		// c = D^(n + 1) / (n^n * x * ann)
+		let x_n = x.checked_mul(&n).ok_or(CurveError::Overflow)?;
+		let ann_n = ann.checked_mul(&n).ok_or(CurveError::Overflow)?;
+		let c = mul_div(mul_div(d, d, x_n)?, d, ann_n)?;
+		// b = x + D / ann
+		let b = d
+			.checked_div(&ann)
+			.and_then(|v| v.checked_add(&x))
+			.ok_or(CurveError::Overflow)?;
+
+		let mut y = d;
+		for _ in 0..MAX_ITERATIONS {
+			let previous = y;
+			// y = (y^2 + c) / (2 * y + b - D)
+			let numerator =
+				y.checked_mul(&y).and_then(|v| v.checked_add(&c)).ok_or(CurveError::Overflow)?;
+			let denominator = y
+				.checked_mul(&n)
+				.and_then(|v| v.checked_add(&b))
+				.and_then(|v| v.checked_sub(&d))
+				.ok_or(CurveError::Overflow)?;
+			y = numerator.checked_div(&denominator).ok_or(CurveError::Overflow)?;
+			if abs_diff(y, previous) <= One::one() {
+				return Ok(y)
+			}
+		}
+		Err(CurveError::NotConverged)
+	}
+}
+
+impl<B> SwapCurve<B> for StableSwap
+where
+	B: Unsigned + Ensure + From<u32> + Copy,
+{
+	fn amount_out(
+		&self,
+		amount_in: B,
+		reserve_in: B,
+		reserve_out: B,
+		fee: u32,
+	) -> Result<B, CurveError> {
+		ensure_liquidity(reserve_in, reserve_out)?;
+
+		let amount_in =
+			mul_div(amount_in, B::from(FEE_DENOMINATOR) - B::from(fee), B::from(FEE_DENOMINATOR))?;
+		let d = self.compute_d(reserve_in, reserve_out)?;
+		let new_reserve_in = reserve_in.checked_add(&amount_in).ok_or(CurveError::Overflow)?;
+		let new_reserve_out = self.compute_y(new_reserve_in, d)?;
+
+		// round down in favour of the pool
+		Ok(reserve_out
+			.checked_sub(&new_reserve_out)
+			.and_then(|v| v.checked_sub(&One::one()))
+			.unwrap_or_else(Zero::zero))
+	}
+
+	fn amount_in(
+		&self,
+		amount_out: B,
+		reserve_in: B,
+		reserve_out: B,
+		fee: u32,
+	) -> Result<B, CurveError> {
+		ensure_liquidity(reserve_in, reserve_out)?;
+
+		if amount_out >= reserve_out {
+			return Err(CurveError::AmountOutTooHigh)
+		}
+
+		let d = self.compute_d(reserve_in, reserve_out)?;
+		let new_reserve_out = reserve_out - amount_out;
+		let new_reserve_in = self.compute_y(new_reserve_out, d)?;
+		let amount_in = new_reserve_in
+			.checked_sub(&reserve_in)
+			.and_then(|v| v.checked_add(&One::one()))
+			.ok_or(CurveError::Overflow)?;
+
+		// round up in favour of the pool
+		mul_div(amount_in, B::from(FEE_DENOMINATOR), B::from(FEE_DENOMINATOR) - B::from(fee))?
+			.checked_add(&One::one())
+			.ok_or(CurveError::Overflow)
+	}
+
+	fn spot_quote(&self, amount: B, reserve_in: B, reserve_out: B) -> Result<B, CurveError> {
+		ensure_liquidity(reserve_in, reserve_out)?;
+
+		// The marginal price is the ratio of the partial derivatives of the invariant:
+		// (ann * x + D_P) * y / ((ann * y + D_P) * x), where D_P = D^(n + 1) / (n^n * x * y).
+		let d = self.compute_d(reserve_in, reserve_out)?;
+		let d_p = Self::d_product(d, reserve_in, reserve_out)?;
+		let ann = self.ann::<B>()?;
+		let weight_in = ann
+			.checked_mul(&reserve_in)
+			.and_then(|v| v.checked_add(&d_p))
+			.ok_or(CurveError::Overflow)?;
+		let weight_out = ann
+			.checked_mul(&reserve_out)
+			.and_then(|v| v.checked_add(&d_p))
+			.ok_or(CurveError::Overflow)?;
+
+		mul_div(mul_div(amount, weight_in, weight_out)?, reserve_out, reserve_in)
+	}
+}
+
+fn ensure_liquidity<B: Zero>(reserve_in: B, reserve_out: B) -> Result<(), CurveError> {
+	if reserve_in.is_zero() || reserve_out.is_zero() {
+		return Err(CurveError::ZeroLiquidity)
+	}
+	Ok(())
+}
+
+fn mul_div<B: Unsigned + Ensure + Copy>(a: B, b: B, c: B) -> Result<B, CurveError> {
+	a.checked_mul(&b)
+		.ok_or(CurveError::Overflow)?
+		.checked_div(&c)
+		.ok_or(CurveError::Overflow)
+}
+
+fn abs_diff<B: Unsigned + PartialOrd + Copy>(a: B, b: B) -> B {
+	if a > b {
+		a - b
+	} else {
+		b - a
+	}
+}
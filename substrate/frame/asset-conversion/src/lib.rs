@@ -24,6 +24,9 @@
 //! This pallet allows you to:
 //!
 //!  - [create a liquidity pool](`Pallet::create_pool()`) for 2 assets
+//!  - [create a liquidity pool with a chosen pricing curve](`Pallet::create_pool_with_curve()`),
+//!    e.g. a [StableSwap](`curve::StableSwap`) pool for assets trading close to parity, which
+//!    [`Config::PoolCurveOrigin`] can [change](`Pallet::set_pool_curve()`) later
 //!  - [provide the liquidity](`Pallet::add_liquidity()`) and receive back an LP token
 //!  - [exchange the LP token back to assets](`Pallet::remove_liquidity()`)
 //!  - [swap a specific amount of assets for another](`Pallet::swap_exact_tokens_for_tokens()`) if
@@ -34,6 +37,8 @@
 //!    a runtime call endpoint
 //!  - [query the size of a liquidity pool](`AssetConversionApi::get_reserves`) via a runtime api
 //!    endpoint.
+//!  - [query a time-weighted average price](`AssetConversionApi::get_twap`) via a runtime api
+//!    endpoint, or from other pallets through the [`TwapOracle`] trait.
 //!
 //! The `quote_price_exact_tokens_for_tokens` and `quote_price_tokens_for_exact_tokens` functions
 //! both take a path parameter of the route to take. If you want to swap from native asset to
@@ -56,8 +61,10 @@
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod curve;
 #[cfg(test)]
 mod mock;
+pub mod oracle;
 mod swap;
 #[cfg(test)]
 mod tests;
@@ -65,6 +72,8 @@ mod types;
 pub mod weights;
 #[cfg(feature = "runtime-benchmarks")]
 pub use benchmarking::{BenchmarkHelper, NativeOrWithIdFactory};
+pub use curve::{PoolCurve, SwapCurve};
+pub use oracle::TwapOracle;
 pub use pallet::*;
 pub use swap::*;
 pub use types::*;
@@ -94,7 +103,7 @@ use sp_runtime::{
 		CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Ensure, IntegerSquareRoot, MaybeDisplay,
 		One, TrailingZeroInput, Zero,
 	},
-	DispatchError, Saturating, TokenError, TransactionOutcome,
+	DispatchError, FixedU128, Saturating, TokenError, TransactionOutcome,
 };
 
 #[frame_support::pallet]
@@ -125,7 +134,8 @@ pub mod pallet {
 			+ Unsigned
 			+ From<u32>
 			+ From<Self::Balance>
-			+ TryInto<Self::Balance>;
+			+ TryInto<Self::Balance>
+			+ Copy;
 
 		/// Type of asset class, sourced from [`Config::Assets`], utilized to offer liquidity to a
 		/// pool.
@@ -185,10 +195,24 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxSwapPathLength: Get<u32>;
 
+		/// The minimum number of blocks between two snapshots of the cumulative prices of a pool.
+		#[pallet::constant]
+		type PriceObservationPeriod: Get<BlockNumberFor<Self>>;
+
+		/// The number of snapshots of the cumulative prices kept per pool.
+		///
+		/// Together with [`Config::PriceObservationPeriod`], bounds the longest window
+		/// [`TwapOracle`] can average over.
+		#[pallet::constant]
+		type MaxPriceObservations: Get<u32>;
+
 		/// The pallet's id, used for deriving its sovereign account ID.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
+		/// The origin allowed to change the pricing curve of a pool.
+		type PoolCurveOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
@@ -203,6 +227,34 @@ pub mod pallet {
 	pub type Pools<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::PoolId, PoolInfo<T::PoolAssetId>, OptionQuery>;
 
+	/// The pricing curve of each pool. Pools without an entry follow
+	/// [`PoolCurve::ConstantProduct`].
+	#[pallet::storage]
+	pub type PoolCurves<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, PoolCurve, ValueQuery>;
+
+	/// The cumulative prices of each pool, see [`oracle`].
+	#[pallet::storage]
+	pub type PriceAccumulators<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		oracle::PriceAccumulator<T::AssetKind, T::Balance, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Ring buffer of snapshots of the cumulative prices of each pool, keyed by position.
+	#[pallet::storage]
+	pub type PriceObservations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		Twox64Concat,
+		u32,
+		oracle::PriceObservation<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
 	/// Stores the `PoolAssetId` that is going to be used for the next lp token.
 	/// This gets incremented whenever a new lp pool is created.
 	#[pallet::storage]
@@ -288,6 +340,14 @@ pub mod pallet {
 			/// E.g. (A, amount_in) -> (Dot, amount_out) -> (B, amount_out)
 			path: BalancePath<T>,
 		},
+		/// The pricing curve of a pool has been set, on creation for a curve other than the
+		/// default one.
+		PoolCurveSet {
+			/// The ID of the pool.
+			pool_id: T::PoolId,
+			/// The pricing curve of the pool.
+			curve: PoolCurve,
+		},
 		/// Pool has been touched in order to fulfill operational requirements.
 		Touched {
 			/// The ID of the pool.
@@ -348,6 +408,10 @@ pub mod pallet {
 		IncorrectPoolAssetId,
 		/// The destination account cannot exist with the swapped funds.
 		BelowMinimum,
+		/// The parameters of the pricing curve are out of bounds.
+		InvalidCurve,
+		/// The pricing curve of the pool could not be solved for the requested amounts.
+		CurveNotConverged,
 	}
 
 	#[pallet::hooks]
@@ -357,6 +421,10 @@ pub mod pallet {
 				T::MaxSwapPathLength::get() > 1,
 				"the `MaxSwapPathLength` should be greater than 1",
 			);
+			assert!(
+				T::MaxPriceObservations::get() > 0,
+				"the `MaxPriceObservations` should be greater than 0",
+			);
 		}
 	}
 
@@ -375,51 +443,7 @@ pub mod pallet {
 			asset2: Box<T::AssetKind>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			ensure!(asset1 != asset2, Error::<T>::InvalidAssetPair);
-
-			// prepare pool_id
-			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2)
-				.map_err(|_| Error::<T>::InvalidAssetPair)?;
-			ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
-
-			let pool_account =
-				T::PoolLocator::address(&pool_id).map_err(|_| Error::<T>::InvalidAssetPair)?;
-
-			// pay the setup fee
-			let fee =
-				Self::withdraw(T::PoolSetupFeeAsset::get(), &sender, T::PoolSetupFee::get(), true)?;
-			T::PoolSetupFeeTarget::on_unbalanced(fee);
-
-			if T::Assets::should_touch(*asset1.clone(), &pool_account) {
-				T::Assets::touch(*asset1, &pool_account, &sender)?
-			};
-
-			if T::Assets::should_touch(*asset2.clone(), &pool_account) {
-				T::Assets::touch(*asset2, &pool_account, &sender)?
-			};
-
-			let lp_token = NextPoolAssetId::<T>::get()
-				.or(T::PoolAssetId::initial_value())
-				.ok_or(Error::<T>::IncorrectPoolAssetId)?;
-			let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
-			NextPoolAssetId::<T>::set(Some(next_lp_token_id));
-
-			T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
-			if T::PoolAssets::should_touch(lp_token.clone(), &pool_account) {
-				T::PoolAssets::touch(lp_token.clone(), &pool_account, &sender)?
-			};
-
-			let pool_info = PoolInfo { lp_token: lp_token.clone() };
-			Pools::<T>::insert(pool_id.clone(), pool_info);
-
-			Self::deposit_event(Event::PoolCreated {
-				creator: sender,
-				pool_id,
-				pool_account,
-				lp_token,
-			});
-
-			Ok(())
+			Self::do_create_pool(sender, *asset1, *asset2, PoolCurve::ConstantProduct)
 		}
 
 		/// Provide liquidity into the pool of `asset1` and `asset2`.
@@ -504,8 +528,10 @@ pub mod pallet {
 				Error::<T>::AmountTwoLessThanMinimal
 			);
 
-			T::Assets::transfer(*asset1, &sender, &pool_account, amount1, Preserve)?;
-			T::Assets::transfer(*asset2, &sender, &pool_account, amount2, Preserve)?;
+			T::Assets::transfer(*asset1.clone(), &sender, &pool_account, amount1, Preserve)?;
+			T::Assets::transfer(*asset2.clone(), &sender, &pool_account, amount2, Preserve)?;
+
+			Self::update_price_oracle(&asset1, &asset2);
 
 			let total_supply = T::PoolAssets::total_issuance(pool.lp_token.clone());
 
@@ -607,8 +633,10 @@ pub mod pallet {
 				Polite,
 			)?;
 
-			T::Assets::transfer(*asset1, &pool_account, &withdraw_to, amount1, Expendable)?;
-			T::Assets::transfer(*asset2, &pool_account, &withdraw_to, amount2, Expendable)?;
+			T::Assets::transfer(*asset1.clone(), &pool_account, &withdraw_to, amount1, Expendable)?;
+			T::Assets::transfer(*asset2.clone(), &pool_account, &withdraw_to, amount2, Expendable)?;
+
+			Self::update_price_oracle(&asset1, &asset2);
 
 			Self::deposit_event(Event::LiquidityRemoved {
 				who: sender,
@@ -631,7 +659,12 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_exact_tokens_for_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32))]
+		#[pallet::weight(
+			T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32).saturating_add(
+				T::WeightInfo::stable_swap_hop()
+					.saturating_mul(path.len().saturating_sub(1) as u64),
+			)
+		)]
 		pub fn swap_exact_tokens_for_tokens(
 			origin: OriginFor<T>,
 			path: Vec<Box<T::AssetKind>>,
@@ -639,17 +672,22 @@ pub mod pallet {
 			amount_out_min: T::Balance,
 			send_to: T::AccountId,
 			keep_alive: bool,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
+			let path: Vec<_> = path.into_iter().map(|a| *a).collect();
+			let weight = Self::swap_weight(
+				&path,
+				T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32),
+			);
 			Self::do_swap_exact_tokens_for_tokens(
 				sender,
-				path.into_iter().map(|a| *a).collect(),
+				path,
 				amount_in,
 				Some(amount_out_min),
 				send_to,
 				keep_alive,
 			)?;
-			Ok(())
+			Ok(Some(weight).into())
 		}
 
 		/// Swap any amount of `asset1` to get the exact amount of `asset2`.
@@ -659,7 +697,12 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_tokens_for_exact_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32))]
+		#[pallet::weight(
+			T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32).saturating_add(
+				T::WeightInfo::stable_swap_hop()
+					.saturating_mul(path.len().saturating_sub(1) as u64),
+			)
+		)]
 		pub fn swap_tokens_for_exact_tokens(
 			origin: OriginFor<T>,
 			path: Vec<Box<T::AssetKind>>,
@@ -667,17 +710,22 @@ pub mod pallet {
 			amount_in_max: T::Balance,
 			send_to: T::AccountId,
 			keep_alive: bool,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
+			let path: Vec<_> = path.into_iter().map(|a| *a).collect();
+			let weight = Self::swap_weight(
+				&path,
+				T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32),
+			);
 			Self::do_swap_tokens_for_exact_tokens(
 				sender,
-				path.into_iter().map(|a| *a).collect(),
+				path,
 				amount_out,
 				Some(amount_in_max),
 				send_to,
 				keep_alive,
 			)?;
-			Ok(())
+			Ok(Some(weight).into())
 		}
 
 		/// Touch an existing pool to fulfill prerequisites before providing liquidity, such as
@@ -722,9 +770,124 @@ pub mod pallet {
 			Self::deposit_event(Event::Touched { pool_id, who });
 			Ok(Some(T::WeightInfo::touch(refunds_number)).into())
 		}
+
+		/// Creates an empty liquidity pool that prices swaps along the given `curve`, and an
+		/// associated new `lp_token` asset.
+		///
+		/// Liquidity is added and removed in proportion to the pool reserves, as for pools created
+		/// with [`Pallet::create_pool`], which follow [`PoolCurve::ConstantProduct`].
+		///
+		/// The curve can only be changed later by [`Config::PoolCurveOrigin`], see
+		/// [`Pallet::set_pool_curve`].
+		///
+		/// Emits `PoolCreated` and, for a non-default curve, `PoolCurveSet` events when
+		/// successful.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::create_pool_with_curve())]
+		pub fn create_pool_with_curve(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			curve: PoolCurve,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_create_pool(sender, *asset1, *asset2, curve)
+		}
+
+		/// Changes the pricing curve of the pool of `asset1` and `asset2` to `curve`.
+		///
+		/// The dispatch origin for this call must be [`Config::PoolCurveOrigin`]. The new curve
+		/// prices all swaps from the next one on, against the current reserves of the pool.
+		///
+		/// Emits a `PoolCurveSet` event when successful.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::set_pool_curve())]
+		pub fn set_pool_curve(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			curve: PoolCurve,
+		) -> DispatchResult {
+			T::PoolCurveOrigin::ensure_origin(origin)?;
+			ensure!(curve.is_valid(), Error::<T>::InvalidCurve);
+
+			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2)
+				.map_err(|_| Error::<T>::InvalidAssetPair)?;
+			ensure!(Pools::<T>::contains_key(&pool_id), Error::<T>::PoolNotFound);
+
+			if curve == PoolCurve::default() {
+				PoolCurves::<T>::remove(&pool_id);
+			} else {
+				PoolCurves::<T>::insert(&pool_id, curve);
+			}
+
+			Self::deposit_event(Event::PoolCurveSet { pool_id, curve });
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Creates a pool of `asset1` and `asset2` following `curve`, paying the setup fee from
+		/// `sender`.
+		pub(crate) fn do_create_pool(
+			sender: T::AccountId,
+			asset1: T::AssetKind,
+			asset2: T::AssetKind,
+			curve: PoolCurve,
+		) -> DispatchResult {
+			ensure!(asset1 != asset2, Error::<T>::InvalidAssetPair);
+			ensure!(curve.is_valid(), Error::<T>::InvalidCurve);
+
+			// prepare pool_id
+			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2)
+				.map_err(|_| Error::<T>::InvalidAssetPair)?;
+			ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
+
+			let pool_account =
+				T::PoolLocator::address(&pool_id).map_err(|_| Error::<T>::InvalidAssetPair)?;
+
+			// pay the setup fee
+			let fee =
+				Self::withdraw(T::PoolSetupFeeAsset::get(), &sender, T::PoolSetupFee::get(), true)?;
+			T::PoolSetupFeeTarget::on_unbalanced(fee);
+
+			if T::Assets::should_touch(asset1.clone(), &pool_account) {
+				T::Assets::touch(asset1, &pool_account, &sender)?
+			};
+
+			if T::Assets::should_touch(asset2.clone(), &pool_account) {
+				T::Assets::touch(asset2, &pool_account, &sender)?
+			};
+
+			let lp_token = NextPoolAssetId::<T>::get()
+				.or(T::PoolAssetId::initial_value())
+				.ok_or(Error::<T>::IncorrectPoolAssetId)?;
+			let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
+			NextPoolAssetId::<T>::set(Some(next_lp_token_id));
+
+			T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
+			if T::PoolAssets::should_touch(lp_token.clone(), &pool_account) {
+				T::PoolAssets::touch(lp_token.clone(), &pool_account, &sender)?
+			};
+
+			let pool_info = PoolInfo { lp_token: lp_token.clone() };
+			Pools::<T>::insert(pool_id.clone(), pool_info);
+
+			Self::deposit_event(Event::PoolCreated {
+				creator: sender,
+				pool_id: pool_id.clone(),
+				pool_account,
+				lp_token,
+			});
+
+			if curve != PoolCurve::default() {
+				PoolCurves::<T>::insert(pool_id.clone(), curve);
+				Self::deposit_event(Event::PoolCurveSet { pool_id, curve });
+			}
+
+			Ok(())
+		}
+
 		/// Swap exactly `amount_in` of asset `path[0]` for asset `path[1]`.
 		/// If an `amount_out_min` is specified, it will return an error if it is unable to acquire
 		/// the amount desired.
@@ -999,6 +1162,12 @@ pub mod pallet {
 			T::Assets::resolve(&pool_to, credit_in)
 				.map_err(|c| (c, Error::<T>::BelowMinimum.into()))?;
 
+			for pair in path.windows(2) {
+				if let [(asset1, _), (asset2, _)] = pair {
+					Self::update_price_oracle(asset1, asset2);
+				}
+			}
+
 			Ok(credit_out)
 		}
 
@@ -1024,7 +1193,7 @@ pub mod pallet {
 
 		/// Get the `owner`'s balance of `asset`, which could be the chain's native asset or another
 		/// fungible. Returns a value in the form of an `Balance`.
-		fn get_balance(owner: &T::AccountId, asset: T::AssetKind) -> T::Balance {
+		pub(crate) fn get_balance(owner: &T::AccountId, asset: T::AssetKind) -> T::Balance {
 			T::Assets::reducible_balance(asset, owner, Expendable, Polite)
 		}
 
@@ -1065,8 +1234,10 @@ pub mod pallet {
 					},
 				};
 				let (reserve_in, reserve_out) = Self::get_reserves(asset1.clone(), asset2.clone())?;
+				let curve = Self::get_pool_curve(asset1, &asset2)?;
 				balance_path.push((asset2, amount_in));
-				amount_in = Self::get_amount_in(&amount_in, &reserve_in, &reserve_out)?;
+				amount_in =
+					Self::get_curve_amount_in(&curve, &amount_in, &reserve_in, &reserve_out)?;
 			}
 			balance_path.reverse();
 
@@ -1091,8 +1262,10 @@ pub mod pallet {
 					},
 				};
 				let (reserve_in, reserve_out) = Self::get_reserves(asset1.clone(), asset2.clone())?;
+				let curve = Self::get_pool_curve(&asset1, asset2)?;
 				balance_path.push((asset1, amount_out));
-				amount_out = Self::get_amount_out(&amount_out, &reserve_in, &reserve_out)?;
+				amount_out =
+					Self::get_curve_amount_out(&curve, &amount_out, &reserve_in, &reserve_out)?;
 			}
 			Ok(balance_path)
 		}
@@ -1105,14 +1278,15 @@ pub mod pallet {
 			include_fee: bool,
 		) -> Option<T::Balance> {
 			let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
+			let curve = Self::get_pool_curve(&asset1, &asset2).ok()?;
 
 			let balance1 = Self::get_balance(&pool_account, asset1);
 			let balance2 = Self::get_balance(&pool_account, asset2);
 			if !balance1.is_zero() {
 				if include_fee {
-					Self::get_amount_out(&amount, &balance1, &balance2).ok()
+					Self::get_curve_amount_out(&curve, &amount, &balance1, &balance2).ok()
 				} else {
-					Self::quote(&amount, &balance1, &balance2).ok()
+					Self::get_curve_quote(&curve, &amount, &balance1, &balance2).ok()
 				}
 			} else {
 				None
@@ -1127,14 +1301,15 @@ pub mod pallet {
 			include_fee: bool,
 		) -> Option<T::Balance> {
 			let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
+			let curve = Self::get_pool_curve(&asset1, &asset2).ok()?;
 
 			let balance1 = Self::get_balance(&pool_account, asset1);
 			let balance2 = Self::get_balance(&pool_account, asset2);
 			if !balance1.is_zero() {
 				if include_fee {
-					Self::get_amount_in(&amount, &balance1, &balance2).ok()
+					Self::get_curve_amount_in(&curve, &amount, &balance1, &balance2).ok()
 				} else {
-					Self::quote(&amount, &balance2, &balance1).ok()
+					Self::get_curve_quote(&curve, &amount, &balance2, &balance1).ok()
 				}
 			} else {
 				None
@@ -1182,79 +1357,122 @@ pub mod pallet {
 			result.try_into().map_err(|_| Error::<T>::Overflow)
 		}
 
+		/// Returns the weight of a swap along `path`, given the `swap_weight` of a swap along a
+		/// path of constant product pools of the same length.
+		///
+		/// Every hop through a [`PoolCurve::StableSwap`] pool adds the cost of solving its
+		/// invariant, [`WeightInfo::stable_swap_hop`].
+		pub(crate) fn swap_weight(path: &[T::AssetKind], swap_weight: Weight) -> Weight {
+			let stable_hops = path
+				.windows(2)
+				.filter(|pair| {
+					matches!(
+						Self::get_pool_curve(&pair[0], &pair[1]),
+						Ok(PoolCurve::StableSwap { .. })
+					)
+				})
+				.count();
+			swap_weight
+				.saturating_add(T::WeightInfo::stable_swap_hop().saturating_mul(stable_hops as u64))
+		}
+
+		/// Returns the pricing curve of the pool of `asset1` and `asset2`.
+		pub fn get_pool_curve(
+			asset1: &T::AssetKind,
+			asset2: &T::AssetKind,
+		) -> Result<PoolCurve, Error<T>> {
+			let pool_id = T::PoolLocator::pool_id(asset1, asset2)
+				.map_err(|_| Error::<T>::InvalidAssetPair)?;
+			Ok(PoolCurves::<T>::get(pool_id))
+		}
+
 		/// Calculates amount out.
 		///
 		/// Given an input amount of an asset and pair reserves, returns the maximum output amount
-		/// of the other asset.
+		/// of the other asset for a [`PoolCurve::ConstantProduct`] pool.
 		pub fn get_amount_out(
 			amount_in: &T::Balance,
 			reserve_in: &T::Balance,
 			reserve_out: &T::Balance,
 		) -> Result<T::Balance, Error<T>> {
-			let amount_in = T::HigherPrecisionBalance::from(*amount_in);
-			let reserve_in = T::HigherPrecisionBalance::from(*reserve_in);
-			let reserve_out = T::HigherPrecisionBalance::from(*reserve_out);
-
-			if reserve_in.is_zero() || reserve_out.is_zero() {
-				return Err(Error::<T>::ZeroLiquidity)
-			}
-
-			let amount_in_with_fee = amount_in
-				.checked_mul(&(T::HigherPrecisionBalance::from(1000u32) - (T::LPFee::get().into())))
-				.ok_or(Error::<T>::Overflow)?;
-
-			let numerator =
-				amount_in_with_fee.checked_mul(&reserve_out).ok_or(Error::<T>::Overflow)?;
-
-			let denominator = reserve_in
-				.checked_mul(&1000u32.into())
-				.ok_or(Error::<T>::Overflow)?
-				.checked_add(&amount_in_with_fee)
-				.ok_or(Error::<T>::Overflow)?;
-
-			let result = numerator.checked_div(&denominator).ok_or(Error::<T>::Overflow)?;
-
-			result.try_into().map_err(|_| Error::<T>::Overflow)
+			Self::get_curve_amount_out(
+				&PoolCurve::ConstantProduct,
+				amount_in,
+				reserve_in,
+				reserve_out,
+			)
 		}
 
 		/// Calculates amount in.
 		///
 		/// Given an output amount of an asset and pair reserves, returns a required input amount
-		/// of the other asset.
+		/// of the other asset for a [`PoolCurve::ConstantProduct`] pool.
 		pub fn get_amount_in(
 			amount_out: &T::Balance,
 			reserve_in: &T::Balance,
 			reserve_out: &T::Balance,
 		) -> Result<T::Balance, Error<T>> {
-			let amount_out = T::HigherPrecisionBalance::from(*amount_out);
-			let reserve_in = T::HigherPrecisionBalance::from(*reserve_in);
-			let reserve_out = T::HigherPrecisionBalance::from(*reserve_out);
+			Self::get_curve_amount_in(
+				&PoolCurve::ConstantProduct,
+				amount_out,
+				reserve_in,
+				reserve_out,
+			)
+		}
 
-			if reserve_in.is_zero() || reserve_out.is_zero() {
-				Err(Error::<T>::ZeroLiquidity)?
-			}
+		/// Calculates amount out along `curve`.
+		///
+		/// Given an input amount of an asset and pair reserves, returns the maximum output amount
+		/// of the other asset.
+		pub fn get_curve_amount_out(
+			curve: &PoolCurve,
+			amount_in: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+		) -> Result<T::Balance, Error<T>> {
+			let result = curve.amount_out(
+				T::HigherPrecisionBalance::from(*amount_in),
+				T::HigherPrecisionBalance::from(*reserve_in),
+				T::HigherPrecisionBalance::from(*reserve_out),
+				T::LPFee::get(),
+			)?;
 
-			if amount_out >= reserve_out {
-				Err(Error::<T>::AmountOutTooHigh)?
-			}
+			result.try_into().map_err(|_| Error::<T>::Overflow)
+		}
 
-			let numerator = reserve_in
-				.checked_mul(&amount_out)
-				.ok_or(Error::<T>::Overflow)?
-				.checked_mul(&1000u32.into())
-				.ok_or(Error::<T>::Overflow)?;
+		/// Calculates amount in along `curve`.
+		///
+		/// Given an output amount of an asset and pair reserves, returns a required input amount
+		/// of the other asset.
+		pub fn get_curve_amount_in(
+			curve: &PoolCurve,
+			amount_out: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+		) -> Result<T::Balance, Error<T>> {
+			let result = curve.amount_in(
+				T::HigherPrecisionBalance::from(*amount_out),
+				T::HigherPrecisionBalance::from(*reserve_in),
+				T::HigherPrecisionBalance::from(*reserve_out),
+				T::LPFee::get(),
+			)?;
 
-			let denominator = reserve_out
-				.checked_sub(&amount_out)
-				.ok_or(Error::<T>::Overflow)?
-				.checked_mul(&(T::HigherPrecisionBalance::from(1000u32) - T::LPFee::get().into()))
-				.ok_or(Error::<T>::Overflow)?;
+			result.try_into().map_err(|_| Error::<T>::Overflow)
+		}
 
-			let result = numerator
-				.checked_div(&denominator)
-				.ok_or(Error::<T>::Overflow)?
-				.checked_add(&One::one())
-				.ok_or(Error::<T>::Overflow)?;
+		/// Values `amount` of an asset at the marginal price of a pool following `curve`, without
+		/// fees and price impact.
+		pub fn get_curve_quote(
+			curve: &PoolCurve,
+			amount: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+		) -> Result<T::Balance, Error<T>> {
+			let result = curve.spot_quote(
+				T::HigherPrecisionBalance::from(*amount),
+				T::HigherPrecisionBalance::from(*reserve_in),
+				T::HigherPrecisionBalance::from(*reserve_out),
+			)?;
 
 			result.try_into().map_err(|_| Error::<T>::Overflow)
 		}
@@ -1290,9 +1508,21 @@ pub mod pallet {
 	}
 }
 
+impl<T> From<curve::CurveError> for Error<T> {
+	fn from(error: curve::CurveError) -> Self {
+		match error {
+			curve::CurveError::ZeroLiquidity => Error::<T>::ZeroLiquidity,
+			curve::CurveError::AmountOutTooHigh => Error::<T>::AmountOutTooHigh,
+			curve::CurveError::Overflow => Error::<T>::Overflow,
+			curve::CurveError::NotConverged => Error::<T>::CurveNotConverged,
+		}
+	}
+}
+
 sp_api::decl_runtime_apis! {
 	/// This runtime api allows people to query the size of the liquidity pools
 	/// and quote prices for swaps.
+	#[api_version(2)]
 	pub trait AssetConversionApi<Balance, AssetId>
 	where
 		Balance: frame_support::traits::tokens::Balance + MaybeDisplay,
@@ -1322,6 +1552,11 @@ sp_api::decl_runtime_apis! {
 
 		/// Returns the size of the liquidity pool for the given asset pair.
 		fn get_reserves(asset1: AssetId, asset2: AssetId) -> Option<(Balance, Balance)>;
+
+		/// Returns the time-weighted average price of `asset1` in units of `asset2` over at least
+		/// the last `window` blocks, see [`TwapOracle::twap`].
+		#[api_version(2)]
+		fn get_twap(asset1: AssetId, asset2: AssetId, window: u32) -> Option<FixedU128>;
 	}
 }
 
@@ -0,0 +1,287 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Time-weighted average price oracle.
+//!
+//! Every pool keeps Uniswap V2 style cumulative prices, the sum over blocks of the price of one
+//! asset in units of the other. They are brought up to date after each swap and liquidity change,
+//! using the reserves snapshotted by the previous update rather than the live pool balances, which
+//! anyone can move by transferring assets to the pool account. At most once every
+//! [`Config::PriceObservationPeriod`] blocks, a snapshot of the cumulative prices is recorded in a
+//! ring buffer of [`Config::MaxPriceObservations`] entries. The average price over a window is the
+//! difference between the current cumulative price and the one of the newest snapshot taken at
+//! least `window` blocks ago, divided by the number of blocks in between.
+//!
+//! Moving the average requires holding the pool price off the market for the whole window, which
+//! makes it far more costly to manipulate than the spot price.
+
+use super::*;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Bounded, FixedPointNumber, FixedU128, SaturatedConversion};
+
+/// Provides time-weighted average prices.
+pub trait TwapOracle<AssetKind, BlockNumber> {
+	/// Returns the average price of `base` in units of `quote` over at least the last `window`
+	/// blocks.
+	///
+	/// The averaged window may be longer than requested by up to the observation period of the
+	/// oracle. Returns `None` if there is no pool for the pair or it has no price history that
+	/// old.
+	fn twap(base: &AssetKind, quote: &AssetKind, window: BlockNumber) -> Option<FixedU128>;
+}
+
+/// Cumulative prices of a pool.
+#[derive(Clone, Decode, Encode, Debug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub struct PriceAccumulator<AssetKind, Balance, BlockNumber> {
+	/// The asset `price1_cumulative` is the price of.
+	pub asset1: AssetKind,
+	/// The asset `price2_cumulative` is the price of.
+	pub asset2: AssetKind,
+	/// Sum over blocks of the price of `asset1` in units of `asset2`, as the inner value of a
+	/// [`FixedU128`]. Wraps around on overflow.
+	pub price1_cumulative: u128,
+	/// Sum over blocks of the price of `asset2` in units of `asset1`, as the inner value of a
+	/// [`FixedU128`]. Wraps around on overflow.
+	pub price2_cumulative: u128,
+	/// The reserve of `asset1` held by the pool as of the last update.
+	pub reserve1: Balance,
+	/// The reserve of `asset2` held by the pool as of the last update.
+	pub reserve2: Balance,
+	/// The block the cumulative prices were last brought up to date in.
+	pub last_updated: BlockNumber,
+	/// The block the newest entry in [`PriceObservations`] was recorded in.
+	pub last_observed: BlockNumber,
+	/// The block the price history was last started afresh in. Entries of [`PriceObservations`]
+	/// recorded before belong to an earlier history and are ignored.
+	pub history_start: BlockNumber,
+	/// The position of the newest entry in [`PriceObservations`].
+	pub observation_index: u32,
+}
+
+/// Snapshot of the cumulative prices of a pool.
+#[derive(Clone, Decode, Encode, Debug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub struct PriceObservation<BlockNumber> {
+	/// The block the snapshot was taken in.
+	pub block: BlockNumber,
+	/// [`PriceAccumulator::price1_cumulative`] as of `block`.
+	pub price1_cumulative: u128,
+	/// [`PriceAccumulator::price2_cumulative`] as of `block`.
+	pub price2_cumulative: u128,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Brings the cumulative prices of the pool of `asset1` and `asset2` up to date, records a
+	/// snapshot of them if the observation period has elapsed, and snapshots the pool reserves
+	/// the next update accumulates over.
+	///
+	/// Must be called after every change of the pool reserves.
+	pub(crate) fn update_price_oracle(asset1: &T::AssetKind, asset2: &T::AssetKind) {
+		let Ok(pool_id) = T::PoolLocator::pool_id(asset1, asset2) else { return };
+		let Ok(pool_account) = T::PoolLocator::address(&pool_id) else { return };
+		let now = frame_system::Pallet::<T>::block_number();
+
+		let Some(mut accumulator) = PriceAccumulators::<T>::get(&pool_id) else {
+			Self::reset_price_oracle(&pool_id, &pool_account, asset1, asset2, now, 0);
+			return
+		};
+
+		if accumulator.last_updated < now {
+			let Some((price1_cumulative, price2_cumulative)) =
+				Self::cumulative_prices_at(&accumulator, now)
+			else {
+				// The pool had no price since the last update, so no average can cover that time.
+				let observation_index =
+					(accumulator.observation_index + 1) % T::MaxPriceObservations::get().max(1);
+				let (asset1, asset2) = (accumulator.asset1, accumulator.asset2);
+				Self::reset_price_oracle(
+					&pool_id,
+					&pool_account,
+					&asset1,
+					&asset2,
+					now,
+					observation_index,
+				);
+				return
+			};
+			accumulator.price1_cumulative = price1_cumulative;
+			accumulator.price2_cumulative = price2_cumulative;
+			accumulator.last_updated = now;
+
+			if now.saturating_sub(accumulator.last_observed) >= T::PriceObservationPeriod::get() {
+				accumulator.last_observed = now;
+				accumulator.observation_index =
+					(accumulator.observation_index + 1) % T::MaxPriceObservations::get().max(1);
+				PriceObservations::<T>::insert(
+					&pool_id,
+					accumulator.observation_index,
+					PriceObservation { block: now, price1_cumulative, price2_cumulative },
+				);
+			}
+		}
+
+		accumulator.reserve1 = Self::get_balance(&pool_account, accumulator.asset1.clone());
+		accumulator.reserve2 = Self::get_balance(&pool_account, accumulator.asset2.clone());
+		PriceAccumulators::<T>::insert(&pool_id, accumulator);
+	}
+
+	/// Starts the price history of a pool afresh as of block `now`, recording its first
+	/// observation at `observation_index`.
+	///
+	/// The observations of the earlier history are left in place rather than cleared, so the cost
+	/// does not depend on the size of the ring buffer. They are overwritten as the new history
+	/// grows, and ignored until then.
+	fn reset_price_oracle(
+		pool_id: &T::PoolId,
+		pool_account: &T::AccountId,
+		asset1: &T::AssetKind,
+		asset2: &T::AssetKind,
+		now: BlockNumberFor<T>,
+		observation_index: u32,
+	) {
+		PriceAccumulators::<T>::insert(
+			pool_id,
+			PriceAccumulator {
+				asset1: asset1.clone(),
+				asset2: asset2.clone(),
+				price1_cumulative: 0,
+				price2_cumulative: 0,
+				reserve1: Self::get_balance(pool_account, asset1.clone()),
+				reserve2: Self::get_balance(pool_account, asset2.clone()),
+				last_updated: now,
+				last_observed: now,
+				history_start: now,
+				observation_index,
+			},
+		);
+		PriceObservations::<T>::insert(
+			pool_id,
+			observation_index,
+			PriceObservation { block: now, price1_cumulative: 0, price2_cumulative: 0 },
+		);
+	}
+
+	/// Returns the average price of `base` in units of `quote` over at least the last `window`
+	/// blocks.
+	///
+	/// See [`TwapOracle::twap`].
+	pub fn get_twap(
+		base: &T::AssetKind,
+		quote: &T::AssetKind,
+		window: BlockNumberFor<T>,
+	) -> Option<FixedU128> {
+		if window.is_zero() {
+			return None
+		}
+		let pool_id = T::PoolLocator::pool_id(base, quote).ok()?;
+		let accumulator = PriceAccumulators::<T>::get(&pool_id)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		let target = now.checked_sub(&window)?;
+
+		let observation = Self::newest_observation_at_or_before(&pool_id, &accumulator, target)?;
+		let elapsed: u128 = now.saturating_sub(observation.block).saturated_into();
+		if elapsed.is_zero() {
+			return None
+		}
+
+		let (price1_cumulative, price2_cumulative) = Self::cumulative_prices_at(&accumulator, now)?;
+		let (cumulative_now, cumulative_then) = if accumulator.asset1 == *base {
+			(price1_cumulative, observation.price1_cumulative)
+		} else {
+			(price2_cumulative, observation.price2_cumulative)
+		};
+
+		Some(FixedU128::from_inner(cumulative_now.wrapping_sub(cumulative_then) / elapsed))
+	}
+
+	/// The cumulative prices of a pool as of block `now`, given that its reserves stayed at the
+	/// snapshot taken by the last update.
+	///
+	/// Returns `None` if the pool had no price since then because one of the reserves was empty.
+	fn cumulative_prices_at(
+		accumulator: &PriceAccumulator<T::AssetKind, T::Balance, BlockNumberFor<T>>,
+		now: BlockNumberFor<T>,
+	) -> Option<(u128, u128)> {
+		let elapsed: u128 = now.saturating_sub(accumulator.last_updated).saturated_into();
+		let (price1_cumulative, price2_cumulative) =
+			(accumulator.price1_cumulative, accumulator.price2_cumulative);
+		if elapsed.is_zero() {
+			return Some((price1_cumulative, price2_cumulative))
+		}
+		if accumulator.reserve1.is_zero() || accumulator.reserve2.is_zero() {
+			return None
+		}
+
+		// Neither reserve is zero, so the division only fails if the price does not fit into a
+		// `FixedU128`, in which case it is capped.
+		let price1 = FixedU128::checked_from_rational(accumulator.reserve2, accumulator.reserve1)
+			.unwrap_or(FixedU128::max_value());
+		let price2 = FixedU128::checked_from_rational(accumulator.reserve1, accumulator.reserve2)
+			.unwrap_or(FixedU128::max_value());
+		Some((
+			price1_cumulative.wrapping_add(price1.into_inner().wrapping_mul(elapsed)),
+			price2_cumulative.wrapping_add(price2.into_inner().wrapping_mul(elapsed)),
+		))
+	}
+
+	/// Binary searches the observation ring buffer of a pool for the newest snapshot taken at or
+	/// before `target`, provided it belongs to the current price history.
+	fn newest_observation_at_or_before(
+		pool_id: &T::PoolId,
+		accumulator: &PriceAccumulator<T::AssetKind, T::Balance, BlockNumberFor<T>>,
+		target: BlockNumberFor<T>,
+	) -> Option<PriceObservation<BlockNumberFor<T>>> {
+		let capacity = T::MaxPriceObservations::get().max(1);
+		let newest = accumulator.observation_index % capacity;
+		// The buffer is full once the slot after the newest one has been written.
+		let (oldest, len) =
+			if PriceObservations::<T>::contains_key(pool_id, (newest + 1) % capacity) {
+				((newest + 1) % capacity, capacity)
+			} else {
+				(0, newest + 1)
+			};
+		let at = |i: u32| PriceObservations::<T>::get(pool_id, (oldest + i) % capacity);
+
+		if at(0)?.block > target {
+			return None
+		}
+		// invariant: the observation at `low` is at or before `target`
+		let (mut low, mut high) = (0u32, len);
+		while high - low > 1 {
+			let mid = low + (high - low) / 2;
+			if at(mid)?.block <= target {
+				low = mid;
+			} else {
+				high = mid;
+			}
+		}
+		// The buffer is written in order, so an observation of an earlier history is older than
+		// every one of the current history.
+		at(low).filter(|observation| observation.block >= accumulator.history_start)
+	}
+}
+
+impl<T: Config> TwapOracle<T::AssetKind, BlockNumberFor<T>> for Pallet<T> {
+	fn twap(
+		base: &T::AssetKind,
+		quote: &T::AssetKind,
+		window: BlockNumberFor<T>,
+	) -> Option<FixedU128> {
+		Self::get_twap(base, quote, window)
+	}
+}
@@ -26,9 +26,10 @@ use frame_support::{
 		fungibles::{Inspect, InspectEnumerable},
 		Get,
 	},
+	weights::Weight,
 };
 use sp_arithmetic::Permill;
-use sp_runtime::{DispatchError, TokenError};
+use sp_runtime::{DispatchError, FixedPointNumber, TokenError};
 
 fn events() -> Vec<Event<Test>> {
 	let result = System::events()
@@ -2493,3 +2494,656 @@ fn swap_credit_invalid_path() {
 		assert_eq!(error, (expected_credit_in, Error::<Test>::InvalidPath.into()));
 	});
 }
+
+#[test]
+fn can_create_pool_with_curve() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+		let pool_id = (token_1.clone(), token_2.clone());
+		let curve = PoolCurve::StableSwap { amplification: 100 };
+
+		create_tokens(user, vec![token_2.clone(), token_3.clone()]);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 1000));
+
+		assert_noop!(
+			AssetConversion::create_pool_with_curve(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				PoolCurve::StableSwap { amplification: 0 },
+			),
+			Error::<Test>::InvalidCurve
+		);
+		assert_noop!(
+			AssetConversion::create_pool_with_curve(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				PoolCurve::StableSwap { amplification: curve::MAX_AMPLIFICATION + 1 },
+			),
+			Error::<Test>::InvalidCurve
+		);
+
+		let lp_token = AssetConversion::get_next_pool_asset_id();
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_2.clone()),
+			Box::new(token_1.clone()),
+			curve,
+		));
+		assert_eq!(pools(), vec![pool_id.clone()]);
+		assert_eq!(PoolCurves::<Test>::get(&pool_id), curve);
+		assert_eq!(AssetConversion::get_pool_curve(&token_2, &token_1), Ok(curve));
+		assert_eq!(
+			events(),
+			[
+				Event::<Test>::PoolCreated {
+					creator: user,
+					pool_id: pool_id.clone(),
+					pool_account: <Test as Config>::PoolLocator::address(&pool_id).unwrap(),
+					lp_token,
+				},
+				Event::<Test>::PoolCurveSet { pool_id, curve },
+			]
+		);
+
+		// the default curve is not stored
+		let pool_id = (token_1.clone(), token_3.clone());
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_3.clone()),
+			PoolCurve::ConstantProduct,
+		));
+		assert!(!PoolCurves::<Test>::contains_key(&pool_id));
+		assert_eq!(
+			AssetConversion::get_pool_curve(&token_1, &token_3),
+			Ok(PoolCurve::ConstantProduct)
+		);
+	});
+}
+
+#[test]
+fn set_pool_curve_works() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+		let curve = PoolCurve::StableSwap { amplification: 100 };
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 1000));
+
+		assert_noop!(
+			AssetConversion::set_pool_curve(
+				RuntimeOrigin::root(),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				curve,
+			),
+			Error::<Test>::PoolNotFound
+		);
+
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			PoolCurve::StableSwap { amplification: 1 },
+		));
+		events();
+
+		assert_noop!(
+			AssetConversion::set_pool_curve(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				curve,
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			AssetConversion::set_pool_curve(
+				RuntimeOrigin::root(),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				PoolCurve::StableSwap { amplification: 0 },
+			),
+			Error::<Test>::InvalidCurve
+		);
+
+		assert_ok!(AssetConversion::set_pool_curve(
+			RuntimeOrigin::root(),
+			Box::new(token_2.clone()),
+			Box::new(token_1.clone()),
+			curve,
+		));
+		assert_eq!(PoolCurves::<Test>::get(&pool_id), curve);
+		assert_eq!(events(), [Event::<Test>::PoolCurveSet { pool_id: pool_id.clone(), curve }]);
+
+		// going back to the default curve clears the entry
+		assert_ok!(AssetConversion::set_pool_curve(
+			RuntimeOrigin::root(),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			PoolCurve::ConstantProduct,
+		));
+		assert!(!PoolCurves::<Test>::contains_key(&pool_id));
+		assert_eq!(
+			events(),
+			[Event::<Test>::PoolCurveSet { pool_id, curve: PoolCurve::ConstantProduct }]
+		);
+	});
+}
+
+#[test]
+fn stable_swap_curve_math() {
+	let stable = PoolCurve::StableSwap { amplification: 100 };
+	let reserve = 1_000_000u128;
+
+	// close to parity on a balanced pool
+	assert_eq!(stable.amount_out(1000u128, reserve, reserve, 0), Ok(999));
+	assert_eq!(stable.amount_in(999u128, reserve, reserve, 0), Ok(1001));
+	assert_eq!(stable.amount_out(1001u128, reserve, reserve, 0), Ok(1000));
+	assert_eq!(stable.spot_quote(1000u128, reserve, reserve), Ok(1000));
+
+	// less price impact than the constant product for the same reserves
+	assert_eq!(stable.amount_out(10_000u128, reserve, reserve, 3), Ok(9969));
+	assert_eq!(PoolCurve::ConstantProduct.amount_out(10_000u128, reserve, reserve, 3), Ok(9871));
+	assert_eq!(stable.amount_in(10_000u128, reserve, reserve, 3), Ok(10032));
+
+	// the marginal price moves away from parity as the pool gets imbalanced, the less the
+	// higher the amplification
+	assert_eq!(stable.spot_quote(1000u128, 2 * reserve, reserve), Ok(995));
+	assert_eq!(
+		PoolCurve::StableSwap { amplification: 1 }.spot_quote(1000u128, 2 * reserve, reserve),
+		Ok(778)
+	);
+	assert_eq!(PoolCurve::ConstantProduct.spot_quote(1000u128, 2 * reserve, reserve), Ok(500));
+
+	assert_eq!(stable.amount_out(1000u128, 0, reserve, 3), Err(curve::CurveError::ZeroLiquidity));
+	assert_eq!(
+		stable.amount_in(reserve, reserve, reserve, 3),
+		Err(curve::CurveError::AmountOutTooHigh)
+	);
+}
+
+#[test]
+fn can_swap_on_stable_pool() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+		let curve = PoolCurve::StableSwap { amplification: 100 };
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			curve,
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 2_000_000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 2_000_000));
+
+		let liquidity = 1_000_000;
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			liquidity,
+			liquidity,
+			1,
+			1,
+			user,
+		));
+
+		let input_amount = 10_000;
+		let expect_receive =
+			AssetConversion::get_curve_amount_out(&curve, &input_amount, &liquidity, &liquidity)
+				.unwrap();
+		assert_eq!(expect_receive, 9969);
+		assert!(
+			expect_receive >
+				AssetConversion::get_amount_out(&input_amount, &liquidity, &liquidity).unwrap()
+		);
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(
+				token_2.clone(),
+				token_1.clone(),
+				input_amount,
+				true,
+			),
+			Some(expect_receive)
+		);
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(
+				token_2.clone(),
+				token_1.clone(),
+				input_amount,
+				false,
+			),
+			Some(input_amount)
+		);
+
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_2.clone(), token_1.clone()],
+			input_amount,
+			1,
+			user,
+			false,
+		));
+
+		let pool_account = <Test as Config>::PoolLocator::address(&pool_id).unwrap();
+		assert_eq!(balance(user, token_1.clone()), 2_000_000 - liquidity + expect_receive + ed);
+		assert_eq!(balance(user, token_2.clone()), 2_000_000 - liquidity - input_amount);
+		assert_eq!(balance(pool_account, token_1.clone()), liquidity - expect_receive);
+		assert_eq!(balance(pool_account, token_2.clone()), liquidity + input_amount);
+
+		// the way back is quoted along the same curve
+		let reserve1 = liquidity - expect_receive;
+		let reserve2 = liquidity + input_amount;
+		let exchange_out = 5_000;
+		let expect_in =
+			AssetConversion::get_curve_amount_in(&curve, &exchange_out, &reserve1, &reserve2)
+				.unwrap();
+		assert_eq!(
+			AssetConversion::quote_price_tokens_for_exact_tokens(
+				token_1.clone(),
+				token_2.clone(),
+				exchange_out,
+				true,
+			),
+			Some(expect_in)
+		);
+
+		assert_ok!(AssetConversion::swap_tokens_for_exact_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1.clone(), token_2.clone()],
+			exchange_out,
+			expect_in,
+			user,
+			true,
+		));
+		assert_eq!(balance(pool_account, token_1.clone()), reserve1 + expect_in);
+		assert_eq!(balance(pool_account, token_2.clone()), reserve2 - exchange_out);
+	});
+}
+
+#[test]
+fn only_stable_swap_hops_are_charged_for_the_invariant() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+
+		create_tokens(user, vec![token_2.clone(), token_3.clone()]);
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			PoolCurve::StableSwap { amplification: 100 },
+		));
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_3.clone())
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 3_000_000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 2_000_000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 3, user, 2_000_000));
+		for token in [token_2.clone(), token_3.clone()] {
+			assert_ok!(AssetConversion::add_liquidity(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token),
+				1_000_000,
+				1_000_000,
+				1,
+				1,
+				user,
+			));
+		}
+
+		let swap = |path: Vec<NativeOrWithId<u32>>| {
+			let path_len = path.len() as u32;
+			let info = AssetConversion::swap_exact_tokens_for_tokens(
+				RuntimeOrigin::signed(user),
+				path.into_iter().map(Box::new).collect(),
+				1_000,
+				1,
+				user,
+				false,
+			)
+			.unwrap();
+			let plain = <() as WeightInfo>::swap_exact_tokens_for_tokens(path_len);
+			info.actual_weight.unwrap().saturating_sub(plain)
+		};
+		let stable_hop = <() as WeightInfo>::stable_swap_hop();
+
+		assert_eq!(swap(vec![token_1.clone(), token_3.clone()]), Weight::zero());
+		assert_eq!(swap(vec![token_2.clone(), token_1.clone()]), stable_hop);
+		assert_eq!(swap(vec![token_2.clone(), token_1.clone(), token_3.clone()]), stable_hop);
+	});
+}
+
+#[test]
+fn swap_credit_on_stable_pool() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let curve = PoolCurve::StableSwap { amplification: 100 };
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			curve,
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 1_000_000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 2_000_000));
+
+		let liquidity = 1_000_000;
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			liquidity,
+			liquidity,
+			1,
+			1,
+			user,
+		));
+
+		let expected_change = NativeAndAssets::issue(token_1.clone(), 100);
+		let expected_credit_out = NativeAndAssets::issue(token_2.clone(), 20_000);
+
+		let amount_in_max = AssetConversion::quote_price_tokens_for_exact_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			expected_credit_out.peek(),
+			true,
+		)
+		.unwrap();
+		assert_eq!(amount_in_max, 20063);
+
+		let credit_in =
+			NativeAndAssets::issue(token_1.clone(), amount_in_max + expected_change.peek());
+		assert_ok!(
+			<AssetConversion as SwapCredit<_>>::swap_tokens_for_exact_tokens(
+				vec![token_1.clone(), token_2.clone()],
+				credit_in,
+				expected_credit_out.peek(),
+			),
+			(expected_credit_out, expected_change)
+		);
+	})
+}
+
+#[test]
+fn twap_oracle_tracks_pool_price() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone())
+		));
+		assert_eq!(AssetConversion::get_twap(&token_1, &token_2, 1), None);
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100_000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100_000));
+
+		let liquidity1 = 10_000;
+		let liquidity2 = 20_000;
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			liquidity1,
+			liquidity2,
+			1,
+			1,
+			user,
+		));
+		let accumulator = PriceAccumulators::<Test>::get(&pool_id).unwrap();
+		assert_eq!(accumulator.asset1, token_1);
+		assert_eq!(accumulator.last_updated, 1);
+
+		System::set_block_number(11);
+		assert_eq!(AssetConversion::get_twap(&token_1, &token_2, 10), Some(FixedU128::from_u32(2)));
+		assert_eq!(
+			AssetConversion::get_twap(&token_2, &token_1, 10),
+			Some(FixedU128::from_rational(1, 2))
+		);
+		assert_eq!(
+			<AssetConversion as TwapOracle<_, _>>::twap(&token_1, &token_2, 5),
+			Some(FixedU128::from_u32(2))
+		);
+		// no price history that old
+		assert_eq!(AssetConversion::get_twap(&token_1, &token_2, 11), None);
+		assert_eq!(AssetConversion::get_twap(&token_1, &token_2, 0), None);
+
+		let input_amount = 10_000;
+		let output_amount =
+			AssetConversion::get_amount_out(&input_amount, &liquidity1, &liquidity2).unwrap();
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1.clone(), token_2.clone()],
+			input_amount,
+			1,
+			user,
+			false,
+		));
+		let accumulator = PriceAccumulators::<Test>::get(&pool_id).unwrap();
+		assert_eq!(accumulator.last_updated, 11);
+		assert_eq!(accumulator.price1_cumulative, FixedU128::from_u32(20).into_inner());
+		assert_eq!(accumulator.observation_index, 1);
+
+		System::set_block_number(21);
+		let new_price =
+			FixedU128::checked_from_rational(liquidity2 - output_amount, liquidity1 + input_amount)
+				.unwrap();
+		assert_eq!(AssetConversion::get_twap(&token_1, &token_2, 10), Some(new_price));
+		assert_eq!(
+			AssetConversion::get_twap(&token_1, &token_2, 20),
+			Some(FixedU128::from_inner(
+				(FixedU128::from_u32(2).into_inner() + new_price.into_inner()) / 2
+			))
+		);
+		// averaged since the newest observation taken at least `window` blocks ago
+		assert_eq!(
+			AssetConversion::get_twap(&token_1, &token_2, 15),
+			AssetConversion::get_twap(&token_1, &token_2, 20)
+		);
+	});
+}
+
+#[test]
+fn twap_oracle_keeps_bounded_history() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone())
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100_000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100_000));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			10_000,
+			10_000,
+			1,
+			1,
+			user,
+		));
+
+		let period: u64 = <Test as Config>::PriceObservationPeriod::get();
+		let capacity: u32 = <Test as Config>::MaxPriceObservations::get();
+		for i in 1..=(capacity as u64 + 2) {
+			System::set_block_number(1 + i * period);
+			assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+				RuntimeOrigin::signed(user),
+				bvec![token_1.clone(), token_2.clone()],
+				100,
+				1,
+				user,
+				false,
+			));
+		}
+		let now = System::block_number();
+		assert_eq!(now, 71);
+		assert_eq!(PriceObservations::<Test>::iter_prefix(&pool_id).count(), capacity as usize);
+		assert_eq!(PriceAccumulators::<Test>::get(&pool_id).unwrap().observation_index, 2);
+
+		// the oldest observation kept was recorded in block 31
+		assert!(AssetConversion::get_twap(&token_1, &token_2, now - 31).is_some());
+		assert_eq!(AssetConversion::get_twap(&token_1, &token_2, now - 30), None);
+	});
+}
+
+#[test]
+fn twap_oracle_ignores_transfers_to_the_pool_account() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+		let pool_account = <Test as Config>::PoolLocator::address(&pool_id).unwrap();
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone())
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100_000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100_000));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			10_000,
+			20_000,
+			1,
+			1,
+			user,
+		));
+		let accumulator = PriceAccumulators::<Test>::get(&pool_id).unwrap();
+		assert_eq!((accumulator.reserve1, accumulator.reserve2), (10_000, 20_000));
+
+		// moving the pool balance outside of a swap or liquidity change does not move the price
+		System::set_block_number(6);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(user), 2, pool_account, 60_000));
+		System::set_block_number(11);
+		assert_eq!(AssetConversion::get_twap(&token_1, &token_2, 10), Some(FixedU128::from_u32(2)));
+
+		// until the next swap snapshots the new reserves
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1.clone(), token_2.clone()],
+			100,
+			1,
+			user,
+			false,
+		));
+		let accumulator = PriceAccumulators::<Test>::get(&pool_id).unwrap();
+		assert_eq!(accumulator.price1_cumulative, FixedU128::from_u32(20).into_inner());
+		assert_eq!(
+			(accumulator.reserve1, accumulator.reserve2),
+			AssetConversion::get_reserves(token_1, token_2).unwrap()
+		);
+	});
+}
+
+#[test]
+fn twap_oracle_restarts_history_when_pool_has_no_price() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone())
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100_000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100_000));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			10_000,
+			20_000,
+			1,
+			1,
+			user,
+		));
+		let swap = || {
+			AssetConversion::swap_exact_tokens_for_tokens(
+				RuntimeOrigin::signed(user),
+				bvec![token_1.clone(), token_2.clone()],
+				100,
+				1,
+				user,
+				false,
+			)
+		};
+		System::set_block_number(11);
+		assert_ok!(swap());
+
+		// the pool had no price as of the last update
+		PriceAccumulators::<Test>::mutate(&pool_id, |accumulator| {
+			accumulator.as_mut().unwrap().reserve1 = 0;
+		});
+		System::set_block_number(21);
+		assert_ok!(swap());
+		let accumulator = PriceAccumulators::<Test>::get(&pool_id).unwrap();
+		assert_eq!(accumulator.history_start, 21);
+		assert_eq!(accumulator.observation_index, 2);
+		assert_eq!(accumulator.price1_cumulative, 0);
+		// the observations of the earlier history are kept, but not averaged over
+		assert_eq!(PriceObservations::<Test>::iter_prefix(&pool_id).count(), 3);
+		System::set_block_number(31);
+		assert!(AssetConversion::get_twap(&token_1, &token_2, 10).is_some());
+		assert_eq!(AssetConversion::get_twap(&token_1, &token_2, 11), None);
+		assert_eq!(AssetConversion::get_twap(&token_1, &token_2, 30), None);
+	});
+}
@@ -0,0 +1,182 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Assets Vesting pallet benchmarking.
+
+use super::*;
+use crate::Pallet as AssetsVesting;
+use frame_benchmarking::{account, v2::*, whitelisted_caller, BenchmarkError};
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+
+/// Benchmark Helper
+pub trait BenchmarkHelper<AssetKind> {
+	/// Returns the asset to vest.
+	///
+	/// If the asset does not exist, it is created by the helper.
+	fn asset() -> AssetKind;
+}
+
+/// An amount of `asset` comfortably above its minimum balance.
+fn unit<T: Config>(asset: &T::AssetKind) -> T::Balance {
+	T::Assets::minimum_balance(asset.clone())
+		.max(One::one())
+		.saturating_mul(1000u32.into())
+}
+
+/// A schedule vesting `locked` over 100 blocks from the current block, after a cliff of 10.
+fn schedule<T: Config>(locked: T::Balance) -> VestingScheduleOf<T> {
+	let now = T::BlockNumberProvider::current_block_number();
+	VestingSchedule::new(locked, now, 10u32.into(), 100u32.into(), One::one())
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32, asset: &T::AssetKind) -> T::AccountId {
+	let who = account(name, index, 0);
+	fund::<T>(&who, asset);
+	who
+}
+
+fn fund<T: Config>(who: &T::AccountId, asset: &T::AssetKind) {
+	let amount = unit::<T>(asset).saturating_mul(100u32.into());
+	assert_ok!(T::Assets::mint_into(asset.clone(), who, amount));
+}
+
+/// Adds `count` schedules of `asset` to `target`.
+fn add_schedules<T: Config>(target: &T::AccountId, asset: &T::AssetKind, count: u32) {
+	let source = funded_account::<T>("source", 0, asset);
+	for _ in 0..count {
+		assert_ok!(AssetsVesting::<T>::do_vested_transfer(
+			&source,
+			target,
+			asset.clone(),
+			schedule::<T>(unit::<T>(asset)),
+		));
+	}
+}
+
+fn advance<T: Config>(blocks: u32) {
+	let now = frame_system::Pallet::<T>::block_number();
+	frame_system::Pallet::<T>::set_block_number(now.saturating_add(blocks.into()));
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn vest(s: Linear<1, { T::MaxVestingSchedules::get() }>) -> Result<(), BenchmarkError> {
+		let asset = T::BenchmarkHelper::asset();
+		let caller: T::AccountId = whitelisted_caller();
+		add_schedules::<T>(&caller, &asset, s);
+		advance::<T>(50);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), Box::new(asset.clone()));
+
+		assert_eq!(Vesting::<T>::get(&caller, &asset).map(|s| s.len() as u32), Some(s));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn vest_other(s: Linear<1, { T::MaxVestingSchedules::get() }>) -> Result<(), BenchmarkError> {
+		let asset = T::BenchmarkHelper::asset();
+		let target: T::AccountId = account("target", 0, 0);
+		add_schedules::<T>(&target, &asset, s);
+		advance::<T>(50);
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), T::Lookup::unlookup(target.clone()), Box::new(asset.clone()));
+
+		assert_eq!(Vesting::<T>::get(&target, &asset).map(|s| s.len() as u32), Some(s));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn vested_transfer(
+		s: Linear<0, { T::MaxVestingSchedules::get() - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		let asset = T::BenchmarkHelper::asset();
+		let target: T::AccountId = account("target", 0, 0);
+		add_schedules::<T>(&target, &asset, s);
+		let origin = T::VestedTransferOrigin::try_successful_origin(&asset)
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let caller = T::VestedTransferOrigin::ensure_origin(origin.clone(), &asset)
+			.map_err(|_| BenchmarkError::Weightless)?;
+		fund::<T>(&caller, &asset);
+		let schedule = schedule::<T>(unit::<T>(&asset));
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			T::Lookup::unlookup(target.clone()),
+			Box::new(asset.clone()),
+			schedule,
+		);
+
+		assert_eq!(Vesting::<T>::get(&target, &asset).map(|s| s.len() as u32), Some(s + 1));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_vested_transfer(
+		s: Linear<0, { T::MaxVestingSchedules::get() - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		let asset = T::BenchmarkHelper::asset();
+		let target: T::AccountId = account("target", 0, 0);
+		add_schedules::<T>(&target, &asset, s);
+		let source = funded_account::<T>("caller", 0, &asset);
+		let schedule = schedule::<T>(unit::<T>(&asset));
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Root,
+			T::Lookup::unlookup(source),
+			T::Lookup::unlookup(target.clone()),
+			Box::new(asset.clone()),
+			schedule,
+		);
+
+		assert_eq!(Vesting::<T>::get(&target, &asset).map(|s| s.len() as u32), Some(s + 1));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_remove_vesting_schedule(
+		s: Linear<1, { T::MaxVestingSchedules::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let asset = T::BenchmarkHelper::asset();
+		let target: T::AccountId = account("target", 0, 0);
+		add_schedules::<T>(&target, &asset, s);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, T::Lookup::unlookup(target.clone()), Box::new(asset.clone()), 0);
+
+		if s == 1 {
+			assert_last_event::<T>(Event::VestingCompleted { account: target, asset }.into());
+		} else {
+			assert_eq!(Vesting::<T>::get(&target, &asset).map(|s| s.len() as u32), Some(s - 1));
+		}
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(AssetsVesting, crate::mock::new_test_ext(), crate::mock::Test);
+}
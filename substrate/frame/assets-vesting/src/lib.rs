@@ -0,0 +1,487 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Assets Vesting Pallet
+//!
+//! Vesting of `fungibles` assets, such as `pallet-assets` tokens.
+//!
+//! ## Overview
+//!
+//! Like `pallet-vesting`, this pallet places a restriction on the balance of an account so that
+//! only the vested part of it can be moved. Rather than a lock on the native currency, the
+//! restriction is a freeze on the asset vested, via [`Config::AssetsFreezer`] (for example
+//! `pallet-assets-freezer`), so any asset of [`Config::Assets`] can vest. An account may vest
+//! several assets at once, each with its own schedules.
+//!
+//! A [`VestingSchedule`] may have a cliff, before which nothing vests, and may vest continuously
+//! or in tranches released every period, for example monthly.
+//!
+//! The frozen amount is not updated automatically as schedules vest. Anyone may update it for an
+//! account with [`Pallet::vest_other`]. The vested and unvested amounts of an account can be read
+//! through the [`AssetsVestingApi`] runtime API.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `vest` - Update the frozen amount of an asset of the sender to its unvested amount.
+//! - `vest_other` - Update the frozen amount of an asset of another account.
+//! - `vested_transfer` - Transfer funds to an account, vesting under a schedule. Only
+//!   [`Config::VestedTransferOrigin`] may create vested transfers of an asset, so that accounts
+//!   can't be filled with dust schedules by anyone.
+//! - `force_vested_transfer` - Transfer funds from one account to another under a schedule.
+//! - `force_remove_vesting_schedule` - Remove a schedule of an account.
+
+#![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+mod schedule;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+extern crate alloc;
+
+use alloc::{boxed::Box, vec::Vec};
+use codec::{Codec, Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{
+	ensure,
+	storage::bounded_vec::BoundedVec,
+	traits::{
+		fungibles::{Inspect, InspectFreeze, Mutate, MutateFreeze},
+		tokens::{Balance, Preservation},
+		EnsureOriginWithArg, Get,
+	},
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{AtLeast32BitUnsigned, BlockNumberProvider, One, Saturating, StaticLookup, Zero},
+	DispatchError, DispatchResult, Rounding, RuntimeDebug, SaturatedConversion,
+};
+
+pub use pallet::*;
+pub use schedule::*;
+pub use weights::WeightInfo;
+
+type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+
+/// Convenience alias for `VestingSchedule`.
+pub type VestingScheduleOf<T> = VestingSchedule<<T as Config>::Balance, BlockNumberFor<T>>;
+
+/// The vesting state of an account for one asset.
+#[derive(Clone, Decode, Encode, Debug, PartialEq, Eq, TypeInfo)]
+pub struct VestingBalance<Balance> {
+	/// The amount that vested so far under the schedules still in effect.
+	pub vested: Balance,
+	/// The amount that has not vested yet.
+	pub unvested: Balance,
+	/// The part of `vested` that is still frozen until the frozen amount is next updated.
+	pub claimable: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+	/// This runtime api allows people to query the vesting state of accounts in the assets
+	/// vesting pallet.
+	pub trait AssetsVestingApi<AccountId, AssetKind, Balance> where
+		AccountId: Codec,
+		AssetKind: Codec,
+		Balance: Codec,
+	{
+		/// Returns the vested and unvested amounts of every asset `who` has vesting schedules
+		/// for.
+		fn vesting_balances(who: AccountId) -> Vec<(AssetKind, VestingBalance<Balance>)>;
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// A reason for the pallet freezing funds.
+	#[pallet::composite_enum]
+	pub enum FreezeReason {
+		/// Funds have not vested yet.
+		#[codec(index = 0)]
+		Vesting,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The overarching freeze reason.
+		type RuntimeFreezeReason: From<FreezeReason>;
+
+		/// Type of asset class, sourced from [`Config::Assets`], utilized to identify the assets
+		/// vesting.
+		type AssetKind: Parameter + MaxEncodedLen;
+
+		/// The type in which the assets are measured.
+		type Balance: Balance;
+
+		/// Registry of the assets vesting.
+		type Assets: Inspect<Self::AccountId, AssetId = Self::AssetKind, Balance = Self::Balance>
+			+ Mutate<Self::AccountId>;
+
+		/// Freezer for the assets, freezing the unvested funds.
+		type AssetsFreezer: MutateFreeze<
+			Self::AccountId,
+			Id = Self::RuntimeFreezeReason,
+			AssetId = Self::AssetKind,
+			Balance = Self::Balance,
+		>;
+
+		/// The origin allowed to create vested transfers of an asset, returning the account the
+		/// funds are transferred from.
+		///
+		/// Anyone allowed to create vested transfers can use up the schedules of an account.
+		type VestedTransferOrigin: EnsureOriginWithArg<
+			Self::RuntimeOrigin,
+			Self::AssetKind,
+			Success = Self::AccountId,
+		>;
+
+		/// Maximum number of vesting schedules an account may have for one asset.
+		#[pallet::constant]
+		type MaxVestingSchedules: Get<u32>;
+
+		/// Provider for the block number.
+		type BlockNumberProvider: BlockNumberProvider<BlockNumber = BlockNumberFor<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Helper for benchmarking.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: benchmarking::BenchmarkHelper<Self::AssetKind>;
+	}
+
+	/// Vesting schedules of an account for an asset.
+	#[pallet::storage]
+	pub type Vesting<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AssetKind,
+		BoundedVec<VestingScheduleOf<T>, T::MaxVestingSchedules>,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A vesting schedule has been created.
+		VestingCreated {
+			/// The account vesting.
+			account: T::AccountId,
+			/// The asset vesting.
+			asset: T::AssetKind,
+			/// The index of the new schedule among the schedules of `account` for `asset`.
+			schedule_index: u32,
+		},
+		/// The frozen amount of an asset of an account was updated to its unvested amount.
+		VestingUpdated {
+			/// The account vesting.
+			account: T::AccountId,
+			/// The asset vesting.
+			asset: T::AssetKind,
+			/// The amount still frozen.
+			unvested: T::Balance,
+		},
+		/// An account has fully vested an asset.
+		VestingCompleted {
+			/// The account that vested.
+			account: T::AccountId,
+			/// The asset vested.
+			asset: T::AssetKind,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is not vesting the asset.
+		NotVesting,
+		/// The account already has `MaxVestingSchedules` schedules for the asset and thus cannot
+		/// add another one.
+		AtMaxVestingSchedules,
+		/// Amount being transferred is below the minimum balance of the asset.
+		AmountLow,
+		/// An index was out of bounds of the vesting schedules.
+		ScheduleIndexOutOfBounds,
+		/// Failed to create a new schedule because some parameter was invalid.
+		InvalidScheduleParams,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(
+				T::MaxVestingSchedules::get() > 0,
+				"`MaxVestingSchedules` must be greater than 0"
+			);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Unfreeze any vested funds of `asset` of the sender account.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have funds of
+		/// `asset` still vesting under this pallet.
+		///
+		/// Emits either `VestingCompleted` or `VestingUpdated`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::vest(T::MaxVestingSchedules::get()))]
+		pub fn vest(origin: OriginFor<T>, asset: Box<T::AssetKind>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_vest(&who, &asset)
+		}
+
+		/// Unfreeze any vested funds of `asset` of a `target` account.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `target`: The account whose vested funds should be unfrozen. Must have funds of
+		/// `asset` still vesting under this pallet.
+		///
+		/// Emits either `VestingCompleted` or `VestingUpdated`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::vest_other(T::MaxVestingSchedules::get()))]
+		pub fn vest_other(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			asset: Box<T::AssetKind>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let who = T::Lookup::lookup(target)?;
+			Self::do_vest(&who, &asset)
+		}
+
+		/// Create a vested transfer of `asset`.
+		///
+		/// The dispatch origin for this call must be [`Config::VestedTransferOrigin`] for `asset`.
+		///
+		/// - `target`: The account receiving the vested funds.
+		/// - `schedule`: The vesting schedule attached to the transfer.
+		///
+		/// Emits `VestingCreated`.
+		///
+		/// NOTE: This will unfreeze all vested funds of `asset` of `target` through the current
+		/// block.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::vested_transfer(T::MaxVestingSchedules::get()))]
+		pub fn vested_transfer(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			asset: Box<T::AssetKind>,
+			schedule: VestingScheduleOf<T>,
+		) -> DispatchResult {
+			let transactor = T::VestedTransferOrigin::ensure_origin(origin, &asset)?;
+			let target = T::Lookup::lookup(target)?;
+			Self::do_vested_transfer(&transactor, &target, *asset, schedule)
+		}
+
+		/// Force a vested transfer of `asset`.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// - `source`: The account whose funds should be transferred.
+		/// - `target`: The account that should be transferred the vested funds.
+		/// - `schedule`: The vesting schedule attached to the transfer.
+		///
+		/// Emits `VestingCreated`.
+		///
+		/// NOTE: This will unfreeze all vested funds of `asset` of `target` through the current
+		/// block.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::force_vested_transfer(T::MaxVestingSchedules::get()))]
+		pub fn force_vested_transfer(
+			origin: OriginFor<T>,
+			source: AccountIdLookupOf<T>,
+			target: AccountIdLookupOf<T>,
+			asset: Box<T::AssetKind>,
+			schedule: VestingScheduleOf<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let target = T::Lookup::lookup(target)?;
+			let source = T::Lookup::lookup(source)?;
+			Self::do_vested_transfer(&source, &target, *asset, schedule)
+		}
+
+		/// Force remove a vesting schedule of `asset`.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// - `target`: An account that has a vesting schedule for `asset`.
+		/// - `schedule_index`: The vesting schedule index that should be removed.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::force_remove_vesting_schedule(
+			T::MaxVestingSchedules::get()
+		))]
+		pub fn force_remove_vesting_schedule(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			asset: Box<T::AssetKind>,
+			schedule_index: u32,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(target)?;
+
+			let mut schedules = Vesting::<T>::get(&who, &*asset).ok_or(Error::<T>::NotVesting)?;
+			ensure!(
+				(schedule_index as usize) < schedules.len(),
+				Error::<T>::ScheduleIndexOutOfBounds
+			);
+			schedules.remove(schedule_index as usize);
+
+			Self::write_vesting(&who, &asset, schedules.into_inner())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The vested and unvested amounts of `asset` of `who`, or `None` if `who` is not vesting
+	/// `asset`.
+	pub fn vesting_balance(
+		who: &T::AccountId,
+		asset: &T::AssetKind,
+	) -> Option<VestingBalance<T::Balance>> {
+		let schedules = Vesting::<T>::get(who, asset)?;
+		let now = T::BlockNumberProvider::current_block_number();
+		let (locked, unvested) = schedules.iter().fold(
+			(Zero::zero(), Zero::zero()),
+			|(locked, unvested): (T::Balance, T::Balance), s| {
+				(locked.saturating_add(s.locked()), unvested.saturating_add(s.locked_at(now)))
+			},
+		);
+		let frozen =
+			T::AssetsFreezer::balance_frozen(asset.clone(), &FreezeReason::Vesting.into(), who);
+		Some(VestingBalance {
+			vested: locked.saturating_sub(unvested),
+			unvested,
+			claimable: frozen.saturating_sub(unvested),
+		})
+	}
+
+	/// The vested and unvested amounts of every asset `who` is vesting.
+	///
+	/// See [`AssetsVestingApi::vesting_balances`].
+	pub fn vesting_balances(who: &T::AccountId) -> Vec<(T::AssetKind, VestingBalance<T::Balance>)> {
+		Vesting::<T>::iter_key_prefix(who)
+			.filter_map(|asset| Self::vesting_balance(who, &asset).map(|balance| (asset, balance)))
+			.collect()
+	}
+
+	/// Transfer `schedule.locked()` of `asset` from `source` to `target`, vesting under
+	/// `schedule`.
+	pub fn do_vested_transfer(
+		source: &T::AccountId,
+		target: &T::AccountId,
+		asset: T::AssetKind,
+		schedule: VestingScheduleOf<T>,
+	) -> DispatchResult {
+		ensure!(schedule.is_valid(), Error::<T>::InvalidScheduleParams);
+		ensure!(
+			schedule.locked() >= T::Assets::minimum_balance(asset.clone()),
+			Error::<T>::AmountLow
+		);
+		// Check we can add to this account prior to any storage writes.
+		let mut schedules = Vesting::<T>::get(target, &asset).unwrap_or_default().into_inner();
+		ensure!(
+			schedules.len() < T::MaxVestingSchedules::get() as usize,
+			Error::<T>::AtMaxVestingSchedules
+		);
+
+		T::Assets::transfer(
+			asset.clone(),
+			source,
+			target,
+			schedule.locked(),
+			Preservation::Expendable,
+		)?;
+
+		schedules.push(schedule);
+		Self::deposit_event(Event::VestingCreated {
+			account: target.clone(),
+			asset: asset.clone(),
+			schedule_index: (schedules.len() - 1) as u32,
+		});
+		Self::write_vesting(target, &asset, schedules)
+	}
+
+	/// Unfreeze any vested funds of `asset` of `who`.
+	fn do_vest(who: &T::AccountId, asset: &T::AssetKind) -> DispatchResult {
+		let schedules = Vesting::<T>::get(who, asset).ok_or(Error::<T>::NotVesting)?;
+		Self::write_vesting(who, asset, schedules.into_inner())
+	}
+
+	/// Write the vesting schedules of `asset` of `who` to storage, dropping the fully vested ones,
+	/// and freeze their unvested amount.
+	fn write_vesting(
+		who: &T::AccountId,
+		asset: &T::AssetKind,
+		schedules: Vec<VestingScheduleOf<T>>,
+	) -> DispatchResult {
+		let now = T::BlockNumberProvider::current_block_number();
+		let mut unvested: T::Balance = Zero::zero();
+		let schedules: Vec<_> = schedules
+			.into_iter()
+			.filter(|schedule| {
+				let locked_now = schedule.locked_at(now);
+				unvested = unvested.saturating_add(locked_now);
+				!locked_now.is_zero()
+			})
+			.collect();
+
+		if schedules.is_empty() {
+			Vesting::<T>::remove(who, asset);
+			T::AssetsFreezer::thaw(asset.clone(), &FreezeReason::Vesting.into(), who)?;
+			Self::deposit_event(Event::VestingCompleted {
+				account: who.clone(),
+				asset: asset.clone(),
+			});
+		} else {
+			let schedules: BoundedVec<_, T::MaxVestingSchedules> =
+				schedules.try_into().map_err(|_| Error::<T>::AtMaxVestingSchedules)?;
+			Vesting::<T>::insert(who, asset, schedules);
+			T::AssetsFreezer::set_freeze(
+				asset.clone(),
+				&FreezeReason::Vesting.into(),
+				who,
+				unvested,
+			)?;
+			Self::deposit_event(Event::VestingUpdated {
+				account: who.clone(),
+				asset: asset.clone(),
+				unvested,
+			});
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,142 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Module to enforce private fields on `VestingSchedule`.
+
+use super::*;
+
+/// Struct to encode a vesting schedule of an individual account for one asset.
+///
+/// Nothing vests until `cliff` blocks after `start`. From then on, the schedule vests linearly
+/// over `duration` blocks from `start`, in tranches released every `period` blocks. A `period` of
+/// one block vests continuously, while for example a `period` of a month of blocks releases a
+/// monthly tranche.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Copy,
+	Clone,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub struct VestingSchedule<Balance, BlockNumber> {
+	/// Frozen amount at schedule creation.
+	locked: Balance,
+	/// Block the schedule starts vesting at.
+	start: BlockNumber,
+	/// Number of blocks after `start` before anything vests.
+	cliff: BlockNumber,
+	/// Number of blocks after `start` at which everything is vested.
+	duration: BlockNumber,
+	/// Number of blocks between the release of two tranches.
+	period: BlockNumber,
+}
+
+impl<Balance, BlockNumber> VestingSchedule<Balance, BlockNumber>
+where
+	Balance: AtLeast32BitUnsigned + Copy,
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+	/// Instantiate a new `VestingSchedule`.
+	pub fn new(
+		locked: Balance,
+		start: BlockNumber,
+		cliff: BlockNumber,
+		duration: BlockNumber,
+		period: BlockNumber,
+	) -> VestingSchedule<Balance, BlockNumber> {
+		VestingSchedule { locked, start, cliff, duration, period }
+	}
+
+	/// Instantiate a schedule vesting `locked` continuously over `duration` blocks from `start`,
+	/// without a cliff.
+	pub fn linear(
+		locked: Balance,
+		start: BlockNumber,
+		duration: BlockNumber,
+	) -> VestingSchedule<Balance, BlockNumber> {
+		Self::new(locked, start, Zero::zero(), duration, One::one())
+	}
+
+	/// Validate parameters for `VestingSchedule`. Note that this does not check against the
+	/// minimum balance of the asset.
+	pub fn is_valid(&self) -> bool {
+		!self.locked.is_zero() &&
+			!self.duration.is_zero() &&
+			!self.period.is_zero() &&
+			self.period <= self.duration &&
+			self.cliff <= self.duration
+	}
+
+	/// Frozen amount at schedule creation.
+	pub fn locked(&self) -> Balance {
+		self.locked
+	}
+
+	/// Block the schedule starts vesting at.
+	pub fn start(&self) -> BlockNumber {
+		self.start
+	}
+
+	/// Number of blocks after `start` before anything vests.
+	pub fn cliff(&self) -> BlockNumber {
+		self.cliff
+	}
+
+	/// Number of blocks after `start` at which everything is vested.
+	pub fn duration(&self) -> BlockNumber {
+		self.duration
+	}
+
+	/// Number of blocks between the release of two tranches.
+	pub fn period(&self) -> BlockNumber {
+		self.period
+	}
+
+	/// Amount still frozen at block `n`.
+	pub fn locked_at(&self, n: BlockNumber) -> Balance {
+		// Number of blocks that count toward vesting;
+		// saturating to 0 when n < start.
+		let elapsed = n.saturating_sub(self.start);
+		if elapsed < self.cliff {
+			return self.locked
+		}
+		if elapsed >= self.duration {
+			return Zero::zero()
+		}
+		// Only whole periods count, so that tranches are released at once.
+		let elapsed = elapsed.saturating_sub(elapsed % self.period.max(One::one()));
+		let vested = multiply_by_rational_with_rounding(
+			self.locked.saturated_into(),
+			elapsed.saturated_into(),
+			self.duration.saturated_into(),
+			Rounding::Down,
+		)
+		.map(|vested| vested.saturated_into::<Balance>())
+		.unwrap_or(Zero::zero());
+		self.locked.saturating_sub(vested)
+	}
+
+	/// Block number at which the schedule ends.
+	pub fn ending_block(&self) -> BlockNumber {
+		self.start.saturating_add(self.duration)
+	}
+}
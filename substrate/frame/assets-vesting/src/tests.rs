@@ -0,0 +1,329 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Assets Vesting pallet tests.
+
+use crate::{mock::*, *};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungible::NativeOrWithId,
+		fungibles::{Inspect as _, InspectFreeze as _, Mutate as _},
+		tokens::Preservation,
+	},
+};
+use sp_runtime::{traits::BadOrigin, TokenError};
+
+const NATIVE: NativeOrWithId<u32> = NativeOrWithId::Native;
+const ASSET: NativeOrWithId<u32> = NativeOrWithId::WithId(1);
+
+fn events() -> Vec<Event<Test>> {
+	let result = System::events()
+		.into_iter()
+		.map(|r| r.event)
+		.filter_map(|e| if let RuntimeEvent::AssetsVesting(inner) = e { Some(inner) } else { None })
+		.collect();
+
+	System::reset_events();
+
+	result
+}
+
+fn frozen(who: u64, asset: NativeOrWithId<u32>) -> u64 {
+	NativeAndAssetsFreezer::balance_frozen(asset, &FreezeReason::Vesting.into(), &who)
+}
+
+fn vested_transfer(
+	from: u64,
+	to: u64,
+	asset: NativeOrWithId<u32>,
+	schedule: VestingScheduleOf<Test>,
+) {
+	assert_ok!(AssetsVesting::vested_transfer(
+		RuntimeOrigin::signed(from),
+		to,
+		Box::new(asset),
+		schedule,
+	));
+}
+
+fn run_to(n: u64) {
+	System::set_block_number(n);
+}
+
+#[test]
+fn linear_schedule_vests_every_block() {
+	let schedule = VestingSchedule::<u64, u64>::linear(1_000, 10, 100);
+	assert!(schedule.is_valid());
+	assert_eq!(schedule.locked_at(0), 1_000);
+	assert_eq!(schedule.locked_at(10), 1_000);
+	assert_eq!(schedule.locked_at(11), 990);
+	assert_eq!(schedule.locked_at(60), 500);
+	assert_eq!(schedule.locked_at(109), 10);
+	assert_eq!(schedule.locked_at(110), 0);
+	assert_eq!(schedule.ending_block(), 110);
+}
+
+#[test]
+fn cliff_schedule_vests_nothing_before_cliff() {
+	let schedule = VestingSchedule::<u64, u64>::new(1_000, 0, 40, 100, 1);
+	assert!(schedule.is_valid());
+	assert_eq!(schedule.locked_at(39), 1_000);
+	// what accrued during the cliff vests at once
+	assert_eq!(schedule.locked_at(40), 600);
+	assert_eq!(schedule.locked_at(41), 590);
+	assert_eq!(schedule.locked_at(100), 0);
+}
+
+#[test]
+fn stepped_schedule_vests_in_tranches() {
+	let schedule = VestingSchedule::<u64, u64>::new(1_200, 0, 0, 120, 30);
+	assert!(schedule.is_valid());
+	assert_eq!(schedule.locked_at(29), 1_200);
+	assert_eq!(schedule.locked_at(30), 900);
+	assert_eq!(schedule.locked_at(59), 900);
+	assert_eq!(schedule.locked_at(60), 600);
+	assert_eq!(schedule.locked_at(119), 300);
+	assert_eq!(schedule.locked_at(120), 0);
+
+	// the last tranche is released at the end even if shorter than a period
+	let schedule = VestingSchedule::<u64, u64>::new(1_000, 0, 0, 100, 30);
+	assert_eq!(schedule.locked_at(99), 100);
+	assert_eq!(schedule.locked_at(100), 0);
+}
+
+#[test]
+fn invalid_schedules_are_rejected() {
+	assert!(!VestingSchedule::<u64, u64>::new(0, 0, 0, 100, 1).is_valid());
+	assert!(!VestingSchedule::<u64, u64>::new(1_000, 0, 0, 0, 1).is_valid());
+	assert!(!VestingSchedule::<u64, u64>::new(1_000, 0, 0, 100, 0).is_valid());
+	assert!(!VestingSchedule::<u64, u64>::new(1_000, 0, 0, 100, 101).is_valid());
+	assert!(!VestingSchedule::<u64, u64>::new(1_000, 0, 101, 100, 1).is_valid());
+
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetsVesting::vested_transfer(
+				RuntimeOrigin::signed(1),
+				4,
+				Box::new(ASSET),
+				VestingSchedule::new(1_000, 1, 0, 100, 200),
+			),
+			Error::<Test>::InvalidScheduleParams
+		);
+	});
+}
+
+#[test]
+fn vested_transfer_freezes_funds() {
+	new_test_ext().execute_with(|| {
+		vested_transfer(1, 4, ASSET, VestingSchedule::linear(1_000, 1, 100));
+
+		assert_eq!(NativeAndAssets::balance(ASSET, &4), 1_000);
+		assert_eq!(NativeAndAssets::balance(ASSET, &1), 9_000);
+		assert_eq!(frozen(4, ASSET), 1_000);
+		assert_eq!(
+			events(),
+			[
+				Event::VestingCreated { account: 4, asset: ASSET, schedule_index: 0 },
+				Event::VestingUpdated { account: 4, asset: ASSET, unvested: 1_000 },
+			]
+		);
+		assert_noop!(
+			NativeAndAssets::transfer(ASSET, &4, &1, 1, Preservation::Expendable),
+			TokenError::FundsUnavailable
+		);
+	});
+}
+
+#[test]
+fn vest_unfreezes_vested_funds() {
+	new_test_ext().execute_with(|| {
+		vested_transfer(1, 4, ASSET, VestingSchedule::linear(1_000, 1, 100));
+		events();
+
+		run_to(51);
+		assert_eq!(
+			AssetsVesting::vesting_balance(&4, &ASSET),
+			Some(VestingBalance { vested: 500, unvested: 500, claimable: 500 })
+		);
+		assert_ok!(AssetsVesting::vest(RuntimeOrigin::signed(4), Box::new(ASSET)));
+		assert_eq!(frozen(4, ASSET), 500);
+		assert_eq!(
+			AssetsVesting::vesting_balance(&4, &ASSET),
+			Some(VestingBalance { vested: 500, unvested: 500, claimable: 0 })
+		);
+		assert_eq!(events(), [Event::VestingUpdated { account: 4, asset: ASSET, unvested: 500 }]);
+
+		assert_noop!(
+			NativeAndAssets::transfer(ASSET, &4, &1, 501, Preservation::Expendable),
+			TokenError::FundsUnavailable
+		);
+		assert_ok!(NativeAndAssets::transfer(ASSET, &4, &1, 500, Preservation::Expendable));
+	});
+}
+
+#[test]
+fn vest_other_completes_vesting() {
+	new_test_ext().execute_with(|| {
+		vested_transfer(1, 4, ASSET, VestingSchedule::new(1_200, 1, 0, 120, 30));
+		events();
+
+		assert_noop!(
+			AssetsVesting::vest_other(RuntimeOrigin::signed(2), 3, Box::new(ASSET)),
+			Error::<Test>::NotVesting
+		);
+
+		run_to(61);
+		assert_ok!(AssetsVesting::vest_other(RuntimeOrigin::signed(2), 4, Box::new(ASSET)));
+		assert_eq!(frozen(4, ASSET), 600);
+
+		run_to(121);
+		assert_ok!(AssetsVesting::vest_other(RuntimeOrigin::signed(2), 4, Box::new(ASSET)));
+		assert_eq!(frozen(4, ASSET), 0);
+		assert!(Vesting::<Test>::get(4, ASSET).is_none());
+		assert_eq!(
+			events(),
+			[
+				Event::VestingUpdated { account: 4, asset: ASSET, unvested: 600 },
+				Event::VestingCompleted { account: 4, asset: ASSET },
+			]
+		);
+		assert_noop!(
+			AssetsVesting::vest(RuntimeOrigin::signed(4), Box::new(ASSET)),
+			Error::<Test>::NotVesting
+		);
+	});
+}
+
+#[test]
+fn schedules_add_up_and_are_bounded() {
+	new_test_ext().execute_with(|| {
+		vested_transfer(1, 4, ASSET, VestingSchedule::linear(1_000, 1, 100));
+		vested_transfer(2, 4, ASSET, VestingSchedule::new(500, 1, 50, 100, 1));
+		vested_transfer(3, 4, ASSET, VestingSchedule::linear(100, 1, 10));
+		assert_eq!(frozen(4, ASSET), 1_600);
+		assert_noop!(
+			AssetsVesting::vested_transfer(
+				RuntimeOrigin::signed(1),
+				4,
+				Box::new(ASSET),
+				VestingSchedule::linear(100, 1, 10),
+			),
+			Error::<Test>::AtMaxVestingSchedules
+		);
+
+		// the third schedule is complete and dropped on the next update
+		run_to(21);
+		assert_ok!(AssetsVesting::vest(RuntimeOrigin::signed(4), Box::new(ASSET)));
+		assert_eq!(frozen(4, ASSET), 800 + 500);
+		assert_eq!(Vesting::<Test>::get(4, ASSET).unwrap().len(), 2);
+		vested_transfer(1, 4, ASSET, VestingSchedule::linear(100, 21, 10));
+	});
+}
+
+#[test]
+fn assets_vest_independently() {
+	new_test_ext().execute_with(|| {
+		vested_transfer(1, 4, ASSET, VestingSchedule::linear(1_000, 1, 100));
+		vested_transfer(1, 4, NATIVE, VestingSchedule::linear(2_000, 1, 10));
+		assert_eq!(frozen(4, ASSET), 1_000);
+		assert_eq!(frozen(4, NATIVE), 2_000);
+
+		run_to(11);
+		assert_ok!(AssetsVesting::vest(RuntimeOrigin::signed(4), Box::new(NATIVE)));
+		assert_eq!(frozen(4, NATIVE), 0);
+		assert_eq!(frozen(4, ASSET), 1_000);
+
+		assert_eq!(
+			AssetsVesting::vesting_balances(&4),
+			vec![(ASSET, VestingBalance { vested: 100, unvested: 900, claimable: 100 })]
+		);
+	});
+}
+
+#[test]
+fn vested_transfer_requires_origin() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(NativeAndAssets::transfer(ASSET, &1, &5, 1_000, Preservation::Expendable));
+		assert_noop!(
+			AssetsVesting::vested_transfer(
+				RuntimeOrigin::signed(5),
+				4,
+				Box::new(ASSET),
+				VestingSchedule::linear(1_000, 1, 100),
+			),
+			BadOrigin
+		);
+		assert_eq!(AssetsVesting::vesting_balance(&4, &ASSET), None);
+	});
+}
+
+#[test]
+fn force_calls_require_root() {
+	new_test_ext().execute_with(|| {
+		let schedule = VestingSchedule::linear(1_000, 1, 100);
+		assert_noop!(
+			AssetsVesting::force_vested_transfer(
+				RuntimeOrigin::signed(1),
+				1,
+				4,
+				Box::new(ASSET),
+				schedule,
+			),
+			BadOrigin
+		);
+		assert_ok!(AssetsVesting::force_vested_transfer(
+			RuntimeOrigin::root(),
+			1,
+			4,
+			Box::new(ASSET),
+			schedule,
+		));
+		vested_transfer(2, 4, ASSET, VestingSchedule::linear(500, 1, 100));
+		assert_eq!(frozen(4, ASSET), 1_500);
+
+		assert_noop!(
+			AssetsVesting::force_remove_vesting_schedule(
+				RuntimeOrigin::signed(1),
+				4,
+				Box::new(ASSET),
+				0
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetsVesting::force_remove_vesting_schedule(
+				RuntimeOrigin::root(),
+				4,
+				Box::new(ASSET),
+				2
+			),
+			Error::<Test>::ScheduleIndexOutOfBounds
+		);
+		assert_ok!(AssetsVesting::force_remove_vesting_schedule(
+			RuntimeOrigin::root(),
+			4,
+			Box::new(ASSET),
+			0
+		));
+		assert_eq!(frozen(4, ASSET), 500);
+		assert_eq!(
+			Vesting::<Test>::get(4, ASSET).unwrap().into_inner(),
+			vec![VestingSchedule::linear(500, 1, 100)]
+		);
+	});
+}
@@ -105,6 +105,7 @@ impl pallet_treasury::Config for Test {
 	type BalanceConverter = UnityAssetBalanceConversion;
 	type PayoutPeriod = ConstU64<10>;
 	type BlockNumberProvider = System;
+	type BudgetOrigin = frame_system::EnsureRoot<u128>;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
 }
@@ -128,6 +129,7 @@ impl pallet_treasury::Config<Instance1> for Test {
 	type BalanceConverter = UnityAssetBalanceConversion;
 	type PayoutPeriod = ConstU64<10>;
 	type BlockNumberProvider = System;
+	type BudgetOrigin = frame_system::EnsureRoot<u128>;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
 }
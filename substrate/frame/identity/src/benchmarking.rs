@@ -941,5 +941,93 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn submit_attestation(
+		r: Linear<1, { T::MaxRegistrars::get() - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		// The user
+		let user: T::AccountId = account("user", r, SEED);
+		let user_origin =
+			<T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(user.clone()));
+		let _ = T::Currency::make_free_balance_be(&user, BalanceOf::<T>::max_value());
+
+		add_registrars::<T>(r)?;
+
+		let info = T::IdentityInformation::create_identity_info();
+		let info_hash = T::Hashing::hash_of(&info);
+		Identity::<T>::set_identity(user_origin.clone(), Box::new(info))?;
+
+		// The registrar signs off-chain.
+		let public = sr25519_generate(0.into(), None);
+		let registrar: T::AccountId = MultiSigner::Sr25519(public).into_account().into();
+		let registrar_origin = T::RegistrarOrigin::try_successful_origin()
+			.expect("RegistrarOrigin has no successful origin required for the benchmark");
+		Identity::<T>::add_registrar(registrar_origin, T::Lookup::unlookup(registrar))?;
+
+		let attest = |expires_at: BlockNumberFor<T>, nonce: u64| {
+			let attestation = JudgementAttestation {
+				registrar_index: r,
+				target: user.clone(),
+				judgement: Judgement::Reasonable,
+				identity: info_hash,
+				expires_at,
+				nonce,
+			};
+			let payload = Identity::<T>::attestation_payload(&attestation);
+			let signature =
+				MultiSignature::Sr25519(sr25519_sign(0.into(), &public, &payload[..]).unwrap());
+			(attestation, signature)
+		};
+
+		// Worst case replaces both an earlier attestation and a pending request.
+		let now = frame_system::Pallet::<T>::block_number();
+		let (attestation, signature) = attest(now + 10u32.into(), 1);
+		Identity::<T>::submit_attestation(
+			RawOrigin::Signed(user.clone()).into(),
+			attestation,
+			signature.into(),
+		)?;
+		Identity::<T>::request_judgement(user_origin, r, 10u32.into())?;
+
+		let expires_at = now + 20u32.into();
+		let (attestation, signature) = attest(expires_at, 2);
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), attestation, signature.into());
+
+		assert_last_event::<T>(
+			Event::<T>::JudgementAttested { target: user, registrar_index: r, expires_at }.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn expire_judgements(
+		n: Linear<0, { T::MaxExpiriesPerBlock::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let expires_at = frame_system::Pallet::<T>::block_number() + One::one();
+		for i in 0..n {
+			let target: T::AccountId = account("target", i, SEED);
+			let info = T::IdentityInformation::create_identity_info();
+			Identity::<T>::set_identity_no_deposit(&target, info)?;
+			IdentityOf::<T>::mutate(&target, |id| {
+				let id = id.as_mut().expect("identity was just set");
+				assert_ok!(id.judgements.try_push((0, Judgement::KnownGood)));
+			});
+			JudgementExpiry::<T>::insert(&target, 0, expires_at);
+			assert_ok!(ExpiringJudgements::<T>::try_append(expires_at, (target, 0)));
+		}
+
+		#[block]
+		{
+			Identity::<T>::expire_judgements(expires_at);
+		}
+
+		assert!(ExpiringJudgements::<T>::get(expires_at).is_empty());
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Identity, crate::tests::new_test_ext(), crate::tests::Test);
 }
@@ -40,6 +40,17 @@
 //! The number of registrars should be limited, and the deposit made sufficiently large, to ensure
 //! no state-bloat attack is viable.
 //!
+//! ### Attestations
+//!
+//! Registrars need not be live operators submitting extrinsics themselves. A registrar may instead
+//! sign a [`JudgementAttestation`] off-chain, which anyone can then relay. Attested judgements
+//! carry an expiry block, at which they are automatically downgraded to [`Judgement::OutOfDate`],
+//! so stale verifications do not persist forever. The registrar signs the `blake2_256` hash of the
+//! payload returned by [`Pallet::attestation_payload`], like the signatures checked by the
+//! `VerifySignature` transaction extension. Each attestation carries a nonce, which must exceed that
+//! of every attestation of the registrar upon the account submitted before, so an attestation can
+//! only be submitted once.
+//!
 //! ### Usernames
 //!
 //! The pallet provides functionality for username authorities to issue usernames, which are
@@ -90,6 +101,9 @@
 //! * `set_fields` - Set the fields that a registrar cares about in their judgements.
 //! * `provide_judgement` - Provide a judgement to an identity.
 //!
+//! #### For Relayers
+//! * `submit_attestation` - Submit a judgement signed off-chain by a registrar.
+//!
 //! #### For Username Authorities
 //! * `set_username_for` - Set a username for a given account. The account must approve it.
 //! * `unbind_username` - Start the grace period for a username.
@@ -129,11 +143,13 @@ use frame_support::{
 };
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
+use sp_io::hashing::blake2_256;
 use sp_runtime::traits::{
 	AppendZerosInput, Hash, IdentifyAccount, Saturating, StaticLookup, Verify, Zero,
 };
 pub use types::{
-	Data, IdentityInformationProvider, Judgement, RegistrarIndex, RegistrarInfo, Registration,
+	Data, IdentityInformationProvider, Judgement, JudgementAttestation, RegistrarIndex,
+	RegistrarInfo, Registration,
 };
 pub use weights::WeightInfo;
 
@@ -144,6 +160,12 @@ type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
 >>::NegativeImbalance;
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
 type ProviderOf<T> = Provider<BalanceOf<T>>;
+type JudgementAttestationOf<T> = JudgementAttestation<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::Hash,
+	BlockNumberFor<T>,
+>;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -189,6 +211,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxRegistrars: Get<u32>;
 
+		/// The maximum number of attested judgements which may expire in a single block.
+		#[pallet::constant]
+		type MaxExpiriesPerBlock: Get<u32>;
+
 		/// What to do with slashed funds.
 		type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
@@ -337,6 +363,48 @@ pub mod pallet {
 	pub type UnbindingUsernames<T: Config> =
 		StorageMap<_, Blake2_128Concat, Username<T>, BlockNumberFor<T>, OptionQuery>;
 
+	/// The block at which the latest attested judgement of a registrar upon an account expires.
+	///
+	/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type JudgementExpiry<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		RegistrarIndex,
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// The nonce of the latest attestation of a registrar upon an account.
+	///
+	/// Unlike [`JudgementExpiry`], it is never cleared, so an attestation cannot be submitted again
+	/// once its judgement has been replaced or dropped.
+	///
+	/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type AttestationNonce<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		RegistrarIndex,
+		u64,
+		OptionQuery,
+	>;
+
+	/// The attested judgements expiring at a block, as the judged account and the index of the
+	/// registrar.
+	#[pallet::storage]
+	pub type ExpiringJudgements<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<(T::AccountId, RegistrarIndex), T::MaxExpiriesPerBlock>,
+		ValueQuery,
+	>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Too many subs-accounts.
@@ -400,6 +468,13 @@ pub mod pallet {
 		/// The action cannot be performed because of insufficient privileges (e.g. authority
 		/// trying to unbind a username provided by the system).
 		InsufficientPrivileges,
+		/// The attestation has already expired.
+		AttestationExpired,
+		/// The nonce of the attestation does not exceed that of the last one submitted by the
+		/// registrar for the account.
+		StaleAttestation,
+		/// Too many attested judgements expire at the same block.
+		TooManyExpiries,
 	}
 
 	#[pallet::event]
@@ -451,6 +526,21 @@ pub mod pallet {
 		UsernameRemoved { username: Username<T> },
 		/// A username has been killed.
 		UsernameKilled { username: Username<T> },
+		/// A judgement attested by a registrar was submitted, which holds until `expires_at`.
+		JudgementAttested {
+			target: T::AccountId,
+			registrar_index: RegistrarIndex,
+			expires_at: BlockNumberFor<T>,
+		},
+		/// An attested judgement expired and was downgraded to `OutOfDate`.
+		JudgementExpired { target: T::AccountId, registrar_index: RegistrarIndex },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			Self::expire_judgements(now)
+		}
 	}
 
 	#[pallet::call]
@@ -501,16 +591,23 @@ pub mod pallet {
 		///
 		/// Emits `IdentitySet` if successful.
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::set_identity(T::MaxRegistrars::get()))]
+		#[pallet::weight(T::WeightInfo::set_identity(T::MaxRegistrars::get())
+			.saturating_add(Pallet::<T>::clear_judgement_expiries_weight(T::MaxRegistrars::get()))
+		)]
 		pub fn set_identity(
 			origin: OriginFor<T>,
 			info: Box<T::IdentityInformation>,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
+			let mut dropped = 0u32;
 			let mut id = match IdentityOf::<T>::get(&sender) {
 				Some(mut id) => {
 					// Only keep non-positive judgements.
+					for (reg_index, _) in id.judgements.iter().filter(|j| !j.1.is_sticky()) {
+						Self::clear_judgement_expiry(&sender, *reg_index);
+						dropped.saturating_inc();
+					}
 					id.judgements.retain(|j| j.1.is_sticky());
 					id.info = *info;
 					id
@@ -531,7 +628,11 @@ pub mod pallet {
 			IdentityOf::<T>::insert(&sender, id);
 			Self::deposit_event(Event::IdentitySet { who: sender });
 
-			Ok(Some(T::WeightInfo::set_identity(judgements as u32)).into())
+			Ok(Some(
+				T::WeightInfo::set_identity(judgements as u32)
+					.saturating_add(Self::clear_judgement_expiries_weight(dropped)),
+			)
+			.into())
 		}
 
 		/// Set the sub-accounts of the sender.
@@ -621,7 +722,8 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::clear_identity(
 			T::MaxRegistrars::get(),
 			T::MaxSubAccounts::get(),
-		))]
+		)
+		.saturating_add(Pallet::<T>::clear_judgement_expiries_weight(T::MaxRegistrars::get())))]
 		pub fn clear_identity(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
@@ -631,6 +733,9 @@ pub mod pallet {
 			for sub in sub_ids.iter() {
 				SuperOf::<T>::remove(sub);
 			}
+			for (reg_index, _) in id.judgements.iter() {
+				Self::clear_judgement_expiry(&sender, *reg_index);
+			}
 
 			let err_amount = T::Currency::unreserve(&sender, deposit);
 			debug_assert!(err_amount.is_zero());
@@ -638,10 +743,12 @@ pub mod pallet {
 			Self::deposit_event(Event::IdentityCleared { who: sender, deposit });
 
 			#[allow(deprecated)]
-			Ok(Some(T::WeightInfo::clear_identity(
-				id.judgements.len() as u32,
-				sub_ids.len() as u32,
-			))
+			Ok(Some(
+				T::WeightInfo::clear_identity(id.judgements.len() as u32, sub_ids.len() as u32)
+					.saturating_add(Self::clear_judgement_expiries_weight(
+						id.judgements.len() as u32
+					)),
+			)
 			.into())
 		}
 
@@ -662,7 +769,9 @@ pub mod pallet {
 		///
 		/// Emits `JudgementRequested` if successful.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::request_judgement(T::MaxRegistrars::get(),))]
+		#[pallet::weight(T::WeightInfo::request_judgement(T::MaxRegistrars::get(),)
+			.saturating_add(Pallet::<T>::clear_judgement_expiries_weight(1))
+		)]
 		pub fn request_judgement(
 			origin: OriginFor<T>,
 			#[pallet::compact] reg_index: RegistrarIndex,
@@ -690,6 +799,7 @@ pub mod pallet {
 			}
 
 			T::Currency::reserve(&sender, registrar.fee)?;
+			Self::clear_judgement_expiry(&sender, reg_index);
 
 			let judgements = id.judgements.len();
 			IdentityOf::<T>::insert(&sender, id);
@@ -699,7 +809,11 @@ pub mod pallet {
 				registrar_index: reg_index,
 			});
 
-			Ok(Some(T::WeightInfo::request_judgement(judgements as u32)).into())
+			Ok(Some(
+				T::WeightInfo::request_judgement(judgements as u32)
+					.saturating_add(Self::clear_judgement_expiries_weight(1)),
+			)
+			.into())
 		}
 
 		/// Cancel a previous request.
@@ -853,11 +967,14 @@ pub mod pallet {
 		/// - `identity`: The hash of the [`IdentityInformationProvider`] for that the judgement is
 		///   provided.
 		///
-		/// Note: Judgements do not apply to a username.
+		/// Note: Judgements do not apply to a username. The judgement does not expire, even if it
+		/// replaces an attested one.
 		///
 		/// Emits `JudgementGiven` if successful.
 		#[pallet::call_index(9)]
-		#[pallet::weight(T::WeightInfo::provide_judgement(T::MaxRegistrars::get()))]
+		#[pallet::weight(T::WeightInfo::provide_judgement(T::MaxRegistrars::get())
+			.saturating_add(Pallet::<T>::clear_judgement_expiries_weight(1))
+		)]
 		pub fn provide_judgement(
 			origin: OriginFor<T>,
 			#[pallet::compact] reg_index: RegistrarIndex,
@@ -899,11 +1016,17 @@ pub mod pallet {
 					.map_err(|_| Error::<T>::TooManyRegistrars)?,
 			}
 
+			Self::clear_judgement_expiry(&target, reg_index);
+
 			let judgements = id.judgements.len();
 			IdentityOf::<T>::insert(&target, id);
 			Self::deposit_event(Event::JudgementGiven { target, registrar_index: reg_index });
 
-			Ok(Some(T::WeightInfo::provide_judgement(judgements as u32)).into())
+			Ok(Some(
+				T::WeightInfo::provide_judgement(judgements as u32)
+					.saturating_add(Self::clear_judgement_expiries_weight(1)),
+			)
+			.into())
 		}
 
 		/// Remove an account's identity and sub-account information and slash the deposits.
@@ -922,7 +1045,8 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::kill_identity(
 			T::MaxRegistrars::get(),
 			T::MaxSubAccounts::get(),
-		))]
+		)
+		.saturating_add(Pallet::<T>::clear_judgement_expiries_weight(T::MaxRegistrars::get())))]
 		pub fn kill_identity(
 			origin: OriginFor<T>,
 			target: AccountIdLookupOf<T>,
@@ -938,14 +1062,22 @@ pub mod pallet {
 			for sub in sub_ids.iter() {
 				SuperOf::<T>::remove(sub);
 			}
+			for (reg_index, _) in id.judgements.iter() {
+				Self::clear_judgement_expiry(&target, *reg_index);
+			}
 			// Slash their deposit from them.
 			T::Slashed::on_unbalanced(T::Currency::slash_reserved(&target, deposit).0);
 
 			Self::deposit_event(Event::IdentityKilled { who: target, deposit });
 
 			#[allow(deprecated)]
-			Ok(Some(T::WeightInfo::kill_identity(id.judgements.len() as u32, sub_ids.len() as u32))
-				.into())
+			Ok(Some(
+				T::WeightInfo::kill_identity(id.judgements.len() as u32, sub_ids.len() as u32)
+					.saturating_add(Self::clear_judgement_expiries_weight(
+						id.judgements.len() as u32
+					)),
+			)
+			.into())
 		}
 
 		/// Add the given account to the sender's subs.
@@ -1375,6 +1507,110 @@ pub mod pallet {
 			Self::deposit_event(Event::UsernameKilled { username });
 			Ok((Some(actual_weight), Pays::No).into())
 		}
+
+		/// Submit a judgement signed off-chain by a registrar.
+		///
+		/// The dispatch origin for this call must be _Signed_, but may be any account. The
+		/// transaction fee is waived if the attestation is accepted.
+		///
+		/// - `attestation`: the judgement of the registrar, along with the identity it is provided
+		///   for, the block at which it expires and its nonce.
+		/// - `signature`: the signature of the registrar's account over the
+		///   [`attestation_payload`](Pallet::attestation_payload) of `attestation`.
+		///
+		/// The nonce must exceed that of any attestation of the registrar upon the target submitted
+		/// before, even if its judgement has since been replaced or dropped.
+		///
+		/// The attestation may replace a pending request for judgement, whose fee is paid to the
+		/// registrar, or an earlier attested judgement. Judgements provided through
+		/// `provide_judgement` cannot be replaced, unless they are `Unknown` or `OutOfDate`.
+		///
+		/// Emits `JudgementAttested` if successful.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::submit_attestation(T::MaxRegistrars::get()))]
+		pub fn submit_attestation(
+			origin: OriginFor<T>,
+			attestation: JudgementAttestationOf<T>,
+			signature: T::OffchainSignature,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(attestation.expires_at > now, Error::<T>::AttestationExpired);
+			ensure!(!attestation.judgement.is_sticky(), Error::<T>::InvalidJudgement);
+			let registrar = Registrars::<T>::get()
+				.get(attestation.registrar_index as usize)
+				.and_then(Option::as_ref)
+				.map(|r| r.account.clone())
+				.ok_or(Error::<T>::InvalidIndex)?;
+			Self::validate_signature(
+				&Self::attestation_payload(&attestation),
+				&signature,
+				&registrar,
+			)?;
+
+			let JudgementAttestation {
+				registrar_index: reg_index,
+				target,
+				judgement,
+				identity,
+				expires_at,
+				nonce,
+			} = attestation;
+			let mut id = IdentityOf::<T>::get(&target).ok_or(Error::<T>::InvalidTarget)?;
+			if T::Hashing::hash_of(&id.info) != identity {
+				return Err(Error::<T>::JudgementForDifferentIdentity.into())
+			}
+
+			if let Some(previous) = AttestationNonce::<T>::get(&target, reg_index) {
+				ensure!(nonce > previous, Error::<T>::StaleAttestation);
+			}
+
+			let previous_expiry = JudgementExpiry::<T>::get(&target, reg_index);
+
+			let item = (reg_index, judgement);
+			match id.judgements.binary_search_by_key(&reg_index, |x| x.0) {
+				Ok(position) => {
+					match id.judgements[position].1 {
+						Judgement::FeePaid(fee) => {
+							T::Currency::repatriate_reserved(
+								&target,
+								&registrar,
+								fee,
+								BalanceStatus::Free,
+							)
+							.map_err(|_| Error::<T>::JudgementPaymentFailed)?;
+						},
+						Judgement::Erroneous => return Err(Error::<T>::StickyJudgement.into()),
+						Judgement::Unknown | Judgement::OutOfDate => {},
+						// Only a judgement which is itself attested has an expiry, as the expiry
+						// is cleared whenever the judgement is replaced or dropped.
+						_ if previous_expiry.is_some() => {},
+						_ => return Err(Error::<T>::JudgementGiven.into()),
+					}
+					id.judgements[position] = item
+				},
+				Err(position) => id
+					.judgements
+					.try_insert(position, item)
+					.map_err(|_| Error::<T>::TooManyRegistrars)?,
+			}
+
+			Self::clear_judgement_expiry(&target, reg_index);
+			ExpiringJudgements::<T>::try_append(expires_at, (target.clone(), reg_index))
+				.map_err(|_| Error::<T>::TooManyExpiries)?;
+			JudgementExpiry::<T>::insert(&target, reg_index, expires_at);
+			AttestationNonce::<T>::insert(&target, reg_index, nonce);
+
+			let judgements = id.judgements.len();
+			IdentityOf::<T>::insert(&target, id);
+			Self::deposit_event(Event::JudgementAttested {
+				target,
+				registrar_index: reg_index,
+				expires_at,
+			});
+
+			Ok((Some(T::WeightInfo::submit_attestation(judgements as u32)), Pays::No).into())
+		}
 	}
 }
 
@@ -1503,6 +1739,61 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// The payload a registrar signs to attest a judgement, which is the `blake2_256` hash of the
+	/// attestation encoded along with the genesis hash of the chain.
+	pub fn attestation_payload(attestation: &JudgementAttestationOf<T>) -> [u8; 32] {
+		let genesis = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+		(b"identity/attestation", genesis, attestation).using_encoded(blake2_256)
+	}
+
+	/// Stop the attested judgement of registrar `reg_index` upon `who` from expiring, if any.
+	///
+	/// Must be called whenever a judgement is replaced or dropped, so that an expiry is only ever
+	/// recorded for a judgement which is still attested.
+	fn clear_judgement_expiry(who: &T::AccountId, reg_index: RegistrarIndex) {
+		if let Some(expires_at) = JudgementExpiry::<T>::take(who, reg_index) {
+			ExpiringJudgements::<T>::mutate_exists(expires_at, |maybe_expiring| {
+				if let Some(expiring) = maybe_expiring {
+					expiring.retain(|(account, index)| account != who || *index != reg_index);
+					if expiring.is_empty() {
+						*maybe_expiring = None;
+					}
+				}
+			});
+		}
+	}
+
+	/// The weight of clearing the expiry of `judgements` judgements.
+	fn clear_judgement_expiries_weight(judgements: u32) -> Weight {
+		let ops = 2u64.saturating_mul(judgements.into());
+		T::DbWeight::get().reads_writes(ops, ops)
+	}
+
+	/// Downgrade the attested judgements expiring at `now` to `OutOfDate`.
+	fn expire_judgements(now: BlockNumberFor<T>) -> Weight {
+		let expiring = ExpiringJudgements::<T>::take(now);
+		for (who, reg_index) in expiring.iter() {
+			if JudgementExpiry::<T>::take(who, reg_index) != Some(now) {
+				continue
+			}
+			let Some(mut id) = IdentityOf::<T>::get(who) else { continue };
+			let Ok(position) = id.judgements.binary_search_by_key(reg_index, |x| x.0) else {
+				continue
+			};
+			// A request for a new judgement made since the attestation is kept.
+			if id.judgements[position].1.is_sticky() {
+				continue
+			}
+			id.judgements[position].1 = Judgement::OutOfDate;
+			IdentityOf::<T>::insert(who, id);
+			Self::deposit_event(Event::JudgementExpired {
+				target: who.clone(),
+				registrar_index: *reg_index,
+			});
+		}
+		T::WeightInfo::expire_judgements(expiring.len() as u32)
+	}
+
 	/// A username has met all conditions. Insert the relevant storage items.
 	pub fn insert_username(who: &T::AccountId, username: Username<T>, provider: ProviderOf<T>) {
 		// Check if they already have a primary. If so, leave it. If not, set it.
@@ -1561,6 +1852,11 @@ impl<T: Config> Pallet<T> {
 			SuperOf::<T>::remove(sub);
 		}
 
+		// judgement expiries
+		for (reg_index, _) in id.judgements.iter() {
+			Self::clear_judgement_expiry(who, *reg_index);
+		}
+
 		// unreserve any deposits
 		let deposit = id.total_deposit().saturating_add(subs_deposit);
 		let err_amount = T::Currency::unreserve(&who, deposit);
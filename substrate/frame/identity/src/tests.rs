@@ -25,8 +25,10 @@ use crate::{
 
 use codec::{Decode, Encode};
 use frame_support::{
-	assert_err, assert_noop, assert_ok, derive_impl, parameter_types,
-	traits::{ConstU32, ConstU64, Get},
+	assert_err, assert_noop, assert_ok, derive_impl,
+	dispatch::Pays,
+	parameter_types,
+	traits::{ConstU32, ConstU64, Get, Hooks},
 	BoundedVec,
 };
 use frame_system::EnsureRoot;
@@ -82,6 +84,7 @@ impl pallet_identity::Config for Test {
 	type MaxSubAccounts = ConstU32<2>;
 	type IdentityInformation = IdentityInfo<MaxAdditionalFields>;
 	type MaxRegistrars = MaxRegistrars;
+	type MaxExpiriesPerBlock = ConstU32<2>;
 	type RegistrarOrigin = EnsureRoot<Self::AccountId>;
 	type ForceOrigin = EnsureRoot<Self::AccountId>;
 	type OffchainSignature = MultiSignature;
@@ -2235,3 +2238,511 @@ fn unbind_dangling_username_defensive_should_panic() {
 		);
 	});
 }
+
+// Adds a registrar signing off-chain and returns its public key and account.
+fn add_attesting_registrar() -> (sp_core::sr25519::Public, AccountIdOf<Test>) {
+	let public = sr25519_generate(0.into(), None);
+	let registrar: AccountIdOf<Test> = MultiSigner::Sr25519(public).into_account().into();
+	assert_ok!(Identity::add_registrar(RuntimeOrigin::root(), registrar.clone()));
+	(public, registrar)
+}
+
+fn attestation_of(
+	registrar_index: RegistrarIndex,
+	target: &AccountIdOf<Test>,
+	judgement: Judgement<u64>,
+	expires_at: u64,
+	nonce: u64,
+) -> JudgementAttestationOf<Test> {
+	JudgementAttestation {
+		registrar_index,
+		target: target.clone(),
+		judgement,
+		identity: BlakeTwo256::hash_of(&infoof_ten()),
+		expires_at,
+		nonce,
+	}
+}
+
+fn sign_attestation(
+	public: &sp_core::sr25519::Public,
+	attestation: &JudgementAttestationOf<Test>,
+) -> MultiSignature {
+	let payload = Identity::attestation_payload(attestation);
+	MultiSignature::Sr25519(sr25519_sign(0.into(), public, &payload[..]).unwrap())
+}
+
+fn run_to(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Identity::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn submit_attestation_should_work() {
+	new_test_ext().execute_with(|| {
+		let [_, _, three, _, ten, _, _, _] = accounts();
+		let (public, registrar) = add_attesting_registrar();
+		assert_ok!(Identity::set_identity(
+			RuntimeOrigin::signed(ten.clone()),
+			Box::new(infoof_ten())
+		));
+
+		let attestation = attestation_of(0, &ten, Judgement::Reasonable, 10, 1);
+		let signature = sign_attestation(&public, &attestation);
+		// anyone may relay the attestation, free of charge.
+		let post_info = Identity::submit_attestation(
+			RuntimeOrigin::signed(three.clone()),
+			attestation,
+			signature,
+		)
+		.unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+
+		assert_eq!(
+			IdentityOf::<Test>::get(&ten).unwrap().judgements,
+			vec![(0, Judgement::Reasonable)]
+		);
+		assert_eq!(JudgementExpiry::<Test>::get(&ten, 0), Some(10));
+		assert_eq!(ExpiringJudgements::<Test>::get(10).into_inner(), vec![(ten.clone(), 0)]);
+		System::assert_last_event(
+			Event::JudgementAttested { target: ten.clone(), registrar_index: 0, expires_at: 10 }
+				.into(),
+		);
+		assert_eq!(Balances::free_balance(&registrar), 0);
+	});
+}
+
+#[test]
+fn submit_attestation_checks_attestation() {
+	new_test_ext().execute_with(|| {
+		let [_, _, three, _, ten, twenty, _, _] = accounts();
+		let (public, _) = add_attesting_registrar();
+		assert_ok!(Identity::set_identity(
+			RuntimeOrigin::signed(ten.clone()),
+			Box::new(infoof_ten())
+		));
+		let relayer = RuntimeOrigin::signed(three.clone());
+
+		// signed by another key.
+		let other = sr25519_generate(1.into(), None);
+		let attestation = attestation_of(0, &ten, Judgement::Reasonable, 10, 1);
+		let signature = sign_attestation(&other, &attestation);
+		assert_noop!(
+			Identity::submit_attestation(relayer.clone(), attestation, signature),
+			Error::<Test>::InvalidSignature
+		);
+
+		// tampered with after signing.
+		let attestation = attestation_of(0, &ten, Judgement::Reasonable, 10, 1);
+		let signature = sign_attestation(&public, &attestation);
+		let tampered = attestation_of(0, &ten, Judgement::KnownGood, 10, 1);
+		assert_noop!(
+			Identity::submit_attestation(relayer.clone(), tampered, signature),
+			Error::<Test>::InvalidSignature
+		);
+
+		let attestation = attestation_of(1, &ten, Judgement::Reasonable, 10, 1);
+		let signature = sign_attestation(&public, &attestation);
+		assert_noop!(
+			Identity::submit_attestation(relayer.clone(), attestation, signature),
+			Error::<Test>::InvalidIndex
+		);
+
+		let attestation = attestation_of(0, &ten, Judgement::Reasonable, 1, 1);
+		let signature = sign_attestation(&public, &attestation);
+		assert_noop!(
+			Identity::submit_attestation(relayer.clone(), attestation, signature),
+			Error::<Test>::AttestationExpired
+		);
+
+		for judgement in [Judgement::Erroneous, Judgement::FeePaid(1)] {
+			let attestation = attestation_of(0, &ten, judgement, 10, 1);
+			let signature = sign_attestation(&public, &attestation);
+			assert_noop!(
+				Identity::submit_attestation(relayer.clone(), attestation, signature),
+				Error::<Test>::InvalidJudgement
+			);
+		}
+
+		let attestation = attestation_of(0, &twenty, Judgement::Reasonable, 10, 1);
+		let signature = sign_attestation(&public, &attestation);
+		assert_noop!(
+			Identity::submit_attestation(relayer.clone(), attestation, signature),
+			Error::<Test>::InvalidTarget
+		);
+
+		let mut attestation = attestation_of(0, &ten, Judgement::Reasonable, 10, 1);
+		attestation.identity = H256::random();
+		let signature = sign_attestation(&public, &attestation);
+		assert_noop!(
+			Identity::submit_attestation(relayer, attestation, signature),
+			Error::<Test>::JudgementForDifferentIdentity
+		);
+	});
+}
+
+#[test]
+fn attestation_cannot_be_replayed() {
+	new_test_ext().execute_with(|| {
+		let [_, _, three, _, ten, _, _, _] = accounts();
+		let (public, _) = add_attesting_registrar();
+		assert_ok!(Identity::set_identity(
+			RuntimeOrigin::signed(ten.clone()),
+			Box::new(infoof_ten())
+		));
+		let relayer = RuntimeOrigin::signed(three.clone());
+
+		let first = attestation_of(0, &ten, Judgement::KnownGood, 10, 1);
+		let first_signature = sign_attestation(&public, &first);
+		assert_ok!(Identity::submit_attestation(
+			relayer.clone(),
+			first.clone(),
+			first_signature.clone()
+		));
+
+		// a renewal must have a greater nonce.
+		let renewal = attestation_of(0, &ten, Judgement::LowQuality, 20, 2);
+		let renewal_signature = sign_attestation(&public, &renewal);
+		assert_ok!(Identity::submit_attestation(relayer.clone(), renewal, renewal_signature));
+		assert_noop!(
+			Identity::submit_attestation(relayer.clone(), first, first_signature),
+			Error::<Test>::StaleAttestation
+		);
+		assert!(ExpiringJudgements::<Test>::get(10).is_empty());
+		assert_eq!(ExpiringJudgements::<Test>::get(20).into_inner(), vec![(ten.clone(), 0)]);
+		assert_eq!(
+			IdentityOf::<Test>::get(&ten).unwrap().judgements,
+			vec![(0, Judgement::LowQuality)]
+		);
+	});
+}
+
+#[test]
+fn attestation_cannot_be_replayed_once_judgement_is_dropped() {
+	new_test_ext().execute_with(|| {
+		let [_, _, three, _, ten, _, _, _] = accounts();
+		let (public, registrar) = add_attesting_registrar();
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(registrar.clone()), 0, 10));
+		assert_ok!(Identity::set_identity(
+			RuntimeOrigin::signed(ten.clone()),
+			Box::new(infoof_ten())
+		));
+		let relayer = RuntimeOrigin::signed(three.clone());
+
+		let attestation = attestation_of(0, &ten, Judgement::KnownGood, 10, 1);
+		let signature = sign_attestation(&public, &attestation);
+		assert_ok!(Identity::submit_attestation(
+			relayer.clone(),
+			attestation.clone(),
+			signature.clone()
+		));
+
+		// the registrar revokes the judgement.
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(registrar.clone()),
+			0,
+			ten.clone(),
+			Judgement::OutOfDate,
+			BlakeTwo256::hash_of(&infoof_ten())
+		));
+		assert_noop!(
+			Identity::submit_attestation(relayer.clone(), attestation.clone(), signature.clone()),
+			Error::<Test>::StaleAttestation
+		);
+		assert_eq!(
+			IdentityOf::<Test>::get(&ten).unwrap().judgements,
+			vec![(0, Judgement::OutOfDate)]
+		);
+
+		// the fee of a new request is not paid for the old judgement.
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(ten.clone()), 0, 10));
+		assert_noop!(
+			Identity::submit_attestation(relayer.clone(), attestation, signature),
+			Error::<Test>::StaleAttestation
+		);
+		assert_eq!(
+			IdentityOf::<Test>::get(&ten).unwrap().judgements,
+			vec![(0, Judgement::FeePaid(10))]
+		);
+		assert_eq!(Balances::free_balance(&registrar), 0);
+
+		// a new attestation is accepted.
+		let attestation = attestation_of(0, &ten, Judgement::KnownGood, 10, 2);
+		let signature = sign_attestation(&public, &attestation);
+		assert_ok!(Identity::submit_attestation(relayer, attestation, signature));
+		assert_eq!(Balances::free_balance(&registrar), 10);
+		assert_eq!(AttestationNonce::<Test>::get(&ten, 0), Some(2));
+	});
+}
+
+#[test]
+fn attested_judgement_expires() {
+	new_test_ext().execute_with(|| {
+		let [_, _, three, _, ten, _, _, _] = accounts();
+		let (public, _) = add_attesting_registrar();
+		assert_ok!(Identity::set_identity(
+			RuntimeOrigin::signed(ten.clone()),
+			Box::new(infoof_ten())
+		));
+
+		let attestation = attestation_of(0, &ten, Judgement::KnownGood, 10, 1);
+		let signature = sign_attestation(&public, &attestation);
+		assert_ok!(Identity::submit_attestation(
+			RuntimeOrigin::signed(three.clone()),
+			attestation,
+			signature
+		));
+
+		run_to(9);
+		assert_eq!(
+			IdentityOf::<Test>::get(&ten).unwrap().judgements,
+			vec![(0, Judgement::KnownGood)]
+		);
+		run_to(10);
+		assert_eq!(
+			IdentityOf::<Test>::get(&ten).unwrap().judgements,
+			vec![(0, Judgement::OutOfDate)]
+		);
+		System::assert_last_event(
+			Event::JudgementExpired { target: ten.clone(), registrar_index: 0 }.into(),
+		);
+		assert_eq!(JudgementExpiry::<Test>::get(&ten, 0), None);
+		assert!(!ExpiringJudgements::<Test>::contains_key(10));
+
+		// the registrar may attest the identity again.
+		let attestation = attestation_of(0, &ten, Judgement::KnownGood, 30, 2);
+		let signature = sign_attestation(&public, &attestation);
+		assert_ok!(Identity::submit_attestation(
+			RuntimeOrigin::signed(three.clone()),
+			attestation,
+			signature
+		));
+		assert_eq!(
+			IdentityOf::<Test>::get(&ten).unwrap().judgements,
+			vec![(0, Judgement::KnownGood)]
+		);
+	});
+}
+
+#[test]
+fn attestation_pays_requested_judgement() {
+	new_test_ext().execute_with(|| {
+		let [_, _, three, _, ten, _, _, _] = accounts();
+		let (public, registrar) = add_attesting_registrar();
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(registrar.clone()), 0, 10));
+		assert_ok!(Identity::set_identity(
+			RuntimeOrigin::signed(ten.clone()),
+			Box::new(infoof_ten())
+		));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(ten.clone()), 0, 10));
+
+		let attestation = attestation_of(0, &ten, Judgement::Reasonable, 10, 1);
+		let signature = sign_attestation(&public, &attestation);
+		assert_ok!(Identity::submit_attestation(
+			RuntimeOrigin::signed(three.clone()),
+			attestation,
+			signature
+		));
+		assert_eq!(Balances::free_balance(&registrar), 10);
+		assert_eq!(Balances::free_balance(&ten), 1000 - id_deposit(&infoof_ten()) - 10);
+
+		// a pending request is not downgraded.
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(ten.clone()), 0, 10));
+		run_to(10);
+		assert_eq!(
+			IdentityOf::<Test>::get(&ten).unwrap().judgements,
+			vec![(0, Judgement::FeePaid(10))]
+		);
+	});
+}
+
+#[test]
+fn provided_judgement_does_not_expire() {
+	new_test_ext().execute_with(|| {
+		let [_, _, three, _, ten, _, _, _] = accounts();
+		let (public, registrar) = add_attesting_registrar();
+		assert_ok!(Identity::set_identity(
+			RuntimeOrigin::signed(ten.clone()),
+			Box::new(infoof_ten())
+		));
+
+		let attestation = attestation_of(0, &ten, Judgement::KnownGood, 10, 1);
+		let signature = sign_attestation(&public, &attestation);
+		assert_ok!(Identity::submit_attestation(
+			RuntimeOrigin::signed(three.clone()),
+			attestation.clone(),
+			signature.clone()
+		));
+
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(registrar),
+			0,
+			ten.clone(),
+			Judgement::LowQuality,
+			BlakeTwo256::hash_of(&infoof_ten())
+		));
+		assert_eq!(JudgementExpiry::<Test>::get(&ten, 0), None);
+		assert!(ExpiringJudgements::<Test>::get(10).is_empty());
+
+		// the attestation cannot be submitted again.
+		assert_noop!(
+			Identity::submit_attestation(
+				RuntimeOrigin::signed(three.clone()),
+				attestation,
+				signature
+			),
+			Error::<Test>::StaleAttestation
+		);
+		// nor can a new one override the judgement of the registrar.
+		let attestation = attestation_of(0, &ten, Judgement::KnownGood, 10, 2);
+		let signature = sign_attestation(&public, &attestation);
+		assert_noop!(
+			Identity::submit_attestation(RuntimeOrigin::signed(three), attestation, signature),
+			Error::<Test>::JudgementGiven
+		);
+
+		run_to(10);
+		assert_eq!(
+			IdentityOf::<Test>::get(&ten).unwrap().judgements,
+			vec![(0, Judgement::LowQuality)]
+		);
+	});
+}
+
+#[test]
+fn expiries_per_block_are_limited() {
+	new_test_ext().execute_with(|| {
+		let [_, _, three, _, ten, twenty, thirty, _] = accounts();
+		let (public, _) = add_attesting_registrar();
+		for who in [&ten, &twenty, &thirty] {
+			assert_ok!(Identity::set_identity(
+				RuntimeOrigin::signed(who.clone()),
+				Box::new(infoof_ten())
+			));
+		}
+
+		for who in [&twenty, &thirty] {
+			let attestation = attestation_of(0, who, Judgement::Reasonable, 10, 1);
+			let signature = sign_attestation(&public, &attestation);
+			assert_ok!(Identity::submit_attestation(
+				RuntimeOrigin::signed(three.clone()),
+				attestation,
+				signature
+			));
+		}
+		let attestation = attestation_of(0, &ten, Judgement::Reasonable, 10, 1);
+		let signature = sign_attestation(&public, &attestation);
+		assert_noop!(
+			Identity::submit_attestation(
+				RuntimeOrigin::signed(three.clone()),
+				attestation,
+				signature
+			),
+			Error::<Test>::TooManyExpiries
+		);
+
+		let attestation = attestation_of(0, &ten, Judgement::Reasonable, 11, 1);
+		let signature = sign_attestation(&public, &attestation);
+		assert_ok!(Identity::submit_attestation(
+			RuntimeOrigin::signed(three),
+			attestation,
+			signature
+		));
+	});
+}
+
+#[test]
+fn dropped_judgements_stop_expiring() {
+	new_test_ext().execute_with(|| {
+		let [_, _, three, _, ten, _, _, _] = accounts();
+		let (public, _) = add_attesting_registrar();
+		let attest = |expires_at: u64, nonce: u64| {
+			let attestation = attestation_of(0, &ten, Judgement::KnownGood, expires_at, nonce);
+			let signature = sign_attestation(&public, &attestation);
+			Identity::submit_attestation(
+				RuntimeOrigin::signed(three.clone()),
+				attestation,
+				signature,
+			)
+		};
+		let set_identity =
+			|| Identity::set_identity(RuntimeOrigin::signed(ten.clone()), Box::new(infoof_ten()));
+
+		// setting the identity again drops the attested judgement.
+		assert_ok!(set_identity());
+		assert_ok!(attest(10, 1));
+		assert_ok!(set_identity());
+		assert!(IdentityOf::<Test>::get(&ten).unwrap().judgements.is_empty());
+		assert_eq!(JudgementExpiry::<Test>::get(&ten, 0), None);
+		assert!(!ExpiringJudgements::<Test>::contains_key(10));
+
+		// so does clearing it.
+		assert_ok!(attest(8, 2));
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(ten.clone())));
+		assert_eq!(JudgementExpiry::<Test>::get(&ten, 0), None);
+		assert!(!ExpiringJudgements::<Test>::contains_key(8));
+
+		// and killing it.
+		assert_ok!(set_identity());
+		assert_ok!(attest(6, 3));
+		assert_ok!(Identity::kill_identity(RuntimeOrigin::root(), ten.clone()));
+		assert_eq!(JudgementExpiry::<Test>::get(&ten, 0), None);
+		assert!(!ExpiringJudgements::<Test>::contains_key(6));
+
+		// a judgement given later is not downgraded by any of them.
+		Balances::make_free_balance_be(&ten, 1000);
+		assert_ok!(set_identity());
+		assert_ok!(attest(12, 4));
+		run_to(11);
+		assert_eq!(
+			IdentityOf::<Test>::get(&ten).unwrap().judgements,
+			vec![(0, Judgement::KnownGood)]
+		);
+		run_to(12);
+		assert_eq!(
+			IdentityOf::<Test>::get(&ten).unwrap().judgements,
+			vec![(0, Judgement::OutOfDate)]
+		);
+	});
+}
+
+#[test]
+fn requested_judgement_stops_expiry() {
+	new_test_ext().execute_with(|| {
+		let [_, _, three, _, ten, _, _, _] = accounts();
+		let (public, registrar) = add_attesting_registrar();
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(registrar.clone()), 0, 10));
+		assert_ok!(Identity::set_identity(
+			RuntimeOrigin::signed(ten.clone()),
+			Box::new(infoof_ten())
+		));
+
+		let attestation = attestation_of(0, &ten, Judgement::Reasonable, 10, 1);
+		let signature = sign_attestation(&public, &attestation);
+		assert_ok!(Identity::submit_attestation(
+			RuntimeOrigin::signed(three.clone()),
+			attestation,
+			signature
+		));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(ten.clone()), 0, 10));
+		assert_eq!(JudgementExpiry::<Test>::get(&ten, 0), None);
+		assert!(!ExpiringJudgements::<Test>::contains_key(10));
+
+		// the judgement given for the request cannot be replaced by an attestation.
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(registrar),
+			0,
+			ten.clone(),
+			Judgement::LowQuality,
+			BlakeTwo256::hash_of(&infoof_ten())
+		));
+		let attestation = attestation_of(0, &ten, Judgement::KnownGood, 20, 2);
+		let signature = sign_attestation(&public, &attestation);
+		assert_noop!(
+			Identity::submit_attestation(RuntimeOrigin::signed(three), attestation, signature),
+			Error::<Test>::JudgementGiven
+		);
+	});
+}
@@ -242,6 +242,37 @@ impl<Balance: Encode + Decode + MaxEncodedLen + Copy + Clone + Debug + Eq + Part
 	}
 }
 
+/// A judgement signed off-chain by a registrar, which anyone may submit on its behalf.
+///
+/// The judgement only holds until `expires_at`, after which it is downgraded to
+/// [`Judgement::OutOfDate`].
+#[derive(Clone, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct JudgementAttestation<
+	AccountId,
+	Balance: Encode + Decode + MaxEncodedLen + Copy + Clone + Debug + Eq + PartialEq,
+	Hash,
+	BlockNumber,
+> {
+	/// The index of the registrar providing the judgement.
+	pub registrar_index: RegistrarIndex,
+
+	/// The account whose identity the judgement is upon.
+	pub target: AccountId,
+
+	/// The judgement of the registrar about `target`.
+	pub judgement: Judgement<Balance>,
+
+	/// The hash of the identity information the judgement is provided for.
+	pub identity: Hash,
+
+	/// The block at which the judgement expires.
+	pub expires_at: BlockNumber,
+
+	/// The nonce of the attestation, which must exceed that of any attestation of the registrar
+	/// upon `target` submitted before.
+	pub nonce: u64,
+}
+
 /// Information concerning the identity of the controller of an account.
 pub trait IdentityInformationProvider:
 	Encode + Decode + MaxEncodedLen + Clone + Debug + Eq + PartialEq + TypeInfo + Default
@@ -45,6 +45,23 @@ fn setup_multi<T: Config>(
 	Ok((signatories, Box::new(call)))
 }
 
+/// Create a weighted multisig of `s` signatories with a weight of one each, which needs the
+/// approval of all of them.
+fn setup_weighted_multi<T: Config>(
+	s: u32,
+) -> Result<(T::AccountId, Vec<T::AccountId>), &'static str> {
+	let (signatories, _) = setup_multi::<T>(s, 0)?;
+	let weighted = signatories.iter().cloned().map(|who| (who, 1)).collect();
+	let index = WeightedMultisigCount::<T>::get();
+	Multisig::<T>::create_weighted_multisig(
+		RawOrigin::Signed(signatories[0].clone()).into(),
+		weighted,
+		s,
+	)
+	.map_err(|_| "weighted multisig should be created")?;
+	Ok((Multisig::<T>::weighted_multi_account_id(index), signatories))
+}
+
 #[benchmarks]
 mod benchmarks {
 	use super::*;
@@ -359,5 +376,189 @@ mod benchmarks {
 		Ok(())
 	}
 
+	/// `s`: Signatories
+	#[benchmark]
+	fn create_weighted_multisig(
+		s: Linear<1, { T::MaxSignatories::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let (signatories, _) = setup_multi::<T>(s, 0)?;
+		let weighted = signatories.iter().cloned().map(|who| (who, 1)).collect();
+		let caller = signatories[0].clone();
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), weighted, s);
+
+		assert!(WeightedMultisigs::<T>::contains_key(Multisig::<T>::weighted_multi_account_id(0)));
+
+		Ok(())
+	}
+
+	/// `s`: Signatories
+	#[benchmark]
+	fn update_weighted_multisig(
+		s: Linear<1, { T::MaxSignatories::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let (multisig, signatories) = setup_weighted_multi::<T>(T::MaxSignatories::get())?;
+		let weighted: Vec<_> =
+			signatories.into_iter().take(s as usize).map(|who| (who, 2)).collect();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(multisig.clone()), weighted, s);
+
+		let details = WeightedMultisigs::<T>::get(&multisig).ok_or("multisig not created")?;
+		assert_eq!(details.signatories.len() as u32, s);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn destroy_weighted_multisig() -> Result<(), BenchmarkError> {
+		let (multisig, _) = setup_weighted_multi::<T>(T::MaxSignatories::get())?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(multisig.clone()));
+
+		assert!(!WeightedMultisigs::<T>::contains_key(multisig));
+
+		Ok(())
+	}
+
+	/// `z`: Transaction Length
+	/// `s`: Signatories, need at least 2 people (so we don't complete the proposal)
+	#[benchmark]
+	fn as_weighted_multi_create(
+		s: Linear<2, { T::MaxSignatories::get() }>,
+		z: Linear<0, 10_000>,
+	) -> Result<(), BenchmarkError> {
+		let (multisig, mut signatories) = setup_weighted_multi::<T>(s)?;
+		let (_, call) = setup_multi::<T>(0, z)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let caller = signatories.pop().ok_or("signatories should have len 2 or more")?;
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		as_weighted_multi(RawOrigin::Signed(caller), multisig.clone(), call, Weight::zero());
+
+		assert!(WeightedProposals::<T>::contains_key(multisig, call_hash));
+
+		Ok(())
+	}
+
+	/// `z`: Transaction Length
+	/// `s`: Signatories, need at least 3 people (so we don't complete the proposal)
+	#[benchmark]
+	fn as_weighted_multi_approve(
+		s: Linear<3, { T::MaxSignatories::get() }>,
+		z: Linear<0, 10_000>,
+	) -> Result<(), BenchmarkError> {
+		let (multisig, signatories) = setup_weighted_multi::<T>(s)?;
+		let (_, call) = setup_multi::<T>(0, z)?;
+		let call_hash = call.using_encoded(blake2_256);
+		// Create the proposal
+		Multisig::<T>::as_weighted_multi(
+			RawOrigin::Signed(signatories[0].clone()).into(),
+			multisig.clone(),
+			call.clone(),
+			Weight::zero(),
+		)?;
+		let caller = signatories[1].clone();
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		as_weighted_multi(RawOrigin::Signed(caller), multisig.clone(), call, Weight::zero());
+
+		let proposal =
+			WeightedProposals::<T>::get(multisig, call_hash).ok_or("proposal not created")?;
+		assert_eq!(proposal.approvals.len(), 2);
+
+		Ok(())
+	}
+
+	/// `z`: Transaction Length
+	/// `s`: Signatories, need at least 2 people
+	#[benchmark]
+	fn as_weighted_multi_complete(
+		s: Linear<2, { T::MaxSignatories::get() }>,
+		z: Linear<0, 10_000>,
+	) -> Result<(), BenchmarkError> {
+		let (multisig, mut signatories) = setup_weighted_multi::<T>(s)?;
+		let (_, call) = setup_multi::<T>(0, z)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let caller = signatories.pop().ok_or("signatories should have len 2 or more")?;
+		// Approve the proposal by everyone but the caller
+		for signatory in signatories {
+			Multisig::<T>::approve_as_weighted_multi(
+				RawOrigin::Signed(signatory).into(),
+				multisig.clone(),
+				call_hash,
+			)?;
+		}
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		as_weighted_multi(RawOrigin::Signed(caller), multisig.clone(), call, Weight::MAX);
+
+		assert!(!WeightedProposals::<T>::contains_key(multisig, call_hash));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_as_weighted_multi() -> Result<(), BenchmarkError> {
+		let (multisig, signatories) = setup_weighted_multi::<T>(T::MaxSignatories::get())?;
+		let (_, call) = setup_multi::<T>(0, 10_000)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let caller = signatories[0].clone();
+		// Create the proposal
+		Multisig::<T>::approve_as_weighted_multi(
+			RawOrigin::Signed(caller.clone()).into(),
+			multisig.clone(),
+			call_hash,
+		)?;
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), multisig.clone(), call_hash);
+
+		assert!(!WeightedProposals::<T>::contains_key(multisig, call_hash));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn clear_expired_weighted_proposal() -> Result<(), BenchmarkError> {
+		let (multisig, signatories) = setup_weighted_multi::<T>(T::MaxSignatories::get())?;
+		let (_, call) = setup_multi::<T>(0, 10_000)?;
+		let call_hash = call.using_encoded(blake2_256);
+		// Create the proposal
+		Multisig::<T>::approve_as_weighted_multi(
+			RawOrigin::Signed(signatories[0].clone()).into(),
+			multisig.clone(),
+			call_hash,
+		)?;
+		let proposal =
+			WeightedProposals::<T>::get(&multisig, call_hash).ok_or("proposal not created")?;
+		T::BlockNumberProvider::set_block_number(proposal.expiry);
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), multisig.clone(), call_hash);
+
+		assert!(!WeightedProposals::<T>::contains_key(multisig, call_hash));
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Multisig, crate::tests::new_test_ext(), crate::tests::Test);
 }
@@ -39,6 +39,26 @@
 //!   number of signed origins.
 //! * `approve_as_multi` - Approve a call from a composite origin.
 //! * `cancel_as_multi` - Cancel a call from a composite origin.
+//! * `create_weighted_multisig` - Create a weighted multisig account with a stable account id.
+//! * `update_weighted_multisig` - Change the signatories, their weights or the threshold of a
+//!   weighted multisig account. Must be dispatched by the weighted multisig account itself.
+//! * `destroy_weighted_multisig` - Remove a weighted multisig account. Must be dispatched by the
+//!   weighted multisig account itself.
+//! * `as_weighted_multi` - Approve and if possible dispatch a call from a weighted multisig
+//!   account.
+//! * `approve_as_weighted_multi` - Approve a call from a weighted multisig account.
+//! * `cancel_as_weighted_multi` - Cancel a proposal of a weighted multisig account.
+//! * `clear_expired_weighted_proposal` - Remove an expired proposal of a weighted multisig account.
+//!
+//! ### Weighted multisig accounts
+//!
+//! Besides the stateless composite accounts derived from the signatories and the threshold, the
+//! pallet supports stateful weighted multisig accounts. Their signatories, the weight of each
+//! signatory's approval and the threshold are stored on-chain under an account id that never
+//! changes. Signatories can thus be rotated without moving funds: `update_weighted_multisig` is
+//! itself a call that has to be approved by the weighted multisig account. Proposals of weighted
+//! multisig accounts expire after `ProposalLifetime`, after which anyone can remove them and
+//! return the deposit to the proposer.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -120,6 +140,62 @@ where
 	pub approvals: BoundedVec<AccountId, MaxApprovals>,
 }
 
+/// A stateful multisig account whose signatories carry individual approval weights.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(MaxSignatories))]
+pub struct WeightedMultisig<Balance, AccountId, MaxSignatories>
+where
+	MaxSignatories: Get<u32>,
+{
+	/// The signatories together with the weight of their approval. Always sorted by account.
+	pub signatories: BoundedVec<(AccountId, u32), MaxSignatories>,
+	/// The total weight of approvals needed to dispatch a call.
+	pub threshold: u32,
+	/// The account who created the multisig.
+	pub depositor: AccountId,
+	/// The amount held in reserve of the `depositor`, to be returned once the multisig is
+	/// destroyed.
+	pub deposit: Balance,
+}
+
+impl<Balance, AccountId: Ord, MaxSignatories: Get<u32>>
+	WeightedMultisig<Balance, AccountId, MaxSignatories>
+{
+	/// The approval weight of `who`, if it is a signatory.
+	pub fn weight_of(&self, who: &AccountId) -> Option<u32> {
+		self.signatories
+			.binary_search_by(|(signatory, _)| signatory.cmp(who))
+			.ok()
+			.map(|index| self.signatories[index].1)
+	}
+
+	/// The total weight of the given approvals. Approvals of accounts which are no signatories
+	/// (anymore) are ignored.
+	pub fn approval_weight(&self, approvals: &[AccountId]) -> u32 {
+		approvals
+			.iter()
+			.filter_map(|who| self.weight_of(who))
+			.fold(0, |total, weight| total.saturating_add(weight))
+	}
+}
+
+/// An open proposal of a weighted multisig account.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(MaxApprovals))]
+pub struct WeightedProposal<BlockNumber, Balance, AccountId, MaxApprovals>
+where
+	MaxApprovals: Get<u32>,
+{
+	/// The block number from which on the proposal can no longer be approved.
+	pub expiry: BlockNumber,
+	/// The amount held in reserve of the `depositor`, to be returned once the proposal ends.
+	pub deposit: Balance,
+	/// The account who opened it (i.e. the first to approve it).
+	pub depositor: AccountId,
+	/// The approvals achieved so far, including the depositor. Always sorted.
+	pub approvals: BoundedVec<AccountId, MaxApprovals>,
+}
+
 type CallHash = [u8; 32];
 
 enum CallOrHash<T: Config> {
@@ -164,6 +240,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxSignatories: Get<u32>;
 
+		/// The number of blocks after which a proposal of a weighted multisig account expires.
+		#[pallet::constant]
+		type ProposalLifetime: Get<BlockNumberFor<Self>>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: weights::WeightInfo;
 
@@ -210,6 +290,31 @@ pub mod pallet {
 		Multisig<BlockNumberFor<T>, BalanceOf<T>, T::AccountId, T::MaxSignatories>,
 	>;
 
+	/// The number of weighted multisig accounts created so far. Used to derive the account id of
+	/// the next one.
+	#[pallet::storage]
+	pub type WeightedMultisigCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The weighted multisig accounts.
+	#[pallet::storage]
+	pub type WeightedMultisigs<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		WeightedMultisig<BalanceOf<T>, T::AccountId, T::MaxSignatories>,
+	>;
+
+	/// The open proposals of weighted multisig accounts.
+	#[pallet::storage]
+	pub type WeightedProposals<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		CallHash,
+		WeightedProposal<BlockNumberFor<T>, BalanceOf<T>, T::AccountId, T::MaxSignatories>,
+	>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Threshold must be 2 or greater.
@@ -241,6 +346,17 @@ pub mod pallet {
 		MaxWeightTooLow,
 		/// The data to be stored is already stored.
 		AlreadyStored,
+		/// The sender is not a signatory of the weighted multisig.
+		NotSignatory,
+		/// A signatory of a weighted multisig was given a weight of zero.
+		ZeroWeight,
+		/// The threshold of a weighted multisig must be non-zero and reachable by its
+		/// signatories.
+		InvalidThreshold,
+		/// The proposal has expired and can no longer be approved.
+		ProposalExpired,
+		/// The proposal has not expired yet.
+		ProposalNotExpired,
 	}
 
 	#[pallet::event]
@@ -277,6 +393,41 @@ pub mod pallet {
 			old_deposit: BalanceOf<T>,
 			new_deposit: BalanceOf<T>,
 		},
+		/// A weighted multisig account has been created.
+		WeightedMultisigCreated { creator: T::AccountId, multisig: T::AccountId },
+		/// The signatories or the threshold of a weighted multisig account have been changed.
+		WeightedMultisigUpdated { multisig: T::AccountId, threshold: u32 },
+		/// A weighted multisig account has been destroyed.
+		WeightedMultisigDestroyed { multisig: T::AccountId },
+		/// A new proposal of a weighted multisig account has been opened.
+		NewWeightedProposal {
+			approving: T::AccountId,
+			multisig: T::AccountId,
+			call_hash: CallHash,
+			expiry: BlockNumberFor<T>,
+		},
+		/// A proposal of a weighted multisig account has been approved by someone.
+		WeightedProposalApproval {
+			approving: T::AccountId,
+			multisig: T::AccountId,
+			call_hash: CallHash,
+			approved_weight: u32,
+		},
+		/// A proposal of a weighted multisig account has been executed.
+		WeightedProposalExecuted {
+			approving: T::AccountId,
+			multisig: T::AccountId,
+			call_hash: CallHash,
+			result: DispatchResult,
+		},
+		/// A proposal of a weighted multisig account has been cancelled.
+		WeightedProposalCancelled {
+			cancelling: T::AccountId,
+			multisig: T::AccountId,
+			call_hash: CallHash,
+		},
+		/// An expired proposal of a weighted multisig account has been removed.
+		WeightedProposalExpired { multisig: T::AccountId, call_hash: CallHash },
 	}
 
 	#[pallet::hooks]
@@ -603,6 +754,231 @@ pub mod pallet {
 				},
 			)
 		}
+
+		/// Create a weighted multisig account.
+		///
+		/// Unlike the composite accounts used by `as_multi`, the account id of a weighted multisig
+		/// does not depend on its signatories or threshold. Both can later be changed through
+		/// `update_weighted_multisig` without moving any funds.
+		///
+		/// Payment: `DepositBase` plus `MaxSignatories` times `DepositFactor` is reserved. It is
+		/// returned once the weighted multisig is destroyed.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `signatories`: The accounts who can approve calls, together with the weight of their
+		///   approval. Must be sorted by account and may not contain zero weights.
+		/// - `threshold`: The total weight of approvals needed to dispatch a call.
+		///
+		/// Emits `WeightedMultisigCreated`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(
+			T::WeightInfo::create_weighted_multisig(signatories.len() as u32)
+		)]
+		pub fn create_weighted_multisig(
+			origin: OriginFor<T>,
+			signatories: Vec<(T::AccountId, u32)>,
+			threshold: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let signatories = Self::ensure_valid_weighted_signatories(signatories, threshold)?;
+
+			let index = WeightedMultisigCount::<T>::get();
+			let multisig = Self::weighted_multi_account_id(index);
+			WeightedMultisigCount::<T>::put(index.checked_add(1).ok_or(ArithmeticError::Overflow)?);
+
+			let deposit = Self::weighted_multisig_deposit();
+			T::Currency::reserve(&who, deposit)?;
+
+			WeightedMultisigs::<T>::insert(
+				&multisig,
+				WeightedMultisig { signatories, threshold, depositor: who.clone(), deposit },
+			);
+
+			Self::deposit_event(Event::WeightedMultisigCreated { creator: who, multisig });
+			Ok(())
+		}
+
+		/// Replace the signatories and the threshold of a weighted multisig account.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the weighted multisig account
+		/// itself, i.e. the change needs to be approved through `as_weighted_multi`.
+		///
+		/// Approvals of open proposals given by removed signatories no longer count.
+		///
+		/// - `signatories`: The new signatories together with the weight of their approval. Must be
+		///   sorted by account and may not contain zero weights.
+		/// - `threshold`: The new total weight of approvals needed to dispatch a call.
+		///
+		/// Emits `WeightedMultisigUpdated`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(
+			T::WeightInfo::update_weighted_multisig(signatories.len() as u32)
+		)]
+		pub fn update_weighted_multisig(
+			origin: OriginFor<T>,
+			signatories: Vec<(T::AccountId, u32)>,
+			threshold: u32,
+		) -> DispatchResult {
+			let multisig = ensure_signed(origin)?;
+			let signatories = Self::ensure_valid_weighted_signatories(signatories, threshold)?;
+
+			WeightedMultisigs::<T>::try_mutate(&multisig, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::NotFound)?;
+				details.signatories = signatories;
+				details.threshold = threshold;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::WeightedMultisigUpdated { multisig, threshold });
+			Ok(())
+		}
+
+		/// Destroy a weighted multisig account, returning the deposit to its creator.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the weighted multisig account
+		/// itself. Open proposals can no longer be approved, but can still be cancelled by their
+		/// depositor or cleared once expired.
+		///
+		/// Emits `WeightedMultisigDestroyed`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::destroy_weighted_multisig())]
+		pub fn destroy_weighted_multisig(origin: OriginFor<T>) -> DispatchResult {
+			let multisig = ensure_signed(origin)?;
+			let details = WeightedMultisigs::<T>::take(&multisig).ok_or(Error::<T>::NotFound)?;
+
+			let err_amount = T::Currency::unreserve(&details.depositor, details.deposit);
+			debug_assert!(err_amount.is_zero());
+
+			Self::deposit_event(Event::WeightedMultisigDestroyed { multisig });
+			Ok(())
+		}
+
+		/// Approve a call of a weighted multisig account and dispatch it if the total weight of
+		/// the approvals reaches the threshold.
+		///
+		/// Payment: If this is the first approval, `DepositBase` plus the number of signatories
+		/// times `DepositFactor` is reserved. It is returned once the call is dispatched, the
+		/// proposal is cancelled or it is cleared after expiring.
+		///
+		/// The dispatch origin for this call must be _Signed_ by a signatory of `multisig`.
+		///
+		/// - `multisig`: The weighted multisig account.
+		/// - `call`: The call to be executed.
+		/// - `max_weight`: The maximum weight of `call`.
+		///
+		/// NOTE: Unless this is the final approval, you will generally want to use
+		/// `approve_as_weighted_multi` instead, since it only requires a hash of the call.
+		#[pallet::call_index(8)]
+		#[pallet::weight({
+			let s = T::MaxSignatories::get();
+			let z = call.using_encoded(|d| d.len()) as u32;
+
+			T::WeightInfo::as_weighted_multi_create(s, z)
+				.max(T::WeightInfo::as_weighted_multi_approve(s, z))
+				.max(T::WeightInfo::as_weighted_multi_complete(s, z))
+				.saturating_add(*max_weight)
+		})]
+		pub fn as_weighted_multi(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			call: Box<<T as Config>::RuntimeCall>,
+			max_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::operate_weighted(who, multisig, CallOrHash::Call(*call), max_weight)
+		}
+
+		/// Approve a call of a weighted multisig account without dispatching it.
+		///
+		/// Payment: If this is the first approval, `DepositBase` plus the number of signatories
+		/// times `DepositFactor` is reserved. It is returned once the call is dispatched, the
+		/// proposal is cancelled or it is cleared after expiring.
+		///
+		/// The dispatch origin for this call must be _Signed_ by a signatory of `multisig`.
+		///
+		/// - `multisig`: The weighted multisig account.
+		/// - `call_hash`: The hash of the call to be executed.
+		///
+		/// NOTE: If this is the final approval, you will want to use `as_weighted_multi` instead.
+		#[pallet::call_index(9)]
+		#[pallet::weight({
+			let s = T::MaxSignatories::get();
+
+			T::WeightInfo::as_weighted_multi_create(s, 0)
+				.max(T::WeightInfo::as_weighted_multi_approve(s, 0))
+		})]
+		pub fn approve_as_weighted_multi(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			call_hash: [u8; 32],
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::operate_weighted(who, multisig, CallOrHash::Hash(call_hash), Weight::zero())
+		}
+
+		/// Cancel an open proposal of a weighted multisig account. The deposit reserved for the
+		/// proposal is unreserved on success.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the depositor of the proposal.
+		///
+		/// - `multisig`: The weighted multisig account.
+		/// - `call_hash`: The hash of the call to be executed.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::cancel_as_weighted_multi())]
+		pub fn cancel_as_weighted_multi(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			call_hash: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let proposal =
+				WeightedProposals::<T>::get(&multisig, call_hash).ok_or(Error::<T>::NotFound)?;
+			ensure!(proposal.depositor == who, Error::<T>::NotOwner);
+
+			let err_amount = T::Currency::unreserve(&proposal.depositor, proposal.deposit);
+			debug_assert!(err_amount.is_zero());
+			WeightedProposals::<T>::remove(&multisig, call_hash);
+
+			Self::deposit_event(Event::WeightedProposalCancelled {
+				cancelling: who,
+				multisig,
+				call_hash,
+			});
+			Ok(())
+		}
+
+		/// Remove an expired proposal of a weighted multisig account. The deposit reserved for the
+		/// proposal is returned to its depositor.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `multisig`: The weighted multisig account.
+		/// - `call_hash`: The hash of the call of the proposal.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::clear_expired_weighted_proposal())]
+		pub fn clear_expired_weighted_proposal(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			call_hash: [u8; 32],
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let proposal =
+				WeightedProposals::<T>::get(&multisig, call_hash).ok_or(Error::<T>::NotFound)?;
+			ensure!(
+				T::BlockNumberProvider::current_block_number() >= proposal.expiry,
+				Error::<T>::ProposalNotExpired
+			);
+
+			let err_amount = T::Currency::unreserve(&proposal.depositor, proposal.deposit);
+			debug_assert!(err_amount.is_zero());
+			WeightedProposals::<T>::remove(&multisig, call_hash);
+
+			Self::deposit_event(Event::WeightedProposalExpired { multisig, call_hash });
+			Ok(())
+		}
 	}
 }
 
@@ -746,6 +1122,149 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Derive the account ID of the weighted multisig with the given index.
+	pub fn weighted_multi_account_id(index: u32) -> T::AccountId {
+		let entropy = (b"modlpy/wmultisg", index).using_encoded(blake2_256);
+		Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+			.expect("infinite length input; no invalid inputs for type; qed")
+	}
+
+	fn operate_weighted(
+		who: T::AccountId,
+		multisig: T::AccountId,
+		call_or_hash: CallOrHash<T>,
+		max_weight: Weight,
+	) -> DispatchResultWithPostInfo {
+		let details = WeightedMultisigs::<T>::get(&multisig).ok_or(Error::<T>::NotFound)?;
+		ensure!(details.weight_of(&who).is_some(), Error::<T>::NotSignatory);
+		let signatories_len = details.signatories.len() as u32;
+
+		let (call_hash, call_len, maybe_call) = match call_or_hash {
+			CallOrHash::Call(call) => {
+				let (call_hash, call_len) = call.using_encoded(|d| (blake2_256(d), d.len()));
+				(call_hash, call_len as u32, Some(call))
+			},
+			CallOrHash::Hash(h) => (h, 0, None),
+		};
+
+		let now = T::BlockNumberProvider::current_block_number();
+		let (mut proposal, is_new) = match WeightedProposals::<T>::get(&multisig, call_hash) {
+			Some(proposal) => {
+				ensure!(now < proposal.expiry, Error::<T>::ProposalExpired);
+				(proposal, false)
+			},
+			None => {
+				let deposit =
+					T::DepositBase::get() + T::DepositFactor::get() * signatories_len.into();
+				T::Currency::reserve(&who, deposit)?;
+
+				let expiry = now.saturating_add(T::ProposalLifetime::get());
+				Self::deposit_event(Event::NewWeightedProposal {
+					approving: who.clone(),
+					multisig: multisig.clone(),
+					call_hash,
+					expiry,
+				});
+				let proposal = WeightedProposal {
+					expiry,
+					deposit,
+					depositor: who.clone(),
+					approvals: BoundedVec::new(),
+				};
+				(proposal, true)
+			},
+		};
+
+		// Approvals of removed signatories no longer count, make room for the current ones.
+		proposal.approvals.retain(|approving| details.weight_of(approving).is_some());
+		let newly_approved = match proposal.approvals.binary_search(&who) {
+			Ok(_) => false,
+			Err(pos) => {
+				proposal
+					.approvals
+					.try_insert(pos, who.clone())
+					.map_err(|_| Error::<T>::TooManySignatories)?;
+				true
+			},
+		};
+		let approved_weight = details.approval_weight(&proposal.approvals);
+
+		// We only bother dispatching the call if the threshold is reached.
+		if let Some(call) = maybe_call.filter(|_| approved_weight >= details.threshold) {
+			ensure!(
+				call.get_dispatch_info().call_weight.all_lte(max_weight),
+				Error::<T>::MaxWeightTooLow
+			);
+
+			// Clean up storage before executing call to avoid an possibility of reentrancy
+			// attack.
+			WeightedProposals::<T>::remove(&multisig, call_hash);
+			T::Currency::unreserve(&proposal.depositor, proposal.deposit);
+
+			let result = call.dispatch(RawOrigin::Signed(multisig.clone()).into());
+			Self::deposit_event(Event::WeightedProposalExecuted {
+				approving: who,
+				multisig,
+				call_hash,
+				result: result.map(|_| ()).map_err(|e| e.error),
+			});
+			Ok(get_result_weight(result)
+				.map(|actual_weight| {
+					T::WeightInfo::as_weighted_multi_complete(signatories_len, call_len)
+						.saturating_add(actual_weight)
+				})
+				.into())
+		} else {
+			// If we already approved and didn't provide the call, then this was useless.
+			ensure!(newly_approved, Error::<T>::AlreadyApproved);
+
+			WeightedProposals::<T>::insert(&multisig, call_hash, proposal);
+			Self::deposit_event(Event::WeightedProposalApproval {
+				approving: who,
+				multisig,
+				call_hash,
+				approved_weight,
+			});
+
+			let final_weight = if is_new {
+				T::WeightInfo::as_weighted_multi_create(signatories_len, call_len)
+			} else {
+				T::WeightInfo::as_weighted_multi_approve(signatories_len, call_len)
+			};
+			// Call is not made, so the actual weight does not include call
+			Ok(Some(final_weight).into())
+		}
+	}
+
+	/// Check that the weighted signatories are sorted, have non-zero weights and can reach
+	/// `threshold`.
+	fn ensure_valid_weighted_signatories(
+		signatories: Vec<(T::AccountId, u32)>,
+		threshold: u32,
+	) -> Result<BoundedVec<(T::AccountId, u32), T::MaxSignatories>, DispatchError> {
+		ensure!(!signatories.is_empty(), Error::<T>::TooFewSignatories);
+		ensure!(
+			signatories.windows(2).all(|pair| pair[0].0 < pair[1].0),
+			Error::<T>::SignatoriesOutOfOrder
+		);
+		ensure!(signatories.iter().all(|(_, weight)| *weight > 0), Error::<T>::ZeroWeight);
+
+		let total_weight = signatories
+			.iter()
+			.fold(0u32, |total, (_, weight)| total.saturating_add(*weight));
+		ensure!(threshold > 0 && threshold <= total_weight, Error::<T>::InvalidThreshold);
+
+		signatories.try_into().map_err(|_| Error::<T>::TooManySignatories.into())
+	}
+
+	/// Calculate the deposit for a weighted multisig account.
+	///
+	/// The deposit is calculated as `DepositBase + DepositFactor * MaxSignatories`, so that it
+	/// covers any later change of the signatories.
+	pub fn weighted_multisig_deposit() -> BalanceOf<T> {
+		T::DepositBase::get() + T::DepositFactor::get() * T::MaxSignatories::get().into()
+	}
+
 	/// The current `Timepoint`.
 	pub fn timepoint() -> Timepoint<BlockNumberFor<T>> {
 		Timepoint {
@@ -54,6 +54,8 @@ impl Contains<RuntimeCall> for TestBaseCallFilter {
 			RuntimeCall::Balances(_) => true,
 			// Needed for benchmarking
 			RuntimeCall::System(frame_system::Call::remark { .. }) => true,
+			// Weighted multisig accounts update themselves
+			RuntimeCall::Multisig(Call::update_weighted_multisig { .. }) => true,
 			_ => false,
 		}
 	}
@@ -71,6 +73,7 @@ impl Config for Test {
 	type DepositBase = MultisigDepositBase;
 	type DepositFactor = MultisigDepositFactor;
 	type MaxSignatories = ConstU32<3>;
+	type ProposalLifetime = ConstU32<10>;
 	type WeightInfo = ();
 	type BlockNumberProvider = frame_system::Pallet<Test>;
 }
@@ -1005,3 +1008,249 @@ fn poke_deposit_handles_insufficient_balance() {
 		);
 	});
 }
+
+fn create_weighted_multisig(signatories: Vec<(u64, u32)>, threshold: u32) -> u64 {
+	let index = WeightedMultisigCount::<Test>::get();
+	assert_ok!(Multisig::create_weighted_multisig(
+		RuntimeOrigin::signed(1),
+		signatories,
+		threshold
+	));
+	Multisig::weighted_multi_account_id(index)
+}
+
+#[test]
+fn weighted_multisig_deposit_is_taken_and_returned() {
+	new_test_ext().execute_with(|| {
+		let multi = create_weighted_multisig(vec![(1, 1), (2, 1), (3, 1)], 2);
+		// `DepositBase + DepositFactor * MaxSignatories`
+		assert_eq!(Balances::reserved_balance(1), 4);
+		System::assert_last_event(
+			Event::WeightedMultisigCreated { creator: 1, multisig: multi }.into(),
+		);
+
+		assert_noop!(
+			Multisig::destroy_weighted_multisig(RuntimeOrigin::signed(1)),
+			Error::<Test>::NotFound
+		);
+		assert_ok!(Multisig::destroy_weighted_multisig(RuntimeOrigin::signed(multi)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(!WeightedMultisigs::<Test>::contains_key(multi));
+	});
+}
+
+#[test]
+fn weighted_multisig_checks_signatories_and_threshold() {
+	new_test_ext().execute_with(|| {
+		let create = |signatories, threshold| {
+			Multisig::create_weighted_multisig(RuntimeOrigin::signed(1), signatories, threshold)
+		};
+		assert_noop!(create(vec![], 1), Error::<Test>::TooFewSignatories);
+		assert_noop!(create(vec![(2, 1), (1, 1)], 1), Error::<Test>::SignatoriesOutOfOrder);
+		assert_noop!(create(vec![(1, 1), (1, 1)], 1), Error::<Test>::SignatoriesOutOfOrder);
+		assert_noop!(create(vec![(1, 1), (2, 0)], 1), Error::<Test>::ZeroWeight);
+		assert_noop!(create(vec![(1, 1), (2, 1)], 0), Error::<Test>::InvalidThreshold);
+		assert_noop!(create(vec![(1, 1), (2, 1)], 3), Error::<Test>::InvalidThreshold);
+		assert_noop!(
+			create(vec![(1, 1), (2, 1), (3, 1), (4, 1)], 2),
+			Error::<Test>::TooManySignatories
+		);
+	});
+}
+
+#[test]
+fn weighted_multisig_dispatches_once_threshold_weight_is_reached() {
+	new_test_ext().execute_with(|| {
+		let multi = create_weighted_multisig(vec![(1, 3), (2, 1), (3, 1)], 3);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+
+		let call = call_transfer(6, 10);
+		let call_weight = call.get_dispatch_info().call_weight;
+		let hash = blake2_256(&call.encode());
+
+		assert_noop!(
+			Multisig::approve_as_weighted_multi(RuntimeOrigin::signed(4), multi, hash),
+			Error::<Test>::NotSignatory
+		);
+
+		assert_ok!(Multisig::approve_as_weighted_multi(RuntimeOrigin::signed(2), multi, hash));
+		// `DepositBase + DepositFactor * signatories`
+		assert_eq!(Balances::reserved_balance(2), 4);
+		assert_noop!(
+			Multisig::approve_as_weighted_multi(RuntimeOrigin::signed(2), multi, hash),
+			Error::<Test>::AlreadyApproved
+		);
+
+		// Two signatories of weight one are not enough.
+		assert_ok!(Multisig::as_weighted_multi(
+			RuntimeOrigin::signed(3),
+			multi,
+			call.clone(),
+			call_weight
+		));
+		assert_eq!(Balances::free_balance(6), 0);
+		System::assert_last_event(
+			Event::WeightedProposalApproval {
+				approving: 3,
+				multisig: multi,
+				call_hash: hash,
+				approved_weight: 2,
+			}
+			.into(),
+		);
+
+		assert_noop!(
+			Multisig::as_weighted_multi(
+				RuntimeOrigin::signed(1),
+				multi,
+				call.clone(),
+				Weight::zero()
+			),
+			Error::<Test>::MaxWeightTooLow
+		);
+		assert_ok!(Multisig::as_weighted_multi(RuntimeOrigin::signed(1), multi, call, call_weight));
+		assert_eq!(Balances::free_balance(6), 10);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(!WeightedProposals::<Test>::contains_key(multi, hash));
+	});
+}
+
+#[test]
+fn weighted_multisig_single_heavy_signatory_dispatches_directly() {
+	new_test_ext().execute_with(|| {
+		let multi = create_weighted_multisig(vec![(1, 1), (2, 2)], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 5));
+
+		let call = call_transfer(6, 3);
+		let call_weight = call.get_dispatch_info().call_weight;
+		assert_ok!(Multisig::as_weighted_multi(RuntimeOrigin::signed(2), multi, call, call_weight));
+		assert_eq!(Balances::free_balance(6), 3);
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn weighted_multisig_signatories_rotate_without_changing_account() {
+	new_test_ext().execute_with(|| {
+		let multi = create_weighted_multisig(vec![(1, 1), (2, 1)], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+
+		// Signatories can not be changed without the approval of the multisig.
+		assert_noop!(
+			Multisig::update_weighted_multisig(RuntimeOrigin::signed(1), vec![(1, 1)], 1),
+			Error::<Test>::NotFound
+		);
+
+		// A proposal approved by 2 before the rotation.
+		let transfer = call_transfer(6, 3);
+		let transfer_weight = transfer.get_dispatch_info().call_weight;
+		let transfer_hash = blake2_256(&transfer.encode());
+		assert_ok!(Multisig::approve_as_weighted_multi(
+			RuntimeOrigin::signed(2),
+			multi,
+			transfer_hash
+		));
+
+		// Replace 2 by 3 and let 1 alone reach the threshold.
+		let update = Box::new(RuntimeCall::Multisig(Call::update_weighted_multisig {
+			signatories: vec![(1, 2), (3, 1)],
+			threshold: 2,
+		}));
+		let update_weight = update.get_dispatch_info().call_weight;
+		assert_ok!(Multisig::approve_as_weighted_multi(
+			RuntimeOrigin::signed(2),
+			multi,
+			blake2_256(&update.encode())
+		));
+		assert_ok!(Multisig::as_weighted_multi(
+			RuntimeOrigin::signed(1),
+			multi,
+			update,
+			update_weight
+		));
+		System::assert_has_event(
+			Event::WeightedMultisigUpdated { multisig: multi, threshold: 2 }.into(),
+		);
+
+		let details = WeightedMultisigs::<Test>::get(multi).unwrap();
+		assert_eq!(details.weight_of(&1), Some(2));
+		assert_eq!(details.weight_of(&2), None);
+		assert_eq!(details.weight_of(&3), Some(1));
+
+		// 2 is no signatory anymore and its approval no longer counts.
+		assert_noop!(
+			Multisig::approve_as_weighted_multi(RuntimeOrigin::signed(2), multi, transfer_hash),
+			Error::<Test>::NotSignatory
+		);
+		assert_ok!(Multisig::as_weighted_multi(
+			RuntimeOrigin::signed(3),
+			multi,
+			transfer.clone(),
+			transfer_weight
+		));
+		assert_eq!(Balances::free_balance(6), 0);
+		assert_ok!(Multisig::as_weighted_multi(
+			RuntimeOrigin::signed(1),
+			multi,
+			transfer,
+			transfer_weight
+		));
+		assert_eq!(Balances::free_balance(6), 3);
+		assert_eq!(Balances::free_balance(multi), 2);
+	});
+}
+
+#[test]
+fn weighted_multisig_proposals_expire() {
+	new_test_ext().execute_with(|| {
+		let multi = create_weighted_multisig(vec![(1, 1), (2, 1), (3, 1)], 2);
+		let call = call_transfer(6, 3);
+		let hash = blake2_256(&call.encode());
+
+		assert_ok!(Multisig::approve_as_weighted_multi(RuntimeOrigin::signed(2), multi, hash));
+		System::assert_has_event(
+			Event::NewWeightedProposal {
+				approving: 2,
+				multisig: multi,
+				call_hash: hash,
+				expiry: 11,
+			}
+			.into(),
+		);
+		assert_noop!(
+			Multisig::clear_expired_weighted_proposal(RuntimeOrigin::signed(5), multi, hash),
+			Error::<Test>::ProposalNotExpired
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			Multisig::approve_as_weighted_multi(RuntimeOrigin::signed(3), multi, hash),
+			Error::<Test>::ProposalExpired
+		);
+		assert_ok!(Multisig::clear_expired_weighted_proposal(
+			RuntimeOrigin::signed(5),
+			multi,
+			hash
+		));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(!WeightedProposals::<Test>::contains_key(multi, hash));
+	});
+}
+
+#[test]
+fn cancel_weighted_proposal_works() {
+	new_test_ext().execute_with(|| {
+		let multi = create_weighted_multisig(vec![(1, 1), (2, 1), (3, 1)], 2);
+		let hash = blake2_256(&call_transfer(6, 3).encode());
+
+		assert_ok!(Multisig::approve_as_weighted_multi(RuntimeOrigin::signed(2), multi, hash));
+		assert_noop!(
+			Multisig::cancel_as_weighted_multi(RuntimeOrigin::signed(3), multi, hash),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(Multisig::cancel_as_weighted_multi(RuntimeOrigin::signed(2), multi, hash));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(!WeightedProposals::<Test>::contains_key(multi, hash));
+	});
+}
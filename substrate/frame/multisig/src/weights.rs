@@ -79,6 +79,14 @@ pub trait WeightInfo {
 	fn approve_as_multi_approve(s: u32, ) -> Weight;
 	fn cancel_as_multi(s: u32, ) -> Weight;
 	fn poke_deposit(s: u32, ) -> Weight;
+	fn create_weighted_multisig(s: u32, ) -> Weight;
+	fn update_weighted_multisig(s: u32, ) -> Weight;
+	fn destroy_weighted_multisig() -> Weight;
+	fn as_weighted_multi_create(s: u32, z: u32, ) -> Weight;
+	fn as_weighted_multi_approve(s: u32, z: u32, ) -> Weight;
+	fn as_weighted_multi_complete(s: u32, z: u32, ) -> Weight;
+	fn cancel_as_weighted_multi() -> Weight;
+	fn clear_expired_weighted_proposal() -> Weight;
 }
 
 /// Weights for `pallet_multisig` using the Substrate node and recommended hardware.
@@ -212,6 +220,106 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Multisig::WeightedMultisigCount` (r:1 w:1)
+	/// Proof: `Multisig::WeightedMultisigCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::WeightedMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::WeightedMultisigs` (`max_values`: None, `max_size`: Some(3695), added: 6170, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn create_weighted_multisig(s: u32, ) -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(31_204_117, 7160)
+			.saturating_add(Weight::from_parts(214_562, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Multisig::WeightedMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::WeightedMultisigs` (`max_values`: None, `max_size`: Some(3695), added: 6170, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn update_weighted_multisig(s: u32, ) -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(19_874_306, 7160)
+			.saturating_add(Weight::from_parts(158_371, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::WeightedMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::WeightedMultisigs` (`max_values`: None, `max_size`: Some(3695), added: 6170, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn destroy_weighted_multisig() -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(26_108_000, 7160)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Multisig::WeightedMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::WeightedMultisigs` (`max_values`: None, `max_size`: Some(3695), added: 6170, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::WeightedProposals` (r:1 w:1)
+	/// Proof: `Multisig::WeightedProposals` (`max_values`: None, `max_size`: Some(3342), added: 5817, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_weighted_multi_create(s: u32, z: u32, ) -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(41_527_934, 7160)
+			.saturating_add(Weight::from_parts(121_684, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(1_712, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Multisig::WeightedMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::WeightedMultisigs` (`max_values`: None, `max_size`: Some(3695), added: 6170, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::WeightedProposals` (r:1 w:1)
+	/// Proof: `Multisig::WeightedProposals` (`max_values`: None, `max_size`: Some(3342), added: 5817, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[3, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_weighted_multi_approve(s: u32, z: u32, ) -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(25_318_472, 7160)
+			.saturating_add(Weight::from_parts(133_905, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(1_698, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::WeightedMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::WeightedMultisigs` (`max_values`: None, `max_size`: Some(3695), added: 6170, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::WeightedProposals` (r:1 w:1)
+	/// Proof: `Multisig::WeightedProposals` (`max_values`: None, `max_size`: Some(3342), added: 5817, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_weighted_multi_complete(s: u32, z: u32, ) -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(50_962_817, 7160)
+			.saturating_add(Weight::from_parts(152_437, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(1_804, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Multisig::WeightedProposals` (r:1 w:1)
+	/// Proof: `Multisig::WeightedProposals` (`max_values`: None, `max_size`: Some(3342), added: 5817, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn cancel_as_weighted_multi() -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(27_436_000, 6807)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Multisig::WeightedProposals` (r:1 w:1)
+	/// Proof: `Multisig::WeightedProposals` (`max_values`: None, `max_size`: Some(3342), added: 5817, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn clear_expired_weighted_proposal() -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(27_902_000, 6807)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -344,4 +452,104 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Multisig::WeightedMultisigCount` (r:1 w:1)
+	/// Proof: `Multisig::WeightedMultisigCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::WeightedMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::WeightedMultisigs` (`max_values`: None, `max_size`: Some(3695), added: 6170, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn create_weighted_multisig(s: u32, ) -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(31_204_117, 7160)
+			.saturating_add(Weight::from_parts(214_562, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Multisig::WeightedMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::WeightedMultisigs` (`max_values`: None, `max_size`: Some(3695), added: 6170, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn update_weighted_multisig(s: u32, ) -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(19_874_306, 7160)
+			.saturating_add(Weight::from_parts(158_371, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::WeightedMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::WeightedMultisigs` (`max_values`: None, `max_size`: Some(3695), added: 6170, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn destroy_weighted_multisig() -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(26_108_000, 7160)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Multisig::WeightedMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::WeightedMultisigs` (`max_values`: None, `max_size`: Some(3695), added: 6170, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::WeightedProposals` (r:1 w:1)
+	/// Proof: `Multisig::WeightedProposals` (`max_values`: None, `max_size`: Some(3342), added: 5817, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_weighted_multi_create(s: u32, z: u32, ) -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(41_527_934, 7160)
+			.saturating_add(Weight::from_parts(121_684, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(1_712, 0).saturating_mul(z.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Multisig::WeightedMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::WeightedMultisigs` (`max_values`: None, `max_size`: Some(3695), added: 6170, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::WeightedProposals` (r:1 w:1)
+	/// Proof: `Multisig::WeightedProposals` (`max_values`: None, `max_size`: Some(3342), added: 5817, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[3, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_weighted_multi_approve(s: u32, z: u32, ) -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(25_318_472, 7160)
+			.saturating_add(Weight::from_parts(133_905, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(1_698, 0).saturating_mul(z.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::WeightedMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::WeightedMultisigs` (`max_values`: None, `max_size`: Some(3695), added: 6170, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::WeightedProposals` (r:1 w:1)
+	/// Proof: `Multisig::WeightedProposals` (`max_values`: None, `max_size`: Some(3342), added: 5817, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_weighted_multi_complete(s: u32, z: u32, ) -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(50_962_817, 7160)
+			.saturating_add(Weight::from_parts(152_437, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(1_804, 0).saturating_mul(z.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Multisig::WeightedProposals` (r:1 w:1)
+	/// Proof: `Multisig::WeightedProposals` (`max_values`: None, `max_size`: Some(3342), added: 5817, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn cancel_as_weighted_multi() -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(27_436_000, 6807)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Multisig::WeightedProposals` (r:1 w:1)
+	/// Proof: `Multisig::WeightedProposals` (`max_values`: None, `max_size`: Some(3342), added: 5817, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn clear_expired_weighted_proposal() -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(27_902_000, 6807)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
@@ -56,4 +56,21 @@ sp_api::decl_runtime_apis! {
 
 		fn collection_attribute(collection: CollectionId, key: Vec<u8>) -> Option<Vec<u8>>;
 	}
+
+	/// API to look up the royalties owed on sales of NFTs.
+	pub trait NftsRoyaltiesApi<AccountId, CollectionId, ItemId, Balance>
+	where
+		AccountId: Decode,
+		CollectionId: Encode,
+		ItemId: Encode,
+		Balance: Encode + Decode,
+	{
+		/// Returns the recipients of the royalties owed when `item` of `collection` is sold for
+		/// `sale_price`, along with the amount owed to each of them.
+		fn royalties(
+			collection: CollectionId,
+			item: ItemId,
+			sale_price: Balance,
+		) -> Vec<(AccountId, Balance)>;
+	}
 }
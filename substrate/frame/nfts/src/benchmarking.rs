@@ -30,7 +30,10 @@ use frame_support::{
 	BoundedVec,
 };
 use frame_system::RawOrigin as SystemOrigin;
-use sp_runtime::traits::{Bounded, One};
+use sp_runtime::{
+	traits::{Bounded, One},
+	Permill,
+};
 
 use crate::Pallet as Nfts;
 
@@ -189,6 +192,22 @@ fn add_collection_attribute<T: Config<I>, I: 'static>(
 	(key, caller, caller_lookup)
 }
 
+fn make_royalties<T: Config<I>, I: 'static>(r: u32) -> RoyaltiesOf<T, I> {
+	(0..r)
+		.map(|i| {
+			let recipient: T::AccountId = account("recipient", i, SEED);
+			T::Currency::make_free_balance_be(&recipient, T::Currency::minimum_balance());
+			(recipient, Permill::from_percent(1))
+		})
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
+}
+
+fn sale_price<T: Config<I>, I: 'static>() -> ItemPrice<T, I> {
+	T::Currency::minimum_balance().max(One::one()).saturating_mul(1000u32.into())
+}
+
 fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::RuntimeEvent) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -878,5 +897,54 @@ benchmarks_instance_pallet! {
 		);
 	}
 
+	set_royalties {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let royalties = make_royalties::<T, I>(T::MaxRoyaltyRecipients::get());
+	}: _(SystemOrigin::Signed(caller), collection, Some(item), royalties.clone())
+	verify {
+		assert!(!ItemRoyalties::<T, I>::get(collection, item).unwrap().deposit.is_zero());
+		assert_last_event::<T, I>(Event::RoyaltiesSet {
+			collection,
+			maybe_item: Some(item),
+			royalties,
+		}.into());
+	}
+
+	clear_royalties {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let royalties = make_royalties::<T, I>(T::MaxRoyaltyRecipients::get());
+		let origin = SystemOrigin::Signed(caller.clone()).into();
+		Nfts::<T, I>::set_royalties(origin, collection, Some(item), royalties)?;
+	}: _(SystemOrigin::Signed(caller), collection, Some(item))
+	verify {
+		assert!(!ItemRoyalties::<T, I>::contains_key(collection, item));
+		assert_last_event::<T, I>(Event::RoyaltiesCleared { collection, maybe_item: Some(item) }.into());
+	}
+
+	pay_royalties {
+		let r in 0 .. T::MaxRoyaltyRecipients::get();
+		let (collection, ..) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let royalties = make_royalties::<T, I>(r);
+		let payer: T::AccountId = account("payer", 0, SEED);
+		T::Currency::make_free_balance_be(&payer, DepositBalanceOf::<T, I>::max_value());
+		let price = sale_price::<T, I>();
+	}: {
+		Nfts::<T, I>::do_pay_royalties(collection, item, royalties, &payer, price)?;
+	}
+	verify {
+		if r > 0 {
+			assert_last_event::<T, I>(Event::RoyaltyPaid {
+				collection,
+				item,
+				payer,
+				recipient: account("recipient", r - 1, SEED),
+				amount: Permill::from_percent(1).mul_floor(price),
+			}.into());
+		}
+	}
+
 	impl_benchmark_test_suite!(Nfts, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -39,6 +39,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// specified, the other party must offer the specified `price` or higher for the swap. After
 	/// creating the swap, the function emits the `SwapCreated` event.
 	///
+	/// If the swap has a price, the royalties of the item sold for that price are recorded with the
+	/// swap and are the ones paid when it is claimed. When any item of `desired_collection` can be
+	/// offered in return, the royalties of that collection are recorded.
+	///
 	/// - `caller`: The account creating the swap offer, which must be the owner of the item.
 	/// - `offered_collection_id`: The collection ID containing the offered item.
 	/// - `offered_item_id`: The item ID offered for the swap.
@@ -79,6 +83,25 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let now = T::BlockNumberProvider::current_block_number();
 		let deadline = duration.saturating_add(now);
 
+		// The royalties in effect when the swap is offered are the ones paid when it's claimed.
+		let royalties = match &maybe_price {
+			Some(price) => match (&price.direction, maybe_desired_item_id) {
+				(PriceDirection::Receive, _) =>
+					Self::effective_royalties(&offered_collection_id, &offered_item_id),
+				(PriceDirection::Send, Some(desired_item_id)) =>
+					Self::effective_royalties(&desired_collection_id, &desired_item_id),
+				(PriceDirection::Send, None) =>
+					CollectionRoyalties::<T, I>::get(&desired_collection_id)
+						.map(|details| details.royalties),
+			},
+			None => None,
+		};
+		match royalties {
+			Some(royalties) if !royalties.is_empty() =>
+				SwapRoyalties::<T, I>::insert(&offered_collection_id, &offered_item_id, royalties),
+			_ => SwapRoyalties::<T, I>::remove(&offered_collection_id, &offered_item_id),
+		}
+
 		PendingSwapOf::<T, I>::insert(
 			&offered_collection_id,
 			&offered_item_id,
@@ -127,6 +150,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 
 		PendingSwapOf::<T, I>::remove(&offered_collection_id, &offered_item_id);
+		SwapRoyalties::<T, I>::remove(&offered_collection_id, &offered_item_id);
 
 		Self::deposit_event(Event::SwapCancelled {
 			offered_collection: offered_collection_id,
@@ -146,8 +170,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// `send_item_id`, `receive_collection_id`, and `receive_item_id`. The `caller` account must be
 	/// the owner of the item specified by `send_collection_id` and `send_item_id`. If the claimed
 	/// swap has an associated `price`, it will be transferred between the owners of the two items
-	/// based on the `price.direction`. After the swap is completed, the function emits the
-	/// `SwapClaimed` event.
+	/// based on the `price.direction`, less the royalties recorded when the swap was created. After
+	/// the swap is completed, the function emits the `SwapClaimed` event.
 	///
 	/// - `caller`: The account claiming the swap offer, which must be the owner of the sent item.
 	/// - `send_collection_id`: The identifier of the collection containing the item being sent.
@@ -191,20 +215,23 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		ensure!(now <= swap.deadline, Error::<T, I>::DeadlineExpired);
 
 		if let Some(ref price) = swap.price {
-			match price.direction {
-				PriceDirection::Send => T::Currency::transfer(
-					&receive_item.owner,
-					&send_item.owner,
-					price.amount,
-					KeepAlive,
-				)?,
-				PriceDirection::Receive => T::Currency::transfer(
-					&send_item.owner,
-					&receive_item.owner,
-					price.amount,
-					KeepAlive,
-				)?,
+			// The royalties are due on the item whose owner is paid the price.
+			let (payer, payee, sold_collection_id, sold_item_id) = match price.direction {
+				PriceDirection::Send =>
+					(&receive_item.owner, &send_item.owner, send_collection_id, send_item_id),
+				PriceDirection::Receive =>
+					(&send_item.owner, &receive_item.owner, receive_collection_id, receive_item_id),
 			};
+			let royalties = SwapRoyalties::<T, I>::get(&receive_collection_id, &receive_item_id)
+				.unwrap_or_default();
+			let payee_amount = Self::do_pay_royalties(
+				sold_collection_id,
+				sold_item_id,
+				royalties,
+				payer,
+				price.amount,
+			)?;
+			T::Currency::transfer(payer, payee, payee_amount, KeepAlive)?;
 		}
 
 		// This also removes the swap.
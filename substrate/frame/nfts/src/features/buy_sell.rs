@@ -60,7 +60,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// This function is used to set the price and whitelist a buyer for an item in the
 	/// specified `collection`. The `sender` account must be the owner of the item. The item's price
 	/// and the whitelisted buyer can be set to allow trading the item. If `price` is `None`, the
-	/// item will be marked as not for sale.
+	/// item will be marked as not for sale. The royalties of the item are recorded along with its
+	/// price, and are the ones paid when the item is bought.
 	///
 	/// - `collection`: The identifier of the collection containing the item.
 	/// - `item`: The identifier of the item for which the price and whitelist information will be
@@ -98,6 +99,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		if let Some(ref price) = price {
 			ItemPriceOf::<T, I>::insert(&collection, &item, (price, whitelisted_buyer.clone()));
+			// The royalties in effect at listing are the ones paid when the item is bought.
+			match Self::effective_royalties(&collection, &item) {
+				Some(royalties) if !royalties.is_empty() =>
+					ItemPriceRoyalties::<T, I>::insert(&collection, &item, royalties),
+				_ => ItemPriceRoyalties::<T, I>::remove(&collection, &item),
+			}
 			Self::deposit_event(Event::ItemPriceSet {
 				collection,
 				item,
@@ -106,6 +113,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			});
 		} else {
 			ItemPriceOf::<T, I>::remove(&collection, &item);
+			ItemPriceRoyalties::<T, I>::remove(&collection, &item);
 			Self::deposit_event(Event::ItemPriceRemoved { collection, item });
 		}
 
@@ -119,7 +127,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// receive the bid price if it is equal to or higher than the item's set price. If
 	/// `whitelisted_buyer` is specified in the item's price information, only that account is
 	/// allowed to buy the item. If the item is not for sale, or the bid price is too low, the
-	/// function will return an error.
+	/// function will return an error. The royalties in effect when the item was listed are paid by
+	/// the buyer out of the item's price, and only the remainder goes to the item's owner.
 	///
 	/// - `collection`: The identifier of the collection containing the item to be bought.
 	/// - `item`: The identifier of the item to be bought.
@@ -148,10 +157,13 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			ensure!(only_buyer == buyer, Error::<T, I>::NoPermission);
 		}
 
+		let royalties = ItemPriceRoyalties::<T, I>::get(&collection, &item).unwrap_or_default();
+		let seller_amount =
+			Self::do_pay_royalties(collection, item, royalties, &buyer, price_info.0)?;
 		T::Currency::transfer(
 			&buyer,
 			&details.owner,
-			price_info.0,
+			seller_amount,
 			ExistenceRequirement::KeepAlive,
 		)?;
 
@@ -253,6 +253,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					}
 				}
 
+				// Return the deposit of the item's royalties.
+				if let Some(royalties) = ItemRoyalties::<T, I>::take(&collection, &item) {
+					T::Currency::unreserve(&collection_details.owner, royalties.deposit);
+					collection_details.owner_deposit.saturating_reduce(royalties.deposit);
+				}
+
 				Ok(details.owner)
 			},
 		)?;
@@ -260,7 +266,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Item::<T, I>::remove(&collection, &item);
 		Account::<T, I>::remove((&owner, &collection, &item));
 		ItemPriceOf::<T, I>::remove(&collection, &item);
+		ItemPriceRoyalties::<T, I>::remove(&collection, &item);
 		PendingSwapOf::<T, I>::remove(&collection, &item);
+		SwapRoyalties::<T, I>::remove(&collection, &item);
 		ItemAttributesApprovalsOf::<T, I>::remove(&collection, &item);
 
 		if remove_config {
@@ -0,0 +1,223 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! This module contains helper functions to configure and pay the royalties of items of the NFTs
+//! pallet.
+//!
+//! The royalties are paid out of the sale price in `buy_item` and `claim_swap`, while external
+//! marketplaces pay them through the [`nonfungibles_v2::Royalties`] trait. The royalties paid in
+//! `buy_item` and `claim_swap` are the ones in effect when the item was listed or offered, so
+//! they can't be raised in front of a sale.
+//!
+//! [`nonfungibles_v2::Royalties`]: frame_support::traits::tokens::nonfungibles_v2::Royalties
+
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement::KeepAlive},
+};
+use sp_runtime::Permill;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Set the royalties of a collection or an item.
+	///
+	/// - `maybe_check_owner`: An optional account ID used to check permissions.
+	/// - `collection`: The ID of the collection.
+	/// - `maybe_item`: The ID of the item, or `None` to set the royalties of the collection.
+	/// - `royalties`: The recipients and their shares of the sale price.
+	///
+	/// If `maybe_check_owner` is `Some(owner)`, it checks that the caller is the owner of the
+	/// collection and returns an `Error::NoPermission` otherwise. The shares must not add up to
+	/// more than 100%, otherwise an `Error::RoyaltiesTooHigh` is returned. An empty list of
+	/// royalties set for an item exempts it from the royalties of its collection.
+	///
+	/// Unless `maybe_check_owner` is `None` or the collection doesn't require deposits, a deposit
+	/// based on the size of the royalties is reserved from the owner of the collection.
+	///
+	/// Emits `RoyaltiesSet` event when successful.
+	pub(crate) fn do_set_royalties(
+		maybe_check_owner: Option<T::AccountId>,
+		collection: T::CollectionId,
+		maybe_item: Option<T::ItemId>,
+		royalties: RoyaltiesOf<T, I>,
+	) -> DispatchResult {
+		let mut details =
+			Collection::<T, I>::get(&collection).ok_or(Error::<T, I>::UnknownCollection)?;
+		if let Some(check_owner) = &maybe_check_owner {
+			ensure!(check_owner == &details.owner, Error::<T, I>::NoPermission);
+		}
+
+		let total = royalties
+			.iter()
+			.fold(0u32, |total, (_, share)| total.saturating_add(share.deconstruct()));
+		ensure!(total <= Permill::one().deconstruct(), Error::<T, I>::RoyaltiesTooHigh);
+
+		let old_deposit = match maybe_item {
+			Some(item) => {
+				ensure!(Item::<T, I>::contains_key(&collection, &item), Error::<T, I>::UnknownItem);
+				ItemRoyalties::<T, I>::get(&collection, &item)
+			},
+			None => CollectionRoyalties::<T, I>::get(&collection),
+		}
+		.map_or(Zero::zero(), |r| r.deposit);
+
+		let collection_config = Self::get_collection_config(&collection)?;
+		let mut deposit = Zero::zero();
+		if maybe_check_owner.is_some() &&
+			collection_config.is_setting_enabled(CollectionSetting::DepositRequired)
+		{
+			deposit = T::DepositPerByte::get()
+				.saturating_mul((royalties.encoded_size() as u32).into())
+				.saturating_add(T::MetadataDepositBase::get());
+		}
+		if deposit > old_deposit {
+			T::Currency::reserve(&details.owner, deposit - old_deposit)?;
+		} else if deposit < old_deposit {
+			T::Currency::unreserve(&details.owner, old_deposit - deposit);
+		}
+		details.owner_deposit.saturating_reduce(old_deposit);
+		details.owner_deposit.saturating_accrue(deposit);
+		Collection::<T, I>::insert(&collection, details);
+
+		let royalties_details = RoyaltiesDetails { deposit, royalties: royalties.clone() };
+		match maybe_item {
+			Some(item) => ItemRoyalties::<T, I>::insert(&collection, &item, royalties_details),
+			None => CollectionRoyalties::<T, I>::insert(&collection, royalties_details),
+		}
+
+		Self::deposit_event(Event::RoyaltiesSet { collection, maybe_item, royalties });
+		Ok(())
+	}
+
+	/// Clear the royalties of a collection or an item.
+	///
+	/// - `maybe_check_owner`: An optional account ID used to check permissions.
+	/// - `collection`: The ID of the collection.
+	/// - `maybe_item`: The ID of the item, or `None` to clear the royalties of the collection.
+	///
+	/// If `maybe_check_owner` is `Some(owner)`, it checks that the caller is the owner of the
+	/// collection and returns an `Error::NoPermission` otherwise. Returns an `Error::NoRoyalties`
+	/// if there are no royalties to clear. The deposit of the royalties is returned to the owner
+	/// of the collection.
+	///
+	/// Emits `RoyaltiesCleared` event when successful.
+	pub(crate) fn do_clear_royalties(
+		maybe_check_owner: Option<T::AccountId>,
+		collection: T::CollectionId,
+		maybe_item: Option<T::ItemId>,
+	) -> DispatchResult {
+		let mut details =
+			Collection::<T, I>::get(&collection).ok_or(Error::<T, I>::UnknownCollection)?;
+		if let Some(check_owner) = &maybe_check_owner {
+			ensure!(check_owner == &details.owner, Error::<T, I>::NoPermission);
+		}
+
+		let removed = match maybe_item {
+			Some(item) => ItemRoyalties::<T, I>::take(&collection, &item),
+			None => CollectionRoyalties::<T, I>::take(&collection),
+		}
+		.ok_or(Error::<T, I>::NoRoyalties)?;
+		T::Currency::unreserve(&details.owner, removed.deposit);
+		details.owner_deposit.saturating_reduce(removed.deposit);
+		Collection::<T, I>::insert(&collection, details);
+
+		Self::deposit_event(Event::RoyaltiesCleared { collection, maybe_item });
+		Ok(())
+	}
+
+	/// Returns the royalties that apply to sales of `item` of `collection`.
+	///
+	/// The royalties set for the item take precedence over the royalties of its collection.
+	pub fn effective_royalties(
+		collection: &T::CollectionId,
+		item: &T::ItemId,
+	) -> Option<RoyaltiesOf<T, I>> {
+		ItemRoyalties::<T, I>::get(collection, item)
+			.or_else(|| CollectionRoyalties::<T, I>::get(collection))
+			.map(|details| details.royalties)
+	}
+
+	/// Returns the amounts owed to every royalty recipient when `item` of `collection` is sold for
+	/// `sale_price`.
+	///
+	/// Recipients whose share of the price rounds down to zero, or who don't have an account and
+	/// are owed less than the existential deposit, are left out.
+	pub fn royalty_amounts(
+		collection: &T::CollectionId,
+		item: &T::ItemId,
+		sale_price: ItemPrice<T, I>,
+	) -> Vec<(T::AccountId, ItemPrice<T, I>)> {
+		Self::payable_royalties(
+			Self::effective_royalties(collection, item).unwrap_or_default(),
+			sale_price,
+		)
+	}
+
+	/// Returns the amounts of `royalties` that can be paid out of `sale_price`.
+	///
+	/// Shares that round down to zero are left out, and so are shares below the existential
+	/// deposit owed to recipients without an account, since transferring them would fail the
+	/// whole sale. The shares left out stay with the seller.
+	pub(crate) fn payable_royalties(
+		royalties: RoyaltiesOf<T, I>,
+		sale_price: ItemPrice<T, I>,
+	) -> Vec<(T::AccountId, ItemPrice<T, I>)> {
+		let minimum_balance = T::Currency::minimum_balance();
+		royalties
+			.into_iter()
+			.map(|(recipient, share)| (recipient, share.mul_floor(sale_price)))
+			.filter(|(recipient, amount)| {
+				!amount.is_zero() &&
+					(*amount >= minimum_balance ||
+						!T::Currency::total_balance(recipient).is_zero())
+			})
+			.collect()
+	}
+
+	/// Pays the `royalties` owed on a sale of `item` of `collection` for `sale_price`.
+	///
+	/// - `collection`: The ID of the collection containing the sold item.
+	/// - `item`: The ID of the sold item.
+	/// - `royalties`: The royalties in effect when the item was put up for sale.
+	/// - `payer`: The account paying the sale price.
+	/// - `sale_price`: The price the item is sold for.
+	///
+	/// Every payable royalty is transferred from the `payer` to its recipient. Returns the
+	/// remainder of the sale price, which is owed to the seller.
+	///
+	/// Emits `RoyaltyPaid` event for every royalty paid.
+	pub(crate) fn do_pay_royalties(
+		collection: T::CollectionId,
+		item: T::ItemId,
+		royalties: RoyaltiesOf<T, I>,
+		payer: &T::AccountId,
+		sale_price: ItemPrice<T, I>,
+	) -> Result<ItemPrice<T, I>, DispatchError> {
+		let mut remainder = sale_price;
+		for (recipient, amount) in Self::payable_royalties(royalties, sale_price) {
+			T::Currency::transfer(payer, &recipient, amount, KeepAlive)?;
+			remainder.saturating_reduce(amount);
+			Self::deposit_event(Event::RoyaltyPaid {
+				collection,
+				item,
+				payer: payer.clone(),
+				recipient,
+				amount,
+			});
+		}
+		Ok(remainder)
+	}
+}
@@ -466,6 +466,27 @@ impl<T: Config<I>, I: 'static> Trading<T::AccountId, ItemPrice<T, I>> for Pallet
 	}
 }
 
+impl<T: Config<I>, I: 'static> Royalties<T::AccountId, ItemPrice<T, I>> for Pallet<T, I> {
+	fn royalties(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		sale_price: &ItemPrice<T, I>,
+	) -> Vec<(T::AccountId, ItemPrice<T, I>)> {
+		Self::royalty_amounts(collection, item, *sale_price)
+	}
+
+	fn pay_royalties(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		payer: &T::AccountId,
+		sale_price: &ItemPrice<T, I>,
+	) -> Result<ItemPrice<T, I>, DispatchError> {
+		ensure!(Item::<T, I>::contains_key(collection, item), Error::<T, I>::UnknownItem);
+		let royalties = Self::effective_royalties(collection, item).unwrap_or_default();
+		Self::do_pay_royalties(*collection, *item, royalties, payer, *sale_price)
+	}
+}
+
 impl<T: Config<I>, I: 'static> InspectEnumerable<T::AccountId> for Pallet<T, I> {
 	type CollectionsIterator = KeyPrefixIterator<<T as Config<I>>::CollectionId>;
 	type ItemsIterator = KeyPrefixIterator<<T as Config<I>>::ItemId>;
@@ -216,6 +216,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxAttributesPerCall: Get<u32>;
 
+		/// The max number of royalty recipients a collection or an item could have.
+		#[pallet::constant]
+		type MaxRoyaltyRecipients: Get<u32>;
+
 		/// Disables some of pallet's features.
 		#[pallet::constant]
 		type Features: Get<PalletFeatures>;
@@ -413,6 +417,48 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// Royalties paid on sales of any item of a collection.
+	#[pallet::storage]
+	pub type CollectionRoyalties<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, RoyaltiesDetailsFor<T, I>, OptionQuery>;
+
+	/// Royalties paid on sales of an item, taking precedence over the royalties of its
+	/// collection.
+	#[pallet::storage]
+	pub type ItemRoyalties<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		RoyaltiesDetailsFor<T, I>,
+		OptionQuery,
+	>;
+
+	/// Royalties in effect when an item was put up for sale, paid when the item is bought.
+	#[pallet::storage]
+	pub type ItemPriceRoyalties<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		RoyaltiesOf<T, I>,
+		OptionQuery,
+	>;
+
+	/// Royalties in effect when a swap of an item was offered, paid when the swap is claimed.
+	#[pallet::storage]
+	pub type SwapRoyalties<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		RoyaltiesOf<T, I>,
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -593,6 +639,22 @@ pub mod pallet {
 			attribute: PalletAttributes<T::CollectionId>,
 			value: BoundedVec<u8, T::ValueLimit>,
 		},
+		/// Royalties were set for the `collection` or an `item` within that `collection`.
+		RoyaltiesSet {
+			collection: T::CollectionId,
+			maybe_item: Option<T::ItemId>,
+			royalties: RoyaltiesOf<T, I>,
+		},
+		/// Royalties were cleared for the `collection` or an `item` within that `collection`.
+		RoyaltiesCleared { collection: T::CollectionId, maybe_item: Option<T::ItemId> },
+		/// A royalty was paid on a sale of an `item`.
+		RoyaltyPaid {
+			collection: T::CollectionId,
+			item: T::ItemId,
+			payer: T::AccountId,
+			recipient: T::AccountId,
+			amount: BalanceOf<T, I>,
+		},
 	}
 
 	#[pallet::error]
@@ -687,6 +749,10 @@ pub mod pallet {
 		CollectionNotEmpty,
 		/// The witness data should be provided.
 		WitnessRequired,
+		/// The royalty shares add up to more than the whole sale price.
+		RoyaltiesTooHigh,
+		/// The collection or the item has no royalties set.
+		NoRoyalties,
 	}
 
 	#[pallet::call]
@@ -1757,9 +1823,17 @@ pub mod pallet {
 		/// - `item`: The item the sender wants to buy.
 		/// - `bid_price`: The price the sender is willing to pay.
 		///
+		/// The royalties in effect when the `item` was put up for sale are paid out of the price,
+		/// the rest goes to the owner. Shares below the existential deposit owed to accounts that
+		/// don't exist are left to the owner.
+		///
+		/// Emits `RoyaltyPaid` for every royalty paid.
 		/// Emits `ItemBought` on success.
 		#[pallet::call_index(32)]
-		#[pallet::weight(T::WeightInfo::buy_item())]
+		#[pallet::weight(
+			T::WeightInfo::buy_item()
+				.saturating_add(T::WeightInfo::pay_royalties(T::MaxRoyaltyRecipients::get()))
+		)]
 		pub fn buy_item(
 			origin: OriginFor<T>,
 			collection: T::CollectionId,
@@ -1857,9 +1931,17 @@ pub mod pallet {
 		/// - `receive_item`: The item to be received.
 		/// - `witness_price`: A price that was previously agreed on.
 		///
+		/// The royalties in effect when the swap was created, of the item whose owner receives the
+		/// price, are paid out of it. Shares below the existential deposit owed to accounts that
+		/// don't exist are left to the owner.
+		///
+		/// Emits `RoyaltyPaid` for every royalty paid.
 		/// Emits `SwapClaimed` on success.
 		#[pallet::call_index(36)]
-		#[pallet::weight(T::WeightInfo::claim_swap())]
+		#[pallet::weight(
+			T::WeightInfo::claim_swap()
+				.saturating_add(T::WeightInfo::pay_royalties(T::MaxRoyaltyRecipients::get()))
+		)]
 		pub fn claim_swap(
 			origin: OriginFor<T>,
 			send_collection: T::CollectionId,
@@ -1930,6 +2012,67 @@ pub mod pallet {
 			Self::validate_signature(&Encode::encode(&data), &signature, &signer)?;
 			Self::do_set_attributes_pre_signed(origin, data, signer)
 		}
+
+		/// Set the royalties paid on sales of items of a `collection`, or of a single `item`.
+		///
+		/// Royalties of an item take precedence over the royalties of its collection. They are
+		/// paid out of the sale price by the buyer in `buy_item` and `claim_swap`, and by external
+		/// marketplaces through the `nonfungibles_v2::Royalties` trait. Items already up for sale
+		/// or offered in a swap keep the royalties in effect when they were listed or offered.
+		///
+		/// Origin must be either `ForceOrigin` or `Signed` and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// If the origin is `Signed`, then funds of the collection owner are reserved according to
+		/// the formula: `MetadataDepositBase + DepositPerByte * royalties.encoded_size()` taking
+		/// into account any already reserved funds.
+		///
+		/// - `collection`: The identifier of the collection.
+		/// - `maybe_item`: The identifier of the item, or `None` to set the royalties of the whole
+		///   collection.
+		/// - `royalties`: The recipients and their shares of the sale price. The shares must not
+		///   add up to more than 100%. An empty list set for an item exempts it from the royalties
+		///   of its collection.
+		///
+		/// Emits `RoyaltiesSet` on success.
+		#[pallet::call_index(39)]
+		#[pallet::weight(T::WeightInfo::set_royalties())]
+		pub fn set_royalties(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			maybe_item: Option<T::ItemId>,
+			royalties: RoyaltiesOf<T, I>,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some).map_err(DispatchError::from))?;
+			Self::do_set_royalties(maybe_check_owner, collection, maybe_item, royalties)
+		}
+
+		/// Clear the royalties of a `collection`, or of a single `item`.
+		///
+		/// Origin must be either `ForceOrigin` or `Signed` and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// Any deposit is returned to the collection owner.
+		///
+		/// - `collection`: The identifier of the collection.
+		/// - `maybe_item`: The identifier of the item, or `None` to clear the royalties of the
+		///   whole collection.
+		///
+		/// Emits `RoyaltiesCleared` on success.
+		#[pallet::call_index(40)]
+		#[pallet::weight(T::WeightInfo::clear_royalties())]
+		pub fn clear_royalties(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			maybe_item: Option<T::ItemId>,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some).map_err(DispatchError::from))?;
+			Self::do_clear_royalties(maybe_check_owner, collection, maybe_item)
+		}
 	}
 }
 
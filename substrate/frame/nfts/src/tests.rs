@@ -22,7 +22,7 @@ use enumflags2::BitFlags;
 use frame_support::{
 	assert_noop, assert_ok,
 	traits::{
-		tokens::nonfungibles_v2::{Create, Destroy, Inspect, Mutate},
+		tokens::nonfungibles_v2::{Create, Destroy, Inspect, Mutate, Royalties},
 		Currency, Get,
 	},
 };
@@ -30,7 +30,7 @@ use pallet_balances::Error as BalancesError;
 use sp_core::{bounded::BoundedVec, Pair};
 use sp_runtime::{
 	traits::{Dispatchable, IdentifyAccount},
-	MultiSignature, MultiSigner,
+	MultiSignature, MultiSigner, Permill,
 };
 
 type AccountIdOf<Test> = <Test as frame_system::Config>::AccountId;
@@ -3875,3 +3875,659 @@ fn clear_collection_metadata_works() {
 		assert_eq!(Balances::reserved_balance(&account(1)), 10);
 	});
 }
+
+#[test]
+fn set_and_clear_royalties_should_work() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let collection_id = 0;
+		let item_id = 1;
+		let royalties: RoyaltiesOf<Test> = bvec![
+			(user_2.clone(), Permill::from_percent(10)),
+			(user_3.clone(), Permill::from_percent(5))
+		];
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_2.clone(),
+			None
+		));
+
+		assert_noop!(
+			Nfts::set_royalties(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				None,
+				royalties.clone()
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Nfts::set_royalties(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				Some(2),
+				royalties.clone()
+			),
+			Error::<Test>::UnknownItem
+		);
+		assert_noop!(
+			Nfts::set_royalties(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				None,
+				bvec![
+					(user_2.clone(), Permill::from_percent(60)),
+					(user_3.clone(), Permill::from_percent(50))
+				]
+			),
+			Error::<Test>::RoyaltiesTooHigh
+		);
+
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			None,
+			royalties.clone()
+		));
+		assert_eq!(
+			CollectionRoyalties::<Test>::get(collection_id),
+			Some(RoyaltiesDetails { deposit: 0, royalties: royalties.clone() })
+		);
+		assert!(events().contains(&Event::<Test>::RoyaltiesSet {
+			collection: collection_id,
+			maybe_item: None,
+			royalties: royalties.clone(),
+		}));
+		assert_eq!(Nfts::effective_royalties(&collection_id, &item_id), Some(royalties.clone()));
+
+		// the royalties of an item take precedence over the royalties of its collection
+		let item_royalties: RoyaltiesOf<Test> = bvec![(user_3.clone(), Permill::from_percent(100))];
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::root(),
+			collection_id,
+			Some(item_id),
+			item_royalties.clone()
+		));
+		assert_eq!(Nfts::effective_royalties(&collection_id, &item_id), Some(item_royalties));
+
+		assert_ok!(Nfts::clear_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(item_id)
+		));
+		assert!(events().contains(&Event::<Test>::RoyaltiesCleared {
+			collection: collection_id,
+			maybe_item: Some(item_id),
+		}));
+		assert_eq!(Nfts::effective_royalties(&collection_id, &item_id), Some(royalties.clone()));
+		assert_noop!(
+			Nfts::clear_royalties(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				Some(item_id)
+			),
+			Error::<Test>::NoRoyalties
+		);
+
+		// burning the item removes its royalties
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(item_id),
+			bvec![]
+		));
+		assert_eq!(Nfts::effective_royalties(&collection_id, &item_id), Some(bvec![]));
+		assert_ok!(Nfts::burn(RuntimeOrigin::signed(user_2.clone()), collection_id, item_id));
+		assert!(!ItemRoyalties::<Test>::contains_key(collection_id, item_id));
+
+		// destroying the collection removes its royalties
+		assert_ok!(Nfts::destroy(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Nfts::get_destroy_witness(&collection_id).unwrap()
+		));
+		assert!(!CollectionRoyalties::<Test>::contains_key(collection_id));
+	});
+}
+
+#[test]
+fn royalties_deposit_should_work() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let collection_id = 0;
+		let item_id = 1;
+		Balances::make_free_balance_be(&user_1, 100);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			collection_config_with_all_settings_enabled()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_1.clone(),
+			None
+		));
+		let item_deposit = Item::<Test>::get(collection_id, item_id).unwrap().deposit.amount;
+		let reserved = Balances::reserved_balance(&user_1);
+
+		// 1 + 2 * (32 + 4) bytes of royalties, plus the base deposit
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			None,
+			bvec![
+				(user_2.clone(), Permill::from_percent(10)),
+				(user_3.clone(), Permill::from_percent(5))
+			]
+		));
+		assert_eq!(CollectionRoyalties::<Test>::get(collection_id).unwrap().deposit, 74);
+		assert_eq!(Balances::reserved_balance(&user_1), reserved + 74);
+
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(item_id),
+			bvec![(user_2.clone(), Permill::from_percent(10))]
+		));
+		assert_eq!(Balances::reserved_balance(&user_1), reserved + 74 + 38);
+
+		// updating the royalties only reserves or returns the difference
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(item_id),
+			bvec![]
+		));
+		assert_eq!(Balances::reserved_balance(&user_1), reserved + 74 + 2);
+
+		// royalties set by the force origin require no deposit
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::root(),
+			collection_id,
+			None,
+			bvec![(user_3.clone(), Permill::from_percent(5))]
+		));
+		assert_eq!(CollectionRoyalties::<Test>::get(collection_id).unwrap().deposit, 0);
+		assert_eq!(Balances::reserved_balance(&user_1), reserved + 2);
+
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			None,
+			bvec![(user_3.clone(), Permill::from_percent(5))]
+		));
+		assert_eq!(Balances::reserved_balance(&user_1), reserved + 38 + 2);
+		assert_ok!(Nfts::clear_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			None
+		));
+		assert_eq!(Balances::reserved_balance(&user_1), reserved + 2);
+		assert_eq!(Collection::<Test>::get(collection_id).unwrap().owner_deposit, 2);
+
+		// burning the item returns the deposit of its royalties
+		assert_ok!(Nfts::burn(RuntimeOrigin::signed(user_1.clone()), collection_id, item_id));
+		assert_eq!(Balances::reserved_balance(&user_1), reserved - item_deposit);
+		assert_eq!(Collection::<Test>::get(collection_id).unwrap().owner_deposit, 0);
+	});
+}
+
+#[test]
+fn buy_item_should_pay_royalties() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let user_4 = account(4);
+		let collection_id = 0;
+		let item_1 = 1;
+		let item_2 = 2;
+		let item_3 = 3;
+		let price = 100;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&user_1, initial_balance);
+		Balances::make_free_balance_be(&user_2, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		for item in [item_1, item_2, item_3] {
+			assert_ok!(Nfts::mint(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item,
+				user_1.clone(),
+				None
+			));
+		}
+
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			None,
+			bvec![
+				(user_3.clone(), Permill::from_percent(10)),
+				(user_4.clone(), Permill::from_percent(5))
+			]
+		));
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(item_2),
+			bvec![(user_4.clone(), Permill::from_percent(50))]
+		));
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(item_3),
+			bvec![]
+		));
+		for item in [item_1, item_2, item_3] {
+			assert_ok!(Nfts::set_price(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item,
+				Some(price),
+				None,
+			));
+		}
+
+		// the collection royalties apply
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_1,
+			price
+		));
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 85);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - price);
+		assert_eq!(Balances::total_balance(&user_3), 10);
+		assert_eq!(Balances::total_balance(&user_4), 5);
+		assert!(events().contains(&Event::<Test>::RoyaltyPaid {
+			collection: collection_id,
+			item: item_1,
+			payer: user_2.clone(),
+			recipient: user_3.clone(),
+			amount: 10,
+		}));
+
+		// the item royalties override the collection royalties
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_2,
+			price
+		));
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 135);
+		assert_eq!(Balances::total_balance(&user_3), 10);
+		assert_eq!(Balances::total_balance(&user_4), 55);
+
+		// the item is exempt from the collection royalties
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_3,
+			price
+		));
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 235);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - 3 * price);
+		assert_eq!(Balances::total_balance(&user_3), 10);
+		assert_eq!(Balances::total_balance(&user_4), 55);
+	});
+}
+
+#[test]
+fn claim_swap_should_pay_royalties() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let user_4 = account(4);
+		let collection_id = 0;
+		let price = 100;
+		let duration = 2;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&user_1, initial_balance);
+		Balances::make_free_balance_be(&user_2, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		for (item, owner) in [(1, &user_1), (2, &user_2), (3, &user_1), (4, &user_2)] {
+			assert_ok!(Nfts::force_mint(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item,
+				owner.clone(),
+				default_item_config(),
+			));
+		}
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			None,
+			bvec![(user_3.clone(), Permill::from_percent(10))]
+		));
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(2),
+			bvec![(user_4.clone(), Permill::from_percent(20))]
+		));
+
+		// user_2 is paid for item 2, so its royalties are due
+		let price_with_direction =
+			PriceWithDirection { amount: price, direction: PriceDirection::Send };
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			1,
+			collection_id,
+			Some(2),
+			Some(price_with_direction.clone()),
+			duration,
+		));
+		assert_ok!(Nfts::claim_swap(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			2,
+			collection_id,
+			1,
+			Some(price_with_direction),
+		));
+		assert_eq!(Balances::total_balance(&user_1), initial_balance - price);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance + 80);
+		assert_eq!(Balances::total_balance(&user_3), 0);
+		assert_eq!(Balances::total_balance(&user_4), 20);
+		assert!(events().contains(&Event::<Test>::RoyaltyPaid {
+			collection: collection_id,
+			item: 2,
+			payer: user_1.clone(),
+			recipient: user_4.clone(),
+			amount: 20,
+		}));
+
+		// user_1 is paid for item 3, so the collection royalties are due
+		let price_with_direction =
+			PriceWithDirection { amount: price, direction: PriceDirection::Receive };
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			3,
+			collection_id,
+			Some(4),
+			Some(price_with_direction.clone()),
+			duration,
+		));
+		assert_ok!(Nfts::claim_swap(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			4,
+			collection_id,
+			3,
+			Some(price_with_direction),
+		));
+		assert_eq!(Balances::total_balance(&user_1), initial_balance - 10);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - 20);
+		assert_eq!(Balances::total_balance(&user_3), 10);
+		assert_eq!(Balances::total_balance(&user_4), 20);
+	});
+}
+
+#[test]
+fn royalties_should_be_fixed_when_listed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let user_4 = account(4);
+		let collection_id = 0;
+		let price = 100;
+		let duration = 2;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&user_1, initial_balance);
+		Balances::make_free_balance_be(&user_2, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		for (item, owner) in [(1, &user_1), (2, &user_1), (3, &user_2)] {
+			assert_ok!(Nfts::force_mint(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item,
+				owner.clone(),
+				default_item_config(),
+			));
+		}
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			None,
+			bvec![(user_3.clone(), Permill::from_percent(10))]
+		));
+
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			1,
+			Some(price),
+			None,
+		));
+		assert_eq!(
+			ItemPriceRoyalties::<Test>::get(collection_id, 1),
+			Some(bvec![(user_3.clone(), Permill::from_percent(10))])
+		);
+		let price_with_direction =
+			PriceWithDirection { amount: price, direction: PriceDirection::Receive };
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			2,
+			collection_id,
+			Some(3),
+			Some(price_with_direction.clone()),
+			duration,
+		));
+
+		// raising the royalties in front of the sales has no effect on them
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			None,
+			bvec![(user_4.clone(), Permill::from_percent(50))]
+		));
+
+		assert_ok!(Nfts::buy_item(RuntimeOrigin::signed(user_2.clone()), collection_id, 1, price));
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 90);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - price);
+		assert_eq!(Balances::total_balance(&user_3), 10);
+		assert_eq!(Balances::total_balance(&user_4), 0);
+		assert!(!ItemPriceRoyalties::<Test>::contains_key(collection_id, 1));
+
+		assert_ok!(Nfts::claim_swap(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			3,
+			collection_id,
+			2,
+			Some(price_with_direction),
+		));
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 180);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - 2 * price);
+		assert_eq!(Balances::total_balance(&user_3), 20);
+		assert_eq!(Balances::total_balance(&user_4), 0);
+		assert!(!SwapRoyalties::<Test>::contains_key(collection_id, 2));
+
+		// the royalties in effect are recorded again when the item is listed again
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			1,
+			Some(price),
+			None,
+		));
+		assert_eq!(
+			ItemPriceRoyalties::<Test>::get(collection_id, 1),
+			Some(bvec![(user_4.clone(), Permill::from_percent(50))])
+		);
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			1,
+			None,
+			None,
+		));
+		assert!(!ItemPriceRoyalties::<Test>::contains_key(collection_id, 1));
+	});
+}
+
+#[test]
+fn royalties_below_existential_deposit_should_be_skipped() {
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(10);
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let user_4 = account(4);
+		let collection_id = 0;
+		let item_id = 1;
+		let price = 100;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&user_1, initial_balance);
+		Balances::make_free_balance_be(&user_2, initial_balance);
+		Balances::make_free_balance_be(&user_4, 10);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_1.clone(),
+			None
+		));
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			None,
+			bvec![
+				(user_3.clone(), Permill::from_percent(5)),
+				(user_4.clone(), Permill::from_percent(5))
+			]
+		));
+
+		// user_3 doesn't exist and can't receive less than the existential deposit
+		assert_eq!(
+			<Nfts as Royalties<_, _>>::royalties(&collection_id, &item_id, &price),
+			vec![(user_4.clone(), 5)]
+		);
+
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			Some(price),
+			None,
+		));
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_id,
+			price
+		));
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 95);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - price);
+		assert_eq!(Balances::total_balance(&user_3), 0);
+		assert_eq!(Balances::total_balance(&user_4), 15);
+	});
+}
+
+#[test]
+fn royalties_trait_should_work() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let user_4 = account(4);
+		let collection_id = 0;
+		let item_id = 1;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&user_2, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_1.clone(),
+			None
+		));
+		assert_ok!(Nfts::set_royalties(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			None,
+			bvec![
+				(user_3.clone(), Permill::from_percent(10)),
+				(user_4.clone(), Permill::from_percent(5))
+			]
+		));
+
+		assert_eq!(
+			<Nfts as Royalties<_, _>>::royalties(&collection_id, &item_id, &200),
+			vec![(user_3.clone(), 20), (user_4.clone(), 10)]
+		);
+		// royalties rounding down to zero are not owed
+		assert_eq!(
+			<Nfts as Royalties<_, _>>::royalties(&collection_id, &item_id, &19),
+			vec![(user_3.clone(), 1)]
+		);
+
+		assert_noop!(
+			<Nfts as Royalties<_, _>>::pay_royalties(&collection_id, &2, &user_2, &200),
+			Error::<Test>::UnknownItem
+		);
+		assert_eq!(
+			<Nfts as Royalties<_, _>>::pay_royalties(&collection_id, &item_id, &user_2, &200),
+			Ok(170)
+		);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - 30);
+		assert_eq!(Balances::total_balance(&user_3), 20);
+		assert_eq!(Balances::total_balance(&user_4), 10);
+	});
+}
@@ -28,6 +28,7 @@ use frame_support::{
 	BoundedBTreeMap, BoundedBTreeSet,
 };
 use scale_info::{build::Fields, meta_type, Path, Type, TypeInfo, TypeParameter};
+use sp_runtime::Permill;
 
 pub type BlockNumberFor<T, I = ()> =
 	<<T as Config<I>>::BlockNumberProvider as BlockNumberProvider>::BlockNumber;
@@ -70,6 +71,16 @@ pub type ItemTipOf<T, I = ()> = ItemTip<
 	<T as SystemConfig>::AccountId,
 	BalanceOf<T, I>,
 >;
+/// A type alias for the royalty recipients of a collection or an item, along with their shares of
+/// the sale price.
+pub type RoyaltiesOf<T, I = ()> =
+	BoundedVec<(<T as SystemConfig>::AccountId, Permill), <T as Config<I>>::MaxRoyaltyRecipients>;
+/// A type alias for the royalties of a collection or an item, along with their deposit.
+pub type RoyaltiesDetailsFor<T, I = ()> = RoyaltiesDetails<
+	<T as SystemConfig>::AccountId,
+	DepositBalanceOf<T, I>,
+	<T as Config<I>>::MaxRoyaltyRecipients,
+>;
 /// A type alias for the settings configuration of a collection.
 pub type CollectionConfigFor<T, I = ()> =
 	CollectionConfig<BalanceOf<T, I>, BlockNumberFor<T, I>, <T as Config<I>>::CollectionId>;
@@ -203,6 +214,19 @@ pub struct ItemMetadata<Deposit, StringLimit: Get<u32>> {
 	pub data: BoundedVec<u8, StringLimit>,
 }
 
+/// Information about the royalties of a collection or an item.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(MaxRecipients))]
+#[codec(mel_bound(AccountId: MaxEncodedLen, Deposit: MaxEncodedLen))]
+pub struct RoyaltiesDetails<AccountId, Deposit, MaxRecipients: Get<u32>> {
+	/// The balance deposited for these royalties.
+	///
+	/// This pays for the recipients stored in this struct.
+	pub deposit: Deposit,
+	/// The recipients and their shares of the sale price.
+	pub royalties: BoundedVec<(AccountId, Permill), MaxRecipients>,
+}
+
 /// Information about the tip.
 #[derive(
 	Clone,
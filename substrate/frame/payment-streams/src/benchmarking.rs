@@ -0,0 +1,177 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Payment Streams pallet benchmarking.
+
+use super::*;
+use crate::Pallet as PaymentStreams;
+use frame_benchmarking::{account, v2::*, whitelisted_caller, BenchmarkError};
+use frame_support::assert_ok;
+use frame_system::{Pallet as System, RawOrigin};
+use sp_runtime::traits::One;
+
+/// Benchmark Helper
+pub trait BenchmarkHelper<AssetKind> {
+	/// Returns the asset to stream.
+	///
+	/// If the asset does not exist, it is created by the helper.
+	fn asset() -> AssetKind;
+}
+
+/// An amount of `asset` comfortably above its minimum balance.
+fn unit<T: Config>(asset: &T::AssetKind) -> T::Balance {
+	T::Assets::minimum_balance(asset.clone())
+		.max(T::Balance::one())
+		.saturating_mul(1000u32.into())
+}
+
+/// Funds `who` for the storage deposit of a stream.
+fn fund_deposit<T: Config>(who: &T::AccountId) {
+	let deposit = <T::Currency as fungible::Inspect<_>>::minimum_balance()
+		.saturating_add(T::StreamDeposit::get())
+		.saturating_mul(2u32.into());
+	assert_ok!(<T::Currency as fungible::Mutate<_>>::mint_into(who, deposit));
+}
+
+/// Creates a stream from a funded payer, accruing from the current block.
+fn open_stream<T: Config>(
+	payer: T::AccountId,
+) -> (StreamId, T::AccountId, T::AccountId, T::Balance) {
+	let asset = T::BenchmarkHelper::asset();
+	let deposit = unit::<T>(&asset);
+	assert_ok!(T::Assets::mint_into(asset.clone(), &payer, deposit.saturating_mul(3u32.into())));
+	fund_deposit::<T>(&payer);
+	let payee: T::AccountId = account("payee", 0, 0);
+	assert_ok!(T::Assets::mint_into(asset.clone(), &payee, deposit));
+	let stream_id = PaymentStreams::<T>::do_create_stream(
+		payer.clone(),
+		payee.clone(),
+		asset,
+		One::one(),
+		deposit,
+		None,
+	)
+	.unwrap();
+	(stream_id, payer, payee, deposit)
+}
+
+fn advance<T: Config>(blocks: u32) {
+	System::<T>::set_block_number(System::<T>::block_number().saturating_add(blocks.into()));
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	System::<T>::assert_last_event(generic_event.into());
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn create_stream() -> Result<(), BenchmarkError> {
+		let asset = T::BenchmarkHelper::asset();
+		let deposit = unit::<T>(&asset);
+		let caller: T::AccountId = whitelisted_caller();
+		assert_ok!(T::Assets::mint_into(
+			asset.clone(),
+			&caller,
+			deposit.saturating_mul(2u32.into())
+		));
+		fund_deposit::<T>(&caller);
+		let payee: T::AccountId = account("payee", 0, 0);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			T::Lookup::unlookup(payee),
+			Box::new(asset),
+			One::one(),
+			deposit,
+			None,
+		);
+
+		assert!(Streams::<T>::contains_key(0));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn withdraw() -> Result<(), BenchmarkError> {
+		let (stream_id, _, payee, _) = open_stream::<T>(account("payer", 0, 0));
+		advance::<T>(10);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(payee), stream_id);
+
+		assert_last_event::<T>(Event::Withdrawn { stream_id, amount: 10u32.into() }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn top_up() -> Result<(), BenchmarkError> {
+		let (stream_id, payer, _, deposit) = open_stream::<T>(whitelisted_caller());
+		advance::<T>(10);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(payer), stream_id, deposit);
+
+		assert_last_event::<T>(Event::ToppedUp { stream_id, amount: deposit }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn pause() -> Result<(), BenchmarkError> {
+		let (stream_id, payer, _, _) = open_stream::<T>(whitelisted_caller());
+		advance::<T>(10);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(payer), stream_id);
+
+		assert_last_event::<T>(Event::StreamPaused { stream_id }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn resume() -> Result<(), BenchmarkError> {
+		let (stream_id, payer, _, _) = open_stream::<T>(whitelisted_caller());
+		assert_ok!(PaymentStreams::<T>::pause(RawOrigin::Signed(payer.clone()).into(), stream_id));
+		advance::<T>(10);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(payer), stream_id);
+
+		assert_last_event::<T>(Event::StreamResumed { stream_id }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel() -> Result<(), BenchmarkError> {
+		let (stream_id, payer, _, deposit) = open_stream::<T>(whitelisted_caller());
+		advance::<T>(10);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(payer), stream_id);
+
+		let paid: T::Balance = 10u32.into();
+		assert_last_event::<T>(
+			Event::StreamCancelled { stream_id, paid, refunded: deposit.saturating_sub(paid) }
+				.into(),
+		);
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(PaymentStreams, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,641 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Payment Streams Pallet
+//!
+//! Continuous payments from one account to another, in any asset of [`Config::Assets`].
+//!
+//! ## Overview
+//!
+//! A stream pays its payee a fixed amount per block out of a deposit of the payer. The deposit is
+//! put on hold when the stream is created and moves to the payee as it accrues, whenever the
+//! stream is settled. Anyone may settle a stream with [`Pallet::withdraw`]; a stream is closed
+//! once its deposit is paid out in full.
+//!
+//! The payer can top up the deposit of a stream, and pause and resume it. Nothing accrues while a
+//! stream is paused. Either side can cancel a stream, which pays the payee what accrued up to the
+//! current block and releases the rest of the deposit to the payer. A stream can be cancelled
+//! even if paying the payee fails, in which case the whole deposit returns to the payer.
+//!
+//! Creating a stream also holds [`Config::StreamDeposit`] of the native currency of the payer
+//! for its storage, until the stream is closed.
+//!
+//! [`PayThroughStream`] implements the [`Pay`] trait, so that `pallet-treasury` and other users
+//! of the trait can make their payments over a period of time rather than at once.
+
+#![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use frame_support::{
+	storage::with_storage_layer,
+	traits::{
+		fungible,
+		fungibles::{Inspect, Mutate, MutateHold},
+		tokens::{Balance, Fortitude, Pay, PaymentStatus, Precision, Restriction},
+		Get,
+	},
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{CheckedAdd, Saturating, StaticLookup, Zero},
+	ArithmeticError, DispatchError, SaturatedConversion,
+};
+
+/// Identifier of a stream.
+pub type StreamId = u32;
+
+/// The balance type of the native currency the storage deposit of a stream is held in.
+pub type DepositBalanceOf<T> =
+	<<T as Config>::Currency as fungible::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Convenience alias for `Stream`.
+pub type StreamOf<T> = Stream<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::AssetKind,
+	<T as Config>::Balance,
+	BlockNumberFor<T>,
+	DepositBalanceOf<T>,
+>;
+
+type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+
+/// Whether a stream accrues.
+#[derive(Clone, Copy, Decode, Encode, Debug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub enum StreamStatus {
+	/// The stream pays its rate every block.
+	Active,
+	/// Nothing accrues until the payer resumes the stream.
+	Paused,
+}
+
+/// The state of a payment made through a stream by [`PayThroughStream`].
+#[derive(Clone, Copy, Decode, Encode, Debug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub enum PaymentState {
+	/// The stream is open.
+	Streaming,
+	/// The deposit of the stream was paid out in full.
+	Completed,
+	/// The stream was cancelled after paying what accrued, forfeiting the rest of the deposit.
+	Cancelled,
+	/// The stream was cancelled without paying what accrued, as paying it failed.
+	Failed,
+}
+
+/// A stream paying `rate` of `asset` per block from `payer` to `payee`.
+#[derive(Clone, Decode, Encode, Debug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub struct Stream<AccountId, AssetKind, Balance, BlockNumber, DepositBalance> {
+	/// The account paying, whose deposit is on hold.
+	pub payer: AccountId,
+	/// The account paid.
+	pub payee: AccountId,
+	/// The asset paid.
+	pub asset: AssetKind,
+	/// The amount paid per block.
+	pub rate: Balance,
+	/// The part of the deposit not paid out yet.
+	pub remaining: Balance,
+	/// The block up to which the stream has been paid out, or the block it starts at if that is
+	/// still ahead.
+	pub last_settled: BlockNumber,
+	/// Whether the stream accrues.
+	pub status: StreamStatus,
+	/// The amount of the native currency held from the payer for storing the stream.
+	pub storage_deposit: DepositBalance,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Funds are the deposit of a stream.
+		#[codec(index = 0)]
+		Streaming,
+		/// Funds are held for storing a stream.
+		#[codec(index = 1)]
+		StreamDeposit,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// Type of asset class, sourced from [`Config::Assets`], utilized to identify the assets
+		/// streamed.
+		type AssetKind: Parameter + MaxEncodedLen;
+
+		/// The type in which the assets are measured.
+		type Balance: Balance;
+
+		/// Registry of the assets streamed, holding the deposits of the streams.
+		type Assets: Inspect<Self::AccountId, AssetId = Self::AssetKind, Balance = Self::Balance>
+			+ Mutate<Self::AccountId>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// The native currency the storage deposit of a stream is held in.
+		type Currency: fungible::Mutate<Self::AccountId>
+			+ fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// The amount held from the payer of a stream while it is open.
+		#[pallet::constant]
+		type StreamDeposit: Get<DepositBalanceOf<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Helper for benchmarking.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: benchmarking::BenchmarkHelper<Self::AssetKind>;
+	}
+
+	/// Open streams.
+	#[pallet::storage]
+	pub type Streams<T: Config> = StorageMap<_, Blake2_128Concat, StreamId, StreamOf<T>>;
+
+	/// Stores the [`StreamId`] to use for the next stream.
+	///
+	/// Incremented when a new stream is created.
+	#[pallet::storage]
+	pub type NextStreamId<T: Config> = StorageValue<_, StreamId, ValueQuery>;
+
+	/// The state of the payments made through streams by [`PayThroughStream`].
+	///
+	/// Kept after the stream closes until the outcome of the payment is checked once.
+	#[pallet::storage]
+	pub type Payments<T: Config> = StorageMap<_, Blake2_128Concat, StreamId, PaymentState>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A new stream was created.
+		StreamCreated {
+			/// The stream.
+			stream_id: StreamId,
+			/// The account paying.
+			payer: T::AccountId,
+			/// The account paid.
+			payee: T::AccountId,
+			/// The asset paid.
+			asset: T::AssetKind,
+			/// The amount paid per block.
+			rate: T::Balance,
+			/// The amount put on hold to pay the stream.
+			deposit: T::Balance,
+			/// The block the stream starts accruing at.
+			start: BlockNumberFor<T>,
+		},
+		/// The accrued amount of a stream was paid to its payee.
+		Withdrawn {
+			/// The stream.
+			stream_id: StreamId,
+			/// The amount paid.
+			amount: T::Balance,
+		},
+		/// The deposit of a stream was paid out in full and the stream closed.
+		StreamCompleted {
+			/// The stream.
+			stream_id: StreamId,
+		},
+		/// The payer added to the deposit of a stream.
+		ToppedUp {
+			/// The stream.
+			stream_id: StreamId,
+			/// The amount added.
+			amount: T::Balance,
+		},
+		/// A stream was paused.
+		StreamPaused {
+			/// The stream.
+			stream_id: StreamId,
+		},
+		/// A paused stream was resumed.
+		StreamResumed {
+			/// The stream.
+			stream_id: StreamId,
+		},
+		/// A stream was cancelled and closed.
+		StreamCancelled {
+			/// The stream.
+			stream_id: StreamId,
+			/// The accrued amount paid to the payee, zero if paying it failed.
+			paid: T::Balance,
+			/// The rest of the deposit, released to the payer.
+			refunded: T::Balance,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The payer and payee must differ.
+		SamePayerAndPayee,
+		/// The rate must not be zero.
+		ZeroRate,
+		/// The deposit is below the minimum balance of the asset.
+		DepositTooLow,
+		/// The amount must not be zero.
+		ZeroAmount,
+		/// The stream cannot start in the past.
+		StartInPast,
+		/// The stream does not exist or was closed.
+		UnknownStream,
+		/// Only the payer of the stream can do this.
+		NotPayer,
+		/// Only the payer or the payee of the stream can do this.
+		NotParticipant,
+		/// The stream is already paused.
+		AlreadyPaused,
+		/// The stream is not paused.
+		NotPaused,
+	}
+
+	/// Pallet's callable functions.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a stream paying `rate` of `asset` per block to `payee`, out of a `deposit` put
+		/// on hold from the caller.
+		///
+		/// The stream starts accruing at block `start`, or the current block if `None`, and
+		/// closes once `deposit` is paid out. [`Config::StreamDeposit`] is held from the caller
+		/// until then.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::create_stream())]
+		pub fn create_stream(
+			origin: OriginFor<T>,
+			payee: AccountIdLookupOf<T>,
+			asset: Box<T::AssetKind>,
+			rate: T::Balance,
+			deposit: T::Balance,
+			start: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			let payee = T::Lookup::lookup(payee)?;
+			Self::do_create_stream(payer, payee, *asset, rate, deposit, start)?;
+			Ok(())
+		}
+
+		/// Pay the payee of a stream what accrued so far.
+		///
+		/// Any account may settle a stream. The stream is closed if its deposit is paid out.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::withdraw())]
+		pub fn withdraw(origin: OriginFor<T>, stream_id: StreamId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let mut stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::UnknownStream)?;
+			Self::settle(stream_id, &mut stream)?;
+			if stream.remaining.is_zero() {
+				Self::close(stream_id, &stream, PaymentState::Completed);
+				Self::deposit_event(Event::StreamCompleted { stream_id });
+			} else {
+				Streams::<T>::insert(stream_id, stream);
+			}
+			Ok(())
+		}
+
+		/// Add `amount` to the deposit of a stream of the caller.
+		///
+		/// What accrued so far is paid out first, so the top up does not pay for blocks the
+		/// stream ran dry in.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::top_up())]
+		pub fn top_up(
+			origin: OriginFor<T>,
+			stream_id: StreamId,
+			amount: T::Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			let mut stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::UnknownStream)?;
+			ensure!(stream.payer == who, Error::<T>::NotPayer);
+
+			Self::settle(stream_id, &mut stream)?;
+			T::Assets::hold(stream.asset.clone(), &HoldReason::Streaming.into(), &who, amount)?;
+			stream.remaining =
+				stream.remaining.checked_add(&amount).ok_or(ArithmeticError::Overflow)?;
+			Streams::<T>::insert(stream_id, stream);
+
+			Self::deposit_event(Event::ToppedUp { stream_id, amount });
+			Ok(())
+		}
+
+		/// Pause a stream of the caller, after paying out what accrued so far.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::pause())]
+		pub fn pause(origin: OriginFor<T>, stream_id: StreamId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::UnknownStream)?;
+			ensure!(stream.payer == who, Error::<T>::NotPayer);
+			ensure!(stream.status == StreamStatus::Active, Error::<T>::AlreadyPaused);
+
+			Self::settle(stream_id, &mut stream)?;
+			stream.status = StreamStatus::Paused;
+			Streams::<T>::insert(stream_id, stream);
+
+			Self::deposit_event(Event::StreamPaused { stream_id });
+			Ok(())
+		}
+
+		/// Resume a paused stream of the caller.
+		///
+		/// The stream accrues again from the current block, or from its start if that is still
+		/// ahead.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::resume())]
+		pub fn resume(origin: OriginFor<T>, stream_id: StreamId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::UnknownStream)?;
+			ensure!(stream.payer == who, Error::<T>::NotPayer);
+			ensure!(stream.status == StreamStatus::Paused, Error::<T>::NotPaused);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			stream.last_settled = stream.last_settled.max(now);
+			stream.status = StreamStatus::Active;
+			Streams::<T>::insert(stream_id, stream);
+
+			Self::deposit_event(Event::StreamResumed { stream_id });
+			Ok(())
+		}
+
+		/// Cancel a stream the caller pays or is paid by.
+		///
+		/// The payee is paid what accrued up to the current block and the rest of the deposit is
+		/// released to the payer. If paying the payee fails, the stream is cancelled anyway and
+		/// the whole deposit is released to the payer.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::cancel())]
+		pub fn cancel(origin: OriginFor<T>, stream_id: StreamId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut stream = Streams::<T>::get(stream_id).ok_or(Error::<T>::UnknownStream)?;
+			ensure!(stream.payer == who || stream.payee == who, Error::<T>::NotParticipant);
+
+			let (paid, state) = match with_storage_layer(|| Self::settle(stream_id, &mut stream)) {
+				Ok(paid) => (paid, PaymentState::Cancelled),
+				Err(_) => (Zero::zero(), PaymentState::Failed),
+			};
+			let refunded = T::Assets::release(
+				stream.asset.clone(),
+				&HoldReason::Streaming.into(),
+				&stream.payer,
+				stream.remaining,
+				Precision::BestEffort,
+			)
+			.unwrap_or_else(|_| Zero::zero());
+			Self::close(stream_id, &stream, state);
+
+			Self::deposit_event(Event::StreamCancelled { stream_id, paid, refunded });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Create a stream paying `rate` of `asset` per block from `payer` to `payee`, out of a
+		/// `deposit` put on hold from `payer`.
+		///
+		/// See [`Pallet::create_stream`].
+		pub fn do_create_stream(
+			payer: T::AccountId,
+			payee: T::AccountId,
+			asset: T::AssetKind,
+			rate: T::Balance,
+			deposit: T::Balance,
+			start: Option<BlockNumberFor<T>>,
+		) -> Result<StreamId, DispatchError> {
+			ensure!(payer != payee, Error::<T>::SamePayerAndPayee);
+			ensure!(!rate.is_zero(), Error::<T>::ZeroRate);
+			ensure!(
+				!deposit.is_zero() && deposit >= T::Assets::minimum_balance(asset.clone()),
+				Error::<T>::DepositTooLow
+			);
+			let now = frame_system::Pallet::<T>::block_number();
+			let start = start.unwrap_or(now);
+			ensure!(start >= now, Error::<T>::StartInPast);
+
+			T::Assets::hold(asset.clone(), &HoldReason::Streaming.into(), &payer, deposit)?;
+			let storage_deposit = T::StreamDeposit::get();
+			<T::Currency as fungible::MutateHold<_>>::hold(
+				&HoldReason::StreamDeposit.into(),
+				&payer,
+				storage_deposit,
+			)?;
+
+			let stream_id = NextStreamId::<T>::get();
+			NextStreamId::<T>::put(stream_id.checked_add(1).ok_or(ArithmeticError::Overflow)?);
+			Streams::<T>::insert(
+				stream_id,
+				Stream {
+					payer: payer.clone(),
+					payee: payee.clone(),
+					asset: asset.clone(),
+					rate,
+					remaining: deposit,
+					last_settled: start,
+					status: StreamStatus::Active,
+					storage_deposit,
+				},
+			);
+
+			Self::deposit_event(Event::StreamCreated {
+				stream_id,
+				payer,
+				payee,
+				asset,
+				rate,
+				deposit,
+				start,
+			});
+			Ok(stream_id)
+		}
+
+		/// The amount a stream owes its payee as of the current block.
+		pub fn accrued(stream: &StreamOf<T>) -> T::Balance {
+			let now = frame_system::Pallet::<T>::block_number();
+			if stream.status == StreamStatus::Paused || now <= stream.last_settled {
+				return Zero::zero()
+			}
+			let blocks: T::Balance = now
+				.saturating_sub(stream.last_settled)
+				.saturated_into::<u128>()
+				.saturated_into();
+			stream.rate.saturating_mul(blocks).min(stream.remaining)
+		}
+
+		/// The amount the stream `stream_id` owes its payee as of the current block, if it exists.
+		pub fn withdrawable(stream_id: StreamId) -> Option<T::Balance> {
+			Streams::<T>::get(stream_id).map(|stream| Self::accrued(&stream))
+		}
+
+		/// Removes `stream` from storage, releasing its storage deposit and recording `state` if
+		/// it pays a [`PayThroughStream`] payment.
+		pub(crate) fn close(stream_id: StreamId, stream: &StreamOf<T>, state: PaymentState) {
+			Streams::<T>::remove(stream_id);
+			let _ = <T::Currency as fungible::MutateHold<_>>::release(
+				&HoldReason::StreamDeposit.into(),
+				&stream.payer,
+				stream.storage_deposit,
+				Precision::BestEffort,
+			);
+			Payments::<T>::mutate_extant(stream_id, |s| *s = state);
+		}
+
+		/// Pays the payee of `stream` what accrued so far and returns the amount paid.
+		///
+		/// Does not write `stream` back to storage.
+		fn settle(
+			stream_id: StreamId,
+			stream: &mut StreamOf<T>,
+		) -> Result<T::Balance, DispatchError> {
+			let amount = Self::accrued(stream);
+			let now = frame_system::Pallet::<T>::block_number();
+			if stream.status == StreamStatus::Active {
+				stream.last_settled = stream.last_settled.max(now);
+			}
+			if amount.is_zero() {
+				return Ok(amount)
+			}
+
+			T::Assets::transfer_on_hold(
+				stream.asset.clone(),
+				&HoldReason::Streaming.into(),
+				&stream.payer,
+				&stream.payee,
+				amount,
+				Precision::Exact,
+				Restriction::Free,
+				Fortitude::Polite,
+			)?;
+			stream.remaining.saturating_reduce(amount);
+
+			Self::deposit_event(Event::Withdrawn { stream_id, amount });
+			Ok(amount)
+		}
+	}
+}
+
+/// Implementation of the [`Pay`] trait which pays through a stream from the account `Source`,
+/// spreading each payment evenly over `Duration` blocks.
+///
+/// The payment is in progress for as long as its stream is open. The beneficiary receives the
+/// accrued amount whenever the stream is settled, and may cancel it to forfeit the rest, which
+/// then returns to `Source`. The payment succeeds once its stream is paid out or cancelled, and
+/// fails if paying the beneficiary failed when the stream was cancelled. The outcome is forgotten
+/// once it is checked.
+///
+/// `Source` must hold enough of the native currency for the storage deposit of each stream.
+pub struct PayThroughStream<T, Source, Duration>(PhantomData<(T, Source, Duration)>);
+
+impl<T, Source, Duration> Pay for PayThroughStream<T, Source, Duration>
+where
+	T: Config,
+	Source: Get<T::AccountId>,
+	Duration: Get<BlockNumberFor<T>>,
+{
+	type Balance = T::Balance;
+	type Beneficiary = T::AccountId;
+	type AssetKind = T::AssetKind;
+	type Id = StreamId;
+	type Error = DispatchError;
+
+	fn pay(
+		who: &Self::Beneficiary,
+		asset_kind: Self::AssetKind,
+		amount: Self::Balance,
+	) -> Result<Self::Id, Self::Error> {
+		let blocks: T::Balance =
+			Duration::get().max(1u32.into()).saturated_into::<u128>().saturated_into();
+		let rate = amount / blocks;
+		let rate = if rate.saturating_mul(blocks) < amount {
+			rate.saturating_add(1u32.into())
+		} else {
+			rate
+		};
+		let stream_id = Pallet::<T>::do_create_stream(
+			Source::get(),
+			who.clone(),
+			asset_kind,
+			rate,
+			amount,
+			None,
+		)?;
+		Payments::<T>::insert(stream_id, PaymentState::Streaming);
+		Ok(stream_id)
+	}
+
+	fn check_payment(id: Self::Id) -> PaymentStatus {
+		let status = match Payments::<T>::get(id) {
+			Some(PaymentState::Streaming) => return PaymentStatus::InProgress,
+			Some(PaymentState::Completed | PaymentState::Cancelled) => PaymentStatus::Success,
+			Some(PaymentState::Failed) => PaymentStatus::Failure,
+			None => return PaymentStatus::Unknown,
+		};
+		// the outcome of a payment is only reported once, as required by `Pay`
+		Payments::<T>::remove(id);
+		status
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_successful(
+		_: &Self::Beneficiary,
+		asset_kind: Self::AssetKind,
+		amount: Self::Balance,
+	) {
+		let minimum = T::Assets::minimum_balance(asset_kind.clone());
+		T::Assets::mint_into(asset_kind, &Source::get(), amount.saturating_add(minimum)).unwrap();
+		let deposit = <T::Currency as fungible::Inspect<_>>::minimum_balance()
+			.saturating_add(T::StreamDeposit::get());
+		<T::Currency as fungible::Mutate<_>>::mint_into(&Source::get(), deposit).unwrap();
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_concluded(id: Self::Id) {
+		if let Some(stream) = Streams::<T>::get(id) {
+			let _ = T::Assets::release(
+				stream.asset.clone(),
+				&HoldReason::Streaming.into(),
+				&stream.payer,
+				stream.remaining,
+				Precision::BestEffort,
+			);
+			Pallet::<T>::close(id, &stream, PaymentState::Completed);
+		}
+	}
+}
@@ -0,0 +1,419 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Payment Streams pallet tests.
+
+use crate::{mock::*, *};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungible::NativeOrWithId,
+		fungibles::{Inspect as _, InspectHold},
+	},
+};
+
+const NATIVE: NativeOrWithId<u32> = NativeOrWithId::Native;
+const ASSET: NativeOrWithId<u32> = NativeOrWithId::WithId(1);
+
+fn events() -> Vec<Event<Test>> {
+	let result = System::events()
+		.into_iter()
+		.map(|r| r.event)
+		.filter_map(
+			|e| if let RuntimeEvent::PaymentStreams(inner) = e { Some(inner) } else { None },
+		)
+		.collect();
+
+	System::reset_events();
+
+	result
+}
+
+fn create(
+	payer: u64,
+	payee: u64,
+	asset: NativeOrWithId<u32>,
+	rate: u64,
+	deposit: u64,
+	start: Option<u64>,
+) -> StreamId {
+	let stream_id = NextStreamId::<Test>::get();
+	assert_ok!(PaymentStreams::create_stream(
+		RuntimeOrigin::signed(payer),
+		payee,
+		Box::new(asset),
+		rate,
+		deposit,
+		start,
+	));
+	stream_id
+}
+
+fn held(who: u64, asset: NativeOrWithId<u32>) -> u64 {
+	NativeAndAssets::balance_on_hold(asset, &HoldReason::Streaming.into(), &who)
+}
+
+fn storage_deposit(who: u64) -> u64 {
+	NativeAndAssets::balance_on_hold(NATIVE, &HoldReason::StreamDeposit.into(), &who)
+}
+
+fn balance(who: u64, asset: NativeOrWithId<u32>) -> u64 {
+	NativeAndAssets::balance(asset, &who)
+}
+
+fn run_to(n: u64) {
+	System::set_block_number(n);
+}
+
+#[test]
+fn create_stream_holds_deposit() {
+	new_test_ext().execute_with(|| {
+		let stream_id = create(1, 2, ASSET, 10, 1_000, None);
+
+		assert_eq!(held(1, ASSET), 1_000);
+		assert_eq!(balance(1, ASSET), 9_000);
+		assert_eq!(
+			Streams::<Test>::get(stream_id),
+			Some(Stream {
+				payer: 1,
+				payee: 2,
+				asset: ASSET,
+				rate: 10,
+				remaining: 1_000,
+				last_settled: 1,
+				status: StreamStatus::Active,
+				storage_deposit: 5,
+			})
+		);
+		assert_eq!(storage_deposit(1), 5);
+		assert_eq!(
+			events(),
+			[Event::StreamCreated {
+				stream_id,
+				payer: 1,
+				payee: 2,
+				asset: ASSET,
+				rate: 10,
+				deposit: 1_000,
+				start: 1,
+			}]
+		);
+		assert_eq!(NextStreamId::<Test>::get(), 1);
+	});
+}
+
+#[test]
+fn create_stream_checks_parameters() {
+	new_test_ext().execute_with(|| {
+		let origin = RuntimeOrigin::signed(1);
+		assert_noop!(
+			PaymentStreams::create_stream(origin.clone(), 1, Box::new(ASSET), 10, 1_000, None),
+			Error::<Test>::SamePayerAndPayee
+		);
+		assert_noop!(
+			PaymentStreams::create_stream(origin.clone(), 2, Box::new(ASSET), 0, 1_000, None),
+			Error::<Test>::ZeroRate
+		);
+		assert_noop!(
+			PaymentStreams::create_stream(origin.clone(), 2, Box::new(ASSET), 10, 0, None),
+			Error::<Test>::DepositTooLow
+		);
+		run_to(5);
+		assert_noop!(
+			PaymentStreams::create_stream(origin.clone(), 2, Box::new(ASSET), 10, 1_000, Some(4)),
+			Error::<Test>::StartInPast
+		);
+		assert!(
+			PaymentStreams::create_stream(origin, 2, Box::new(ASSET), 10, 20_000, None).is_err()
+		);
+	});
+}
+
+#[test]
+fn withdraw_pays_accrued_amount() {
+	new_test_ext().execute_with(|| {
+		let stream_id = create(1, 2, NATIVE, 10, 1_000, None);
+		events();
+
+		run_to(11);
+		assert_eq!(PaymentStreams::withdrawable(stream_id), Some(100));
+		// anyone may settle the stream
+		assert_ok!(PaymentStreams::withdraw(RuntimeOrigin::signed(3), stream_id));
+
+		assert_eq!(balance(2, NATIVE), 10_100);
+		assert_eq!(held(1, NATIVE), 900);
+		assert_eq!(events(), [Event::Withdrawn { stream_id, amount: 100 }]);
+
+		// nothing more accrued in the same block
+		assert_ok!(PaymentStreams::withdraw(RuntimeOrigin::signed(2), stream_id));
+		assert_eq!(balance(2, NATIVE), 10_100);
+		assert_eq!(events(), []);
+	});
+}
+
+#[test]
+fn stream_completes_when_deposit_is_paid_out() {
+	new_test_ext().execute_with(|| {
+		let stream_id = create(1, 2, ASSET, 10, 1_000, None);
+		events();
+
+		run_to(500);
+		assert_eq!(PaymentStreams::withdrawable(stream_id), Some(1_000));
+		assert_ok!(PaymentStreams::withdraw(RuntimeOrigin::signed(2), stream_id));
+
+		assert_eq!(balance(2, ASSET), 11_000);
+		assert_eq!(balance(1, ASSET), 9_000);
+		assert_eq!(held(1, ASSET), 0);
+		assert_eq!(storage_deposit(1), 0);
+		assert!(!Streams::<Test>::contains_key(stream_id));
+		assert_eq!(
+			events(),
+			[Event::Withdrawn { stream_id, amount: 1_000 }, Event::StreamCompleted { stream_id }]
+		);
+	});
+}
+
+#[test]
+fn stream_accrues_from_start() {
+	new_test_ext().execute_with(|| {
+		let stream_id = create(1, 2, ASSET, 10, 1_000, Some(20));
+
+		run_to(20);
+		assert_eq!(PaymentStreams::withdrawable(stream_id), Some(0));
+		run_to(25);
+		assert_eq!(PaymentStreams::withdrawable(stream_id), Some(50));
+	});
+}
+
+#[test]
+fn pause_and_resume_work() {
+	new_test_ext().execute_with(|| {
+		let stream_id = create(1, 2, ASSET, 10, 1_000, None);
+		events();
+
+		run_to(6);
+		assert_noop!(
+			PaymentStreams::pause(RuntimeOrigin::signed(2), stream_id),
+			Error::<Test>::NotPayer
+		);
+		assert_noop!(
+			PaymentStreams::resume(RuntimeOrigin::signed(1), stream_id),
+			Error::<Test>::NotPaused
+		);
+		// settles what accrued before pausing
+		assert_ok!(PaymentStreams::pause(RuntimeOrigin::signed(1), stream_id));
+		assert_eq!(balance(2, ASSET), 10_050);
+		assert_eq!(
+			events(),
+			[Event::Withdrawn { stream_id, amount: 50 }, Event::StreamPaused { stream_id }]
+		);
+		assert_noop!(
+			PaymentStreams::pause(RuntimeOrigin::signed(1), stream_id),
+			Error::<Test>::AlreadyPaused
+		);
+
+		// nothing accrues while paused
+		run_to(50);
+		assert_eq!(PaymentStreams::withdrawable(stream_id), Some(0));
+		assert_ok!(PaymentStreams::withdraw(RuntimeOrigin::signed(2), stream_id));
+		assert_eq!(balance(2, ASSET), 10_050);
+
+		assert_ok!(PaymentStreams::resume(RuntimeOrigin::signed(1), stream_id));
+		assert_eq!(events(), [Event::StreamResumed { stream_id }]);
+		run_to(53);
+		assert_eq!(PaymentStreams::withdrawable(stream_id), Some(30));
+	});
+}
+
+#[test]
+fn top_up_extends_stream() {
+	new_test_ext().execute_with(|| {
+		let stream_id = create(1, 2, ASSET, 10, 100, None);
+		events();
+
+		assert_noop!(
+			PaymentStreams::top_up(RuntimeOrigin::signed(2), stream_id, 100),
+			Error::<Test>::NotPayer
+		);
+		assert_noop!(
+			PaymentStreams::top_up(RuntimeOrigin::signed(1), stream_id, 0),
+			Error::<Test>::ZeroAmount
+		);
+
+		// the stream ran dry at block 11
+		run_to(30);
+		assert_ok!(PaymentStreams::top_up(RuntimeOrigin::signed(1), stream_id, 500));
+		assert_eq!(balance(2, ASSET), 10_100);
+		assert_eq!(held(1, ASSET), 500);
+		assert_eq!(
+			events(),
+			[
+				Event::Withdrawn { stream_id, amount: 100 },
+				Event::ToppedUp { stream_id, amount: 500 },
+			]
+		);
+
+		// the top up only pays for blocks after it
+		run_to(35);
+		assert_eq!(PaymentStreams::withdrawable(stream_id), Some(50));
+	});
+}
+
+#[test]
+fn cancel_settles_pro_rata() {
+	new_test_ext().execute_with(|| {
+		let stream_id = create(1, 2, ASSET, 10, 1_000, None);
+		events();
+
+		assert_noop!(
+			PaymentStreams::cancel(RuntimeOrigin::signed(3), stream_id),
+			Error::<Test>::NotParticipant
+		);
+
+		run_to(31);
+		assert_ok!(PaymentStreams::cancel(RuntimeOrigin::signed(1), stream_id));
+
+		assert_eq!(balance(2, ASSET), 10_300);
+		assert_eq!(balance(1, ASSET), 9_700);
+		assert_eq!(held(1, ASSET), 0);
+		assert_eq!(storage_deposit(1), 0);
+		assert!(!Streams::<Test>::contains_key(stream_id));
+		assert_eq!(
+			events(),
+			[
+				Event::Withdrawn { stream_id, amount: 300 },
+				Event::StreamCancelled { stream_id, paid: 300, refunded: 700 },
+			]
+		);
+		assert_noop!(
+			PaymentStreams::withdraw(RuntimeOrigin::signed(2), stream_id),
+			Error::<Test>::UnknownStream
+		);
+	});
+}
+
+#[test]
+fn cancel_works_when_payee_cannot_be_paid() {
+	new_test_ext().execute_with(|| {
+		let stream_id = create(1, 2, ASSET, 10, 1_000, None);
+		events();
+
+		run_to(31);
+		assert_ok!(Assets::block(RuntimeOrigin::signed(1), 1, 2));
+		assert!(PaymentStreams::withdraw(RuntimeOrigin::signed(2), stream_id).is_err());
+
+		assert_ok!(PaymentStreams::cancel(RuntimeOrigin::signed(1), stream_id));
+		assert_eq!(balance(2, ASSET), 10_000);
+		assert_eq!(balance(1, ASSET), 10_000);
+		assert_eq!(held(1, ASSET), 0);
+		assert_eq!(storage_deposit(1), 0);
+		assert!(!Streams::<Test>::contains_key(stream_id));
+		assert_eq!(events(), [Event::StreamCancelled { stream_id, paid: 0, refunded: 1_000 }]);
+	});
+}
+
+#[test]
+fn payee_can_cancel() {
+	new_test_ext().execute_with(|| {
+		let stream_id = create(1, 2, NATIVE, 10, 1_000, None);
+		events();
+
+		run_to(3);
+		assert_ok!(PaymentStreams::cancel(RuntimeOrigin::signed(2), stream_id));
+		assert_eq!(balance(2, NATIVE), 10_020);
+		assert_eq!(balance(1, NATIVE), 9_980);
+		assert_eq!(
+			events(),
+			[
+				Event::Withdrawn { stream_id, amount: 20 },
+				Event::StreamCancelled { stream_id, paid: 20, refunded: 980 },
+			]
+		);
+	});
+}
+
+#[test]
+fn streams_of_one_payer_are_independent() {
+	new_test_ext().execute_with(|| {
+		let first = create(1, 2, ASSET, 10, 1_000, None);
+		let second = create(1, 3, ASSET, 5, 1_000, None);
+		assert_eq!(held(1, ASSET), 2_000);
+
+		run_to(11);
+		assert_ok!(PaymentStreams::cancel(RuntimeOrigin::signed(1), first));
+		assert_ok!(PaymentStreams::withdraw(RuntimeOrigin::signed(3), second));
+
+		assert_eq!(balance(2, ASSET), 10_100);
+		assert_eq!(balance(3, ASSET), 10_050);
+		assert_eq!(held(1, ASSET), 950);
+		assert_eq!(Streams::<Test>::get(second).unwrap().remaining, 950);
+	});
+}
+
+#[test]
+fn pay_through_stream_works() {
+	use frame_support::traits::tokens::{Pay, PaymentStatus};
+
+	new_test_ext().execute_with(|| {
+		let stream_id = StreamPaymaster::pay(&4, NATIVE, 95).unwrap();
+
+		// rounded up so the payment completes within the duration
+		let stream = Streams::<Test>::get(stream_id).unwrap();
+		assert_eq!((stream.payer, stream.payee, stream.rate, stream.remaining), (10, 4, 10, 95));
+		assert_eq!(held(10, NATIVE), 95);
+		assert_eq!(StreamPaymaster::check_payment(stream_id), PaymentStatus::InProgress);
+
+		run_to(11);
+		assert_ok!(PaymentStreams::withdraw(RuntimeOrigin::signed(4), stream_id));
+		assert_eq!(balance(4, NATIVE), 95);
+		assert_eq!(balance(10, NATIVE), 9_905);
+		assert_eq!(storage_deposit(10), 0);
+		assert_eq!(StreamPaymaster::check_payment(stream_id), PaymentStatus::Success);
+		// the outcome is reported once and then pruned
+		assert_eq!(StreamPaymaster::check_payment(stream_id), PaymentStatus::Unknown);
+		assert!(!Payments::<Test>::contains_key(stream_id));
+
+		// streams not created by the paymaster are unknown to it
+		let stream_id = create(1, 2, NATIVE, 10, 1_000, None);
+		assert_eq!(StreamPaymaster::check_payment(stream_id), PaymentStatus::Unknown);
+	});
+}
+
+#[test]
+fn pay_through_stream_reports_cancelled_payments() {
+	use frame_support::traits::tokens::{Pay, PaymentStatus};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 1, 10, 1_000));
+
+		// the beneficiary forfeits the rest of the payment
+		let stream_id = StreamPaymaster::pay(&2, ASSET, 100).unwrap();
+		run_to(5);
+		assert_ok!(PaymentStreams::cancel(RuntimeOrigin::signed(2), stream_id));
+		assert_eq!(balance(2, ASSET), 10_040);
+		assert_eq!(StreamPaymaster::check_payment(stream_id), PaymentStatus::Success);
+
+		// paying the beneficiary fails
+		let stream_id = StreamPaymaster::pay(&3, ASSET, 100).unwrap();
+		run_to(7);
+		assert_ok!(Assets::block(RuntimeOrigin::signed(1), 1, 3));
+		assert_ok!(PaymentStreams::cancel(RuntimeOrigin::signed(3), stream_id));
+		assert_eq!(balance(3, ASSET), 10_000);
+		assert_eq!(StreamPaymaster::check_payment(stream_id), PaymentStatus::Failure);
+		assert_eq!(StreamPaymaster::check_payment(stream_id), PaymentStatus::Unknown);
+	});
+}
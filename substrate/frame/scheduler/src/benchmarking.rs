@@ -62,6 +62,21 @@ fn fill_schedule<T: Config>(when: BlockNumberFor<T>, n: u32) -> Result<(), &'sta
 	Ok(())
 }
 
+/// Add `n` recurring tasks, due one after the other after the current time.
+fn fill_recurring<T: Config>(n: u32) -> Result<(), &'static str> {
+	let origin: <T as Config>::PalletsOrigin = frame_system::RawOrigin::Root.into();
+	let now = T::TimeProvider::now();
+	for i in 0..n {
+		let call = make_call::<T>(None);
+		let start = now.saturating_add((i + 1).into());
+		let name = u32_to_name(i);
+		Pallet::<T>::do_schedule_recurring(name, start, 100u32.into(), None, origin.clone(), call)
+			.map_err(|_| "failed to schedule recurring task")?;
+	}
+	ensure!(RecurringQueue::<T>::get().len() == n as usize, "didn't fill recurring queue");
+	Ok(())
+}
+
 fn u32_to_name(i: u32) -> TaskName {
 	i.using_encoded(blake2_256)
 }
@@ -506,6 +521,82 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn schedule_recurring(
+		s: Linear<0, { T::MaxRecurringTasks::get() - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		let id = u32_to_name(s);
+		// Earliest of all, so that every other task is moved in the queue.
+		let start = T::TimeProvider::now();
+		// Essentially a no-op call.
+		let call = Box::new(SystemCall::set_storage { items: vec![] }.into());
+
+		fill_recurring::<T>(s)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, id, start, 100u32.into(), None, call);
+
+		ensure!(RecurringQueue::<T>::get()[0] == (start, id), "didn't add to recurring queue");
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_recurring(
+		s: Linear<1, { T::MaxRecurringTasks::get() }>,
+	) -> Result<(), BenchmarkError> {
+		fill_recurring::<T>(s)?;
+		let id = u32_to_name(s - 1);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, id);
+
+		ensure!(!RecurringTasks::<T>::contains_key(id), "didn't remove recurring task");
+		assert_last_event::<T>(Event::RecurringCanceled { id }.into());
+
+		Ok(())
+	}
+
+	// `service_recurring` when no task is due.
+	#[benchmark]
+	fn service_recurring_base(
+		s: Linear<0, { T::MaxRecurringTasks::get() }>,
+	) -> Result<(), BenchmarkError> {
+		fill_recurring::<T>(s)?;
+		let now = T::TimeProvider::now();
+
+		#[block]
+		{
+			Pallet::<T>::service_recurring(&mut WeightMeter::new(), now);
+		}
+
+		ensure!(RecurringQueue::<T>::get().len() == s as usize, "serviced a recurring task");
+
+		Ok(())
+	}
+
+	// `service_recurring` for a single task which is not dispatched since it can never fit in the
+	// given weight.
+	#[benchmark]
+	fn service_recurring_task() -> Result<(), BenchmarkError> {
+		fill_recurring::<T>(1)?;
+		let (when, id) = RecurringQueue::<T>::get()[0];
+		let mut counter = WeightMeter::with_limit(
+			T::WeightInfo::service_recurring_base(T::MaxRecurringTasks::get())
+				.saturating_add(T::WeightInfo::service_recurring_task()),
+		);
+
+		#[block]
+		{
+			Pallet::<T>::service_recurring(&mut counter, when);
+		}
+
+		let next = when.saturating_add(100u32.into());
+		ensure!(RecurringQueue::<T>::get()[0] == (next, id), "didn't service recurring task");
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite! {
 		Pallet,
 		mock::new_test_ext(),
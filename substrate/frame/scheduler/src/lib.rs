@@ -33,6 +33,9 @@
 //! number or at a specified period. These scheduled runtime calls may be named or anonymous and may
 //! be canceled.
 //!
+//! Named runtime calls may also recur at a fixed interval of time, as measured by the configured
+//! `TimeProvider`, either a bounded number of times or until they are canceled.
+//!
 //! __NOTE:__ Instead of using the filter contained in the origin to call `fn schedule`, scheduled
 //! runtime calls will be dispatched with the default filter for the origin: namely
 //! `frame_system::Config::BaseCallFilter` for all origin types (except root which will get no
@@ -88,15 +91,16 @@ pub mod weights;
 extern crate alloc;
 
 use alloc::{boxed::Box, vec::Vec};
-use codec::{Decode, Encode, MaxEncodedLen};
+use codec::{Codec, Decode, Encode, MaxEncodedLen};
 use core::{borrow::Borrow, cmp::Ordering, marker::PhantomData};
 use frame_support::{
+	defensive,
 	dispatch::{DispatchResult, GetDispatchInfo, Parameter, RawOrigin},
 	ensure,
 	traits::{
 		schedule::{self, DispatchTime, MaybeHashed},
 		Bounded, CallerTrait, EnsureOrigin, Get, IsType, OriginTrait, PalletInfoAccess,
-		PrivilegeCmp, QueryPreimage, StorageVersion, StorePreimage,
+		PrivilegeCmp, QueryPreimage, StorageVersion, StorePreimage, Time,
 	},
 	weights::{Weight, WeightMeter},
 };
@@ -125,6 +129,8 @@ pub type BoundedCallOf<T> =
 pub type BlockNumberFor<T> =
 	<<T as Config>::BlockNumberProvider as BlockNumberProvider>::BlockNumber;
 
+pub type MomentOf<T> = <<T as Config>::TimeProvider as Time>::Moment;
+
 /// The configuration of the retry mechanism for a given task along with its current state.
 #[derive(Clone, Copy, RuntimeDebug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
 pub struct RetryConfig<Period> {
@@ -182,6 +188,55 @@ where
 	}
 }
 
+/// A task which is dispatched repeatedly, every `period` units of time.
+#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct RecurringTask<Call, Moment, PalletsOrigin, AccountId> {
+	/// The call to be dispatched.
+	pub call: Call,
+	/// The origin with which to dispatch the call.
+	pub origin: PalletsOrigin,
+	/// The time at or after which the call is next dispatched.
+	pub next: Moment,
+	/// The time between two dispatches of the call.
+	pub period: Moment,
+	/// The number of dispatches left, or `None` if the task repeats until canceled. Skipped
+	/// executions are not counted.
+	pub remaining: Option<u32>,
+	#[doc(hidden)]
+	pub _phantom: PhantomData<AccountId>,
+}
+
+pub type RecurringTaskOf<T> = RecurringTask<
+	BoundedCallOf<T>,
+	MomentOf<T>,
+	<T as Config>::PalletsOrigin,
+	<T as frame_system::Config>::AccountId,
+>;
+
+/// The next execution of a named task.
+#[derive(Clone, Copy, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum NextExecution<BlockNumber, Moment> {
+	/// The task is dispatched at the given block.
+	Block(BlockNumber),
+	/// The recurring task is dispatched in the first block after the given time.
+	Timestamp(Moment),
+}
+
+sp_api::decl_runtime_apis! {
+	/// This runtime api allows people to query when scheduled tasks are executed.
+	pub trait SchedulerApi<BlockNumber, Moment> where
+		BlockNumber: Codec,
+		Moment: Codec,
+	{
+		/// Returns the next execution of the named or recurring task `id`, if it exists.
+		fn next_execution(id: TaskName) -> Option<NextExecution<BlockNumber, Moment>>;
+
+		/// Returns all recurring tasks along with the time of their next execution, earliest
+		/// first.
+		fn recurring_tasks() -> Vec<(TaskName, Moment)>;
+	}
+}
+
 use crate::{Scheduled as ScheduledV3, Scheduled as ScheduledV2};
 
 pub type ScheduledV2Of<T> = ScheduledV2<
@@ -321,6 +376,17 @@ pub mod pallet {
 		/// swap block number providers on the fly, then please at least ensure that you do not run
 		/// any pallet migration in the same runtime upgrade.
 		type BlockNumberProvider: BlockNumberProvider;
+
+		/// Query the current time, used to dispatch recurring tasks.
+		///
+		/// Recurring tasks are serviced in `on_initialize`, before the time of the current block
+		/// is set, so a task is dispatched in the first block following the one whose time
+		/// reached its next execution.
+		type TimeProvider: Time;
+
+		/// The maximum number of recurring tasks which can be scheduled at once.
+		#[pallet::constant]
+		type MaxRecurringTasks: Get<u32>;
 	}
 
 	/// Block number at which the agenda began incomplete execution.
@@ -355,6 +421,16 @@ pub mod pallet {
 	pub type Lookup<T: Config> =
 		StorageMap<_, Twox64Concat, TaskName, TaskAddress<BlockNumberFor<T>>>;
 
+	/// Recurring tasks, indexed by their name.
+	#[pallet::storage]
+	pub type RecurringTasks<T: Config> = StorageMap<_, Twox64Concat, TaskName, RecurringTaskOf<T>>;
+
+	/// The names of all recurring tasks along with the time of their next execution, earliest
+	/// first.
+	#[pallet::storage]
+	pub type RecurringQueue<T: Config> =
+		StorageValue<_, BoundedVec<(MomentOf<T>, TaskName), T::MaxRecurringTasks>, ValueQuery>;
+
 	/// Events type.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -389,6 +465,16 @@ pub mod pallet {
 		PermanentlyOverweight { task: TaskAddress<BlockNumberFor<T>>, id: Option<TaskName> },
 		/// Agenda is incomplete from `when`.
 		AgendaIncomplete { when: BlockNumberFor<T> },
+		/// Scheduled a recurring task.
+		RecurringScheduled { id: TaskName, next: MomentOf<T> },
+		/// Canceled a recurring task.
+		RecurringCanceled { id: TaskName },
+		/// Dispatched a recurring task which was due at `when`.
+		RecurringDispatched { id: TaskName, when: MomentOf<T>, result: DispatchResult },
+		/// The execution of a recurring task due at `when` was skipped since its call is
+		/// unavailable or can never fit in a block. The task remains scheduled, and the skipped
+		/// execution does not count towards its repetitions.
+		RecurringSkipped { id: TaskName, when: MomentOf<T> },
 	}
 
 	#[pallet::error]
@@ -403,6 +489,12 @@ pub mod pallet {
 		RescheduleNoChange,
 		/// Attempt to use a non-named function on a named task.
 		Named,
+		/// Given start time is in the past.
+		TargetTimeInPast,
+		/// A recurring task must have a non-zero period and be executed at least once.
+		InvalidRecurrence,
+		/// There are already `MaxRecurringTasks` recurring tasks.
+		TooManyRecurringTasks,
 	}
 
 	#[pallet::hooks]
@@ -412,6 +504,7 @@ pub mod pallet {
 			let now = T::BlockNumberProvider::current_block_number();
 			let mut weight_counter = WeightMeter::with_limit(T::MaximumWeight::get());
 			Self::service_agendas(&mut weight_counter, now, u32::MAX);
+			Self::service_recurring(&mut weight_counter, T::TimeProvider::now());
 			weight_counter.consumed()
 		}
 	}
@@ -634,6 +727,43 @@ pub mod pallet {
 			Self::deposit_event(Event::RetryCancelled { task, id: Some(id) });
 			Ok(())
 		}
+
+		/// Schedule a named task to be dispatched every `period` units of time, starting at
+		/// `start`.
+		///
+		/// The task is dispatched `repetitions` times, or until it is canceled if `None`. An
+		/// execution which cannot be dispatched in time is skipped rather than queued up, and an
+		/// execution which can never be dispatched does not remove the task.
+		#[pallet::call_index(10)]
+		#[pallet::weight(<T as Config>::WeightInfo::schedule_recurring(T::MaxRecurringTasks::get()))]
+		pub fn schedule_recurring(
+			origin: OriginFor<T>,
+			id: TaskName,
+			start: MomentOf<T>,
+			period: MomentOf<T>,
+			repetitions: Option<u32>,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			Self::do_schedule_recurring(
+				id,
+				start,
+				period,
+				repetitions,
+				origin.caller().clone(),
+				T::Preimages::bound(*call)?,
+			)
+		}
+
+		/// Cancel a recurring task.
+		#[pallet::call_index(11)]
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_recurring(T::MaxRecurringTasks::get()))]
+		pub fn cancel_recurring(origin: OriginFor<T>, id: TaskName) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			Self::do_cancel_recurring(Some(origin.caller().clone()), id)
+		}
 	}
 }
 
@@ -1081,7 +1211,7 @@ impl<T: Config> Pallet<T> {
 		call: BoundedCallOf<T>,
 	) -> Result<TaskAddress<BlockNumberFor<T>>, DispatchError> {
 		// ensure id it is unique
-		if Lookup::<T>::contains_key(&id) {
+		if Lookup::<T>::contains_key(&id) || RecurringTasks::<T>::contains_key(&id) {
 			return Err(Error::<T>::FailedToSchedule.into())
 		}
 
@@ -1172,6 +1302,73 @@ impl<T: Config> Pallet<T> {
 		Retries::<T>::remove((when, index));
 		Ok(())
 	}
+
+	fn do_schedule_recurring(
+		id: TaskName,
+		start: MomentOf<T>,
+		period: MomentOf<T>,
+		repetitions: Option<u32>,
+		origin: T::PalletsOrigin,
+		call: BoundedCallOf<T>,
+	) -> DispatchResult {
+		ensure!(!period.is_zero() && repetitions != Some(0), Error::<T>::InvalidRecurrence);
+		// ensure id it is unique
+		if Lookup::<T>::contains_key(&id) || RecurringTasks::<T>::contains_key(&id) {
+			return Err(Error::<T>::FailedToSchedule.into())
+		}
+		ensure!(start >= T::TimeProvider::now(), Error::<T>::TargetTimeInPast);
+
+		RecurringQueue::<T>::try_mutate(|queue| {
+			let index = queue.partition_point(|(next, _)| *next <= start);
+			queue
+				.try_insert(index, (start, id))
+				.map_err(|_| Error::<T>::TooManyRecurringTasks)
+		})?;
+
+		if let Some(hash) = call.lookup_hash() {
+			// Request the call to be made available.
+			T::Preimages::request(&hash);
+		}
+
+		RecurringTasks::<T>::insert(
+			id,
+			RecurringTask {
+				call,
+				origin,
+				next: start,
+				period,
+				remaining: repetitions,
+				_phantom: Default::default(),
+			},
+		);
+		Self::deposit_event(Event::RecurringScheduled { id, next: start });
+		Ok(())
+	}
+
+	fn do_cancel_recurring(origin: Option<T::PalletsOrigin>, id: TaskName) -> DispatchResult {
+		let task = RecurringTasks::<T>::get(&id).ok_or(Error::<T>::NotFound)?;
+		if let Some(ref o) = origin {
+			Self::ensure_privilege(o, &task.origin)?;
+		}
+		RecurringTasks::<T>::remove(&id);
+		RecurringQueue::<T>::mutate(|queue| queue.retain(|(_, name)| *name != id));
+		T::Preimages::drop(&task.call);
+		Self::deposit_event(Event::RecurringCanceled { id });
+		Ok(())
+	}
+
+	/// Returns the next execution of the named or recurring task `id`, if it exists.
+	pub fn next_execution(id: TaskName) -> Option<NextExecution<BlockNumberFor<T>, MomentOf<T>>> {
+		if let Some((when, _)) = Lookup::<T>::get(&id) {
+			return Some(NextExecution::Block(when))
+		}
+		RecurringTasks::<T>::get(&id).map(|task| NextExecution::Timestamp(task.next))
+	}
+
+	/// Returns all recurring tasks along with the time of their next execution, earliest first.
+	pub fn recurring_tasks() -> Vec<(TaskName, MomentOf<T>)> {
+		RecurringQueue::<T>::get().into_iter().map(|(next, id)| (id, next)).collect()
+	}
 }
 
 enum ServiceTaskError {
@@ -1391,11 +1588,7 @@ impl<T: Config> Pallet<T> {
 		origin: T::PalletsOrigin,
 		call: <T as Config>::RuntimeCall,
 	) -> Result<DispatchResult, ()> {
-		let base_weight = match origin.as_system_ref() {
-			Some(&RawOrigin::Signed(_)) => T::WeightInfo::execute_dispatch_signed(),
-			_ => T::WeightInfo::execute_dispatch_unsigned(),
-		};
-		let call_weight = call.get_dispatch_info().call_weight;
+		let (base_weight, call_weight) = Self::dispatch_weight(&origin, &call);
 		// We only allow a scheduled call if it cannot push the weight past the limit.
 		let max_weight = base_weight.saturating_add(call_weight);
 
@@ -1415,6 +1608,19 @@ impl<T: Config> Pallet<T> {
 		Ok(result)
 	}
 
+	/// The weight of dispatching `call` from `origin`, as the base weight of the dispatch and the
+	/// weight of the call itself.
+	fn dispatch_weight(
+		origin: &T::PalletsOrigin,
+		call: &<T as Config>::RuntimeCall,
+	) -> (Weight, Weight) {
+		let base_weight = match origin.as_system_ref() {
+			Some(&RawOrigin::Signed(_)) => T::WeightInfo::execute_dispatch_signed(),
+			_ => T::WeightInfo::execute_dispatch_unsigned(),
+		};
+		(base_weight, call.get_dispatch_info().call_weight)
+	}
+
 	/// Check if a task has a retry configuration in place and, if so, try to reschedule it.
 	///
 	/// Possible causes for failure to schedule a retry for a task:
@@ -1465,6 +1671,98 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Service the recurring tasks which are due at `now`, earliest first.
+	///
+	/// A task which does not fit in the remaining weight stops the servicing and is retried in the
+	/// next block, while a task which can never be dispatched has its execution skipped. Either
+	/// way the task keeps its schedule.
+	fn service_recurring(weight: &mut WeightMeter, now: MomentOf<T>) {
+		let recurring_base_weight =
+			T::WeightInfo::service_recurring_base(T::MaxRecurringTasks::get());
+		if weight.try_consume(recurring_base_weight).is_err() {
+			return
+		}
+
+		let mut queue = RecurringQueue::<T>::get();
+		let task_weight = T::WeightInfo::service_recurring_task();
+		let mut serviced = false;
+		while let Some(&(when, id)) = queue.first() {
+			if when > now || weight.try_consume(task_weight).is_err() {
+				break
+			}
+			let Some(mut task) = RecurringTasks::<T>::get(&id) else {
+				// The queue and the tasks are always updated together.
+				defensive!("Recurring task in queue has no entry");
+				queue.remove(0);
+				serviced = true;
+				continue
+			};
+
+			let executed = match T::Preimages::peek(&task.call) {
+				Ok((call, lookup_len)) => {
+					if let Some(l) = lookup_len {
+						let fetch_weight = T::WeightInfo::service_task_fetched(l)
+							.saturating_sub(T::WeightInfo::service_task_base());
+						if weight.try_consume(fetch_weight).is_err() {
+							break
+						}
+					}
+					let (base_weight, call_weight) = Self::dispatch_weight(&task.origin, &call);
+					// The weight left for the call in a block with no other task to service.
+					let max_weight = weight.limit().saturating_sub(
+						T::WeightInfo::service_agendas_base()
+							.saturating_add(recurring_base_weight)
+							.saturating_add(task_weight),
+					);
+					if !max_weight.all_gte(base_weight.saturating_add(call_weight)) {
+						false
+					} else {
+						match Self::execute_dispatch(weight, task.origin.clone(), call) {
+							Ok(result) => {
+								Self::deposit_event(Event::RecurringDispatched {
+									id,
+									when,
+									result,
+								});
+								true
+							},
+							// Retry in the next block.
+							Err(()) => break,
+						}
+					}
+				},
+				Err(_) => false,
+			};
+			if !executed {
+				Self::deposit_event(Event::RecurringSkipped { id, when });
+			}
+			queue.remove(0);
+			serviced = true;
+
+			// Only an execution which took place counts towards the repetitions.
+			if executed {
+				task.remaining = task.remaining.map(|r| r.saturating_sub(1));
+			}
+			if task.remaining == Some(0) {
+				RecurringTasks::<T>::remove(&id);
+				T::Preimages::drop(&task.call);
+				continue
+			}
+			// Skip any execution which was missed, so that the next one is in the future.
+			let missed = now.saturating_sub(when) / task.period;
+			task.next =
+				when.saturating_add(missed.saturating_add(One::one()).saturating_mul(task.period));
+			let index = queue.partition_point(|(next, _)| *next <= task.next);
+			// An entry was just removed, so there is room for it.
+			let _ = queue.try_insert(index, (task.next, id));
+			RecurringTasks::<T>::insert(&id, task);
+		}
+
+		if serviced {
+			RecurringQueue::<T>::put(queue);
+		}
+	}
+
 	/// Ensure that `left` has at least the same level of privilege or higher than `right`.
 	///
 	/// Returns an error if `left` has a lower level of privilege or the two cannot be compared.
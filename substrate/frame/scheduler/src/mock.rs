@@ -210,10 +210,31 @@ impl WeightInfo for TestWeightInfo {
 	fn cancel_retry_named() -> Weight {
 		Weight::from_parts(50, 0)
 	}
+	fn schedule_recurring(_s: u32) -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn cancel_recurring(_s: u32) -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn service_recurring_base(_s: u32) -> Weight {
+		Weight::zero()
+	}
+	fn service_recurring_task() -> Weight {
+		Weight::from_parts(0b0000_0100, 0)
+	}
 }
 parameter_types! {
 	pub storage MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
 		BlockWeights::get().max_block;
+	pub storage Now: u64 = 0;
+}
+
+pub struct MockTime;
+impl Time for MockTime {
+	type Moment = u64;
+	fn now() -> u64 {
+		Now::get()
+	}
 }
 
 impl Config for Test {
@@ -228,6 +249,8 @@ impl Config for Test {
 	type WeightInfo = TestWeightInfo;
 	type Preimages = Preimage;
 	type BlockNumberProvider = frame_system::Pallet<Self>;
+	type TimeProvider = MockTime;
+	type MaxRecurringTasks = ConstU32<3>;
 }
 
 pub type LoggerCall = logger::Call<Test>;
@@ -3076,3 +3076,273 @@ fn postponed_task_is_still_available() {
 		assert!(Agenda::<Test>::get(4).is_empty());
 	});
 }
+
+#[test]
+fn recurring_scheduling_works() {
+	new_test_ext().execute_with(|| {
+		let call =
+			RuntimeCall::Logger(LoggerCall::log { i: 42, weight: Weight::from_parts(10, 0) });
+		// from time 100, every 50 units of time, 3 times.
+		assert_ok!(Scheduler::schedule_recurring(
+			RuntimeOrigin::root(),
+			[1u8; 32],
+			100,
+			50,
+			Some(3),
+			Box::new(call),
+		));
+		System::assert_last_event(
+			crate::Event::RecurringScheduled { id: [1u8; 32], next: 100 }.into(),
+		);
+
+		Now::set(&99);
+		System::run_to_block::<AllPalletsWithSystem>(2);
+		assert!(logger::log().is_empty());
+
+		Now::set(&100);
+		System::run_to_block::<AllPalletsWithSystem>(3);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		System::assert_last_event(
+			crate::Event::RecurringDispatched { id: [1u8; 32], when: 100, result: Ok(()) }.into(),
+		);
+		assert_eq!(Scheduler::recurring_tasks(), vec![([1u8; 32], 150)]);
+
+		// Executions missed in between are skipped.
+		Now::set(&260);
+		System::run_to_block::<AllPalletsWithSystem>(5);
+		assert_eq!(logger::log(), vec![(root(), 42u32), (root(), 42u32)]);
+		System::assert_last_event(
+			crate::Event::RecurringDispatched { id: [1u8; 32], when: 150, result: Ok(()) }.into(),
+		);
+		assert_eq!(Scheduler::recurring_tasks(), vec![([1u8; 32], 300)]);
+
+		Now::set(&300);
+		System::run_to_block::<AllPalletsWithSystem>(6);
+		assert_eq!(logger::log(), vec![(root(), 42u32), (root(), 42u32), (root(), 42u32)]);
+		assert!(!RecurringTasks::<Test>::contains_key([1u8; 32]));
+		assert!(RecurringQueue::<Test>::get().is_empty());
+
+		Now::set(&1000);
+		System::run_to_block::<AllPalletsWithSystem>(10);
+		assert_eq!(logger::log().len(), 3);
+	});
+}
+
+#[test]
+fn unbounded_recurring_task_runs_until_canceled() {
+	new_test_ext().execute_with(|| {
+		let call = RuntimeCall::Logger(LoggerCall::log { i: 7, weight: Weight::from_parts(10, 0) });
+		let hash = <Test as frame_system::Config>::Hashing::hash_of(&call);
+		let len = call.using_encoded(|x| x.len()) as u32;
+		// Important to use here `Bounded::Lookup` to ensure that we request the hash.
+		let bound = Bounded::Lookup { hash, len };
+		assert_ok!(Preimage::note_preimage(RuntimeOrigin::signed(0), call.encode()));
+		assert_ok!(Scheduler::do_schedule_recurring([2u8; 32], 10, 10, None, root(), bound));
+		assert!(Preimage::is_requested(&hash));
+
+		for (block, time) in (1..=20).zip((10..).step_by(10)) {
+			Now::set(&time);
+			System::run_to_block::<AllPalletsWithSystem>(block);
+		}
+		assert_eq!(logger::log().len(), 20);
+
+		// The canceling origin must be at least as privileged as the scheduling one.
+		assert_noop!(Scheduler::cancel_recurring(RuntimeOrigin::signed(1), [2u8; 32]), BadOrigin);
+		assert_ok!(Scheduler::cancel_recurring(RuntimeOrigin::root(), [2u8; 32]));
+		System::assert_last_event(crate::Event::RecurringCanceled { id: [2u8; 32] }.into());
+		assert!(Scheduler::recurring_tasks().is_empty());
+		assert!(!Preimage::is_requested(&hash));
+		assert_noop!(
+			Scheduler::cancel_recurring(RuntimeOrigin::root(), [2u8; 32]),
+			Error::<Test>::NotFound
+		);
+
+		Now::set(&1000);
+		System::run_to_block::<AllPalletsWithSystem>(21);
+		assert_eq!(logger::log().len(), 20);
+	});
+}
+
+#[test]
+fn schedule_recurring_checks_parameters() {
+	new_test_ext().execute_with(|| {
+		let call = || {
+			Box::new(RuntimeCall::Logger(LoggerCall::log {
+				i: 42,
+				weight: Weight::from_parts(10, 0),
+			}))
+		};
+		let origin = RuntimeOrigin::root();
+		Now::set(&10);
+
+		assert_noop!(
+			Scheduler::schedule_recurring(origin.clone(), [1u8; 32], 10, 0, None, call()),
+			Error::<Test>::InvalidRecurrence
+		);
+		assert_noop!(
+			Scheduler::schedule_recurring(origin.clone(), [1u8; 32], 10, 5, Some(0), call()),
+			Error::<Test>::InvalidRecurrence
+		);
+		assert_noop!(
+			Scheduler::schedule_recurring(origin.clone(), [1u8; 32], 9, 5, None, call()),
+			Error::<Test>::TargetTimeInPast
+		);
+		assert_noop!(
+			Scheduler::schedule_recurring(RuntimeOrigin::signed(2), [1u8; 32], 10, 5, None, call()),
+			BadOrigin
+		);
+
+		// Names are shared with named tasks.
+		assert_ok!(Scheduler::schedule_named(origin.clone(), [1u8; 32], 4, None, 127, call()));
+		assert_noop!(
+			Scheduler::schedule_recurring(origin.clone(), [1u8; 32], 10, 5, None, call()),
+			Error::<Test>::FailedToSchedule
+		);
+		assert_ok!(Scheduler::schedule_recurring(origin.clone(), [2u8; 32], 30, 5, None, call()));
+		assert_noop!(
+			Scheduler::schedule_named(origin.clone(), [2u8; 32], 4, None, 127, call()),
+			Error::<Test>::FailedToSchedule
+		);
+		assert_noop!(
+			Scheduler::schedule_recurring(origin.clone(), [2u8; 32], 10, 5, None, call()),
+			Error::<Test>::FailedToSchedule
+		);
+
+		assert_ok!(Scheduler::schedule_recurring(origin.clone(), [3u8; 32], 10, 5, None, call()));
+		assert_ok!(Scheduler::schedule_recurring(origin.clone(), [4u8; 32], 20, 5, None, call()));
+		assert_noop!(
+			Scheduler::schedule_recurring(origin, [5u8; 32], 10, 5, None, call()),
+			Error::<Test>::TooManyRecurringTasks
+		);
+		// Ordered by next execution.
+		assert_eq!(
+			Scheduler::recurring_tasks(),
+			vec![([3u8; 32], 10), ([4u8; 32], 20), ([2u8; 32], 30)]
+		);
+	});
+}
+
+#[test]
+fn skipped_recurring_executions_are_not_counted() {
+	new_test_ext().execute_with(|| {
+		let max_weight: Weight = <Test as Config>::MaximumWeight::get();
+		// Can never fit in a block.
+		let call = RuntimeCall::Logger(LoggerCall::log { i: 1, weight: max_weight });
+		assert_ok!(Scheduler::do_schedule_recurring(
+			[1u8; 32],
+			10,
+			10,
+			Some(1),
+			root(),
+			Preimage::bound(call).unwrap(),
+		));
+
+		for (block, now) in [(1, 10), (2, 20)] {
+			Now::set(&now);
+			System::run_to_block::<AllPalletsWithSystem>(block);
+			System::assert_last_event(
+				crate::Event::RecurringSkipped { id: [1u8; 32], when: now }.into(),
+			);
+			let task = RecurringTasks::<Test>::get([1u8; 32]).unwrap();
+			assert_eq!(task.remaining, Some(1));
+			assert_eq!(task.next, now + 10);
+		}
+		assert!(logger::log().is_empty());
+	});
+}
+
+#[test]
+fn overweight_recurring_task_keeps_schedule() {
+	new_test_ext().execute_with(|| {
+		let max_weight: Weight = <Test as Config>::MaximumWeight::get();
+		// Can never fit in a block.
+		let call = RuntimeCall::Logger(LoggerCall::log { i: 1, weight: max_weight });
+		assert_ok!(Scheduler::do_schedule_recurring(
+			[1u8; 32],
+			10,
+			10,
+			None,
+			root(),
+			Preimage::bound(call).unwrap(),
+		));
+
+		Now::set(&10);
+		System::run_to_block::<AllPalletsWithSystem>(1);
+		assert!(logger::log().is_empty());
+		System::assert_last_event(
+			crate::Event::RecurringSkipped { id: [1u8; 32], when: 10 }.into(),
+		);
+		assert_eq!(Scheduler::recurring_tasks(), vec![([1u8; 32], 20)]);
+		assert_ok!(Scheduler::cancel_recurring(RuntimeOrigin::root(), [1u8; 32]));
+
+		// Fits in a block, but not along with the agenda of block 2.
+		let weight = max_weight / 2;
+		let call = RuntimeCall::Logger(LoggerCall::log { i: 2, weight });
+		assert_ok!(Scheduler::do_schedule(
+			DispatchTime::At(2),
+			None,
+			127,
+			root(),
+			Preimage::bound(call).unwrap(),
+		));
+		let call = RuntimeCall::Logger(LoggerCall::log { i: 3, weight });
+		assert_ok!(Scheduler::do_schedule_recurring(
+			[2u8; 32],
+			20,
+			10,
+			Some(1),
+			root(),
+			Preimage::bound(call).unwrap(),
+		));
+
+		Now::set(&20);
+		System::run_to_block::<AllPalletsWithSystem>(2);
+		assert_eq!(logger::log(), vec![(root(), 2u32)]);
+		assert_eq!(Scheduler::recurring_tasks(), vec![([2u8; 32], 20)]);
+
+		// Executed in the next block, for the time it was due.
+		System::run_to_block::<AllPalletsWithSystem>(3);
+		assert_eq!(logger::log(), vec![(root(), 2u32), (root(), 3u32)]);
+		System::assert_last_event(
+			crate::Event::RecurringDispatched { id: [2u8; 32], when: 20, result: Ok(()) }.into(),
+		);
+		assert!(Scheduler::recurring_tasks().is_empty());
+	});
+}
+
+#[test]
+fn next_execution_works() {
+	new_test_ext().execute_with(|| {
+		let call = || {
+			Box::new(RuntimeCall::Logger(LoggerCall::log {
+				i: 42,
+				weight: Weight::from_parts(10, 0),
+			}))
+		};
+		assert_ok!(Scheduler::schedule_named(
+			RuntimeOrigin::root(),
+			[1u8; 32],
+			4,
+			None,
+			127,
+			call()
+		));
+		assert_ok!(Scheduler::schedule_recurring(
+			RuntimeOrigin::root(),
+			[2u8; 32],
+			100,
+			50,
+			None,
+			call()
+		));
+
+		assert_eq!(Scheduler::next_execution([1u8; 32]), Some(NextExecution::Block(4)));
+		assert_eq!(Scheduler::next_execution([2u8; 32]), Some(NextExecution::Timestamp(100)));
+		assert_eq!(Scheduler::next_execution([3u8; 32]), None);
+
+		Now::set(&120);
+		System::run_to_block::<AllPalletsWithSystem>(4);
+		assert_eq!(Scheduler::next_execution([1u8; 32]), None);
+		assert_eq!(Scheduler::next_execution([2u8; 32]), Some(NextExecution::Timestamp(150)));
+	});
+}
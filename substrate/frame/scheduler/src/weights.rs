@@ -89,6 +89,10 @@ pub trait WeightInfo {
 	fn set_retry_named() -> Weight;
 	fn cancel_retry() -> Weight;
 	fn cancel_retry_named() -> Weight;
+	fn schedule_recurring(s: u32, ) -> Weight;
+	fn cancel_recurring(s: u32, ) -> Weight;
+	fn service_recurring_base(s: u32, ) -> Weight;
+	fn service_recurring_task() -> Weight;
 }
 
 /// Weights for `pallet_scheduler` using the Substrate node and recommended hardware.
@@ -319,6 +323,69 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Scheduler::Lookup` (r:1 w:0)
+	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringTasks` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringTasks` (`max_values`: None, `max_size`: Some(199), added: 2674, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(4004), added: 4499, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 99]`.
+	fn schedule_recurring(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76 + s * (40 ±0)`
+		//  Estimated: `5489`
+		// Minimum execution time: 14_562_000 picoseconds.
+		Weight::from_parts(15_873_402, 5489)
+			// Standard Error: 1_102
+			.saturating_add(Weight::from_parts(41_315, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Scheduler::RecurringTasks` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringTasks` (`max_values`: None, `max_size`: Some(199), added: 2674, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(4004), added: 4499, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn cancel_recurring(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212 + s * (40 ±0)`
+		//  Estimated: `5489`
+		// Minimum execution time: 17_208_000 picoseconds.
+		Weight::from_parts(18_391_755, 5489)
+			// Standard Error: 1_344
+			.saturating_add(Weight::from_parts(52_807, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(4004), added: 4499, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 100]`.
+	fn service_recurring_base(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76 + s * (40 ±0)`
+		//  Estimated: `5489`
+		// Minimum execution time: 3_104_000 picoseconds.
+		Weight::from_parts(3_612_890, 5489)
+			// Standard Error: 402
+			.saturating_add(Weight::from_parts(12_571, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Scheduler::RecurringTasks` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringTasks` (`max_values`: None, `max_size`: Some(199), added: 2674, mode: `MaxEncodedLen`)
+	fn service_recurring_task() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212`
+		//  Estimated: `3664`
+		// Minimum execution time: 8_931_000 picoseconds.
+		Weight::from_parts(9_408_000, 3664)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -548,4 +615,67 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Scheduler::Lookup` (r:1 w:0)
+	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringTasks` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringTasks` (`max_values`: None, `max_size`: Some(199), added: 2674, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(4004), added: 4499, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 99]`.
+	fn schedule_recurring(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76 + s * (40 ±0)`
+		//  Estimated: `5489`
+		// Minimum execution time: 14_562_000 picoseconds.
+		Weight::from_parts(15_873_402, 5489)
+			// Standard Error: 1_102
+			.saturating_add(Weight::from_parts(41_315, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Scheduler::RecurringTasks` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringTasks` (`max_values`: None, `max_size`: Some(199), added: 2674, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(4004), added: 4499, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn cancel_recurring(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212 + s * (40 ±0)`
+		//  Estimated: `5489`
+		// Minimum execution time: 17_208_000 picoseconds.
+		Weight::from_parts(18_391_755, 5489)
+			// Standard Error: 1_344
+			.saturating_add(Weight::from_parts(52_807, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(4004), added: 4499, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 100]`.
+	fn service_recurring_base(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76 + s * (40 ±0)`
+		//  Estimated: `5489`
+		// Minimum execution time: 3_104_000 picoseconds.
+		Weight::from_parts(3_612_890, 5489)
+			// Standard Error: 402
+			.saturating_add(Weight::from_parts(12_571, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Scheduler::RecurringTasks` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringTasks` (`max_values`: None, `max_size`: Some(199), added: 2674, mode: `MaxEncodedLen`)
+	fn service_recurring_task() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212`
+		//  Estimated: `3664`
+		// Minimum execution time: 8_931_000 picoseconds.
+		Weight::from_parts(9_408_000, 3664)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
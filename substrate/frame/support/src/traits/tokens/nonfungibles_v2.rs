@@ -441,3 +441,31 @@ pub trait Trading<AccountId, ItemPrice>: Inspect<AccountId> {
 	/// Returns the item price of `item` or `None` if the item is not for sale.
 	fn item_price(collection: &Self::CollectionId, item: &Self::ItemId) -> Option<ItemPrice>;
 }
+
+/// Trait for the royalties owed on sales of non-fungible items.
+///
+/// Marketplaces which settle sales of items without going through [`Trading`] must call
+/// [`Royalties::pay_royalties`] to pay the royalties owed on them.
+pub trait Royalties<AccountId, Balance>: Inspect<AccountId> {
+	/// Returns the recipients of the royalties owed when `item` of `collection` is sold for
+	/// `sale_price`, along with the amount owed to each of them.
+	///
+	/// By default this is empty; no royalties are owed.
+	fn royalties(
+		_collection: &Self::CollectionId,
+		_item: &Self::ItemId,
+		_sale_price: &Balance,
+	) -> Vec<(AccountId, Balance)> {
+		Vec::new()
+	}
+
+	/// Pays the royalties owed when `item` of `collection` is sold for `sale_price` from `payer`.
+	///
+	/// Returns the remainder of `sale_price` which is owed to the seller.
+	fn pay_royalties(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		payer: &AccountId,
+		sale_price: &Balance,
+	) -> Result<Balance, DispatchError>;
+}
@@ -96,6 +96,24 @@ fn setup_pot_account<T: Config<I>, I: 'static>() {
 	let _ = T::Currency::make_free_balance_be(&pot_account, value);
 }
 
+// Budget the spends of `asset_kind` by `origin`, allowing `limit` to be approved per block.
+fn set_spend_budget_of<T: Config<I>, I: 'static>(
+	origin: &T::RuntimeOrigin,
+	asset_kind: &T::AssetKind,
+	limit: AssetBalanceOf<T, I>,
+) {
+	SpendBudgets::<T, I>::insert(
+		origin.caller(),
+		asset_kind,
+		SpendBudget {
+			limit,
+			period: 1u32.into(),
+			spent: Zero::zero(),
+			updated_at: T::BlockNumberProvider::current_block_number(),
+		},
+	);
+}
+
 fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::RuntimeEvent) {
 	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
@@ -188,6 +206,8 @@ mod benchmarks {
 		let (asset_kind, amount, beneficiary, beneficiary_lookup) =
 			create_spend_arguments::<T, _>(SEED);
 		T::BalanceConverter::ensure_successful(asset_kind.clone());
+		// the worst case is a budgeted spend.
+		set_spend_budget_of::<T, I>(&origin, &asset_kind, amount);
 
 		#[extrinsic_call]
 		_(
@@ -273,6 +293,7 @@ mod benchmarks {
 		let caller: T::AccountId = account("caller", 0, SEED);
 
 		let spend_exists = if let Ok(origin) = T::SpendOrigin::try_successful_origin() {
+			set_spend_budget_of::<T, I>(&origin, &asset_kind, amount);
 			Treasury::<T, _>::spend(
 				origin,
 				Box::new(asset_kind),
@@ -318,6 +339,8 @@ mod benchmarks {
 		let (asset_kind, amount, _, beneficiary_lookup) = create_spend_arguments::<T, _>(SEED);
 		T::BalanceConverter::ensure_successful(asset_kind.clone());
 		let spend_exists = if let Ok(origin) = T::SpendOrigin::try_successful_origin() {
+			// the worst case is a budgeted spend, which is credited back to the budget.
+			set_spend_budget_of::<T, I>(&origin, &asset_kind, amount);
 			Treasury::<T, _>::spend(
 				origin,
 				Box::new(asset_kind.clone()),
@@ -325,7 +348,7 @@ mod benchmarks {
 				Box::new(beneficiary_lookup),
 				None,
 			)?;
-			assert!(Spends::<T, I>::get(0).is_some());
+			assert!(BudgetedSpends::<T, I>::contains_key(0));
 
 			true
 		} else {
@@ -347,6 +370,52 @@ mod benchmarks {
 		}
 
 		assert!(Spends::<T, I>::get(0).is_none());
+		assert!(!BudgetedSpends::<T, I>::contains_key(0));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_spend_budget() -> Result<(), BenchmarkError> {
+		let origin =
+			T::BudgetOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let spender: PalletsOriginOf<T> = RawOrigin::Root.into();
+		let asset_kind = T::BenchmarkHelper::create_asset_kind(SEED);
+		let limit: AssetBalanceOf<T, I> = 100u32.into();
+		let period: BlockNumberFor<T, I> = 10u32.into();
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			Box::new(spender.clone()),
+			Box::new(asset_kind.clone()),
+			limit,
+			period,
+		);
+
+		assert_last_event::<T, I>(
+			Event::SpendBudgetSet { origin: spender, asset_kind, limit, period }.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_spend_budget() -> Result<(), BenchmarkError> {
+		let origin =
+			T::BudgetOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let spender: PalletsOriginOf<T> = RawOrigin::Root.into();
+		let asset_kind = T::BenchmarkHelper::create_asset_kind(SEED);
+		Treasury::<T, I>::set_spend_budget(
+			origin.clone(),
+			Box::new(spender.clone()),
+			Box::new(asset_kind.clone()),
+			100u32.into(),
+			10u32.into(),
+		)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Box::new(spender.clone()), Box::new(asset_kind.clone()));
+
+		assert_last_event::<T, I>(Event::SpendBudgetRemoved { origin: spender, asset_kind }.into());
 		Ok(())
 	}
 
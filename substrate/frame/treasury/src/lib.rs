@@ -69,6 +69,11 @@
 //! [`pallet::Config::Paymaster`]. To claim these spends, the `payout` dispatchable should be called
 //! within some temporal bounds, starting from the moment they become valid and within one
 //! [`pallet::Config::PayoutPeriod`].
+//!
+//! The cumulative amount of an asset kind that a given origin may approve through `spend` can be
+//! capped with a spend budget, set by the [`pallet::Config::BudgetOrigin`]. A budget allows a
+//! `limit` to be approved within any `period` of blocks, the approved amount being recovered
+//! linearly over the period. Voided and expired spends are credited back to their budget.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -84,16 +89,16 @@ pub use benchmarking::ArgumentsFactory;
 
 extern crate alloc;
 
-use codec::{Decode, Encode, MaxEncodedLen};
+use codec::{Codec, Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 
-use alloc::{boxed::Box, collections::btree_map::BTreeMap};
+use alloc::{boxed::Box, collections::btree_map::BTreeMap, vec::Vec};
 use sp_runtime::{
 	traits::{
-		AccountIdConversion, BlockNumberProvider, CheckedAdd, One, Saturating, StaticLookup,
-		UniqueSaturatedInto, Zero,
+		AccountIdConversion, AtLeast32BitUnsigned, BlockNumberProvider, CheckedAdd, One,
+		Saturating, StaticLookup, UniqueSaturatedInto, Zero,
 	},
-	PerThing, Permill, RuntimeDebug,
+	PerThing, Perbill, Permill, RuntimeDebug,
 };
 
 use frame_support::{
@@ -101,7 +106,7 @@ use frame_support::{
 	ensure, print,
 	traits::{
 		tokens::Pay, Currency, ExistenceRequirement::KeepAlive, Get, Imbalance, OnUnbalanced,
-		ReservableCurrency, WithdrawReasons,
+		OriginTrait, ReservableCurrency, WithdrawReasons,
 	},
 	weights::Weight,
 	BoundedVec, PalletId,
@@ -124,6 +129,8 @@ type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup
 type BeneficiaryLookupOf<T, I> = <<T as Config<I>>::BeneficiaryLookup as StaticLookup>::Source;
 pub type BlockNumberFor<T, I = ()> =
 	<<T as Config<I>>::BlockNumberProvider as BlockNumberProvider>::BlockNumber;
+pub type PalletsOriginOf<T> =
+	<<T as frame_system::Config>::RuntimeOrigin as OriginTrait>::PalletsOrigin;
 
 /// A trait to allow the Treasury Pallet to spend it's funds for other purposes.
 /// There is an expectation that the implementer of this trait will correctly manage
@@ -196,6 +203,90 @@ pub struct SpendStatus<AssetKind, AssetBalance, Beneficiary, BlockNumber, Paymen
 /// Index of an approved treasury spend.
 pub type SpendIndex = u32;
 
+/// The cap on the amount of an asset kind an origin may approve for spending per period.
+///
+/// The cap applies over a sliding window: the spent amount is recovered linearly, at a rate of
+/// `limit` per `period`, so no more than `limit` may be approved within any `period` blocks.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	PartialEq,
+	Eq,
+	MaxEncodedLen,
+	RuntimeDebug,
+	TypeInfo,
+)]
+pub struct SpendBudget<Balance, BlockNumber> {
+	/// The amount which may be approved within a single period.
+	pub limit: Balance,
+	/// The length of a period in blocks. A zero period means the spent amount is never
+	/// recovered.
+	pub period: BlockNumber,
+	/// The amount approved and not yet recovered as of `updated_at`.
+	pub spent: Balance,
+	/// The block number at which `spent` was last brought up to date.
+	pub updated_at: BlockNumber,
+}
+
+impl<Balance, BlockNumber> SpendBudget<Balance, BlockNumber>
+where
+	Balance: AtLeast32BitUnsigned + Copy,
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+	/// The amount the budget recovers over `blocks` blocks.
+	pub fn recovered_over(&self, blocks: BlockNumber) -> Balance {
+		if self.period.is_zero() {
+			Zero::zero()
+		} else if blocks >= self.period {
+			self.limit
+		} else {
+			Perbill::from_rational(blocks, self.period).mul_floor(self.limit)
+		}
+	}
+
+	/// Brings the spent amount up to date with `now`, recovering the part of it which has
+	/// fallen out of the window since the last update.
+	pub fn decay(&mut self, now: BlockNumber) {
+		let elapsed = now.saturating_sub(self.updated_at);
+		self.spent.saturating_reduce(self.recovered_over(elapsed));
+		self.updated_at = now;
+	}
+
+	/// Returns `amount`, approved at `approved_at` but never paid out, to the budget.
+	///
+	/// Only the part of `amount` which has not already been recovered since `approved_at` is
+	/// credited back.
+	pub fn credit(&mut self, amount: Balance, approved_at: BlockNumber, now: BlockNumber) {
+		self.decay(now);
+		let recovered = self.recovered_over(now.saturating_sub(approved_at));
+		self.spent.saturating_reduce(amount.saturating_sub(recovered));
+	}
+
+	/// The amount which may still be approved within the current window.
+	pub fn remaining(&self) -> Balance {
+		self.limit.saturating_sub(self.spent)
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// This runtime api allows people to query the spend budgets of the treasury.
+	pub trait TreasuryApi<PalletsOrigin, AssetKind, Balance> where
+		PalletsOrigin: Codec,
+		AssetKind: Codec,
+		Balance: Codec,
+	{
+		/// Returns the amount of `asset_kind` which `origin` may still approve for spending in
+		/// the current period, or `None` if its spends of `asset_kind` are not budgeted.
+		fn remaining_budget(origin: PalletsOrigin, asset_kind: AssetKind) -> Option<Balance>;
+
+		/// Returns the spend budgets of `origin` for every asset kind it has one for, along with
+		/// the amount which may still be approved in the current period.
+		fn budgets_of(origin: PalletsOrigin) -> Vec<(AssetKind, Balance)>;
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -287,6 +378,9 @@ pub mod pallet {
 
 		/// Provider for the block number. Normally this is the `frame_system` pallet.
 		type BlockNumberProvider: BlockNumberProvider;
+
+		/// The origin required for setting and removing spend budgets.
+		type BudgetOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 	}
 
 	#[pallet::extra_constants]
@@ -355,6 +449,32 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type LastSpendPeriod<T, I = ()> = StorageValue<_, BlockNumberFor<T, I>, OptionQuery>;
 
+	/// The spend budgets of origins, per asset kind.
+	///
+	/// Spends of an asset kind by an origin without a budget for it are only limited by the
+	/// [`Config::SpendOrigin`].
+	#[pallet::storage]
+	pub type SpendBudgets<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		PalletsOriginOf<T>,
+		Blake2_128Concat,
+		T::AssetKind,
+		SpendBudget<AssetBalanceOf<T, I>, BlockNumberFor<T, I>>,
+		OptionQuery,
+	>;
+
+	/// The origin and approval block of spends counted against a budget, which are credited
+	/// back to it should they never be paid out.
+	#[pallet::storage]
+	pub type BudgetedSpends<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		SpendIndex,
+		(PalletsOriginOf<T>, BlockNumberFor<T, I>),
+		OptionQuery,
+	>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
@@ -413,6 +533,15 @@ pub mod pallet {
 		/// A spend was processed and removed from the storage. It might have been successfully
 		/// paid or it may have expired.
 		SpendProcessed { index: SpendIndex },
+		/// The spend budget of an origin for an asset kind was set.
+		SpendBudgetSet {
+			origin: PalletsOriginOf<T>,
+			asset_kind: T::AssetKind,
+			limit: AssetBalanceOf<T, I>,
+			period: BlockNumberFor<T, I>,
+		},
+		/// The spend budget of an origin for an asset kind was removed.
+		SpendBudgetRemoved { origin: PalletsOriginOf<T>, asset_kind: T::AssetKind },
 	}
 
 	/// Error for the treasury pallet.
@@ -441,6 +570,14 @@ pub mod pallet {
 		NotAttempted,
 		/// The payment has neither failed nor succeeded yet.
 		Inconclusive,
+		/// The spend would exceed the budget of the origin for the current period.
+		BudgetExceeded,
+		/// The period of a spend budget cannot be zero.
+		ZeroBudgetPeriod,
+		/// The origin has no spend budget for the asset kind.
+		NoBudget,
+		/// The origin has a spend budget and may only spend through `spend`.
+		BudgetedOrigin,
 	}
 
 	#[pallet::hooks]
@@ -507,6 +644,10 @@ pub mod pallet {
 		/// NOTE: For record-keeping purposes, the proposer is deemed to be equivalent to the
 		/// beneficiary.
 		///
+		/// Origins with a spend budget for any asset kind are rejected with
+		/// [`Error::BudgetedOrigin`], since spends of the native currency through this call cannot
+		/// be counted against their budgets.
+		///
 		/// ### Parameters
 		/// - `amount`: The amount to be transferred from the treasury to the `beneficiary`.
 		/// - `beneficiary`: The destination account for the transfer.
@@ -525,8 +666,10 @@ pub mod pallet {
 			#[pallet::compact] amount: BalanceOf<T, I>,
 			beneficiary: AccountIdLookupOf<T>,
 		) -> DispatchResult {
+			let caller = origin.caller().clone();
 			let max_amount = T::SpendOrigin::ensure_origin(origin)?;
 			ensure!(amount <= max_amount, Error::<T, I>::InsufficientPermission);
+			ensure!(!SpendBudgets::<T, I>::contains_prefix(&caller), Error::<T, I>::BudgetedOrigin);
 
 			with_context::<SpendContext<BalanceOf<T, I>>, _>(|v| {
 				let context = v.or_default();
@@ -629,6 +772,9 @@ pub mod pallet {
 		/// designated beneficiary. The spend must be claimed using the `payout` dispatchable within
 		/// the [`Config::PayoutPeriod`].
 		///
+		/// If the origin has a spend budget for `asset_kind`, the `amount` is counted against it
+		/// and the spend fails with [`Error::BudgetExceeded`] if it does not fit.
+		///
 		/// ### Parameters
 		/// - `asset_kind`: An indicator of the specific asset class to be spent.
 		/// - `amount`: The amount to be transferred from the treasury to the `beneficiary`.
@@ -650,6 +796,7 @@ pub mod pallet {
 			beneficiary: Box<BeneficiaryLookupOf<T, I>>,
 			valid_from: Option<BlockNumberFor<T, I>>,
 		) -> DispatchResult {
+			let caller = origin.caller().clone();
 			let max_amount = T::SpendOrigin::ensure_origin(origin)?;
 			let beneficiary = T::BeneficiaryLookup::lookup(*beneficiary)?;
 
@@ -682,7 +829,19 @@ pub mod pallet {
 			})
 			.unwrap_or(Ok(()))?;
 
+			let budgeted =
+				SpendBudgets::<T, I>::try_mutate(&caller, &*asset_kind, |maybe_budget| {
+					let Some(budget) = maybe_budget else { return Ok::<_, Error<T, I>>(false) };
+					budget.decay(now);
+					ensure!(amount <= budget.remaining(), Error::<T, I>::BudgetExceeded);
+					budget.spent.saturating_accrue(amount);
+					Ok(true)
+				})?;
+
 			let index = SpendCount::<T, I>::get();
+			if budgeted {
+				BudgetedSpends::<T, I>::insert(index, (caller, now));
+			}
 			Spends::<T, I>::insert(
 				index,
 				SpendStatus {
@@ -760,7 +919,8 @@ pub mod pallet {
 		///
 		/// The status check is a prerequisite for retrying a failed payout.
 		/// If a spend has either succeeded or expired, it is removed from the storage by this
-		/// function. In such instances, transaction fees are refunded.
+		/// function. In such instances, transaction fees are refunded. An expired spend counted
+		/// against a spend budget has the part of it not yet recovered credited back.
 		///
 		/// ### Parameters
 		/// - `index`: The spend index.
@@ -782,6 +942,7 @@ pub mod pallet {
 			if now > spend.expire_at && !matches!(spend.status, State::Attempted { .. }) {
 				// spend has expired and no further status update is expected.
 				Spends::<T, I>::remove(index);
+				Self::credit_budget(index, &spend.asset_kind, spend.amount, now);
 				Self::deposit_event(Event::<T, I>::SpendProcessed { index });
 				return Ok(Pays::No.into())
 			}
@@ -799,6 +960,7 @@ pub mod pallet {
 				},
 				Status::Success | Status::Unknown => {
 					Spends::<T, I>::remove(index);
+					BudgetedSpends::<T, I>::remove(index);
 					Self::deposit_event(Event::<T, I>::SpendProcessed { index });
 					return Ok(Pays::No.into())
 				},
@@ -815,7 +977,8 @@ pub mod pallet {
 		///
 		/// ## Details
 		///
-		/// A spend void is only possible if the payout has not been attempted yet.
+		/// A spend void is only possible if the payout has not been attempted yet. If the spend was
+		/// counted against a spend budget, the part of it not yet recovered is credited back.
 		///
 		/// ### Parameters
 		/// - `index`: The spend index.
@@ -834,9 +997,102 @@ pub mod pallet {
 			);
 
 			Spends::<T, I>::remove(index);
+			let now = T::BlockNumberProvider::current_block_number();
+			Self::credit_budget(index, &spend.asset_kind, spend.amount, now);
 			Self::deposit_event(Event::<T, I>::AssetSpendVoided { index });
 			Ok(())
 		}
+
+		/// Set the spend budget of an origin for an asset kind.
+		///
+		/// ## Dispatch Origin
+		///
+		/// Must be [`Config::BudgetOrigin`].
+		///
+		/// ## Details
+		///
+		/// Once set, the amounts of `asset_kind` approved by `spender` through `spend` may not
+		/// exceed `limit` in any `period` of blocks, the approved amount being recovered linearly
+		/// over `period`. Updating an existing budget keeps the amount approved and not yet
+		/// recovered.
+		///
+		/// ### Parameters
+		/// - `spender`: The origin whose spends are budgeted.
+		/// - `asset_kind`: The asset kind the budget applies to.
+		/// - `limit`: The amount of `asset_kind` which may be approved per period.
+		/// - `period`: The length of a period in blocks.
+		///
+		/// ## Events
+		///
+		/// Emits [`Event::SpendBudgetSet`] if successful.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::set_spend_budget())]
+		pub fn set_spend_budget(
+			origin: OriginFor<T>,
+			spender: Box<PalletsOriginOf<T>>,
+			asset_kind: Box<T::AssetKind>,
+			#[pallet::compact] limit: AssetBalanceOf<T, I>,
+			period: BlockNumberFor<T, I>,
+		) -> DispatchResult {
+			T::BudgetOrigin::ensure_origin(origin)?;
+			ensure!(!period.is_zero(), Error::<T, I>::ZeroBudgetPeriod);
+
+			let now = T::BlockNumberProvider::current_block_number();
+			SpendBudgets::<T, I>::mutate(&*spender, &*asset_kind, |maybe_budget| {
+				let budget = maybe_budget.get_or_insert(SpendBudget {
+					limit,
+					period,
+					spent: Zero::zero(),
+					updated_at: now,
+				});
+				budget.decay(now);
+				budget.limit = limit;
+				budget.period = period;
+			});
+
+			Self::deposit_event(Event::<T, I>::SpendBudgetSet {
+				origin: *spender,
+				asset_kind: *asset_kind,
+				limit,
+				period,
+			});
+			Ok(())
+		}
+
+		/// Remove the spend budget of an origin for an asset kind.
+		///
+		/// ## Dispatch Origin
+		///
+		/// Must be [`Config::BudgetOrigin`].
+		///
+		/// ## Details
+		///
+		/// The spends of `asset_kind` by `spender` are then only limited by the
+		/// [`Config::SpendOrigin`].
+		///
+		/// ### Parameters
+		/// - `spender`: The origin whose spends are budgeted.
+		/// - `asset_kind`: The asset kind the budget applies to.
+		///
+		/// ## Events
+		///
+		/// Emits [`Event::SpendBudgetRemoved`] if successful.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::remove_spend_budget())]
+		pub fn remove_spend_budget(
+			origin: OriginFor<T>,
+			spender: Box<PalletsOriginOf<T>>,
+			asset_kind: Box<T::AssetKind>,
+		) -> DispatchResult {
+			T::BudgetOrigin::ensure_origin(origin)?;
+			SpendBudgets::<T, I>::take(&*spender, &*asset_kind).ok_or(Error::<T, I>::NoBudget)?;
+
+			Self::deposit_event(Event::<T, I>::SpendBudgetRemoved {
+				origin: *spender,
+				asset_kind: *asset_kind,
+			});
+			Ok(())
+		}
 	}
 }
 
@@ -988,6 +1244,47 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		total_weight
 	}
 
+	/// The amount of `asset_kind` which `origin` may still approve for spending now, or `None` if
+	/// its spends of `asset_kind` are not budgeted.
+	pub fn remaining_budget(
+		origin: &PalletsOriginOf<T>,
+		asset_kind: &T::AssetKind,
+	) -> Option<AssetBalanceOf<T, I>> {
+		let now = T::BlockNumberProvider::current_block_number();
+		SpendBudgets::<T, I>::get(origin, asset_kind).map(|mut budget| {
+			budget.decay(now);
+			budget.remaining()
+		})
+	}
+
+	/// The amounts which `origin` may still approve for spending now, for every asset kind it has a
+	/// spend budget for.
+	pub fn budgets_of(origin: &PalletsOriginOf<T>) -> Vec<(T::AssetKind, AssetBalanceOf<T, I>)> {
+		let now = T::BlockNumberProvider::current_block_number();
+		SpendBudgets::<T, I>::iter_prefix(origin)
+			.map(|(asset_kind, mut budget)| {
+				budget.decay(now);
+				(asset_kind, budget.remaining())
+			})
+			.collect()
+	}
+
+	/// Credit the spend at `index` of `amount` of `asset_kind` back to the budget it was counted
+	/// against, if any, as it is never going to be paid out.
+	fn credit_budget(
+		index: SpendIndex,
+		asset_kind: &T::AssetKind,
+		amount: AssetBalanceOf<T, I>,
+		now: BlockNumberFor<T, I>,
+	) {
+		let Some((origin, approved_at)) = BudgetedSpends::<T, I>::take(index) else { return };
+		SpendBudgets::<T, I>::mutate(&origin, asset_kind, |maybe_budget| {
+			if let Some(budget) = maybe_budget {
+				budget.credit(amount, approved_at, now);
+			}
+		});
+	}
+
 	/// Return the amount of money in the pot.
 	// The existential deposit is not part of the pot so treasury account never gets deleted.
 	pub fn pot() -> BalanceOf<T, I> {
@@ -1048,6 +1345,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// [`SpendCount`].
 	/// 3. For each spend entry contained in [`Spends`] we should have spend.expire_at
 	/// > spend.valid_from.
+	/// 4. Each key in [`BudgetedSpends`] should also be a key in [`Spends`].
 	#[cfg(any(feature = "try-runtime", test))]
 	fn try_state_spends() -> Result<(), sp_runtime::TryRuntimeError> {
 		let current_spend_count = SpendCount::<T, I>::get();
@@ -1072,6 +1370,14 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			Ok(())
 		})?;
 
+		BudgetedSpends::<T, I>::iter_keys().try_for_each(|spend_index| -> DispatchResult {
+			ensure!(
+				Spends::<T, I>::contains_key(spend_index),
+				"Each key in `BudgetedSpends` should also be a key in `Spends`."
+			);
+			Ok(())
+		})?;
+
 		Ok(())
 	}
 }
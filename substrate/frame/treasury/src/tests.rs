@@ -194,6 +194,7 @@ impl Config for Test {
 	type BalanceConverter = MulBy<ConstU64<2>>;
 	type PayoutPeriod = SpendPayoutPeriod;
 	type BlockNumberProvider = System;
+	type BudgetOrigin = frame_system::EnsureRoot<u128>;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
 }
@@ -1015,3 +1016,223 @@ fn multiple_spend_periods_work() {
 		assert_eq!(LastSpendPeriod::<Test>::get(), Some(8));
 	});
 }
+
+fn signed_caller(who: u128) -> Box<OriginCaller> {
+	Box::new(OriginCaller::system(frame_system::RawOrigin::Signed(who)))
+}
+
+#[test]
+fn set_and_remove_spend_budget_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Treasury::set_spend_budget(
+				RuntimeOrigin::signed(1),
+				signed_caller(14),
+				Box::new(1),
+				10,
+				5
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			Treasury::set_spend_budget(
+				RuntimeOrigin::root(),
+				signed_caller(14),
+				Box::new(1),
+				10,
+				0
+			),
+			Error::<Test, _>::ZeroBudgetPeriod
+		);
+		assert_noop!(
+			Treasury::remove_spend_budget(RuntimeOrigin::root(), signed_caller(14), Box::new(1)),
+			Error::<Test, _>::NoBudget
+		);
+
+		assert_ok!(Treasury::set_spend_budget(
+			RuntimeOrigin::root(),
+			signed_caller(14),
+			Box::new(1),
+			10,
+			5
+		));
+		System::assert_last_event(
+			Event::<Test, _>::SpendBudgetSet {
+				origin: *signed_caller(14),
+				asset_kind: 1,
+				limit: 10,
+				period: 5,
+			}
+			.into(),
+		);
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), Some(10));
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &2), None);
+		assert_eq!(Treasury::budgets_of(&signed_caller(14)), vec![(1, 10)]);
+
+		assert_noop!(
+			Treasury::remove_spend_budget(RuntimeOrigin::signed(1), signed_caller(14), Box::new(1)),
+			BadOrigin
+		);
+		assert_ok!(Treasury::remove_spend_budget(
+			RuntimeOrigin::root(),
+			signed_caller(14),
+			Box::new(1)
+		));
+		System::assert_last_event(
+			Event::<Test, _>::SpendBudgetRemoved { origin: *signed_caller(14), asset_kind: 1 }
+				.into(),
+		);
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), None);
+	});
+}
+
+#[test]
+fn spend_respects_budget() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Treasury::set_spend_budget(
+			RuntimeOrigin::root(),
+			signed_caller(14),
+			Box::new(1),
+			10,
+			5
+		));
+
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(14), Box::new(1), 6, Box::new(6), None));
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), Some(4));
+		assert_noop!(
+			Treasury::spend(RuntimeOrigin::signed(14), Box::new(1), 5, Box::new(6), None),
+			Error::<Test, _>::BudgetExceeded
+		);
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(14), Box::new(1), 4, Box::new(6), None));
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), Some(0));
+
+		// other assets and origins are not budgeted.
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(14), Box::new(2), 20, Box::new(6), None));
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(13), Box::new(1), 20, Box::new(6), None));
+
+		// the spent amount is recovered linearly, so the budget cannot be spent twice around
+		// the end of a period.
+		System::set_block_number(2);
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), Some(2));
+		assert_noop!(
+			Treasury::spend(RuntimeOrigin::signed(14), Box::new(1), 3, Box::new(6), None),
+			Error::<Test, _>::BudgetExceeded
+		);
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(14), Box::new(1), 2, Box::new(6), None));
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), Some(0));
+
+		System::set_block_number(4);
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), Some(4));
+
+		// the whole budget is recovered once a period passes without spends.
+		System::set_block_number(7);
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), Some(10));
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(14), Box::new(1), 10, Box::new(6), None));
+		assert_eq!(
+			SpendBudgets::<Test, _>::get(&*signed_caller(14), 1),
+			Some(SpendBudget { limit: 10, period: 5, spent: 10, updated_at: 7 })
+		);
+	});
+}
+
+#[test]
+fn unpaid_spends_are_credited_to_budget() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Treasury::set_spend_budget(
+			RuntimeOrigin::root(),
+			signed_caller(14),
+			Box::new(1),
+			10,
+			100
+		));
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(14), Box::new(1), 6, Box::new(6), None));
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(14), Box::new(1), 4, Box::new(6), None));
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), Some(0));
+
+		// a voided spend is credited back.
+		assert_ok!(Treasury::void_spend(RuntimeOrigin::root(), 0));
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), Some(6));
+		assert!(!BudgetedSpends::<Test, _>::contains_key(0));
+
+		// an expired spend is credited back, less the amount recovered since its approval.
+		System::set_block_number(11);
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), Some(7));
+		assert_ok!(Treasury::check_status(RuntimeOrigin::signed(1), 1));
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), Some(10));
+		assert!(!BudgetedSpends::<Test, _>::contains_key(1));
+
+		// a paid spend is not.
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(14), Box::new(1), 5, Box::new(6), None));
+		assert_ok!(Treasury::payout(RuntimeOrigin::signed(1), 2));
+		set_status(get_payment_id(2).expect("no payment attempt"), PaymentStatus::Success);
+		assert_ok!(Treasury::check_status(RuntimeOrigin::signed(1), 2));
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), Some(5));
+		assert!(!BudgetedSpends::<Test, _>::contains_key(2));
+	});
+}
+
+#[test]
+fn updating_budget_keeps_spent_amount() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Treasury::set_spend_budget(
+			RuntimeOrigin::root(),
+			signed_caller(14),
+			Box::new(1),
+			10,
+			5
+		));
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(14), Box::new(1), 8, Box::new(6), None));
+
+		assert_ok!(Treasury::set_spend_budget(
+			RuntimeOrigin::root(),
+			signed_caller(14),
+			Box::new(1),
+			20,
+			5
+		));
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), Some(12));
+
+		// lowering the limit below the spent amount blocks further spends until it is recovered.
+		assert_ok!(Treasury::set_spend_budget(
+			RuntimeOrigin::root(),
+			signed_caller(14),
+			Box::new(1),
+			5,
+			5
+		));
+		assert_eq!(Treasury::remaining_budget(&signed_caller(14), &1), Some(0));
+		assert_noop!(
+			Treasury::spend(RuntimeOrigin::signed(14), Box::new(1), 1, Box::new(6), None),
+			Error::<Test, _>::BudgetExceeded
+		);
+	});
+}
+
+#[test]
+fn spend_local_rejects_budgeted_origins() {
+	#[allow(deprecated)]
+	ExtBuilder::default().build().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 102);
+		assert_ok!(Treasury::set_spend_budget(
+			RuntimeOrigin::root(),
+			signed_caller(14),
+			Box::new(1),
+			10,
+			5
+		));
+
+		assert_noop!(
+			Treasury::spend_local(RuntimeOrigin::signed(14), 5, 6),
+			Error::<Test, _>::BudgetedOrigin
+		);
+		// origins without a budget are unaffected
+		assert_ok!(Treasury::spend_local(RuntimeOrigin::signed(13), 5, 6));
+
+		assert_ok!(Treasury::remove_spend_budget(
+			RuntimeOrigin::root(),
+			signed_caller(14),
+			Box::new(1)
+		));
+		assert_ok!(Treasury::spend_local(RuntimeOrigin::signed(14), 5, 6));
+	});
+}
@@ -79,6 +79,8 @@ pub trait WeightInfo {
 	fn payout() -> Weight;
 	fn check_status() -> Weight;
 	fn void_spend() -> Weight;
+	fn set_spend_budget() -> Weight;
+	fn remove_spend_budget() -> Weight;
 }
 
 /// Weights for `pallet_treasury` using the Substrate node and recommended hardware.
@@ -90,13 +92,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Treasury::Approvals` (`max_values`: Some(1), `max_size`: Some(402), added: 897, mode: `MaxEncodedLen`)
 	/// Storage: `Treasury::Proposals` (r:0 w:1)
 	/// Proof: `Treasury::Proposals` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:0)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(151), added: 2626, mode: `MaxEncodedLen`)
 	fn spend_local() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `1887`
-		// Minimum execution time: 9_084_000 picoseconds.
-		Weight::from_parts(9_260_000, 1887)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(9_260_000, 3616)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
 	/// Storage: `Treasury::Approvals` (r:1 w:1)
@@ -132,14 +133,15 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Treasury::SpendCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
 	/// Storage: `Treasury::Spends` (r:0 w:1)
 	/// Proof: `Treasury::Spends` (`max_values`: None, `max_size`: Some(74), added: 2549, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(151), added: 2626, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::BudgetedSpends` (r:0 w:1)
+	/// Proof: `Treasury::BudgetedSpends` (`max_values`: None, `max_size`: Some(59), added: 2534, mode: `MaxEncodedLen`)
 	fn spend() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `42`
-		//  Estimated: `3502`
-		// Minimum execution time: 11_348_000 picoseconds.
-		Weight::from_parts(11_874_000, 3502)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(11_874_000, 3616)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 	/// Storage: `Treasury::Spends` (r:1 w:1)
 	/// Proof: `Treasury::Spends` (`max_values`: None, `max_size`: Some(74), added: 2549, mode: `MaxEncodedLen`)
@@ -160,23 +162,41 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	}
 	/// Storage: `Treasury::Spends` (r:1 w:1)
 	/// Proof: `Treasury::Spends` (`max_values`: None, `max_size`: Some(74), added: 2549, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::BudgetedSpends` (r:1 w:1)
+	/// Proof: `Treasury::BudgetedSpends` (`max_values`: None, `max_size`: Some(59), added: 2534, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(151), added: 2626, mode: `MaxEncodedLen`)
 	fn check_status() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `143`
-		//  Estimated: `3539`
-		// Minimum execution time: 12_058_000 picoseconds.
-		Weight::from_parts(12_297_000, 3539)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(12_297_000, 3616)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
 	/// Storage: `Treasury::Spends` (r:1 w:1)
 	/// Proof: `Treasury::Spends` (`max_values`: None, `max_size`: Some(74), added: 2549, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::BudgetedSpends` (r:1 w:1)
+	/// Proof: `Treasury::BudgetedSpends` (`max_values`: None, `max_size`: Some(59), added: 2534, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(151), added: 2626, mode: `MaxEncodedLen`)
 	fn void_spend() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `144`
-		//  Estimated: `3539`
-		// Minimum execution time: 10_730_000 picoseconds.
-		Weight::from_parts(10_908_000, 3539)
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(10_908_000, 3616)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(151), added: 2626, mode: `MaxEncodedLen`)
+	fn set_spend_budget() -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(9_867_000, 3616)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(151), added: 2626, mode: `MaxEncodedLen`)
+	fn remove_spend_budget() -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(10_683_000, 3616)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
@@ -190,13 +210,12 @@ impl WeightInfo for () {
 	/// Proof: `Treasury::Approvals` (`max_values`: Some(1), `max_size`: Some(402), added: 897, mode: `MaxEncodedLen`)
 	/// Storage: `Treasury::Proposals` (r:0 w:1)
 	/// Proof: `Treasury::Proposals` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:0)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(151), added: 2626, mode: `MaxEncodedLen`)
 	fn spend_local() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `1887`
-		// Minimum execution time: 9_084_000 picoseconds.
-		Weight::from_parts(9_260_000, 1887)
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(9_260_000, 3616)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
 	/// Storage: `Treasury::Approvals` (r:1 w:1)
@@ -232,14 +251,15 @@ impl WeightInfo for () {
 	/// Proof: `Treasury::SpendCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
 	/// Storage: `Treasury::Spends` (r:0 w:1)
 	/// Proof: `Treasury::Spends` (`max_values`: None, `max_size`: Some(74), added: 2549, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(151), added: 2626, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::BudgetedSpends` (r:0 w:1)
+	/// Proof: `Treasury::BudgetedSpends` (`max_values`: None, `max_size`: Some(59), added: 2534, mode: `MaxEncodedLen`)
 	fn spend() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `42`
-		//  Estimated: `3502`
-		// Minimum execution time: 11_348_000 picoseconds.
-		Weight::from_parts(11_874_000, 3502)
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(11_874_000, 3616)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 	/// Storage: `Treasury::Spends` (r:1 w:1)
 	/// Proof: `Treasury::Spends` (`max_values`: None, `max_size`: Some(74), added: 2549, mode: `MaxEncodedLen`)
@@ -260,23 +280,41 @@ impl WeightInfo for () {
 	}
 	/// Storage: `Treasury::Spends` (r:1 w:1)
 	/// Proof: `Treasury::Spends` (`max_values`: None, `max_size`: Some(74), added: 2549, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::BudgetedSpends` (r:1 w:1)
+	/// Proof: `Treasury::BudgetedSpends` (`max_values`: None, `max_size`: Some(59), added: 2534, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(151), added: 2626, mode: `MaxEncodedLen`)
 	fn check_status() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `143`
-		//  Estimated: `3539`
-		// Minimum execution time: 12_058_000 picoseconds.
-		Weight::from_parts(12_297_000, 3539)
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(12_297_000, 3616)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
 	/// Storage: `Treasury::Spends` (r:1 w:1)
 	/// Proof: `Treasury::Spends` (`max_values`: None, `max_size`: Some(74), added: 2549, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::BudgetedSpends` (r:1 w:1)
+	/// Proof: `Treasury::BudgetedSpends` (`max_values`: None, `max_size`: Some(59), added: 2534, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(151), added: 2626, mode: `MaxEncodedLen`)
 	fn void_spend() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `144`
-		//  Estimated: `3539`
-		// Minimum execution time: 10_730_000 picoseconds.
-		Weight::from_parts(10_908_000, 3539)
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(10_908_000, 3616)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(151), added: 2626, mode: `MaxEncodedLen`)
+	fn set_spend_budget() -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(9_867_000, 3616)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Treasury::SpendBudgets` (r:1 w:1)
+	/// Proof: `Treasury::SpendBudgets` (`max_values`: None, `max_size`: Some(151), added: 2626, mode: `MaxEncodedLen`)
+	fn remove_spend_budget() -> Weight {
+		// Not benchmarked yet: placeholder until this file is regenerated.
+		Weight::from_parts(10_683_000, 3616)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
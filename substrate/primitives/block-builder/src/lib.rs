@@ -51,4 +51,14 @@ sp_api::decl_runtime_apis! {
 		/// Check that the inherents are valid. The inherent data will vary from chain to chain.
 		fn check_inherents(block: Block, data: InherentData) -> CheckInherentsResult;
 	}
+
+	/// The `BlockRetentionApi` api trait that selects the block bodies kept by nodes pruning
+	/// blocks.
+	pub trait BlockRetentionApi {
+		/// Returns whether the body of a block with the given extrinsics should be kept when the
+		/// block is pruned.
+		///
+		/// Called on top of the state of the parent of the block when the block is imported.
+		fn retain_body(extrinsics: alloc::vec::Vec<<Block as BlockT>::Extrinsic>) -> bool;
+	}
 }
@@ -563,6 +563,24 @@ pub struct BlockGap<N> {
 	pub gap_type: BlockGapType,
 }
 
+/// Range of finalized blocks removed by block pruning.
+///
+/// Headers of pruned blocks are always kept, bodies and justifications are kept only when
+/// retained by the pruning policy.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub struct PrunedBlocks<N> {
+	/// The first pruned block number (inclusive).
+	pub start: N,
+	/// The last pruned block number (inclusive).
+	pub end: N,
+	/// Whether justifications of all blocks in the range were retained.
+	pub justifications_retained: bool,
+	/// Whether bodies of the blocks in the range selected by the runtime were retained.
+	///
+	/// Blocks imported without being executed, e.g. by warp or gap sync, are never selected.
+	pub bodies_retained: bool,
+}
+
 /// Blockchain info
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Info<Block: BlockT> {
@@ -582,6 +600,8 @@ pub struct Info<Block: BlockT> {
 	pub number_leaves: usize,
 	/// Missing blocks after warp sync or fast sync.
 	pub block_gap: Option<BlockGap<NumberFor<Block>>>,
+	/// Finalized blocks removed by block pruning.
+	pub pruned_blocks: Option<PrunedBlocks<NumberFor<Block>>>,
 }
 
 /// Block status.
@@ -0,0 +1,220 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generate `beefy-light-client` fixtures from a running relay chain network.
+//!
+//! The generator waits for the next BEEFY justification of the node at `--uri` and proves the head
+//! of `--para-id` in the MMR leaf of the justified block. The resulting proof is verified before it
+//! is written to the output, so every generated fixture is known to be valid.
+
+use beefy_light_client::{
+	fixture::{BlockNumber, Fixture},
+	para_head_merkle_leaf, validator_merkle_leaf, verify_parachain_head, CommitmentProof, MmrLeaf,
+	ParaHeadProof, ParachainHeadProof, ValidatorProof,
+};
+use clap::Parser;
+use codec::Decode;
+use serde::Deserialize;
+use sp_consensus_beefy::{
+	ecdsa_crypto::{AuthorityId, AuthoritySignature},
+	mmr::BeefyAuthoritySet,
+	ValidatorSet, VersionedFinalityProof,
+};
+use sp_core::{storage::StorageKey, Bytes, H256};
+use sp_mmr_primitives::{EncodableOpaqueLeaf, LeafProof};
+use sp_runtime::traits::Keccak256;
+use std::path::PathBuf;
+use substrate_rpc_client::{rpc_params, ws_client, ClientT, SubscriptionClientT, WsClient};
+
+#[derive(Debug, Parser)]
+struct Opt {
+	/// Websocket endpoint of a relay chain node with BEEFY and MMR RPCs enabled.
+	#[arg(long, default_value = "ws://127.0.0.1:9944")]
+	uri: String,
+
+	/// Id of the parachain whose head is proven.
+	#[arg(long)]
+	para_id: u32,
+
+	/// Where to write the fixture.
+	output: PathBuf,
+}
+
+/// `LeavesProof` returned by `mmr_generateProof`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LeavesProof {
+	leaves: Bytes,
+	proof: Bytes,
+}
+
+type Error = Box<dyn std::error::Error>;
+
+/// Maximal number of keys returned by a single `state_getKeysPaged` request.
+const KEYS_PAGE_SIZE: u32 = 1000;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+	let Opt { uri, para_id, output } = Opt::parse();
+	let client = ws_client(&uri).await?;
+
+	let (authority_set, proof) = generate(&client, para_id).await?;
+	verify_parachain_head(&authority_set, proof.clone())?;
+
+	let fixture = Fixture::new(&authority_set, &proof);
+	std::fs::write(&output, serde_json::to_string_pretty(&fixture)?)?;
+	println!("Fixture of #{} written to {}", fixture.block_number, output.display());
+	Ok(())
+}
+
+async fn generate(
+	client: &WsClient,
+	para_id: u32,
+) -> Result<(BeefyAuthoritySet<H256>, ParachainHeadProof<BlockNumber>), Error> {
+	let mut justifications = client
+		.subscribe::<Bytes, _>(
+			"beefy_subscribeJustifications",
+			rpc_params![],
+			"beefy_unsubscribeJustifications",
+		)
+		.await?;
+	let justification = justifications.next().await.ok_or("BEEFY subscription closed")??;
+	let VersionedFinalityProof::V1(signed_commitment) =
+		VersionedFinalityProof::<BlockNumber, AuthoritySignature>::decode(&mut &justification[..])?;
+	let block_number = signed_commitment.commitment.block_number;
+	let block_hash: H256 = client
+		.request::<Option<H256>, _>("chain_getBlockHash", rpc_params![block_number])
+		.await?
+		.ok_or("Justified block is unknown")?;
+
+	// Validators that signed the commitment.
+	let validator_set: ValidatorSet<AuthorityId> =
+		state_call::<Option<_>>(client, "BeefyApi_validator_set", block_hash)
+			.await?
+			.ok_or("BEEFY is not enabled at the justified block")?;
+	let authority_set: BeefyAuthoritySet<H256> =
+		state_call(client, "BeefyMmrApi_authority_set_proof", block_hash).await?;
+	if validator_set.id() != signed_commitment.commitment.validator_set_id ||
+		authority_set.id != validator_set.id()
+	{
+		return Err("Commitment is not signed by the validator set of the justified block".into())
+	}
+	let validator_leaves =
+		validator_set.validators().iter().map(validator_merkle_leaf).collect::<Vec<_>>();
+	let validators = signed_commitment
+		.signatures
+		.iter()
+		.enumerate()
+		.filter(|(_, signature)| signature.is_some())
+		.map(|(index, _)| ValidatorProof {
+			index: index as u32,
+			id: validator_set.validators()[index].clone(),
+			proof: binary_merkle_tree::merkle_proof::<Keccak256, _, _>(
+				&validator_leaves,
+				index as u32,
+			)
+			.proof,
+		})
+		.collect();
+
+	// MMR leaf of the justified block.
+	let leaves_proof: LeavesProof = client
+		.request(
+			"mmr_generateProof",
+			rpc_params![vec![block_number], Some(block_number), Some(block_hash)],
+		)
+		.await?;
+	let leaves = Vec::<EncodableOpaqueLeaf>::decode(&mut &leaves_proof.leaves[..])?;
+	let leaf = leaves.first().ok_or("MMR proof doesn't contain the leaf")?;
+	let mmr_leaf = MmrLeaf::<BlockNumber>::decode(&mut &leaf.0[..])?;
+	let mmr_proof = LeafProof::<H256>::decode(&mut &leaves_proof.proof[..])?;
+
+	// The leaf is built in `on_initialize`, so the heads root is computed from the parent state.
+	let para_heads = para_heads(client, mmr_leaf.parent_number_and_hash.1).await?;
+	let para_head_leaves = para_heads
+		.iter()
+		.map(|(id, head)| para_head_merkle_leaf(*id, head))
+		.collect::<Vec<_>>();
+	if binary_merkle_tree::merkle_root::<Keccak256, _>(&para_head_leaves) != mmr_leaf.leaf_extra {
+		return Err("Parachain heads don't match the MMR leaf".into())
+	}
+	let leaf_index = para_heads
+		.iter()
+		.position(|(id, _)| *id == para_id)
+		.ok_or("Parachain head is not found")?;
+	let para_head_proof =
+		binary_merkle_tree::merkle_proof::<Keccak256, _, _>(&para_head_leaves, leaf_index as u32);
+
+	Ok((
+		authority_set,
+		ParachainHeadProof {
+			commitment: CommitmentProof { signed_commitment, validators },
+			mmr_leaf,
+			mmr_proof,
+			para_id,
+			para_head: para_heads[leaf_index].1.clone(),
+			para_head_proof: ParaHeadProof {
+				proof: para_head_proof.proof,
+				number_of_leaves: para_head_proof.number_of_leaves,
+				leaf_index: para_head_proof.leaf_index,
+			},
+		},
+	))
+}
+
+/// Call a runtime API without arguments.
+async fn state_call<T: Decode>(client: &WsClient, method: &str, at: H256) -> Result<T, Error> {
+	let result: Bytes =
+		client.request("state_call", rpc_params![method, Bytes(Vec::new()), at]).await?;
+	Ok(T::decode(&mut &result[..])?)
+}
+
+/// Read the heads of all parachains, sorted by id, like `Paras::sorted_para_heads`.
+async fn para_heads(client: &WsClient, at: H256) -> Result<Vec<(u32, Vec<u8>)>, Error> {
+	let prefix = [
+		sp_crypto_hashing::twox_128(b"Paras").as_slice(),
+		sp_crypto_hashing::twox_128(b"Heads").as_slice(),
+	]
+	.concat();
+	let mut keys: Vec<StorageKey> = Vec::new();
+	loop {
+		let page: Vec<StorageKey> = client
+			.request(
+				"state_getKeysPaged",
+				rpc_params![StorageKey(prefix.clone()), KEYS_PAGE_SIZE, keys.last(), at],
+			)
+			.await?;
+		let is_last_page = page.len() < KEYS_PAGE_SIZE as usize;
+		keys.extend(page);
+		if is_last_page {
+			break
+		}
+	}
+
+	let mut heads = Vec::with_capacity(keys.len());
+	for key in keys {
+		let head: Bytes = client
+			.request::<Option<Bytes>, _>("state_getStorage", rpc_params![&key, at])
+			.await?
+			.ok_or("Parachain head is removed")?;
+		// `Twox64Concat` hashed key: 8 bytes of hash, followed by the para id.
+		let para_id = u32::decode(&mut &key.0[prefix.len() + 8..])?;
+		heads.push((para_id, Vec::<u8>::decode(&mut &head[..])?));
+	}
+	heads.sort_by_key(|(id, _)| *id);
+	Ok(heads)
+}
@@ -0,0 +1,66 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! JSON fixtures of parachain head proofs.
+//!
+//! Fixtures are generated from a running network with `beefy-light-client-fixture-generator` and
+//! allow light client implementations to be tested against proofs produced by real nodes.
+
+use crate::ParachainHeadProof;
+use codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use sp_consensus_beefy::mmr::BeefyAuthoritySet;
+use sp_core::{Bytes, H256};
+
+/// Block number of the relay chains the fixtures are generated from.
+pub type BlockNumber = u32;
+
+/// Parachain head proof together with the validator set that finalized it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fixture {
+	/// Id of the parachain.
+	pub para_id: u32,
+	/// Number of the relay chain block finalized by the commitment.
+	pub block_number: BlockNumber,
+	/// SCALE-encoded `BeefyAuthoritySet<H256>` that signed the commitment.
+	pub authority_set: Bytes,
+	/// SCALE-encoded `ParachainHeadProof<BlockNumber>`.
+	pub proof: Bytes,
+}
+
+impl Fixture {
+	/// Create a fixture of the given proof.
+	pub fn new(
+		authority_set: &BeefyAuthoritySet<H256>,
+		proof: &ParachainHeadProof<BlockNumber>,
+	) -> Self {
+		Fixture {
+			para_id: proof.para_id,
+			block_number: proof.commitment.signed_commitment.commitment.block_number,
+			authority_set: authority_set.encode().into(),
+			proof: proof.encode().into(),
+		}
+	}
+
+	/// Decode the validator set and the proof of the fixture.
+	pub fn decode(
+		&self,
+	) -> Result<(BeefyAuthoritySet<H256>, ParachainHeadProof<BlockNumber>), codec::Error> {
+		Ok((Decode::decode(&mut &self.authority_set[..])?, Decode::decode(&mut &self.proof[..])?))
+	}
+}
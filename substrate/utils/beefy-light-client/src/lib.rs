@@ -0,0 +1,339 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+//! Verification of BEEFY finality proofs for BEEFY light clients.
+//!
+//! The crate implements the stateless part of a BEEFY+MMR light client, as deployed on Polkadot
+//! and its test networks:
+//!
+//! 1. A BEEFY [`SignedCommitment`] is checked against the [`BeefyAuthoritySet`] known to the light
+//!    client. Every signature is accompanied by a [`ValidatorProof`], a binary merkle proof of the
+//!    signer's Ethereum address in the `keyset_commitment` of the set, so that light clients don't
+//!    need to know the whole validator set. The MMR root is then taken from the commitment payload.
+//! 2. An [`MmrLeaf`] is checked against that MMR root with a [`LeafProof`].
+//! 3. A parachain head is checked against the parachain heads root stored in the `leaf_extra` of
+//!    the MMR leaf, using the same binary merkle tree as `ParaHeadsRootProvider` in the relay chain
+//!    runtimes.
+//!
+//! [`verify_parachain_head`] performs all three steps. Keeping track of the current and the next
+//! authority set, which can be taken from the verified MMR leaves, is up to the light client.
+//!
+//! The crate supports no-std compilation targets, so it can be used both in runtimes and in
+//! off-chain code.
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod fixture;
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_consensus_beefy::{
+	ecdsa_crypto::{AuthorityId, AuthoritySignature},
+	known_payloads::MMR_ROOT_ID,
+	mmr::{BeefyAuthoritySet, BeefyNextAuthoritySet},
+	BeefyAuthorityId, SignedCommitment,
+};
+use sp_core::{crypto::ByteArray, H256};
+use sp_mmr_primitives::{mmr_lib, utils::NodesUtils, LeafProof};
+use sp_runtime::{
+	traits::{Hash, Keccak256},
+	RuntimeDebug,
+};
+
+/// Major version of the [`MmrLeaf`] format supported by the crate.
+///
+/// Leaves of a later minor version only append fields, which are ignored, so any minor version is
+/// accepted.
+pub const MMR_LEAF_MAJOR_VERSION: u8 = 0;
+
+/// MMR leaf, as built by `pallet-beefy-mmr` in the relay chain runtimes.
+///
+/// The `leaf_extra` contains the merkle root of the parachain heads.
+pub type MmrLeaf<BlockNumber> = sp_consensus_beefy::mmr::MmrLeaf<BlockNumber, H256, H256, H256>;
+
+/// Signature of a BEEFY validator, together with the proof that the validator belongs to the
+/// validator set.
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct ValidatorProof {
+	/// Index of the validator in the validator set.
+	///
+	/// This is also the index of the validator signature in the signed commitment.
+	pub index: u32,
+	/// Public key of the validator.
+	pub id: AuthorityId,
+	/// Merkle proof of the validator's Ethereum address in the `keyset_commitment` of the
+	/// validator set.
+	pub proof: Vec<H256>,
+}
+
+/// BEEFY commitment together with the proofs of its signers.
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct CommitmentProof<BlockNumber> {
+	/// The signed commitment.
+	pub signed_commitment: SignedCommitment<BlockNumber, AuthoritySignature>,
+	/// Proofs of the validators that signed the commitment, ordered by validator index.
+	///
+	/// Only signatures that are accompanied by a proof are checked, so it is enough to provide
+	/// proofs for [`signatures_required`] validators.
+	pub validators: Vec<ValidatorProof>,
+}
+
+/// Proof that a parachain head was finalized by BEEFY.
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct ParachainHeadProof<BlockNumber> {
+	/// Commitment to the MMR root, which includes the `mmr_leaf`.
+	pub commitment: CommitmentProof<BlockNumber>,
+	/// The MMR leaf of the relay chain block the parachain head was read at.
+	pub mmr_leaf: MmrLeaf<BlockNumber>,
+	/// Proof of the `mmr_leaf` in the MMR.
+	pub mmr_proof: LeafProof<H256>,
+	/// Id of the parachain.
+	pub para_id: u32,
+	/// Head data of the parachain.
+	pub para_head: Vec<u8>,
+	/// Proof of the parachain head in the parachain heads root of the `mmr_leaf`.
+	pub para_head_proof: ParaHeadProof,
+}
+
+/// Binary merkle proof of a parachain head in the parachain heads root.
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct ParaHeadProof {
+	/// Proof items.
+	pub proof: Vec<H256>,
+	/// Number of parachain heads in the tree.
+	pub number_of_leaves: u32,
+	/// Index of the parachain head in the tree.
+	pub leaf_index: u32,
+}
+
+/// Parachain head verified with [`verify_parachain_head`].
+#[derive(RuntimeDebug, Clone, PartialEq, Eq)]
+pub struct VerifiedParachainHead<BlockNumber> {
+	/// Number of the relay chain block that was finalized by the commitment.
+	pub commitment_block_number: BlockNumber,
+	/// Number and hash of the parent of the relay chain block the MMR leaf was added at.
+	pub parent_number_and_hash: (BlockNumber, H256),
+	/// The next BEEFY authority set, as of the MMR leaf.
+	pub beefy_next_authority_set: BeefyNextAuthoritySet<H256>,
+	/// Id of the parachain.
+	pub para_id: u32,
+	/// Head data of the parachain.
+	pub para_head: Vec<u8>,
+}
+
+/// BEEFY light client verification error.
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+#[derive(RuntimeDebug, Clone, PartialEq, Eq)]
+pub enum Error {
+	/// The validator set is empty, so no commitment can be signed by it.
+	#[cfg_attr(feature = "std", error("Validator set is empty"))]
+	EmptyValidatorSet,
+	/// The commitment is signed by a different validator set.
+	#[cfg_attr(feature = "std", error("Commitment is signed by an unexpected validator set"))]
+	InvalidValidatorSetId,
+	/// The number of signatures doesn't match the size of the validator set.
+	#[cfg_attr(feature = "std", error("Number of signatures doesn't match the validator set"))]
+	InvalidSignaturesLen,
+	/// Validator proofs are not ordered by strictly increasing validator index.
+	#[cfg_attr(feature = "std", error("Validator proofs are not ordered by validator index"))]
+	UnorderedValidatorProofs,
+	/// The validator is not a member of the validator set.
+	#[cfg_attr(feature = "std", error("Validator #{0} is not a member of the validator set"))]
+	InvalidValidatorProof(u32),
+	/// The signature of the validator is missing or invalid.
+	#[cfg_attr(feature = "std", error("Signature of validator #{0} is missing or invalid"))]
+	InvalidSignature(u32),
+	/// The commitment isn't signed by enough validators.
+	#[cfg_attr(feature = "std", error("Commitment isn't signed by enough validators"))]
+	NotEnoughSignatures,
+	/// The commitment payload doesn't contain the MMR root.
+	#[cfg_attr(feature = "std", error("MMR root is missing from the commitment payload"))]
+	MmrRootMissing,
+	/// The MMR proof is not a proof of exactly one leaf.
+	#[cfg_attr(feature = "std", error("MMR proof must prove a single leaf"))]
+	InvalidMmrProofLeaves,
+	/// The MMR leaf is of a format that isn't supported.
+	#[cfg_attr(feature = "std", error("Unsupported MMR leaf version {0}.{1}"))]
+	UnsupportedMmrLeafVersion(u8, u8),
+	/// The MMR leaf isn't included in the MMR.
+	#[cfg_attr(feature = "std", error("Invalid MMR leaf proof"))]
+	InvalidMmrProof,
+	/// The parachain head isn't included in the parachain heads root.
+	#[cfg_attr(feature = "std", error("Invalid parachain head proof"))]
+	InvalidParaHeadProof,
+}
+
+/// Number of valid signatures required to accept a commitment of a validator set of the given
+/// size, i.e. `2/3 + 1` of the validators.
+pub fn signatures_required(validators_len: usize) -> usize {
+	validators_len - validators_len.saturating_sub(1) / 3
+}
+
+/// Leaf of the validator in the `keyset_commitment` of a [`BeefyAuthoritySet`], which is the
+/// Ethereum address of the validator.
+///
+/// Matches `BeefyEcdsaToEthereum` of `pallet-beefy-mmr`, including the empty leaf of a key that
+/// isn't a valid secp256k1 point.
+pub fn validator_merkle_leaf(id: &AuthorityId) -> Vec<u8> {
+	use k256::{elliptic_curve::sec1::ToEncodedPoint, PublicKey};
+
+	PublicKey::from_sec1_bytes(id.as_slice())
+		.map(|public| {
+			let uncompressed = public.to_encoded_point(false);
+			Keccak256::hash(&uncompressed.as_bytes()[1..]).as_bytes()[12..].to_vec()
+		})
+		.unwrap_or_default()
+}
+
+/// Leaf of the parachain head in the parachain heads root of an [`MmrLeaf`].
+pub fn para_head_merkle_leaf(para_id: u32, para_head: &[u8]) -> Vec<u8> {
+	(para_id, para_head).encode()
+}
+
+/// Verify that the commitment is signed by the given validator set.
+///
+/// Returns the MMR root from the commitment payload.
+pub fn verify_commitment<BlockNumber: Encode>(
+	authority_set: &BeefyAuthoritySet<H256>,
+	proof: &CommitmentProof<BlockNumber>,
+) -> Result<H256, Error> {
+	let SignedCommitment { commitment, signatures } = &proof.signed_commitment;
+	if authority_set.len == 0 {
+		return Err(Error::EmptyValidatorSet)
+	}
+	if commitment.validator_set_id != authority_set.id {
+		return Err(Error::InvalidValidatorSetId)
+	}
+	if signatures.len() != authority_set.len as usize {
+		return Err(Error::InvalidSignaturesLen)
+	}
+
+	let encoded_commitment = commitment.encode();
+	let mut previous_index = None;
+	for ValidatorProof { index, id, proof } in &proof.validators {
+		if previous_index.is_some_and(|previous| previous >= *index) {
+			return Err(Error::UnorderedValidatorProofs)
+		}
+		previous_index = Some(*index);
+
+		if !binary_merkle_tree::verify_proof::<Keccak256, _, _>(
+			&authority_set.keyset_commitment,
+			proof.iter().copied(),
+			authority_set.len,
+			*index,
+			&validator_merkle_leaf(id),
+		) {
+			return Err(Error::InvalidValidatorProof(*index))
+		}
+
+		let signature = signatures.get(*index as usize).and_then(Option::as_ref);
+		if !signature.is_some_and(|signature| {
+			BeefyAuthorityId::<Keccak256>::verify(id, signature, &encoded_commitment)
+		}) {
+			return Err(Error::InvalidSignature(*index))
+		}
+	}
+
+	if proof.validators.len() < signatures_required(authority_set.len as usize) {
+		return Err(Error::NotEnoughSignatures)
+	}
+
+	commitment.payload.get_decoded(&MMR_ROOT_ID).ok_or(Error::MmrRootMissing)
+}
+
+/// Merging of MMR nodes, as done by `pallet-mmr` with the `Keccak256` hashing of the relay chain
+/// runtimes.
+struct MmrKeccakMerge;
+
+impl mmr_lib::Merge for MmrKeccakMerge {
+	type Item = H256;
+
+	fn merge(left: &H256, right: &H256) -> mmr_lib::Result<H256> {
+		Ok(Keccak256::hash(&[left.as_bytes(), right.as_bytes()].concat()))
+	}
+}
+
+/// Verify that the MMR leaf is of a supported version and included in the MMR with the given
+/// root.
+pub fn verify_mmr_leaf<BlockNumber: Encode>(
+	mmr_root: H256,
+	mmr_leaf: &MmrLeaf<BlockNumber>,
+	mmr_proof: LeafProof<H256>,
+) -> Result<(), Error> {
+	let (major, minor) = mmr_leaf.version.split();
+	if major != MMR_LEAF_MAJOR_VERSION {
+		return Err(Error::UnsupportedMmrLeafVersion(major, minor))
+	}
+	let [leaf_index] = mmr_proof.leaf_indices[..] else { return Err(Error::InvalidMmrProofLeaves) };
+
+	let leaf_hash = Keccak256::hash(&mmr_leaf.encode());
+	let proof = mmr_lib::MerkleProof::<H256, MmrKeccakMerge>::new(
+		NodesUtils::new(mmr_proof.leaf_count).size(),
+		mmr_proof.items,
+	);
+	proof
+		.verify(mmr_root, alloc::vec![(mmr_lib::leaf_index_to_pos(leaf_index), leaf_hash)])
+		.unwrap_or(false)
+		.then_some(())
+		.ok_or(Error::InvalidMmrProof)
+}
+
+/// Verify that the parachain head is included in the given parachain heads root.
+pub fn verify_para_head(
+	para_heads_root: &H256,
+	para_id: u32,
+	para_head: &[u8],
+	proof: &ParaHeadProof,
+) -> Result<(), Error> {
+	binary_merkle_tree::verify_proof::<Keccak256, _, _>(
+		para_heads_root,
+		proof.proof.iter().copied(),
+		proof.number_of_leaves,
+		proof.leaf_index,
+		&para_head_merkle_leaf(para_id, para_head),
+	)
+	.then_some(())
+	.ok_or(Error::InvalidParaHeadProof)
+}
+
+/// Verify that the parachain head was finalized by the given BEEFY validator set.
+pub fn verify_parachain_head<BlockNumber: Encode>(
+	authority_set: &BeefyAuthoritySet<H256>,
+	proof: ParachainHeadProof<BlockNumber>,
+) -> Result<VerifiedParachainHead<BlockNumber>, Error> {
+	let ParachainHeadProof { commitment, mmr_leaf, mmr_proof, para_id, para_head, para_head_proof } =
+		proof;
+
+	let mmr_root = verify_commitment(authority_set, &commitment)?;
+	verify_mmr_leaf(mmr_root, &mmr_leaf, mmr_proof)?;
+	verify_para_head(&mmr_leaf.leaf_extra, para_id, &para_head, &para_head_proof)?;
+
+	Ok(VerifiedParachainHead {
+		commitment_block_number: commitment.signed_commitment.commitment.block_number,
+		parent_number_and_hash: mmr_leaf.parent_number_and_hash,
+		beefy_next_authority_set: mmr_leaf.beefy_next_authority_set,
+		para_id,
+		para_head,
+	})
+}
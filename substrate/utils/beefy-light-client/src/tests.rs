@@ -0,0 +1,253 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::fixture::Fixture;
+use sp_consensus_beefy::{
+	mmr::MmrLeafVersion, test_utils::Keyring, Commitment, Payload, ValidatorSetId,
+};
+
+type TestKeyring = Keyring<AuthorityId>;
+
+const VALIDATORS: [TestKeyring; 4] =
+	[Keyring::Alice, Keyring::Bob, Keyring::Charlie, Keyring::Dave];
+
+fn para_heads() -> Vec<(u32, Vec<u8>)> {
+	vec![(1000, vec![1; 32]), (2000, vec![2; 48]), (3000, vec![3; 16])]
+}
+
+fn authority_set(id: ValidatorSetId) -> BeefyAuthoritySet<H256> {
+	BeefyAuthoritySet {
+		id,
+		len: VALIDATORS.len() as u32,
+		keyset_commitment: binary_merkle_tree::merkle_root::<Keccak256, _>(
+			VALIDATORS.iter().map(|v| validator_merkle_leaf(&v.public())),
+		),
+	}
+}
+
+fn mmr_leaf(parent_number: u32, leaf_extra: H256) -> MmrLeaf<u32> {
+	MmrLeaf {
+		version: MmrLeafVersion::new(0, 0),
+		parent_number_and_hash: (parent_number, H256::repeat_byte(parent_number as u8)),
+		beefy_next_authority_set: authority_set(1),
+		leaf_extra,
+	}
+}
+
+fn validator_proof(index: usize) -> ValidatorProof {
+	let leaves = VALIDATORS
+		.iter()
+		.map(|v| validator_merkle_leaf(&v.public()))
+		.collect::<Vec<_>>();
+	ValidatorProof {
+		index: index as u32,
+		id: VALIDATORS[index].public(),
+		proof: binary_merkle_tree::merkle_proof::<Keccak256, _, _>(leaves, index as u32).proof,
+	}
+}
+
+/// Build a proof of the second parachain head, signed by the validators at `signers`.
+///
+/// The MMR contains two leaves, the head is proven against the second one.
+fn build_proof(signers: &[usize]) -> ParachainHeadProof<u32> {
+	let para_heads_root = binary_merkle_tree::merkle_root::<Keccak256, _>(
+		para_heads().iter().map(|(id, head)| para_head_merkle_leaf(*id, head)),
+	);
+	let para_head_proof = binary_merkle_tree::merkle_proof::<Keccak256, _, _>(
+		para_heads().iter().map(|(id, head)| para_head_merkle_leaf(*id, head)),
+		1,
+	);
+
+	let first_leaf_hash = Keccak256::hash(&mmr_leaf(3, H256::zero()).encode());
+	let mmr_leaf = mmr_leaf(4, para_heads_root);
+	let mmr_root = Keccak256::hash(
+		&[first_leaf_hash.as_bytes(), Keccak256::hash(&mmr_leaf.encode()).as_bytes()].concat(),
+	);
+
+	let commitment = Commitment {
+		payload: Payload::from_single_entry(MMR_ROOT_ID, mmr_root.encode()),
+		block_number: 5,
+		validator_set_id: 0,
+	};
+	let signatures = VALIDATORS
+		.iter()
+		.enumerate()
+		.map(|(index, v)| signers.contains(&index).then(|| v.sign(&commitment.encode())))
+		.collect();
+
+	let (para_id, para_head) = para_heads().remove(1);
+	ParachainHeadProof {
+		commitment: CommitmentProof {
+			signed_commitment: SignedCommitment { commitment, signatures },
+			validators: signers.iter().map(|index| validator_proof(*index)).collect(),
+		},
+		mmr_leaf,
+		mmr_proof: LeafProof { leaf_indices: vec![1], leaf_count: 2, items: vec![first_leaf_hash] },
+		para_id,
+		para_head,
+		para_head_proof: ParaHeadProof {
+			proof: para_head_proof.proof,
+			number_of_leaves: para_head_proof.number_of_leaves,
+			leaf_index: para_head_proof.leaf_index,
+		},
+	}
+}
+
+#[test]
+fn signatures_required_works() {
+	assert_eq!(signatures_required(1), 1);
+	assert_eq!(signatures_required(3), 3);
+	assert_eq!(signatures_required(4), 3);
+	assert_eq!(signatures_required(100), 67);
+}
+
+#[test]
+fn parachain_head_proof_is_verified() {
+	let verified = verify_parachain_head(&authority_set(0), build_proof(&[0, 2, 3])).unwrap();
+
+	assert_eq!(
+		verified,
+		VerifiedParachainHead {
+			commitment_block_number: 5,
+			parent_number_and_hash: (4, H256::repeat_byte(4)),
+			beefy_next_authority_set: authority_set(1),
+			para_id: 2000,
+			para_head: vec![2; 48],
+		}
+	);
+}
+
+#[test]
+fn commitment_of_unexpected_validator_set_is_rejected() {
+	assert_eq!(
+		verify_parachain_head(&authority_set(1), build_proof(&[0, 1, 2])),
+		Err(Error::InvalidValidatorSetId),
+	);
+
+	let mut proof = build_proof(&[0, 1, 2]);
+	proof.commitment.signed_commitment.signatures.pop();
+	assert_eq!(verify_parachain_head(&authority_set(0), proof), Err(Error::InvalidSignaturesLen));
+}
+
+#[test]
+fn commitment_of_empty_validator_set_is_rejected() {
+	let mut proof = build_proof(&[]);
+	proof.commitment.signed_commitment.signatures.clear();
+	let authority_set = BeefyAuthoritySet { len: 0, ..authority_set(0) };
+	assert_eq!(verify_parachain_head(&authority_set, proof), Err(Error::EmptyValidatorSet));
+}
+
+#[test]
+fn commitment_without_enough_signatures_is_rejected() {
+	assert_eq!(
+		verify_parachain_head(&authority_set(0), build_proof(&[1, 3])),
+		Err(Error::NotEnoughSignatures),
+	);
+
+	// The same validator can't be counted twice.
+	let mut proof = build_proof(&[0, 1]);
+	proof.commitment.validators.push(validator_proof(1));
+	assert_eq!(
+		verify_parachain_head(&authority_set(0), proof),
+		Err(Error::UnorderedValidatorProofs),
+	);
+}
+
+#[test]
+fn validator_outside_of_validator_set_is_rejected() {
+	let mut proof = build_proof(&[0, 1, 2]);
+	proof.commitment.validators[2].id = Keyring::<AuthorityId>::Eve.public();
+	assert_eq!(
+		verify_parachain_head(&authority_set(0), proof),
+		Err(Error::InvalidValidatorProof(2)),
+	);
+}
+
+#[test]
+fn invalid_signature_is_rejected() {
+	// Proof of a validator that didn't sign.
+	let mut proof = build_proof(&[0, 1, 2]);
+	proof.commitment.validators[2] = validator_proof(3);
+	assert_eq!(verify_parachain_head(&authority_set(0), proof), Err(Error::InvalidSignature(3)));
+
+	// Signature of a different commitment.
+	let mut proof = build_proof(&[0, 1, 2]);
+	proof.commitment.signed_commitment.commitment.block_number = 6;
+	assert_eq!(verify_parachain_head(&authority_set(0), proof), Err(Error::InvalidSignature(0)));
+}
+
+#[test]
+fn mmr_root_is_required() {
+	let mut proof = build_proof(&[]);
+	proof.commitment.signed_commitment.commitment.payload =
+		Payload::from_single_entry(*b"xx", vec![]);
+	let signatures = VALIDATORS
+		.iter()
+		.map(|v| Some(v.sign(&proof.commitment.signed_commitment.commitment.encode())))
+		.collect();
+	proof.commitment.signed_commitment.signatures = signatures;
+	proof.commitment.validators = (0..VALIDATORS.len()).map(validator_proof).collect();
+
+	assert_eq!(verify_parachain_head(&authority_set(0), proof), Err(Error::MmrRootMissing));
+}
+
+#[test]
+fn invalid_mmr_leaf_is_rejected() {
+	let mut proof = build_proof(&[0, 1, 2]);
+	proof.mmr_leaf.parent_number_and_hash.0 = 3;
+	assert_eq!(verify_parachain_head(&authority_set(0), proof), Err(Error::InvalidMmrProof));
+
+	let mut proof = build_proof(&[0, 1, 2]);
+	proof.mmr_proof.leaf_indices.push(0);
+	assert_eq!(verify_parachain_head(&authority_set(0), proof), Err(Error::InvalidMmrProofLeaves));
+}
+
+#[test]
+fn mmr_leaf_of_unsupported_version_is_rejected() {
+	let mut proof = build_proof(&[0, 1, 2]);
+	proof.mmr_leaf.version = MmrLeafVersion::new(1, 0);
+	assert_eq!(
+		verify_parachain_head(&authority_set(0), proof),
+		Err(Error::UnsupportedMmrLeafVersion(1, 0)),
+	);
+}
+
+#[test]
+fn invalid_para_head_is_rejected() {
+	let mut proof = build_proof(&[0, 1, 2]);
+	proof.para_head = vec![1; 32];
+	assert_eq!(verify_parachain_head(&authority_set(0), proof), Err(Error::InvalidParaHeadProof));
+
+	let mut proof = build_proof(&[0, 1, 2]);
+	proof.para_id = 1000;
+	assert_eq!(verify_parachain_head(&authority_set(0), proof), Err(Error::InvalidParaHeadProof));
+}
+
+#[test]
+fn fixture_roundtrip_works() {
+	let proof = build_proof(&[0, 1, 2]);
+	let fixture = Fixture::new(&authority_set(0), &proof);
+	assert_eq!(fixture.para_id, 2000);
+	assert_eq!(fixture.block_number, 5);
+
+	let json = serde_json::to_string(&fixture).unwrap();
+	let fixture: Fixture = serde_json::from_str(&json).unwrap();
+	let (authority_set, decoded) = fixture.decode().unwrap();
+	assert_eq!(decoded, proof);
+	assert!(verify_parachain_head(&authority_set, decoded).is_ok());
+}
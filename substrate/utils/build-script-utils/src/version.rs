@@ -49,6 +49,43 @@ pub fn generate_cargo_keys() {
 	println!("cargo:rustc-env=SUBSTRATE_CLI_IMPL_VERSION={}", get_version(&commit))
 }
 
+/// Generate the `SUBSTRATE_WASMTIME_VERSION` env variable with the version of the `wasmtime`
+/// crate used by the current workspace, or `unknown` if it can't be determined.
+pub fn generate_wasmtime_version() {
+	generate_dependency_version("wasmtime", "SUBSTRATE_WASMTIME_VERSION");
+}
+
+fn generate_dependency_version(dep: &str, env_var: &str) {
+	let version = match Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()))
+		.args(["tree", "--depth=0", "--package", dep])
+		.output()
+	{
+		// `cargo tree` prints `<dep> v<version>` for the root of the tree.
+		Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout)
+			.lines()
+			.next()
+			.and_then(|line| line.strip_prefix(&format!("{dep} v")))
+			.and_then(|rest| rest.split_whitespace().next())
+			.map(ToOwned::to_owned)
+			.unwrap_or_else(|| {
+				println!("cargo:warning=Unexpected `cargo tree` output for {dep}");
+				"unknown".into()
+			}),
+		// `cargo tree` fails if the dependency is missing, or present in multiple versions.
+		Ok(o) => {
+			let stderr = String::from_utf8_lossy(&o.stderr).trim().to_owned();
+			println!("cargo:warning=`cargo tree` failed for {dep} with message: '{}'", stderr);
+			"unknown".into()
+		},
+		Err(err) => {
+			println!("cargo:warning=Failed to execute `cargo tree`: {}", err);
+			"unknown".into()
+		},
+	};
+
+	println!("cargo:rustc-env={env_var}={version}");
+}
+
 fn get_version(impl_commit: &str) -> String {
 	let commit_dash = if impl_commit.is_empty() { "" } else { "-" };
 
@@ -489,6 +489,7 @@ impl OverheadCmd {
 			trie_cache_maximum_size: self.trie_cache_maximum_size()?,
 			state_pruning: None,
 			blocks_pruning: BlocksPruning::KeepAll,
+			blocks_retention: Default::default(),
 			source: database_source,
 		})?;
 
@@ -520,6 +521,7 @@ impl OverheadCmd {
 				no_genesis: false,
 				wasm_runtime_substitutes: Default::default(),
 				enable_import_proof_recording: chain_type.requires_proof_recording(),
+				retain_block_bodies: false,
 			},
 		)?);
 
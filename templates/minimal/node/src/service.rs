@@ -144,6 +144,7 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 			import_queue,
 			block_announce_validator_builder: None,
 			warp_sync_config: None,
+			state_sync_store: Some(client.clone()),
 			block_relay: None,
 			metrics,
 		})?;
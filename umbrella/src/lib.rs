@@ -19,6 +19,11 @@ pub use asset_test_utils;
 #[cfg(feature = "assets-common")]
 pub use assets_common;
 
+/// A no-std/Substrate compatible library to verify BEEFY finality and MMR proofs of parachain
+/// heads.
+#[cfg(feature = "beefy-light-client")]
+pub use beefy_light_client;
+
 /// A no-std/Substrate compatible library to construct binary merkle tree.
 #[cfg(feature = "binary-merkle-tree")]
 pub use binary_merkle_tree;
@@ -300,6 +305,10 @@ pub use pallet_alliance;
 #[cfg(feature = "pallet-asset-conversion")]
 pub use pallet_asset_conversion;
 
+/// FRAME limit order book settling against asset conversion pools.
+#[cfg(feature = "pallet-asset-conversion-limit-orders")]
+pub use pallet_asset_conversion_limit_orders;
+
 /// FRAME asset conversion pallet's operations suite.
 #[cfg(feature = "pallet-asset-conversion-ops")]
 pub use pallet_asset_conversion_ops;
@@ -332,6 +341,10 @@ pub use pallet_assets_freezer;
 #[cfg(feature = "pallet-assets-holder")]
 pub use pallet_assets_holder;
 
+/// FRAME pallet for vesting of fungibles assets.
+#[cfg(feature = "pallet-assets-vesting")]
+pub use pallet_assets_vesting;
+
 /// FRAME atomic swap pallet.
 #[cfg(feature = "pallet-atomic-swap")]
 pub use pallet_atomic_swap;
@@ -571,6 +584,10 @@ pub use pallet_paged_list;
 #[cfg(feature = "pallet-parameters")]
 pub use pallet_parameters;
 
+/// FRAME pallet for continuous payment streams between accounts.
+#[cfg(feature = "pallet-payment-streams")]
+pub use pallet_payment_streams;
+
 /// FRAME pallet for storing preimages of hashes.
 #[cfg(feature = "pallet-preimage")]
 pub use pallet_preimage;
@@ -839,6 +856,10 @@ pub use polkadot_node_core_approval_voting_parallel;
 #[cfg(feature = "polkadot-node-core-av-store")]
 pub use polkadot_node_core_av_store;
 
+/// RPC interface to export and import the data of the availability store of the Polkadot node.
+#[cfg(feature = "polkadot-node-core-av-store-rpc")]
+pub use polkadot_node_core_av_store_rpc;
+
 /// The Candidate Backing Subsystem. Tracks parachain candidates that can be backed, as well as
 /// the issuance of statements about candidates.
 #[cfg(feature = "polkadot-node-core-backing")]
@@ -908,6 +929,15 @@ pub use polkadot_node_core_pvf_prepare_worker;
 #[cfg(feature = "polkadot-node-core-runtime-api")]
 pub use polkadot_node_core_runtime_api;
 
+/// Polkadot crate that implements the validator health subsystem. Keeps track of the
+/// performance of the node as a parachain validator in recent sessions.
+#[cfg(feature = "polkadot-node-core-validator-health")]
+pub use polkadot_node_core_validator_health;
+
+/// RPC interface to the validator health subsystem of the Polkadot node.
+#[cfg(feature = "polkadot-node-core-validator-health-rpc")]
+pub use polkadot_node_core_validator_health_rpc;
+
 /// Subsystem metric helpers.
 #[cfg(feature = "polkadot-node-metrics")]
 pub use polkadot_node_metrics;